@@ -94,6 +94,7 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 			block_announce_validator_builder: None,
 			finality_proof_request_builder: None,
 			finality_proof_provider: Some(finality_proof_provider.clone()),
+			warp_sync_provider: Some(sc_finality_grandpa::WarpSyncProofProvider::new_for_service(backend.clone())),
 		})?;
 
 	if config.offchain_worker.enabled {
@@ -265,6 +266,7 @@ pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 			block_announce_validator_builder: None,
 			finality_proof_request_builder: Some(finality_proof_request_builder),
 			finality_proof_provider: Some(finality_proof_provider),
+			warp_sync_provider: None,
 		})?;
 
 	if config.offchain_worker.enabled {
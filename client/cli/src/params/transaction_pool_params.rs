@@ -16,7 +16,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use sc_service::config::TransactionPoolOptions;
+use sc_service::config::{TagPrefixLimit, TransactionPoolJournalOptions, TransactionPoolOptions};
+use std::time::Duration;
 use structopt::StructOpt;
 
 /// Parameters used to create the pool configuration.
@@ -29,6 +30,30 @@ pub struct TransactionPoolParams {
 	/// Maximum number of kilobytes of all transactions stored in the pool.
 	#[structopt(long = "pool-kbytes", value_name = "COUNT", default_value = "20480")]
 	pub pool_kbytes: usize,
+
+	/// Maximum number of transactions in the transaction pool from a single sender.
+	///
+	/// Transactions are attributed to a sender by the prefix of the tags they provide, see
+	/// `--pool-sender-tag-prefix`. When a sender goes over the limit, its transactions with the
+	/// lowest priority are evicted first. Unlimited if not set.
+	#[structopt(long = "pool-limit-per-sender", value_name = "COUNT")]
+	pub pool_limit_per_sender: Option<usize>,
+
+	/// Number of leading bytes of a transaction tag that identify its sender.
+	///
+	/// The default matches the nonce tags of FRAME based runtimes using 32 bytes account ids.
+	#[structopt(long = "pool-sender-tag-prefix", value_name = "BYTES", default_value = "32")]
+	pub pool_sender_tag_prefix: usize,
+
+	/// Keep a journal of the transaction pool on disk, and restore it on restart.
+	///
+	/// The journal is bounded by `--pool-limit` and `--pool-kbytes`. Only used by full nodes.
+	#[structopt(long = "pool-journal")]
+	pub pool_journal: bool,
+
+	/// Maximum age in seconds of the journaled transactions restored on restart.
+	#[structopt(long = "pool-journal-max-age", value_name = "SECONDS", default_value = "3600")]
+	pub pool_journal_max_age: u64,
 }
 
 impl TransactionPoolParams {
@@ -45,6 +70,21 @@ impl TransactionPoolParams {
 		opts.future.count = self.pool_limit / factor;
 		opts.future.total_bytes = self.pool_kbytes * 1024 / factor;
 
+		// per sender limit
+		opts.tag_prefix_limit = self.pool_limit_per_sender.map(|count| TagPrefixLimit {
+			prefix_len: self.pool_sender_tag_prefix,
+			count,
+		});
+
+		// journal
+		if self.pool_journal {
+			opts.journal = Some(TransactionPoolJournalOptions {
+				count: self.pool_limit,
+				total_bytes: self.pool_kbytes * 1024,
+				max_age: Duration::from_secs(self.pool_journal_max_age),
+			});
+		}
+
 		opts
 	}
 }
@@ -1050,6 +1050,9 @@ impl<Block: BlockT> Backend<Block> {
 			let hash = pending_block.header.hash();
 			let parent_hash = *pending_block.header.parent_hash();
 			let number = pending_block.header.number().clone();
+			// a block imported again, e.g. with the state it was first imported without.
+			let existing_header = number <= self.blockchain.meta.read().best_number
+				&& self.blockchain.header(BlockId::Hash(hash))?.is_some();
 
 			// blocks are keyed by number + hash.
 			let lookup_key = utils::number_and_hash_to_lookup_key(number, hash)?;
@@ -1156,6 +1159,8 @@ impl<Block: BlockT> Backend<Block> {
 			} else {
 				false
 			};
+			let reimported_finalized = existing_header && operation.commit_state
+				&& number <= self.blockchain.meta.read().finalized_number;
 
 			let header = &pending_block.header;
 			let is_best = pending_block.leaf_state.is_best();
@@ -1189,14 +1194,25 @@ impl<Block: BlockT> Backend<Block> {
 					&mut changes_trie_cache_ops,
 					&mut finalization_displaced_leaves,
 				)?;
-			} else {
+			} else if reimported_finalized {
+				// the block was finalized before its state was imported: canonicalize the state
+				// right away, as finalization skipped it.
+				trace!(target: "db", "Canonicalize imported state of block #{} ({:?})", number, hash);
+				let commit = self.storage.state_db.canonicalize_block(&hash)
+					.map_err(|e: sc_state_db::Error<io::Error>|
+						sp_blockchain::Error::from(format!("State database error: {:?}", e))
+					)?;
+				apply_state_commit(&mut transaction, commit);
+			} else if operation.commit_state {
 				// canonicalize blocks which are old enough, regardless of finality.
 				self.force_delayed_canonicalize(&mut transaction, hash, *header.number())?
 			}
 
 			debug!(target: "db", "DB Commit {:?} ({}), best = {}", hash, number, is_best);
 
-			let displaced_leaf = {
+			let displaced_leaf = if existing_header {
+				None
+			} else {
 				let mut leaves = self.blockchain.leaves.write();
 				let displaced_leaf = leaves.import(hash, number, parent_hash);
 				leaves.prepare_transaction(&mut transaction, columns::META, meta_keys::LEAF_PREFIX);
@@ -1204,20 +1220,22 @@ impl<Block: BlockT> Backend<Block> {
 				displaced_leaf
 			};
 
-			let mut children = children::read_children(
-				&*self.storage.db,
-				columns::META,
-				meta_keys::CHILDREN_PREFIX,
-				parent_hash,
-			)?;
-			children.push(hash);
-			children::write_children(
-				&mut transaction,
-				columns::META,
-				meta_keys::CHILDREN_PREFIX,
-				parent_hash,
-				children,
-			);
+			if !existing_header {
+				let mut children = children::read_children(
+					&*self.storage.db,
+					columns::META,
+					meta_keys::CHILDREN_PREFIX,
+					parent_hash,
+				)?;
+				children.push(hash);
+				children::write_children(
+					&mut transaction,
+					columns::META,
+					meta_keys::CHILDREN_PREFIX,
+					parent_hash,
+					children,
+				);
+			}
 
 			meta_updates.push((hash, number, pending_block.leaf_state.is_best(), finalized));
 
@@ -1301,9 +1319,13 @@ impl<Block: BlockT> Backend<Block> {
 			let lookup_key = utils::number_and_hash_to_lookup_key(f_num, f_hash.clone())?;
 			transaction.set_from_vec(columns::META, meta_keys::FINALIZED_BLOCK, lookup_key);
 
-			let commit = self.storage.state_db.canonicalize_block(&f_hash)
-				.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from(format!("State database error: {:?}", e)))?;
-			apply_state_commit(transaction, commit);
+			// blocks imported without their state, e.g. by fast sync, have it canonicalized once
+			// it is imported.
+			if sc_client_api::Backend::have_state_at(self, &f_hash, f_num) {
+				let commit = self.storage.state_db.canonicalize_block(&f_hash)
+					.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from(format!("State database error: {:?}", e)))?;
+				apply_state_commit(transaction, commit);
+			}
 
 			if !f_num.is_zero() {
 				let new_changes_trie_cache_ops = self.changes_tries_storage.finalize(
@@ -1818,6 +1840,72 @@ pub(crate) mod tests {
 		}
 	}
 
+	#[test]
+	fn import_state_of_block_finalized_without_state() {
+		let backend = Backend::<Block>::new_test(10, 10);
+		let storage = vec![(vec![1], vec![2])];
+
+		// headers are imported and finalized without their state.
+		let mut hashes = Vec::new();
+		let mut headers = Vec::new();
+		for number in 0..3 {
+			let header = Header {
+				number,
+				parent_hash: hashes.last().cloned().unwrap_or_default(),
+				state_root: BlakeTwo256::trie_root(storage.clone()),
+				digest: Default::default(),
+				extrinsics_root: Default::default(),
+			};
+			let mut op = backend.begin_operation().unwrap();
+			op.set_block_data(header.clone(), Some(Vec::new()), None, NewBlockState::Best).unwrap();
+			backend.commit_operation(op).unwrap();
+			hashes.push(header.hash());
+			headers.push(header);
+		}
+		backend.finalize_block(BlockId::Hash(hashes[1]), None).unwrap();
+		backend.finalize_block(BlockId::Hash(hashes[2]), None).unwrap();
+		assert!(!backend.have_state_at(&hashes[2], 2));
+
+		// the state of the last finalized block is imported.
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, BlockId::Hash(Default::default())).unwrap();
+		op.reset_storage(Storage {
+			top: storage.into_iter().collect(),
+			children_default: Default::default(),
+		}).unwrap();
+		op.set_block_data(headers[2].clone(), Some(Vec::new()), None, NewBlockState::Normal).unwrap();
+		backend.commit_operation(op).unwrap();
+
+		assert!(backend.have_state_at(&hashes[2], 2));
+		assert!(!backend.have_state_at(&hashes[1], 1));
+		assert_eq!(backend.blockchain().leaves().unwrap(), vec![hashes[2]]);
+		assert_eq!(backend.blockchain().info().finalized_hash, hashes[2]);
+		let state = backend.state_at(BlockId::Hash(hashes[2])).unwrap();
+		assert_eq!(state.storage(&[1]).unwrap(), Some(vec![2]));
+
+		// blocks are imported on top of it.
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, BlockId::Hash(hashes[2])).unwrap();
+		let changes = vec![(vec![1], Some(vec![3]))];
+		let (root, overlay) = op.old_state.storage_root(
+			changes.iter().map(|(k, v)| (&k[..], v.as_ref().map(|v| &v[..])))
+		);
+		op.update_db_storage(overlay).unwrap();
+		op.update_storage(changes, Vec::new()).unwrap();
+		let header = Header {
+			number: 3,
+			parent_hash: hashes[2],
+			state_root: root.into(),
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		op.set_block_data(header.clone(), Some(Vec::new()), None, NewBlockState::Best).unwrap();
+		backend.commit_operation(op).unwrap();
+
+		let state = backend.state_at(BlockId::Hash(header.hash())).unwrap();
+		assert_eq!(state.storage(&[1]).unwrap(), Some(vec![3]));
+	}
+
 	#[test]
 	fn set_state_data() {
 		let db = Backend::<Block>::new_test(2, 0);
@@ -23,7 +23,7 @@ use sc_client_api::backend::AuxStore;
 use sp_blockchain::{Result as ClientResult, Error as ClientError};
 use fork_tree::ForkTree;
 use finality_grandpa::round::State as RoundState;
-use sp_runtime::traits::{Block as BlockT, NumberFor};
+use sp_runtime::{Justification, traits::{Block as BlockT, NumberFor}};
 use log::{info, warn};
 use sp_finality_grandpa::{AuthorityList, SetId, RoundNumber};
 
@@ -39,6 +39,9 @@ const SET_STATE_KEY: &[u8] = b"grandpa_completed_round";
 const CONCLUDED_ROUNDS: &[u8] = b"grandpa_concluded_rounds";
 const AUTHORITY_SET_KEY: &[u8] = b"grandpa_voters";
 const CONSENSUS_CHANGES_KEY: &[u8] = b"grandpa_consensus_changes";
+const SET_CHANGES_KEY: &[u8] = b"grandpa_set_changes";
+const SET_CHANGE_JUSTIFICATION_PREFIX: &[u8] = b"grandpa_set_change_justification";
+const LATEST_JUSTIFICATION_KEY: &[u8] = b"grandpa_latest_justification";
 
 const CURRENT_VERSION: u32 = 2;
 
@@ -433,6 +436,78 @@ pub(crate) fn update_consensus_changes<H, N, F, R>(
 	write_aux(&[(CONSENSUS_CHANGES_KEY, set.encode().as_slice())])
 }
 
+/// Blocks enacting a standard authority set change, in ascending order, along with the id of
+/// the set whose last block they are.
+pub(crate) type SetChanges<N> = Vec<(SetId, N)>;
+
+fn set_change_justification_key(set_id: SetId) -> Vec<u8> {
+	let mut key = SET_CHANGE_JUSTIFICATION_PREFIX.to_vec();
+	set_id.using_encoded(|id| key.extend(id));
+	key
+}
+
+/// Load the blocks enacting the standard authority set changes seen so far.
+pub(crate) fn load_set_changes<Block: BlockT, B: AuxStore>(
+	backend: &B,
+) -> ClientResult<SetChanges<NumberFor<Block>>> {
+	load_decode(backend, SET_CHANGES_KEY).map(|changes| changes.unwrap_or_default())
+}
+
+/// Load the justification of the last block of the set `set_id`, which enacts the change to
+/// the next set.
+pub(crate) fn load_set_change_justification<B: AuxStore>(
+	backend: &B,
+	set_id: SetId,
+) -> ClientResult<Option<Justification>> {
+	backend.get_aux(&set_change_justification_key(set_id))
+}
+
+/// Cache the justification of the block `number` enacting the change from the set `set_id`
+/// to the next one. These justifications make up the warp sync proofs served to other nodes.
+pub(crate) fn write_set_change_justification<Block: BlockT, B: AuxStore, F, R>(
+	backend: &B,
+	set_id: SetId,
+	number: NumberFor<Block>,
+	justification: &[u8],
+	write_aux: F,
+) -> ClientResult<R> where
+	F: FnOnce(&[(&[u8], &[u8])]) -> R,
+{
+	let mut set_changes = load_set_changes::<Block, _>(backend)?;
+	// the voter may finalize the same change again after a restart.
+	set_changes.retain(|(id, _)| *id < set_id);
+	set_changes.push((set_id, number));
+
+	let encoded = set_changes.encode();
+	let key = set_change_justification_key(set_id);
+
+	Ok(write_aux(&[
+		(SET_CHANGES_KEY, &encoded[..]),
+		(&key[..], justification),
+	]))
+}
+
+/// Load the latest justification that doesn't enact a set change, along with the id of the
+/// set that produced it.
+pub(crate) fn load_latest_justification<B: AuxStore>(
+	backend: &B,
+) -> ClientResult<Option<(SetId, Justification)>> {
+	load_decode(backend, LATEST_JUSTIFICATION_KEY)
+}
+
+/// Cache the latest justification produced by the set `set_id`, which doesn't enact a set
+/// change. It ends the warp sync proofs served to other nodes.
+pub(crate) fn write_latest_justification<F, R>(
+	set_id: SetId,
+	justification: &[u8],
+	write_aux: F,
+) -> R where
+	F: FnOnce(&[(&[u8], &[u8])]) -> R,
+{
+	let encoded = (set_id, justification).encode();
+	write_aux(&[(LATEST_JUSTIFICATION_KEY, &encoded[..])])
+}
+
 #[cfg(test)]
 pub(crate) fn load_authorities<B: AuxStore, H: Decode, N: Decode>(backend: &B)
 	-> Option<AuthoritySet<H, N>> {
@@ -649,4 +724,34 @@ mod test {
 			Some(completed_round),
 		);
 	}
+
+	#[test]
+	fn write_read_set_change_justifications() {
+		let client = substrate_test_runtime_client::new();
+		type Block = substrate_test_runtime_client::runtime::Block;
+
+		let write = |set_id, number, justification: &[u8]| {
+			write_set_change_justification::<Block, _, _, _>(
+				&client,
+				set_id,
+				number,
+				justification,
+				|insert| client.insert_aux(insert, &[]),
+			).unwrap().unwrap();
+		};
+
+		write(0, 10, b"first");
+		write(1, 20, b"second");
+		// finalizing the same change again replaces it.
+		write(1, 20, b"second again");
+
+		assert_eq!(load_set_changes::<Block, _>(&client).unwrap(), vec![(0, 10), (1, 20)]);
+		assert_eq!(load_set_change_justification(&client, 0).unwrap(), Some(b"first".to_vec()));
+		assert_eq!(load_set_change_justification(&client, 1).unwrap(), Some(b"second again".to_vec()));
+		assert_eq!(load_set_change_justification(&client, 2).unwrap(), None);
+
+		assert_eq!(load_latest_justification(&client).unwrap(), None);
+		write_latest_justification(2, b"latest", |insert| client.insert_aux(insert, &[])).unwrap();
+		assert_eq!(load_latest_justification(&client).unwrap(), Some((2, b"latest".to_vec())));
+	}
 }
@@ -1204,6 +1204,32 @@ pub(crate) fn finalize_block<BE, Block, Client>(
 
 		let justification = justification.map(|j| j.encode());
 
+		// keep the justifications needed to build warp sync proofs at hand.
+		if let Some(justification) = justification.as_ref() {
+			let write_result = if status.new_set_block.is_some() {
+				crate::aux_schema::write_set_change_justification::<Block, _, _, _>(
+					&*client,
+					old_authority_set.set_id,
+					number,
+					justification,
+					|insert| apply_aux(import_op, insert, &[]),
+				).and_then(|result| result)
+			} else {
+				crate::aux_schema::write_latest_justification(
+					authority_set.set_id,
+					justification,
+					|insert| apply_aux(import_op, insert, &[]),
+				)
+			};
+
+			if let Err(e) = write_result {
+				warn!(target: "afg", "Failed to write justification of finalized block to disk. Bailing.");
+				warn!(target: "afg", "Node is in a potentially inconsistent state.");
+
+				return Err(e.into());
+			}
+		}
+
 		debug!(target: "afg", "Finalizing blocks up to ({:?}, {})", number, hash);
 
 		// ideally some handle to a synchronization oracle would be used
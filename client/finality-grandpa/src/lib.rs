@@ -123,6 +123,7 @@ mod notification;
 mod observer;
 mod until_imported;
 mod voting_rule;
+mod warp_proof;
 
 pub use authorities::SharedAuthoritySet;
 pub use finality_proof::{FinalityProofProvider, StorageAndProofProvider};
@@ -130,6 +131,7 @@ pub use notification::{GrandpaJustificationSender, GrandpaJustificationStream};
 pub use import::GrandpaBlockImport;
 pub use justification::GrandpaJustification;
 pub use light_import::{light_block_import, GrandpaLightBlockImport};
+pub use warp_proof::{WarpSyncFragment, WarpSyncProof, WarpSyncProofProvider};
 pub use voting_rule::{
 	BeforeBestBlockBy, ThreeQuartersOfTheUnfinalizedChain, VotingRule, VotingRulesBuilder
 };
@@ -0,0 +1,465 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! GRANDPA warp sync proof generation and check.
+//!
+//! A warp sync proof lets a node learn the latest finalized header of the chain, and the
+//! authority set that finalized it, starting from a block whose authority set it already
+//! knows (usually genesis). It is made of one fragment per authority set change since then:
+//! the header of the block enacting the change, which carries the new authority set in its
+//! `ScheduledChange` digest, along with its justification by the previous set. The proof ends
+//! with the latest justification of the current set, if any.
+//!
+//! Only standard changes signaled in the block enacting them (i.e. with a zero delay) can be
+//! proven this way. Proof generation fails at any other kind of change, or when a justification
+//! is missing, since the chain of trust between authority sets is broken there.
+//!
+//! Proofs are limited in size, so the whole chain may take several requests: each one starts
+//! from the header of the last fragment of the previous proof, until a proof is finished.
+
+use std::sync::Arc;
+use log::trace;
+
+use sc_client_api::backend::Backend;
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use parity_scale_codec::{Encode, Decode};
+use finality_grandpa::{BlockNumberOps, voter_set::VoterSet};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{NumberFor, Block as BlockT, Header as HeaderT, Zero},
+};
+use sp_finality_grandpa::{AuthorityList, SetId};
+
+use crate::aux_schema;
+use crate::import::find_scheduled_change;
+use crate::justification::GrandpaJustification;
+
+/// Maximum size in bytes of the fragments returned in a single warp sync proof. The first fragment
+/// is always returned, whatever its size, so that the syncing node can make progress.
+const MAX_WARP_SYNC_PROOF_SIZE: usize = 8 * 1024 * 1024;
+
+/// A fragment of a warp sync proof: a block and its justification.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct WarpSyncFragment<Block: BlockT> {
+	/// Header of the block. Unless this is the last fragment of a finished proof, the block
+	/// enacts an authority set change and its digest holds the new authority set.
+	pub header: Block::Header,
+	/// Justification of the block by the authority set in charge before it.
+	pub justification: GrandpaJustification<Block>,
+}
+
+/// A proof of the finality of a recent block, from a block of known authority set.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct WarpSyncProof<Block: BlockT> {
+	/// The fragments, in order.
+	pub proofs: Vec<WarpSyncFragment<Block>>,
+	/// Whether the proof reaches the latest authority set known to the prover. If not, the proof
+	/// was cut to its size limit and a new one must be requested, starting from the header of
+	/// the last fragment.
+	pub is_finished: bool,
+}
+
+impl<Block: BlockT> WarpSyncProof<Block> {
+	/// Generate a proof starting from the finalized block `begin`, using the set change
+	/// justifications cached by the voter.
+	///
+	/// Fails if the chain of trust from `begin` to the latest authority set is broken.
+	pub fn generate<B: Backend<Block>>(backend: &B, begin: Block::Hash) -> ClientResult<Self> {
+		let blockchain = backend.blockchain();
+
+		let begin_number = blockchain.expect_block_number_from_id(&BlockId::Hash(begin))?;
+		if begin_number > blockchain.info().finalized_number {
+			return Err(ClientError::Backend(
+				format!("Cannot generate warp sync proof from non-finalized block: {}", begin),
+			));
+		}
+
+		let canonical_begin = blockchain.expect_block_hash_from_id(&BlockId::Number(begin_number))?;
+		if begin != canonical_begin {
+			return Err(ClientError::Backend(
+				format!("Cannot generate warp sync proof from non-canonical block: {}", begin),
+			));
+		}
+
+		let set_changes = aux_schema::load_set_changes::<Block, _>(backend)?;
+
+		// the authority set in charge after `begin`, if we know it. Requests start either at
+		// genesis or at a block enacting a change.
+		let mut current_set_id = if begin_number.is_zero() {
+			Some(0)
+		} else {
+			set_changes.iter()
+				.find(|(_, number)| *number == begin_number)
+				.map(|(set_id, _)| set_id + 1)
+		};
+
+		let mut proofs = Vec::new();
+		let mut proofs_encoded_len = 0;
+		let mut proof_limit_reached = false;
+
+		let chain_of_trust_broken = |reason: String| ClientError::Backend(format!(
+			"Cannot generate warp sync proof from {}, the chain of trust is broken: {}",
+			begin,
+			reason,
+		));
+
+		for (set_id, number) in set_changes.into_iter().filter(|(_, number)| *number > begin_number) {
+			if let Some(current_set_id) = current_set_id.filter(|id| *id != set_id) {
+				return Err(chain_of_trust_broken(format!(
+					"set {} was ended by a forced change before #{}",
+					current_set_id,
+					number,
+				)));
+			}
+
+			let header = blockchain.expect_header(BlockId::Number(number))?;
+			if find_scheduled_change::<Block>(&header).map_or(true, |change| !change.delay.is_zero()) {
+				return Err(chain_of_trust_broken(
+					format!("block #{} doesn't signal the change it enacts", number),
+				));
+			}
+
+			let justification = match aux_schema::load_set_change_justification(backend, set_id)? {
+				Some(justification) => decode_justification::<Block>(&justification)?,
+				None => return Err(chain_of_trust_broken(
+					format!("the justification of block #{} is missing", number),
+				)),
+			};
+
+			let fragment = WarpSyncFragment { header, justification };
+			let fragment_size = fragment.encoded_size();
+			if !proofs.is_empty() && proofs_encoded_len + fragment_size > MAX_WARP_SYNC_PROOF_SIZE {
+				proof_limit_reached = true;
+				break;
+			}
+
+			proofs_encoded_len += fragment_size;
+			proofs.push(fragment);
+			current_set_id = Some(set_id + 1);
+		}
+
+		if !proof_limit_reached {
+			let last_number = proofs.last()
+				.map(|fragment: &WarpSyncFragment<Block>| *fragment.header.number())
+				.unwrap_or(begin_number);
+
+			if let Some((set_id, justification)) = aux_schema::load_latest_justification(backend)? {
+				// a justification of a later set means that the change to it isn't cached.
+				if current_set_id.map_or(false, |current_set_id| set_id > current_set_id) {
+					return Err(chain_of_trust_broken(
+						format!("the change to set {} is missing", set_id),
+					));
+				}
+
+				let justification = decode_justification::<Block>(&justification)?;
+				if current_set_id == Some(set_id) && justification.commit.target_number > last_number {
+					let header = blockchain.expect_header(BlockId::Hash(justification.commit.target_hash))?;
+					proofs.push(WarpSyncFragment { header, justification });
+				}
+			}
+		}
+
+		Ok(WarpSyncProof {
+			proofs,
+			is_finished: !proof_limit_reached,
+		})
+	}
+
+	/// Verify the proof, given the authority set in charge after the block it starts from.
+	///
+	/// Returns the authority set in charge after the last block of the proof.
+	pub fn verify(
+		&self,
+		set_id: SetId,
+		authorities: AuthorityList,
+	) -> ClientResult<(SetId, AuthorityList)> where
+		NumberFor<Block>: BlockNumberOps,
+	{
+		let mut current_set_id = set_id;
+		let mut current_authorities = authorities;
+
+		for (index, fragment) in self.proofs.iter().enumerate() {
+			let voters = VoterSet::new(current_authorities.iter().cloned())
+				.ok_or(ClientError::InvalidAuthoritiesSet)?;
+
+			if fragment.justification.commit.target_hash != fragment.header.hash() {
+				return Err(ClientError::BadJustification(
+					"warp sync proof justification doesn't target its header".to_string(),
+				));
+			}
+
+			fragment.justification.verify(current_set_id, &voters)?;
+
+			match find_scheduled_change::<Block>(&fragment.header) {
+				Some(change) if change.delay.is_zero() => {
+					current_set_id += 1;
+					current_authorities = change.next_authorities;
+				},
+				// the latest justification of the current set.
+				_ if self.is_finished && index + 1 == self.proofs.len() => {},
+				_ => return Err(ClientError::BadJustification(
+					"warp sync proof header doesn't enact an authority set change".to_string(),
+				)),
+			}
+		}
+
+		Ok((current_set_id, current_authorities))
+	}
+
+	/// The header of the last block proven, if any.
+	pub fn last_header(&self) -> Option<&Block::Header> {
+		self.proofs.last().map(|fragment| &fragment.header)
+	}
+}
+
+fn decode_justification<Block: BlockT>(encoded: &[u8]) -> ClientResult<GrandpaJustification<Block>> {
+	GrandpaJustification::decode(&mut &encoded[..]).map_err(|_| ClientError::JustificationDecode)
+}
+
+/// Serves warp sync proofs to other nodes of the network.
+pub struct WarpSyncProofProvider<B, Block> {
+	backend: Arc<B>,
+	_phantom: std::marker::PhantomData<Block>,
+}
+
+impl<B, Block> WarpSyncProofProvider<B, Block>
+	where
+		Block: BlockT,
+		B: Backend<Block> + Send + Sync + 'static,
+{
+	/// Create a new provider reading the justifications cached in the given backend.
+	pub fn new(backend: Arc<B>) -> Self {
+		WarpSyncProofProvider {
+			backend,
+			_phantom: std::marker::PhantomData,
+		}
+	}
+
+	/// Create a new provider to pass to the network service.
+	pub fn new_for_service(backend: Arc<B>) -> Arc<dyn sc_network::config::WarpSyncProvider<Block>> {
+		Arc::new(Self::new(backend))
+	}
+}
+
+impl<B, Block> sc_network::config::WarpSyncProvider<Block> for WarpSyncProofProvider<B, Block>
+	where
+		Block: BlockT,
+		B: Backend<Block> + Send + Sync + 'static,
+{
+	fn generate(&self, begin: Block::Hash) -> Result<Vec<u8>, ClientError> {
+		let proof = WarpSyncProof::<Block>::generate(&*self.backend, begin)?;
+		trace!(
+			target: "afg",
+			"Generated warp sync proof from {} with {} fragments",
+			begin,
+			proof.proofs.len(),
+		);
+		Ok(proof.encode())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_block_builder::BlockBuilderProvider;
+	use sp_consensus::BlockOrigin;
+	use sp_finality_grandpa::{ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
+	use sp_keyring::Ed25519Keyring;
+	use sp_runtime::DigestItem;
+	use sc_client_api::AuxStore;
+	use substrate_test_runtime_client::{
+		ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt, TestClientBuilder,
+		TestClientBuilderExt,
+		runtime::{Block, Header},
+	};
+
+	fn justification(
+		client: &Arc<substrate_test_runtime_client::Client<substrate_test_runtime_client::Backend>>,
+		header: &Header,
+		set_id: SetId,
+		signer: Ed25519Keyring,
+	) -> GrandpaJustification<Block> {
+		let round = 1;
+		let precommit = finality_grandpa::Precommit {
+			target_hash: header.hash(),
+			target_number: *header.number(),
+		};
+
+		let msg = finality_grandpa::Message::Precommit(precommit.clone());
+		let encoded = sp_finality_grandpa::localized_payload(round, set_id, &msg);
+		let precommit = finality_grandpa::SignedPrecommit {
+			precommit,
+			signature: signer.sign(&encoded[..]).into(),
+			id: signer.public().into(),
+		};
+
+		let commit = finality_grandpa::Commit {
+			target_hash: header.hash(),
+			target_number: *header.number(),
+			precommits: vec![precommit],
+		};
+
+		GrandpaJustification::from_commit(client, round, commit).unwrap()
+	}
+
+	type TestClient = Arc<
+		substrate_test_runtime_client::Client<substrate_test_runtime_client::Backend>
+	>;
+
+	/// Import and finalize six blocks, the ones in `changes` signaling a change to the given
+	/// authority with the given delay.
+	fn build_chain(
+		changes: &[(u64, Ed25519Keyring, u64)],
+	) -> (TestClient, Arc<substrate_test_runtime_client::Backend>, Vec<Header>) {
+		let (mut client, backend) = TestClientBuilder::new().build_with_backend();
+
+		let mut headers = Vec::new();
+		for number in 1..=6 {
+			let mut block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+			if let Some((_, next, delay)) = changes.iter().find(|(n, _, _)| *n == number) {
+				let change = ScheduledChange {
+					next_authorities: vec![(next.public().into(), 1)],
+					delay: *delay,
+				};
+				block.header.digest_mut().push(DigestItem::Consensus(
+					GRANDPA_ENGINE_ID,
+					ConsensusLog::ScheduledChange(change).encode(),
+				));
+			}
+			headers.push(block.header.clone());
+			client.import(BlockOrigin::Own, block).unwrap();
+		}
+		client.finalize_block(BlockId::Number(6), None).unwrap();
+
+		(Arc::new(client), backend, headers)
+	}
+
+	fn write_set_change_justification(
+		client: &TestClient,
+		set_id: SetId,
+		header: &Header,
+		signer: Ed25519Keyring,
+	) {
+		let justification = justification(client, header, set_id, signer).encode();
+		aux_schema::write_set_change_justification::<Block, _, _, _>(
+			&**client,
+			set_id,
+			*header.number(),
+			&justification,
+			|insert| client.insert_aux(insert, &[]),
+		).unwrap().unwrap();
+	}
+
+	#[test]
+	fn warp_sync_proof_proves_set_changes_and_latest_justification() {
+		// the set changes to Bob at #2 and to Charlie at #4.
+		let (client, backend, headers) = build_chain(&[
+			(2, Ed25519Keyring::Bob, 0),
+			(4, Ed25519Keyring::Charlie, 0),
+		]);
+		let genesis_hash = client.chain_info().genesis_hash;
+
+		write_set_change_justification(&client, 0, &headers[1], Ed25519Keyring::Alice);
+		write_set_change_justification(&client, 1, &headers[3], Ed25519Keyring::Bob);
+		let latest = justification(&client, &headers[5], 2, Ed25519Keyring::Charlie).encode();
+		aux_schema::write_latest_justification(2, &latest, |insert| client.insert_aux(insert, &[]))
+			.unwrap();
+
+		let proof = WarpSyncProof::<Block>::generate(&*backend, genesis_hash).unwrap();
+		assert!(proof.is_finished);
+		assert_eq!(proof.proofs.len(), 3);
+		assert_eq!(proof.last_header(), Some(&headers[5]));
+
+		let genesis_authorities = vec![(Ed25519Keyring::Alice.public().into(), 1)];
+		assert_eq!(
+			proof.verify(0, genesis_authorities.clone()).unwrap(),
+			(2, vec![(Ed25519Keyring::Charlie.public().into(), 1)]),
+		);
+
+		// the proof doesn't hold for another starting authority set.
+		let other_authorities = vec![(Ed25519Keyring::Dave.public().into(), 1)];
+		assert!(proof.verify(0, other_authorities).is_err());
+		assert!(proof.verify(1, genesis_authorities).is_err());
+
+		// proofs can start from a set change.
+		let proof = WarpSyncProof::<Block>::generate(&*backend, headers[1].hash()).unwrap();
+		assert_eq!(proof.proofs.len(), 2);
+		assert_eq!(
+			proof.verify(1, vec![(Ed25519Keyring::Bob.public().into(), 1)]).unwrap().0,
+			2,
+		);
+	}
+	#[test]
+	fn warp_sync_proof_fails_at_forced_change() {
+		// set 1 is ended by a forced change, so only the changes of sets 0 and 2 are cached.
+		let (client, backend, headers) = build_chain(&[
+			(2, Ed25519Keyring::Bob, 0),
+			(4, Ed25519Keyring::Charlie, 0),
+		]);
+		let genesis_hash = client.chain_info().genesis_hash;
+		write_set_change_justification(&client, 0, &headers[1], Ed25519Keyring::Alice);
+		write_set_change_justification(&client, 2, &headers[3], Ed25519Keyring::Dave);
+
+		assert!(WarpSyncProof::<Block>::generate(&*backend, genesis_hash).is_err());
+	}
+
+	#[test]
+	fn warp_sync_proof_fails_at_delayed_change() {
+		let (client, backend, headers) = build_chain(&[(2, Ed25519Keyring::Bob, 1)]);
+		let genesis_hash = client.chain_info().genesis_hash;
+		write_set_change_justification(&client, 0, &headers[2], Ed25519Keyring::Alice);
+
+		assert!(WarpSyncProof::<Block>::generate(&*backend, genesis_hash).is_err());
+	}
+
+	#[test]
+	fn warp_sync_proof_fails_at_missing_justification() {
+		let (client, backend, headers) = build_chain(&[
+			(2, Ed25519Keyring::Bob, 0),
+			(4, Ed25519Keyring::Charlie, 0),
+		]);
+		let genesis_hash = client.chain_info().genesis_hash;
+		write_set_change_justification(&client, 0, &headers[1], Ed25519Keyring::Alice);
+		// only record the change of set 1, without its justification.
+		let justification = justification(&client, &headers[3], 1, Ed25519Keyring::Bob).encode();
+		aux_schema::write_set_change_justification::<Block, _, _, _>(
+			&*client,
+			1,
+			4,
+			&justification,
+			|insert| client.insert_aux(&insert[..1], &[]),
+		).unwrap().unwrap();
+
+		assert!(WarpSyncProof::<Block>::generate(&*backend, genesis_hash).is_err());
+
+		// starting after the cached justification doesn't get around the missing one.
+		assert!(WarpSyncProof::<Block>::generate(&*backend, headers[1].hash()).is_err());
+	}
+
+	#[test]
+	fn warp_sync_proof_fails_if_latest_justification_is_of_a_later_set() {
+		let (client, backend, headers) = build_chain(&[(2, Ed25519Keyring::Bob, 0)]);
+		let genesis_hash = client.chain_info().genesis_hash;
+		write_set_change_justification(&client, 0, &headers[1], Ed25519Keyring::Alice);
+		let latest = justification(&client, &headers[5], 2, Ed25519Keyring::Charlie).encode();
+		aux_schema::write_latest_justification(2, &latest, |insert| client.insert_aux(insert, &[]))
+			.unwrap();
+
+		assert!(WarpSyncProof::<Block>::generate(&*backend, genesis_hash).is_err());
+	}
+}
@@ -15,7 +15,8 @@
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	config::{ProtocolId, Role}, block_requests, light_client_handler, finality_requests,
+	config::{ProtocolId, Role}, block_requests, light_client_requests, finality_requests,
+	request_responses::{self, RequestFailure}, state_requests,
 	peer_info, discovery::{DiscoveryBehaviour, DiscoveryConfig, DiscoveryOut},
 	protocol::{message::{self, Roles}, CustomMessageOutcome, NotificationsSink, Protocol},
 	ObservedRole, DhtEvent, ExHashT,
@@ -23,6 +24,7 @@ use crate::{
 
 use bytes::Bytes;
 use codec::Encode as _;
+use futures::{channel::oneshot, future::BoxFuture, prelude::*, stream::FuturesUnordered};
 use libp2p::NetworkBehaviour;
 use libp2p::core::{Multiaddr, PeerId, PublicKey};
 use libp2p::identify::IdentifyInfo;
@@ -50,12 +52,8 @@ pub struct Behaviour<B: BlockT, H: ExHashT> {
 	peer_info: peer_info::PeerInfoBehaviour,
 	/// Discovers nodes of the network.
 	discovery: DiscoveryBehaviour,
-	/// Block request handling.
-	block_requests: block_requests::BlockRequests<B>,
-	/// Finality proof request handling.
-	finality_proof_requests: finality_requests::FinalityProofRequests<B>,
-	/// Light client request handling.
-	light_client_handler: light_client_handler::LightClientHandler<B>,
+	/// Generic request-response protocols.
+	request_responses: request_responses::RequestResponsesBehaviour,
 
 	/// Queue of events to produce for the outside.
 	#[behaviour(ignore)]
@@ -64,6 +62,45 @@ pub struct Behaviour<B: BlockT, H: ExHashT> {
 	/// Role of our local node, as originally passed from the configuration.
 	#[behaviour(ignore)]
 	role: Role,
+
+	/// Protocol name used to send out block requests.
+	#[behaviour(ignore)]
+	block_request_protocol_name: Cow<'static, [u8]>,
+
+	/// Protocol name used to send out state requests.
+	#[behaviour(ignore)]
+	state_request_protocol_name: Cow<'static, [u8]>,
+
+	/// Protocol name used to send out finality proof requests.
+	#[behaviour(ignore)]
+	finality_proof_request_protocol_name: Cow<'static, [u8]>,
+
+	/// Sends out the light client requests, over the light client and block request protocols.
+	#[behaviour(ignore)]
+	light_client_request_sender: light_client_requests::LightClientRequestSender<B>,
+
+	/// Responses to the requests emitted on behalf of the sync, to report back to the
+	/// substrate protocol.
+	#[behaviour(ignore)]
+	pending_sync_responses: FuturesUnordered<BoxFuture<'static, SyncResponse<B>>>,
+}
+
+/// Outcome of a request emitted on behalf of the sync.
+enum SyncResponse<B: BlockT> {
+	Block {
+		peer: PeerId,
+		request: message::BlockRequest<B>,
+		response: Result<Vec<u8>, RequestFailure>,
+	},
+	State {
+		peer: PeerId,
+		response: Result<Vec<u8>, RequestFailure>,
+	},
+	FinalityProof {
+		peer: PeerId,
+		block_hash: B::Hash,
+		response: Result<Vec<u8>, RequestFailure>,
+	},
 }
 
 /// Event generated by `Behaviour`.
@@ -152,26 +189,39 @@ pub enum BehaviourOut<B: BlockT> {
 
 impl<B: BlockT, H: ExHashT> Behaviour<B, H> {
 	/// Builds a new `Behaviour`.
+	///
+	/// The block, state and finality proof request protocols are used by the sync, and are
+	/// registered along with the other `request_response_protocols`.
 	pub fn new(
 		substrate: Protocol<B, H>,
 		role: Role,
 		user_agent: String,
 		local_public_key: PublicKey,
-		block_requests: block_requests::BlockRequests<B>,
-		finality_proof_requests: finality_requests::FinalityProofRequests<B>,
-		light_client_handler: light_client_handler::LightClientHandler<B>,
+		block_request_protocol_config: request_responses::ProtocolConfig,
+		state_request_protocol_config: request_responses::ProtocolConfig,
+		finality_proof_request_protocol_config: request_responses::ProtocolConfig,
+		request_response_protocols: Vec<request_responses::ProtocolConfig>,
+		light_client_request_sender: light_client_requests::LightClientRequestSender<B>,
 		disco_config: DiscoveryConfig,
-	) -> Self {
-		Behaviour {
+	) -> Result<Self, request_responses::RegisterError> {
+		Ok(Behaviour {
 			substrate,
 			peer_info: peer_info::PeerInfoBehaviour::new(user_agent, local_public_key),
 			discovery: disco_config.finish(),
-			block_requests,
-			finality_proof_requests,
-			light_client_handler,
+			block_request_protocol_name: block_request_protocol_config.name.clone(),
+			state_request_protocol_name: state_request_protocol_config.name.clone(),
+			finality_proof_request_protocol_name: finality_proof_request_protocol_config.name.clone(),
+			request_responses: request_responses::RequestResponsesBehaviour::new(
+				iter::once(block_request_protocol_config)
+					.chain(iter::once(state_request_protocol_config))
+					.chain(iter::once(finality_proof_request_protocol_config))
+					.chain(request_response_protocols.into_iter())
+			)?,
+			light_client_request_sender,
 			events: VecDeque::new(),
 			role,
-		}
+			pending_sync_responses: FuturesUnordered::new(),
+		})
 	}
 
 	/// Returns the list of nodes that we know exist in the network.
@@ -257,8 +307,111 @@ impl<B: BlockT, H: ExHashT> Behaviour<B, H> {
 	}
 
 	/// Issue a light client request.
-	pub fn light_client_request(&mut self, r: light_client_handler::Request<B>) -> Result<(), light_client_handler::Error> {
-		self.light_client_handler.request(r)
+	pub fn light_client_request(&mut self, r: light_client_requests::Request<B>) -> Result<(), light_client_requests::Error> {
+		self.light_client_request_sender.request(r)
+	}
+
+	/// Initiates sending a request on one of the request-response protocols.
+	///
+	/// The response, or the reason why the request failed, is sent to `pending_response`.
+	pub fn send_request(
+		&mut self,
+		target: &PeerId,
+		protocol: &[u8],
+		request: Vec<u8>,
+		pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+	) {
+		match self.request_responses.send_request(target, protocol, request, pending_response) {
+			request_responses::SendRequestOutcome::Ok => {
+				self.events.push_back(BehaviourOut::RequestStarted {
+					peer: target.clone(),
+					protocol: protocol.to_vec(),
+				});
+			},
+			request_responses::SendRequestOutcome::NotConnected |
+			request_responses::SendRequestOutcome::UnknownProtocol => {},
+		}
+	}
+
+	/// Sends a request on behalf of the sync. `on_response` turns the outcome of the request
+	/// into the `SyncResponse` to report back to the substrate protocol.
+	fn send_sync_request(
+		&mut self,
+		target: PeerId,
+		protocol: Cow<'static, [u8]>,
+		request: Vec<u8>,
+		on_response: impl FnOnce(PeerId, Result<Vec<u8>, RequestFailure>) -> SyncResponse<B> + Send + 'static,
+	) {
+		let (tx, rx) = oneshot::channel();
+		self.send_request(&target, &protocol, request, tx);
+		self.pending_sync_responses.push(async move {
+			// The sender is only dropped once the request has finished, so this can't fail.
+			let response = rx.await.unwrap_or(Err(RequestFailure::Refused));
+			on_response(target, response)
+		}.boxed());
+	}
+
+	/// Reports the outcome of a request emitted on behalf of the sync to the substrate protocol.
+	fn on_sync_response(&mut self, response: SyncResponse<B>) {
+		match response {
+			SyncResponse::Block { peer, request, response } => {
+				let response = match response {
+					Ok(response) => response,
+					Err(RequestFailure::NotConnected) => return,
+					Err(err) => {
+						debug!(target: "sync", "Block request to {} failed: {}", peer, err);
+						// There doesn't exist any mechanism to report cancellations or timeouts
+						// yet, so we process them by disconnecting the node.
+						self.substrate.on_block_request_failed(&peer);
+						return
+					}
+				};
+				match block_requests::decode_block_response(&request, &response) {
+					Ok(response) => {
+						let ev = self.substrate.on_block_response(peer, response);
+						self.inject_event(ev);
+					}
+					Err(err) => {
+						debug!(target: "sync", "Failed to decode block response from peer {}: {}", peer, err);
+						self.substrate.on_block_request_failed(&peer);
+					}
+				}
+			}
+			SyncResponse::State { peer, response } => {
+				let response = match response {
+					Ok(response) => response,
+					Err(RequestFailure::NotConnected) => return,
+					Err(err) => {
+						debug!(target: "sync", "State request to {} failed: {}", peer, err);
+						self.substrate.on_state_request_failed(&peer);
+						return
+					}
+				};
+				match state_requests::decode_state_response(&response) {
+					Ok(response) => {
+						let ev = self.substrate.on_state_response(peer, response);
+						self.inject_event(ev);
+					}
+					Err(err) => {
+						debug!(target: "sync", "Failed to decode state response from peer {}: {}", peer, err);
+						self.substrate.on_state_request_failed(&peer);
+					}
+				}
+			}
+			SyncResponse::FinalityProof { peer, block_hash, response } => {
+				let response = response.map_err(|err| err.to_string()).and_then(|response| {
+					finality_requests::decode_finality_proof_response(block_hash, &response)
+						.map_err(|err| err.to_string())
+				});
+				match response {
+					Ok(response) => {
+						let ev = self.substrate.on_finality_proof_response(peer, response);
+						self.inject_event(ev);
+					}
+					Err(err) => debug!(target: "sync", "Finality proof request to {} failed: {}", peer, err),
+				}
+			}
+		}
 	}
 }
 
@@ -296,33 +449,56 @@ Behaviour<B, H> {
 			CustomMessageOutcome::FinalityProofImport(origin, hash, nb, proof) =>
 				self.events.push_back(BehaviourOut::FinalityProofImport(origin, hash, nb, proof)),
 			CustomMessageOutcome::BlockRequest { target, request } => {
-				match self.block_requests.send_request(&target, request) {
-					block_requests::SendRequestOutcome::Ok => {
-						self.events.push_back(BehaviourOut::RequestStarted {
-							peer: target,
-							protocol: self.block_requests.protocol_name().to_vec(),
-						});
-					},
-					block_requests::SendRequestOutcome::Replaced { request_duration, .. } => {
-						self.events.push_back(BehaviourOut::RequestFinished {
-							peer: target.clone(),
-							protocol: self.block_requests.protocol_name().to_vec(),
-							request_duration,
-						});
-						self.events.push_back(BehaviourOut::RequestStarted {
-							peer: target,
-							protocol: self.block_requests.protocol_name().to_vec(),
+				match block_requests::encode_block_request(&request) {
+					Ok(payload) => {
+						let protocol = self.block_request_protocol_name.clone();
+						self.send_sync_request(target, protocol, payload, move |peer, response| {
+							SyncResponse::Block { peer, request, response }
 						});
 					}
-					block_requests::SendRequestOutcome::NotConnected |
-					block_requests::SendRequestOutcome::EncodeError(_) => {},
+					Err(err) => log::warn!(
+						target: "sync",
+						"Failed to encode block request {:?}: {:?}",
+						request,
+						err
+					),
 				}
 			},
 			CustomMessageOutcome::FinalityProofRequest { target, block_hash, request } => {
-				self.finality_proof_requests.send_request(&target, block_hash, request);
+				match finality_requests::encode_finality_proof_request(&block_hash, request) {
+					Ok(payload) => {
+						let protocol = self.finality_proof_request_protocol_name.clone();
+						self.send_sync_request(target, protocol, payload, move |peer, response| {
+							SyncResponse::FinalityProof { peer, block_hash, response }
+						});
+					}
+					Err(err) => log::warn!(
+						target: "sync",
+						"Failed to encode finality proof request for {:?}: {:?}",
+						block_hash,
+						err
+					),
+				}
+			},
+			CustomMessageOutcome::StateRequest { target, request } => {
+				match state_requests::encode_state_request(&request) {
+					Ok(payload) => {
+						let protocol = self.state_request_protocol_name.clone();
+						self.send_sync_request(target, protocol, payload, |peer, response| {
+							SyncResponse::State { peer, response }
+						});
+					}
+					Err(err) => log::warn!(
+						target: "sync",
+						"Failed to encode state request {:?}: {:?}",
+						request,
+						err
+					),
+				}
 			},
 			CustomMessageOutcome::NotificationStreamOpened { remote, protocols, roles, notifications_sink } => {
 				let role = reported_roles_to_observed_role(&self.role, &remote, roles);
+				self.light_client_request_sender.inject_connected(remote.clone());
 				for engine_id in protocols {
 					self.events.push_back(BehaviourOut::NotificationStreamOpened {
 						remote: remote.clone(),
@@ -340,74 +516,52 @@ Behaviour<B, H> {
 						notifications_sink: notifications_sink.clone(),
 					});
 				},
-			CustomMessageOutcome::NotificationStreamClosed { remote, protocols } =>
+			CustomMessageOutcome::NotificationStreamClosed { remote, protocols } => {
+				self.light_client_request_sender.inject_disconnected(&remote);
 				for engine_id in protocols {
 					self.events.push_back(BehaviourOut::NotificationStreamClosed {
 						remote: remote.clone(),
 						engine_id,
 					});
-				},
+				}
+			},
 			CustomMessageOutcome::NotificationsReceived { remote, messages } => {
 				self.events.push_back(BehaviourOut::NotificationsReceived { remote, messages });
 			},
 			CustomMessageOutcome::PeerNewBest(peer_id, number) => {
-				self.light_client_handler.update_best_block(&peer_id, number);
+				self.light_client_request_sender.update_best_block(&peer_id, number);
 			}
 			CustomMessageOutcome::None => {}
 		}
 	}
 }
 
-impl<B: BlockT, H: ExHashT> NetworkBehaviourEventProcess<block_requests::Event<B>> for Behaviour<B, H> {
-	fn inject_event(&mut self, event: block_requests::Event<B>) {
+impl<B: BlockT, H: ExHashT> NetworkBehaviourEventProcess<request_responses::Event> for Behaviour<B, H> {
+	fn inject_event(&mut self, event: request_responses::Event) {
 		match event {
-			block_requests::Event::AnsweredRequest { peer, total_handling_time } => {
+			request_responses::Event::InboundRequest { peer, protocol, result: Ok(build_time) } => {
 				self.events.push_back(BehaviourOut::AnsweredRequest {
 					peer,
-					protocol: self.block_requests.protocol_name().to_vec(),
-					build_time: total_handling_time,
+					protocol: protocol.into_owned(),
+					build_time,
 				});
 			},
-			block_requests::Event::Response { peer, original_request: _, response, request_duration } => {
-				self.events.push_back(BehaviourOut::RequestFinished {
-					peer: peer.clone(),
-					protocol: self.block_requests.protocol_name().to_vec(),
-					request_duration,
-				});
-				let ev = self.substrate.on_block_response(peer, response);
-				self.inject_event(ev);
-			}
-			block_requests::Event::RequestCancelled { peer, request_duration, .. } |
-			block_requests::Event::RequestTimeout { peer, request_duration, .. } => {
-				// There doesn't exist any mechanism to report cancellations or timeouts yet, so
-				// we process them by disconnecting the node.
+			request_responses::Event::InboundRequest { peer, protocol, result: Err(err) } => {
+				debug!(
+					target: "sub-libp2p",
+					"Failed to answer {} request from {}: {}",
+					String::from_utf8_lossy(&protocol),
+					peer,
+					err,
+				);
+			},
+			request_responses::Event::RequestFinished { peer, protocol, request_duration } => {
 				self.events.push_back(BehaviourOut::RequestFinished {
-					peer: peer.clone(),
-					protocol: self.block_requests.protocol_name().to_vec(),
+					peer,
+					protocol: protocol.into_owned(),
 					request_duration,
 				});
-				self.substrate.on_block_request_failed(&peer);
-			}
-		}
-	}
-}
-
-impl<B: BlockT, H: ExHashT> NetworkBehaviourEventProcess<finality_requests::Event<B>> for Behaviour<B, H> {
-	fn inject_event(&mut self, event: finality_requests::Event<B>) {
-		match event {
-			finality_requests::Event::Response { peer, block_hash, proof } => {
-				let response = message::FinalityProofResponse {
-					id: 0,
-					block: block_hash,
-					proof: if !proof.is_empty() {
-						Some(proof)
-					} else {
-						None
-					},
-				};
-				let ev = self.substrate.on_finality_proof_response(peer, response);
-				self.inject_event(ev);
-			}
+			},
 		}
 	}
 }
@@ -477,7 +631,19 @@ impl<B: BlockT, H: ExHashT> NetworkBehaviourEventProcess<DiscoveryOut>
 }
 
 impl<B: BlockT, H: ExHashT> Behaviour<B, H> {
-	fn poll<TEv>(&mut self, _: &mut Context, _: &mut impl PollParameters) -> Poll<NetworkBehaviourAction<TEv, BehaviourOut<B>>> {
+	fn poll<TEv>(&mut self, cx: &mut Context, _: &mut impl PollParameters) -> Poll<NetworkBehaviourAction<TEv, BehaviourOut<B>>> {
+		while let Poll::Ready(Some(response)) = self.pending_sync_responses.poll_next_unpin(cx) {
+			self.on_sync_response(response);
+		}
+
+		while let Poll::Ready(Some(event)) = self.light_client_request_sender.poll_next_unpin(cx) {
+			match event {
+				light_client_requests::OutEvent::SendRequest {
+					target, request, pending_response, protocol_name,
+				} => self.send_request(&target, &protocol_name, request, pending_response),
+			}
+		}
+
 		if let Some(event) = self.events.pop_front() {
 			return Poll::Ready(NetworkBehaviourAction::GenerateEvent(event))
 		}
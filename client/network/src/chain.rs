@@ -44,3 +44,9 @@ impl<Block: BlockT> FinalityProofProvider<Block> for () {
 		Ok(None)
 	}
 }
+
+/// Warp sync proof provider.
+pub trait WarpSyncProvider<Block: BlockT>: Send + Sync {
+	/// Generate a proof of the latest finalized block, starting from the finalized block `begin`.
+	fn generate(&self, begin: Block::Hash) -> Result<Vec<u8>, Error>;
+}
@@ -21,8 +21,9 @@
 //! The [`Params`] struct is the struct that must be passed in order to initialize the networking.
 //! See the documentation of [`Params`].
 
-pub use crate::chain::{Client, FinalityProofProvider};
+pub use crate::chain::{Client, FinalityProofProvider, WarpSyncProvider};
 pub use crate::on_demand_layer::{AlwaysBadChecker, OnDemand};
+pub use crate::request_responses::{IncomingRequest, ProtocolConfig as RequestResponseConfig};
 pub use libp2p::{identity, core::PublicKey, wasm_ext::ExtTransport, build_multiaddr};
 
 // Note: this re-export shouldn't be part of the public API of the crate and will be removed in
@@ -78,6 +79,12 @@ pub struct Params<B: BlockT, H: ExHashT> {
 	/// This object, if `Some`, is used when we need a proof of finality from another node.
 	pub finality_proof_request_builder: Option<BoxFinalityProofRequestBuilder<B>>,
 
+	/// Warp sync proof provider.
+	///
+	/// This object, if `Some`, is used when a node on the network requests a warp sync proof
+	/// from us.
+	pub warp_sync_provider: Option<Arc<dyn WarpSyncProvider<B>>>,
+
 	/// The `OnDemand` object acts as a "receiver" for block data requests from the client.
 	/// If `Some`, the network worker will process these requests and answer them.
 	/// Normally used only for light clients.
@@ -407,6 +414,9 @@ pub struct NetworkConfiguration {
 	/// List of notifications protocols that the node supports. Must also include a
 	/// `ConsensusEngineId` for backwards-compatibility.
 	pub notifications_protocols: Vec<(ConsensusEngineId, Cow<'static, [u8]>)>,
+	/// List of request-response protocols that the node supports, in addition to the ones
+	/// built into the network.
+	pub request_response_protocols: Vec<RequestResponseConfig>,
 	/// Maximum allowed number of incoming connections.
 	pub in_peers: u32,
 	/// Number of outgoing connections we're trying to maintain.
@@ -423,6 +433,8 @@ pub struct NetworkConfiguration {
 	pub transport: TransportConfig,
 	/// Maximum number of peers to ask the same blocks in parallel.
 	pub max_parallel_downloads: u32,
+	/// How to synchronize with the rest of the network.
+	pub sync_mode: SyncMode,
 	/// Should we insert non-global addresses into the DHT?
 	pub allow_non_globals_in_dht: bool,
 }
@@ -442,6 +454,7 @@ impl NetworkConfiguration {
 			boot_nodes: Vec::new(),
 			node_key,
 			notifications_protocols: Vec::new(),
+			request_response_protocols: Vec::new(),
 			in_peers: 25,
 			out_peers: 75,
 			reserved_nodes: Vec::new(),
@@ -455,6 +468,7 @@ impl NetworkConfiguration {
 				use_yamux_flow_control: false,
 			},
 			max_parallel_downloads: 5,
+			sync_mode: SyncMode::Full,
 			allow_non_globals_in_dht: false,
 		}
 	}
@@ -551,6 +565,27 @@ impl NonReservedPeerMode {
 	}
 }
 
+/// How the node synchronizes with the rest of the network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncMode {
+	/// Download and execute every block, starting from genesis. This is the default.
+	Full,
+	/// Download the headers up to a recent finalized block, then download the state of that
+	/// block instead of executing the blocks preceding it.
+	Fast,
+}
+
+impl SyncMode {
+	/// Attempt to parse the sync mode from a string.
+	pub fn parse(s: &str) -> Option<Self> {
+		match s {
+			"full" => Some(SyncMode::Full),
+			"fast" => Some(SyncMode::Fast),
+			_ => None,
+		}
+	}
+}
+
 /// The configuration of a node's secret key, describing the type of key
 /// and how it is obtained. A node's identity keypair is the result of
 /// the evaluation of the node key configuration.
@@ -14,390 +14,137 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
-//! `NetworkBehaviour` implementation which handles incoming finality proof requests.
+//! Finality proof requests, exchanged over the request-response protocol
+//! `/<id>/finality-proof/1`.
 //!
-//! Every request is coming in on a separate connection substream which gets
-//! closed after we have sent the response back. Incoming requests are encoded
-//! as protocol buffers (cf. `finality.v1.proto`).
+//! [`FinalityProofRequestHandler`] answers the finality proof requests coming from remotes,
+//! while the helpers of this module encode our own requests and decode their responses.
+//! Requests and responses are encoded as protocol buffers (cf. `finality.v1.proto`).
 
-#![allow(unused)]
-
-use bytes::Bytes;
 use codec::{Encode, Decode};
 use crate::{
 	chain::FinalityProofProvider,
 	config::ProtocolId,
 	protocol::message,
+	request_responses::{self, IncomingRequest, ProtocolConfig},
 	schema,
 };
-use futures::{future::BoxFuture, prelude::*, stream::FuturesUnordered};
-use libp2p::{
-	core::{
-		ConnectedPoint,
-		Multiaddr,
-		PeerId,
-		connection::ConnectionId,
-		upgrade::{InboundUpgrade, OutboundUpgrade, ReadOneError, UpgradeInfo, Negotiated},
-		upgrade::{DeniedUpgrade, read_one, write_one}
-	},
-	swarm::{
-		NegotiatedSubstream,
-		NetworkBehaviour,
-		NetworkBehaviourAction,
-		NotifyHandler,
-		OneShotHandler,
-		OneShotHandlerConfig,
-		PollParameters,
-		SubstreamProtocol
-	}
-};
+use futures::{channel::mpsc, prelude::*};
+use libp2p::PeerId;
 use prost::Message;
-use sp_runtime::{generic::BlockId, traits::{Block, Header, One, Zero}};
-use std::{
-	cmp::min,
-	collections::VecDeque,
-	io,
-	iter,
-	marker::PhantomData,
-	sync::Arc,
-	time::Duration,
-	task::{Context, Poll}
-};
-use void::{Void, unreachable};
+use sp_runtime::traits::Block;
+use std::{sync::Arc, time::Duration};
 
 // Type alias for convenience.
 pub type Error = Box<dyn std::error::Error + 'static>;
 
-/// Event generated by the finality proof requests behaviour.
-#[derive(Debug)]
-pub enum Event<B: Block> {
-	/// A response to a finality proof request has arrived.
-	Response {
-		peer: PeerId,
-		/// Block hash originally passed to `send_request`.
-		block_hash: B::Hash,
-		/// Finality proof returned by the remote.
-		proof: Vec<u8>,
-	},
-}
-
-/// Configuration options for `FinalityProofRequests`.
-#[derive(Debug, Clone)]
-pub struct Config {
-	max_request_len: usize,
-	max_response_len: usize,
-	inactivity_timeout: Duration,
-	protocol: Bytes,
-}
-
-impl Config {
-	/// Create a fresh configuration with the following options:
-	///
-	/// - max. request size = 1 MiB
-	/// - max. response size = 1 MiB
-	/// - inactivity timeout = 15s
-	pub fn new(id: &ProtocolId) -> Self {
-		let mut c = Config {
-			max_request_len: 1024 * 1024,
-			max_response_len: 1024 * 1024,
-			inactivity_timeout: Duration::from_secs(15),
-			protocol: Bytes::new(),
-		};
-		c.set_protocol(id);
-		c
-	}
-
-	/// Limit the max. length of incoming finality proof request bytes.
-	pub fn set_max_request_len(&mut self, v: usize) -> &mut Self {
-		self.max_request_len = v;
-		self
-	}
+/// Number of incoming requests that can be queued before new ones are refused.
+const INBOUND_QUEUE_SIZE: usize = 20;
 
-	/// Limit the max. length of incoming finality proof response bytes.
-	pub fn set_max_response_len(&mut self, v: usize) -> &mut Self {
-		self.max_response_len = v;
-		self
-	}
-
-	/// Limit the max. duration the substream may remain inactive before closing it.
-	pub fn set_inactivity_timeout(&mut self, v: Duration) -> &mut Self {
-		self.inactivity_timeout = v;
-		self
-	}
-
-	/// Set protocol to use for upgrade negotiation.
-	pub fn set_protocol(&mut self, id: &ProtocolId) -> &mut Self {
-		let mut v = Vec::new();
-		v.extend_from_slice(b"/");
-		v.extend_from_slice(id.as_bytes());
-		v.extend_from_slice(b"/finality-proof/1");
-		self.protocol = v.into();
-		self
+/// Generates a [`ProtocolConfig`] for the finality proof request protocol, refusing incoming
+/// requests.
+pub fn generate_protocol_config(protocol_id: &ProtocolId) -> ProtocolConfig {
+	ProtocolConfig {
+		name: request_responses::protocol_name(protocol_id, b"/finality-proof/1"),
+		max_request_size: 1024 * 1024,
+		max_response_size: 1024 * 1024,
+		request_timeout: Duration::from_secs(10),
+		inbound_queue: None,
 	}
 }
 
-/// The finality proof request handling behaviour.
-pub struct FinalityProofRequests<B: Block> {
-	/// This behaviour's configuration.
-	config: Config,
+/// Handler for incoming finality proof requests from a remote peer.
+pub struct FinalityProofRequestHandler<B: Block> {
 	/// How to construct finality proofs.
-	finality_proof_provider: Option<Arc<dyn FinalityProofProvider<B>>>,
-	/// Futures sending back the finality proof request responses.
-	outgoing: FuturesUnordered<BoxFuture<'static, ()>>,
-	/// Events to return as soon as possible from `poll`.
-	pending_events: VecDeque<NetworkBehaviourAction<OutboundProtocol<B>, Event<B>>>,
+	finality_proof_provider: Arc<dyn FinalityProofProvider<B>>,
+	/// Incoming requests, sent by the network.
+	request_receiver: mpsc::Receiver<IncomingRequest>,
 }
 
-impl<B> FinalityProofRequests<B>
+impl<B> FinalityProofRequestHandler<B>
 where
 	B: Block,
 {
-	/// Initializes the behaviour.
-	///
-	/// If the proof provider is `None`, then the behaviour will not support the finality proof
-	/// requests protocol.
-	pub fn new(cfg: Config, finality_proof_provider: Option<Arc<dyn FinalityProofProvider<B>>>) -> Self {
-		FinalityProofRequests {
-			config: cfg,
-			finality_proof_provider,
-			outgoing: FuturesUnordered::new(),
-			pending_events: VecDeque::new(),
-		}
-	}
-
-	/// Issue a new finality proof request.
-	///
-	/// If the response doesn't arrive in time, or if the remote answers improperly, the target
-	/// will be disconnected.
-	pub fn send_request(&mut self, target: &PeerId, block_hash: B::Hash, request: Vec<u8>) {
-		let protobuf_rq = schema::v1::finality::FinalityProofRequest {
-			block_hash: block_hash.encode(),
-			request,
-		};
-
-		let mut buf = Vec::with_capacity(protobuf_rq.encoded_len());
-		if let Err(err) = protobuf_rq.encode(&mut buf) {
-			log::warn!("failed to encode finality proof request {:?}: {:?}", protobuf_rq, err);
-			return;
-		}
-
-		log::trace!("enqueueing finality proof request to {:?}: {:?}", target, protobuf_rq);
-		self.pending_events.push_back(NetworkBehaviourAction::NotifyHandler {
-			peer_id: target.clone(),
-			handler: NotifyHandler::Any,
-			event: OutboundProtocol {
-				request: buf,
-				block_hash,
-				max_response_size: self.config.max_response_len,
-				protocol: self.config.protocol.clone(),
-			},
-		});
-	}
-
-	/// Callback, invoked when a new finality request has been received from remote.
-	fn on_finality_request(&mut self, peer: &PeerId, request: &schema::v1::finality::FinalityProofRequest)
-		-> Result<schema::v1::finality::FinalityProofResponse, Error>
-	{
-		let block_hash = Decode::decode(&mut request.block_hash.as_ref())?;
-
-		log::trace!(target: "sync", "Finality proof request from {} for {}", peer, block_hash);
-
-		// Note that an empty Vec is sent if no proof is available.
-		let finality_proof = if let Some(provider) = &self.finality_proof_provider {
-			provider
-				.prove_finality(block_hash, &request.request)?
-				.unwrap_or_default()
-		} else {
-			log::error!("Answering a finality proof request while finality provider is empty");
-			return Err(From::from("Empty finality proof provider".to_string()))
-		};
-
-		Ok(schema::v1::finality::FinalityProofResponse { proof: finality_proof })
-	}
-}
-
-impl<B> NetworkBehaviour for FinalityProofRequests<B>
-where
-	B: Block
-{
-	type ProtocolsHandler = OneShotHandler<InboundProtocol<B>, OutboundProtocol<B>, NodeEvent<B, NegotiatedSubstream>>;
-	type OutEvent = Event<B>;
-
-	fn new_handler(&mut self) -> Self::ProtocolsHandler {
-		let p = InboundProtocol {
-			max_request_len: self.config.max_request_len,
-			protocol: if self.finality_proof_provider.is_some() {
-				Some(self.config.protocol.clone())
-			} else {
-				None
-			},
-			marker: PhantomData,
-		};
-		let mut cfg = OneShotHandlerConfig::default();
-		cfg.keep_alive_timeout = self.config.inactivity_timeout;
-		OneShotHandler::new(SubstreamProtocol::new(p), cfg)
-	}
-
-	fn addresses_of_peer(&mut self, _: &PeerId) -> Vec<Multiaddr> {
-		Vec::new()
-	}
-
-	fn inject_connected(&mut self, _peer: &PeerId) {
-	}
-
-	fn inject_disconnected(&mut self, _peer: &PeerId) {
-	}
-
-	fn inject_event(
-		&mut self,
-		peer: PeerId,
-		connection: ConnectionId,
-		event: NodeEvent<B, NegotiatedSubstream>
-	) {
-		match event {
-			NodeEvent::Request(request, mut stream) => {
-				match self.on_finality_request(&peer, &request) {
-					Ok(res) => {
-						log::trace!("enqueueing finality response for peer {}", peer);
-						let mut data = Vec::with_capacity(res.encoded_len());
-						if let Err(e) = res.encode(&mut data) {
-							log::debug!("error encoding finality response for peer {}: {}", peer, e)
-						} else {
-							let future = async move {
-								if let Err(e) = write_one(&mut stream, data).await {
-									log::debug!("error writing finality response: {}", e)
-								}
-							};
-							self.outgoing.push(future.boxed())
-						}
-					}
-					Err(e) => log::debug!("error handling finality request from peer {}: {}", peer, e)
+	/// Create a new [`FinalityProofRequestHandler`], along with the configuration of the
+	/// protocol to register on the network.
+	pub fn new(
+		protocol_id: &ProtocolId,
+		finality_proof_provider: Arc<dyn FinalityProofProvider<B>>,
+	) -> (Self, ProtocolConfig) {
+		let (tx, request_receiver) = mpsc::channel(INBOUND_QUEUE_SIZE);
+
+		let mut protocol_config = generate_protocol_config(protocol_id);
+		protocol_config.inbound_queue = Some(tx);
+
+		(FinalityProofRequestHandler { finality_proof_provider, request_receiver }, protocol_config)
+	}
+
+	/// Run the handler, answering the incoming requests until the network drops its side of
+	/// the queue.
+	pub async fn run(mut self) {
+		while let Some(IncomingRequest { peer, payload, pending_response }) = self.request_receiver.next().await {
+			match self.handle_request(&peer, &payload) {
+				Ok(response) => {
+					log::trace!(target: "sync", "Enqueueing finality response for peer {}", peer);
+					// The requester may no longer be interested in the response.
+					let _ = pending_response.send(response);
 				}
+				// Dropping `pending_response` refuses the request.
+				Err(e) => log::debug!(
+					target: "sync",
+					"Error handling finality request from peer {}: {}", peer, e
+				)
 			}
-			NodeEvent::Response(response, block_hash) => {
-				let ev = Event::Response {
-					peer,
-					block_hash,
-					proof: response.proof,
-				};
-				self.pending_events.push_back(NetworkBehaviourAction::GenerateEvent(ev));
-			}
-		}
-	}
-
-	fn poll(&mut self, cx: &mut Context, _: &mut impl PollParameters)
-		-> Poll<NetworkBehaviourAction<OutboundProtocol<B>, Event<B>>>
-	{
-		if let Some(ev) = self.pending_events.pop_front() {
-			return Poll::Ready(ev);
 		}
-
-		while let Poll::Ready(Some(_)) = self.outgoing.poll_next_unpin(cx) {}
-		Poll::Pending
 	}
-}
 
-/// Output type of inbound and outbound substream upgrades.
-#[derive(Debug)]
-pub enum NodeEvent<B: Block, T> {
-	/// Incoming request from remote and substream to use for the response.
-	Request(schema::v1::finality::FinalityProofRequest, T),
-	/// Incoming response from remote.
-	Response(schema::v1::finality::FinalityProofResponse, B::Hash),
-}
+	/// Decode a finality proof request, and build the encoded response.
+	fn handle_request(&self, peer: &PeerId, payload: &[u8]) -> Result<Vec<u8>, Error> {
+		let request = schema::v1::finality::FinalityProofRequest::decode(payload)?;
+		let block_hash: B::Hash = Decode::decode(&mut request.block_hash.as_ref())?;
 
-/// Substream upgrade protocol.
-///
-/// We attempt to parse an incoming protobuf encoded request (cf. `Request`)
-/// which will be handled by the `FinalityProofRequests` behaviour, i.e. the request
-/// will become visible via `inject_node_event` which then dispatches to the
-/// relevant callback to process the message and prepare a response.
-#[derive(Debug, Clone)]
-pub struct InboundProtocol<B> {
-	/// The max. request length in bytes.
-	max_request_len: usize,
-	/// The protocol to use during upgrade negotiation. If `None`, then the incoming protocol
-	/// is simply disabled.
-	protocol: Option<Bytes>,
-	/// Marker to pin the block type.
-	marker: PhantomData<B>,
-}
-
-impl<B: Block> UpgradeInfo for InboundProtocol<B> {
-	type Info = Bytes;
-	// This iterator will return either 0 elements if `self.protocol` is `None`, or 1 element if
-	// it is `Some`.
-	type InfoIter = std::option::IntoIter<Self::Info>;
-
-	fn protocol_info(&self) -> Self::InfoIter {
-		self.protocol.clone().into_iter()
-	}
-}
+		log::trace!(target: "sync", "Finality proof request from {} for {}", peer, block_hash);
 
-impl<B, T> InboundUpgrade<T> for InboundProtocol<B>
-where
-	B: Block,
-	T: AsyncRead + AsyncWrite + Unpin + Send + 'static
-{
-	type Output = NodeEvent<B, T>;
-	type Error = ReadOneError;
-	type Future = BoxFuture<'static, Result<Self::Output, Self::Error>>;
+		// Note that an empty Vec is sent if no proof is available.
+		let finality_proof = self.finality_proof_provider
+			.prove_finality(block_hash, &request.request)?
+			.unwrap_or_default();
+		let response = schema::v1::finality::FinalityProofResponse { proof: finality_proof };
 
-	fn upgrade_inbound(self, mut s: T, _: Self::Info) -> Self::Future {
-		async move {
-			let len = self.max_request_len;
-			let vec = read_one(&mut s, len).await?;
-			match schema::v1::finality::FinalityProofRequest::decode(&vec[..]) {
-				Ok(r) => Ok(NodeEvent::Request(r, s)),
-				Err(e) => Err(ReadOneError::Io(io::Error::new(io::ErrorKind::Other, e)))
-			}
-		}.boxed()
+		let mut data = Vec::with_capacity(response.encoded_len());
+		response.encode(&mut data)?;
+		Ok(data)
 	}
 }
 
-/// Substream upgrade protocol.
-///
-/// Sends a request to remote and awaits the response.
-#[derive(Debug, Clone)]
-pub struct OutboundProtocol<B: Block> {
-	/// The serialized protobuf request.
+/// Encode a finality proof request, to be sent with the finality proof request protocol.
+pub(crate) fn encode_finality_proof_request<Hash: Encode>(
+	block_hash: &Hash,
 	request: Vec<u8>,
-	/// Block hash that has been requested.
-	block_hash: B::Hash,
-	/// The max. response length in bytes.
-	max_response_size: usize,
-	/// The protocol to use for upgrade negotiation.
-	protocol: Bytes,
-}
-
-impl<B: Block> UpgradeInfo for OutboundProtocol<B> {
-	type Info = Bytes;
-	type InfoIter = iter::Once<Self::Info>;
-
-	fn protocol_info(&self) -> Self::InfoIter {
-		iter::once(self.protocol.clone())
-	}
+) -> Result<Vec<u8>, prost::EncodeError> {
+	let protobuf_rq = schema::v1::finality::FinalityProofRequest {
+		block_hash: block_hash.encode(),
+		request,
+	};
+
+	let mut buf = Vec::with_capacity(protobuf_rq.encoded_len());
+	protobuf_rq.encode(&mut buf)?;
+	Ok(buf)
 }
 
-impl<B, T> OutboundUpgrade<T> for OutboundProtocol<B>
-where
-	B: Block,
-	T: AsyncRead + AsyncWrite + Unpin + Send + 'static
-{
-	type Output = NodeEvent<B, T>;
-	type Error = ReadOneError;
-	type Future = BoxFuture<'static, Result<Self::Output, Self::Error>>;
-
-	fn upgrade_outbound(self, mut s: T, _: Self::Info) -> Self::Future {
-		async move {
-			write_one(&mut s, &self.request).await?;
-			let vec = read_one(&mut s, self.max_response_size).await?;
-
-			schema::v1::finality::FinalityProofResponse::decode(&vec[..])
-				.map(|r| NodeEvent::Response(r, self.block_hash))
-				.map_err(|e| {
-					ReadOneError::Io(io::Error::new(io::ErrorKind::Other, e))
-				})
-		}.boxed()
-	}
+/// Decode the response to a finality proof request for the block `block_hash`.
+pub(crate) fn decode_finality_proof_response<Hash>(
+	block_hash: Hash,
+	response: &[u8],
+) -> Result<message::FinalityProofResponse<Hash>, Error> {
+	let response = schema::v1::finality::FinalityProofResponse::decode(response)?;
+	Ok(message::FinalityProofResponse {
+		id: 0,
+		block: block_hash,
+		proof: if !response.proof.is_empty() {
+			Some(response.proof)
+		} else {
+			None
+		},
+	})
 }
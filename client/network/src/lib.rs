@@ -132,6 +132,17 @@
 //! light-client-related requests for information about the state. Each request is the encoding of
 //! a `light::Request` and each response is the encoding of a `light::Response`, as defined in the
 //! `light.v1.proto` file in this source tree.
+//! - **`/<protocol-id>/state/1`** is a request-response protocol (see below) that lets one
+//! download the state of a block in chunks, as done by fast sync. Each request is the encoding of
+//! a `state::StateRequest` and each response is the encoding of a `state::StateResponse`, as
+//! defined in the `state.v1.proto` file in this source tree.
+//! - **`/<protocol-id>/warp-sync/1`** is a request-response protocol (see below) that lets one
+//! download a proof of the latest finalized block, starting from a block of known finality. Each
+//! request is the encoding of a `warp::WarpSyncRequest` and each response is the encoding of a
+//! `warp::WarpSyncResponse`, as defined in the `warp.v1.proto` file in this source tree. The
+//! content of the proof is defined by the finality gadget.
+//! - Request-response protocols that are registered through the `request_response_protocols`
+//! field of the network configuration. See below for more information.
 //! - **`/<protocol-id>/transactions/1`** is a notifications protocol (see below) where
 //! transactions are pushed to other nodes. The handshake is empty on both sides. The message
 //! format is a SCALE-encoded list of transactions, where each transaction is an opaque list of
@@ -187,6 +198,12 @@
 //!
 //! Each request is performed in a new separate substream.
 //!
+//! In addition to the protocols above, additional request-response protocols can be registered
+//! through [`config::NetworkConfiguration::request_response_protocols`]. Requests on these
+//! protocols are sent with [`NetworkService::request`], and the incoming requests are delivered
+//! on the channel passed in their [`config::RequestResponseConfig`], along with a channel to send
+//! back the response.
+//!
 //! ## Notifications protocols
 //!
 //! A so-called notifications protocol is defined as follow:
@@ -249,13 +266,16 @@ mod chain;
 mod peer_info;
 mod discovery;
 mod finality_requests;
-mod light_client_handler;
+mod light_client_requests;
 mod on_demand_layer;
 mod protocol;
+mod request_responses;
 mod schema;
 mod service;
+mod state_requests;
 mod transport;
 mod utils;
+mod warp_sync_requests;
 
 pub mod config;
 pub mod error;
@@ -265,6 +285,7 @@ pub use service::{NetworkService, NetworkWorker};
 pub use protocol::PeerInfo;
 pub use protocol::event::{Event, DhtEvent, ObservedRole};
 pub use protocol::sync::SyncState;
+pub use request_responses::RequestFailure;
 pub use libp2p::{Multiaddr, PeerId};
 #[doc(inline)]
 pub use libp2p::multiaddr;
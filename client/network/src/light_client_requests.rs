@@ -0,0 +1,1590 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+//
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Light client requests, exchanged over the request-response protocol `/<id>/light/2`.
+//!
+//! [`LightClientRequestHandler`] answers the light client requests coming from remotes, while
+//! [`LightClientRequestSender`] sends out our own requests to suitable peers, checks their
+//! responses and retries them with other peers if needed. Requests for block bodies are sent over
+//! the block request protocol instead. Requests and responses are encoded as protocol buffers
+//! (cf. `api.v1.proto`).
+
+use codec::{self, Encode, Decode};
+use crate::{
+	block_requests::{self, build_protobuf_block_request},
+	chain::Client,
+	config::ProtocolId,
+	protocol::message::{BlockAttributes, Direction, FromBlock},
+	request_responses::{self, IncomingRequest, ProtocolConfig, RequestFailure},
+	schema,
+};
+use futures::{channel::{mpsc, oneshot}, future::BoxFuture, prelude::*, stream::FuturesUnordered};
+use libp2p::PeerId;
+use prost::Message;
+use sc_client_api::{
+	StorageProof,
+	light::{self, RemoteBodyRequest},
+};
+use sc_peerset::ReputationChange;
+use sp_core::{
+	storage::{ChildInfo, ChildType, StorageKey, PrefixedStorageKey},
+	hexdisplay::HexDisplay,
+};
+use sp_blockchain::{Error as ClientError};
+use sp_runtime::{
+	traits::{Block, Header, NumberFor, Zero},
+	generic::BlockId,
+};
+use std::{
+	borrow::Cow,
+	collections::{BTreeMap, VecDeque, HashMap},
+	pin::Pin,
+	sync::Arc,
+	time::Duration,
+	task::{Context, Poll},
+};
+use wasm_timer::Instant;
+
+/// Reputation change for a peer when a request timed out.
+pub(crate) const TIMEOUT_REPUTATION_CHANGE: i32 = -(1 << 8);
+
+/// Reputation change for a peer sending us a bad request.
+const BAD_REQUEST_REPUTATION_CHANGE: i32 = -(1 << 12);
+
+/// Number of incoming requests that can be queued before new ones are refused.
+const INBOUND_QUEUE_SIZE: usize = 20;
+
+/// Maximum number of local requests waiting for a peer to be sent to.
+const MAX_PENDING_REQUESTS: usize = 128;
+
+/// Time a local request may wait for a peer before the wait counts as a failed attempt.
+const PENDING_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Generates a [`ProtocolConfig`] for the light client request protocol, refusing incoming
+/// requests.
+pub fn generate_protocol_config(protocol_id: &ProtocolId) -> ProtocolConfig {
+	ProtocolConfig {
+		name: request_responses::protocol_name(protocol_id, b"/light/2"),
+		max_request_size: 1 * 1024 * 1024,
+		max_response_size: 16 * 1024 * 1024,
+		request_timeout: Duration::from_secs(15),
+		inbound_queue: None,
+	}
+}
+
+/// Possible errors while handling light clients.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// There are currently too many pending request.
+	#[error("too many pending requests")]
+	TooManyRequests,
+	/// The response type does not correspond to the issued request.
+	#[error("unexpected response")]
+	UnexpectedResponse,
+	/// A bad request has been received.
+	#[error("bad request: {0}")]
+	BadRequest(&'static str),
+	/// The chain client errored.
+	#[error("client error: {0}")]
+	Client(#[from] ClientError),
+	/// Encoding or decoding of some data failed.
+	#[error("codec error: {0}")]
+	Codec(#[from] codec::Error),
+	/// Decoding of a protocol buffer failed.
+	#[error("prost decode error: {0}")]
+	ProstDecode(#[from] prost::DecodeError),
+	/// Encoding of a protocol buffer failed.
+	#[error("prost encode error: {0}")]
+	ProstEncode(#[from] prost::EncodeError),
+}
+
+/// The possible light client requests we support.
+///
+/// The associated `oneshot::Sender` will be used to convey the result of
+/// their request back to them (cf. `Reply`).
+//
+// This is modeled after light_dispatch.rs's `RequestData` which is not
+// used because we currently only support a subset of those.
+#[derive(Debug)]
+pub enum Request<B: Block> {
+	Body {
+		request: RemoteBodyRequest<B::Header>,
+		sender: oneshot::Sender<Result<Vec<B::Extrinsic>, ClientError>>
+	},
+	Header {
+		request: light::RemoteHeaderRequest<B::Header>,
+		sender: oneshot::Sender<Result<B::Header, ClientError>>
+	},
+	Read {
+		request: light::RemoteReadRequest<B::Header>,
+		sender: oneshot::Sender<Result<HashMap<Vec<u8>, Option<Vec<u8>>>, ClientError>>
+	},
+	ReadChild {
+		request: light::RemoteReadChildRequest<B::Header>,
+		sender: oneshot::Sender<Result<HashMap<Vec<u8>, Option<Vec<u8>>>, ClientError>>
+	},
+	Call {
+		request: light::RemoteCallRequest<B::Header>,
+		sender: oneshot::Sender<Result<Vec<u8>, ClientError>>
+	},
+	Changes {
+		request: light::RemoteChangesRequest<B::Header>,
+		sender: oneshot::Sender<Result<Vec<(NumberFor<B>, u32)>, ClientError>>
+	}
+}
+
+/// The data to send back to the light client over the oneshot channel.
+//
+// It is unified here in order to be able to return it as a function
+// result instead of delivering it to the client as a side effect of
+// response processing.
+#[derive(Debug)]
+enum Reply<B: Block> {
+	VecU8(Vec<u8>),
+	VecNumberU32(Vec<(<B::Header as Header>::Number, u32)>),
+	MapVecU8OptVecU8(HashMap<Vec<u8>, Option<Vec<u8>>>),
+	Header(B::Header),
+	Extrinsics(Vec<B::Extrinsic>),
+}
+
+/// Handler for incoming light client requests from a remote peer.
+pub struct LightClientRequestHandler<B: Block> {
+	/// Blockchain client.
+	chain: Arc<dyn Client<B>>,
+	/// Incoming requests, sent by the network.
+	request_receiver: mpsc::Receiver<IncomingRequest>,
+	/// Handle to use for reporting misbehaviour of peers.
+	peerset: sc_peerset::PeersetHandle,
+}
+
+impl<B> LightClientRequestHandler<B>
+where
+	B: Block,
+{
+	/// Create a new [`LightClientRequestHandler`], along with the configuration of the protocol
+	/// to register on the network.
+	pub fn new(
+		protocol_id: &ProtocolId,
+		chain: Arc<dyn Client<B>>,
+		peerset: sc_peerset::PeersetHandle,
+	) -> (Self, ProtocolConfig) {
+		let (tx, request_receiver) = mpsc::channel(INBOUND_QUEUE_SIZE);
+
+		let mut protocol_config = generate_protocol_config(protocol_id);
+		protocol_config.inbound_queue = Some(tx);
+
+		(LightClientRequestHandler { chain, request_receiver, peerset }, protocol_config)
+	}
+
+	/// Run the handler, answering the incoming requests until the network drops its side of
+	/// the queue.
+	pub async fn run(mut self) {
+		while let Some(IncomingRequest { peer, payload, pending_response }) = self.request_receiver.next().await {
+			match self.handle_request(&peer, &payload) {
+				Ok(response) => {
+					log::trace!("enqueueing response for peer {}", peer);
+					// The requester may no longer be interested in the response.
+					let _ = pending_response.send(response);
+				}
+				// Dropping `pending_response` refuses the request.
+				Err(Error::BadRequest(_)) => self.peerset.report_peer(
+					peer,
+					ReputationChange::new(BAD_REQUEST_REPUTATION_CHANGE, "bad request"),
+				),
+				Err(e) => log::debug!("error handling request from peer {}: {}", peer, e),
+			}
+		}
+	}
+
+	/// Decode a light client request, and build the encoded response.
+	fn handle_request(&self, peer: &PeerId, payload: &[u8]) -> Result<Vec<u8>, Error> {
+		log::trace!("incoming request from {}", peer);
+		let request = schema::v1::light::Request::decode(payload)?;
+		let response = match &request.request {
+			Some(schema::v1::light::request::Request::RemoteCallRequest(r)) =>
+				self.on_remote_call_request(peer, r)?,
+			Some(schema::v1::light::request::Request::RemoteReadRequest(r)) =>
+				self.on_remote_read_request(peer, r)?,
+			Some(schema::v1::light::request::Request::RemoteHeaderRequest(r)) =>
+				self.on_remote_header_request(peer, r)?,
+			Some(schema::v1::light::request::Request::RemoteReadChildRequest(r)) =>
+				self.on_remote_read_child_request(peer, r)?,
+			Some(schema::v1::light::request::Request::RemoteChangesRequest(r)) =>
+				self.on_remote_changes_request(peer, r)?,
+			None => {
+				log::debug!("ignoring request without request data from peer {}", peer);
+				return Err(Error::BadRequest("request without request data"))
+			}
+		};
+
+		let mut data = Vec::with_capacity(response.encoded_len());
+		response.encode(&mut data)?;
+		Ok(data)
+	}
+
+	fn on_remote_call_request
+		( &self
+		, peer: &PeerId
+		, request: &schema::v1::light::RemoteCallRequest
+		) -> Result<schema::v1::light::Response, Error>
+	{
+		log::trace!("remote call request from {} ({} at {:?})",
+			peer,
+			request.method,
+			request.block,
+		);
+
+		let block = Decode::decode(&mut request.block.as_ref())?;
+
+		let proof = match self.chain.execution_proof(&BlockId::Hash(block), &request.method, &request.data) {
+			Ok((_, proof)) => proof,
+			Err(e) => {
+				log::trace!("remote call request from {} ({} at {:?}) failed with: {}",
+					peer,
+					request.method,
+					request.block,
+					e,
+				);
+				StorageProof::empty()
+			}
+		};
+
+		let response = {
+			let r = schema::v1::light::RemoteCallResponse { proof: proof.encode() };
+			schema::v1::light::response::Response::RemoteCallResponse(r)
+		};
+
+		Ok(schema::v1::light::Response { response: Some(response) })
+	}
+
+	fn on_remote_read_request
+		( &self
+		, peer: &PeerId
+		, request: &schema::v1::light::RemoteReadRequest
+		) -> Result<schema::v1::light::Response, Error>
+	{
+		if request.keys.is_empty() {
+			log::debug!("invalid remote read request sent by {}", peer);
+			return Err(Error::BadRequest("remote read request without keys"))
+		}
+
+		log::trace!("remote read request from {} ({} at {:?})",
+			peer,
+			fmt_keys(request.keys.first(), request.keys.last()),
+			request.block);
+
+		let block = Decode::decode(&mut request.block.as_ref())?;
+
+		let proof = match self.chain.read_proof(&BlockId::Hash(block), &mut request.keys.iter().map(AsRef::as_ref)) {
+			Ok(proof) => proof,
+			Err(error) => {
+				log::trace!("remote read request from {} ({} at {:?}) failed with: {}",
+					peer,
+					fmt_keys(request.keys.first(), request.keys.last()),
+					request.block,
+					error);
+				StorageProof::empty()
+			}
+		};
+
+		let response = {
+			let r = schema::v1::light::RemoteReadResponse { proof: proof.encode() };
+			schema::v1::light::response::Response::RemoteReadResponse(r)
+		};
+
+		Ok(schema::v1::light::Response { response: Some(response) })
+	}
+
+	fn on_remote_read_child_request
+		( &self
+		, peer: &PeerId
+		, request: &schema::v1::light::RemoteReadChildRequest
+		) -> Result<schema::v1::light::Response, Error>
+	{
+		if request.keys.is_empty() {
+			log::debug!("invalid remote child read request sent by {}", peer);
+			return Err(Error::BadRequest("remove read child request without keys"))
+		}
+
+		log::trace!("remote read child request from {} ({} {} at {:?})",
+			peer,
+			HexDisplay::from(&request.storage_key),
+			fmt_keys(request.keys.first(), request.keys.last()),
+			request.block);
+
+		let block = Decode::decode(&mut request.block.as_ref())?;
+
+		let prefixed_key = PrefixedStorageKey::new_ref(&request.storage_key);
+		let child_info = match ChildType::from_prefixed_key(prefixed_key) {
+			Some((ChildType::ParentKeyId, storage_key)) => Ok(ChildInfo::new_default(storage_key)),
+			None => Err("Invalid child storage key".into()),
+		};
+		let proof = match child_info.and_then(|child_info| self.chain.read_child_proof(
+			&BlockId::Hash(block),
+			&child_info,
+			&mut request.keys.iter().map(AsRef::as_ref)
+		)) {
+			Ok(proof) => proof,
+			Err(error) => {
+				log::trace!("remote read child request from {} ({} {} at {:?}) failed with: {}",
+					peer,
+					HexDisplay::from(&request.storage_key),
+					fmt_keys(request.keys.first(), request.keys.last()),
+					request.block,
+					error);
+				StorageProof::empty()
+			}
+		};
+
+		let response = {
+			let r = schema::v1::light::RemoteReadResponse { proof: proof.encode() };
+			schema::v1::light::response::Response::RemoteReadResponse(r)
+		};
+
+		Ok(schema::v1::light::Response { response: Some(response) })
+	}
+
+	fn on_remote_header_request
+		( &self
+		, peer: &PeerId
+		, request: &schema::v1::light::RemoteHeaderRequest
+		) -> Result<schema::v1::light::Response, Error>
+	{
+		log::trace!("remote header proof request from {} ({:?})", peer, request.block);
+
+		let block = Decode::decode(&mut request.block.as_ref())?;
+		let (header, proof) = match self.chain.header_proof(&BlockId::Number(block)) {
+			Ok((header, proof)) => (header.encode(), proof),
+			Err(error) => {
+				log::trace!("remote header proof request from {} ({:?}) failed with: {}",
+					peer,
+					request.block,
+					error);
+				(Default::default(), StorageProof::empty())
+			}
+		};
+
+		let response = {
+			let r = schema::v1::light::RemoteHeaderResponse { header, proof: proof.encode() };
+			schema::v1::light::response::Response::RemoteHeaderResponse(r)
+		};
+
+		Ok(schema::v1::light::Response { response: Some(response) })
+	}
+
+	fn on_remote_changes_request
+		( &self
+		, peer: &PeerId
+		, request: &schema::v1::light::RemoteChangesRequest
+		) -> Result<schema::v1::light::Response, Error>
+	{
+		log::trace!("remote changes proof request from {} for key {} ({:?}..{:?})",
+			peer,
+			if !request.storage_key.is_empty() {
+				format!("{} : {}", HexDisplay::from(&request.storage_key), HexDisplay::from(&request.key))
+			} else {
+				HexDisplay::from(&request.key).to_string()
+			},
+			request.first,
+			request.last);
+
+		let first = Decode::decode(&mut request.first.as_ref())?;
+		let last = Decode::decode(&mut request.last.as_ref())?;
+		let min = Decode::decode(&mut request.min.as_ref())?;
+		let max = Decode::decode(&mut request.max.as_ref())?;
+		let key = StorageKey(request.key.clone());
+		let storage_key = if request.storage_key.is_empty() {
+			None
+		} else {
+			Some(PrefixedStorageKey::new_ref(&request.storage_key))
+		};
+
+		let proof = match self.chain.key_changes_proof(first, last, min, max, storage_key, &key) {
+			Ok(proof) => proof,
+			Err(error) => {
+				log::trace!("remote changes proof request from {} for key {} ({:?}..{:?}) failed with: {}",
+					peer,
+					format!("{} : {}", HexDisplay::from(&request.storage_key), HexDisplay::from(&key.0)),
+					request.first,
+					request.last,
+					error);
+
+				light::ChangesProof::<B::Header> {
+					max_block: Zero::zero(),
+					proof: Vec::new(),
+					roots: BTreeMap::new(),
+					roots_proof: StorageProof::empty(),
+				}
+			}
+		};
+
+		let response = {
+			let r = schema::v1::light::RemoteChangesResponse {
+				max: proof.max_block.encode(),
+				proof: proof.proof,
+				roots: proof.roots.into_iter()
+					.map(|(k, v)| schema::v1::light::Pair { fst: k.encode(), snd: v.encode() })
+					.collect(),
+				roots_proof: proof.roots_proof.encode(),
+			};
+			schema::v1::light::response::Response::RemoteChangesResponse(r)
+		};
+
+		Ok(schema::v1::light::Response { response: Some(response) })
+	}
+}
+
+/// Event generated by the [`LightClientRequestSender`].
+#[derive(Debug)]
+pub enum OutEvent {
+	/// Emit a request to be sent out on the network, e.g. through the request-response
+	/// protocols.
+	SendRequest {
+		/// The peer to send the request to.
+		target: PeerId,
+		/// The encoded request.
+		request: Vec<u8>,
+		/// Where to send the response, or the reason why the request failed.
+		pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+		/// The protocol to send the request on.
+		protocol_name: Cow<'static, [u8]>,
+	},
+}
+
+/// A local request waiting for a peer to be sent to.
+#[derive(Debug)]
+struct PendingRequest<B: Block> {
+	/// Time when the request started waiting.
+	timestamp: Instant,
+	/// Remaining retries.
+	retries: usize,
+	/// The actual request.
+	request: Request<B>,
+}
+
+impl<B: Block> PendingRequest<B> {
+	fn new(request: Request<B>, retries: usize) -> Self {
+		PendingRequest { timestamp: Instant::now(), retries, request }
+	}
+}
+
+/// A local request sent to a peer, waiting for its response.
+#[derive(Debug)]
+struct SentRequest<B: Block> {
+	/// The peer the request was sent to.
+	peer: PeerId,
+	/// Remaining retries.
+	retries: usize,
+	/// The actual request.
+	request: Request<B>,
+}
+
+/// Information we have about some peer.
+#[derive(Debug)]
+struct PeerInfo<B: Block> {
+	best_block: Option<NumberFor<B>>,
+	status: PeerStatus,
+}
+
+impl<B: Block> Default for PeerInfo<B> {
+	fn default() -> Self {
+		PeerInfo {
+			best_block: None,
+			status: PeerStatus::Idle,
+		}
+	}
+}
+
+/// A peer is either idle or busy processing a request from us.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PeerStatus {
+	/// The peer is available.
+	Idle,
+	/// We wait for the peer to return us a response.
+	Busy,
+}
+
+/// Sends out the local light client requests, and checks their responses.
+///
+/// The requests are emitted as [`OutEvent::SendRequest`] by polling the sender as a `Stream`,
+/// and must be sent out on the network by the caller.
+pub struct LightClientRequestSender<B: Block> {
+	/// Verifies that received responses are correct.
+	checker: Arc<dyn light::FetchChecker<B>>,
+	/// Peer information (their best block and whether they are busy with a request from us).
+	peers: HashMap<PeerId, PeerInfo<B>>,
+	/// Local requests waiting for a peer.
+	pending_requests: VecDeque<PendingRequest<B>>,
+	/// Requests on their way to remote peers, resolving with their response.
+	sent_requests: FuturesUnordered<BoxFuture<
+		'static,
+		(SentRequest<B>, Result<Result<Vec<u8>, RequestFailure>, oneshot::Canceled>),
+	>>,
+	/// Protocol to send the light client requests on.
+	light_protocol: Cow<'static, [u8]>,
+	/// Protocol to send the body requests on.
+	block_protocol: Cow<'static, [u8]>,
+	/// Handle to use for reporting misbehaviour of peers.
+	peerset: sc_peerset::PeersetHandle,
+}
+
+impl<B> LightClientRequestSender<B>
+where
+	B: Block,
+{
+	/// Construct a new light client request sender.
+	pub fn new(
+		protocol_id: &ProtocolId,
+		checker: Arc<dyn light::FetchChecker<B>>,
+		peerset: sc_peerset::PeersetHandle,
+	) -> Self {
+		LightClientRequestSender {
+			checker,
+			peers: HashMap::new(),
+			pending_requests: VecDeque::new(),
+			sent_requests: FuturesUnordered::new(),
+			light_protocol: generate_protocol_config(protocol_id).name,
+			block_protocol: block_requests::generate_protocol_config(protocol_id).name,
+			peerset,
+		}
+	}
+
+	/// We rely on external information about peers best blocks as we lack the
+	/// means to determine it ourselves.
+	pub fn update_best_block(&mut self, peer: &PeerId, num: NumberFor<B>) {
+		if let Some(info) = self.peers.get_mut(peer) {
+			log::trace!("new best block for {:?}: {:?}", peer, num);
+			info.best_block = Some(num)
+		}
+	}
+
+	/// Issue a new light client request.
+	pub fn request(&mut self, req: Request<B>) -> Result<(), Error> {
+		if self.pending_requests.len() >= MAX_PENDING_REQUESTS {
+			return Err(Error::TooManyRequests)
+		}
+		let retries = retries(&req);
+		self.pending_requests.push_back(PendingRequest::new(req, retries));
+		Ok(())
+	}
+
+	/// Make a peer available for our requests.
+	pub fn inject_connected(&mut self, peer: PeerId) {
+		log::trace!("peer {} connected", peer);
+		self.peers.entry(peer).or_default();
+	}
+
+	/// Stop sending requests to the given peer.
+	///
+	/// A request in flight to this peer will fail on its own, and then be sent to another peer.
+	pub fn inject_disconnected(&mut self, peer: &PeerId) {
+		log::trace!("peer {} disconnected", peer);
+		self.remove_peer(peer)
+	}
+
+	/// Remove the given peer.
+	fn remove_peer(&mut self, peer: &PeerId) {
+		self.peers.remove(peer);
+	}
+
+	/// Selects a suitable idle peer to send a request for the block `number` to.
+	fn find_peer(&self, number: NumberFor<B>) -> Option<PeerId> {
+		let mut peer = None;
+		for (peer_id, peer_info) in self.peers.iter() {
+			if peer_info.status == PeerStatus::Idle {
+				match peer_info.best_block {
+					Some(n) => if n >= number {
+						peer = Some(peer_id);
+						break
+					},
+					None => peer = Some(peer_id)
+				}
+			}
+		}
+		peer.cloned()
+	}
+
+	/// Queue `request` again if it has retries left, or report its failure otherwise.
+	fn retry(&mut self, request: Request<B>, retries: usize) {
+		if retries > 0 {
+			self.pending_requests.push_back(PendingRequest::new(request, retries - 1))
+		} else {
+			send_reply(Err(ClientError::RemoteFetchFailed), request)
+		}
+	}
+
+	/// Process the outcome of a request sent to a remote.
+	///
+	/// Sends the checked reply back to the client, or sends the request again to another peer.
+	fn on_request_outcome(
+		&mut self,
+		SentRequest { peer, retries, request }: SentRequest<B>,
+		outcome: Result<Vec<u8>, RequestFailure>,
+	) {
+		if let Some(info) = self.peers.get_mut(&peer) {
+			info.status = PeerStatus::Idle; // Make peer available again.
+		}
+
+		let response = match outcome {
+			Ok(response) => response,
+			Err(RequestFailure::NotConnected) | Err(RequestFailure::Refused) => {
+				// The peer went away, which doesn't count as a failed attempt.
+				log::debug!("light request to {} dropped", peer);
+				self.remove_peer(&peer);
+				self.pending_requests.push_back(PendingRequest::new(request, retries));
+				return
+			}
+			Err(RequestFailure::Timeout) => {
+				log::debug!("light request to {} timed out", peer);
+				self.remove_peer(&peer);
+				self.peerset.report_peer(
+					peer,
+					ReputationChange::new(TIMEOUT_REPUTATION_CHANGE, "light request timeout"),
+				);
+				self.retry(request, retries);
+				return
+			}
+			Err(RequestFailure::UnknownProtocol) => {
+				log::error!("light request protocols are not registered");
+				send_reply(Err(ClientError::RemoteFetchFailed), request);
+				return
+			}
+		};
+
+		log::trace!("response from {}", peer);
+		match self.on_response(&request, &response) {
+			Ok(reply) => send_reply(Ok(reply), request),
+			Err(Error::UnexpectedResponse) => {
+				log::debug!("unexpected response from peer {}", peer);
+				self.remove_peer(&peer);
+				self.peerset.report_peer(peer, ReputationChange::new_fatal("unexpected response from peer"));
+				self.pending_requests.push_back(PendingRequest::new(request, retries));
+			}
+			Err(other) => {
+				log::debug!("error handling response from peer {}: {}", peer, other);
+				self.remove_peer(&peer);
+				self.peerset.report_peer(peer, ReputationChange::new_fatal("invalid response from peer"));
+				self.retry(request, retries);
+			}
+		}
+	}
+
+	/// Process a local request's response from remote.
+	///
+	/// If successful, this will give us the actual, checked data we should be
+	/// sending back to the client, otherwise an error.
+	fn on_response(&self, request: &Request<B>, response: &[u8]) -> Result<Reply<B>, Error> {
+		match request {
+			Request::Body { .. } =>
+				self.on_response_block(request, schema::v1::BlockResponse::decode(response)?),
+			_ =>
+				self.on_response_light(request, schema::v1::light::Response::decode(response)?),
+		}
+	}
+
+	fn on_response_light
+		( &self
+		, request: &Request<B>
+		, response: schema::v1::light::Response
+		) -> Result<Reply<B>, Error>
+	{
+		use schema::v1::light::response::Response;
+		match response.response {
+			Some(Response::RemoteCallResponse(response)) =>
+				if let Request::Call { request , .. } = request {
+					let proof = Decode::decode(&mut response.proof.as_ref())?;
+					let reply = self.checker.check_execution_proof(request, proof)?;
+					Ok(Reply::VecU8(reply))
+				} else {
+					Err(Error::UnexpectedResponse)
+				}
+			Some(Response::RemoteReadResponse(response)) =>
+				match request {
+					Request::Read { request, .. } => {
+						let proof = Decode::decode(&mut response.proof.as_ref())?;
+						let reply = self.checker.check_read_proof(&request, proof)?;
+						Ok(Reply::MapVecU8OptVecU8(reply))
+					}
+					Request::ReadChild { request, .. } => {
+						let proof = Decode::decode(&mut response.proof.as_ref())?;
+						let reply = self.checker.check_read_child_proof(&request, proof)?;
+						Ok(Reply::MapVecU8OptVecU8(reply))
+					}
+					_ => Err(Error::UnexpectedResponse)
+				}
+			Some(Response::RemoteChangesResponse(response)) =>
+				if let Request::Changes { request, .. } = request {
+					let max_block = Decode::decode(&mut response.max.as_ref())?;
+					let roots_proof = Decode::decode(&mut response.roots_proof.as_ref())?;
+					let roots = {
+						let mut r = BTreeMap::new();
+						for pair in response.roots {
+							let k = Decode::decode(&mut pair.fst.as_ref())?;
+							let v = Decode::decode(&mut pair.snd.as_ref())?;
+							r.insert(k, v);
+						}
+						r
+					};
+					let reply = self.checker.check_changes_proof(&request, light::ChangesProof {
+						max_block,
+						proof: response.proof,
+						roots,
+						roots_proof,
+					})?;
+					Ok(Reply::VecNumberU32(reply))
+				} else {
+					Err(Error::UnexpectedResponse)
+				}
+			Some(Response::RemoteHeaderResponse(response)) =>
+				if let Request::Header { request, .. } = request {
+					let header =
+						if response.header.is_empty() {
+							None
+						} else {
+							Some(Decode::decode(&mut response.header.as_ref())?)
+						};
+					let proof = Decode::decode(&mut response.proof.as_ref())?;
+					let reply = self.checker.check_header_proof(&request, header, proof)?;
+					Ok(Reply::Header(reply))
+				} else {
+					Err(Error::UnexpectedResponse)
+				}
+			None => Err(Error::UnexpectedResponse)
+		}
+	}
+
+	fn on_response_block
+		( &self
+		, request: &Request<B>
+		, response: schema::v1::BlockResponse
+		) -> Result<Reply<B>, Error>
+	{
+		let request = if let Request::Body { request , .. } = &request {
+			request
+		} else {
+			return Err(Error::UnexpectedResponse);
+		};
+
+		let body: Vec<_> = match response.blocks.into_iter().next() {
+			Some(b) => b.body,
+			None => return Err(Error::UnexpectedResponse),
+		};
+
+		let body = body.into_iter()
+			.map(|extrinsic| B::Extrinsic::decode(&mut &extrinsic[..]))
+			.collect::<Result<_, _>>()?;
+
+		let body = self.checker.check_body_proof(&request, body)?;
+		Ok(Reply::Extrinsics(body))
+	}
+}
+
+impl<B> Stream for LightClientRequestSender<B>
+where
+	B: Block,
+{
+	type Item = OutEvent;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+		// Process the responses to the requests sent out earlier.
+		while let Poll::Ready(Some((sent, outcome))) = self.sent_requests.poll_next_unpin(cx) {
+			// The network only drops `pending_response` once the request is over.
+			let outcome = outcome.unwrap_or(Err(RequestFailure::Refused));
+			self.on_request_outcome(sent, outcome);
+		}
+
+		// If we have a pending request to send, try to find an available peer and send it.
+		let now = Instant::now();
+		while let Some(mut pending) = self.pending_requests.pop_front() {
+			if now > pending.timestamp + PENDING_REQUEST_TIMEOUT {
+				if pending.retries == 0 {
+					send_reply(Err(ClientError::RemoteFetchFailed), pending.request);
+					continue
+				}
+				pending.timestamp = Instant::now();
+				pending.retries -= 1
+			}
+
+			let peer = match self.find_peer(required_block(&pending.request)) {
+				Some(peer) => peer,
+				None => {
+					self.pending_requests.push_front(pending);
+					log::debug!("no peer available to send request to");
+					break
+				}
+			};
+
+			let request_bytes = match serialize_request(&pending.request) {
+				Ok(bytes) => bytes,
+				Err(error) => {
+					log::debug!("failed to serialize request: {}", error);
+					send_reply(Err(ClientError::RemoteFetchFailed), pending.request);
+					continue
+				}
+			};
+
+			let protocol_name = match pending.request {
+				Request::Body { .. } => self.block_protocol.clone(),
+				_ => self.light_protocol.clone(),
+			};
+
+			if let Some(info) = self.peers.get_mut(&peer) {
+				info.status = PeerStatus::Busy;
+			}
+
+			let (tx, rx) = oneshot::channel();
+			let sent = SentRequest { peer: peer.clone(), retries: pending.retries, request: pending.request };
+			self.sent_requests.push(async move { (sent, rx.await) }.boxed());
+
+			log::trace!("sending request to peer {}", peer);
+
+			return Poll::Ready(Some(OutEvent::SendRequest {
+				target: peer,
+				request: request_bytes,
+				pending_response: tx,
+				protocol_name,
+			}))
+		}
+
+		Poll::Pending
+	}
+}
+
+fn required_block<B: Block>(request: &Request<B>) -> NumberFor<B> {
+	match request {
+		Request::Body { request, .. } => *request.header.number(),
+		Request::Header { request, .. } => request.block,
+		Request::Read { request, .. } => *request.header.number(),
+		Request::ReadChild { request, .. } => *request.header.number(),
+		Request::Call { request, .. } => *request.header.number(),
+		Request::Changes { request, .. } => request.max_block.0,
+	}
+}
+
+fn retries<B: Block>(request: &Request<B>) -> usize {
+	let rc = match request {
+		Request::Body { request, .. } => request.retry_count,
+		Request::Header { request, .. } => request.retry_count,
+		Request::Read { request, .. } => request.retry_count,
+		Request::ReadChild { request, .. } => request.retry_count,
+		Request::Call { request, .. } => request.retry_count,
+		Request::Changes { request, .. } => request.retry_count,
+	};
+	rc.unwrap_or(0)
+}
+
+fn serialize_request<B: Block>(request: &Request<B>) -> Result<Vec<u8>, prost::EncodeError> {
+	let request = match request {
+		Request::Body { request, .. } => {
+			let rq = build_protobuf_block_request::<_, NumberFor<B>>(
+				BlockAttributes::BODY,
+				FromBlock::Hash(request.header.hash()),
+				None,
+				Direction::Ascending,
+				Some(1),
+			);
+			let mut buf = Vec::with_capacity(rq.encoded_len());
+			rq.encode(&mut buf)?;
+			return Ok(buf);
+		}
+		Request::Header { request, .. } => {
+			let r = schema::v1::light::RemoteHeaderRequest { block: request.block.encode() };
+			schema::v1::light::request::Request::RemoteHeaderRequest(r)
+		}
+		Request::Read { request, .. } => {
+			let r = schema::v1::light::RemoteReadRequest {
+				block: request.block.encode(),
+				keys: request.keys.clone(),
+			};
+			schema::v1::light::request::Request::RemoteReadRequest(r)
+		}
+		Request::ReadChild { request, .. } => {
+			let r = schema::v1::light::RemoteReadChildRequest {
+				block: request.block.encode(),
+				storage_key: request.storage_key.clone().into_inner(),
+				keys: request.keys.clone(),
+			};
+			schema::v1::light::request::Request::RemoteReadChildRequest(r)
+		}
+		Request::Call { request, .. } => {
+			let r = schema::v1::light::RemoteCallRequest {
+				block: request.block.encode(),
+				method: request.method.clone(),
+				data: request.call_data.clone(),
+			};
+			schema::v1::light::request::Request::RemoteCallRequest(r)
+		}
+		Request::Changes { request, .. } => {
+			let r = schema::v1::light::RemoteChangesRequest {
+				first: request.first_block.1.encode(),
+				last: request.last_block.1.encode(),
+				min: request.tries_roots.1.encode(),
+				max: request.max_block.1.encode(),
+				storage_key: request.storage_key.clone().map(|s| s.into_inner())
+					.unwrap_or_default(),
+				key: request.key.clone(),
+			};
+			schema::v1::light::request::Request::RemoteChangesRequest(r)
+		}
+	};
+
+	let rq = schema::v1::light::Request { request: Some(request) };
+	let mut buf = Vec::with_capacity(rq.encoded_len());
+	rq.encode(&mut buf)?;
+	Ok(buf)
+}
+
+fn send_reply<B: Block>(result: Result<Reply<B>, ClientError>, request: Request<B>) {
+	fn send<T>(item: T, sender: oneshot::Sender<T>) {
+		let _ = sender.send(item); // It is okay if the other end already hung up.
+	}
+	match request {
+		Request::Body { request, sender } => match result {
+			Err(e) => send(Err(e), sender),
+			Ok(Reply::Extrinsics(x)) => send(Ok(x), sender),
+			reply => log::error!("invalid reply for body request: {:?}, {:?}", reply, request),
+		}
+		Request::Header { request, sender } => match result {
+			Err(e) => send(Err(e), sender),
+			Ok(Reply::Header(x)) => send(Ok(x), sender),
+			reply => log::error!("invalid reply for header request: {:?}, {:?}", reply, request),
+		}
+		Request::Read { request, sender } => match result {
+			Err(e) => send(Err(e), sender),
+			Ok(Reply::MapVecU8OptVecU8(x)) => send(Ok(x), sender),
+			reply => log::error!("invalid reply for read request: {:?}, {:?}", reply, request),
+		}
+		Request::ReadChild { request, sender } => match result {
+			Err(e) => send(Err(e), sender),
+			Ok(Reply::MapVecU8OptVecU8(x)) => send(Ok(x), sender),
+			reply => log::error!("invalid reply for read child request: {:?}, {:?}", reply, request),
+		}
+		Request::Call { request, sender } => match result {
+			Err(e) => send(Err(e), sender),
+			Ok(Reply::VecU8(x)) => send(Ok(x), sender),
+			reply => log::error!("invalid reply for call request: {:?}, {:?}", reply, request),
+		}
+		Request::Changes { request, sender } => match result {
+			Err(e) => send(Err(e), sender),
+			Ok(Reply::VecNumberU32(x)) => send(Ok(x), sender),
+			reply => log::error!("invalid reply for changes request: {:?}, {:?}", reply, request),
+		}
+	}
+}
+
+fn fmt_keys(first: Option<&Vec<u8>>, last: Option<&Vec<u8>>) -> String {
+	if let (Some(first), Some(last)) = (first, last) {
+		if first == last {
+			HexDisplay::from(first).to_string()
+		} else {
+			format!("{}..{}", HexDisplay::from(first), HexDisplay::from(last))
+		}
+	} else {
+		String::from("n/a")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use async_std::task;
+	use assert_matches::assert_matches;
+	use sc_client_api::{
+		ChangesProof, RemoteCallRequest, RemoteChangesRequest, RemoteHeaderRequest,
+		RemoteReadChildRequest, RemoteReadRequest,
+	};
+	use sp_runtime::{generic::Header, traits::{BlakeTwo256, Block as BlockT}};
+	use std::{collections::HashSet, iter::{self, FromIterator}};
+
+	type Block = sp_runtime::generic::Block<Header<u64, BlakeTwo256>, substrate_test_runtime::Extrinsic>;
+	type Sender = LightClientRequestSender<Block>;
+
+	fn empty_proof() -> Vec<u8> {
+		StorageProof::empty().encode()
+	}
+
+	fn protocol_id() -> ProtocolId {
+		ProtocolId::from(&b"foo"[..])
+	}
+
+	struct DummyFetchChecker<B> {
+		ok: bool,
+		_mark: std::marker::PhantomData<B>
+	}
+
+	impl<B: BlockT> light::FetchChecker<B> for DummyFetchChecker<B> {
+		fn check_header_proof(
+			&self,
+			_request: &RemoteHeaderRequest<B::Header>,
+			header: Option<B::Header>,
+			_remote_proof: StorageProof,
+		) -> Result<B::Header, ClientError> {
+			match self.ok {
+				true if header.is_some() => Ok(header.unwrap()),
+				_ => Err(ClientError::Backend("Test error".into())),
+			}
+		}
+
+		fn check_read_proof(
+			&self,
+			request: &RemoteReadRequest<B::Header>,
+			_: StorageProof,
+		) -> Result<HashMap<Vec<u8>, Option<Vec<u8>>>, ClientError> {
+			match self.ok {
+				true => Ok(request.keys
+					.iter()
+					.cloned()
+					.map(|k| (k, Some(vec![42])))
+					.collect()
+				),
+				false => Err(ClientError::Backend("Test error".into())),
+			}
+		}
+
+		fn check_read_child_proof(
+			&self,
+			request: &RemoteReadChildRequest<B::Header>,
+			_: StorageProof,
+		) -> Result<HashMap<Vec<u8>, Option<Vec<u8>>>, ClientError> {
+			match self.ok {
+				true => Ok(request.keys
+					.iter()
+					.cloned()
+					.map(|k| (k, Some(vec![42])))
+					.collect()
+				),
+				false => Err(ClientError::Backend("Test error".into())),
+			}
+		}
+
+		fn check_execution_proof(
+			&self,
+			_: &RemoteCallRequest<B::Header>,
+			_: StorageProof,
+		) -> Result<Vec<u8>, ClientError> {
+			match self.ok {
+				true => Ok(vec![42]),
+				false => Err(ClientError::Backend("Test error".into())),
+			}
+		}
+
+		fn check_changes_proof(
+			&self,
+			_: &RemoteChangesRequest<B::Header>,
+			_: ChangesProof<B::Header>
+		) -> Result<Vec<(NumberFor<B>, u32)>, ClientError> {
+			match self.ok {
+				true => Ok(vec![(100.into(), 2)]),
+				false => Err(ClientError::Backend("Test error".into())),
+			}
+		}
+
+		fn check_body_proof(
+			&self,
+			_: &RemoteBodyRequest<B::Header>,
+			body: Vec<B::Extrinsic>
+		) -> Result<Vec<B::Extrinsic>, ClientError> {
+			match self.ok {
+				true => Ok(body),
+				false => Err(ClientError::Backend("Test error".into())),
+			}
+		}
+	}
+
+	fn dummy_header() -> sp_test_primitives::Header {
+		sp_test_primitives::Header {
+			parent_hash: Default::default(),
+			number: 0,
+			state_root: Default::default(),
+			extrinsics_root: Default::default(),
+			digest: Default::default(),
+		}
+	}
+
+	fn peerset() -> (sc_peerset::Peerset, sc_peerset::PeersetHandle) {
+		let cfg = sc_peerset::PeersetConfig {
+			in_peers: 128,
+			out_peers: 128,
+			bootnodes: Vec::new(),
+			reserved_only: false,
+			priority_groups: Vec::new(),
+		};
+		sc_peerset::Peerset::from_config(cfg)
+	}
+
+	fn make_sender(ok: bool, ps: sc_peerset::PeersetHandle) -> Sender {
+		let checker = Arc::new(DummyFetchChecker { ok, _mark: std::marker::PhantomData });
+		LightClientRequestSender::new(&protocol_id(), checker, ps)
+	}
+
+	fn call_request(retry_count: Option<usize>) -> RemoteCallRequest<sp_test_primitives::Header> {
+		light::RemoteCallRequest {
+			block: Default::default(),
+			header: dummy_header(),
+			method: "test".into(),
+			call_data: vec![],
+			retry_count,
+		}
+	}
+
+	fn call_response() -> Vec<u8> {
+		let r = schema::v1::light::RemoteCallResponse { proof: empty_proof() };
+		encode_response(schema::v1::light::response::Response::RemoteCallResponse(r))
+	}
+
+	fn encode_response(response: schema::v1::light::response::Response) -> Vec<u8> {
+		let response = schema::v1::light::Response { response: Some(response) };
+		let mut data = Vec::new();
+		response.encode(&mut data).unwrap();
+		data
+	}
+
+	/// Poll the sender once, returning the request it emits if any.
+	fn poll(sender: &mut Sender) -> Option<(PeerId, oneshot::Sender<Result<Vec<u8>, RequestFailure>>)> {
+		match sender.next().now_or_never() {
+			Some(Some(OutEvent::SendRequest { target, pending_response, .. })) =>
+				Some((target, pending_response)),
+			Some(None) => panic!("the sender stream never ends"),
+			None => None,
+		}
+	}
+
+	#[test]
+	fn removes_peer_if_told() {
+		let peer = PeerId::random();
+		let pset = peerset();
+		let mut sender = make_sender(true, pset.1);
+
+		sender.inject_connected(peer.clone());
+		assert_eq!(1, sender.peers.len());
+
+		sender.inject_disconnected(&peer);
+		assert_eq!(0, sender.peers.len())
+	}
+
+	#[test]
+	fn removes_peer_if_request_times_out() {
+		let peer0 = PeerId::random();
+		let peer1 = PeerId::random();
+		let pset = peerset();
+		let mut sender = make_sender(true, pset.1);
+
+		sender.inject_connected(peer0.clone());
+		sender.inject_connected(peer1.clone());
+
+		// We now know about two peers.
+		assert_eq!(HashSet::from_iter(&[peer0.clone(), peer1.clone()]), sender.peers.keys().collect::<HashSet<_>>());
+
+		// No requests have been made yet.
+		assert!(sender.pending_requests.is_empty());
+		assert!(sender.sent_requests.is_empty());
+
+		// Issue our first request!
+		let chan = oneshot::channel();
+		sender.request(Request::Call { request: call_request(Some(1)), sender: chan.0 }).unwrap();
+		assert_eq!(1, sender.pending_requests.len());
+
+		// The sender should now attempt to send the request.
+		let (target, pending_response) = poll(&mut sender).unwrap();
+		assert!(target == peer0 || target == peer1);
+
+		// And we should have one busy peer.
+		assert!({
+			let (idle, busy): (Vec<_>, Vec<_>) =
+				sender.peers.iter().partition(|(_, info)| info.status == PeerStatus::Idle);
+
+			idle.len() == 1 && busy.len() == 1
+				&& (idle[0].0 == &peer0 || busy[0].0 == &peer0)
+				&& (idle[0].0 == &peer1 || busy[0].0 == &peer1)
+		});
+
+		// No more pending requests, but one should be sent.
+		assert_eq!(0, sender.pending_requests.len());
+		assert_eq!(1, sender.sent_requests.len());
+
+		// The request times out, and is sent to the other peer.
+		pending_response.send(Err(RequestFailure::Timeout)).unwrap();
+		let (retry_target, _pending_response) = poll(&mut sender).unwrap();
+		assert!(retry_target != target);
+
+		// The peer of the timed out request should be removed.
+		assert_eq!(1, sender.peers.len());
+		assert_eq!(0, sender.pending_requests.len());
+		assert_eq!(1, sender.sent_requests.len());
+	}
+
+	#[test]
+	fn removes_peer_on_incorrect_response() {
+		let peer = PeerId::random();
+		let pset = peerset();
+		let mut sender = make_sender(false, pset.1);
+		//                            ^--- Making sure the response data check fails.
+
+		sender.inject_connected(peer.clone());
+		assert_eq!(1, sender.peers.len());
+
+		let chan = oneshot::channel();
+		sender.request(Request::Call { request: call_request(Some(1)), sender: chan.0 }).unwrap();
+
+		assert_eq!(1, sender.pending_requests.len());
+		assert_eq!(0, sender.sent_requests.len());
+		let (_, pending_response) = poll(&mut sender).unwrap();
+		assert_eq!(0, sender.pending_requests.len());
+		assert_eq!(1, sender.sent_requests.len());
+
+		pending_response.send(Ok(call_response())).unwrap();
+		assert!(poll(&mut sender).is_none());
+		assert!(sender.peers.is_empty());
+
+		// The request should be back in the pending queue
+		assert_eq!(1, sender.pending_requests.len());
+		assert_eq!(0, sender.sent_requests.len());
+	}
+
+	#[test]
+	fn removes_peer_on_wrong_response_type() {
+		let peer = PeerId::random();
+		let pset = peerset();
+		let mut sender = make_sender(true, pset.1);
+
+		sender.inject_connected(peer.clone());
+		assert_eq!(1, sender.peers.len());
+
+		let chan = oneshot::channel();
+		sender.request(Request::Call { request: call_request(Some(1)), sender: chan.0 }).unwrap();
+
+		let (_, pending_response) = poll(&mut sender).unwrap();
+		assert_eq!(0, sender.pending_requests.len());
+		assert_eq!(1, sender.sent_requests.len());
+
+		let response = {
+			let r = schema::v1::light::RemoteReadResponse { proof: empty_proof() }; // Not a RemoteCallResponse!
+			encode_response(schema::v1::light::response::Response::RemoteReadResponse(r))
+		};
+		pending_response.send(Ok(response)).unwrap();
+		assert!(poll(&mut sender).is_none());
+		assert!(sender.peers.is_empty());
+
+		// The request should be back in the pending queue
+		assert_eq!(1, sender.pending_requests.len());
+		assert_eq!(0, sender.sent_requests.len());
+	}
+
+	#[test]
+	fn requeues_request_if_peer_goes_away() {
+		let peer = PeerId::random();
+		let pset = peerset();
+		let mut sender = make_sender(true, pset.1);
+
+		sender.inject_connected(peer.clone());
+
+		let mut chan = oneshot::channel();
+		sender.request(Request::Call { request: call_request(None), sender: chan.0 }).unwrap();
+
+		let (_, pending_response) = poll(&mut sender).unwrap();
+		pending_response.send(Err(RequestFailure::Refused)).unwrap();
+		assert!(poll(&mut sender).is_none());
+
+		// Without any retry left, the request is still pending as the peer didn't answer.
+		assert!(sender.peers.is_empty());
+		assert_eq!(1, sender.pending_requests.len());
+		assert_matches!(chan.1.try_recv(), Ok(None));
+
+		sender.inject_connected(peer.clone());
+		let (target, _) = poll(&mut sender).unwrap();
+		assert_eq!(target, peer);
+	}
+
+	#[test]
+	fn receives_remote_failure_after_retry_count_failures() {
+		let pset = peerset();
+		let mut sender = make_sender(false, pset.1);
+		//                            ^--- Making sure the response data check fails.
+
+		for _ in 0..4 {
+			sender.inject_connected(PeerId::random());
+		}
+		assert_eq!(4, sender.peers.len());
+
+		let mut chan = oneshot::channel();
+		// Attempt up to three retries.
+		sender.request(Request::Call { request: call_request(Some(3)), sender: chan.0 }).unwrap();
+
+		assert_eq!(1, sender.pending_requests.len());
+		assert_eq!(0, sender.sent_requests.len());
+		let (_, mut pending_response) = poll(&mut sender).unwrap();
+		assert_eq!(0, sender.pending_requests.len());
+		assert_eq!(1, sender.sent_requests.len());
+
+		for _ in 1 ..= 3 {
+			// Send an invalid response
+			pending_response.send(Ok(call_response())).unwrap();
+			pending_response = poll(&mut sender).unwrap().1;
+			assert_matches!(chan.1.try_recv(), Ok(None))
+		}
+		// Final invalid response
+		pending_response.send(Ok(call_response())).unwrap();
+		assert!(poll(&mut sender).is_none());
+		assert_matches!(chan.1.try_recv(), Ok(Some(Err(ClientError::RemoteFetchFailed))))
+	}
+
+	fn issue_request(request: Request<Block>) {
+		let peer = PeerId::random();
+		let pset = peerset();
+		let mut sender = make_sender(true, pset.1);
+
+		sender.inject_connected(peer.clone());
+		assert_eq!(1, sender.peers.len());
+
+		let response = match request {
+			Request::Body { .. } => unimplemented!(),
+			Request::Header{..} => {
+				let r = schema::v1::light::RemoteHeaderResponse {
+					header: dummy_header().encode(),
+					proof: empty_proof()
+				};
+				encode_response(schema::v1::light::response::Response::RemoteHeaderResponse(r))
+			}
+			Request::Read{..} | Request::ReadChild{..} => {
+				let r = schema::v1::light::RemoteReadResponse { proof: empty_proof() };
+				encode_response(schema::v1::light::response::Response::RemoteReadResponse(r))
+			}
+			Request::Call{..} => call_response(),
+			Request::Changes{..} => {
+				let r = schema::v1::light::RemoteChangesResponse {
+					max: iter::repeat(1).take(32).collect(),
+					proof: Vec::new(),
+					roots: Vec::new(),
+					roots_proof: empty_proof()
+				};
+				encode_response(schema::v1::light::response::Response::RemoteChangesResponse(r))
+			}
+		};
+
+		sender.request(request).unwrap();
+
+		assert_eq!(1, sender.pending_requests.len());
+		assert_eq!(0, sender.sent_requests.len());
+		let (target, pending_response) = poll(&mut sender).unwrap();
+		assert_eq!(target, peer);
+		assert_eq!(0, sender.pending_requests.len());
+		assert_eq!(1, sender.sent_requests.len());
+
+		pending_response.send(Ok(response)).unwrap();
+		assert!(poll(&mut sender).is_none());
+
+		assert_eq!(0, sender.pending_requests.len());
+		assert_eq!(0, sender.sent_requests.len());
+		assert_eq!(sender.peers.get(&peer).unwrap().status, PeerStatus::Idle);
+	}
+
+	#[test]
+	fn receives_remote_call_response() {
+		let mut chan = oneshot::channel();
+		issue_request(Request::Call { request: call_request(None), sender: chan.0 });
+		assert_matches!(chan.1.try_recv(), Ok(Some(Ok(_))))
+	}
+
+	#[test]
+	fn receives_remote_read_response() {
+		let mut chan = oneshot::channel();
+		let request = light::RemoteReadRequest {
+			header: dummy_header(),
+			block: Default::default(),
+			keys: vec![b":key".to_vec()],
+			retry_count: None,
+		};
+		issue_request(Request::Read { request, sender: chan.0 });
+		assert_matches!(chan.1.try_recv(), Ok(Some(Ok(_))))
+	}
+
+	#[test]
+	fn receives_remote_read_child_response() {
+		let mut chan = oneshot::channel();
+		let child_info = ChildInfo::new_default(&b":child_storage:default:sub"[..]);
+		let request = light::RemoteReadChildRequest {
+			header: dummy_header(),
+			block: Default::default(),
+			storage_key: child_info.prefixed_storage_key(),
+			keys: vec![b":key".to_vec()],
+			retry_count: None,
+		};
+		issue_request(Request::ReadChild { request, sender: chan.0 });
+		assert_matches!(chan.1.try_recv(), Ok(Some(Ok(_))))
+	}
+
+	#[test]
+	fn receives_remote_header_response() {
+		let mut chan = oneshot::channel();
+		let request = light::RemoteHeaderRequest {
+			cht_root: Default::default(),
+			block: 1,
+			retry_count: None,
+		};
+		issue_request(Request::Header { request, sender: chan.0 });
+		assert_matches!(chan.1.try_recv(), Ok(Some(Ok(_))))
+	}
+
+	#[test]
+	fn receives_remote_changes_response() {
+		let mut chan = oneshot::channel();
+		let request = light::RemoteChangesRequest {
+			changes_trie_configs: vec![sp_core::ChangesTrieConfigurationRange {
+				zero: (0, Default::default()),
+				end: None,
+				config: Some(sp_core::ChangesTrieConfiguration::new(4, 2)),
+			}],
+			first_block: (1, Default::default()),
+			last_block: (100, Default::default()),
+			max_block: (100, Default::default()),
+			tries_roots: (1, Default::default(), Vec::new()),
+			key: Vec::new(),
+			storage_key: None,
+			retry_count: None,
+		};
+		issue_request(Request::Changes { request, sender: chan.0 });
+		assert_matches!(chan.1.try_recv(), Ok(Some(Ok(_))))
+	}
+
+	/// Send `request` with a sender, and answer it with a handler serving the test client, the
+	/// way the request-response protocol would.
+	fn send_receive(request: Request<Block>) {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let handler_pset = peerset();
+		let (handler, protocol_config) =
+			LightClientRequestHandler::<Block>::new(&protocol_id(), client, handler_pset.1);
+		let mut inbound_queue = protocol_config.inbound_queue.unwrap();
+		task::spawn(handler.run());
+
+		let peer = PeerId::random();
+		let sender_pset = peerset();
+		let mut sender = make_sender(true, sender_pset.1);
+		sender.inject_connected(peer.clone());
+		sender.request(request).unwrap();
+
+		let (target, payload, pending_response, protocol_name) = match task::block_on(sender.next()) {
+			Some(OutEvent::SendRequest { target, request, pending_response, protocol_name }) =>
+				(target, request, pending_response, protocol_name),
+			None => panic!("the sender stream never ends"),
+		};
+		assert_eq!(target, peer);
+		assert_eq!(protocol_name, protocol_config.name);
+
+		let (tx, rx) = oneshot::channel();
+		task::block_on(inbound_queue.send(IncomingRequest {
+			peer: PeerId::random(),
+			payload,
+			pending_response: tx,
+		})).unwrap();
+		let response = task::block_on(rx).map_err(|_| RequestFailure::Refused);
+		pending_response.send(response).unwrap();
+
+		// Let the sender check the response and reply.
+		assert!(poll(&mut sender).is_none());
+	}
+
+	#[test]
+	fn send_receive_call() {
+		let chan = oneshot::channel();
+		send_receive(Request::Call { request: call_request(None), sender: chan.0 });
+		assert_eq!(vec![42], task::block_on(chan.1).unwrap().unwrap());
+		//              ^--- from `DummyFetchChecker::check_execution_proof`
+	}
+
+	#[test]
+	fn send_receive_read() {
+		let chan = oneshot::channel();
+		let request = light::RemoteReadRequest {
+			header: dummy_header(),
+			block: Default::default(),
+			keys: vec![b":key".to_vec()],
+			retry_count: None
+		};
+		send_receive(Request::Read { request, sender: chan.0 });
+		assert_eq!(Some(vec![42]), task::block_on(chan.1).unwrap().unwrap().remove(&b":key"[..]).unwrap());
+		//                   ^--- from `DummyFetchChecker::check_read_proof`
+	}
+
+	#[test]
+	fn send_receive_read_child() {
+		let chan = oneshot::channel();
+		let child_info = ChildInfo::new_default(&b":child_storage:default:sub"[..]);
+		let request = light::RemoteReadChildRequest {
+			header: dummy_header(),
+			block: Default::default(),
+			storage_key: child_info.prefixed_storage_key(),
+			keys: vec![b":key".to_vec()],
+			retry_count: None,
+		};
+		send_receive(Request::ReadChild { request, sender: chan.0 });
+		assert_eq!(Some(vec![42]), task::block_on(chan.1).unwrap().unwrap().remove(&b":key"[..]).unwrap());
+		//                   ^--- from `DummyFetchChecker::check_read_child_proof`
+	}
+
+	#[test]
+	fn send_receive_header() {
+		let _ = env_logger::try_init();
+		let chan = oneshot::channel();
+		let request = light::RemoteHeaderRequest {
+			cht_root: Default::default(),
+			block: 1,
+			retry_count: None,
+		};
+		send_receive(Request::Header { request, sender: chan.0 });
+		// The remote does not know block 1:
+		assert_matches!(task::block_on(chan.1).unwrap(), Err(ClientError::RemoteFetchFailed));
+	}
+
+	#[test]
+	fn send_receive_changes() {
+		let chan = oneshot::channel();
+		let request = light::RemoteChangesRequest {
+			changes_trie_configs: vec![sp_core::ChangesTrieConfigurationRange {
+				zero: (0, Default::default()),
+				end: None,
+				config: Some(sp_core::ChangesTrieConfiguration::new(4, 2)),
+			}],
+			first_block: (1, Default::default()),
+			last_block: (100, Default::default()),
+			max_block: (100, Default::default()),
+			tries_roots: (1, Default::default(), Vec::new()),
+			key: Vec::new(),
+			storage_key: None,
+			retry_count: None,
+		};
+		send_receive(Request::Changes { request, sender: chan.0 });
+		assert_eq!(vec![(100, 2)], task::block_on(chan.1).unwrap().unwrap());
+		//              ^--- from `DummyFetchChecker::check_changes_proof`
+	}
+
+	#[test]
+	fn body_request_fields_encoded_properly() {
+		let (sender, _) = oneshot::channel();
+		let serialized_request = serialize_request::<Block>(&Request::Body {
+			request: RemoteBodyRequest {
+				header: dummy_header(),
+				retry_count: None,
+			},
+			sender,
+		}).unwrap();
+		let deserialized_request = schema::v1::BlockRequest::decode(&serialized_request[..]).unwrap();
+		assert!(
+			BlockAttributes::from_be_u32(deserialized_request.fields)
+				.unwrap()
+				.contains(BlockAttributes::BODY)
+		);
+	}
+}
@@ -18,7 +18,7 @@
 
 //! On-demand requests service.
 
-use crate::light_client_handler;
+use crate::light_client_requests;
 
 use futures::{channel::oneshot, prelude::*};
 use parking_lot::Mutex;
@@ -45,10 +45,10 @@ pub struct OnDemand<B: BlockT> {
 	/// Note that a better alternative would be to use a MPMC queue here, and add a `poll` method
 	/// from the `OnDemand`. However there exists no popular implementation of MPMC channels in
 	/// asynchronous Rust at the moment
-	requests_queue: Mutex<Option<TracingUnboundedReceiver<light_client_handler::Request<B>>>>,
+	requests_queue: Mutex<Option<TracingUnboundedReceiver<light_client_requests::Request<B>>>>,
 
 	/// Sending side of `requests_queue`.
-	requests_send: TracingUnboundedSender<light_client_handler::Request<B>>,
+	requests_send: TracingUnboundedSender<light_client_requests::Request<B>>,
 }
 
 /// Dummy implementation of `FetchChecker` that always assumes that responses are bad.
@@ -138,7 +138,7 @@ where
 	/// If this function returns `None`, that means that the receiver has already been extracted in
 	/// the past, and therefore that something already handles the requests.
 	pub(crate) fn extract_receiver(&self)
-		-> Option<TracingUnboundedReceiver<light_client_handler::Request<B>>>
+		-> Option<TracingUnboundedReceiver<light_client_requests::Request<B>>>
 	{
 		self.requests_queue.lock().take()
 	}
@@ -159,7 +159,7 @@ where
 		let (sender, receiver) = oneshot::channel();
 		let _ = self
 			.requests_send
-			.unbounded_send(light_client_handler::Request::Header { request, sender });
+			.unbounded_send(light_client_requests::Request::Header { request, sender });
 		RemoteResponse { receiver }
 	}
 
@@ -167,7 +167,7 @@ where
 		let (sender, receiver) = oneshot::channel();
 		let _ = self
 			.requests_send
-			.unbounded_send(light_client_handler::Request::Read { request, sender });
+			.unbounded_send(light_client_requests::Request::Read { request, sender });
 		RemoteResponse { receiver }
 	}
 
@@ -178,7 +178,7 @@ where
 		let (sender, receiver) = oneshot::channel();
 		let _ = self
 			.requests_send
-			.unbounded_send(light_client_handler::Request::ReadChild { request, sender });
+			.unbounded_send(light_client_requests::Request::ReadChild { request, sender });
 		RemoteResponse { receiver }
 	}
 
@@ -186,7 +186,7 @@ where
 		let (sender, receiver) = oneshot::channel();
 		let _ = self
 			.requests_send
-			.unbounded_send(light_client_handler::Request::Call { request, sender });
+			.unbounded_send(light_client_requests::Request::Call { request, sender });
 		RemoteResponse { receiver }
 	}
 
@@ -197,7 +197,7 @@ where
 		let (sender, receiver) = oneshot::channel();
 		let _ = self
 			.requests_send
-			.unbounded_send(light_client_handler::Request::Changes { request, sender });
+			.unbounded_send(light_client_requests::Request::Changes { request, sender });
 		RemoteResponse { receiver }
 	}
 
@@ -205,7 +205,7 @@ where
 		let (sender, receiver) = oneshot::channel();
 		let _ = self
 			.requests_send
-			.unbounded_send(light_client_handler::Request::Body { request, sender });
+			.unbounded_send(light_client_requests::Request::Body { request, sender });
 		RemoteResponse { receiver }
 	}
 }
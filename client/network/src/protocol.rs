@@ -19,7 +19,7 @@
 use crate::{
 	ExHashT,
 	chain::{Client, FinalityProofProvider},
-	config::{BoxFinalityProofRequestBuilder, ProtocolId, TransactionPool, TransactionImportFuture, TransactionImport},
+	config::{BoxFinalityProofRequestBuilder, ProtocolId, SyncMode, TransactionPool, TransactionImportFuture, TransactionImport},
 	error,
 	utils::interval
 };
@@ -317,6 +317,8 @@ pub struct ProtocolConfig {
 	pub roles: Roles,
 	/// Maximum number of peers to ask the same blocks in parallel.
 	pub max_parallel_downloads: u32,
+	/// How to synchronize with the rest of the network.
+	pub sync_mode: SyncMode,
 }
 
 impl Default for ProtocolConfig {
@@ -324,6 +326,7 @@ impl Default for ProtocolConfig {
 		ProtocolConfig {
 			roles: Roles::FULL,
 			max_parallel_downloads: 5,
+			sync_mode: SyncMode::Full,
 		}
 	}
 }
@@ -403,6 +406,7 @@ impl<B: BlockT, H: ExHashT> Protocol<B, H> {
 			finality_proof_request_builder,
 			block_announce_validator,
 			config.max_parallel_downloads,
+			config.sync_mode,
 		);
 
 		let important_peers = {
@@ -914,6 +918,40 @@ impl<B: BlockT, H: ExHashT> Protocol<B, H> {
 		self.behaviour.disconnect_peer(peer);
 	}
 
+	/// Must be called in response to a [`CustomMessageOutcome::StateRequest`] being emitted.
+	/// Must contain the same `PeerId` that has been emitted.
+	pub fn on_state_response(
+		&mut self,
+		peer: PeerId,
+		response: message::StateResponse,
+	) -> CustomMessageOutcome<B> {
+		trace!(target: "sync", "State response from {}", peer);
+		match self.sync.on_state_data(&peer, response) {
+			Ok(sync::OnStateData::Import(origin, block)) =>
+				CustomMessageOutcome::BlockImport(origin, vec![block]),
+			Ok(sync::OnStateData::Request(peer, request)) =>
+				CustomMessageOutcome::StateRequest {
+					target: peer,
+					request,
+				},
+			Ok(sync::OnStateData::Nothing) => CustomMessageOutcome::None,
+			Err(sync::BadPeer(id, repu)) => {
+				self.behaviour.disconnect_peer(&id);
+				self.peerset_handle.report_peer(id, repu);
+				CustomMessageOutcome::None
+			}
+		}
+	}
+
+	/// Must be called in response to a [`CustomMessageOutcome::StateRequest`] if it has failed.
+	pub fn on_state_request_failed(
+		&mut self,
+		peer: &PeerId,
+	) {
+		self.peerset_handle.report_peer(peer.clone(), rep::TIMEOUT);
+		self.behaviour.disconnect_peer(peer);
+	}
+
 	/// Perform time based maintenance.
 	///
 	/// > **Note**: This method normally doesn't have to be called except for testing purposes.
@@ -1883,6 +1921,11 @@ pub enum CustomMessageOutcome<B: BlockT> {
 	/// If the request times out, or the peer responds in an invalid way, the peer has to be
 	/// disconnect. This will inform the state machine that the request it has emitted is stale.
 	FinalityProofRequest { target: PeerId, block_hash: B::Hash, request: Vec<u8> },
+	/// A new state request must be emitted.
+	/// You must later call either [`Protocol::on_state_response`] or
+	/// [`Protocol::on_state_request_failed`].
+	/// It is the responsibility of the handler to ensure that a timeout exists.
+	StateRequest { target: PeerId, request: message::StateRequest<B::Hash> },
 	/// Peer has a reported a new head of chain.
 	PeerNewBest(PeerId, NumberFor<B>),
 	None,
@@ -2005,6 +2048,13 @@ impl<B: BlockT, H: ExHashT> NetworkBehaviour for Protocol<B, H> {
 			};
 			self.pending_messages.push_back(event);
 		}
+		if let Some((id, request)) = self.sync.state_request() {
+			let event = CustomMessageOutcome::StateRequest {
+				target: id,
+				request,
+			};
+			self.pending_messages.push_back(event);
+		}
 		if let Poll::Ready(Some((tx_hash, result))) = self.pending_transactions.poll_next_unpin(cx) {
 			if let Some(peers) = self.pending_transactions_peers.remove(&tx_hash) {
 				peers.into_iter().for_each(|p| self.on_handle_transaction_import(p, result));
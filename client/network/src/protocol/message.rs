@@ -133,6 +133,24 @@ pub enum BlockState {
 	Best,
 }
 
+/// Request for a chunk of the state of a block, used by fast sync.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StateRequest<Hash> {
+	/// Hash of the block whose state is requested.
+	pub block: Hash,
+	/// Prefixed storage key of the child trie to read, or `None` to read the top trie.
+	pub child_key: Option<Vec<u8>>,
+	/// Key after which to start reading, or `None` to start at the first key.
+	pub start: Option<Vec<u8>>,
+}
+
+/// Response to a `StateRequest`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StateResponse {
+	/// Range proof of the key-values following the requested start key.
+	pub proof: StorageProof,
+}
+
 /// Remote call response.
 #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode)]
 pub struct RemoteCallResponse {
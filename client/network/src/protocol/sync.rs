@@ -35,12 +35,13 @@ use sp_consensus::{BlockOrigin, BlockStatus,
 	import_queue::{IncomingBlock, BlockImportResult, BlockImportError}
 };
 use crate::{
-	config::BoxFinalityProofRequestBuilder,
+	config::{BoxFinalityProofRequestBuilder, SyncMode},
 	protocol::message::{self, generic::FinalityProofRequest, BlockAnnounce, BlockAttributes, BlockRequest, BlockResponse,
-	FinalityProofResponse, Roles},
+	FinalityProofResponse, Roles, StateRequest, StateResponse},
 };
 use either::Either;
 use extra_requests::ExtraRequests;
+use state::StateSync;
 use libp2p::PeerId;
 use log::{debug, trace, warn, info, error};
 use sp_runtime::{
@@ -53,6 +54,7 @@ use std::{fmt, ops::Range, collections::{HashMap, HashSet, VecDeque}, sync::Arc}
 
 mod blocks;
 mod extra_requests;
+mod state;
 
 /// Maximum blocks to request in a single packet.
 const MAX_BLOCKS_TO_REQUEST: usize = 128;
@@ -73,6 +75,10 @@ const MAJOR_SYNC_BLOCKS: u8 = 5;
 /// Number of recently announced blocks to track for each peer.
 const ANNOUNCE_HISTORY_SIZE: usize = 64;
 
+/// Fast sync downloads the state of a finalized block once it is at most this many blocks behind
+/// the best block of the median peer.
+const STATE_SYNC_FINALITY_THRESHOLD: u32 = 8;
+
 mod rep {
 	use sc_peerset::ReputationChange as Rep;
 	/// Reputation change when a peer sent us a message that led to a
@@ -106,6 +112,9 @@ mod rep {
 
 	/// Reputation change when a peer sent us invlid ancestry result.
 	pub const UNKNOWN_ANCESTOR:Rep = Rep::new(-(1 << 16), "DB Error");
+
+	/// Reputation change for peers which send us a state chunk which we fail to verify.
+	pub const BAD_STATE: Rep = Rep::new(-(1 << 29), "Bad state");
 }
 
 enum PendingRequests {
@@ -192,6 +201,14 @@ pub struct ChainSync<B: BlockT> {
 	max_parallel_downloads: u32,
 	/// Total number of downloaded blocks.
 	downloaded_blocks: usize,
+	/// How the chain is synchronized.
+	mode: SyncMode,
+	/// State sync of a recent finalized block, started by fast sync once the headers are
+	/// downloaded.
+	state_sync: Option<StateSync<B>>,
+	/// Blocks up to this number have been imported by fast sync without their state. They are
+	/// downloaded and imported again on top of the state imported by state sync.
+	reimport_up_to: Option<NumberFor<B>>,
 }
 
 /// All the data we have about a Peer that we are trying to sync with
@@ -250,7 +267,9 @@ pub enum PeerSyncState<B: BlockT> {
 	/// Downloading justification for given block hash.
 	DownloadingJustification(B::Hash),
 	/// Downloading finality proof for given block hash.
-	DownloadingFinalityProof(B::Hash)
+	DownloadingFinalityProof(B::Hash),
+	/// Downloading a chunk of the state of the state sync target.
+	DownloadingState,
 }
 
 impl<B: BlockT> PeerSyncState<B> {
@@ -306,6 +325,17 @@ pub enum OnBlockData<B: BlockT> {
 	Request(PeerId, BlockRequest<B>)
 }
 
+/// Result of [`ChainSync::on_state_data`].
+#[derive(Debug)]
+pub enum OnStateData<B: BlockT> {
+	/// The state response needs no further handling.
+	Nothing,
+	/// The block and its state should be imported.
+	Import(BlockOrigin, IncomingBlock<B>),
+	/// A new state request needs to be made to the given peer.
+	Request(PeerId, StateRequest<B::Hash>)
+}
+
 /// Result of [`ChainSync::on_block_announce`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OnBlockAnnounce {
@@ -352,10 +382,22 @@ impl<B: BlockT> ChainSync<B> {
 		request_builder: Option<BoxFinalityProofRequestBuilder<B>>,
 		block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>,
 		max_parallel_downloads: u32,
+		mode: SyncMode,
 	) -> Self {
 		let mut required_block_attributes = BlockAttributes::HEADER | BlockAttributes::JUSTIFICATION;
 
-		if role.is_full() {
+		// Fast sync only applies to full nodes which have not yet imported the state of a block
+		// after genesis.
+		let mode = match mode {
+			SyncMode::Fast if role.is_full() && (
+				info.finalized_number.is_zero() ||
+				client.block_status(&BlockId::Hash(info.finalized_hash)).ok()
+					!= Some(BlockStatus::InChainWithState)
+			) => SyncMode::Fast,
+			_ => SyncMode::Full,
+		};
+
+		if role.is_full() && mode == SyncMode::Full {
 			required_block_attributes |= BlockAttributes::BODY
 		}
 
@@ -377,6 +419,9 @@ impl<B: BlockT> ChainSync<B> {
 			block_announce_validator,
 			max_parallel_downloads,
 			downloaded_blocks: 0,
+			mode,
+			state_sync: None,
+			reimport_up_to: None,
 		}
 	}
 
@@ -391,7 +436,9 @@ impl<B: BlockT> ChainSync<B> {
 	pub fn status(&self) -> Status<B> {
 		let best_seen = self.peers.values().map(|p| p.best_number).max();
 		let sync_state =
-			if let Some(n) = best_seen {
+			if self.state_sync.is_some() {
+				SyncState::Downloading
+			} else if let Some(n) = best_seen {
 				// A chain is classified as downloading if the provided best block is
 				// more than `MAJOR_SYNC_BLOCKS` behind the best queued block.
 				if n > self.best_queued_number && n - self.best_queued_number > MAJOR_SYNC_BLOCKS.into() {
@@ -501,8 +548,14 @@ impl<B: BlockT> ChainSync<B> {
 			}
 			Ok(BlockStatus::Queued) | Ok(BlockStatus::InChainWithState) | Ok(BlockStatus::InChainPruned) => {
 				debug!(target:"sync", "New peer with known best hash {} ({}).", best_hash, best_number);
+				// Blocks imported without their state by fast sync are downloaded again.
+				let common_number = if self.reimport_up_to.is_some() {
+					std::cmp::min(best_number, self.best_queued_number)
+				} else {
+					best_number
+				};
 				self.peers.insert(who.clone(), PeerSync {
-					common_number: best_number,
+					common_number,
 					best_hash,
 					best_number,
 					state: PeerSyncState::Available,
@@ -641,7 +694,7 @@ impl<B: BlockT> ChainSync<B> {
 
 	/// Get an iterator over all block requests of all peers.
 	pub fn block_requests(&mut self) -> impl Iterator<Item = (&PeerId, BlockRequest<B>)> + '_ {
-		if self.pending_requests.is_empty() {
+		if self.pending_requests.is_empty() || self.state_sync.is_some() {
 			return Either::Left(std::iter::empty())
 		}
 		if self.queue_blocks.len() > MAX_IMPORTING_BLOCKS {
@@ -704,6 +757,27 @@ impl<B: BlockT> ChainSync<B> {
 		Either::Right(iter)
 	}
 
+	/// Get a state request, if any.
+	pub fn state_request(&mut self) -> Option<(PeerId, StateRequest<B::Hash>)> {
+		if self.peers.iter().any(|(_, peer)| peer.state == PeerSyncState::DownloadingState) {
+			// Only one pending state request is allowed.
+			return None
+		}
+		if let Some(sync) = &self.state_sync {
+			if sync.is_complete() {
+				return None
+			}
+			for (id, peer) in self.peers.iter_mut() {
+				if peer.state.is_available() && peer.common_number >= sync.target_block_num() {
+					trace!(target: "sync", "New state request to {}", id);
+					peer.state = PeerSyncState::DownloadingState;
+					return Some((id.clone(), sync.next_request()))
+				}
+			}
+		}
+		None
+	}
+
 	/// Handle a response from the remote to a block request that we made.
 	///
 	/// `request` must be the original request that triggered `response`.
@@ -734,10 +808,15 @@ impl<B: BlockT> ChainSync<B> {
 							peer.state = PeerSyncState::Available;
 							validate_blocks::<B>(&blocks, who)?;
 							self.blocks.insert(start_block, blocks, who.clone());
+							let reimport_up_to = self.reimport_up_to;
 							self.blocks
 								.drain(self.best_queued_number + One::one())
 								.into_iter()
 								.map(|block_data| {
+									let import_existing = match (&block_data.block.header, reimport_up_to) {
+										(Some(header), Some(number)) => *header.number() <= number,
+										_ => false,
+									};
 									IncomingBlock {
 										hash: block_data.block.hash,
 										header: block_data.block.header,
@@ -745,7 +824,8 @@ impl<B: BlockT> ChainSync<B> {
 										justification: block_data.block.justification,
 										origin: block_data.origin,
 										allow_missing_state: true,
-										import_existing: false,
+										import_existing,
+										import_state: None,
 									}
 								}).collect()
 						}
@@ -765,6 +845,7 @@ impl<B: BlockT> ChainSync<B> {
 									origin: Some(who.clone()),
 									allow_missing_state: true,
 									import_existing: false,
+									import_state: None,
 								}
 							}).collect()
 						}
@@ -836,7 +917,8 @@ impl<B: BlockT> ChainSync<B> {
 
 						| PeerSyncState::Available
 						| PeerSyncState::DownloadingJustification(..)
-						| PeerSyncState::DownloadingFinalityProof(..) => Vec::new()
+						| PeerSyncState::DownloadingFinalityProof(..)
+						| PeerSyncState::DownloadingState => Vec::new()
 					}
 				} else {
 					// When request.is_none() this is a block announcement. Just accept blocks.
@@ -850,6 +932,7 @@ impl<B: BlockT> ChainSync<B> {
 							origin: Some(who.clone()),
 							allow_missing_state: true,
 							import_existing: false,
+							import_state: None,
 						}
 					}).collect()
 				}
@@ -866,7 +949,7 @@ impl<B: BlockT> ChainSync<B> {
 			})
 			.unwrap_or(false);
 
-		if !is_recent && new_blocks.last().map_or(false, |b| self.is_known(&b.hash)) {
+		if !is_recent && new_blocks.last().map_or(false, |b| !b.import_existing && self.is_known(&b.hash)) {
 			// When doing initial sync we don't request blocks in parallel.
 			// So the only way this can happen is when peers lie about the
 			// common block.
@@ -896,6 +979,58 @@ impl<B: BlockT> ChainSync<B> {
 		Ok(OnBlockData::Import(origin, new_blocks))
 	}
 
+	/// Handle a response from the remote to a state request that we made.
+	///
+	/// Returns the block to import along with its state once the state is complete.
+	pub fn on_state_data(
+		&mut self,
+		who: &PeerId,
+		response: StateResponse,
+	) -> Result<OnStateData<B>, BadPeer> {
+		if let Some(peer) = self.peers.get_mut(who) {
+			if let PeerSyncState::DownloadingState = peer.state {
+				peer.state = PeerSyncState::Available;
+			}
+		}
+
+		let import_result = if let Some(sync) = &mut self.state_sync {
+			trace!(target: "sync", "Importing state data from {}", who);
+			sync.import(response)
+		} else {
+			debug!(target: "sync", "Ignored obsolete state response from {}", who);
+			return Ok(OnStateData::Nothing)
+		};
+
+		match import_result {
+			state::ImportResult::Import(hash, header, state) => {
+				let block = IncomingBlock {
+					hash,
+					header: Some(header),
+					body: None,
+					justification: None,
+					origin: None,
+					allow_missing_state: true,
+					import_existing: true,
+					import_state: Some(state),
+				};
+				debug!(target: "sync", "State download is complete, importing {}", hash);
+				Ok(OnStateData::Import(BlockOrigin::NetworkInitialSync, block))
+			}
+			state::ImportResult::Continue(request) => {
+				if let Some(peer) = self.peers.get_mut(who) {
+					peer.state = PeerSyncState::DownloadingState;
+					Ok(OnStateData::Request(who.clone(), request))
+				} else {
+					Ok(OnStateData::Nothing)
+				}
+			}
+			state::ImportResult::BadResponse => {
+				debug!(target: "sync", "Bad state data received from {}", who);
+				Err(BadPeer(who.clone(), rep::BAD_STATE))
+			}
+		}
+	}
+
 	/// Handle a response from the remote to a justification request that we made.
 	///
 	/// `request` must be the original request that triggered `response`.
@@ -1012,6 +1147,11 @@ impl<B: BlockT> ChainSync<B> {
 				has_error = true;
 			}
 
+			if self.state_sync.as_ref().map_or(false, |sync| sync.is_complete() && sync.target() == hash) {
+				self.on_state_imported(hash, result.is_ok());
+				continue;
+			}
+
 			match result {
 				Ok(BlockImportResult::ImportedKnown(_number)) => {}
 				Ok(BlockImportResult::ImportedUnknown(number, aux, who)) => {
@@ -1045,6 +1185,11 @@ impl<B: BlockT> ChainSync<B> {
 					if number > self.best_imported_number {
 						self.best_imported_number = number;
 					}
+
+					if self.reimport_up_to.map_or(false, |n| number >= n) {
+						debug!(target: "sync", "Blocks imported without state by fast sync are imported again");
+						self.reimport_up_to = None;
+					}
 				},
 				Err(BlockImportError::IncompleteHeader(who)) => {
 					if let Some(peer) = who {
@@ -1085,6 +1230,49 @@ impl<B: BlockT> ChainSync<B> {
 		output.into_iter()
 	}
 
+	/// Called when the block of the state sync target has been processed by the import queue.
+	///
+	/// On success, switches to full sync from the target block. The blocks imported after it
+	/// without their state are downloaded and imported again.
+	fn on_state_imported(&mut self, hash: B::Hash, success: bool) {
+		let sync = match self.state_sync.take() {
+			Some(sync) => sync,
+			None => return,
+		};
+		let number = sync.target_block_num();
+		if !success {
+			// State sync starts again on the next finalized block.
+			warn!(target: "sync", "💔 Error importing the state of #{} ({})", number, hash);
+			return
+		}
+
+		info!(
+			"✅ Imported the state of #{} ({}), {} bytes, continuing with full sync",
+			number,
+			hash,
+			sync.imported_bytes(),
+		);
+		self.mode = SyncMode::Full;
+		self.required_block_attributes |= BlockAttributes::BODY;
+		if self.best_queued_number > number {
+			self.reimport_up_to = Some(self.best_queued_number);
+		}
+		self.best_queued_number = number;
+		self.best_queued_hash = hash;
+		self.best_imported_number = number;
+		self.blocks.clear();
+		for peer in self.peers.values_mut() {
+			if let PeerSyncState::DownloadingNew(_) = peer.state {
+				// Responses to header requests are ignored.
+				peer.state = PeerSyncState::Available;
+			}
+			if peer.common_number > number {
+				peer.common_number = number;
+			}
+		}
+		self.pending_requests.set_all();
+	}
+
 	/// Call this when a justification has been processed by the import queue,
 	/// with or without errors.
 	pub fn on_justification_import(&mut self, hash: B::Hash, number: NumberFor<B>, success: bool) {
@@ -1117,6 +1305,22 @@ impl<B: BlockT> ChainSync<B> {
 		if let Err(err) = r {
 			warn!(target: "sync", "💔 Error cleaning up pending extra justification data requests: {:?}", err);
 		}
+
+		if self.mode == SyncMode::Fast && self.state_sync.is_none() && !self.peers.is_empty() {
+			let mut heads: Vec<_> = self.peers.values().map(|peer| peer.best_number).collect();
+			heads.sort();
+			let median = heads[heads.len() / 2];
+			if number + STATE_SYNC_FINALITY_THRESHOLD.saturated_into() >= median {
+				match self.client.header(BlockId::Hash(*hash)) {
+					Ok(Some(header)) => {
+						info!("⏩ Starting state sync of #{} ({})", number, hash);
+						self.state_sync = Some(StateSync::new(self.client.clone(), header));
+					},
+					Ok(None) => warn!(target: "sync", "💔 Missing header of finalized block {}", hash),
+					Err(e) => warn!(target: "sync", "💔 Error reading header of finalized block {}: {:?}", hash, e),
+				}
+			}
+		}
 	}
 
 	/// Called when a block has been queued for import.
@@ -1274,8 +1478,18 @@ impl<B: BlockT> ChainSync<B> {
 	fn restart<'a>(&'a mut self) -> impl Iterator<Item = Result<(PeerId, BlockRequest<B>), BadPeer>> + 'a {
 		self.blocks.clear();
 		let info = self.client.info();
-		self.best_queued_hash = info.best_hash;
-		self.best_queued_number = std::cmp::max(info.best_number, self.best_imported_number);
+		if self.reimport_up_to.is_some() {
+			// Blocks imported without their state are imported again from the last one imported
+			// with it.
+			self.best_queued_number = self.best_imported_number;
+			self.best_queued_hash = self.client.hash(self.best_imported_number)
+				.ok()
+				.flatten()
+				.unwrap_or(info.best_hash);
+		} else {
+			self.best_queued_hash = info.best_hash;
+			self.best_queued_number = std::cmp::max(info.best_number, self.best_imported_number);
+		}
 		self.pending_requests.set_all();
 		debug!(target:"sync", "Restarted with {} ({})", self.best_queued_number, self.best_queued_hash);
 		let old_peers = std::mem::take(&mut self.peers);
@@ -1560,7 +1774,9 @@ mod test {
 	};
 	use sp_blockchain::HeaderBackend;
 	use sc_block_builder::BlockBuilderProvider;
+	use sc_client_api::ProofProvider;
 	use sp_consensus::block_validation::DefaultBlockAnnounceValidator;
+	use sp_core::{H256, storage::{well_known_keys, ChildInfo}};
 
 	#[test]
 	fn processes_empty_response_on_justification_request_for_unknown_block() {
@@ -1580,6 +1796,7 @@ mod test {
 			None,
 			block_announce_validator,
 			1,
+			SyncMode::Full,
 		);
 
 		let (a1_hash, a1_number) = {
@@ -1639,4 +1856,61 @@ mod test {
 			})
 		);
 	}
+
+	#[test]
+	fn fast_sync_downloads_state_of_finalized_block() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let info = client.info();
+		let block_announce_validator = Box::new(DefaultBlockAnnounceValidator);
+		let peer_id = PeerId::random();
+
+		let mut sync = ChainSync::new(
+			Roles::FULL,
+			client.clone(),
+			&info,
+			None,
+			block_announce_validator,
+			1,
+			SyncMode::Fast,
+		);
+		assert!(!sync.required_block_attributes.contains(BlockAttributes::BODY));
+
+		// a peer a few blocks ahead of our finalized block
+		sync.new_peer(peer_id.clone(), H256::random(), 5).unwrap();
+		sync.on_block_finalized(&info.genesis_hash, 0);
+		assert!(sync.state_sync.is_some());
+		assert_eq!(sync.block_requests().count(), 0);
+
+		let mut request = sync.state_request().map(|(who, request)| {
+			assert_eq!(who, peer_id);
+			request
+		});
+		let block = loop {
+			let StateRequest { block, child_key, start } = request.take()
+				.expect("state requests are emitted until the state is complete");
+			assert_eq!(block, info.genesis_hash);
+			let child_info = child_key.map(|key| {
+				ChildInfo::new_default(&key[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..])
+			});
+			let (proof, _) = client.read_range_proof(
+				&BlockId::Hash(block),
+				child_info.as_ref(),
+				start.as_ref().map(|key| &key[..]),
+				100,
+			).unwrap();
+			match sync.on_state_data(&peer_id, StateResponse { proof }).unwrap() {
+				OnStateData::Request(who, next) => {
+					assert_eq!(who, peer_id);
+					request = Some(next);
+				},
+				OnStateData::Import(_, block) => break block,
+				OnStateData::Nothing => panic!("the state response is expected"),
+			}
+		};
+
+		assert_eq!(block.hash, info.genesis_hash);
+		assert!(block.import_existing);
+		assert!(block.import_state.is_some());
+		assert!(sync.state_sync.as_ref().unwrap().is_complete());
+	}
 }
@@ -0,0 +1,186 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::VecDeque, sync::Arc};
+use crate::{chain::Client, protocol::message::{StateRequest, StateResponse}};
+use log::{debug, warn};
+use sp_core::storage::{
+	well_known_keys, ChildInfo, ChildType, PrefixedStorageKey, Storage, StorageChild, StorageMap,
+};
+use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
+
+/// Result of importing a state response.
+#[derive(Debug)]
+pub(crate) enum ImportResult<B: BlockT> {
+	/// The state is complete and ready to be imported along with its block.
+	Import(B::Hash, B::Header, Storage),
+	/// More state must be downloaded.
+	Continue(StateRequest<B::Hash>),
+	/// The response is invalid.
+	BadResponse,
+}
+
+/// Downloads the state of a target block, in chunks checked against its state root.
+///
+/// The top trie is downloaded first. The default child tries whose roots it contains are
+/// then downloaded one after the other.
+pub(crate) struct StateSync<B: BlockT> {
+	target_header: B::Header,
+	target_hash: B::Hash,
+	/// Prefixed storage key of the child trie being downloaded, `None` for the top trie.
+	current_child: Option<Vec<u8>>,
+	/// Last key received for the trie being downloaded.
+	last_key: Option<Vec<u8>>,
+	/// Prefixed storage keys of the child tries left to download.
+	pending_children: VecDeque<Vec<u8>>,
+	top: StorageMap,
+	children: Vec<StorageChild>,
+	complete: bool,
+	imported_bytes: u64,
+	client: Arc<dyn Client<B>>,
+}
+
+impl<B: BlockT> StateSync<B> {
+	/// Create a new instance, downloading the state of the given block.
+	pub fn new(client: Arc<dyn Client<B>>, target_header: B::Header) -> Self {
+		StateSync {
+			target_hash: target_header.hash(),
+			target_header,
+			current_child: None,
+			last_key: None,
+			pending_children: VecDeque::new(),
+			top: StorageMap::new(),
+			children: Vec::new(),
+			complete: false,
+			imported_bytes: 0,
+			client,
+		}
+	}
+
+	/// Validate and import a state response.
+	pub fn import(&mut self, response: StateResponse) -> ImportResult<B> {
+		if self.complete {
+			debug!(target: "sync", "Unexpected state response for completed state sync");
+			return ImportResult::BadResponse
+		}
+
+		let child_info = self.current_child.as_ref().and_then(|key| default_child_info(key));
+		let (entries, complete) = match self.client.verify_range_proof(
+			*self.target_header.state_root(),
+			response.proof,
+			child_info.as_ref(),
+			self.last_key.as_ref().map(|key| &key[..]),
+		) {
+			Ok(result) => result,
+			Err(e) => {
+				debug!(target: "sync", "Bad state response: {:?}", e);
+				return ImportResult::BadResponse
+			}
+		};
+
+		if let Some((key, _)) = entries.last() {
+			self.last_key = Some(key.clone());
+		}
+		for (key, value) in entries {
+			self.imported_bytes += (key.len() + value.len()) as u64;
+			if child_info.is_some() {
+				if let Some(child) = self.children.last_mut() {
+					child.data.insert(key, value);
+				}
+			} else if well_known_keys::is_child_storage_key(&key) {
+				// Child roots are computed again when the state is imported.
+				if default_child_info(&key).is_some() {
+					self.pending_children.push_back(key);
+				} else {
+					warn!(target: "sync", "Ignoring child trie of unknown type in state: {:?}", key);
+				}
+			} else {
+				self.top.insert(key, value);
+			}
+		}
+
+		if complete {
+			self.last_key = None;
+			self.current_child = self.pending_children.pop_front();
+			match self.current_child.as_ref().and_then(|key| default_child_info(key)) {
+				Some(child_info) => self.children.push(StorageChild {
+					data: StorageMap::new(),
+					child_info,
+				}),
+				None => self.complete = true,
+			}
+		}
+
+		if self.complete {
+			debug!(
+				target: "sync",
+				"State sync of #{} ({}) complete, {} bytes",
+				self.target_header.number(),
+				self.target_hash,
+				self.imported_bytes,
+			);
+			let storage = Storage {
+				top: std::mem::take(&mut self.top),
+				children_default: std::mem::take(&mut self.children)
+					.into_iter()
+					.map(|child| (child.child_info.storage_key().to_vec(), child))
+					.collect(),
+			};
+			ImportResult::Import(self.target_hash, self.target_header.clone(), storage)
+		} else {
+			ImportResult::Continue(self.next_request())
+		}
+	}
+
+	/// Produce the next state request.
+	pub fn next_request(&self) -> StateRequest<B::Hash> {
+		StateRequest {
+			block: self.target_hash,
+			child_key: self.current_child.clone(),
+			start: self.last_key.clone(),
+		}
+	}
+
+	/// Whether the whole state has been downloaded.
+	pub fn is_complete(&self) -> bool {
+		self.complete
+	}
+
+	/// Number of the block whose state is downloaded.
+	pub fn target_block_num(&self) -> NumberFor<B> {
+		*self.target_header.number()
+	}
+
+	/// Hash of the block whose state is downloaded.
+	pub fn target(&self) -> B::Hash {
+		self.target_hash
+	}
+
+	/// Number of bytes of key-values downloaded so far.
+	pub fn imported_bytes(&self) -> u64 {
+		self.imported_bytes
+	}
+}
+
+/// Child info of the default child trie stored at the given prefixed key.
+fn default_child_info(prefixed_key: &Vec<u8>) -> Option<ChildInfo> {
+	match ChildType::from_prefixed_key(PrefixedStorageKey::new_ref(prefixed_key)) {
+		Some((ChildType::ParentKeyId, storage_key)) => Some(ChildInfo::new_default(storage_key)),
+		None => None,
+	}
+}
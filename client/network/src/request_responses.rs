@@ -0,0 +1,660 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Collection of request-response protocols.
+//!
+//! The [`RequestResponsesBehaviour`] struct defined in this module provides support for zero or
+//! more so-called "request-response" protocols, each of them described by a [`ProtocolConfig`].
+//!
+//! A request-response protocol works in the following way:
+//!
+//! - For every emitted request, a new substream is open and the protocol is negotiated. If the
+//! remote supports the protocol, the size of the request is sent as a LEB128 number, followed
+//! with the request itself. The remote then sends the size of the response as a LEB128 number,
+//! followed with the response, and closes the substream.
+//!
+//! - Requests have a certain time limit before they time out. This time includes the time it
+//! takes to send/receive the request and response.
+//!
+//! - If provided, a ["requests processing"](ProtocolConfig::inbound_queue) channel is used to
+//! handle incoming requests. Inbound requests are refused if no such channel has been provided.
+//!
+//! The content of requests and responses is opaque to this module, and is entirely up to the
+//! users of each protocol.
+
+use crate::config::ProtocolId;
+use futures::{
+	channel::{mpsc, oneshot},
+	future::BoxFuture,
+	prelude::*,
+	stream::FuturesUnordered,
+};
+use futures_timer::Delay;
+use libp2p::{
+	core::{
+		ConnectedPoint,
+		Multiaddr,
+		PeerId,
+		connection::ConnectionId,
+		upgrade::{InboundUpgrade, OutboundUpgrade, ReadOneError, UpgradeInfo},
+		upgrade::{read_one, write_one}
+	},
+	swarm::{
+		NegotiatedSubstream,
+		NetworkBehaviour,
+		NetworkBehaviourAction,
+		NotifyHandler,
+		OneShotHandler,
+		OneShotHandlerConfig,
+		PollParameters,
+		SubstreamProtocol
+	}
+};
+use std::{
+	borrow::Cow,
+	collections::{HashMap, VecDeque},
+	convert::TryFrom as _,
+	iter,
+	pin::Pin,
+	sync::Arc,
+	task::{Context, Poll},
+	time::Duration,
+};
+use wasm_timer::Instant;
+
+/// Configuration for a single request-response protocol.
+#[derive(Debug, Clone)]
+pub struct ProtocolConfig {
+	/// Name of the protocol on the wire. Should be something like `/foo/bar`.
+	pub name: Cow<'static, [u8]>,
+
+	/// Maximum allowed size, in bytes, of a request.
+	///
+	/// Any request larger than this value will be declined as a way to avoid allocating too
+	/// much memory for it.
+	pub max_request_size: u64,
+
+	/// Maximum allowed size, in bytes, of a response.
+	///
+	/// Any response larger than this value will be declined as a way to avoid allocating too
+	/// much memory for it.
+	pub max_response_size: u64,
+
+	/// Duration after which emitted requests are considered timed out.
+	///
+	/// If you expect the response to come back quickly, you should set this to a smaller duration.
+	pub request_timeout: Duration,
+
+	/// Channel on which the networking service will send incoming requests.
+	///
+	/// Every time a peer sends a request to the local node using this protocol, the networking
+	/// service will push an element on this channel. The receiving side of this channel then has
+	/// to pull this element, process the request, and send back the response to send back to the
+	/// peer.
+	///
+	/// The size of the channel has to be carefully chosen. If the channel is full, the networking
+	/// service will discard the incoming request. If the channel is closed, the incoming request
+	/// is discarded as well.
+	///
+	/// If this is `None`, then the local node will not advertise support for this protocol towards
+	/// other peers. If this is `Some` but the channel is closed, then the local node will
+	/// advertise support for this protocol, but any incoming request will be discarded.
+	pub inbound_queue: Option<mpsc::Sender<IncomingRequest>>,
+}
+
+/// A single request received by a peer on a request-response protocol.
+#[derive(Debug)]
+pub struct IncomingRequest {
+	/// Who sent the request.
+	pub peer: PeerId,
+
+	/// Request sent by the remote. Will always be smaller than
+	/// [`ProtocolConfig::max_request_size`].
+	pub payload: Vec<u8>,
+
+	/// Channel to send back the response to.
+	///
+	/// Dropping this sender without sending a response refuses the request, in which case the
+	/// substream is closed without an answer.
+	pub pending_response: oneshot::Sender<Vec<u8>>,
+}
+
+/// Event generated by the [`RequestResponsesBehaviour`].
+#[derive(Debug)]
+pub enum Event {
+	/// A remote sent a request and either we have successfully answered it or an error happened.
+	///
+	/// This event is generated for statistics purposes.
+	InboundRequest {
+		/// Peer which has emitted the request.
+		peer: PeerId,
+		/// Name of the protocol in question.
+		protocol: Cow<'static, [u8]>,
+		/// If `Ok`, contains the time elapsed between when we received the request and when we
+		/// sent back the response. If `Err`, the error that happened.
+		result: Result<Duration, ResponseFailure>,
+	},
+
+	/// A request initiated using [`RequestResponsesBehaviour::send_request`] has succeeded or
+	/// failed. Its outcome has been sent to the channel passed to `send_request`.
+	///
+	/// This event is generated for statistics purposes.
+	RequestFinished {
+		/// Peer that we sent the request to.
+		peer: PeerId,
+		/// Name of the protocol in question.
+		protocol: Cow<'static, [u8]>,
+		/// Time elapsed between the start of the request and its end.
+		request_duration: Duration,
+	},
+}
+
+/// Outcome of calling [`RequestResponsesBehaviour::send_request`].
+#[derive(Debug)]
+#[must_use]
+pub enum SendRequestOutcome {
+	/// Request has been emitted.
+	Ok,
+	/// Didn't start a request because we have no connection to this node. The request has
+	/// failed with [`RequestFailure::NotConnected`].
+	NotConnected,
+	/// Didn't start a request because the protocol isn't registered. The request has failed
+	/// with [`RequestFailure::UnknownProtocol`].
+	UnknownProtocol,
+}
+
+/// Implementation of `NetworkBehaviour` that provides support for request-response protocols.
+pub struct RequestResponsesBehaviour {
+	/// The multiple sub-protocols, by name.
+	protocols: HashMap<Cow<'static, [u8]>, ProtocolConfig>,
+	/// List of all active connections, by peer.
+	peers: HashMap<PeerId, Vec<ConnectionId>>,
+	/// Requests we've emitted and that haven't finished yet, by request identifier.
+	pending_requests: HashMap<u64, PendingRequest>,
+	/// Identifier of the next request we emit.
+	next_request_id: u64,
+	/// Futures sending back the responses to inbound requests.
+	pending_responses: FuturesUnordered<
+		BoxFuture<'static, (PeerId, Cow<'static, [u8]>, Result<Duration, ResponseFailure>)>
+	>,
+	/// Events to return as soon as possible from `poll`.
+	pending_events: VecDeque<NetworkBehaviourAction<OutboundRequest, Event>>,
+}
+
+/// Request we have emitted and for which we are waiting for a response.
+struct PendingRequest {
+	/// Peer the request has been sent to.
+	peer: PeerId,
+	/// Connection the request has been sent on.
+	connection: ConnectionId,
+	/// Name of the protocol of the request.
+	protocol: Cow<'static, [u8]>,
+	/// `Instant` when the request has been emitted. Used for diagnostic purposes.
+	emitted: Instant,
+	/// Fires when the request must be considered as failed.
+	timeout: Delay,
+	/// Where to send the outcome of the request.
+	pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+}
+
+impl PendingRequest {
+	/// Send the outcome of the request and return its duration.
+	fn finish(self, result: Result<Vec<u8>, RequestFailure>) -> Duration {
+		// The requester may no longer be interested in the response.
+		let _ = self.pending_response.send(result);
+		self.emitted.elapsed()
+	}
+}
+
+impl RequestResponsesBehaviour {
+	/// Creates a new behaviour. Must be passed a list of supported protocols. Returns an error if
+	/// the same protocol is passed twice.
+	pub fn new(list: impl Iterator<Item = ProtocolConfig>) -> Result<Self, RegisterError> {
+		let mut protocols = HashMap::new();
+		for protocol in list {
+			if protocols.contains_key(&protocol.name) {
+				return Err(RegisterError::DuplicateProtocol(protocol.name))
+			}
+			protocols.insert(protocol.name.clone(), protocol);
+		}
+
+		Ok(RequestResponsesBehaviour {
+			protocols,
+			peers: HashMap::new(),
+			pending_requests: HashMap::new(),
+			next_request_id: 0,
+			pending_responses: FuturesUnordered::new(),
+			pending_events: VecDeque::new(),
+		})
+	}
+
+	/// Initiates sending a request.
+	///
+	/// An error is returned if we are not connected to the target peer or if the protocol
+	/// doesn't match one that has been registered. In both cases, the error is also sent to
+	/// `pending_response`, which otherwise receives the response once it arrives.
+	pub fn send_request(
+		&mut self,
+		target: &PeerId,
+		protocol: &[u8],
+		request: Vec<u8>,
+		pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+	) -> SendRequestOutcome {
+		let (protocol, max_response_size, request_timeout) = match self.protocols.get(protocol) {
+			Some(config) => (config.name.clone(), config.max_response_size, config.request_timeout),
+			None => {
+				let _ = pending_response.send(Err(RequestFailure::UnknownProtocol));
+				return SendRequestOutcome::UnknownProtocol
+			}
+		};
+
+		let connection = match self.peers.get(target).and_then(|connections| connections.first()) {
+			Some(connection) => *connection,
+			None => {
+				let _ = pending_response.send(Err(RequestFailure::NotConnected));
+				return SendRequestOutcome::NotConnected
+			}
+		};
+
+		let request_id = self.next_request_id;
+		self.next_request_id += 1;
+
+		self.pending_requests.insert(request_id, PendingRequest {
+			peer: target.clone(),
+			connection,
+			protocol: protocol.clone(),
+			emitted: Instant::now(),
+			timeout: Delay::new(request_timeout),
+			pending_response,
+		});
+
+		log::trace!(
+			target: "sub-libp2p",
+			"Enqueueing {} request to {:?} ({} bytes)",
+			String::from_utf8_lossy(&protocol),
+			target,
+			request.len(),
+		);
+		self.pending_events.push_back(NetworkBehaviourAction::NotifyHandler {
+			peer_id: target.clone(),
+			handler: NotifyHandler::One(connection),
+			event: OutboundRequest {
+				protocol,
+				request,
+				request_id,
+				max_response_size: usize::try_from(max_response_size).unwrap_or(usize::max_value()),
+			},
+		});
+
+		SendRequestOutcome::Ok
+	}
+
+	/// Callback, invoked when a new request has been received from remote.
+	fn on_request(
+		&mut self,
+		peer: PeerId,
+		protocol: Cow<'static, [u8]>,
+		request: Vec<u8>,
+		mut substream: NegotiatedSubstream,
+		handling_start: Instant,
+	) {
+		let inbound_queue = match self.protocols.get_mut(&protocol) {
+			Some(config) => config.inbound_queue.as_mut(),
+			None => None,
+		};
+		let inbound_queue = match inbound_queue {
+			Some(inbound_queue) => inbound_queue,
+			None => {
+				log::error!(
+					target: "sub-libp2p",
+					"State inconsistency: request received on a protocol we don't answer"
+				);
+				return
+			}
+		};
+
+		let (tx, rx) = oneshot::channel();
+		let incoming = IncomingRequest { peer: peer.clone(), payload: request, pending_response: tx };
+
+		// If the queue is full or closed, the request is dropped without an answer, which
+		// closes the substream.
+		if let Err(err) = inbound_queue.try_send(incoming) {
+			log::debug!(
+				target: "sub-libp2p",
+				"Discarding {} request from {}: {}",
+				String::from_utf8_lossy(&protocol),
+				peer,
+				if err.is_full() { "handler is busy" } else { "handler is gone" },
+			);
+			let ev = Event::InboundRequest { peer, protocol, result: Err(ResponseFailure::Busy) };
+			self.pending_events.push_back(NetworkBehaviourAction::GenerateEvent(ev));
+			return
+		}
+
+		self.pending_responses.push(async move {
+			let result = match rx.await {
+				Ok(response) => match write_one(&mut substream, response).await {
+					Ok(()) => Ok(handling_start.elapsed()),
+					Err(err) => {
+						log::debug!(target: "sub-libp2p", "Error writing response: {}", err);
+						Err(ResponseFailure::Network)
+					}
+				},
+				Err(oneshot::Canceled) => Err(ResponseFailure::Refused),
+			};
+			(peer, protocol, result)
+		}.boxed());
+	}
+}
+
+impl NetworkBehaviour for RequestResponsesBehaviour {
+	type ProtocolsHandler = OneShotHandler<InboundProtocols, OutboundRequest, HandlerEvent<NegotiatedSubstream>>;
+	type OutEvent = Event;
+
+	fn new_handler(&mut self) -> Self::ProtocolsHandler {
+		// Only the protocols we answer are advertised to remotes.
+		let protocols = self.protocols.values()
+			.filter(|config| config.inbound_queue.is_some())
+			.map(|config| (
+				config.name.clone(),
+				usize::try_from(config.max_request_size).unwrap_or(usize::max_value()),
+			))
+			.collect();
+		let p = InboundProtocols { protocols: Arc::new(protocols) };
+
+		// Each request has its own timeout, enforced by the behaviour. The substream timeout
+		// only has to be at least as long as the longest of them.
+		let mut cfg = OneShotHandlerConfig::default();
+		cfg.keep_alive_timeout = Duration::from_secs(15);
+		cfg.outbound_substream_timeout = self.protocols.values()
+			.map(|config| config.request_timeout)
+			.max()
+			.unwrap_or(cfg.outbound_substream_timeout);
+		OneShotHandler::new(SubstreamProtocol::new(p), cfg)
+	}
+
+	fn addresses_of_peer(&mut self, _: &PeerId) -> Vec<Multiaddr> {
+		Vec::new()
+	}
+
+	fn inject_connected(&mut self, _peer: &PeerId) {
+	}
+
+	fn inject_disconnected(&mut self, _peer: &PeerId) {
+	}
+
+	fn inject_connection_established(&mut self, peer_id: &PeerId, id: &ConnectionId, _: &ConnectedPoint) {
+		self.peers.entry(peer_id.clone()).or_default().push(*id);
+	}
+
+	fn inject_connection_closed(&mut self, peer_id: &PeerId, id: &ConnectionId, _: &ConnectedPoint) {
+		if let Some(connections) = self.peers.get_mut(peer_id) {
+			connections.retain(|c| c != id);
+			if connections.is_empty() {
+				self.peers.remove(peer_id);
+			}
+		} else {
+			log::error!(
+				target: "sub-libp2p",
+				"State inconsistency: peer_id not found in list of connections"
+			);
+		}
+
+		let closed = self.pending_requests.iter()
+			.filter(|(_, request)| request.connection == *id)
+			.map(|(request_id, _)| *request_id)
+			.collect::<Vec<_>>();
+		for request_id in closed {
+			if let Some(request) = self.pending_requests.remove(&request_id) {
+				log::debug!(
+					target: "sub-libp2p",
+					"Connection {:?} with {} closed with ongoing {} request",
+					id,
+					peer_id,
+					String::from_utf8_lossy(&request.protocol),
+				);
+				let ev = Event::RequestFinished {
+					peer: peer_id.clone(),
+					protocol: request.protocol.clone(),
+					request_duration: request.finish(Err(RequestFailure::Refused)),
+				};
+				self.pending_events.push_back(NetworkBehaviourAction::GenerateEvent(ev));
+			}
+		}
+	}
+
+	fn inject_event(
+		&mut self,
+		peer: PeerId,
+		_connection: ConnectionId,
+		event: HandlerEvent<NegotiatedSubstream>
+	) {
+		match event {
+			HandlerEvent::Request { protocol, request, substream, handling_start } =>
+				self.on_request(peer, protocol, request, substream, handling_start),
+			HandlerEvent::Response { request_id, response } => {
+				match self.pending_requests.remove(&request_id) {
+					Some(request) => {
+						let ev = Event::RequestFinished {
+							peer,
+							protocol: request.protocol.clone(),
+							request_duration: request.finish(Ok(response)),
+						};
+						self.pending_events.push_back(NetworkBehaviourAction::GenerateEvent(ev));
+					}
+					None => {
+						// Requests are removed when they time out, so this can legitimately
+						// happen.
+						log::trace!(
+							target: "sub-libp2p",
+							"Response discarded because it concerns an obsolete request"
+						);
+					}
+				}
+			}
+		}
+	}
+
+	fn poll(&mut self, cx: &mut Context, _: &mut impl PollParameters)
+		-> Poll<NetworkBehaviourAction<OutboundRequest, Event>>
+	{
+		if let Some(ev) = self.pending_events.pop_front() {
+			return Poll::Ready(ev);
+		}
+
+		// Check the request timeouts.
+		let timed_out = self.pending_requests.iter_mut()
+			.find_map(|(request_id, request)| {
+				if Pin::new(&mut request.timeout).poll(cx).is_ready() {
+					Some(*request_id)
+				} else {
+					None
+				}
+			});
+		if let Some(request) = timed_out.and_then(|id| self.pending_requests.remove(&id)) {
+			log::debug!(
+				target: "sub-libp2p",
+				"{} request to {} timed out",
+				String::from_utf8_lossy(&request.protocol),
+				request.peer,
+			);
+			let ev = Event::RequestFinished {
+				peer: request.peer.clone(),
+				protocol: request.protocol.clone(),
+				request_duration: request.finish(Err(RequestFailure::Timeout)),
+			};
+			return Poll::Ready(NetworkBehaviourAction::GenerateEvent(ev));
+		}
+
+		if let Poll::Ready(Some((peer, protocol, result))) = self.pending_responses.poll_next_unpin(cx) {
+			let ev = Event::InboundRequest { peer, protocol, result };
+			return Poll::Ready(NetworkBehaviourAction::GenerateEvent(ev));
+		}
+
+		Poll::Pending
+	}
+}
+
+/// Error when registering a protocol.
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum RegisterError {
+	/// A protocol has been specified multiple times.
+	#[display(fmt = "Protocol registered multiple times: {}", "String::from_utf8_lossy(_0)")]
+	DuplicateProtocol(#[error(ignore)] Cow<'static, [u8]>),
+}
+
+/// Error in a request.
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum RequestFailure {
+	/// We are not connected to the requested peer.
+	#[display(fmt = "Not connected to the peer")]
+	NotConnected,
+	/// The given protocol hasn't been registered.
+	#[display(fmt = "Protocol is not registered")]
+	UnknownProtocol,
+	/// The connection closed before the peer answered, or the peer answered improperly.
+	#[display(fmt = "Request refused by the peer")]
+	Refused,
+	/// The peer didn't answer in time.
+	#[display(fmt = "Request timed out")]
+	Timeout,
+}
+
+/// Error when processing a request sent by a remote.
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum ResponseFailure {
+	/// The inbound queue of the protocol is full or closed.
+	#[display(fmt = "Request handler is busy")]
+	Busy,
+	/// The request handler dropped the request without answering it.
+	#[display(fmt = "Request refused by the handler")]
+	Refused,
+	/// Error while sending the response back.
+	#[display(fmt = "Failed to send the response")]
+	Network,
+}
+
+/// Generates the name of a request-response protocol of the chain identified by `protocol_id`,
+/// e.g. `/dot/sync/2`.
+pub(crate) fn protocol_name(protocol_id: &ProtocolId, suffix: &[u8]) -> Cow<'static, [u8]> {
+	let mut v = Vec::new();
+	v.extend_from_slice(b"/");
+	v.extend_from_slice(protocol_id.as_bytes());
+	v.extend_from_slice(suffix);
+	Cow::Owned(v)
+}
+
+/// Output type of inbound and outbound substream upgrades.
+#[derive(Debug)]
+pub enum HandlerEvent<T> {
+	/// Incoming request from remote, substream to use for the response, and when we started
+	/// handling this request.
+	Request {
+		protocol: Cow<'static, [u8]>,
+		request: Vec<u8>,
+		substream: T,
+		handling_start: Instant,
+	},
+	/// Incoming response from remote to the request with the given identifier.
+	Response {
+		request_id: u64,
+		response: Vec<u8>,
+	},
+}
+
+/// Substream upgrade protocol.
+///
+/// Accepts any of the protocols that we answer, and reads the incoming request, which will
+/// then be forwarded to the inbound queue of the protocol.
+#[derive(Debug, Clone)]
+pub struct InboundProtocols {
+	/// The protocols we answer, with the max. request length in bytes of each of them.
+	protocols: Arc<Vec<(Cow<'static, [u8]>, usize)>>,
+}
+
+impl UpgradeInfo for InboundProtocols {
+	type Info = Cow<'static, [u8]>;
+	type InfoIter = std::vec::IntoIter<Self::Info>;
+
+	fn protocol_info(&self) -> Self::InfoIter {
+		self.protocols.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().into_iter()
+	}
+}
+
+impl<T> InboundUpgrade<T> for InboundProtocols
+where
+	T: AsyncRead + AsyncWrite + Unpin + Send + 'static
+{
+	type Output = HandlerEvent<T>;
+	type Error = ReadOneError;
+	type Future = BoxFuture<'static, Result<Self::Output, Self::Error>>;
+
+	fn upgrade_inbound(self, mut s: T, protocol: Self::Info) -> Self::Future {
+		// This `Instant` will be passed around until the processing of this request is done.
+		let handling_start = Instant::now();
+		let max_request_len = self.protocols.iter()
+			.find(|(name, _)| *name == protocol)
+			.map_or(0, |(_, max_request_len)| *max_request_len);
+
+		async move {
+			let request = read_one(&mut s, max_request_len).await?;
+			Ok(HandlerEvent::Request { protocol, request, substream: s, handling_start })
+		}.boxed()
+	}
+}
+
+/// Substream upgrade protocol.
+///
+/// Sends a request to remote and awaits the response.
+#[derive(Debug, Clone)]
+pub struct OutboundRequest {
+	/// The protocol to use for upgrade negotiation.
+	protocol: Cow<'static, [u8]>,
+	/// The request to send.
+	request: Vec<u8>,
+	/// Identifier of the request. Passed back through the API when the response comes back.
+	request_id: u64,
+	/// The max. response length in bytes.
+	max_response_size: usize,
+}
+
+impl UpgradeInfo for OutboundRequest {
+	type Info = Cow<'static, [u8]>;
+	type InfoIter = iter::Once<Self::Info>;
+
+	fn protocol_info(&self) -> Self::InfoIter {
+		iter::once(self.protocol.clone())
+	}
+}
+
+impl<T> OutboundUpgrade<T> for OutboundRequest
+where
+	T: AsyncRead + AsyncWrite + Unpin + Send + 'static
+{
+	type Output = HandlerEvent<T>;
+	type Error = ReadOneError;
+	type Future = BoxFuture<'static, Result<Self::Output, Self::Error>>;
+
+	fn upgrade_outbound(self, mut s: T, _: Self::Info) -> Self::Future {
+		async move {
+			write_one(&mut s, &self.request).await?;
+			let response = read_one(&mut s, self.max_response_size).await?;
+			Ok(HandlerEvent::Response { request_id: self.request_id, response })
+		}.boxed()
+	}
+}
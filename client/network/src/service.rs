@@ -38,11 +38,12 @@ use crate::{
 		NetworkState, NotConnectedPeer as NetworkStateNotConnectedPeer, Peer as NetworkStatePeer,
 	},
 	on_demand_layer::AlwaysBadChecker,
-	light_client_handler, block_requests, finality_requests,
+	light_client_requests, block_requests, finality_requests, state_requests, warp_sync_requests,
 	protocol::{self, event::Event, NotifsHandlerError, LegacyConnectionKillError, NotificationsSink, Ready, sync::SyncState, PeerInfo, Protocol},
+	request_responses::{self, RequestFailure},
 	transport, ReputationChange,
 };
-use futures::prelude::*;
+use futures::{channel::oneshot, future::BoxFuture, prelude::*, stream::FuturesUnordered};
 use libp2p::{PeerId, multiaddr, Multiaddr};
 use libp2p::core::{ConnectedPoint, Executor, connection::{ConnectionError, PendingConnectionError}, either::EitherError};
 use libp2p::kad::record;
@@ -65,6 +66,7 @@ use std::{
 	borrow::{Borrow, Cow},
 	collections::{HashMap, HashSet},
 	fs,
+	iter,
 	marker::PhantomData,
 	num:: NonZeroUsize,
 	pin::Pin,
@@ -102,6 +104,8 @@ pub struct NetworkService<B: BlockT + 'static, H: ExHashT> {
 	peers_notifications_sinks: Arc<Mutex<HashMap<(PeerId, ConsensusEngineId), NotificationsSink>>>,
 	/// For each legacy gossiping engine ID, the corresponding new protocol name.
 	protocol_name_by_engine: Mutex<HashMap<ConsensusEngineId, Cow<'static, [u8]>>>,
+	/// Name of the request-response protocol used for warp sync requests.
+	warp_sync_protocol_name: Cow<'static, [u8]>,
 	/// Field extracted from the [`Metrics`] struct and necessary to report the
 	/// notifications-related metrics.
 	notifications_sizes_metric: Option<HistogramVec>,
@@ -241,6 +245,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			protocol::ProtocolConfig {
 				roles: From::from(&params.role),
 				max_parallel_downloads: params.network_config.max_parallel_downloads,
+				sync_mode: params.network_config.sync_mode,
 			},
 			local_peer_id.clone(),
 			params.chain.clone(),
@@ -254,6 +259,10 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			boot_node_ids.clone(),
 		)?;
 
+		// Handlers of the request-response protocols built into the network.
+		let mut request_handlers = FuturesUnordered::new();
+		let warp_sync_protocol_name = warp_sync_requests::generate_protocol_config(&params.protocol_id).name;
+
 		// Build the swarm.
 		let (mut swarm, bandwidth): (Swarm<B, H>, _) = {
 			let user_agent = format!(
@@ -261,23 +270,58 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 				params.network_config.client_version,
 				params.network_config.node_name
 			);
-			let block_requests = {
-				let config = block_requests::Config::new(&params.protocol_id);
-				block_requests::BlockRequests::new(config, params.chain.clone())
+			let block_request_protocol_config = {
+				let (handler, protocol_config) = block_requests::BlockRequestHandler::new(
+					&params.protocol_id,
+					params.chain.clone(),
+				);
+				request_handlers.push(handler.run().boxed());
+				protocol_config
+			};
+			let state_request_protocol_config = {
+				let (handler, protocol_config) = state_requests::StateRequestHandler::new(
+					&params.protocol_id,
+					params.chain.clone(),
+				);
+				request_handlers.push(handler.run().boxed());
+				protocol_config
 			};
-			let finality_proof_requests = {
-				let config = finality_requests::Config::new(&params.protocol_id);
-				finality_requests::FinalityProofRequests::new(config, params.finality_proof_provider.clone())
+			let finality_proof_request_protocol_config = match params.finality_proof_provider.clone() {
+				Some(provider) => {
+					let (handler, protocol_config) = finality_requests::FinalityProofRequestHandler::new(
+						&params.protocol_id,
+						provider,
+					);
+					request_handlers.push(handler.run().boxed());
+					protocol_config
+				}
+				None => finality_requests::generate_protocol_config(&params.protocol_id),
+			};
+			let warp_sync_request_protocol_config = match params.warp_sync_provider {
+				Some(provider) => {
+					let (handler, protocol_config) = warp_sync_requests::WarpSyncRequestHandler::new(
+						&params.protocol_id,
+						provider,
+					);
+					request_handlers.push(handler.run().boxed());
+					protocol_config
+				}
+				None => warp_sync_requests::generate_protocol_config(&params.protocol_id),
 			};
-			let light_client_handler = {
-				let config = light_client_handler::Config::new(&params.protocol_id);
-				light_client_handler::LightClientHandler::new(
-					config,
+			let light_client_request_protocol_config = {
+				let (handler, protocol_config) = light_client_requests::LightClientRequestHandler::new(
+					&params.protocol_id,
 					params.chain,
-					checker,
 					peerset_handle.clone(),
-				)
+				);
+				request_handlers.push(handler.run().boxed());
+				protocol_config
 			};
+			let light_client_request_sender = light_client_requests::LightClientRequestSender::new(
+				&params.protocol_id,
+				checker,
+				peerset_handle.clone(),
+			);
 
 			let discovery_config = {
 				let mut config = DiscoveryConfig::new(local_public.clone());
@@ -305,11 +349,19 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 				params.role,
 				user_agent,
 				local_public,
-				block_requests,
-				finality_proof_requests,
-				light_client_handler,
+				block_request_protocol_config,
+				state_request_protocol_config,
+				finality_proof_request_protocol_config,
+				iter::once(warp_sync_request_protocol_config)
+					.chain(iter::once(light_client_request_protocol_config))
+					.chain(params.network_config.request_response_protocols.iter().cloned())
+					.collect(),
+				light_client_request_sender,
 				discovery_config
-			);
+			).map_err(|err| match err {
+				request_responses::RegisterError::DuplicateProtocol(protocol) =>
+					Error::DuplicateRequestResponseProtocol { protocol },
+			})?;
 
 			for (engine_id, protocol_name) in &params.network_config.notifications_protocols {
 				behaviour.register_notifications_protocol(*engine_id, protocol_name.clone());
@@ -366,6 +418,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			to_worker,
 			peers_notifications_sinks: peers_notifications_sinks.clone(),
 			protocol_name_by_engine,
+			warp_sync_protocol_name,
 			notifications_sizes_metric:
 				metrics.as_ref().map(|metrics| metrics.notifications_sizes.clone()),
 			_marker: PhantomData,
@@ -380,6 +433,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			import_queue: params.import_queue,
 			from_service,
 			light_client_rqs: params.on_demand.and_then(|od| od.extract_receiver()),
+			request_handlers,
 			event_streams: out_events::OutChannels::new(params.metrics_registry.as_ref())?,
 			peers_notifications_sinks,
 			metrics,
@@ -807,6 +861,61 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkService<B, H> {
 			.unbounded_send(ServiceToWorkerMsg::RequestJustification(*hash, number));
 	}
 
+	/// Sends a single targeted request to a specific peer. On success, returns the response of
+	/// the peer.
+	///
+	/// Request-response protocols are a way to complement notifications protocols, but
+	/// notifications should remain the default ways of communicating information. For example, a
+	/// peer can announce something through a notification, after which the recipient can obtain
+	/// more information by performing a request.
+	/// As such, this function is meant to be called only with peers we are already connected to.
+	/// Calling this method with a `target` we are not connected to will *not* attempt to connect
+	/// to said peer, and the request fails with [`RequestFailure::NotConnected`].
+	///
+	/// The protocol must have been registered through
+	/// [`NetworkConfiguration::request_response_protocols`](crate::config::NetworkConfiguration::request_response_protocols).
+	pub async fn request(
+		&self,
+		target: PeerId,
+		protocol: impl Into<Cow<'static, [u8]>>,
+		request: Vec<u8>,
+	) -> Result<Vec<u8>, RequestFailure> {
+		let (tx, rx) = oneshot::channel();
+		let _ = self.to_worker.unbounded_send(ServiceToWorkerMsg::Request {
+			target,
+			protocol: protocol.into(),
+			request,
+			pending_response: tx,
+		});
+
+		match rx.await {
+			Ok(result) => result,
+			// the worker dropped the request, e.g. because it is shutting down.
+			Err(oneshot::Canceled) => Err(RequestFailure::Refused),
+		}
+	}
+
+	/// Request a warp sync proof starting from the finalized block `begin` from the given peer.
+	///
+	/// The returned future resolves to the proof, encoded as defined by the finality gadget, or
+	/// to the reason why the request failed. Proofs aren't checked by the network.
+	pub async fn warp_sync_request(
+		&self,
+		target: PeerId,
+		begin: B::Hash,
+	) -> Result<Vec<u8>, RequestFailure> {
+		let request = warp_sync_requests::encode_warp_sync_request(&begin);
+		let response = self.request(target.clone(), self.warp_sync_protocol_name.clone(), request).await?;
+
+		warp_sync_requests::decode_warp_sync_response(&response).map_err(|err| {
+			log::debug!(
+				target: "sync",
+				"Failed to decode warp sync response from peer {}: {}", target, err
+			);
+			RequestFailure::Refused
+		})
+	}
+
 	/// Are we in the process of downloading the chain?
 	pub fn is_major_syncing(&self) -> bool {
 		self.is_major_syncing.load(Ordering::Relaxed)
@@ -1066,6 +1175,12 @@ enum ServiceToWorkerMsg<B: BlockT, H: ExHashT> {
 	PropagateTransaction(H),
 	PropagateTransactions,
 	RequestJustification(B::Hash, NumberFor<B>),
+	Request {
+		target: PeerId,
+		protocol: Cow<'static, [u8]>,
+		request: Vec<u8>,
+		pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+	},
 	AnnounceBlock(B::Hash, Vec<u8>),
 	GetValue(record::Key),
 	PutValue(record::Key, Vec<u8>),
@@ -1101,7 +1216,10 @@ pub struct NetworkWorker<B: BlockT + 'static, H: ExHashT> {
 	/// Messages from the [`NetworkService`] that must be processed.
 	from_service: TracingUnboundedReceiver<ServiceToWorkerMsg<B, H>>,
 	/// Receiver for queries from the light client that must be processed.
-	light_client_rqs: Option<TracingUnboundedReceiver<light_client_handler::Request<B>>>,
+	light_client_rqs: Option<TracingUnboundedReceiver<light_client_requests::Request<B>>>,
+	/// Handlers answering the requests of the request-response protocols built into the
+	/// network, polled as part of the worker.
+	request_handlers: FuturesUnordered<BoxFuture<'static, ()>>,
 	/// Senders for events that happen on the network.
 	event_streams: out_events::OutChannels,
 	/// Prometheus network metrics.
@@ -1346,6 +1464,9 @@ impl<B: BlockT + 'static, H: ExHashT> Future for NetworkWorker<B, H> {
 			protocol: &mut this.network_service,
 		});
 
+		// Answer the requests received on the request-response protocols built into the network.
+		while let Poll::Ready(Some(())) = this.request_handlers.poll_next_unpin(cx) {}
+
 		// Check for new incoming light client requests.
 		if let Some(light_client_rqs) = this.light_client_rqs.as_mut() {
 			while let Poll::Ready(Some(rq)) = light_client_rqs.poll_next_unpin(cx) {
@@ -1372,6 +1493,8 @@ impl<B: BlockT + 'static, H: ExHashT> Future for NetworkWorker<B, H> {
 					this.network_service.user_protocol_mut().announce_block(hash, data),
 				ServiceToWorkerMsg::RequestJustification(hash, number) =>
 					this.network_service.user_protocol_mut().request_justification(&hash, number),
+				ServiceToWorkerMsg::Request { target, protocol, request, pending_response } =>
+					this.network_service.send_request(&target, &protocol, request, pending_response),
 				ServiceToWorkerMsg::PropagateTransaction(hash) =>
 					this.network_service.user_protocol_mut().propagate_transaction(&hash),
 				ServiceToWorkerMsg::PropagateTransactions =>
@@ -99,6 +99,7 @@ fn build_test_full_node(config: config::NetworkConfiguration)
 		chain: client.clone(),
 		finality_proof_provider: None,
 		finality_proof_request_builder: None,
+		warp_sync_provider: None,
 		on_demand: None,
 		transaction_pool: Arc::new(crate::config::EmptyTransactionPool),
 		protocol_id: config::ProtocolId::from(&b"/test-protocol-name"[..]),
@@ -513,3 +514,84 @@ fn ensure_public_addresses_consistent_with_transport_not_memory() {
 		.. config::NetworkConfiguration::new("test-node", "test-client", Default::default(), None)
 	});
 }
+
+#[test]
+fn request_response_protocols_work() {
+	const PROTOCOL_NAME: &[u8] = b"/foo/echo/1";
+
+	let listen_addr = config::build_multiaddr![Memory(rand::random::<u64>())];
+	let protocol_config = |inbound_queue| config::RequestResponseConfig {
+		name: From::from(PROTOCOL_NAME),
+		max_request_size: 1024,
+		max_response_size: 1024,
+		request_timeout: Duration::from_secs(20),
+		inbound_queue,
+	};
+
+	let (inbound_tx, mut inbound_rx) = futures::channel::mpsc::channel(16);
+	let (node1, _) = build_test_full_node(config::NetworkConfiguration {
+		notifications_protocols: vec![(ENGINE_ID, From::from(&b"/foo"[..]))],
+		request_response_protocols: vec![protocol_config(Some(inbound_tx))],
+		listen_addresses: vec![listen_addr.clone()],
+		transport: config::TransportConfig::MemoryOnly,
+		.. config::NetworkConfiguration::new_local()
+	});
+
+	let (node2, mut events_stream2) = build_test_full_node(config::NetworkConfiguration {
+		notifications_protocols: vec![(ENGINE_ID, From::from(&b"/foo"[..]))],
+		request_response_protocols: vec![protocol_config(None)],
+		listen_addresses: vec![],
+		reserved_nodes: vec![config::MultiaddrWithPeerId {
+			multiaddr: listen_addr,
+			peer_id: node1.local_peer_id().clone(),
+		}],
+		transport: config::TransportConfig::MemoryOnly,
+		.. config::NetworkConfiguration::new_local()
+	});
+
+	// The first node answers every request with the reversed payload.
+	let node2_peer_id = node2.local_peer_id().clone();
+	async_std::task::spawn(async move {
+		while let Some(request) = inbound_rx.next().await {
+			assert_eq!(request.peer, node2_peer_id);
+			let mut response = request.payload;
+			response.reverse();
+			let _ = request.pending_response.send(response);
+		}
+	});
+
+	async_std::task::block_on(async move {
+		// Wait for the nodes to be connected.
+		loop {
+			if let Event::NotificationStreamOpened { .. } = events_stream2.next().await.unwrap() {
+				break
+			}
+		}
+
+		let node1_peer_id = node1.local_peer_id().clone();
+		let response = node2.request(node1_peer_id.clone(), PROTOCOL_NAME, b"ping".to_vec()).await;
+		assert_eq!(response.unwrap(), b"gnip".to_vec());
+
+		// Protocols must have been registered.
+		let response = node2.request(node1_peer_id, &b"/foo/unknown/1"[..], b"ping".to_vec()).await;
+		assert!(matches!(response, Err(crate::RequestFailure::UnknownProtocol)));
+	});
+}
+
+#[test]
+#[should_panic(expected = "registered multiple times")]
+fn ensure_request_response_protocols_are_unique() {
+	let protocol_config = config::RequestResponseConfig {
+		name: From::from(&b"/foo/echo/1"[..]),
+		max_request_size: 1024,
+		max_response_size: 1024,
+		request_timeout: Duration::from_secs(20),
+		inbound_queue: None,
+	};
+
+	let _ = build_test_full_node(config::NetworkConfiguration {
+		request_response_protocols: vec![protocol_config.clone(), protocol_config],
+		transport: config::TransportConfig::MemoryOnly,
+		.. config::NetworkConfiguration::new_local()
+	});
+}
@@ -0,0 +1,204 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! State requests, exchanged over the request-response protocol `/<id>/state/1`.
+//!
+//! State requests are emitted by fast sync to download the state of a recent finalized
+//! block. Each response carries a range proof of the key-values following the requested
+//! start key, up to a configured size, which the requester checks against the state root
+//! of the block. [`StateRequestHandler`] answers the requests coming from remotes, while the
+//! helpers of this module encode our own requests and decode their responses. Requests and
+//! responses are encoded as protocol buffers (cf. `state.v1.proto`).
+
+use codec::{Encode, Decode};
+use crate::{
+	chain::Client,
+	config::ProtocolId,
+	protocol::message,
+	request_responses::{self, IncomingRequest, ProtocolConfig},
+	schema,
+};
+use futures::{channel::mpsc, prelude::*};
+use libp2p::PeerId;
+use prost::Message;
+use sp_core::storage::{ChildInfo, ChildType, PrefixedStorageKey};
+use sp_runtime::{generic::BlockId, traits::Block};
+use std::{
+	io,
+	sync::Arc,
+	time::Duration,
+};
+
+// Type alias for convenience.
+pub type Error = Box<dyn std::error::Error + 'static>;
+
+/// Maximum size of the key-values read to answer a request.
+const MAX_RESPONSE_DATA: usize = 2 * 1024 * 1024;
+
+/// Number of incoming requests that can be queued before new ones are refused.
+const INBOUND_QUEUE_SIZE: usize = 20;
+
+/// Generates a [`ProtocolConfig`] for the state request protocol, refusing incoming requests.
+pub fn generate_protocol_config(protocol_id: &ProtocolId) -> ProtocolConfig {
+	ProtocolConfig {
+		name: request_responses::protocol_name(protocol_id, b"/state/1"),
+		max_request_size: 1024 * 1024,
+		max_response_size: 16 * 1024 * 1024,
+		request_timeout: Duration::from_secs(40),
+		inbound_queue: None,
+	}
+}
+
+/// Handler for incoming state requests from a remote peer.
+pub struct StateRequestHandler<B: Block> {
+	/// Blockchain client.
+	chain: Arc<dyn Client<B>>,
+	/// Incoming requests, sent by the network.
+	request_receiver: mpsc::Receiver<IncomingRequest>,
+}
+
+impl<B> StateRequestHandler<B>
+where
+	B: Block,
+{
+	/// Create a new [`StateRequestHandler`], along with the configuration of the protocol to
+	/// register on the network.
+	pub fn new(protocol_id: &ProtocolId, chain: Arc<dyn Client<B>>) -> (Self, ProtocolConfig) {
+		let (tx, request_receiver) = mpsc::channel(INBOUND_QUEUE_SIZE);
+
+		let mut protocol_config = generate_protocol_config(protocol_id);
+		protocol_config.inbound_queue = Some(tx);
+
+		(StateRequestHandler { chain, request_receiver }, protocol_config)
+	}
+
+	/// Run the handler, answering the incoming requests until the network drops its side of
+	/// the queue.
+	pub async fn run(mut self) {
+		while let Some(IncomingRequest { peer, payload, pending_response }) = self.request_receiver.next().await {
+			match self.handle_request(&peer, &payload) {
+				Ok(response) => {
+					log::trace!(
+						target: "sync",
+						"Enqueueing state response for peer {} with {} bytes",
+						peer, response.len()
+					);
+					// The requester may no longer be interested in the response.
+					let _ = pending_response.send(response);
+				}
+				// Dropping `pending_response` refuses the request.
+				Err(e) => log::debug!(
+					target: "sync",
+					"Error handling state request from peer {}: {}", peer, e
+				)
+			}
+		}
+	}
+
+	/// Decode a state request, and build the encoded response.
+	fn handle_request(&self, peer: &PeerId, payload: &[u8]) -> Result<Vec<u8>, Error> {
+		let request = schema::v1::state::StateRequest::decode(payload)?;
+		let response = self.on_state_request(peer, &request)?;
+
+		let mut data = Vec::with_capacity(response.encoded_len());
+		response.encode(&mut data)?;
+		Ok(data)
+	}
+
+	/// Callback, invoked when a new state request has been received from remote.
+	fn on_state_request
+		( &self
+		, peer: &PeerId
+		, request: &schema::v1::state::StateRequest
+		) -> Result<schema::v1::state::StateResponse, Error>
+	{
+		log::trace!(
+			target: "sync",
+			"State request from peer {}: block {:?}, start {:?}, child key {:?}",
+			peer,
+			request.block,
+			request.start,
+			request.child_key);
+
+		let block: B::Hash = Decode::decode(&mut request.block.as_ref())?;
+
+		let child_info = if request.child_key.is_empty() {
+			None
+		} else {
+			match ChildType::from_prefixed_key(PrefixedStorageKey::new_ref(&request.child_key)) {
+				Some((ChildType::ParentKeyId, storage_key)) => Some(ChildInfo::new_default(storage_key)),
+				None => {
+					let msg = "invalid `StateRequest::child_key` value";
+					return Err(io::Error::new(io::ErrorKind::Other, msg).into())
+				}
+			}
+		};
+
+		let start = if request.start.is_empty() {
+			None
+		} else {
+			Some(&request.start[..])
+		};
+
+		let (proof, count) = self.chain.read_range_proof(
+			&BlockId::Hash(block),
+			child_info.as_ref(),
+			start,
+			MAX_RESPONSE_DATA,
+		)?;
+
+		log::trace!(
+			target: "sync",
+			"Proved {} key-values of the state of {:?} for peer {}",
+			count,
+			block,
+			peer,
+		);
+
+		Ok(schema::v1::state::StateResponse { proof: proof.encode() })
+	}
+}
+
+/// Encode a state request, to be sent with the state request protocol.
+pub(crate) fn encode_state_request<Hash: Encode>(
+	request: &message::StateRequest<Hash>,
+) -> Result<Vec<u8>, prost::EncodeError> {
+	let protobuf_rq = build_protobuf_state_request(request);
+
+	let mut buf = Vec::with_capacity(protobuf_rq.encoded_len());
+	protobuf_rq.encode(&mut buf)?;
+	Ok(buf)
+}
+
+/// Decode the response to a state request.
+pub(crate) fn decode_state_response(response: &[u8]) -> Result<message::StateResponse, Error> {
+	let response = schema::v1::state::StateResponse::decode(response)?;
+	let proof = Decode::decode(&mut response.proof.as_ref())?;
+	Ok(message::StateResponse { proof })
+}
+
+/// Build protobuf state request message.
+pub(crate) fn build_protobuf_state_request<Hash: Encode>(
+	request: &message::StateRequest<Hash>,
+) -> schema::v1::state::StateRequest {
+	schema::v1::state::StateRequest {
+		block: request.block.encode(),
+		start: request.start.clone().unwrap_or_default(),
+		child_key: request.child_key.clone().unwrap_or_default(),
+	}
+}
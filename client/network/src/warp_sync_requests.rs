@@ -0,0 +1,137 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Warp sync requests, exchanged over the request-response protocol `/<id>/warp-sync/1`.
+//!
+//! A warp sync proof lets a node prove the latest finalized block of the chain from a block
+//! whose finality it already knows, usually genesis, with a handful of requests. The content
+//! of the proofs is defined by the finality gadget, through the `WarpSyncProvider` passed at
+//! initialization. [`WarpSyncRequestHandler`] answers the requests coming from remotes.
+//! Requests and responses are encoded as protocol buffers (cf. `warp.v1.proto`).
+
+use codec::{Encode, Decode};
+use crate::{
+	chain::WarpSyncProvider,
+	config::ProtocolId,
+	request_responses::{self, IncomingRequest, ProtocolConfig},
+	schema,
+};
+use futures::{channel::mpsc, prelude::*};
+use libp2p::PeerId;
+use prost::Message;
+use sp_runtime::traits::Block;
+use std::{sync::Arc, time::Duration};
+
+// Type alias for convenience.
+pub type Error = Box<dyn std::error::Error + 'static>;
+
+/// Number of incoming requests that can be queued before new ones are refused.
+const INBOUND_QUEUE_SIZE: usize = 10;
+
+/// Generates a [`ProtocolConfig`] for the warp sync request protocol, refusing incoming
+/// requests.
+pub fn generate_protocol_config(protocol_id: &ProtocolId) -> ProtocolConfig {
+	ProtocolConfig {
+		name: request_responses::protocol_name(protocol_id, b"/warp-sync/1"),
+		max_request_size: 1024 * 1024,
+		max_response_size: 16 * 1024 * 1024,
+		request_timeout: Duration::from_secs(40),
+		inbound_queue: None,
+	}
+}
+
+/// Handler for incoming warp sync requests from a remote peer.
+pub struct WarpSyncRequestHandler<B: Block> {
+	/// How to construct warp sync proofs.
+	warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+	/// Incoming requests, sent by the network.
+	request_receiver: mpsc::Receiver<IncomingRequest>,
+}
+
+impl<B> WarpSyncRequestHandler<B>
+where
+	B: Block,
+{
+	/// Create a new [`WarpSyncRequestHandler`], along with the configuration of the protocol
+	/// to register on the network.
+	pub fn new(
+		protocol_id: &ProtocolId,
+		warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+	) -> (Self, ProtocolConfig) {
+		let (tx, request_receiver) = mpsc::channel(INBOUND_QUEUE_SIZE);
+
+		let mut protocol_config = generate_protocol_config(protocol_id);
+		protocol_config.inbound_queue = Some(tx);
+
+		(WarpSyncRequestHandler { warp_sync_provider, request_receiver }, protocol_config)
+	}
+
+	/// Run the handler, answering the incoming requests until the network drops its side of
+	/// the queue.
+	pub async fn run(mut self) {
+		while let Some(IncomingRequest { peer, payload, pending_response }) = self.request_receiver.next().await {
+			match self.handle_request(&peer, &payload) {
+				Ok(response) => {
+					log::trace!(
+						target: "sync",
+						"Enqueueing warp sync response for peer {} with {} bytes",
+						peer, response.len()
+					);
+					// The requester may no longer be interested in the response.
+					let _ = pending_response.send(response);
+				}
+				// Dropping `pending_response` refuses the request.
+				Err(e) => log::debug!(
+					target: "sync",
+					"Error handling warp sync request from peer {}: {}", peer, e
+				)
+			}
+		}
+	}
+
+	/// Decode a warp sync request, and build the encoded response.
+	fn handle_request(&self, peer: &PeerId, payload: &[u8]) -> Result<Vec<u8>, Error> {
+		let request = schema::v1::warp::WarpSyncRequest::decode(payload)?;
+		let begin: B::Hash = Decode::decode(&mut request.begin.as_ref())?;
+
+		log::trace!(target: "sync", "Warp sync request from {} starting at {}", peer, begin);
+
+		let proof = self.warp_sync_provider.generate(begin)?;
+		let response = schema::v1::warp::WarpSyncResponse { proof };
+
+		let mut data = Vec::with_capacity(response.encoded_len());
+		response.encode(&mut data)?;
+		Ok(data)
+	}
+}
+
+/// Encode a warp sync request for a proof starting from the block `begin`.
+pub(crate) fn encode_warp_sync_request<Hash: Encode>(begin: &Hash) -> Vec<u8> {
+	let protobuf_rq = schema::v1::warp::WarpSyncRequest {
+		begin: begin.encode(),
+	};
+
+	let mut buf = Vec::with_capacity(protobuf_rq.encoded_len());
+	protobuf_rq.encode(&mut buf).expect("Vec<u8> grows as needed, encoding can't fail; qed");
+	buf
+}
+
+/// Decode the response to a warp sync request, returning the encoded proof.
+pub(crate) fn decode_warp_sync_response(response: &[u8]) -> Result<Vec<u8>, prost::DecodeError> {
+	schema::v1::warp::WarpSyncResponse::decode(response).map(|response| response.proof)
+}
@@ -673,6 +673,7 @@ pub trait TestNetFactory: Sized {
 				PeersClient::Full(client.clone(), backend.clone()),
 			),
 			finality_proof_request_builder,
+			warp_sync_provider: None,
 			on_demand: None,
 			transaction_pool: Arc::new(EmptyTransactionPool),
 			protocol_id: ProtocolId::from(&b"test-protocol-name"[..]),
@@ -753,6 +754,7 @@ pub trait TestNetFactory: Sized {
 				PeersClient::Light(client.clone(), backend.clone())
 			),
 			finality_proof_request_builder,
+			warp_sync_provider: None,
 			on_demand: None,
 			transaction_pool: Arc::new(EmptyTransactionPool),
 			protocol_id: ProtocolId::from(&b"test-protocol-name"[..]),
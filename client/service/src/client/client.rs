@@ -29,7 +29,7 @@ use codec::{Encode, Decode};
 use hash_db::Prefix;
 use sp_core::{
 	convert_hash,
-	storage::{well_known_keys, ChildInfo, PrefixedStorageKey, StorageData, StorageKey},
+	storage::{well_known_keys, ChildInfo, PrefixedStorageKey, Storage, StorageData, StorageKey},
 	ChangesTrieConfiguration, ExecutionContext, NativeOrEncoded,
 };
 use sc_telemetry::{telemetry, SUBSTRATE_INFO};
@@ -43,7 +43,8 @@ use sp_runtime::{
 };
 use sp_state_machine::{
 	DBValue, Backend as StateBackend, ChangesTrieAnchorBlockId,
-	prove_read, prove_child_read, ChangesTrieRootsStorage, ChangesTrieStorage,
+	prove_read, prove_child_read, prove_range_read_with_size, read_range_proof_check,
+	ChangesTrieRootsStorage, ChangesTrieStorage,
 	ChangesTrieConfigurationRange, key_changes, key_changes_proof,
 };
 use sc_executor::RuntimeVersion;
@@ -613,6 +614,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			fork_choice,
 			intermediates,
 			import_existing,
+			import_state,
 			..
 		} = import_block;
 
@@ -652,6 +654,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			auxiliary,
 			fork_choice,
 			import_existing,
+			import_state,
 		);
 
 		if let Ok(ImportResult::Imported(ref aux)) = result {
@@ -688,6 +691,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 		aux: Vec<(Vec<u8>, Option<Vec<u8>>)>,
 		fork_choice: ForkChoiceStrategy,
 		import_existing: bool,
+		import_state: Option<Storage>,
 	) -> sp_blockchain::Result<ImportResult> where
 		Self: ProvideRuntimeApi<Block>,
 		<Self as ProvideRuntimeApi<Block>>::Api: CoreApi<Block, Error = Error> +
@@ -707,7 +711,9 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 
 		// the block is lower than our last finalized block so it must revert
 		// finality, refusing import.
-		if *import_headers.post().number() <= info.finalized_number {
+		if status == blockchain::BlockStatus::Unknown
+			&& *import_headers.post().number() <= info.finalized_number
+		{
 			return Err(sp_blockchain::Error::NotInFinalizedChain);
 		}
 
@@ -719,8 +725,20 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			BlockOrigin::Genesis | BlockOrigin::NetworkInitialSync | BlockOrigin::File => false,
 		};
 
-		let storage_changes = match storage_changes {
-			Some(storage_changes) => {
+		let storage_changes = match (import_state, storage_changes) {
+			(Some(state), _) => {
+				// the state is imported as a whole, on top of the empty state.
+				self.backend.begin_state_operation(&mut operation.op, BlockId::Hash(Default::default()))?;
+				let state_root = operation.op.reset_storage(state)?;
+				if state_root != *import_headers.post().state_root() {
+					return Err(Error::InvalidStateRoot)
+				}
+
+				operation.op.update_cache(new_cache);
+
+				None
+			},
+			(None, Some(storage_changes)) => {
 				self.backend.begin_state_operation(&mut operation.op, BlockId::Hash(parent_hash))?;
 
 				// ensure parent block is finalized to maintain invariant that
@@ -758,7 +776,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 
 				Some((main_sc, child_sc))
 			},
-			None => None,
+			(None, None) => None,
 		};
 
 		let is_new_best = finalized || match fork_choice {
@@ -836,6 +854,17 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 	{
 		let parent_hash = import_block.header.parent_hash();
 		let at = BlockId::Hash(*parent_hash);
+		if import_block.import_state.is_some() {
+			// The imported state replaces the execution of the block, so only the parent header is
+			// needed.
+			import_block.storage_changes = None;
+			return match self.block_status(&at)? {
+				BlockStatus::Unknown => Ok(Some(ImportResult::UnknownParent)),
+				BlockStatus::KnownBad => Ok(Some(ImportResult::KnownBad)),
+				_ => Ok(None),
+			}
+		}
+
 		let enact_state = match self.block_status(&at)? {
 			BlockStatus::Unknown => return Ok(Some(ImportResult::UnknownParent)),
 			BlockStatus::InChainWithState | BlockStatus::Queued => true,
@@ -1255,6 +1284,29 @@ impl<B, E, Block, RA> ProofProvider<Block> for Client<B, E, Block, RA> where
 			cht::size(),
 		)
 	}
+
+	fn read_range_proof(
+		&self,
+		id: &BlockId<Block>,
+		child_info: Option<&ChildInfo>,
+		start_key: Option<&[u8]>,
+		size_limit: usize,
+	) -> sp_blockchain::Result<(StorageProof, u32)> {
+		self.state_at(id)
+			.and_then(|state| prove_range_read_with_size(state, child_info, start_key, size_limit)
+				.map_err(Into::into))
+	}
+
+	fn verify_range_proof(
+		&self,
+		root: Block::Hash,
+		proof: StorageProof,
+		child_info: Option<&ChildInfo>,
+		start_key: Option<&[u8]>,
+	) -> sp_blockchain::Result<(Vec<(Vec<u8>, Vec<u8>)>, bool)> {
+		read_range_proof_check::<HashFor<Block>>(root, proof, child_info, start_key)
+			.map_err(Into::into)
+	}
 }
 
 
@@ -1745,7 +1797,8 @@ impl<B, E, Block, RA> sp_consensus::BlockImport<Block> for &Client<B, E, Block,
 		{
 			BlockStatus::InChainWithState | BlockStatus::Queued if !import_existing  => return Ok(ImportResult::AlreadyInChain),
 			BlockStatus::InChainWithState | BlockStatus::Queued => {},
-			BlockStatus::InChainPruned => return Ok(ImportResult::AlreadyInChain),
+			BlockStatus::InChainPruned if !import_existing => return Ok(ImportResult::AlreadyInChain),
+			BlockStatus::InChainPruned => {},
 			BlockStatus::Unknown => {},
 			BlockStatus::KnownBad => return Ok(ImportResult::KnownBad),
 		}
@@ -210,8 +210,12 @@ impl<BlockHash: Hash, Key: Hash> NonCanonicalOverlay<BlockHash, Key> {
 	pub fn insert<E: fmt::Debug>(&mut self, hash: &BlockHash, number: u64, parent_hash: &BlockHash, changeset: ChangeSet<Key>) -> Result<CommitSet<Key>, Error<E>> {
 		let mut commit = CommitSet::default();
 		let front_block_number = self.front_block_number();
-		if self.levels.is_empty() && self.last_canonicalized.is_none() && number > 0 {
-			// assume that parent was canonicalized
+		if self.levels.is_empty()
+			&& number > 0
+			&& self.last_canonicalized.as_ref().map_or(true, |&(_, n)| n + 1 < number)
+		{
+			// assume that parent was canonicalized. Besides the first block, this is the case of
+			// a block whose full state is imported after its ancestors were imported without it.
 			let last_canonicalized = (parent_hash.clone(), number - 1);
 			commit.meta.inserted.push((to_meta_key(LAST_CANONICAL, &()), last_canonicalized.encode()));
 			self.last_canonicalized = Some(last_canonicalized);
@@ -605,6 +609,29 @@ mod tests {
 		assert!(db.data_eq(&make_db(&[1, 3, 4])));
 	}
 
+	#[test]
+	fn insert_ahead_of_canonicalized_without_pending_blocks() {
+		let h1 = H256::random();
+		let h2 = H256::random();
+		let mut db = make_db(&[1, 2]);
+		let mut overlay = NonCanonicalOverlay::<H256, H256>::new(&db).unwrap();
+		db.commit(&overlay.insert::<io::Error>(&h1, 1, &H256::default(), make_changeset(&[3], &[])).unwrap());
+		let mut commit = CommitSet::default();
+		overlay.canonicalize::<io::Error>(&h1, &mut commit).unwrap();
+		db.commit(&commit);
+		overlay.apply_pending();
+
+		db.commit(&overlay.insert::<io::Error>(&h2, 10, &H256::random(), make_changeset(&[4], &[])).unwrap());
+		assert_eq!(overlay.levels.len(), 1);
+		assert!(contains(&overlay, 4));
+		let mut commit = CommitSet::default();
+		overlay.canonicalize::<io::Error>(&h2, &mut commit).unwrap();
+		db.commit(&commit);
+		overlay.apply_pending();
+		assert_eq!(overlay.last_canonicalized, Some((h2, 10)));
+		assert!(db.data_eq(&make_db(&[1, 2, 3, 4])));
+	}
+
 	#[test]
 	fn restore_from_journal() {
 		let h1 = H256::random();
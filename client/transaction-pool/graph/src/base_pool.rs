@@ -21,7 +21,7 @@
 //! For a more full-featured pool, have a look at the `pool` module.
 
 use std::{
-	collections::HashSet,
+	collections::{HashMap, HashSet},
 	fmt,
 	hash,
 	sync::Arc,
@@ -38,6 +38,7 @@ use sp_runtime::transaction_validity::{
 	TransactionSource as Source,
 };
 use sp_transaction_pool::{error, PoolStatus, InPoolTransaction};
+use wasm_timer::Instant;
 
 use crate::future::{FutureTransactions, WaitingTransaction};
 use crate::ready::ReadyTransactions;
@@ -379,7 +380,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	}
 
 	/// Returns an iterator over future transactions in the pool.
-	pub fn futures(&self) -> impl Iterator<Item=&Transaction<Hash, Ex>> {
+	pub fn futures(&self) -> impl Iterator<Item=Arc<Transaction<Hash, Ex>>> + '_ {
 		self.future.all()
 	}
 
@@ -407,7 +408,9 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on them.
 	/// Technically the worst transaction should be evaluated by computing the entire pending set.
-	/// We use a simplified approach to remove the transaction that occupies the pool for the longest time.
+	/// We use a simplified approach: the transaction with the lowest priority is removed first,
+	/// then the one closest to the end of its longevity, then the one that occupies the pool for
+	/// the longest time.
 	pub fn enforce_limits(&mut self, ready: &Limit, future: &Limit) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = vec![];
 
@@ -418,7 +421,15 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 					let transaction = &current.transaction;
 					match minimal {
 						None => Some(transaction.clone()),
-						Some(ref tx) if tx.insertion_id > transaction.insertion_id => {
+						Some(ref tx) if (
+							tx.transaction.priority,
+							tx.transaction.valid_till,
+							tx.insertion_id,
+						) > (
+							transaction.transaction.priority,
+							transaction.transaction.valid_till,
+							transaction.insertion_id,
+						) => {
 							Some(transaction.clone())
 						},
 						other => other,
@@ -438,7 +449,15 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 				.fold(|minimal, current| {
 					match minimal {
 						None => Some(current.clone()),
-						Some(ref tx) if tx.imported_at > current.imported_at => {
+						Some(ref tx) if (
+							tx.transaction.priority,
+							tx.transaction.valid_till,
+							tx.imported_at,
+						) > (
+							current.transaction.priority,
+							current.transaction.valid_till,
+							current.imported_at,
+						) => {
 							Some(current.clone())
 						},
 						other => other,
@@ -455,6 +474,92 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		removed
 	}
 
+	/// Makes sure that no more than `limit.count` transactions share a `provides` tag prefix
+	/// with any of the transactions represented by the hashes.
+	///
+	/// Removes and returns worst transactions of the groups over the limit and all transactions
+	/// that depend on them. Within a group, future transactions are removed before ready ones,
+	/// then the transaction with the lowest priority, then the one closest to the end of its
+	/// longevity, then the most recently imported one. The latter is usually the tail of a
+	/// sender's nonce chain, so removing it leaves the rest of the chain in place.
+	pub fn enforce_tag_prefix_limit(
+		&mut self,
+		limit: &TagPrefixLimit,
+		hashes: &[Hash],
+	) -> Vec<Arc<Transaction<Hash, Ex>>> {
+		let prefixes = self.by_hashes(hashes)
+			.into_iter()
+			.flatten()
+			.flat_map(|tx| limit.prefixes(&tx.provides).map(|prefix| prefix.to_vec()).collect::<Vec<_>>())
+			.collect::<HashSet<_>>();
+		if prefixes.is_empty() {
+			return vec![]
+		}
+
+		// gather the transactions of the groups we are interested in
+		let mut candidates = Vec::new();
+		self.ready.fold::<(), _>(|_, current| {
+			candidates.push(EvictionCandidate {
+				transaction: current.transaction.transaction.clone(),
+				insertion_id: Some(current.transaction.insertion_id),
+				imported_at: None,
+			});
+			None
+		});
+		self.future.fold::<(), _>(|_, current| {
+			candidates.push(EvictionCandidate {
+				transaction: current.transaction.clone(),
+				insertion_id: None,
+				imported_at: Some(current.imported_at),
+			});
+			None
+		});
+
+		let mut groups = HashMap::<&[u8], Vec<usize>>::new();
+		for (index, candidate) in candidates.iter().enumerate() {
+			let candidate_prefixes = limit.prefixes(&candidate.transaction.provides)
+				.filter(|prefix| prefixes.contains(*prefix))
+				.collect::<HashSet<_>>();
+			for prefix in candidate_prefixes {
+				groups.entry(prefix).or_default().push(index);
+			}
+		}
+
+		let mut removed = vec![];
+		let mut removed_hashes = HashSet::new();
+		for (_, mut group) in groups {
+			loop {
+				group.retain(|index| !removed_hashes.contains(&candidates[*index].transaction.hash));
+				if group.len() <= limit.count {
+					break;
+				}
+
+				// find the worst transaction
+				let worst = group.iter()
+					.map(|index| &candidates[*index])
+					.fold(None, |worst: Option<&EvictionCandidate<Hash, Ex>>, current| {
+						match worst {
+							Some(tx) if !current.goes_before(tx) => Some(tx),
+							_ => Some(current),
+						}
+					});
+
+				if let Some(worst) = worst {
+					let hash = worst.transaction.hash.clone();
+					for tx in self.remove_subtree(&[hash.clone()]) {
+						removed_hashes.insert(tx.hash.clone());
+						removed.push(tx);
+					}
+					removed_hashes.insert(hash);
+				} else {
+					break;
+				}
+			}
+		}
+
+		removed
+	}
+
 	/// Removes all transactions represented by the hashes and all other transactions
 	/// that depend on them.
 	///
@@ -543,6 +648,54 @@ impl Limit {
 	}
 }
 
+/// Limit on the number of transactions sharing a `provides` tag prefix.
+///
+/// With a prefix covering the account id of the `(AccountId, Index)` tags provided by
+/// `frame_system`'s nonce check, this caps the number of transactions per sender.
+#[derive(Debug, Clone)]
+pub struct TagPrefixLimit {
+	/// Length of the prefix in bytes. Shorter tags are not limited.
+	pub prefix_len: usize,
+	/// Maximal number of transactions sharing a prefix.
+	pub count: usize,
+}
+
+impl TagPrefixLimit {
+	/// Returns the prefixes of the given tags that are subject to the limit.
+	fn prefixes<'a>(&'a self, tags: &'a [Tag]) -> impl Iterator<Item=&'a [u8]> + 'a {
+		tags.iter()
+			.filter(move |tag| tag.len() >= self.prefix_len)
+			.map(move |tag| &tag[..self.prefix_len])
+	}
+}
+
+/// A transaction that might be removed to enforce a `TagPrefixLimit`.
+struct EvictionCandidate<Hash, Ex> {
+	transaction: Arc<Transaction<Hash, Ex>>,
+	/// Insertion id, if the transaction is in the ready queue.
+	insertion_id: Option<u64>,
+	/// Time of import, if the transaction is in the future queue.
+	imported_at: Option<Instant>,
+}
+
+impl<Hash, Ex> EvictionCandidate<Hash, Ex> {
+	/// Returns true if this transaction should be removed before the `other` one.
+	fn goes_before(&self, other: &Self) -> bool {
+		let key = |tx: &Self| (
+			tx.insertion_id.is_some(),
+			tx.transaction.priority,
+			tx.transaction.valid_till,
+		);
+
+		match key(self).cmp(&key(other)) {
+			std::cmp::Ordering::Less => true,
+			std::cmp::Ordering::Greater => false,
+			std::cmp::Ordering::Equal =>
+				self.insertion_id > other.insertion_id || self.imported_at > other.imported_at,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1201,4 +1354,68 @@ source: TransactionSource::External, requires: [03,02], provides: [04], data: [4
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	#[test]
+	fn should_evict_transactions_with_lowest_priority_first() {
+		// given
+		let mut pool = pool();
+		for (hash, priority) in vec![(1u64, 5u64), (2, 1), (3, 3)] {
+			pool.import(Transaction {
+				data: vec![hash as u8],
+				bytes: 1,
+				hash,
+				priority,
+				valid_till: 64u64,
+				requires: vec![],
+				provides: vec![vec![hash as u8]],
+				propagate: true,
+				source: Source::External,
+			}).unwrap();
+		}
+		let limit = Limit {
+			count: 2,
+			total_bytes: 1000,
+		};
+
+		// when
+		let removed = pool.enforce_limits(&limit, &limit);
+
+		// then
+		assert_eq!(removed.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![2]);
+		assert_eq!(pool.ready().map(|tx| tx.hash).collect::<Vec<_>>(), vec![1, 3]);
+	}
+
+	#[test]
+	fn should_enforce_tag_prefix_limit() {
+		// given
+		let mut pool = pool();
+		let mut import = |hash: u64, requires: Vec<Tag>, provides: Vec<Tag>| {
+			pool.import(Transaction {
+				data: vec![hash as u8],
+				bytes: 1,
+				hash,
+				priority: 5u64,
+				valid_till: 64u64,
+				requires,
+				provides,
+				propagate: true,
+				source: Source::External,
+			}).unwrap();
+		};
+		import(1, vec![], vec![vec![1, 0]]);
+		import(2, vec![vec![1, 0]], vec![vec![1, 1]]);
+		import(3, vec![], vec![vec![2, 0]]);
+		import(4, vec![vec![1, 1]], vec![vec![1, 2]]);
+		let limit = TagPrefixLimit {
+			prefix_len: 1,
+			count: 2,
+		};
+
+		// when
+		let removed = pool.enforce_tag_prefix_limit(&limit, &[4]);
+
+		// then
+		assert_eq!(removed.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![4]);
+		assert_eq!(pool.ready().map(|tx| tx.hash).collect::<Vec<_>>(), vec![1, 2, 3]);
+	}
 }
@@ -39,6 +39,7 @@ pub mod watcher;
 
 pub use self::base_pool::Transaction;
 pub use self::pool::{
-	Pool, Options, ChainApi, EventStream, ExtrinsicFor, ExtrinsicHash,
+	Pool, Options, JournalOptions, ChainApi, EventStream, ExtrinsicFor, ExtrinsicHash,
 	BlockHash, NumberFor, TransactionFor, ValidatedTransaction,
 };
+pub use self::validated_pool::Evictions;
@@ -19,6 +19,7 @@
 use std::{
 	collections::HashMap,
 	sync::Arc,
+	time::Duration,
 };
 
 use crate::{base_pool as base, watcher::Watcher};
@@ -107,6 +108,21 @@ pub struct Options {
 	pub future: base::Limit,
 	/// Reject future transactions.
 	pub reject_future_transactions: bool,
+	/// Limit on the number of transactions sharing a `provides` tag prefix, e.g. per sender.
+	pub tag_prefix_limit: Option<base::TagPrefixLimit>,
+	/// On-disk journal of the pool contents, restored on restart. Only used by full nodes.
+	pub journal: Option<JournalOptions>,
+}
+
+/// Options of the on-disk journal of the pool contents.
+#[derive(Debug, Clone)]
+pub struct JournalOptions {
+	/// Maximal number of transactions in the journal.
+	pub count: usize,
+	/// Maximal size of encodings of all transactions in the journal.
+	pub total_bytes: usize,
+	/// Transactions journaled for longer than this are not restored.
+	pub max_age: Duration,
 }
 
 impl Default for Options {
@@ -121,6 +137,8 @@ impl Default for Options {
 				total_bytes: 1 * 1024 * 1024,
 			},
 			reject_future_transactions: false,
+			tag_prefix_limit: None,
+			journal: None,
 		}
 	}
 }
@@ -90,6 +90,15 @@ pub type ValidatedTransactionFor<B> = ValidatedTransaction<
 	<B as ChainApi>::Error,
 >;
 
+/// Number of transactions evicted from the pool to enforce its limits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Evictions {
+	/// Transactions evicted because the ready or future queue was over its limits.
+	pub queue_limits: u64,
+	/// Transactions evicted because too many transactions shared a `provides` tag prefix.
+	pub tag_prefix_limit: u64,
+}
+
 /// Pool that deals with validated transactions.
 pub struct ValidatedPool<B: ChainApi> {
 	api: Arc<B>,
@@ -101,6 +110,7 @@ pub struct ValidatedPool<B: ChainApi> {
 	>>,
 	import_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<B>>>>,
 	rotator: PoolRotator<ExtrinsicHash<B>>,
+	evictions: Mutex<Evictions>,
 }
 
 #[cfg(not(target_os = "unknown"))]
@@ -125,6 +135,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 			pool: RwLock::new(base_pool),
 			import_notification_sinks: Default::default(),
 			rotator: Default::default(),
+			evictions: Default::default(),
 		}
 	}
 
@@ -167,8 +178,11 @@ impl<B: ChainApi> ValidatedPool<B> {
 			.collect::<Vec<_>>();
 
 		// only enforce limits if there is at least one imported transaction
-		let removed = if results.iter().any(|res| res.is_ok()) {
-			self.enforce_limits()
+		let imported = results.iter()
+			.filter_map(|res| res.as_ref().ok().cloned())
+			.collect::<Vec<_>>();
+		let removed = if !imported.is_empty() {
+			self.enforce_limits(&imported)
 		} else {
 			Default::default()
 		};
@@ -217,45 +231,70 @@ impl<B: ChainApi> ValidatedPool<B> {
 		}
 	}
 
-	fn enforce_limits(&self) -> HashSet<ExtrinsicHash<B>> {
+	fn enforce_limits(&self, imported: &[ExtrinsicHash<B>]) -> HashSet<ExtrinsicHash<B>> {
 		let status = self.pool.read().status();
 		let ready_limit = &self.options.ready;
 		let future_limit = &self.options.future;
 
 		log::debug!(target: "txpool", "Pool Status: {:?}", status);
-		if ready_limit.is_exceeded(status.ready, status.ready_bytes)
-			|| future_limit.is_exceeded(status.future, status.future_bytes)
-		{
-			log::debug!(
-				target: "txpool",
-				"Enforcing limits ({}/{}kB ready, {}/{}kB future",
-				ready_limit.count, ready_limit.total_bytes / 1024,
-				future_limit.count, future_limit.total_bytes / 1024,
-			);
-
-			// clean up the pool
-			let removed = {
-				let mut pool = self.pool.write();
-				let removed = pool.enforce_limits(ready_limit, future_limit)
-					.into_iter().map(|x| x.hash.clone()).collect::<HashSet<_>>();
-				// ban all removed transactions
-				self.rotator.ban(&Instant::now(), removed.iter().map(|x| x.clone()));
-				removed
-			};
-			if !removed.is_empty() {
-				log::debug!(target: "txpool", "Enforcing limits: {} dropped", removed.len());
+		let queue_limits_exceeded = ready_limit.is_exceeded(status.ready, status.ready_bytes)
+			|| future_limit.is_exceeded(status.future, status.future_bytes);
+		if !queue_limits_exceeded && self.options.tag_prefix_limit.is_none() {
+			return Default::default()
+		}
+
+		// clean up the pool
+		let removed = {
+			let mut pool = self.pool.write();
+			let mut evictions = self.evictions.lock();
+			let mut removed = HashSet::new();
+
+			if queue_limits_exceeded {
+				log::debug!(
+					target: "txpool",
+					"Enforcing limits ({}/{}kB ready, {}/{}kB future",
+					ready_limit.count, ready_limit.total_bytes / 1024,
+					future_limit.count, future_limit.total_bytes / 1024,
+				);
+
+				let evicted = pool.enforce_limits(ready_limit, future_limit);
+				evictions.queue_limits += evicted.len() as u64;
+				removed.extend(evicted.into_iter().map(|x| x.hash.clone()));
 			}
 
-			// run notifications
-			let mut listener = self.listener.write();
-			for h in &removed {
-				listener.dropped(h, None);
+			if let Some(ref tag_prefix_limit) = self.options.tag_prefix_limit {
+				let evicted = pool.enforce_tag_prefix_limit(tag_prefix_limit, imported);
+				if !evicted.is_empty() {
+					log::debug!(
+						target: "txpool",
+						"Enforcing limit of {} transactions per {} bytes tag prefix",
+						tag_prefix_limit.count, tag_prefix_limit.prefix_len,
+					);
+				}
+				evictions.tag_prefix_limit += evicted.len() as u64;
+				removed.extend(evicted.into_iter().map(|x| x.hash.clone()));
 			}
 
+			// ban all removed transactions
+			self.rotator.ban(&Instant::now(), removed.iter().map(|x| x.clone()));
 			removed
-		} else {
-			Default::default()
+		};
+		if !removed.is_empty() {
+			log::debug!(target: "txpool", "Enforcing limits: {} dropped", removed.len());
+		}
+
+		// run notifications
+		let mut listener = self.listener.write();
+		for h in &removed {
+			listener.dropped(h, None);
 		}
+
+		removed
+	}
+
+	/// Returns the number of transactions evicted to enforce the pool limits since the last call.
+	pub fn take_evictions(&self) -> Evictions {
+		std::mem::take(&mut *self.evictions.lock())
 	}
 
 	/// Import a single extrinsic and starts to watch their progress in the pool.
@@ -591,6 +630,11 @@ impl<B: ChainApi> ValidatedPool<B> {
 		self.pool.read().ready()
 	}
 
+	/// Returns all transactions in the future queue.
+	pub fn futures(&self) -> Vec<TransactionFor<B>> {
+		self.pool.read().futures().collect()
+	}
+
 	/// Returns pool status.
 	pub fn status(&self) -> PoolStatus {
 		self.pool.read().status()
@@ -0,0 +1,345 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-disk journal of the pool contents.
+//!
+//! The journal lives in the auxiliary storage of the client. It is made of an index of the
+//! journaled transactions, with their source and the time they were journaled at, and of the
+//! encoded transactions, each under its own key. It is periodically updated with the
+//! transactions that entered and left the pool, and read back on startup to re-import the
+//! transactions that are recent enough.
+
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use codec::{Encode, Decode};
+use futures::prelude::*;
+use intervalier::IntoStream;
+use sc_client_api::backend::AuxStore;
+use sc_transaction_graph::{ChainApi, ExtrinsicFor, ExtrinsicHash, JournalOptions, Pool};
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_runtime::{generic::BlockId, transaction_validity::TransactionSource};
+
+const JOURNAL_INDEX_KEY: &[u8] = b"txpool_journal_index";
+const JOURNAL_TRANSACTION_PREFIX: &[u8] = b"txpool_journal_tx";
+
+/// Interval at which the journal is updated.
+const JOURNAL_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Entry of the journal index.
+#[derive(Debug, Clone, Encode, Decode)]
+struct IndexEntry<Hash> {
+	/// Hash of the journaled transaction.
+	hash: Hash,
+	/// Source of the transaction, used when re-importing it.
+	source: TransactionSource,
+	/// When the transaction was journaled, in seconds since the UNIX epoch.
+	journaled_at: u64,
+}
+
+/// Journal of the transactions of a pool.
+pub struct Journal<Api: ChainApi, Client> {
+	pool: Arc<Pool<Api>>,
+	client: Arc<Client>,
+	options: JournalOptions,
+	/// The journal index, as stored in the database.
+	index: Vec<IndexEntry<ExtrinsicHash<Api>>>,
+}
+
+impl<Api, Client> Journal<Api, Client>
+where
+	Api: ChainApi + 'static,
+	Client: AuxStore + HeaderBackend<Api::Block> + Send + Sync + 'static,
+{
+	/// Create a new journal for the given pool, stored in the auxiliary storage of `client`.
+	pub fn new(pool: Arc<Pool<Api>>, client: Arc<Client>, options: JournalOptions) -> Self {
+		Journal {
+			pool,
+			client,
+			options,
+			index: Vec::new(),
+		}
+	}
+
+	/// Journal main loop.
+	///
+	/// Restores the journaled transactions to the pool against the best block, then keeps the
+	/// journal up to date with the pool contents.
+	pub async fn run(mut self) {
+		let best_block = BlockId::Hash(self.client.info().best_hash);
+		self.restore(&best_block).await;
+
+		let interval = intervalier::Interval::new(JOURNAL_UPDATE_INTERVAL).into_stream();
+		futures::pin_mut!(interval);
+
+		while let Some(_) = interval.next().await {
+			if let Err(e) = self.update() {
+				log::warn!(target: "txpool", "Failed to update the pool journal: {:?}", e);
+			}
+		}
+	}
+
+	/// Re-import to the pool the journaled transactions that are recent enough, validating them
+	/// at the given block.
+	async fn restore(&mut self, at: &BlockId<Api::Block>) {
+		self.index = match self.client.get_aux(JOURNAL_INDEX_KEY) {
+			Ok(Some(encoded)) => match Decode::decode(&mut &encoded[..]) {
+				Ok(index) => index,
+				Err(e) => {
+					log::warn!(target: "txpool", "Pool journal is corrupted, clearing it. Decode error: {}", e.what());
+					self.clear(&encoded);
+					return
+				},
+			},
+			Ok(None) => Vec::new(),
+			Err(e) => {
+				log::warn!(target: "txpool", "Failed to read the pool journal: {:?}", e);
+				return
+			},
+		};
+
+		let now = now();
+		let mut to_import = Vec::<(TransactionSource, Vec<ExtrinsicFor<Api>>)>::new();
+		for entry in &self.index {
+			if now.saturating_sub(entry.journaled_at) > self.options.max_age.as_secs() {
+				continue
+			}
+
+			let xt = match load_decode(&*self.client, &transaction_key(&entry.hash)) {
+				Ok(Some(xt)) => xt,
+				Ok(None) => continue,
+				Err(e) => {
+					log::debug!(target: "txpool", "[{:?}] Failed to read from the pool journal: {:?}", entry.hash, e);
+					continue
+				},
+			};
+
+			match to_import.iter_mut().find(|(source, _)| *source == entry.source) {
+				Some((_, xts)) => xts.push(xt),
+				None => to_import.push((entry.source, vec![xt])),
+			}
+		}
+
+		let mut submitted = 0;
+		let mut imported = 0;
+		for (source, xts) in to_import {
+			submitted += xts.len();
+			match self.pool.submit_at(at, source, xts).await {
+				Ok(results) => imported += results.iter().filter(|res| res.is_ok()).count(),
+				Err(e) => log::warn!(target: "txpool", "Failed to restore the pool journal: {:?}", e),
+			}
+		}
+
+		if submitted > 0 {
+			log::info!(
+				target: "txpool",
+				"Restored {} out of {} journaled transactions to the pool",
+				imported, submitted,
+			);
+		}
+	}
+
+	/// Remove from the database the journal whose index failed to decode.
+	///
+	/// The hashes of the journaled transactions are recovered from the encoded index on a best
+	/// effort basis, reading entries until one fails to decode.
+	fn clear(&self, encoded_index: &[u8]) {
+		let input = &mut &encoded_index[..];
+		let mut hashes = Vec::<ExtrinsicHash<Api>>::new();
+		if let Ok(len) = codec::Compact::<u32>::decode(input) {
+			for _ in 0..len.0 {
+				// `IndexEntry` with the source as a raw byte, so that an unknown source still decodes.
+				match <(ExtrinsicHash<Api>, u8, u64)>::decode(input) {
+					Ok((hash, _, _)) => hashes.push(hash),
+					Err(_) => break,
+				}
+			}
+		}
+
+		let mut removed = hashes.iter().map(transaction_key).collect::<Vec<_>>();
+		removed.push(JOURNAL_INDEX_KEY.to_vec());
+		let delete = removed.iter().map(|k| &k[..]).collect::<Vec<_>>();
+		if let Err(e) = self.client.insert_aux(&[], &delete) {
+			log::warn!(target: "txpool", "Failed to clear the pool journal: {:?}", e);
+		}
+	}
+
+	/// Write to the journal the transactions that entered and left the pool since the last
+	/// update.
+	///
+	/// Ready transactions are journaled first, in the order they would be included in a block,
+	/// until the limits of the journal are reached.
+	fn update(&mut self) -> ClientResult<()> {
+		let validated_pool = self.pool.validated_pool();
+		let transactions = validated_pool.ready().chain(validated_pool.futures());
+
+		let mut previous = self.index.iter()
+			.map(|entry| (&entry.hash, entry))
+			.collect::<HashMap<_, _>>();
+		let now = now();
+		let mut index = Vec::new();
+		let mut inserted = Vec::new();
+		let mut total_bytes = 0;
+		for tx in transactions {
+			if index.len() >= self.options.count || total_bytes + tx.bytes > self.options.total_bytes {
+				break
+			}
+			total_bytes += tx.bytes;
+
+			match previous.remove(&tx.hash) {
+				Some(entry) => index.push(entry.clone()),
+				None => {
+					inserted.push((transaction_key(&tx.hash), tx.data.encode()));
+					index.push(IndexEntry { hash: tx.hash.clone(), source: tx.source, journaled_at: now });
+				},
+			}
+		}
+		let removed = previous.keys().map(|hash| transaction_key(*hash)).collect::<Vec<_>>();
+
+		if !inserted.is_empty() || !removed.is_empty() {
+			log::trace!(
+				target: "txpool",
+				"Updating the pool journal: {} inserted, {} removed",
+				inserted.len(), removed.len(),
+			);
+
+			inserted.push((JOURNAL_INDEX_KEY.to_vec(), index.encode()));
+			let insert = inserted.iter().map(|(k, v)| (&k[..], &v[..])).collect::<Vec<_>>();
+			let delete = removed.iter().map(|k| &k[..]).collect::<Vec<_>>();
+			self.client.insert_aux(&insert, &delete)?;
+		}
+
+		self.index = index;
+		Ok(())
+	}
+}
+
+/// Database key of a journaled transaction.
+fn transaction_key<Hash: Encode>(hash: &Hash) -> Vec<u8> {
+	let mut key = JOURNAL_TRANSACTION_PREFIX.to_vec();
+	hash.using_encoded(|h| key.extend(h));
+	key
+}
+
+fn load_decode<C: AuxStore, T: Decode>(backend: &C, key: &[u8]) -> ClientResult<Option<T>> {
+	match backend.get_aux(key)? {
+		None => Ok(None),
+		Some(t) => T::decode(&mut &t[..])
+			.map_err(
+				|e| ClientError::Backend(format!("Pool journal is corrupted. Decode error: {}", e.what())),
+			)
+			.map(Some)
+	}
+}
+
+/// Current time, in seconds since the UNIX epoch.
+fn now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use substrate_test_runtime_client::AccountKeyring::*;
+	use substrate_test_runtime_transaction_pool::{TestApi, uxt};
+
+	fn options() -> JournalOptions {
+		JournalOptions {
+			count: 16,
+			total_bytes: 16 * 1024,
+			max_age: Duration::from_secs(60),
+		}
+	}
+
+	fn pool() -> Arc<Pool<TestApi>> {
+		Arc::new(Pool::new(Default::default(), TestApi::with_alice_nonce(209).into()))
+	}
+
+	#[test]
+	fn should_restore_journaled_transactions() {
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let txpool = pool();
+		let mut journal = Journal::new(txpool.clone(), client.clone(), options());
+		block_on(txpool.submit_one(&BlockId::number(0), TransactionSource::External, uxt(Alice, 209))).unwrap();
+		block_on(txpool.submit_one(&BlockId::number(0), TransactionSource::External, uxt(Alice, 211))).unwrap();
+		journal.update().unwrap();
+
+		// when
+		let restored_pool = pool();
+		let mut restored_journal = Journal::new(restored_pool.clone(), client, options());
+		block_on(restored_journal.restore(&BlockId::number(0)));
+
+		// then
+		assert_eq!(restored_pool.validated_pool().status().ready, 1);
+		assert_eq!(restored_pool.validated_pool().status().future, 1);
+	}
+
+	#[test]
+	fn should_remove_transactions_that_left_the_pool() {
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let txpool = pool();
+		let mut journal = Journal::new(txpool.clone(), client.clone(), options());
+		let hash = block_on(
+			txpool.submit_one(&BlockId::number(0), TransactionSource::External, uxt(Alice, 209))
+		).unwrap();
+		journal.update().unwrap();
+		assert!(client.get_aux(&transaction_key(&hash)).unwrap().is_some());
+
+		// when
+		txpool.validated_pool().remove_invalid(&[hash]);
+		journal.update().unwrap();
+
+		// then
+		assert!(client.get_aux(&transaction_key(&hash)).unwrap().is_none());
+		let restored_pool = pool();
+		block_on(Journal::new(restored_pool.clone(), client, options()).restore(&BlockId::number(0)));
+		assert_eq!(restored_pool.validated_pool().status().ready, 0);
+	}
+
+	#[test]
+	fn should_clear_journal_with_corrupted_index() {
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let txpool = pool();
+		let mut journal = Journal::new(txpool.clone(), client.clone(), options());
+		let hash = block_on(
+			txpool.submit_one(&BlockId::number(0), TransactionSource::External, uxt(Alice, 209))
+		).unwrap();
+		journal.update().unwrap();
+
+		// when
+		// the source of the only entry, right before its `journaled_at`, becomes unknown
+		let mut encoded_index = client.get_aux(JOURNAL_INDEX_KEY).unwrap().unwrap();
+		let source_position = encoded_index.len() - 9;
+		encoded_index[source_position] = 0xff;
+		client.insert_aux(&[(JOURNAL_INDEX_KEY, &encoded_index[..])], &[]).unwrap();
+		let restored_pool = pool();
+		block_on(Journal::new(restored_pool.clone(), client.clone(), options()).restore(&BlockId::number(0)));
+
+		// then
+		assert_eq!(restored_pool.validated_pool().status().ready, 0);
+		assert!(client.get_aux(JOURNAL_INDEX_KEY).unwrap().is_none());
+		assert!(client.get_aux(&transaction_key(&hash)).unwrap().is_none());
+	}
+}
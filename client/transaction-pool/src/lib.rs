@@ -23,6 +23,7 @@
 #![warn(unused_extern_crates)]
 
 mod api;
+mod journal;
 mod revalidation;
 mod metrics;
 
@@ -244,9 +245,15 @@ impl<PoolApi, Block> TransactionPool for BasicPool<PoolApi, Block>
 		let pool = self.pool.clone();
 		let at = *at;
 
+		let metrics = self.metrics.clone();
+
 		self.metrics.report(|metrics| metrics.submitted_transactions.inc_by(xts.len() as u64));
 
-		async move { pool.submit_at(&at, source, xts).await }.boxed()
+		async move {
+			let results = pool.submit_at(&at, source, xts).await;
+			report_evictions(&pool, &metrics);
+			results
+		}.boxed()
 	}
 
 	fn submit_one(
@@ -258,9 +265,15 @@ impl<PoolApi, Block> TransactionPool for BasicPool<PoolApi, Block>
 		let pool = self.pool.clone();
 		let at = *at;
 
+		let metrics = self.metrics.clone();
+
 		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
 
-		async move { pool.submit_one(&at, source, xt).await }.boxed()
+		async move {
+			let result = pool.submit_one(&at, source, xt).await;
+			report_evictions(&pool, &metrics);
+			result
+		}.boxed()
 	}
 
 	fn submit_and_watch(
@@ -272,12 +285,16 @@ impl<PoolApi, Block> TransactionPool for BasicPool<PoolApi, Block>
 		let at = *at;
 		let pool = self.pool.clone();
 
+		let metrics = self.metrics.clone();
+
 		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
 
 		async move {
-			pool.submit_and_watch(&at, source, xt)
+			let result = pool.submit_and_watch(&at, source, xt)
 				.map(|result| result.map(|watcher| Box::new(watcher.into_stream()) as _))
-				.await
+				.await;
+			report_evictions(&pool, &metrics);
+			result
 		}.boxed()
 	}
 
@@ -356,23 +373,34 @@ where
 	Block: BlockT,
 	Client: sp_api::ProvideRuntimeApi<Block>
 		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::backend::AuxStore
+		+ sp_blockchain::HeaderBackend<Block>
 		+ sp_runtime::traits::BlockIdTo<Block>,
 	Client: sc_client_api::ExecutorProvider<Block> + Send + Sync + 'static,
 	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
 	sp_api::ApiErrorFor<Client, Block>: Send + std::fmt::Display,
 {
 	/// Create new basic transaction pool for a full node with the provided api.
+	///
+	/// If `options.journal` is set, the pool contents are journaled in the auxiliary storage of
+	/// `client` and restored on the next start.
 	pub fn new_full(
 		options: sc_transaction_graph::Options,
 		prometheus: Option<&PrometheusRegistry>,
 		spawner: impl SpawnNamed,
 		client: Arc<Client>,
 	) -> Arc<Self> {
+		let journal_options = options.journal.clone();
 		let pool_api = Arc::new(FullChainApi::new(client.clone(), prometheus));
 		let pool = Arc::new(Self::with_revalidation_type(
-			options, pool_api, prometheus, RevalidationType::Full, spawner
+			options, pool_api, prometheus, RevalidationType::Full, spawner.clone(),
 		));
 
+		if let Some(journal_options) = journal_options {
+			let journal = journal::Journal::new(pool.pool().clone(), client.clone(), journal_options);
+			spawner.spawn("txpool-journal", journal.run().boxed());
+		}
+
 		// make transaction pool available for off-chain runtime calls.
 		client.execution_extensions().register_transaction_pool(&pool);
 
@@ -429,7 +457,9 @@ where
 			validity,
 		);
 
-		self.pool.validated_pool().submit(vec![validated]).remove(0)
+		let result = self.pool.validated_pool().submit(vec![validated]).remove(0);
+		report_evictions(&self.pool, &self.metrics);
+		result
 	}
 }
 
@@ -542,6 +572,18 @@ async fn prune_known_txs_for_block<Block: BlockT, Api: ChainApi<Block = Block>>(
 	hashes
 }
 
+/// Report the transactions evicted by the pool limits to the metrics.
+fn report_evictions<Api: ChainApi>(
+	pool: &sc_transaction_graph::Pool<Api>,
+	metrics: &PrometheusMetrics,
+) {
+	let evictions = pool.validated_pool().take_evictions();
+	metrics.report(|metrics| {
+		metrics.queue_limit_evictions.inc_by(evictions.queue_limits);
+		metrics.tag_prefix_limit_evictions.inc_by(evictions.tag_prefix_limit);
+	});
+}
+
 impl<PoolApi, Block> MaintainedTransactionPool for BasicPool<PoolApi, Block>
 	where
 		Block: BlockT,
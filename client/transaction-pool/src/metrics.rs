@@ -49,6 +49,8 @@ pub struct Metrics {
 	pub validations_invalid: Counter<U64>,
 	pub block_transactions_pruned: Counter<U64>,
 	pub block_transactions_resubmitted: Counter<U64>,
+	pub queue_limit_evictions: Counter<U64>,
+	pub tag_prefix_limit_evictions: Counter<U64>,
 }
 
 impl Metrics {
@@ -82,6 +84,20 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			queue_limit_evictions: register(
+				Counter::new(
+					"sub_txpool_queue_limit_evictions",
+					"Total number of transactions that were evicted because the ready or future queue was full",
+				)?,
+				registry,
+			)?,
+			tag_prefix_limit_evictions: register(
+				Counter::new(
+					"sub_txpool_tag_prefix_limit_evictions",
+					"Total number of transactions that were evicted because too many transactions shared their tag prefix",
+				)?,
+				registry,
+			)?,
 		})
 	}
 }
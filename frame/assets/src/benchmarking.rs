@@ -0,0 +1,325 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Assets pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use sp_std::prelude::*;
+use sp_runtime::traits::Bounded;
+use frame_system::RawOrigin as SystemOrigin;
+use frame_benchmarking::{benchmarks, account};
+use frame_support::traits::UnfilteredDispatchable;
+
+use crate::Module as Assets;
+
+const SEED: u32 = 0;
+
+fn create_default_asset<T: Trait>() -> (T::AccountId, <T::Lookup as StaticLookup>::Source) {
+	let caller: T::AccountId = account("caller", 0, SEED);
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	assert!(Assets::<T>::force_create(
+		T::ForceOrigin::successful_origin(),
+		Default::default(),
+		caller_lookup.clone(),
+		1.into(),
+	).is_ok());
+	(caller, caller_lookup)
+}
+
+fn create_default_minted_asset<T: Trait>(amount: T::Balance)
+	-> (T::AccountId, <T::Lookup as StaticLookup>::Source)
+{
+	let (caller, caller_lookup) = create_default_asset::<T>();
+	assert!(Assets::<T>::mint(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Default::default(),
+		caller_lookup.clone(),
+		amount,
+	).is_ok());
+	(caller, caller_lookup)
+}
+
+fn add_accounts<T: Trait>(minter: T::AccountId, n: u32) {
+	let origin = SystemOrigin::Signed(minter);
+	for i in 0..n {
+		let target = account("member", i, SEED);
+		let target_lookup = T::Lookup::unlookup(target);
+		assert!(Assets::<T>::mint(origin.clone().into(), Default::default(), target_lookup, 100.into()).is_ok());
+	}
+}
+
+fn add_approvals<T: Trait>(minter: T::AccountId, n: u32) {
+	T::Currency::make_free_balance_be(&minter, DepositBalanceOf::<T>::max_value());
+	let minter_lookup = T::Lookup::unlookup(minter.clone());
+	let origin = SystemOrigin::Signed(minter);
+	Assets::<T>::mint(origin.clone().into(), Default::default(), minter_lookup, (100 * (n + 1)).into())
+		.unwrap();
+	for i in 0..n {
+		let target = account("approval", i, SEED);
+		let target_lookup = T::Lookup::unlookup(target);
+		Assets::<T>::approve_transfer(origin.clone().into(), Default::default(), target_lookup, 100.into())
+			.unwrap();
+	}
+}
+
+benchmarks! {
+	_ { }
+
+	create {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T>::max_value());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup, 1.into())
+	verify {
+		assert!(Asset::<T>::contains_key(T::AssetId::default()));
+	}
+
+	force_create {
+		let origin = T::ForceOrigin::successful_origin();
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let call = Call::<T>::force_create(Default::default(), caller_lookup, 1.into());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Asset::<T>::contains_key(T::AssetId::default()));
+	}
+
+	destroy {
+		let a in 0 .. 5_000;
+		let p in 0 .. 500;
+		let (caller, _) = create_default_asset::<T>();
+		add_accounts::<T>(caller.clone(), a);
+		add_approvals::<T>(caller.clone(), p);
+		let witness = DestroyWitness {
+			accounts: Asset::<T>::get(T::AssetId::default()).unwrap().accounts,
+			approvals: p,
+		};
+	}: _(SystemOrigin::Signed(caller), Default::default(), witness)
+	verify {
+		assert!(!Asset::<T>::contains_key(T::AssetId::default()));
+	}
+
+	mint {
+		let (caller, caller_lookup) = create_default_asset::<T>();
+		let amount = T::Balance::from(100);
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::balance(Default::default(), caller), amount);
+	}
+
+	burn {
+		let amount = T::Balance::from(100);
+		let (caller, caller_lookup) = create_default_minted_asset::<T>(amount);
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::balance(Default::default(), caller), Zero::zero());
+	}
+
+	transfer {
+		let amount = T::Balance::from(100);
+		let (caller, caller_lookup) = create_default_minted_asset::<T>(amount);
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), target_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::balance(Default::default(), target), amount);
+	}
+
+	force_transfer {
+		let amount = T::Balance::from(100);
+		let (caller, caller_lookup) = create_default_minted_asset::<T>(amount);
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup, target_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::balance(Default::default(), target), amount);
+	}
+
+	freeze {
+		let (caller, caller_lookup) = create_default_minted_asset::<T>(100.into());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup)
+	verify {
+		assert!(Account::<T>::get(T::AssetId::default(), &caller).is_frozen);
+	}
+
+	thaw {
+		let (caller, caller_lookup) = create_default_minted_asset::<T>(100.into());
+		assert!(Assets::<T>::freeze(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+			caller_lookup.clone(),
+		).is_ok());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup)
+	verify {
+		assert!(!Account::<T>::get(T::AssetId::default(), &caller).is_frozen);
+	}
+
+	freeze_asset {
+		let (caller, _) = create_default_minted_asset::<T>(100.into());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default())
+	verify {
+		assert!(Asset::<T>::get(T::AssetId::default()).unwrap().is_frozen);
+	}
+
+	thaw_asset {
+		let (caller, _) = create_default_minted_asset::<T>(100.into());
+		assert!(Assets::<T>::freeze_asset(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+		).is_ok());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default())
+	verify {
+		assert!(!Asset::<T>::get(T::AssetId::default()).unwrap().is_frozen);
+	}
+
+	transfer_ownership {
+		let (caller, _) = create_default_asset::<T>();
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		T::Currency::make_free_balance_be(&target, DepositBalanceOf::<T>::max_value());
+	}: _(SystemOrigin::Signed(caller), Default::default(), target_lookup)
+	verify {
+		assert_eq!(Asset::<T>::get(T::AssetId::default()).unwrap().owner, target);
+	}
+
+	set_team {
+		let (caller, _) = create_default_asset::<T>();
+		let target0 = T::Lookup::unlookup(account("target", 0, SEED));
+		let target1 = T::Lookup::unlookup(account("target", 1, SEED));
+		let target2 = T::Lookup::unlookup(account("target", 2, SEED));
+	}: _(SystemOrigin::Signed(caller), Default::default(), target0.clone(), target1.clone(), target2.clone())
+	verify {
+		let details = Asset::<T>::get(T::AssetId::default()).unwrap();
+		assert_eq!(details.issuer, account("target", 0, SEED));
+		assert_eq!(details.admin, account("target", 1, SEED));
+		assert_eq!(details.freezer, account("target", 2, SEED));
+	}
+
+	set_metadata {
+		let n in 0 .. T::StringLimit::get();
+		let s in 0 .. T::StringLimit::get();
+
+		let name = vec![0u8; n as usize];
+		let symbol = vec![0u8; s as usize];
+		let decimals = 12;
+
+		let (caller, _) = create_default_asset::<T>();
+		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T>::max_value());
+	}: _(SystemOrigin::Signed(caller), Default::default(), name.clone(), symbol.clone(), decimals)
+	verify {
+		let metadata = Metadata::<T>::get(T::AssetId::default());
+		assert_eq!(metadata.name, name);
+		assert_eq!(metadata.symbol, symbol);
+		assert_eq!(metadata.decimals, decimals);
+	}
+
+	clear_metadata {
+		let (caller, _) = create_default_asset::<T>();
+		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T>::max_value());
+		let dummy = vec![0u8; T::StringLimit::get() as usize];
+		assert!(Assets::<T>::set_metadata(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+			dummy.clone(),
+			dummy,
+			12,
+		).is_ok());
+	}: _(SystemOrigin::Signed(caller), Default::default())
+	verify {
+		assert!(!Metadata::<T>::contains_key(T::AssetId::default()));
+	}
+
+	approve_transfer {
+		let (caller, _) = create_default_minted_asset::<T>(100.into());
+		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T>::max_value());
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+		let amount = T::Balance::from(100);
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), delegate_lookup, amount)
+	verify {
+		let approval = Approvals::<T>::get(T::AssetId::default(), (&caller, &delegate)).unwrap();
+		assert_eq!(approval.amount, amount);
+	}
+
+	cancel_approval {
+		let (caller, _) = create_default_minted_asset::<T>(100.into());
+		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T>::max_value());
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+		assert!(Assets::<T>::approve_transfer(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Default::default(),
+			delegate_lookup.clone(),
+			100.into(),
+		).is_ok());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), delegate_lookup)
+	verify {
+		assert!(Approvals::<T>::get(T::AssetId::default(), (&caller, &delegate)).is_none());
+	}
+
+	transfer_approved {
+		let (owner, owner_lookup) = create_default_minted_asset::<T>(100.into());
+		T::Currency::make_free_balance_be(&owner, DepositBalanceOf::<T>::max_value());
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+		let amount = T::Balance::from(100);
+		assert!(Assets::<T>::approve_transfer(
+			SystemOrigin::Signed(owner.clone()).into(),
+			Default::default(),
+			delegate_lookup,
+			amount,
+		).is_ok());
+		let dest: T::AccountId = account("dest", 0, SEED);
+		let dest_lookup = T::Lookup::unlookup(dest.clone());
+	}: _(SystemOrigin::Signed(delegate), Default::default(), owner_lookup, dest_lookup, amount)
+	verify {
+		assert_eq!(Assets::<T>::balance(Default::default(), dest), amount);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create::<Test>());
+			assert_ok!(test_benchmark_force_create::<Test>());
+			assert_ok!(test_benchmark_destroy::<Test>());
+			assert_ok!(test_benchmark_mint::<Test>());
+			assert_ok!(test_benchmark_burn::<Test>());
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_force_transfer::<Test>());
+			assert_ok!(test_benchmark_freeze::<Test>());
+			assert_ok!(test_benchmark_thaw::<Test>());
+			assert_ok!(test_benchmark_freeze_asset::<Test>());
+			assert_ok!(test_benchmark_thaw_asset::<Test>());
+			assert_ok!(test_benchmark_transfer_ownership::<Test>());
+			assert_ok!(test_benchmark_set_team::<Test>());
+			assert_ok!(test_benchmark_set_metadata::<Test>());
+			assert_ok!(test_benchmark_clear_metadata::<Test>());
+			assert_ok!(test_benchmark_approve_transfer::<Test>());
+			assert_ok!(test_benchmark_cancel_approval::<Test>());
+			assert_ok!(test_benchmark_transfer_approved::<Test>());
+		});
+	}
+}
@@ -0,0 +1,268 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations for the `fungibles` traits of `frame_support`.
+
+use super::*;
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::traits::fungibles::{
+	Inspect, Mutate, Transfer, Unbalanced, InspectHold, MutateHold, DepositConsequence,
+	WithdrawConsequence,
+};
+
+impl<T: Trait> Inspect<<T as frame_system::Trait>::AccountId> for Module<T> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+		Self::total_supply(asset)
+	}
+
+	fn minimum_balance(asset: Self::AssetId) -> Self::Balance {
+		Asset::<T>::get(asset).map(|x| x.min_balance).unwrap_or_else(Zero::zero)
+	}
+
+	fn balance(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		Account::<T>::get(asset, who).balance
+	}
+
+	fn reducible_balance(asset: Self::AssetId, who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		let details = match Asset::<T>::get(asset) {
+			Some(details) => details,
+			None => return Zero::zero(),
+		};
+		let account = Account::<T>::get(asset, who);
+		if details.is_frozen || account.is_frozen {
+			return Zero::zero()
+		}
+		let held = Held::<T>::get(asset, who);
+		if keep_alive || !held.is_zero() {
+			// An account with funds on hold may not be reaped.
+			account.balance.saturating_sub(held).min(account.balance.saturating_sub(details.min_balance))
+		} else {
+			account.balance
+		}
+	}
+
+	fn can_deposit(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance)
+		-> DepositConsequence
+	{
+		let details = match Asset::<T>::get(asset) {
+			Some(details) => details,
+			None => return DepositConsequence::UnknownAsset,
+		};
+		if details.supply.checked_add(&amount).is_none() {
+			return DepositConsequence::Overflow
+		}
+		let balance = Account::<T>::get(asset, who).balance;
+		match balance.checked_add(&amount) {
+			None => DepositConsequence::Overflow,
+			Some(b) if b < details.min_balance => DepositConsequence::BelowMinimum,
+			Some(_) => DepositConsequence::Success,
+		}
+	}
+
+	fn can_withdraw(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance)
+		-> WithdrawConsequence<Self::Balance>
+	{
+		let details = match Asset::<T>::get(asset) {
+			Some(details) => details,
+			None => return WithdrawConsequence::UnknownAsset,
+		};
+		if details.supply.checked_sub(&amount).is_none() {
+			return WithdrawConsequence::Underflow
+		}
+		let account = Account::<T>::get(asset, who);
+		if details.is_frozen || account.is_frozen {
+			return WithdrawConsequence::Frozen
+		}
+		let held = Held::<T>::get(asset, who);
+		match account.balance.checked_sub(&amount) {
+			None => WithdrawConsequence::NoFunds,
+			Some(rest) if rest < held => WithdrawConsequence::Frozen,
+			Some(rest) if !held.is_zero() && rest < details.min_balance => WithdrawConsequence::WouldDie,
+			Some(rest) if !rest.is_zero() && rest < details.min_balance =>
+				WithdrawConsequence::ReducedToZero(rest),
+			Some(_) => WithdrawConsequence::Success,
+		}
+	}
+}
+
+impl<T: Trait> Mutate<<T as frame_system::Trait>::AccountId> for Module<T> {
+	fn mint_into(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		Self::do_mint(asset, who, amount, None)
+	}
+
+	fn burn_from(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance)
+		-> Result<Self::Balance, DispatchError>
+	{
+		Self::do_burn(asset, who, amount, None)
+	}
+}
+
+impl<T: Trait> Transfer<<T as frame_system::Trait>::AccountId> for Module<T> {
+	fn transfer(
+		asset: Self::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		if keep_alive {
+			let balance = Account::<T>::get(asset, source).balance;
+			let min_balance = <Self as Inspect<T::AccountId>>::minimum_balance(asset);
+			ensure!(balance.saturating_sub(amount) >= min_balance, Error::<T>::WouldDie);
+		}
+		Self::do_transfer(asset, source, dest, amount, None)
+	}
+}
+
+impl<T: Trait> Unbalanced<<T as frame_system::Trait>::AccountId> for Module<T> {
+	fn set_balance(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		Asset::<T>::try_mutate(asset, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+			ensure!(amount.is_zero() || amount >= details.min_balance, Error::<T>::BalanceLow);
+			ensure!(amount >= Held::<T>::get(asset, who), Error::<T>::BalanceLow);
+
+			Account::<T>::mutate_exists(asset, who, |maybe_account| {
+				match (maybe_account.is_some(), amount.is_zero()) {
+					(false, false) => details.accounts = details.accounts.saturating_add(1),
+					(true, true) => details.accounts = details.accounts.saturating_sub(1),
+					_ => {}
+				}
+				if amount.is_zero() {
+					*maybe_account = None;
+				} else {
+					maybe_account.get_or_insert_with(Default::default).balance = amount;
+				}
+			});
+			Ok(())
+		})
+	}
+
+	fn set_total_issuance(asset: Self::AssetId, amount: Self::Balance) {
+		Asset::<T>::mutate_exists(asset, |maybe_details| {
+			if let Some(ref mut details) = maybe_details {
+				details.supply = amount;
+			}
+		});
+	}
+}
+
+impl<T: Trait> InspectHold<<T as frame_system::Trait>::AccountId> for Module<T> {
+	fn balance_on_hold(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		Held::<T>::get(asset, who)
+	}
+
+	fn can_hold(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> bool {
+		match Asset::<T>::get(asset) {
+			Some(details) if !details.is_frozen => {}
+			_ => return false,
+		}
+		let account = Account::<T>::get(asset, who);
+		!account.is_frozen && Held::<T>::get(asset, who)
+			.checked_add(&amount)
+			.map_or(false, |held| held <= account.balance)
+	}
+}
+
+impl<T: Trait> MutateHold<<T as frame_system::Trait>::AccountId> for Module<T> {
+	fn hold(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		ensure!(<Self as InspectHold<T::AccountId>>::can_hold(asset, who, amount), Error::<T>::BalanceLow);
+		Self::set_held(asset, who, Held::<T>::get(asset, who) + amount);
+		Ok(())
+	}
+
+	fn release(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance, best_effort: bool)
+		-> Result<Self::Balance, DispatchError>
+	{
+		let held = Held::<T>::get(asset, who);
+		ensure!(best_effort || held >= amount, Error::<T>::BalanceLow);
+		let released = amount.min(held);
+		Self::set_held(asset, who, held - released);
+		Ok(released)
+	}
+
+	fn transfer_held(
+		asset: Self::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+		on_hold: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		let held = Held::<T>::get(asset, source);
+		ensure!(best_effort || held >= amount, Error::<T>::BalanceLow);
+		if on_hold {
+			ensure!(Account::<T>::contains_key(asset, dest), Error::<T>::BalanceZero);
+		}
+		let amount = amount.min(held);
+		if amount.is_zero() {
+			return Ok(amount)
+		}
+
+		with_transaction(|| {
+			Self::set_held(asset, source, held - amount);
+			let result = Self::do_transfer(asset, source, dest, amount, None).map(|_| {
+				if on_hold {
+					Self::set_held(asset, dest, Held::<T>::get(asset, dest).saturating_add(amount));
+				}
+				amount
+			});
+			match result {
+				Ok(_) => TransactionOutcome::Commit(result),
+				Err(_) => TransactionOutcome::Rollback(result),
+			}
+		})
+	}
+
+	fn slash_held(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance)
+		-> Result<Self::Balance, DispatchError>
+	{
+		let held = Held::<T>::get(asset, who);
+		let slashed = amount.min(held);
+		if slashed.is_zero() {
+			return Ok(slashed)
+		}
+
+		Asset::<T>::try_mutate(asset, |maybe_details| {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+			let still_held = held - slashed;
+			Self::set_held(asset, who, still_held);
+
+			let mut burned = slashed;
+			Account::<T>::mutate_exists(asset, who, |maybe_account| {
+				if let Some(mut account) = maybe_account.take() {
+					account.balance = account.balance.saturating_sub(slashed);
+					// The account is kept while any of its funds are on hold, otherwise the dust
+					// is burned along with the slashed funds.
+					*maybe_account = if still_held.is_zero() && account.balance < details.min_balance {
+						burned += account.balance;
+						details.accounts = details.accounts.saturating_sub(1);
+						None
+					} else {
+						Some(account)
+					};
+				}
+			});
+			details.supply = details.supply.saturating_sub(burned);
+
+			Self::deposit_event(RawEvent::Burned(asset, who.clone(), burned));
+			Ok(slashed)
+		})
+	}
+}
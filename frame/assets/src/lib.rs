@@ -22,10 +22,14 @@
 //! ## Overview
 //!
 //! The Assets module provides functionality for asset management of fungible asset classes
-//! with a fixed supply, including:
+//! with a managed supply, including:
 //!
-//! * Asset Issuance
+//! * Asset Creation
+//! * Asset Issuance (Minting) and Burning
 //! * Asset Transfer
+//! * Asset Transfer Approvals
+//! * Account and Asset Freezing
+//! * Asset Metadata
 //! * Asset Destruction
 //!
 //! To use it in your runtime, you need to implement the assets [`Trait`](./trait.Trait.html).
@@ -34,31 +38,73 @@
 //!
 //! ### Terminology
 //!
-//! * **Asset issuance:** The creation of a new asset, whose total supply will belong to the
-//!   account that issues the asset.
-//! * **Asset transfer:** The action of transferring assets from one account to another.
-//! * **Asset destruction:** The process of an account removing its entire holding of an asset.
-//! * **Fungible asset:** An asset whose units are interchangeable.
-//! * **Non-fungible asset:** An asset for which each unit has unique characteristics.
+//! * **Admin:** An account ID uniquely privileged to be able to unfreeze (thaw) an account and its
+//!   assets, as well as forcibly transfer a particular class of assets between arbitrary accounts
+//!   and reduce the balance of a particular class of assets of arbitrary accounts.
+//! * **Asset issuance/minting**: The creation of a new asset, whose total supply will belong to the
+//!   account that issues the asset. This is a privileged operation.
+//! * **Asset transfer**: The reduction of the balance of an asset of one account with the
+//!   corresponding increase in the balance of another.
+//! * **Asset destruction**: The removal of an asset class, together with all of its balances,
+//!   approvals and metadata. This is a privileged operation.
+//! * **Fungible asset**: An asset whose units are interchangeable.
+//! * **Issuer**: An account ID uniquely privileged to be able to mint a particular class of assets.
+//! * **Freezer**: An account ID uniquely privileged to be able to freeze an account from
+//!   transferring a particular class of assets.
+//! * **Freezing**: Removing the possibility of an unpermissioned transfer of an asset from a
+//!   particular account.
+//! * **Minimum balance**: The minimum balance required for an account to hold an asset class.
+//!   Accounts whose balance falls below it are removed, in the same way that the existential
+//!   deposit works for the native currency.
+//! * **Approval**: An allowance granted by an account holder to a delegate, permitting the
+//!   delegate to transfer up to a given amount of the holder's assets.
+//! * **Non-fungible asset**: An asset for which each unit has unique characteristics.
+//! * **Owner**: An account ID uniquely privileged to be able to destroy a particular asset class,
+//!   or to set the Issuer, Freezer or Admin of that asset class.
 //!
 //! ### Goals
 //!
 //! The assets system in Substrate is designed to make the following possible:
 //!
-//! * Issue a unique asset to its creator's account.
-//! * Move assets between accounts.
-//! * Remove an account's balance of an asset when requested by that account's owner and update
-//!   the asset's total supply.
+//! * Create a new asset class, whose management is delegated to a team of accounts.
+//! * Issue new assets in a permissioned or permissionless way, depending on the team.
+//! * Move assets between accounts, either directly or through an approved delegate.
+//! * Freeze and thaw individual accounts or entire asset classes.
+//! * Attach name, symbol and decimals metadata to an asset class, held under a deposit.
+//! * Destroy an entire asset class and return its deposits to the owner.
 //!
 //! ## Interface
 //!
-//! ### Dispatchable Functions
+//! ### Permissionless Functions
 //!
-//! * `issue` - Issues the total supply of a new fungible asset to the account of the caller of the function.
-//! * `transfer` - Transfers an `amount` of units of fungible asset `id` from the balance of
-//! the function caller's account (`origin`) to a `target` account.
-//! * `destroy` - Destroys the entire holding of a fungible asset `id` associated with the account
-//! that called the function.
+//! * `create`: Creates a new asset class, taking the required deposit.
+//! * `transfer`: Transfer sender's assets to another account.
+//! * `approve_transfer`: Allow a delegate to transfer some of the sender's assets.
+//! * `cancel_approval`: Revoke a previously granted approval, returning its deposit.
+//! * `transfer_approved`: Transfer assets of another account under a previous approval.
+//!
+//! ### Permissioned Functions
+//!
+//! * `force_create`: Creates a new asset class without taking any deposit.
+//! * `destroy`: Destroys an entire asset class; called by the asset class's Owner or the
+//!   `ForceOrigin`.
+//!
+//! ### Privileged Functions
+//! * `mint`: Increases the asset balance of an account; called by the asset class's Issuer.
+//! * `burn`: Decreases the asset balance of an account; called by the asset class's Admin.
+//! * `force_transfer`: Transfers between arbitrary accounts; called by the asset class's Admin.
+//! * `freeze`: Disallows further `transfer`s from an account; called by the asset class's Freezer.
+//! * `thaw`: Allows further `transfer`s from an account; called by the asset class's Admin.
+//! * `freeze_asset`: Disallows further `transfer`s of the whole asset class; called by the asset
+//!   class's Freezer.
+//! * `thaw_asset`: Allows further `transfer`s of the whole asset class; called by the asset
+//!   class's Admin.
+//! * `transfer_ownership`: Changes an asset class's Owner; called by the asset class's Owner.
+//! * `set_team`: Changes an asset class's Admin, Freezer and Issuer; called by the asset class's
+//!   Owner.
+//! * `set_metadata`: Sets the name, symbol and decimals of an asset class; called by the asset
+//!   class's Owner.
+//! * `clear_metadata`: Removes the metadata of an asset class; called by the asset class's Owner.
 //!
 //! Please refer to the [`Call`](./enum.Call.html) enum and its associated variants for documentation on each function.
 //!
@@ -68,62 +114,11 @@
 //! * `balance` - Get the asset `id` balance of `who`.
 //! * `total_supply` - Get the total supply of an asset `id`.
 //!
-//! Please refer to the [`Module`](./struct.Module.html) struct for details on publicly available functions.
-//!
-//! ## Usage
-//!
-//! The following example shows how to use the Assets module in your runtime by exposing public functions to:
-//!
-//! * Issue a new fungible asset for a token distribution event (airdrop).
-//! * Query the fungible asset holding balance of an account.
-//! * Query the total supply of a fungible asset that has been issued.
-//!
-//! ### Prerequisites
-//!
-//! Import the Assets module and types and derive your runtime's configuration traits from the Assets module trait.
-//!
-//! ### Simple Code Snippet
-//!
-//! ```rust,ignore
-//! use pallet_assets as assets;
-//! use frame_support::{decl_module, dispatch, ensure};
-//! use frame_system::ensure_signed;
-//!
-//! pub trait Trait: assets::Trait { }
+//! The module also implements the `fungibles::{Inspect, Mutate, Transfer, Unbalanced}` traits of
+//! `frame_support`, so that other modules can deal with any asset it holds. A single asset may be
+//! used as a `Currency` through `frame_support::traits::fungibles::ItemOf`.
 //!
-//! decl_module! {
-//! 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-//! 		pub fn issue_token_airdrop(origin) -> dispatch::DispatchResult {
-//! 			let sender = ensure_signed(origin).map_err(|e| e.as_str())?;
-//!
-//! 			const ACCOUNT_ALICE: u64 = 1;
-//! 			const ACCOUNT_BOB: u64 = 2;
-//! 			const COUNT_AIRDROP_RECIPIENTS: u64 = 2;
-//! 			const TOKENS_FIXED_SUPPLY: u64 = 100;
-//!
-//! 			ensure!(!COUNT_AIRDROP_RECIPIENTS.is_zero(), "Divide by zero error.");
-//!
-//! 			let asset_id = Self::next_asset_id();
-//!
-//! 			<NextAssetId<T>>::mutate(|asset_id| *asset_id += 1);
-//! 			<Balances<T>>::insert((asset_id, &ACCOUNT_ALICE), TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
-//! 			<Balances<T>>::insert((asset_id, &ACCOUNT_BOB), TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
-//! 			<TotalSupply<T>>::insert(asset_id, TOKENS_FIXED_SUPPLY);
-//!
-//! 			Self::deposit_event(RawEvent::Issued(asset_id, sender, TOKENS_FIXED_SUPPLY));
-//! 			Ok(())
-//! 		}
-//! 	}
-//! }
-//! ```
-//!
-//! ## Assumptions
-//!
-//! Below are assumptions that must be held when using this module.  If any of
-//! them are violated, the behavior of this module is undefined.
-//!
-//! * The total count of assets should be less than
-//!   `Trait::AssetId::max_value()`.
+//! Please refer to the [`Module`](./struct.Module.html) struct for details on publicly available functions.
 //!
 //! ## Related Modules
 //!
@@ -133,10 +128,52 @@
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{Parameter, decl_module, decl_event, decl_storage, decl_error, ensure};
-use sp_runtime::traits::{Member, AtLeast32Bit, AtLeast32BitUnsigned, Zero, StaticLookup};
+mod benchmarking;
+mod default_weight;
+mod impl_fungibles;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_runtime::{
+	RuntimeDebug, DispatchResult, DispatchError,
+	traits::{
+		Member, AtLeast32Bit, AtLeast32BitUnsigned, Zero, StaticLookup, Saturating, CheckedAdd, CheckedSub,
+		MaybeSerializeDeserialize,
+	},
+};
+use frame_support::{
+	Parameter, decl_module, decl_event, decl_storage, decl_error, ensure, IterableStorageDoubleMap,
+	traits::{Currency, ReservableCurrency, EnsureOrigin, Get, BalanceStatus::Reserved},
+	weights::Weight,
+};
 use frame_system::ensure_signed;
-use sp_runtime::traits::One;
+
+type DepositBalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn force_create() -> Weight;
+	fn destroy(a: u32, p: u32, ) -> Weight;
+	fn mint() -> Weight;
+	fn burn() -> Weight;
+	fn transfer() -> Weight;
+	fn force_transfer() -> Weight;
+	fn freeze() -> Weight;
+	fn thaw() -> Weight;
+	fn freeze_asset() -> Weight;
+	fn thaw_asset() -> Weight;
+	fn transfer_ownership() -> Weight;
+	fn set_team() -> Weight;
+	fn set_metadata(n: u32, s: u32, ) -> Weight;
+	fn clear_metadata() -> Weight;
+	fn approve_transfer() -> Weight;
+	fn cancel_approval() -> Weight;
+	fn transfer_approved() -> Weight;
+}
 
 /// The module configuration trait.
 pub trait Trait: frame_system::Trait {
@@ -144,10 +181,214 @@ pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
 	/// The units in which we record balances.
-	type Balance: Member + Parameter + AtLeast32BitUnsigned + Default + Copy;
+	type Balance: Member + Parameter + AtLeast32BitUnsigned + Default + Copy + MaybeSerializeDeserialize;
 
 	/// The arithmetic type of asset identifier.
 	type AssetId: Parameter + AtLeast32Bit + Default + Copy;
+
+	/// The currency mechanism, used for paying for deposits.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The origin which may forcibly create or destroy an asset.
+	type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The basic amount of funds that must be reserved when creating a new asset class.
+	type AssetDeposit: Get<DepositBalanceOf<Self>>;
+
+	/// The basic amount of funds that must be reserved when adding metadata to an asset class.
+	type MetadataDepositBase: Get<DepositBalanceOf<Self>>;
+
+	/// The additional funds that must be reserved for the number of bytes stored in the
+	/// metadata of an asset class.
+	type MetadataDepositPerByte: Get<DepositBalanceOf<Self>>;
+
+	/// The amount of funds that must be reserved when creating a new transfer approval.
+	type ApprovalDeposit: Get<DepositBalanceOf<Self>>;
+
+	/// The maximum length of a name or symbol stored on-chain.
+	type StringLimit: Get<u32>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct AssetDetails<Balance, AccountId, DepositBalance> {
+	/// Can change `owner`, `issuer`, `freezer` and `admin` accounts.
+	owner: AccountId,
+	/// Can mint tokens.
+	issuer: AccountId,
+	/// Can thaw tokens, force transfers and burn tokens from any account.
+	admin: AccountId,
+	/// Can freeze tokens.
+	freezer: AccountId,
+	/// The total supply across all accounts.
+	supply: Balance,
+	/// The balance deposited for this asset. This pays for the data stored here.
+	deposit: DepositBalance,
+	/// The minimum balance an account must hold to exist. Accounts whose balance would fall
+	/// below this are removed.
+	min_balance: Balance,
+	/// The total number of accounts holding this asset.
+	accounts: u32,
+	/// The total number of approvals outstanding for this asset.
+	approvals: u32,
+	/// Whether the asset is frozen for non-admin transfers.
+	is_frozen: bool,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct AssetBalance<Balance> {
+	/// The balance.
+	balance: Balance,
+	/// Whether the account is frozen.
+	is_frozen: bool,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct AssetMetadata<DepositBalance> {
+	/// The balance deposited for this metadata.
+	///
+	/// This pays for the data stored in this struct.
+	deposit: DepositBalance,
+	/// The user friendly name of this asset. Limited in length by `StringLimit`.
+	name: Vec<u8>,
+	/// The ticker symbol for this asset. Limited in length by `StringLimit`.
+	symbol: Vec<u8>,
+	/// The number of decimals this asset uses to represent one unit.
+	decimals: u8,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct Approval<Balance, DepositBalance> {
+	/// The amount of funds approved for the balance transfer from the owner to some delegated
+	/// target.
+	amount: Balance,
+	/// The amount reserved on the owner's account to hold this item in storage.
+	deposit: DepositBalance,
+}
+
+/// Witness data for the `destroy` call, bounding the work it has to do.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct DestroyWitness {
+	/// The number of accounts holding the asset.
+	#[codec(compact)]
+	pub accounts: u32,
+	/// The number of transfer approvals outstanding for the asset.
+	#[codec(compact)]
+	pub approvals: u32,
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Assets {
+		/// Details of an asset.
+		Asset: map hasher(blake2_128_concat) T::AssetId
+			=> Option<AssetDetails<T::Balance, T::AccountId, DepositBalanceOf<T>>>;
+
+		/// The number of units of assets held by any given account.
+		Account: double_map
+			hasher(blake2_128_concat) T::AssetId,
+			hasher(blake2_128_concat) T::AccountId
+			=> AssetBalance<T::Balance>;
+
+		/// The part of the balance of an account which is on hold. Keyed by the asset and then by
+		/// the account. Funds on hold can't be transferred or burned until they are released.
+		Held: double_map
+			hasher(blake2_128_concat) T::AssetId,
+			hasher(blake2_128_concat) T::AccountId
+			=> T::Balance;
+
+		/// Approved balance transfers. First balance is the amount approved for transfer. Second
+		/// is the amount of `T::Currency` reserved for storing this. Keyed by the asset and then
+		/// by the `(owner, delegate)` pair.
+		Approvals: double_map
+			hasher(blake2_128_concat) T::AssetId,
+			hasher(blake2_128_concat) (T::AccountId, T::AccountId)
+			=> Option<Approval<T::Balance, DepositBalanceOf<T>>>;
+
+		/// Metadata of an asset.
+		Metadata: map hasher(blake2_128_concat) T::AssetId => AssetMetadata<DepositBalanceOf<T>>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		<T as frame_system::Trait>::AccountId,
+		<T as Trait>::Balance,
+		<T as Trait>::AssetId,
+	{
+		/// Some asset class was created. [asset_id, creator, owner]
+		Created(AssetId, AccountId, AccountId),
+		/// Some assets were issued. [asset_id, owner, amount]
+		Issued(AssetId, AccountId, Balance),
+		/// Some assets were transferred. [asset_id, from, to, amount]
+		Transferred(AssetId, AccountId, AccountId, Balance),
+		/// Some assets were destroyed. [asset_id, owner, balance]
+		Burned(AssetId, AccountId, Balance),
+		/// The management team changed [asset_id, issuer, admin, freezer]
+		TeamChanged(AssetId, AccountId, AccountId, AccountId),
+		/// The owner changed [asset_id, owner]
+		OwnerChanged(AssetId, AccountId),
+		/// Some assets was transferred by an admin. [asset_id, from, to, amount]
+		ForceTransferred(AssetId, AccountId, AccountId, Balance),
+		/// Some account `who` was frozen. [asset_id, who]
+		Frozen(AssetId, AccountId),
+		/// Some account `who` was thawed. [asset_id, who]
+		Thawed(AssetId, AccountId),
+		/// Some asset `asset_id` was frozen. [asset_id]
+		AssetFrozen(AssetId),
+		/// Some asset `asset_id` was thawed. [asset_id]
+		AssetThawed(AssetId),
+		/// An asset class was destroyed. [asset_id]
+		Destroyed(AssetId),
+		/// Some asset class was force-created. [asset_id, owner]
+		ForceCreated(AssetId, AccountId),
+		/// New metadata has been set for an asset. [asset_id, name, symbol, decimals]
+		MetadataSet(AssetId, Vec<u8>, Vec<u8>, u8),
+		/// Metadata has been cleared for an asset. [asset_id]
+		MetadataCleared(AssetId),
+		/// (Additional) funds have been approved for transfer to a destination account.
+		/// [asset_id, source, delegate, amount]
+		ApprovedTransfer(AssetId, AccountId, AccountId, Balance),
+		/// An approval for account `delegate` was cancelled by `owner`.
+		/// [asset_id, owner, delegate]
+		ApprovalCancelled(AssetId, AccountId, AccountId),
+		/// An `amount` was transferred in its entirety from `owner` to `destination` by
+		/// the approved `delegate`.
+		/// [asset_id, owner, delegate, destination, amount]
+		TransferredApproved(AssetId, AccountId, AccountId, AccountId, Balance),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Transfer amount should be non-zero.
+		AmountZero,
+		/// Account balance must be greater than or equal to the transfer amount.
+		BalanceLow,
+		/// Balance should be non-zero.
+		BalanceZero,
+		/// The signing account has no permission to do the operation.
+		NoPermission,
+		/// The given asset ID is unknown.
+		Unknown,
+		/// The origin account is frozen.
+		Frozen,
+		/// The asset ID is already taken.
+		InUse,
+		/// Invalid witness data given.
+		BadWitness,
+		/// Minimum balance should be non-zero.
+		MinBalanceZero,
+		/// A mint operation lead to an overflow.
+		Overflow,
+		/// Invalid metadata given.
+		BadMetadata,
+		/// No approval exists that would allow the transfer.
+		Unapproved,
+		/// The source account would be left with a non-zero balance below the minimum balance.
+		WouldDie,
+	}
 }
 
 decl_module! {
@@ -155,30 +396,247 @@ decl_module! {
 		type Error = Error<T>;
 
 		fn deposit_event() = default;
-		/// Issue a new class of fungible assets. There are, and will only ever be, `total`
-		/// such assets and they'll all belong to the `origin` initially. It will have an
-		/// identifier `AssetId` instance: this will be specified in the `Issued` event.
+
+		/// The basic amount of funds that must be reserved when creating a new asset class.
+		const AssetDeposit: DepositBalanceOf<T> = T::AssetDeposit::get();
+
+		/// The basic amount of funds that must be reserved when adding metadata to an asset class.
+		const MetadataDepositBase: DepositBalanceOf<T> = T::MetadataDepositBase::get();
+
+		/// The additional funds that must be reserved for each byte of metadata.
+		const MetadataDepositPerByte: DepositBalanceOf<T> = T::MetadataDepositPerByte::get();
+
+		/// The amount of funds that must be reserved when creating a new transfer approval.
+		const ApprovalDeposit: DepositBalanceOf<T> = T::ApprovalDeposit::get();
+
+		/// The maximum length of a name or symbol stored on-chain.
+		const StringLimit: u32 = T::StringLimit::get();
+
+		/// Issue a new class of fungible assets from a public origin.
+		///
+		/// This new asset class has no assets initially.
+		///
+		/// The origin must be Signed and the sender must have sufficient funds free.
+		///
+		/// Funds of sender are reserved according to the formula:
+		/// `AssetDeposit`.
+		///
+		/// Parameters:
+		/// - `id`: The identifier of the new asset. This must not be currently in use to identify
+		/// an existing asset.
+		/// - `admin`: The admin of this class of assets. The admin is the initial address of each
+		/// member of the asset class's admin team.
+		/// - `min_balance`: The minimum balance of this new asset that any single account must
+		/// have. If an account's balance is reduced below this, then it collapses to zero.
+		///
+		/// Emits `Created` event when successful.
 		///
 		/// # <weight>
 		/// - `O(1)`
-		/// - 1 storage mutation (codec `O(1)`).
-		/// - 2 storage writes (condec `O(1)`).
+		/// - 1 storage read and write.
+		/// - 1 reserve.
 		/// - 1 event.
 		/// # </weight>
-		#[weight = 0]
-		fn issue(origin, #[compact] total: T::Balance) {
-			let origin = ensure_signed(origin)?;
+		#[weight = T::WeightInfo::create()]
+		fn create(origin,
+			#[compact] id: T::AssetId,
+			admin: <T::Lookup as StaticLookup>::Source,
+			#[compact] min_balance: T::Balance,
+		) {
+			let owner = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+
+			ensure!(!Asset::<T>::contains_key(id), Error::<T>::InUse);
+			ensure!(!min_balance.is_zero(), Error::<T>::MinBalanceZero);
+
+			let deposit = T::AssetDeposit::get();
+			T::Currency::reserve(&owner, deposit)?;
 
-			let id = Self::next_asset_id();
-			<NextAssetId<T>>::mutate(|id| *id += One::one());
+			Asset::<T>::insert(id, AssetDetails {
+				owner: owner.clone(),
+				issuer: admin.clone(),
+				admin: admin.clone(),
+				freezer: admin.clone(),
+				supply: Zero::zero(),
+				deposit,
+				min_balance,
+				accounts: 0,
+				approvals: 0,
+				is_frozen: false,
+			});
+			Self::deposit_event(RawEvent::Created(id, owner, admin));
+		}
+
+		/// Issue a new class of fungible assets from a privileged origin.
+		///
+		/// This new asset class has no assets initially.
+		///
+		/// The origin must conform to `ForceOrigin`.
+		///
+		/// Unlike `create`, no funds are reserved.
+		///
+		/// - `id`: The identifier of the new asset. This must not be currently in use to identify
+		/// an existing asset.
+		/// - `owner`: The owner of this class of assets. The owner has full superuser permissions
+		/// over this asset, but may later change and configure the permissions using `transfer_ownership`
+		/// and `set_team`.
+		/// - `min_balance`: The minimum balance of this new asset that any single account must
+		/// have. If an account's balance is reduced below this, then it collapses to zero.
+		///
+		/// Emits `ForceCreated` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::force_create()]
+		fn force_create(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			#[compact] min_balance: T::Balance,
+		) {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			ensure!(!Asset::<T>::contains_key(id), Error::<T>::InUse);
+			ensure!(!min_balance.is_zero(), Error::<T>::MinBalanceZero);
+
+			Asset::<T>::insert(id, AssetDetails {
+				owner: owner.clone(),
+				issuer: owner.clone(),
+				admin: owner.clone(),
+				freezer: owner.clone(),
+				supply: Zero::zero(),
+				deposit: Zero::zero(),
+				min_balance,
+				accounts: 0,
+				approvals: 0,
+				is_frozen: false,
+			});
+			Self::deposit_event(RawEvent::ForceCreated(id, owner));
+		}
+
+		/// Destroy a class of fungible assets.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// owner of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be destroyed. This must identify an existing
+		/// asset.
+		/// - `witness`: The number of accounts and outstanding approvals of the asset, which must
+		/// be at least as large as the values currently held in storage.
+		///
+		/// All balances, approvals and metadata of the asset are removed, and all deposits taken
+		/// for them are returned.
+		///
+		/// Emits `Destroyed` event when successful.
+		///
+		/// # <weight>
+		/// - `O(A + P)` where `A` is the number of accounts and `P` the number of approvals
+		///   given in the witness.
+		/// - 1 storage removal of the asset, up to `A + P` storage deletions.
+		/// - Up to `1 + P` unreserves.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::destroy(witness.accounts, witness.approvals)]
+		fn destroy(origin, #[compact] id: T::AssetId, witness: DestroyWitness) {
+			let maybe_check_owner = match T::ForceOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+			Asset::<T>::try_mutate_exists(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.take().ok_or(Error::<T>::Unknown)?;
+				if let Some(check_owner) = maybe_check_owner {
+					ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+				}
+				ensure!(details.accounts <= witness.accounts, Error::<T>::BadWitness);
+				ensure!(details.approvals <= witness.approvals, Error::<T>::BadWitness);
 
-			<Balances<T>>::insert((id, &origin), total);
-			<TotalSupply<T>>::insert(id, total);
+				Account::<T>::remove_prefix(&id);
+				Held::<T>::remove_prefix(&id);
+				for ((owner, _), approval) in Approvals::<T>::drain_prefix(&id) {
+					T::Currency::unreserve(&owner, approval.deposit);
+				}
+				let metadata = Metadata::<T>::take(&id);
+				T::Currency::unreserve(&details.owner, details.deposit.saturating_add(metadata.deposit));
 
-			Self::deposit_event(RawEvent::Issued(id, origin, total));
+				Self::deposit_event(RawEvent::Destroyed(id));
+				Ok(())
+			})?;
+		}
+
+		/// Mint assets of a particular class.
+		///
+		/// The origin must be Signed and the sender must be the Issuer of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to have some amount minted.
+		/// - `beneficiary`: The account to be credited with the minted assets.
+		/// - `amount`: The amount of the asset to be minted.
+		///
+		/// Emits `Issued` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the asset.
+		/// - 1 storage mutation of the beneficiary's balance.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::mint()]
+		fn mint(origin,
+			#[compact] id: T::AssetId,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let origin = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			Self::do_mint(id, &beneficiary, amount, Some(origin))?;
+		}
+
+		/// Reduce the balance of `who` by as much as possible up to `amount` assets of `id`.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// Bails with `BalanceZero` if the `who` is already dead.
+		///
+		/// - `id`: The identifier of the asset to have some amount burned.
+		/// - `who`: The account to be debited from.
+		/// - `amount`: The maximum amount by which `who`'s balance should be reduced.
+		///
+		/// Emits `Burned` with the actual amount burned. If this takes the balance to below the
+		/// minimum for the asset, then the amount burned is increased to take it to zero.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the asset.
+		/// - 1 storage mutation or deletion of `who`'s balance.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::burn()]
+		fn burn(origin,
+			#[compact] id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::do_burn(id, &who, amount, Some(origin))?;
 		}
 
-		/// Move some assets from one holder to another.
+		/// Move some assets from the sender account to another.
+		///
+		/// Origin must be Signed.
+		///
+		/// - `id`: The identifier of the asset to have some amount transferred.
+		/// - `target`: The account to be credited.
+		/// - `amount`: The amount by which the sender's balance of assets should be reduced and
+		/// `target`'s balance increased. The amount actually transferred may be slightly greater in
+		/// the case that the transfer would otherwise take the sender balance above zero but below
+		/// the minimum balance. Must be greater than zero.
+		///
+		/// Emits `Transferred` with the actual amount transferred. If this takes the source balance
+		/// to below the minimum for the asset, then the amount transferred is increased to take it
+		/// to zero.
 		///
 		/// # <weight>
 		/// - `O(1)`
@@ -186,80 +644,500 @@ decl_module! {
 		/// - 2 storage mutations (codec `O(1)`).
 		/// - 1 event.
 		/// # </weight>
-		#[weight = 0]
+		#[weight = T::WeightInfo::transfer()]
 		fn transfer(origin,
 			#[compact] id: T::AssetId,
 			target: <T::Lookup as StaticLookup>::Source,
 			#[compact] amount: T::Balance
 		) {
 			let origin = ensure_signed(origin)?;
-			let origin_account = (id, origin.clone());
-			let origin_balance = <Balances<T>>::get(&origin_account);
 			let target = T::Lookup::lookup(target)?;
-			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
-			ensure!(origin_balance >= amount, Error::<T>::BalanceLow);
 
-			Self::deposit_event(RawEvent::Transferred(id, origin, target.clone(), amount));
-			<Balances<T>>::insert(origin_account, origin_balance - amount);
-			<Balances<T>>::mutate((id, target), |balance| *balance += amount);
+			let amount = Self::do_transfer(id, &origin, &target, amount, None)?;
+			Self::deposit_event(RawEvent::Transferred(id, origin, target, amount));
 		}
 
-		/// Destroy any assets of `id` owned by `origin`.
+		/// Move some assets from one account to another.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to have some amount transferred.
+		/// - `source`: The account to be debited.
+		/// - `dest`: The account to be credited.
+		/// - `amount`: The amount by which the `source`'s balance of assets should be reduced and
+		/// `dest`'s balance increased. The amount actually transferred may be slightly greater in
+		/// the case that the transfer would otherwise take the `source` balance above zero but
+		/// below the minimum balance. Must be greater than zero.
+		///
+		/// Emits `ForceTransferred` with the actual amount transferred. If this takes the source
+		/// balance to below the minimum for the asset, then the amount transferred is increased to
+		/// take it to zero.
 		///
 		/// # <weight>
 		/// - `O(1)`
-		/// - 1 storage mutation (codec `O(1)`).
-		/// - 1 storage deletion (codec `O(1)`).
+		/// - 2 static lookups
+		/// - 2 storage mutations (codec `O(1)`).
 		/// - 1 event.
 		/// # </weight>
-		#[weight = 0]
-		fn destroy(origin, #[compact] id: T::AssetId) {
+		#[weight = T::WeightInfo::force_transfer()]
+		fn force_transfer(origin,
+			#[compact] id: T::AssetId,
+			source: <T::Lookup as StaticLookup>::Source,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
 			let origin = ensure_signed(origin)?;
-			let balance = <Balances<T>>::take((id, &origin));
-			ensure!(!balance.is_zero(), Error::<T>::BalanceZero);
+			let source = T::Lookup::lookup(source)?;
+			let dest = T::Lookup::lookup(dest)?;
 
-			<TotalSupply<T>>::mutate(id, |total_supply| *total_supply -= balance);
-			Self::deposit_event(RawEvent::Destroyed(id, origin, balance));
+			let amount = Self::do_transfer(id, &source, &dest, amount, Some(origin))?;
+			Self::deposit_event(RawEvent::ForceTransferred(id, source, dest, amount));
 		}
-	}
-}
 
-decl_event! {
-	pub enum Event<T> where
-		<T as frame_system::Trait>::AccountId,
-		<T as Trait>::Balance,
-		<T as Trait>::AssetId,
-	{
-		/// Some assets were issued. [asset_id, owner, total_supply]
-		Issued(AssetId, AccountId, Balance),
-		/// Some assets were transferred. [asset_id, from, to, amount]
-		Transferred(AssetId, AccountId, AccountId, Balance),
-		/// Some assets were destroyed. [asset_id, owner, balance]
-		Destroyed(AssetId, AccountId, Balance),
-	}
-}
+		/// Disallow further unprivileged transfers from an account.
+		///
+		/// Origin must be Signed and the sender should be the Freezer of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be frozen.
+		/// - `who`: The account to be frozen.
+		///
+		/// Emits `Frozen`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read of the asset, 1 storage mutation of `who`'s balance.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::freeze()]
+		fn freeze(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+			let origin = ensure_signed(origin)?;
 
-decl_error! {
-	pub enum Error for Module<T: Trait> {
-		/// Transfer amount should be non-zero
-		AmountZero,
-		/// Account balance must be greater than or equal to the transfer amount
-		BalanceLow,
-		/// Balance should be non-zero
-		BalanceZero,
-	}
-}
+			let d = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(&origin == &d.freezer, Error::<T>::NoPermission);
+			let who = T::Lookup::lookup(who)?;
+			ensure!(Account::<T>::contains_key(id, &who), Error::<T>::BalanceZero);
 
-decl_storage! {
-	trait Store for Module<T: Trait> as Assets {
-		/// The number of units of assets held by any given account.
-		Balances: map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => T::Balance;
-		/// The next asset identifier up for grabs.
-		NextAssetId get(fn next_asset_id): T::AssetId;
-		/// The total unit supply of an asset.
+			Account::<T>::mutate(id, &who, |a| a.is_frozen = true);
+
+			Self::deposit_event(RawEvent::Frozen(id, who));
+		}
+
+		/// Allow unprivileged transfers from an account again.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be thawed.
+		/// - `who`: The account to be unfrozen.
+		///
+		/// Emits `Thawed`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read of the asset, 1 storage mutation of `who`'s balance.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::thaw()]
+		fn thaw(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+			let origin = ensure_signed(origin)?;
+
+			let details = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(&origin == &details.admin, Error::<T>::NoPermission);
+			let who = T::Lookup::lookup(who)?;
+			ensure!(Account::<T>::contains_key(id, &who), Error::<T>::BalanceZero);
+
+			Account::<T>::mutate(id, &who, |a| a.is_frozen = false);
+
+			Self::deposit_event(RawEvent::Thawed(id, who));
+		}
+
+		/// Disallow further unprivileged transfers for the asset class.
+		///
+		/// Origin must be Signed and the sender should be the Freezer of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be frozen.
+		///
+		/// Emits `AssetFrozen`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the asset.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::freeze_asset()]
+		fn freeze_asset(origin, #[compact] id: T::AssetId) {
+			let origin = ensure_signed(origin)?;
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let d = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(&origin == &d.freezer, Error::<T>::NoPermission);
+
+				d.is_frozen = true;
+
+				Self::deposit_event(RawEvent::AssetFrozen(id));
+				Ok(())
+			})?;
+		}
+
+		/// Allow unprivileged transfers for the asset again.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be thawed.
+		///
+		/// Emits `AssetThawed`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the asset.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::thaw_asset()]
+		fn thaw_asset(origin, #[compact] id: T::AssetId) {
+			let origin = ensure_signed(origin)?;
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let d = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(&origin == &d.admin, Error::<T>::NoPermission);
+
+				d.is_frozen = false;
+
+				Self::deposit_event(RawEvent::AssetThawed(id));
+				Ok(())
+			})?;
+		}
+
+		/// Change the Owner of an asset.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `owner`: The new Owner of this asset.
+		///
+		/// The deposits held for the asset and its metadata are moved to the new Owner.
+		///
+		/// Emits `OwnerChanged`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the asset, 1 storage read of the metadata.
+		/// - 1 repatriation of reserved funds.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::transfer_ownership()]
+		fn transfer_ownership(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			Asset::<T>::try_mutate(id, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(&origin == &details.owner, Error::<T>::NoPermission);
+				if details.owner == owner { return Ok(()) }
+
+				let metadata_deposit = Metadata::<T>::get(id).deposit;
+				let deposit = details.deposit.saturating_add(metadata_deposit);
+
+				// Move the deposit to the new owner.
+				T::Currency::repatriate_reserved(&details.owner, &owner, deposit, Reserved)?;
+
+				details.owner = owner.clone();
+
+				Self::deposit_event(RawEvent::OwnerChanged(id, owner));
+				Ok(())
+			})
+		}
+
+		/// Change the Issuer, Admin and Freezer of an asset.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset to be frozen.
+		/// - `issuer`: The new Issuer of this asset.
+		/// - `admin`: The new Admin of this asset.
+		/// - `freezer`: The new Freezer of this asset.
 		///
-		/// TWOX-NOTE: `AssetId` is trusted, so this is safe.
-		TotalSupply: map hasher(twox_64_concat) T::AssetId => T::Balance;
+		/// Emits `TeamChanged`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the asset.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::set_team()]
+		fn set_team(origin,
+			#[compact] id: T::AssetId,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			admin: <T::Lookup as StaticLookup>::Source,
+			freezer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let freezer = T::Lookup::lookup(freezer)?;
+
+			Asset::<T>::try_mutate(id, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(&origin == &details.owner, Error::<T>::NoPermission);
+
+				details.issuer = issuer.clone();
+				details.admin = admin.clone();
+				details.freezer = freezer.clone();
+
+				Self::deposit_event(RawEvent::TeamChanged(id, issuer, admin, freezer));
+				Ok(())
+			})
+		}
+
+		/// Set the metadata for an asset.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the asset `id`.
+		///
+		/// Funds of sender are reserved according to the formula:
+		/// `MetadataDepositBase + MetadataDepositPerByte * (name.len + symbol.len)` taking into
+		/// account any already reserved funds.
+		///
+		/// - `id`: The identifier of the asset to update.
+		/// - `name`: The user friendly name of this asset. Limited in length by `StringLimit`.
+		/// - `symbol`: The exchange symbol for this asset. Limited in length by `StringLimit`.
+		/// - `decimals`: The number of decimals this asset uses to represent one unit.
+		///
+		/// Emits `MetadataSet`.
+		///
+		/// # <weight>
+		/// - `O(N + S)` where N and S are the length of the name and symbol respectively.
+		/// - 1 storage read of the asset, 1 storage mutation of the metadata.
+		/// - 1 reserve or unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::set_metadata(name.len() as u32, symbol.len() as u32)]
+		fn set_metadata(origin,
+			#[compact] id: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			ensure!(name.len() <= T::StringLimit::get() as usize, Error::<T>::BadMetadata);
+			ensure!(symbol.len() <= T::StringLimit::get() as usize, Error::<T>::BadMetadata);
+
+			let d = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(&origin == &d.owner, Error::<T>::NoPermission);
+
+			Metadata::<T>::try_mutate_exists(id, |metadata| {
+				let bytes_used = name.len() + symbol.len();
+				let old_deposit = metadata.take().map_or(Zero::zero(), |m| m.deposit);
+				let new_deposit = T::MetadataDepositPerByte::get()
+					.saturating_mul((bytes_used as u32).into())
+					.saturating_add(T::MetadataDepositBase::get());
+
+				if new_deposit > old_deposit {
+					T::Currency::reserve(&origin, new_deposit - old_deposit)?;
+				} else {
+					T::Currency::unreserve(&origin, old_deposit - new_deposit);
+				}
+
+				*metadata = Some(AssetMetadata {
+					deposit: new_deposit,
+					name: name.clone(),
+					symbol: symbol.clone(),
+					decimals,
+				});
+
+				Self::deposit_event(RawEvent::MetadataSet(id, name, symbol, decimals));
+				Ok(())
+			})
+		}
+
+		/// Clear the metadata for an asset.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the asset `id`.
+		///
+		/// Any deposit is freed for the asset owner.
+		///
+		/// - `id`: The identifier of the asset to clear.
+		///
+		/// Emits `MetadataCleared`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read of the asset, 1 storage deletion of the metadata.
+		/// - 1 unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::clear_metadata()]
+		fn clear_metadata(origin, #[compact] id: T::AssetId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			let d = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(&origin == &d.owner, Error::<T>::NoPermission);
+
+			Metadata::<T>::try_mutate_exists(id, |metadata| {
+				let deposit = metadata.take().ok_or(Error::<T>::Unknown)?.deposit;
+				T::Currency::unreserve(&d.owner, deposit);
+				Self::deposit_event(RawEvent::MetadataCleared(id));
+				Ok(())
+			})
+		}
+
+		/// Approve an amount of asset for transfer by a delegated third-party account.
+		///
+		/// Origin must be Signed.
+		///
+		/// Ensures that `ApprovalDeposit` worth of `Currency` is reserved from signing account
+		/// for the purpose of holding the approval. If some non-zero amount of assets is already
+		/// approved from signing account to `delegate`, then it is topped up or unreserved to
+		/// meet the right value.
+		///
+		/// NOTE: The signing account does not need to own `amount` of assets at the point of
+		/// making this call.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `delegate`: The account to delegate permission to transfer asset.
+		/// - `amount`: The amount of asset that may be transferred by `delegate`. If there is
+		/// already an approval in place, then this acts additively.
+		///
+		/// Emits `ApprovedTransfer` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the approval, up to 1 storage mutation of the asset.
+		/// - Up to 1 reserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::approve_transfer()]
+		fn approve_transfer(origin,
+			#[compact] id: T::AssetId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance,
+		) {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+
+			Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let d = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				Approvals::<T>::try_mutate(id, (&owner, &delegate), |maybe_approved| -> DispatchResult {
+					let mut approved = match maybe_approved.take() {
+						Some(approved) => approved,
+						None => {
+							d.approvals = d.approvals.saturating_add(1);
+							Default::default()
+						}
+					};
+					let deposit_required = T::ApprovalDeposit::get();
+					if approved.deposit < deposit_required {
+						T::Currency::reserve(&owner, deposit_required - approved.deposit)?;
+						approved.deposit = deposit_required;
+					}
+					approved.amount = approved.amount.saturating_add(amount);
+					*maybe_approved = Some(approved);
+					Ok(())
+				})
+			})?;
+
+			Self::deposit_event(RawEvent::ApprovedTransfer(id, owner, delegate, amount));
+		}
+
+		/// Cancel all of some asset approved for delegated transfer by a third-party account.
+		///
+		/// Origin must be Signed and there must be an approval in place between signer and
+		/// `delegate`.
+		///
+		/// Unreserves any deposit previously reserved by `approve_transfer` for the approval.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `delegate`: The account delegated permission to transfer asset.
+		///
+		/// Emits `ApprovalCancelled` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage deletion of the approval, 1 storage mutation of the asset.
+		/// - 1 unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::cancel_approval()]
+		fn cancel_approval(origin,
+			#[compact] id: T::AssetId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+
+			let approval = Approvals::<T>::take(id, (&owner, &delegate)).ok_or(Error::<T>::Unknown)?;
+			T::Currency::unreserve(&owner, approval.deposit);
+			Asset::<T>::mutate(id, |maybe_details| if let Some(d) = maybe_details {
+				d.approvals = d.approvals.saturating_sub(1);
+			});
+
+			Self::deposit_event(RawEvent::ApprovalCancelled(id, owner, delegate));
+		}
+
+		/// Transfer some asset balance from a previously delegated account to some third-party
+		/// account.
+		///
+		/// Origin must be Signed and there must be an approval in place by the `owner` to the
+		/// signer.
+		///
+		/// If the entire amount approved for transfer is transferred, then any deposit previously
+		/// reserved by `approve_transfer` is unreserved.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `owner`: The account which previously approved for a transfer of at least `amount` and
+		/// from which the asset balance will be withdrawn.
+		/// - `destination`: The account to which the asset balance of `amount` will be transferred.
+		/// - `amount`: The amount of assets to transfer. The `owner` must be left with either no
+		/// balance or at least the minimum balance of the asset.
+		///
+		/// Emits `TransferredApproved` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the approval.
+		/// - 2 storage mutations of balances, 1 storage mutation of the asset.
+		/// - Up to 1 unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::transfer_approved()]
+		fn transfer_approved(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			destination: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance,
+		) {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let destination = T::Lookup::lookup(destination)?;
+
+			Approvals::<T>::try_mutate_exists(id, (&owner, &delegate), |maybe_approved| -> DispatchResult {
+				let mut approved = maybe_approved.take().ok_or(Error::<T>::Unapproved)?;
+				let remaining = approved.amount.checked_sub(&amount).ok_or(Error::<T>::Unapproved)?;
+
+				// An approved transfer must never move more than was approved, so it may not
+				// sweep the dust of the owner's account along with it.
+				let min_balance = Asset::<T>::get(id).ok_or(Error::<T>::Unknown)?.min_balance;
+				let left = Account::<T>::get(id, &owner).balance.saturating_sub(amount);
+				ensure!(left.is_zero() || left >= min_balance, Error::<T>::WouldDie);
+
+				Self::do_transfer(id, &owner, &destination, amount, None)?;
+
+				if remaining.is_zero() {
+					T::Currency::unreserve(&owner, approved.deposit);
+					Asset::<T>::mutate(id, |maybe_details| if let Some(d) = maybe_details {
+						d.approvals = d.approvals.saturating_sub(1);
+					});
+				} else {
+					approved.amount = remaining;
+					*maybe_approved = Some(approved);
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::TransferredApproved(id, owner, delegate, destination, amount));
+		}
 	}
 }
 
@@ -269,156 +1147,161 @@ impl<T: Trait> Module<T> {
 
 	/// Get the asset `id` balance of `who`.
 	pub fn balance(id: T::AssetId, who: T::AccountId) -> T::Balance {
-		<Balances<T>>::get((id, who))
+		Account::<T>::get(id, who).balance
 	}
 
 	/// Get the total supply of an asset `id`.
 	pub fn total_supply(id: T::AssetId) -> T::Balance {
-		<TotalSupply<T>>::get(id)
+		Asset::<T>::get(id).map(|x| x.supply).unwrap_or_else(Zero::zero)
 	}
-}
 
-#[cfg(test)]
-mod tests {
-	use super::*;
+	/// Set the amount of asset `id` on hold for `who`, removing the entry if it is zero.
+	fn set_held(id: T::AssetId, who: &T::AccountId, amount: T::Balance) {
+		if amount.is_zero() {
+			Held::<T>::remove(id, who);
+		} else {
+			Held::<T>::insert(id, who, amount);
+		}
+	}
 
-	use frame_support::{impl_outer_origin, assert_ok, assert_noop, parameter_types, weights::Weight};
-	use sp_core::H256;
-	use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
+	/// Increase the balance of `beneficiary` by `amount` of asset `id`, increasing the supply.
+	///
+	/// If `maybe_check_issuer` is `Some`, the account given must be the Issuer of the asset.
+	fn do_mint(
+		id: T::AssetId,
+		beneficiary: &T::AccountId,
+		amount: T::Balance,
+		maybe_check_issuer: Option<T::AccountId>,
+	) -> DispatchResult {
+		Asset::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
 
-	impl_outer_origin! {
-		pub enum Origin for Test where system = frame_system {}
-	}
+			if let Some(check_issuer) = maybe_check_issuer {
+				ensure!(&check_issuer == &details.issuer, Error::<T>::NoPermission);
+			}
+			details.supply = details.supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
 
-	#[derive(Clone, Eq, PartialEq)]
-	pub struct Test;
-	parameter_types! {
-		pub const BlockHashCount: u64 = 250;
-		pub const MaximumBlockWeight: Weight = 1024;
-		pub const MaximumBlockLength: u32 = 2 * 1024;
-		pub const AvailableBlockRatio: Perbill = Perbill::one();
-	}
-	impl frame_system::Trait for Test {
-		type BaseCallFilter = ();
-		type Origin = Origin;
-		type Index = u64;
-		type Call = ();
-		type BlockNumber = u64;
-		type Hash = H256;
-		type Hashing = BlakeTwo256;
-		type AccountId = u64;
-		type Lookup = IdentityLookup<Self::AccountId>;
-		type Header = Header;
-		type Event = ();
-		type BlockHashCount = BlockHashCount;
-		type MaximumBlockWeight = MaximumBlockWeight;
-		type DbWeight = ();
-		type BlockExecutionWeight = ();
-		type ExtrinsicBaseWeight = ();
-		type MaximumExtrinsicWeight = MaximumBlockWeight;
-		type AvailableBlockRatio = AvailableBlockRatio;
-		type MaximumBlockLength = MaximumBlockLength;
-		type Version = ();
-		type ModuleToIndex = ();
-		type AccountData = ();
-		type OnNewAccount = ();
-		type OnKilledAccount = ();
-		type SystemWeightInfo = ();
-	}
-	impl Trait for Test {
-		type Event = ();
-		type Balance = u64;
-		type AssetId = u32;
+			Account::<T>::try_mutate(id, beneficiary, |t| -> DispatchResult {
+				let new_balance = t.balance.saturating_add(amount);
+				ensure!(new_balance >= details.min_balance, Error::<T>::BalanceLow);
+				if t.balance.is_zero() {
+					details.accounts = details.accounts.saturating_add(1);
+				}
+				t.balance = new_balance;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::Issued(id, beneficiary.clone(), amount));
+			Ok(())
+		})
 	}
-	type Assets = Module<Test>;
 
-	fn new_test_ext() -> sp_io::TestExternalities {
-		frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
-	}
+	/// Reduce the balance of `target` by as much as possible up to `amount` of asset `id`,
+	/// reducing the supply. Returns the amount actually burned.
+	///
+	/// If this takes the balance below the minimum for the asset, then the amount burned is
+	/// increased to take it to zero. Funds on hold are never burned, and an account with funds on
+	/// hold is kept at the minimum balance. If `maybe_check_admin` is `Some`, the account given
+	/// must be the Admin of the asset.
+	fn do_burn(
+		id: T::AssetId,
+		target: &T::AccountId,
+		amount: T::Balance,
+		maybe_check_admin: Option<T::AccountId>,
+	) -> Result<T::Balance, DispatchError> {
+		Asset::<T>::try_mutate(id, |maybe_details| {
+			let d = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+			if let Some(check_admin) = maybe_check_admin {
+				ensure!(&check_admin == &d.admin, Error::<T>::NoPermission);
+			}
 
-	#[test]
-	fn issuing_asset_units_to_issuer_should_work() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 1), 100);
-		});
-	}
+			let burned = Account::<T>::try_mutate_exists(
+				id,
+				target,
+				|maybe_account| -> Result<T::Balance, DispatchError> {
+					let mut account = maybe_account.take().ok_or(Error::<T>::BalanceZero)?;
+					let held = Held::<T>::get(id, target);
+					let mut burned = amount.min(account.balance.saturating_sub(held));
+					if !held.is_zero() {
+						burned = burned.min(account.balance.saturating_sub(d.min_balance));
+					}
+					account.balance -= burned;
+					*maybe_account = if held.is_zero() && account.balance < d.min_balance {
+						burned += account.balance;
+						d.accounts = d.accounts.saturating_sub(1);
+						None
+					} else {
+						Some(account)
+					};
+					Ok(burned)
+				}
+			)?;
 
-	#[test]
-	fn querying_total_supply_should_work() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 1), 100);
-			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
-			assert_eq!(Assets::balance(0, 1), 50);
-			assert_eq!(Assets::balance(0, 2), 50);
-			assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 31));
-			assert_eq!(Assets::balance(0, 1), 50);
-			assert_eq!(Assets::balance(0, 2), 19);
-			assert_eq!(Assets::balance(0, 3), 31);
-			assert_ok!(Assets::destroy(Origin::signed(3), 0));
-			assert_eq!(Assets::total_supply(0), 69);
-		});
-	}
+			d.supply = d.supply.saturating_sub(burned);
 
-	#[test]
-	fn transferring_amount_above_available_balance_should_work() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 1), 100);
-			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
-			assert_eq!(Assets::balance(0, 1), 50);
-			assert_eq!(Assets::balance(0, 2), 50);
-		});
+			Self::deposit_event(RawEvent::Burned(id, target.clone(), burned));
+			Ok(burned)
+		})
 	}
 
-	#[test]
-	fn transferring_amount_more_than_available_balance_should_not_work() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 1), 100);
-			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
-			assert_eq!(Assets::balance(0, 1), 50);
-			assert_eq!(Assets::balance(0, 2), 50);
-			assert_ok!(Assets::destroy(Origin::signed(1), 0));
-			assert_eq!(Assets::balance(0, 1), 0);
-			assert_noop!(Assets::transfer(Origin::signed(1), 0, 1, 50), Error::<Test>::BalanceLow);
-		});
-	}
+	/// Move `amount` of asset `id` from `source` to `dest`, returning the amount actually moved.
+	///
+	/// If `source` would be left with a non-zero balance below the minimum balance of the asset,
+	/// the remainder is moved as well, unless some of it is on hold in which case the transfer
+	/// fails. Funds on hold are never moved. If `maybe_need_admin` is `Some`, the account given
+	/// must be the Admin of the asset and frozen accounts and assets are not respected.
+	fn do_transfer(
+		id: T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: T::Balance,
+		maybe_need_admin: Option<T::AccountId>,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(!amount.is_zero(), Error::<T>::AmountZero);
 
-	#[test]
-	fn transferring_less_than_one_unit_should_not_work() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 1), 100);
-			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 0), Error::<Test>::AmountZero);
-		});
-	}
+		let mut source_account = Account::<T>::get(id, source);
+		let held = Held::<T>::get(id, source);
+		ensure!(source_account.balance.saturating_sub(held) >= amount, Error::<T>::BalanceLow);
 
-	#[test]
-	fn transferring_more_units_than_total_supply_should_not_work() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 1), 100);
-			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 101), Error::<Test>::BalanceLow);
-		});
-	}
+		Asset::<T>::try_mutate(id, |maybe_details| {
+			let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
 
-	#[test]
-	fn destroying_asset_balance_with_positive_balance_should_work() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 1), 100);
-			assert_ok!(Assets::destroy(Origin::signed(1), 0));
-		});
-	}
+			if let Some(need_admin) = maybe_need_admin {
+				ensure!(&need_admin == &details.admin, Error::<T>::NoPermission);
+			} else {
+				ensure!(!details.is_frozen, Error::<T>::Frozen);
+				ensure!(!source_account.is_frozen, Error::<T>::Frozen);
+			}
+
+			// Skip if source == dest
+			if source == dest {
+				return Ok(amount)
+			}
+
+			let mut amount = amount;
+			if source_account.balance - amount < details.min_balance {
+				ensure!(held.is_zero(), Error::<T>::WouldDie);
+				amount = source_account.balance;
+			}
+
+			Account::<T>::try_mutate(id, dest, |a| -> DispatchResult {
+				let new_balance = a.balance.saturating_add(amount);
+				ensure!(new_balance >= details.min_balance, Error::<T>::BalanceLow);
+				if a.balance.is_zero() {
+					details.accounts = details.accounts.saturating_add(1);
+				}
+				a.balance = new_balance;
+				Ok(())
+			})?;
+
+			source_account.balance -= amount;
+			if source_account.balance.is_zero() {
+				details.accounts = details.accounts.saturating_sub(1);
+				Account::<T>::remove(id, source);
+			} else {
+				Account::<T>::insert(id, source, &source_account);
+			}
 
-	#[test]
-	fn destroying_asset_balance_with_zero_balance_should_not_work() {
-		new_test_ext().execute_with(|| {
-			assert_ok!(Assets::issue(Origin::signed(1), 100));
-			assert_eq!(Assets::balance(0, 2), 0);
-			assert_noop!(Assets::destroy(Origin::signed(2), 0), Error::<Test>::BalanceZero);
-		});
+			Ok(amount)
+		})
 	}
 }
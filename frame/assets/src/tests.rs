@@ -0,0 +1,592 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Assets pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{
+	assert_ok, assert_noop, parameter_types,
+	traits::{
+		Currency, ReservableCurrency, BalanceStatus, WithdrawReasons,
+		ExistenceRequirement::{KeepAlive, AllowDeath}, Imbalance, fungibles::{self, ItemOf},
+	},
+};
+use pallet_balances::Error as BalancesError;
+
+fn witness(accounts: u32, approvals: u32) -> DestroyWitness {
+	DestroyWitness { accounts, approvals }
+}
+
+#[test]
+fn basic_minting_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+		assert_eq!(Assets::balance(0, 2), 100);
+		assert_eq!(Assets::total_supply(0), 200);
+	});
+}
+
+#[test]
+fn minting_requires_issuer_and_min_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 10));
+		assert_noop!(Assets::mint(Origin::signed(2), 0, 1, 100), Error::<Test>::NoPermission);
+		assert_noop!(Assets::mint(Origin::signed(1), 0, 1, 9), Error::<Test>::BalanceLow);
+		assert_noop!(Assets::mint(Origin::signed(1), 1, 1, 100), Error::<Test>::Unknown);
+	});
+}
+
+#[test]
+fn lifecycle_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+		assert_eq!(Balances::reserved_balance(&1), 1);
+		assert!(Asset::<Test>::contains_key(0));
+
+		assert_ok!(Assets::set_metadata(Origin::signed(1), 0, vec![0], vec![0], 12));
+		assert_eq!(Balances::reserved_balance(&1), 4);
+		assert!(Metadata::<Test>::contains_key(0));
+
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 10, 100));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 20, 100));
+		assert_eq!(Account::<Test>::iter_prefix(0).count(), 2);
+
+		assert_noop!(Assets::destroy(Origin::signed(1), 0, witness(1, 0)), Error::<Test>::BadWitness);
+		assert_noop!(Assets::destroy(Origin::signed(2), 0, witness(2, 0)), Error::<Test>::NoPermission);
+		assert_ok!(Assets::destroy(Origin::signed(1), 0, witness(2, 0)));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+
+		assert!(!Asset::<Test>::contains_key(0));
+		assert!(!Metadata::<Test>::contains_key(0));
+		assert_eq!(Account::<Test>::iter_prefix(0).count(), 0);
+
+		assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 10, 100));
+		assert_ok!(Assets::destroy(Origin::root(), 0, witness(1, 0)));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert!(!Asset::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn create_should_fail_for_taken_id_or_zero_min_balance() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		assert_noop!(Assets::create(Origin::signed(1), 0, 1, 0), Error::<Test>::MinBalanceZero);
+		assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+		assert_noop!(Assets::create(Origin::signed(1), 0, 1, 1), Error::<Test>::InUse);
+		assert_noop!(Assets::force_create(Origin::root(), 0, 1, 1), Error::<Test>::InUse);
+	});
+}
+
+#[test]
+fn create_requires_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Assets::create(Origin::signed(1), 0, 1, 1),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn min_balance_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 10));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 1);
+
+		// Cannot create a new account with a balance that is below minimum...
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 9), Error::<Test>::BalanceLow);
+		assert_noop!(Assets::force_transfer(Origin::signed(1), 0, 1, 2, 9), Error::<Test>::BalanceLow);
+
+		// When deducting from an account to below minimum, it should be reaped.
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 91));
+		assert_eq!(Assets::balance(0, 1), 0);
+		assert_eq!(Assets::balance(0, 2), 100);
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 1);
+
+		assert_ok!(Assets::force_transfer(Origin::signed(1), 0, 2, 1, 91));
+		assert_eq!(Assets::balance(0, 2), 0);
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 1);
+
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 1, 91));
+		assert_eq!(Assets::balance(0, 1), 0);
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 0);
+		assert_eq!(Assets::total_supply(0), 0);
+	});
+}
+
+#[test]
+fn querying_total_supply_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+		assert_eq!(Assets::balance(0, 1), 50);
+		assert_eq!(Assets::balance(0, 2), 50);
+		assert_ok!(Assets::transfer(Origin::signed(2), 0, 3, 31));
+		assert_eq!(Assets::balance(0, 1), 50);
+		assert_eq!(Assets::balance(0, 2), 19);
+		assert_eq!(Assets::balance(0, 3), 31);
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 3, u64::max_value()));
+		assert_eq!(Assets::total_supply(0), 69);
+	});
+}
+
+#[test]
+fn transferring_amount_below_available_balance_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+		assert_eq!(Assets::balance(0, 1), 50);
+		assert_eq!(Assets::balance(0, 2), 50);
+	});
+}
+
+#[test]
+fn transferring_frozen_user_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_ok!(Assets::freeze(Origin::signed(1), 0, 1));
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 50), Error::<Test>::Frozen);
+		assert_ok!(Assets::thaw(Origin::signed(1), 0, 1));
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+	});
+}
+
+#[test]
+fn transferring_frozen_asset_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 50), Error::<Test>::Frozen);
+		assert_ok!(Assets::thaw_asset(Origin::signed(1), 0));
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+	});
+}
+
+#[test]
+fn force_transfer_ignores_freezing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+		assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+		assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+		assert_noop!(Assets::force_transfer(Origin::signed(2), 0, 2, 3, 50), Error::<Test>::NoPermission);
+		assert_ok!(Assets::force_transfer(Origin::signed(1), 0, 2, 3, 50));
+		assert_eq!(Assets::balance(0, 2), 50);
+		assert_eq!(Assets::balance(0, 3), 50);
+	});
+}
+
+#[test]
+fn origin_guards_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_noop!(Assets::transfer_ownership(Origin::signed(2), 0, 2), Error::<Test>::NoPermission);
+		assert_noop!(Assets::set_team(Origin::signed(2), 0, 2, 2, 2), Error::<Test>::NoPermission);
+		assert_noop!(Assets::freeze(Origin::signed(2), 0, 1), Error::<Test>::NoPermission);
+		assert_noop!(Assets::thaw(Origin::signed(2), 0, 2), Error::<Test>::NoPermission);
+		assert_noop!(Assets::freeze_asset(Origin::signed(2), 0), Error::<Test>::NoPermission);
+		assert_noop!(Assets::thaw_asset(Origin::signed(2), 0), Error::<Test>::NoPermission);
+		assert_noop!(Assets::mint(Origin::signed(2), 0, 2, 100), Error::<Test>::NoPermission);
+		assert_noop!(Assets::burn(Origin::signed(2), 0, 1, 100), Error::<Test>::NoPermission);
+		assert_noop!(Assets::force_transfer(Origin::signed(2), 0, 1, 2, 100), Error::<Test>::NoPermission);
+		assert_noop!(Assets::destroy(Origin::signed(2), 0, witness(100, 0)), Error::<Test>::NoPermission);
+	});
+}
+
+#[test]
+fn transfer_owner_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 30);
+		assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+
+		assert_eq!(Balances::reserved_balance(&1), 1);
+
+		assert_ok!(Assets::transfer_ownership(Origin::signed(1), 0, 2));
+		assert_eq!(Balances::reserved_balance(&2), 1);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+
+		assert_noop!(Assets::transfer_ownership(Origin::signed(1), 0, 1), Error::<Test>::NoPermission);
+
+		// Set metadata now and make sure that deposit gets transferred back.
+		assert_ok!(Assets::set_metadata(Origin::signed(2), 0, vec![0u8; 10], vec![0u8; 10], 12));
+		assert_ok!(Assets::transfer_ownership(Origin::signed(2), 0, 1));
+		assert_eq!(Balances::reserved_balance(&1), 22);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+	});
+}
+
+#[test]
+fn set_team_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::set_team(Origin::signed(1), 0, 2, 3, 4));
+
+		assert_ok!(Assets::mint(Origin::signed(2), 0, 2, 100));
+		assert_ok!(Assets::freeze(Origin::signed(4), 0, 2));
+		assert_ok!(Assets::thaw(Origin::signed(3), 0, 2));
+		assert_ok!(Assets::force_transfer(Origin::signed(3), 0, 2, 3, 100));
+		assert_ok!(Assets::burn(Origin::signed(3), 0, 3, 100));
+	});
+}
+
+#[test]
+fn transferring_to_frozen_account_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_eq!(Assets::balance(0, 2), 100);
+		assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+		assert_eq!(Assets::balance(0, 2), 150);
+	});
+}
+
+#[test]
+fn transferring_amount_more_than_available_balance_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+		assert_eq!(Assets::balance(0, 1), 50);
+		assert_eq!(Assets::balance(0, 2), 50);
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 1, u64::max_value()));
+		assert_eq!(Assets::balance(0, 1), 0);
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 1, 50), Error::<Test>::BalanceLow);
+	});
+}
+
+#[test]
+fn transferring_less_than_one_unit_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 0), Error::<Test>::AmountZero);
+	});
+}
+
+#[test]
+fn transferring_more_units_than_total_supply_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 101), Error::<Test>::BalanceLow);
+	});
+}
+
+#[test]
+fn burning_asset_balance_with_positive_balance_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 1, u64::max_value()));
+		assert_eq!(Assets::balance(0, 1), 0);
+	});
+}
+
+#[test]
+fn burning_asset_balance_with_zero_balance_should_not_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 2), 0);
+		assert_noop!(Assets::burn(Origin::signed(1), 0, 2, u64::max_value()), Error::<Test>::BalanceZero);
+	});
+}
+
+#[test]
+fn set_metadata_should_work() {
+	new_test_ext().execute_with(|| {
+		// Cannot add metadata to unknown asset
+		assert_noop!(
+			Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 10], 12),
+			Error::<Test>::Unknown,
+		);
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		// Cannot add metadata to unowned asset
+		assert_noop!(
+			Assets::set_metadata(Origin::signed(2), 0, vec![0u8; 10], vec![0u8; 10], 12),
+			Error::<Test>::NoPermission,
+		);
+
+		// Cannot add oversized metadata
+		assert_noop!(
+			Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 100], vec![0u8; 10], 12),
+			Error::<Test>::BadMetadata,
+		);
+		assert_noop!(
+			Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 100], 12),
+			Error::<Test>::BadMetadata,
+		);
+
+		// Successfully add metadata and take deposit
+		Balances::make_free_balance_be(&1, 30);
+		assert_ok!(Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 10], 12));
+		assert_eq!(Balances::free_balance(&1), 9);
+
+		// Update deposit
+		assert_ok!(Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 5], 12));
+		assert_eq!(Balances::free_balance(&1), 14);
+		assert_ok!(Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 10], vec![0u8; 15], 12));
+		assert_eq!(Balances::free_balance(&1), 4);
+
+		// Cannot over-reserve
+		assert_noop!(
+			Assets::set_metadata(Origin::signed(1), 0, vec![0u8; 20], vec![0u8; 20], 12),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+
+		// Clear Metadata
+		assert!(Metadata::<Test>::contains_key(0));
+		assert_noop!(Assets::clear_metadata(Origin::signed(2), 0), Error::<Test>::NoPermission);
+		assert_noop!(Assets::clear_metadata(Origin::signed(1), 1), Error::<Test>::Unknown);
+		assert_ok!(Assets::clear_metadata(Origin::signed(1), 0));
+		assert!(!Metadata::<Test>::contains_key(0));
+		assert_eq!(Balances::free_balance(&1), 30);
+	});
+}
+
+#[test]
+fn approval_lifecycle_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		Balances::make_free_balance_be(&1, 1);
+		assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 50));
+		assert_eq!(Balances::reserved_balance(&1), 1);
+		assert_eq!(Asset::<Test>::get(0).unwrap().approvals, 1);
+
+		assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 40));
+		assert_eq!(Assets::balance(0, 1), 60);
+		assert_eq!(Assets::balance(0, 3), 40);
+		assert_eq!(Balances::reserved_balance(&1), 1);
+
+		assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 10));
+		assert_eq!(Assets::balance(0, 1), 50);
+		assert_eq!(Assets::balance(0, 3), 50);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Asset::<Test>::get(0).unwrap().approvals, 0);
+		assert!(Approvals::<Test>::get(0, (1, 2)).is_none());
+	});
+}
+
+#[test]
+fn approvals_are_additive() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		Balances::make_free_balance_be(&1, 1);
+		assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 30));
+		assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 20));
+		assert_eq!(Balances::reserved_balance(&1), 1);
+		assert_eq!(Asset::<Test>::get(0).unwrap().approvals, 1);
+
+		assert_noop!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 51), Error::<Test>::Unapproved);
+		assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 50));
+		assert_eq!(Assets::balance(0, 3), 50);
+	});
+}
+
+#[test]
+fn transfer_approved_checks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 10));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		Balances::make_free_balance_be(&1, 1);
+		assert_noop!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 10), Error::<Test>::Unapproved);
+		assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 200));
+
+		// The delegate may not sweep the owner's dust along with the approved amount.
+		assert_noop!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 95), Error::<Test>::WouldDie);
+		assert_noop!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 101), Error::<Test>::BalanceLow);
+
+		assert_ok!(Assets::freeze(Origin::signed(1), 0, 1));
+		assert_noop!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 50), Error::<Test>::Frozen);
+		assert_ok!(Assets::thaw(Origin::signed(1), 0, 1));
+
+		assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 100));
+		assert_eq!(Assets::balance(0, 1), 0);
+		assert_eq!(Assets::balance(0, 3), 100);
+		assert_eq!(Approvals::<Test>::get(0, (1, 2)).unwrap().amount, 100);
+	});
+}
+
+#[test]
+fn cancel_approval_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		Balances::make_free_balance_be(&1, 1);
+		assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 50));
+		assert_noop!(Assets::cancel_approval(Origin::signed(1), 0, 3), Error::<Test>::Unknown);
+		assert_noop!(Assets::cancel_approval(Origin::signed(2), 0, 1), Error::<Test>::Unknown);
+		assert_ok!(Assets::cancel_approval(Origin::signed(1), 0, 2));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Asset::<Test>::get(0).unwrap().approvals, 0);
+		assert_noop!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 10), Error::<Test>::Unapproved);
+	});
+}
+
+#[test]
+fn destroy_returns_approval_deposits() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 10);
+		Balances::make_free_balance_be(&2, 10);
+		assert_ok!(Assets::create(Origin::signed(1), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+		assert_ok!(Assets::approve_transfer(Origin::signed(2), 0, 3, 50));
+		assert_eq!(Balances::reserved_balance(&2), 1);
+
+		assert_noop!(Assets::destroy(Origin::signed(1), 0, witness(1, 0)), Error::<Test>::BadWitness);
+		assert_ok!(Assets::destroy(Origin::signed(1), 0, witness(1, 1)));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert!(Approvals::<Test>::get(0, (2, 3)).is_none());
+	});
+}
+
+#[test]
+fn fungibles_traits_should_work() {
+	new_test_ext().execute_with(|| {
+		use fungibles::{Inspect, Mutate, Transfer, WithdrawConsequence};
+
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 10));
+		assert_ok!(<Assets as Mutate<u64>>::mint_into(0, &1, 100));
+		assert_eq!(<Assets as Inspect<u64>>::total_issuance(0), 100);
+		assert_eq!(<Assets as Inspect<u64>>::minimum_balance(0), 10);
+		assert_eq!(<Assets as Inspect<u64>>::reducible_balance(0, &1, true), 90);
+		assert_eq!(<Assets as Inspect<u64>>::can_withdraw(0, &1, 95), WithdrawConsequence::ReducedToZero(5));
+
+		assert_noop!(<Assets as Transfer<u64>>::transfer(0, &1, &2, 95, true), Error::<Test>::WouldDie);
+		assert_eq!(<Assets as Transfer<u64>>::transfer(0, &1, &2, 50, true), Ok(50));
+		assert_eq!(<Assets as Mutate<u64>>::burn_from(0, &2, 45), Ok(50));
+		assert_eq!(<Assets as Inspect<u64>>::balance(0, &2), 0);
+		assert_eq!(<Assets as Inspect<u64>>::total_issuance(0), 50);
+
+		assert_ok!(Assets::freeze(Origin::signed(1), 0, 1));
+		assert_eq!(<Assets as Inspect<u64>>::reducible_balance(0, &1, false), 0);
+		assert_eq!(<Assets as Inspect<u64>>::can_withdraw(0, &1, 10), WithdrawConsequence::Frozen);
+	});
+}
+
+parameter_types! {
+	pub const AssetZero: u32 = 0;
+}
+
+type AssetZeroCurrency = ItemOf<Assets, AssetZero, u64>;
+
+#[test]
+fn item_of_should_act_as_currency() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_eq!(AssetZeroCurrency::total_balance(&1), 100);
+		assert_eq!(AssetZeroCurrency::total_issuance(), 100);
+
+		assert_ok!(AssetZeroCurrency::transfer(&1, &2, 50, KeepAlive));
+		assert!(AssetZeroCurrency::transfer(&1, &2, 50, KeepAlive).is_err());
+		assert_eq!(Assets::balance(0, 1), 50);
+		assert_eq!(Assets::balance(0, 2), 50);
+
+		let imbalance = AssetZeroCurrency::deposit_creating(&3, 10);
+		assert_eq!(imbalance.peek(), 10);
+		assert_eq!(AssetZeroCurrency::total_issuance(), 100);
+		drop(imbalance);
+		assert_eq!(AssetZeroCurrency::total_issuance(), 110);
+
+		let (slashed, remaining) = AssetZeroCurrency::slash(&3, 20);
+		assert_eq!((slashed.peek(), remaining), (10, 10));
+		drop(slashed);
+		assert_eq!(AssetZeroCurrency::total_issuance(), 100);
+		assert_eq!(Assets::balance(0, 3), 0);
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 2);
+	});
+}
+
+#[test]
+fn item_of_should_act_as_reservable_currency() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 10));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+
+		assert_ok!(AssetZeroCurrency::reserve(&1, 60));
+		assert_eq!(AssetZeroCurrency::reserved_balance(&1), 60);
+		assert_eq!(AssetZeroCurrency::free_balance(&1), 40);
+		assert_eq!(AssetZeroCurrency::total_balance(&1), 100);
+		assert!(AssetZeroCurrency::reserve(&1, 50).is_err());
+
+		// Funds on hold can't be moved or burned, and keep the account alive.
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 50), Error::<Test>::BalanceLow);
+		assert_ok!(Assets::burn(Origin::signed(1), 0, 1, 100));
+		assert_eq!(Assets::balance(0, 1), 60);
+
+		assert_eq!(AssetZeroCurrency::repatriate_reserved(&1, &2, 20, BalanceStatus::Free), Ok(0));
+		assert_eq!(Assets::balance(0, 2), 20);
+		assert_eq!(AssetZeroCurrency::reserved_balance(&1), 40);
+
+		let (slashed, remaining) = AssetZeroCurrency::slash_reserved(&1, 30);
+		assert_eq!((slashed.peek(), remaining), (30, 0));
+		drop(slashed);
+		assert_eq!(AssetZeroCurrency::total_issuance(), 30);
+		assert_eq!(AssetZeroCurrency::reserved_balance(&1), 10);
+
+		assert_eq!(AssetZeroCurrency::unreserve(&1, 20), 10);
+		assert_eq!(AssetZeroCurrency::reserved_balance(&1), 0);
+		assert!(!Held::<Test>::contains_key(0, 1));
+	});
+}
+
+#[test]
+fn item_of_withdraw_should_reap_dust() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, 10));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+
+		assert!(AssetZeroCurrency::withdraw(&1, 95, WithdrawReasons::all(), KeepAlive).is_err());
+		let imbalance = AssetZeroCurrency::withdraw(&1, 95, WithdrawReasons::all(), AllowDeath).unwrap();
+		assert_eq!(imbalance.peek(), 95);
+		drop(imbalance);
+		assert!(!Account::<Test>::contains_key(0, 1));
+		assert_eq!(AssetZeroCurrency::total_issuance(), 100);
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 1);
+	});
+}
@@ -37,6 +37,7 @@ use sp_consensus_babe::{AuthorityId, AuthorityPair, SlotNumber};
 use sp_consensus_vrf::schnorrkel::{VRFOutput, VRFProof};
 use sp_staking::SessionIndex;
 use pallet_staking::EraIndex;
+use frame_election_provider_support::onchain;
 
 impl_outer_origin!{
 	pub enum Origin for Test where system = frame_system {}
@@ -178,8 +179,6 @@ parameter_types! {
 	pub const AttestationPeriod: u64 = 100;
 	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
 	pub const MaxNominatorRewardedPerValidator: u32 = 64;
-	pub const ElectionLookahead: u64 = 0;
-	pub const StakingUnsignedPriority: u64 = u64::max_value() / 2;
 }
 
 pub struct CurrencyToVoteHandler;
@@ -212,14 +211,18 @@ impl pallet_staking::Trait for Test {
 	type RewardCurve = RewardCurve;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type NextNewSession = Session;
-	type ElectionLookahead = ElectionLookahead;
-	type Call = Call;
-	type UnsignedPriority = StakingUnsignedPriority;
-	type MaxIterations = ();
-	type MinSolutionScoreBump = ();
+	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type OnStakerSlash = ();
 	type WeightInfo = ();
 }
 
+impl onchain::Config for Test {
+	type AccountId = DummyValidatorId;
+	type BlockNumber = u64;
+	type Accuracy = Perbill;
+	type DataProvider = Staking;
+}
+
 parameter_types! {
 	pub OffencesWeightSoftLimit: Weight = Perbill::from_percent(60) * MaximumBlockWeight::get();
 }
@@ -23,7 +23,6 @@ use frame_system::RawOrigin as SystemOrigin;
 use frame_system::EventRecord;
 use frame_benchmarking::{benchmarks_instance, account};
 use sp_runtime::traits::Bounded;
-use sp_std::mem::size_of;
 
 use frame_system::Call as SystemCall;
 use frame_system::Module as System;
@@ -31,7 +30,20 @@ use crate::Module as Collective;
 
 const SEED: u32 = 0;
 
-const MAX_BYTES: u32 = 1_024;
+/// The encoded length of a proposal to remark `b` bytes.
+fn proposal_len<T: Trait<I>, I: Instance>(b: u32) -> u32 {
+	let proposal: T::Proposal = SystemCall::<T>::remark(vec![0; b as usize]).into();
+	proposal.encoded_size() as u32
+}
+
+/// The length of the largest remark which, encoded as a call, fits into `MaxProposalLength`. The
+/// encoding overhead only grows with the length of the remark, so the one of a remark of
+/// `MaxProposalLength` bytes bounds it for any shorter remark.
+fn max_bytes<T: Trait<I>, I: Instance>() -> u32 {
+	let max = T::MaxProposalLength::get();
+	let overhead = proposal_len::<T, I>(max).saturating_sub(max);
+	max.saturating_sub(overhead).max(2)
+}
 
 fn assert_last_event<T: Trait<I>, I: Instance>(generic_event: <T as Trait<I>>::Event) {
 	let events = System::<T>::events();
@@ -77,7 +89,7 @@ benchmarks_instance! {
 				SystemOrigin::Signed(last_old_member.clone()).into(),
 				threshold,
 				Box::new(proposal.clone()),
-				MAX_BYTES,
+				T::MaxProposalLength::get(),
 			)?;
 			let hash = T::Hashing::hash_of(&proposal);
 			// Vote on the proposal to increase state relevant for `set_members`.
@@ -112,9 +124,9 @@ benchmarks_instance! {
 
 	execute {
 		let m in 1 .. MAX_MEMBERS;
-		let b in 1 .. MAX_BYTES;
+		let b in 1 .. max_bytes::<T, I>();
 
-		let bytes_in_storage = b + size_of::<u32>() as u32;
+		let bytes_in_storage = proposal_len::<T, I>(b);
 
 		// Construct `members`.
 		let mut members = vec![];
@@ -142,9 +154,9 @@ benchmarks_instance! {
 	// This tests when execution would happen immediately after proposal
 	propose_execute {
 		let m in 1 .. MAX_MEMBERS;
-		let b in 1 .. MAX_BYTES;
+		let b in 1 .. max_bytes::<T, I>();
 
-		let bytes_in_storage = b + size_of::<u32>() as u32;
+		let bytes_in_storage = proposal_len::<T, I>(b);
 
 		// Construct `members`.
 		let mut members = vec![];
@@ -174,9 +186,9 @@ benchmarks_instance! {
 	propose_proposed {
 		let m in 2 .. MAX_MEMBERS;
 		let p in 1 .. T::MaxProposals::get();
-		let b in 1 .. MAX_BYTES;
+		let b in 1 .. max_bytes::<T, I>();
 
-		let bytes_in_storage = b + size_of::<u32>() as u32;
+		let bytes_in_storage = proposal_len::<T, I>(b);
 
 		// Construct `members`.
 		let mut members = vec![];
@@ -218,8 +230,8 @@ benchmarks_instance! {
 		let m in 5 .. MAX_MEMBERS;
 
 		let p = T::MaxProposals::get();
-		let b = MAX_BYTES;
-		let bytes_in_storage = b + size_of::<u32>() as u32;
+		let b = max_bytes::<T, I>();
+		let bytes_in_storage = proposal_len::<T, I>(b);
 
 		// Construct `members`.
 		let mut members = vec![];
@@ -290,9 +302,9 @@ benchmarks_instance! {
 		// We choose 4 as a minimum so we always trigger a vote in the voting loop (`for j in ...`)
 		let m in 4 .. MAX_MEMBERS;
 		let p in 1 .. T::MaxProposals::get();
-		let b in 1 .. MAX_BYTES;
+		let b in 1 .. max_bytes::<T, I>();
 
-		let bytes_in_storage = b + size_of::<u32>() as u32;
+		let bytes_in_storage = proposal_len::<T, I>(b);
 
 		// Construct `members`.
 		let mut members = vec![];
@@ -367,9 +379,9 @@ benchmarks_instance! {
 		// We choose 4 as a minimum so we always trigger a vote in the voting loop (`for j in ...`)
 		let m in 4 .. MAX_MEMBERS;
 		let p in 1 .. T::MaxProposals::get();
-		let b in 1 .. MAX_BYTES;
+		let b in 1 .. max_bytes::<T, I>();
 
-		let bytes_in_storage = b + size_of::<u32>() as u32;
+		let bytes_in_storage = proposal_len::<T, I>(b);
 
 		// Construct `members`.
 		let mut members = vec![];
@@ -448,9 +460,9 @@ benchmarks_instance! {
 		// We choose 4 as a minimum so we always trigger a vote in the voting loop (`for j in ...`)
 		let m in 4 .. MAX_MEMBERS;
 		let p in 1 .. T::MaxProposals::get();
-		let b in 1 .. MAX_BYTES;
+		let b in 1 .. max_bytes::<T, I>();
 
-		let bytes_in_storage = b + size_of::<u32>() as u32;
+		let bytes_in_storage = proposal_len::<T, I>(b);
 
 		// Construct `members`.
 		let mut members = vec![];
@@ -520,9 +532,9 @@ benchmarks_instance! {
 		// We choose 4 as a minimum so we always trigger a vote in the voting loop (`for j in ...`)
 		let m in 4 .. MAX_MEMBERS;
 		let p in 1 .. T::MaxProposals::get();
-		let b in 1 .. MAX_BYTES;
+		let b in 1 .. max_bytes::<T, I>();
 
-		let bytes_in_storage = b + size_of::<u32>() as u32;
+		let bytes_in_storage = proposal_len::<T, I>(b);
 
 		// Construct `members`.
 		let mut members = vec![];
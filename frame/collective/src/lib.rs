@@ -24,17 +24,25 @@
 //! calculations, but enforces this neither in `set_members` nor in `change_members_sorted`.
 //!
 //! A "prime" member may be set allowing their vote to act as the default vote in case of any
-//! abstentions after the voting period.
+//! abstentions after the voting period. How abstentions are counted is given by `DefaultVote`.
+//!
+//! Each member has a vote weight of one, unless given another weight through
+//! `ChangeMembers::set_vote_weights`. Thresholds and tallies of motions are counted in vote
+//! weight rather than in members.
 //!
 //! Voting happens through motions comprising a proposal (i.e. a curried dispatchable) plus a
 //! number of approvals required for it to pass and be called. Motions are open for members to
 //! vote on for a minimum period given by `MotionDuration`. As soon as the needed number of
 //! approvals is given, the motion is closed and executed. If the number of approvals is not reached
 //! during the voting period, then `close` may be called by any account in order to force the end
-//! the motion explicitly. If a prime member is defined then their vote is used in place of any
-//! abstentions and the proposal is executed if there are enough approvals counting the new votes.
+//! the motion explicitly. Abstentions are then counted as the default vote, by default the vote of
+//! the prime member if one is defined, and the proposal is executed if there are enough approvals
+//! counting the new votes.
+//!
+//! If there are not, then the motion is dropped without being executed.
 //!
-//! If there are not, or if no prime is set, then the motion is dropped without being executed.
+//! Proposals are stored in full until their motion is closed, so their encoded length is bounded
+//! by `MaxProposalLength`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![recursion_limit="128"]
@@ -52,6 +60,7 @@ use frame_support::{
 		PostDispatchInfo,
 	},
 	ensure,
+	storage::StorageValue,
 	traits::{ChangeMembers, EnsureOrigin, Get, InitializeMembers},
 	weights::{DispatchClass, GetDispatchInfo, Weight},
 };
@@ -66,7 +75,8 @@ pub type ProposalIndex = u32;
 /// A number of members.
 ///
 /// This also serves as a number of voting members, and since for motions, each member may
-/// vote exactly once, therefore also the number of votes for any given motion.
+/// vote exactly once, therefore also the number of votes for any given motion. When members have
+/// vote weights, it is the sum of their weights.
 pub type MemberCount = u32;
 
 /// The maximum number of members supported by the pallet. Used for weight estimation.
@@ -100,6 +110,52 @@ impl WeightInfo for () {
 	fn close_approved(_m: u32, _p: u32, _b: u32, ) -> Weight { 1_000_000_000 }
 }
 
+/// Default voting strategy when a member is inactive.
+pub trait DefaultVote {
+	/// Get the default vote for the abstentions of a motion, given:
+	///
+	/// - The vote of the prime member, if there is one and they voted.
+	/// - The vote weight of the yes votes.
+	/// - The vote weight of the no votes.
+	/// - The total vote weight of the members.
+	fn default_vote(
+		prime_vote: Option<bool>,
+		yes_votes: MemberCount,
+		no_votes: MemberCount,
+		len: MemberCount,
+	) -> bool;
+}
+
+/// Set the prime member's vote as the default vote, or no if there is no prime vote.
+pub struct PrimeDefaultVote;
+
+impl DefaultVote for PrimeDefaultVote {
+	fn default_vote(
+		prime_vote: Option<bool>,
+		_yes_votes: MemberCount,
+		_no_votes: MemberCount,
+		_len: MemberCount,
+	) -> bool {
+		prime_vote.unwrap_or(false)
+	}
+}
+
+/// Set the default vote to yes if the yes votes are a majority of the whole collective, otherwise
+/// use the prime member's vote as the default vote.
+pub struct MoreThanMajorityThenPrimeDefaultVote;
+
+impl DefaultVote for MoreThanMajorityThenPrimeDefaultVote {
+	fn default_vote(
+		prime_vote: Option<bool>,
+		yes_votes: MemberCount,
+		_no_votes: MemberCount,
+		len: MemberCount,
+	) -> bool {
+		let more_than_majority = yes_votes.saturating_mul(2) > len;
+		more_than_majority || prime_vote.unwrap_or(false)
+	}
+}
+
 pub trait Trait<I: Instance=DefaultInstance>: frame_system::Trait {
 	/// The outer origin type.
 	type Origin: From<RawOrigin<Self::AccountId, I>>;
@@ -119,6 +175,12 @@ pub trait Trait<I: Instance=DefaultInstance>: frame_system::Trait {
 	/// Maximum number of proposals allowed to be active in parallel.
 	type MaxProposals: Get<u32>;
 
+	/// Maximum length in bytes of an encoded proposal.
+	type MaxProposalLength: Get<u32>;
+
+	/// Default vote strategy of this collective.
+	type DefaultVote: DefaultVote;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -169,6 +231,8 @@ decl_storage! {
 		/// The member who provides the default vote for any other members that do not vote before
 		/// the timeout. If None, then no member has that privilege.
 		pub Prime get(fn prime): Option<T::AccountId>;
+		/// The vote weights of the members whose weight is not one, sorted by account.
+		pub VoteWeights get(fn vote_weights): Vec<(T::AccountId, MemberCount)>;
 	}
 	add_extra_genesis {
 		config(phantom): sp_std::marker::PhantomData<I>;
@@ -230,6 +294,8 @@ decl_error! {
 		WrongProposalWeight,
 		/// The given length bound for the proposal was too low.
 		WrongProposalLength,
+		/// The proposal is longer than `MaxProposalLength`.
+		ProposalTooLong,
 	}
 }
 
@@ -255,6 +321,7 @@ mod weight_for {
 		let db = T::DbWeight::get();
 		db.reads_writes(1, 1) // mutate `Members`
 			.saturating_add(db.writes(1)) // set `Prime`
+			.saturating_add(db.reads_writes(1, 1)) // mutate `VoteWeights`
 			.saturating_add(db.reads(1)) // read `Proposals`
 			.saturating_add(db.reads_writes(proposals, proposals)) // update votes (`Voting`)
 			.saturating_add(old_count.saturating_mul(21_000_000)) // M
@@ -287,7 +354,7 @@ mod weight_for {
 		proposal: Weight,
 		length: Weight,
 	) -> Weight {
-		T::DbWeight::get().reads(2) // `is_member` + `contains_key`
+		T::DbWeight::get().reads(3) // `is_member` + `contains_key` + `VoteWeights`
 			.saturating_add(29_000_000) // constant
 			.saturating_add(length.saturating_mul(3_000)) // B
 			.saturating_add(members.saturating_mul(220_000)) // M
@@ -318,7 +385,7 @@ mod weight_for {
 	pub(crate) fn vote<T: Trait<I>, I: Instance>(
 		members: Weight,
 	) -> Weight {
-		T::DbWeight::get().reads(1) // read `Members`
+		T::DbWeight::get().reads(2) // read `Members`, `VoteWeights`
 			.saturating_add(T::DbWeight::get().reads_writes(1, 1)) // mutate `Voting`
 			.saturating_add(30_000_000) // constant
 			.saturating_add(members.saturating_mul(500_000)) // M
@@ -351,7 +418,7 @@ mod weight_for {
 		members: Weight,
 		length: Weight,
 	) -> Weight {
-		T::DbWeight::get().reads(3) // `Members`, `Voting`, `ProposalOf`
+		T::DbWeight::get().reads(4) // `Members`, `VoteWeights`, `Voting`, `ProposalOf`
 			.saturating_add(66_000_000) // constant
 			.saturating_add(length.saturating_mul(8_000)) // B
 			.saturating_add(members.saturating_mul(250_000)) // M
@@ -488,7 +555,10 @@ decl_module! {
 		/// Requires the sender to be member.
 		///
 		/// `threshold` determines whether `proposal` is executed directly (`threshold < 2`)
-		/// or put up for voting.
+		/// or put up for voting. It is a vote weight, which is the number of members unless some
+		/// have vote weights.
+		///
+		/// The encoded `proposal` must be no longer than `MaxProposalLength`.
 		///
 		/// # <weight>
 		/// ## Weight
@@ -515,13 +585,13 @@ decl_module! {
 				weight_for::propose_execute::<T, I>(
 					MAX_MEMBERS.into(), // M
 					proposal.get_dispatch_info().weight, // P1
-					*length_bound as Weight, // B
+					(*length_bound).min(T::MaxProposalLength::get()) as Weight, // B
 				)
 			} else {
 				weight_for::propose_proposed::<T, I>(
 					MAX_MEMBERS.into(), // M
 					T::MaxProposals::get().into(), // P2
-					*length_bound as Weight, // B
+					(*length_bound).min(T::MaxProposalLength::get()) as Weight, // B
 				)
 			},
 			DispatchClass::Operational
@@ -537,11 +607,15 @@ decl_module! {
 
 			let proposal_len = proposal.using_encoded(|x| x.len());
 			ensure!(proposal_len <= length_bound as usize, Error::<T, I>::WrongProposalLength);
+			ensure!(
+				proposal_len <= T::MaxProposalLength::get() as usize,
+				Error::<T, I>::ProposalTooLong
+			);
 			let proposal_hash = T::Hashing::hash_of(&proposal);
 			ensure!(!<ProposalOf<T, I>>::contains_key(proposal_hash), Error::<T, I>::DuplicateProposal);
 
 			if threshold < 2 {
-				let seats = Self::members().len() as MemberCount;
+				let seats = Self::weight_of_all(&Self::vote_weights(), &members);
 				let result = proposal.dispatch(RawOrigin::Members(1, seats).into());
 				Self::deposit_event(
 					RawEvent::Executed(proposal_hash, result.map(|_| ()).map_err(|e| e.error))
@@ -630,8 +704,9 @@ decl_module! {
 				}
 			}
 
-			let yes_votes = voting.ayes.len() as MemberCount;
-			let no_votes = voting.nays.len() as MemberCount;
+			let weights = Self::vote_weights();
+			let yes_votes = Self::weight_of_all(&weights, &voting.ayes);
+			let no_votes = Self::weight_of_all(&weights, &voting.nays);
 			Self::deposit_event(RawEvent::Voted(who, proposal, approve, yes_votes, no_votes));
 
 			Voting::<T, I>::insert(&proposal, voting);
@@ -646,8 +721,8 @@ decl_module! {
 		/// If called before the end of the voting period it will only close the vote if it is
 		/// has enough votes to be approved or disapproved.
 		///
-		/// If called after the end of the voting period abstentions are counted as rejections
-		/// unless there is a prime member set and the prime member cast an approval.
+		/// If called after the end of the voting period abstentions are counted as the default vote
+		/// given by `DefaultVote`, which may depend on the vote of the prime member.
 		///
 		/// + `proposal_weight_bound`: The maximum amount of weight consumed by executing the closed proposal.
 		/// + `length_bound`: The upper bound for the length of the proposal in storage. Checked via
@@ -668,10 +743,10 @@ decl_module! {
 		/// # </weight>
 		#[weight = (
 			weight_for::close::<T, I>(
-				MAX_MEMBERS.into(), // `M`
+				Module::<T, I>::member_count().into(), // `M`
 				*proposal_weight_bound, // `P1`
 				T::MaxProposals::get().into(), // `P2`
-				*length_bound as Weight, // B
+				(*length_bound).min(T::MaxProposalLength::get()) as Weight, // B
 			),
 			DispatchClass::Operational
 		)]
@@ -686,9 +761,12 @@ decl_module! {
 			let voting = Self::voting(&proposal_hash).ok_or(Error::<T, I>::ProposalMissing)?;
 			ensure!(voting.index == index, Error::<T, I>::WrongIndex);
 
-			let mut no_votes = voting.nays.len() as MemberCount;
-			let mut yes_votes = voting.ayes.len() as MemberCount;
-			let seats = Self::members().len() as MemberCount;
+			let members = Self::members();
+			let member_count = members.len() as MemberCount;
+			let weights = Self::vote_weights();
+			let mut no_votes = Self::weight_of_all(&weights, &voting.nays);
+			let mut yes_votes = Self::weight_of_all(&weights, &voting.ayes);
+			let seats = Self::weight_of_all(&weights, &members);
 			let approved = yes_votes >= voting.threshold;
 			let disapproved = seats.saturating_sub(no_votes) < voting.threshold;
 			// Allow (dis-)approving the proposal as soon as there are enough votes.
@@ -701,14 +779,14 @@ decl_module! {
 				Self::deposit_event(RawEvent::Closed(proposal_hash, yes_votes, no_votes));
 				let approve_weight = Self::do_approve_proposal(seats, voting, proposal_hash, proposal);
 				return Ok(Some(
-					weight_for::close_without_finalize::<T, I>(seats.into(), len as Weight)
+					weight_for::close_without_finalize::<T, I>(member_count.into(), len as Weight)
 						.saturating_add(approve_weight)
 				).into());
 			} else if disapproved {
 				Self::deposit_event(RawEvent::Closed(proposal_hash, yes_votes, no_votes));
 				let disapprove_weight = Self::do_disapprove_proposal(proposal_hash);
 				return Ok(Some(
-					weight_for::close_without_finalize::<T, I>(seats.into(), 0)
+					weight_for::close_without_finalize::<T, I>(member_count.into(), 0)
 						.saturating_add(disapprove_weight)
 				).into());
 			}
@@ -716,10 +794,18 @@ decl_module! {
 			// Only allow actual closing of the proposal after the voting period has ended.
 			ensure!(system::Module::<T>::block_number() >= voting.end, Error::<T, I>::TooEarly);
 
-			// default to true only if there's a prime and they voted in favour.
-			let default = Self::prime().map_or(false, |who| voting.ayes.iter().any(|a| a == &who));
+			let prime_vote = Self::prime().and_then(|who| {
+				if voting.ayes.contains(&who) {
+					Some(true)
+				} else if voting.nays.contains(&who) {
+					Some(false)
+				} else {
+					None
+				}
+			});
+			let default = T::DefaultVote::default_vote(prime_vote, yes_votes, no_votes, seats);
 
-			let abstentions = seats - (yes_votes + no_votes);
+			let abstentions = seats.saturating_sub(yes_votes.saturating_add(no_votes));
 			match default {
 				true => yes_votes += abstentions,
 				false => no_votes += abstentions,
@@ -735,7 +821,7 @@ decl_module! {
 				Self::deposit_event(RawEvent::Closed(proposal_hash, yes_votes, no_votes));
 				let approve_weight = Self::do_approve_proposal(seats, voting, proposal_hash, proposal);
 				return Ok(Some(
-					weight_for::close_without_finalize::<T, I>(seats.into(), len as Weight)
+					weight_for::close_without_finalize::<T, I>(member_count.into(), len as Weight)
 						.saturating_add(T::DbWeight::get().reads(1)) // read `Prime`
 						.saturating_add(approve_weight)
 				).into());
@@ -743,7 +829,7 @@ decl_module! {
 				Self::deposit_event(RawEvent::Closed(proposal_hash, yes_votes, no_votes));
 				let disapprove_weight = Self::do_disapprove_proposal(proposal_hash);
 				return Ok(Some(
-					weight_for::close_without_finalize::<T, I>(seats.into(), 0)
+					weight_for::close_without_finalize::<T, I>(member_count.into(), 0)
 						.saturating_add(T::DbWeight::get().reads(1)) // read `Prime`
 						.saturating_add(disapprove_weight)
 				).into());
@@ -776,6 +862,11 @@ decl_module! {
 }
 
 impl<T: Trait<I>, I: Instance> Module<T, I> {
+	/// The number of members of the collective, read without decoding them.
+	fn member_count() -> MemberCount {
+		Members::<T, I>::decode_len().unwrap_or_default() as MemberCount
+	}
+
 	/// Check whether `who` is a member of the collective.
 	pub fn is_member(who: &T::AccountId) -> bool {
 		// Note: The dispatchables *do not* use this to check membership so make sure
@@ -783,6 +874,21 @@ impl<T: Trait<I>, I: Instance> Module<T, I> {
 		Self::members().contains(who)
 	}
 
+	/// The vote weight of `who`, one unless set through `ChangeMembers::set_vote_weights`.
+	pub fn vote_weight(who: &T::AccountId) -> MemberCount {
+		Self::weight_of(&Self::vote_weights(), who)
+	}
+
+	/// The vote weight of `who` given the non-default `weights`.
+	fn weight_of(weights: &[(T::AccountId, MemberCount)], who: &T::AccountId) -> MemberCount {
+		weights.binary_search_by(|(a, _)| a.cmp(who)).map_or(1, |i| weights[i].1)
+	}
+
+	/// The total vote weight of `voters` given the non-default `weights`.
+	fn weight_of_all(weights: &[(T::AccountId, MemberCount)], voters: &[T::AccountId]) -> MemberCount {
+		voters.iter().fold(0, |total, who| total.saturating_add(Self::weight_of(weights, who)))
+	}
+
 	/// Ensure that the right proposal bounds were passed and get the proposal from storage.
 	///
 	/// Checks the length in storage via `storage::read` which adds an extra `size_of::<u32>() == 4`
@@ -878,6 +984,7 @@ impl<T: Trait<I>, I: Instance> ChangeMembers<T::AccountId> for Module<T, I> {
 	///   - `P` storage mutations for updating the votes (codec `O(M)`)
 	///   - 1 storage write (codec `O(N)`) for storing the new members
 	///   - 1 storage write (codec `O(1)`) for deleting the old prime
+	///   - 1 storage mutation (codec `O(M)`) for removing the vote weights of outgoing members
 	/// # </weight>
 	fn change_members_sorted(
 		_incoming: &[T::AccountId],
@@ -907,6 +1014,11 @@ impl<T: Trait<I>, I: Instance> ChangeMembers<T::AccountId> for Module<T, I> {
 				}
 			);
 		}
+		if !outgoing.is_empty() {
+			<VoteWeights<T, I>>::mutate(|weights|
+				weights.retain(|(who, _)| outgoing.binary_search(who).is_err())
+			);
+		}
 		Members::<T, I>::put(new);
 		Prime::<T, I>::kill();
 	}
@@ -914,6 +1026,19 @@ impl<T: Trait<I>, I: Instance> ChangeMembers<T::AccountId> for Module<T, I> {
 	fn set_prime(prime: Option<T::AccountId>) {
 		Prime::<T, I>::set(prime);
 	}
+
+	/// Set the vote weights of the members. Weights of accounts which are not members are
+	/// ignored, and weights of departing members are removed with them.
+	fn set_vote_weights(weights: &[(T::AccountId, u32)]) {
+		let members = Self::members();
+		let mut weights: Vec<_> = weights.iter()
+			.filter(|(who, weight)| *weight != 1 && members.binary_search(who).is_ok())
+			.cloned()
+			.collect();
+		weights.sort_by(|a, b| a.0.cmp(&b.0));
+		weights.dedup_by(|a, b| a.0 == b.0);
+		<VoteWeights<T, I>>::put(weights);
+	}
 }
 
 impl<T: Trait<I>, I: Instance> InitializeMembers<T::AccountId> for Module<T, I> {
@@ -1047,6 +1172,7 @@ mod tests {
 		pub const AvailableBlockRatio: Perbill = Perbill::one();
 		pub const MotionDuration: u64 = 3;
 		pub const MaxProposals: u32 = 100;
+		pub const MaxProposalLength: u32 = 1_024;
 	}
 	impl frame_system::Trait for Test {
 		type BaseCallFilter = ();
@@ -1081,6 +1207,8 @@ mod tests {
 		type Event = Event;
 		type MotionDuration = MotionDuration;
 		type MaxProposals = MaxProposals;
+		type MaxProposalLength = MaxProposalLength;
+		type DefaultVote = PrimeDefaultVote;
 		type WeightInfo = ();
 	}
 	impl Trait for Test {
@@ -1089,6 +1217,8 @@ mod tests {
 		type Event = Event;
 		type MotionDuration = MotionDuration;
 		type MaxProposals = MaxProposals;
+		type MaxProposalLength = MaxProposalLength;
+		type DefaultVote = MoreThanMajorityThenPrimeDefaultVote;
 		type WeightInfo = ();
 	}
 
@@ -1249,6 +1379,131 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn close_with_prime_voting_nay_works() {
+		new_test_ext().execute_with(|| {
+			let proposal = make_proposal(42);
+			let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
+			let proposal_weight = proposal.get_dispatch_info().weight;
+			let hash = BlakeTwo256::hash_of(&proposal);
+			assert_ok!(Collective::set_members(Origin::root(), vec![1, 2, 3, 4], Some(3), MAX_MEMBERS));
+
+			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone()), proposal_len));
+			assert_ok!(Collective::vote(Origin::signed(3), hash.clone(), 0, false));
+
+			System::set_block_number(4);
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len));
+			assert_eq!(
+				System::events().last().map(|r| r.event.clone()),
+				Some(Event::collective_Instance1(RawEvent::Disapproved(hash.clone()))),
+			);
+		});
+	}
+
+	#[test]
+	fn close_with_majority_default_vote_works() {
+		new_test_ext().execute_with(|| {
+			let proposal = make_proposal(42);
+			let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
+			let proposal_weight = proposal.get_dispatch_info().weight;
+			let hash = BlakeTwo256::hash_of(&proposal);
+			assert_ok!(DefaultCollective::set_members(Origin::root(), vec![1, 2, 3, 4, 5], None, MAX_MEMBERS));
+
+			assert_ok!(DefaultCollective::propose(Origin::signed(1), 5, Box::new(proposal.clone()), proposal_len));
+			assert_ok!(DefaultCollective::vote(Origin::signed(2), hash.clone(), 0, true));
+			assert_ok!(DefaultCollective::vote(Origin::signed(3), hash.clone(), 0, true));
+
+			System::set_block_number(4);
+			assert_ok!(DefaultCollective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len));
+			assert!(System::events().iter().any(|r|
+				r.event == Event::collective(RawEvent::Closed(hash.clone(), 5, 0))
+			));
+			assert!(System::events().iter().any(|r|
+				r.event == Event::collective(RawEvent::Approved(hash.clone()))
+			));
+		});
+	}
+
+	#[test]
+	fn default_votes_work() {
+		assert!(!PrimeDefaultVote::default_vote(None, 3, 0, 4));
+		assert!(PrimeDefaultVote::default_vote(Some(true), 0, 3, 4));
+		assert!(!PrimeDefaultVote::default_vote(Some(false), 3, 0, 4));
+		assert!(MoreThanMajorityThenPrimeDefaultVote::default_vote(None, 3, 0, 5));
+		assert!(!MoreThanMajorityThenPrimeDefaultVote::default_vote(None, 2, 0, 4));
+		assert!(MoreThanMajorityThenPrimeDefaultVote::default_vote(Some(true), 2, 0, 4));
+	}
+
+	#[test]
+	fn weighted_voting_works() {
+		new_test_ext().execute_with(|| {
+			let proposal = make_proposal(42);
+			let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
+			let proposal_weight = proposal.get_dispatch_info().weight;
+			let hash = BlakeTwo256::hash_of(&proposal);
+			<Collective as ChangeMembers<u64>>::set_vote_weights(&[(1, 3), (2, 1), (4, 2)]);
+			assert_eq!(Collective::vote_weights(), vec![(1, 3)]);
+			assert_eq!(Collective::vote_weight(&1), 3);
+			assert_eq!(Collective::vote_weight(&2), 1);
+
+			assert_ok!(Collective::propose(Origin::signed(1), 4, Box::new(proposal.clone()), proposal_len));
+			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len));
+
+			let record = |event| EventRecord { phase: Phase::Initialization, event, topics: vec![] };
+			assert_eq!(System::events(), vec![
+				record(Event::collective_Instance1(RawEvent::Proposed(1, 0, hash.clone(), 4))),
+				record(Event::collective_Instance1(RawEvent::Voted(2, hash.clone(), true, 4, 0))),
+				record(Event::collective_Instance1(RawEvent::Closed(hash.clone(), 4, 0))),
+				record(Event::collective_Instance1(RawEvent::Approved(hash.clone()))),
+				record(Event::collective_Instance1(RawEvent::Executed(hash.clone(), Err(DispatchError::BadOrigin))))
+			]);
+		});
+	}
+
+	#[test]
+	fn weighted_member_can_block_early() {
+		new_test_ext().execute_with(|| {
+			let proposal = make_proposal(42);
+			let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
+			let hash = BlakeTwo256::hash_of(&proposal);
+			<Collective as ChangeMembers<u64>>::set_vote_weights(&[(3, 3)]);
+
+			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone()), proposal_len));
+			assert_ok!(Collective::vote(Origin::signed(3), hash.clone(), 0, false));
+			// 5 seats, 3 of them against: the threshold of 3 can no longer be reached.
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, 0, 0));
+			assert_eq!(
+				System::events().last().map(|r| r.event.clone()),
+				Some(Event::collective_Instance1(RawEvent::Disapproved(hash.clone()))),
+			);
+		});
+	}
+
+	#[test]
+	fn vote_weights_of_outgoing_members_are_removed() {
+		new_test_ext().execute_with(|| {
+			<Collective as ChangeMembers<u64>>::set_vote_weights(&[(1, 3), (3, 2)]);
+			assert_eq!(Collective::vote_weights(), vec![(1, 3), (3, 2)]);
+			assert_ok!(Collective::set_members(Origin::root(), vec![2, 3, 4], None, MAX_MEMBERS));
+			assert_eq!(Collective::vote_weights(), vec![(3, 2)]);
+			assert_eq!(Collective::vote_weight(&1), 1);
+		});
+	}
+
+	#[test]
+	fn propose_checks_max_proposal_length() {
+		new_test_ext().execute_with(|| {
+			let proposal = Call::System(frame_system::Call::remark(vec![0; 1_024]));
+			let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
+			assert!(proposal_len > MaxProposalLength::get());
+			assert_noop!(
+				Collective::propose(Origin::signed(1), 3, Box::new(proposal), proposal_len),
+				Error::<Test, Instance1>::ProposalTooLong,
+			);
+		});
+	}
+
 	#[test]
 	fn removal_of_old_voters_votes_works() {
 		new_test_ext().execute_with(|| {
@@ -1646,6 +1901,19 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn close_weight_scales_with_members() {
+		new_test_ext().execute_with(|| {
+			let close_weight = || {
+				Call::Collective(crate::Call::close(H256::zero(), 0, 0, 0)).get_dispatch_info().weight
+			};
+			let three_members = close_weight();
+			assert_ok!(Collective::set_members(Origin::root(), vec![1, 2, 3, 4, 5], None, MAX_MEMBERS));
+			// `close` is charged 250_000 per member.
+			assert_eq!(close_weight(), three_members + 2 * 250_000);
+		})
+	}
+
 	#[test]
 	fn disapprove_proposal_works() {
 		new_test_ext().execute_with(|| {
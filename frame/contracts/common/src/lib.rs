@@ -18,8 +18,57 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
+use sp_runtime::{DispatchError, RuntimeDebug};
 use sp_std::prelude::*;
 
+/// Result of a dry-run of a contract call performed through the runtime API.
+///
+/// Everything besides `result` is diagnostic information which is never available
+/// when the same call is executed on-chain.
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct ContractExecResult<Balance> {
+	/// How much gas was consumed by the call.
+	pub gas_consumed: u64,
+	/// The balance the called contract needs to hold after the call in order to be
+	/// exempt from paying rent for its storage.
+	///
+	/// This is zero if the called contract does not exist after the call.
+	pub storage_deposit: Balance,
+	/// The SCALE encoded runtime events that were emitted during the call.
+	pub events: Vec<Vec<u8>>,
+	/// The UTF-8 encoded messages that the executed contracts passed to `ext_debug_message`.
+	pub debug_message: Vec<u8>,
+	/// The outcome of the execution.
+	pub result: Result<ExecReturnValue, ExecTrapReason>,
+}
+
+/// Output of a contract call that ran to completion.
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct ExecReturnValue {
+	/// Flags that the contract passed along on returning to alter its exit behaviour.
+	/// Described in `pallet_contracts::exec::ReturnFlags`.
+	pub flags: u32,
+	/// Output data returned by the contract.
+	///
+	/// Can be empty.
+	pub data: Vec<u8>,
+}
+
+/// The reason why a contract call did not run to completion.
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct ExecTrapReason {
+	/// The error which aborted the execution.
+	pub error: DispatchError,
+	/// The name of the error.
+	///
+	/// It is transported separately because the message of a `DispatchError` is not encoded.
+	pub message: Option<Vec<u8>>,
+	/// Whether the error happened inside the called contract rather than before
+	/// its code was executed.
+	pub in_callee: bool,
+}
+
 /// A result type of a get storage call.
 pub type GetStorageResult = Result<Option<Vec<u8>>, ContractAccessError>;
 
@@ -1,4 +0,0 @@
-(module
-	(func (export "call"))
-	(func (export "deploy"))
-)
@@ -0,0 +1,40 @@
+;; Call chain extension by passing through input and output of this contract
+(module
+	(import "env" "ext_call_chain_extension"
+		(func $ext_call_chain_extension (param i32 i32 i32 i32 i32) (result i32)))
+	(import "env" "ext_input" (func $ext_input (param i32 i32)))
+	(import "env" "ext_return" (func $ext_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 16 16))
+
+	;; [0, 4) len of input buffer
+	(data (i32.const 0) "\40")
+
+	;; [4, 68) buffer for input
+
+	;; [68, 72) len of output buffer
+	(data (i32.const 68) "\40")
+
+	;; [72, 136) buffer for output
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		;; Store input data at 4 and its length at 0
+		(call $ext_input (i32.const 4) (i32.const 0))
+
+		;; Call the chain extension which puts the output at 72 and its length at 68.
+		;; The first byte of the input is used as `func_id`.
+		(drop
+			(call $ext_call_chain_extension
+				(i32.load8_u (i32.const 4))	;; func_id
+				(i32.const 4)				;; input_ptr
+				(i32.load (i32.const 0))	;; input_len
+				(i32.const 72)				;; output_ptr
+				(i32.const 68)				;; output_len_ptr
+			)
+		)
+
+		;; Return the output of the chain extension to the caller.
+		(call $ext_return (i32.const 0) (i32.const 72) (i32.load (i32.const 68)))
+	)
+)
@@ -0,0 +1,46 @@
+;; Writes a debug message and deposits an event on every call.
+;; Traps when called with a non-empty input after writing the message.
+(module
+	(import "env" "ext_debug_message" (func $ext_debug_message (param i32 i32) (result i32)))
+	(import "env" "ext_deposit_event" (func $ext_deposit_event (param i32 i32 i32 i32)))
+	(import "env" "ext_input" (func $ext_input (param i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 12) "Hello World!"
+	(data (i32.const 0) "Hello World!")
+
+	;; [12, 16) length of the input buffer
+	(data (i32.const 12) "\04")
+
+	;; [16, 20) input buffer
+
+	(func $assert (param i32)
+		(block $ok
+			(br_if $ok
+				(get_local 0)
+			)
+			(unreachable)
+		)
+	)
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(drop
+			(call $ext_debug_message
+				(i32.const 0)	;; Pointer to the message
+				(i32.const 12)	;; Length of the message
+			)
+		)
+		(call $ext_deposit_event
+			(i32.const 0)	;; The topics buffer
+			(i32.const 0)	;; The topics buffer's length
+			(i32.const 0)	;; The data buffer
+			(i32.const 5)	;; The data buffer's length
+		)
+		(call $ext_input (i32.const 16) (i32.const 12))
+		(call $assert
+			(i32.eqz (i32.load (i32.const 12)))
+		)
+	)
+)
@@ -23,31 +23,11 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Codec, Decode, Encode};
+use codec::Codec;
 use pallet_contracts_primitives::{GetStorageResult, RentProjectionResult};
-use sp_runtime::RuntimeDebug;
 use sp_std::vec::Vec;
 
-/// A result of execution of a contract.
-#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
-pub enum ContractExecResult {
-	/// The contract returned successfully.
-	///
-	/// There is a status code and, optionally, some data returned by the contract.
-	Success {
-		/// Flags that the contract passed along on returning to alter its exit behaviour.
-		/// Described in `pallet_contracts::exec::ReturnFlags`.
-		flags: u32,
-		/// Output data returned by the contract.
-		///
-		/// Can be empty.
-		data: Vec<u8>,
-		/// How much gas was consumed by the call.
-		gas_consumed: u64,
-	},
-	/// The contract execution either trapped or returned an error.
-	Error,
-}
+pub use pallet_contracts_primitives::{ContractExecResult, ExecReturnValue, ExecTrapReason};
 
 sp_api::decl_runtime_apis! {
 	/// The API to interact with contracts without using executive.
@@ -58,14 +38,15 @@ sp_api::decl_runtime_apis! {
 	{
 		/// Perform a call from a specified account to a given contract.
 		///
-		/// See the contracts' `call` dispatchable function for more details.
+		/// See the contracts' `call` dispatchable function for more details. In contrast to the
+		/// dispatchable this also collects debug messages, emitted events and the trap reason.
 		fn call(
 			origin: AccountId,
 			dest: AccountId,
 			value: Balance,
 			gas_limit: u64,
 			input_data: Vec<u8>,
-		) -> ContractExecResult;
+		) -> ContractExecResult<Balance>;
 
 		/// Query a given storage key in a given contract.
 		///
@@ -36,7 +36,8 @@ use std::convert::TryInto;
 
 pub use self::gen_client::Client as ContractsClient;
 pub use pallet_contracts_rpc_runtime_api::{
-	self as runtime_api, ContractExecResult, ContractsApi as ContractsRuntimeApi,
+	self as runtime_api, ContractExecResult, ContractsApi as ContractsRuntimeApi, ExecReturnValue,
+	ExecTrapReason,
 };
 
 const RUNTIME_ERROR: i64 = 1;
@@ -85,37 +86,65 @@ pub struct CallRequest<AccountId, Balance> {
 	input_data: Bytes,
 }
 
-/// An RPC serializable result of contract execution
+/// An RPC serializable result of a dry-run of a contract call.
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "camelCase")]
-pub enum RpcContractExecResult {
+pub struct RpcContractExecResult<Balance> {
+	/// How much gas was consumed by the call.
+	gas_consumed: u64,
+	/// The balance the callee needs to hold after the call to be exempt from rent.
+	storage_deposit: Balance,
+	/// The SCALE encoded runtime events emitted during the call.
+	events: Vec<Bytes>,
+	/// The messages the executed contracts passed to `ext_debug_message`.
+	debug_message: String,
+	/// The outcome of the execution.
+	result: RpcExecOutcome,
+}
+
+/// The outcome of a contract execution.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcExecOutcome {
 	/// Successful execution
 	Success {
 		/// The return flags
 		flags: u32,
 		/// Output data
 		data: Bytes,
-		/// How much gas was consumed by the call.
-		gas_consumed: u64,
 	},
 	/// Error execution
-	Error(()),
+	Error {
+		/// The debug representation of the error that aborted the execution.
+		error: String,
+		/// The name of the error if it is known.
+		message: Option<String>,
+		/// Whether the error happened inside the called contract.
+		in_callee: bool,
+	},
 }
 
-impl From<ContractExecResult> for RpcContractExecResult {
-	fn from(r: ContractExecResult) -> Self {
-		match r {
-			ContractExecResult::Success {
-				flags,
-				data,
-				gas_consumed
-			} => RpcContractExecResult::Success {
+impl<Balance> From<ContractExecResult<Balance>> for RpcContractExecResult<Balance> {
+	fn from(r: ContractExecResult<Balance>) -> Self {
+		let result = match r.result {
+			Ok(ExecReturnValue { flags, data }) => RpcExecOutcome::Success {
 				flags,
 				data: data.into(),
-				gas_consumed,
 			},
-			ContractExecResult::Error => RpcContractExecResult::Error(()),
+			Err(ExecTrapReason { error, message, in_callee }) => RpcExecOutcome::Error {
+				error: format!("{:?}", error),
+				message: message.map(|m| String::from_utf8_lossy(&m).into_owned()),
+				in_callee,
+			},
+		};
+		RpcContractExecResult {
+			gas_consumed: r.gas_consumed,
+			storage_deposit: r.storage_deposit,
+			events: r.events.into_iter().map(Into::into).collect(),
+			debug_message: String::from_utf8_lossy(&r.debug_message).into_owned(),
+			result,
 		}
 	}
 }
@@ -128,13 +157,15 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance> {
 	/// This call is performed locally without submitting any transactions. Thus executing this
 	/// won't change any state. Nonetheless, the calling state-changing contracts is still possible.
 	///
-	/// This method is useful for calling getter-like methods on contracts.
+	/// This method is useful for calling getter-like methods on contracts. It also returns
+	/// the messages written by `ext_debug_message`, the emitted events and the reason of a
+	/// failure which is useful for debugging contracts.
 	#[rpc(name = "contracts_call")]
 	fn call(
 		&self,
 		call_request: CallRequest<AccountId, Balance>,
 		at: Option<BlockHash>,
-	) -> Result<RpcContractExecResult>;
+	) -> Result<RpcContractExecResult<Balance>>;
 
 	/// Returns the value under a specified storage `key` in a contract given by `address` param,
 	/// or `None` if it is not set.
@@ -198,7 +229,7 @@ where
 		&self,
 		call_request: CallRequest<AccountId, Balance>,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<RpcContractExecResult> {
+	) -> Result<RpcContractExecResult<Balance>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
@@ -312,11 +343,11 @@ mod tests {
 	#[test]
 	fn result_should_serialize_deserialize_properly() {
 		fn test(expected: &str) {
-			let res: RpcContractExecResult = serde_json::from_str(expected).unwrap();
+			let res: RpcContractExecResult<u32> = serde_json::from_str(expected).unwrap();
 			let actual = serde_json::to_string(&res).unwrap();
 			assert_eq!(actual, expected);
 		}
-		test(r#"{"success":{"flags":5,"data":"0x1234","gas_consumed":5000}}"#);
-		test(r#"{"error":null}"#);
+		test(r#"{"gasConsumed":5000,"storageDeposit":10,"events":["0x01"],"debugMessage":"hello","result":{"success":{"flags":5,"data":"0x1234"}}}"#);
+		test(r#"{"gasConsumed":5000,"storageDeposit":0,"events":[],"debugMessage":"","result":{"error":{"error":"BadOrigin","message":null,"in_callee":false}}}"#);
 	}
 }
@@ -0,0 +1,296 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Functions to procedurally construct contract code used for benchmarking.
+//!
+//! In order to be able to benchmark events that are triggered by contract execution
+//! (API calls into the runtime, individual instructions), we need to generate contracts
+//! that perform those events. Because those contracts can get very big we cannot simply
+//! define them as text (.wat) as this will be too slow and consume too much memory.
+//! Therefore we define this simple definition of a contract that can be converted into
+//! a `WasmModule` that can be used as a contract's code.
+
+use crate::Trait;
+use crate::Module as Contracts;
+
+use parity_wasm::elements::{Instruction, Instructions, FuncBody, ValueType, BlockType};
+use sp_runtime::traits::Hash;
+use sp_std::{prelude::*, convert::TryFrom};
+
+/// Pass to `WasmModule::from` in order to create a compiled `WasmModule`.
+///
+/// The `deploy` and `call` functions are always exported. Their bodies default to an
+/// empty function when `None` is supplied.
+#[derive(Default)]
+pub struct ModuleDefinition {
+	/// Imported memory of the module. `None` means no memory is imported.
+	pub memory: Option<ImportedMemory>,
+	/// Initializers for the imported memory.
+	pub data_segments: Vec<DataSegment>,
+	/// Functions imported from the `env` module. The functions are referenced by
+	/// their position in this list when called (`Call(index)`).
+	pub imported_functions: Vec<ImportedFunction>,
+	/// Body of the exported `deploy` function.
+	pub deploy_body: Option<FuncBody>,
+	/// Body of the exported `call` function.
+	pub call_body: Option<FuncBody>,
+}
+
+/// A data segment that is written to the imported memory at instantiation.
+pub struct DataSegment {
+	pub offset: u32,
+	pub value: Vec<u8>,
+}
+
+/// The memory import of a module.
+pub struct ImportedMemory {
+	pub min_pages: u32,
+	pub max_pages: u32,
+}
+
+impl ImportedMemory {
+	/// A memory that is fully allocated and as big as the current `Schedule` allows.
+	pub fn max<T: Trait>() -> Self {
+		let pages = max_pages::<T>();
+		Self { min_pages: pages, max_pages: pages }
+	}
+}
+
+/// A function that is imported from the `env` module.
+pub struct ImportedFunction {
+	pub name: &'static str,
+	pub params: Vec<ValueType>,
+	pub return_type: Option<ValueType>,
+}
+
+/// A wasm module ready to be put on chain with `put_code`.
+pub struct WasmModule<T: Trait> {
+	pub code: Vec<u8>,
+	pub hash: <T::Hashing as Hash>::Output,
+}
+
+impl<T: Trait> Clone for WasmModule<T> {
+	fn clone(&self) -> Self {
+		Self { code: self.code.clone(), hash: self.hash.clone() }
+	}
+}
+
+impl<T: Trait> From<ModuleDefinition> for WasmModule<T> {
+	fn from(def: ModuleDefinition) -> Self {
+		// Internal functions are indexed after all imported functions.
+		let func_offset = u32::try_from(def.imported_functions.len()).unwrap();
+
+		// Every contract must export a "deploy" and a "call" function.
+		let mut contract = parity_wasm::builder::module()
+			// deploy function (first internal function)
+			.function()
+				.signature().with_params(vec![]).with_return_type(None).build()
+				.with_body(def.deploy_body.unwrap_or_else(||
+					FuncBody::new(Vec::new(), Instructions::empty())
+				))
+				.build()
+			// call function (second internal function)
+			.function()
+				.signature().with_params(vec![]).with_return_type(None).build()
+				.with_body(def.call_body.unwrap_or_else(||
+					FuncBody::new(Vec::new(), Instructions::empty())
+				))
+				.build()
+			.export().field("deploy").internal().func(func_offset).build()
+			.export().field("call").internal().func(func_offset + 1).build();
+
+		if let Some(memory) = &def.memory {
+			contract = contract.import()
+				.module("env").field("memory")
+				.external().memory(memory.min_pages, Some(memory.max_pages))
+				.build();
+		}
+
+		for func in def.imported_functions {
+			let sig = parity_wasm::builder::signature()
+				.with_params(func.params)
+				.with_return_type(func.return_type)
+				.build_sig();
+			let sig = contract.push_signature(sig);
+			contract = contract.import()
+				.module("env")
+				.field(func.name)
+				.with_external(parity_wasm::elements::External::Function(sig))
+				.build();
+		}
+
+		for data in def.data_segments {
+			contract = contract.data()
+				.offset(Instruction::I32Const(data.offset as i32))
+				.value(data.value)
+				.build()
+		}
+
+		let code = contract.build().to_bytes().unwrap();
+		let hash = T::Hashing::hash(&code);
+		Self { code, hash }
+	}
+}
+
+impl<T: Trait> WasmModule<T> {
+	/// Creates a wasm module with an empty `call` and `deploy` function and nothing else.
+	pub fn dummy() -> Self {
+		ModuleDefinition::default().into()
+	}
+
+	/// Creates a wasm module of `target_bytes` size. Used to benchmark the performance of
+	/// `put_code` for different sizes of wasm modules. The generated module maximizes
+	/// instrumentation runtime by creating a maximum number of metering blocks per byte.
+	pub fn sized(target_bytes: u32) -> Self {
+		use parity_wasm::elements::Instruction::{If, I32Const, Return, End};
+		// Base size of a contract is 47 bytes and each expansion adds 6 bytes.
+		// We do one expansion less to account for the code section and function body
+		// size fields inside the binary wasm module representation which are leb128 encoded
+		// and therefore grow in size when the contract grows. We are not allowed to overshoot
+		// because of the maximum code size that is enforced by `put_code`.
+		let expansions = (target_bytes.saturating_sub(47) / 6).saturating_sub(1);
+		const EXPANSION: [Instruction; 4] = [
+			I32Const(0),
+			If(BlockType::NoResult),
+			Return,
+			End,
+		];
+		ModuleDefinition {
+			call_body: Some(body::repeated(expansions, &EXPANSION)),
+			.. Default::default()
+		}
+		.into()
+	}
+
+	/// Creates a wasm module that calls the imported function named `getter_name` `repeat`
+	/// times. The imported function is expected to have the "getter signature" of
+	/// (out_ptr: u32, len_ptr: u32) -> ().
+	pub fn getter(getter_name: &'static str, repeat: u32) -> Self {
+		let pages = max_pages::<T>();
+		ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: getter_name,
+				params: vec![ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			// Write the output buffer size. The output size will be overwritten by the
+			// supervisor with the real size when calling the getter. Since this size does not
+			// change between calls it suffices to start with an initial value and then just
+			// leave as whatever value was written there.
+			data_segments: vec![DataSegment {
+				offset: 0,
+				value: (pages * 64 * 1024 - 4).to_le_bytes().to_vec(),
+			}],
+			call_body: Some(body::repeated(repeat, &[
+				Instruction::I32Const(4), // ptr where to store output
+				Instruction::I32Const(0), // ptr to length
+				Instruction::Call(0), // call the imported function
+			])),
+			.. Default::default()
+		}
+		.into()
+	}
+
+	/// Creates a wasm module that calls the imported hash function named `name` `repeat` times
+	/// with an input of size `data_size`. Hash functions have the signature
+	/// (input_ptr: u32, input_len: u32, output_ptr: u32) -> ()
+	pub fn hasher(name: &'static str, repeat: u32, data_size: u32) -> Self {
+		ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name,
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			call_body: Some(body::repeated(repeat, &[
+				Instruction::I32Const(0), // input_ptr
+				Instruction::I32Const(data_size as i32), // input_len
+				Instruction::I32Const(0), // output_ptr
+				Instruction::Call(0),
+			])),
+			.. Default::default()
+		}
+		.into()
+	}
+}
+
+/// Mechanisms to create a function body that can be used inside a `ModuleDefinition`.
+pub mod body {
+	use super::*;
+
+	/// An instruction that is emitted as-is or a constant that changes with every repetition.
+	pub enum CountedInstruction {
+		/// An `I32Const` that starts at the first value and is incremented by the
+		/// second value on every repetition.
+		Counter(u32, u32),
+		/// An instruction that is emitted unchanged.
+		Regular(Instruction),
+	}
+
+	/// A function body consisting of exactly the supplied instructions.
+	pub fn plain(instructions: Vec<Instruction>) -> FuncBody {
+		FuncBody::new(Vec::new(), Instructions::new(instructions))
+	}
+
+	/// A function body that repeats `instructions` `repetitions` times.
+	pub fn repeated(repetitions: u32, instructions: &[Instruction]) -> FuncBody {
+		let instructions = Instructions::new(
+			instructions
+				.iter()
+				.cycle()
+				.take(instructions.len() * usize::try_from(repetitions).unwrap())
+				.cloned()
+				.chain(sp_std::iter::once(Instruction::End))
+				.collect()
+		);
+		FuncBody::new(Vec::new(), instructions)
+	}
+
+	/// Same as `repeated` but allows to use constants that change with every repetition.
+	pub fn counted(repetitions: u32, mut instructions: Vec<CountedInstruction>) -> FuncBody {
+		// We need to iterate over indices because we cannot cycle over mutable references.
+		let body = (0..instructions.len())
+			.cycle()
+			.take(instructions.len() * usize::try_from(repetitions).unwrap())
+			.map(|idx| {
+				match &mut instructions[idx] {
+					CountedInstruction::Counter(offset, increment_by) => {
+						let current = *offset;
+						*offset += *increment_by;
+						Instruction::I32Const(current as i32)
+					},
+					CountedInstruction::Regular(instruction) => instruction.clone(),
+				}
+			})
+			.chain(sp_std::iter::once(Instruction::End))
+			.collect();
+		FuncBody::new(Vec::new(), Instructions::new(body))
+	}
+
+	/// Adds `num` locals of type `ty` to the supplied function body.
+	pub fn inject_locals(body: &mut FuncBody, num: u32, ty: ValueType) {
+		use parity_wasm::elements::Local;
+		*body.locals_mut() = vec![Local::new(num, ty)];
+	}
+}
+
+/// The maximum amount of pages any contract is allowed to have according to the
+/// current `Schedule`.
+pub fn max_pages<T: Trait>() -> u32 {
+	Contracts::<T>::current_schedule().limits.memory_pages
+}
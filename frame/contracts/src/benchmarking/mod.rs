@@ -0,0 +1,2014 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Benchmarks for the contracts pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+mod code;
+
+use crate::*;
+use crate::Module as Contracts;
+use crate::exec::StorageKey;
+use crate::schedule::API_BENCHMARK_BATCH_SIZE;
+use self::code::{
+	body::{self, CountedInstruction::*}, max_pages, ModuleDefinition, DataSegment,
+	ImportedMemory, ImportedFunction, WasmModule,
+};
+
+use frame_benchmarking::{benchmarks, account};
+use frame_system::{Module as System, RawOrigin};
+use parity_wasm::elements::{Instruction, ValueType, BlockType};
+use sp_runtime::traits::{Hash, Bounded};
+use sp_std::default::Default;
+
+/// How many batches we do per API benchmark.
+const API_BENCHMARK_BATCHES: u32 = 20;
+
+/// How many batches we do per Instruction benchmark.
+const INSTR_BENCHMARK_BATCHES: u32 = 50;
+
+/// An instantiated and deployed contract.
+struct Contract<T: Trait> {
+	caller: T::AccountId,
+	account_id: T::AccountId,
+	addr: <T::Lookup as StaticLookup>::Source,
+	endowment: BalanceOf<T>,
+	code_hash: <T::Hashing as Hash>::Output,
+}
+
+/// Describes how much balance should be transferred on instantiate from the caller.
+enum Endow {
+	/// Endow the contract with half of the callers funding. The contract will never
+	/// be evicted and the caller keeps enough balance to transfer value on calls.
+	Large,
+	/// Endow the contract with the subsistence threshold so that it can be evicted after
+	/// a few blocks.
+	Minimum,
+}
+
+impl<T: Trait> Contract<T> {
+	/// Create new contract and use a default account id as instantiator.
+	fn new(
+		module: WasmModule<T>,
+		data: Vec<u8>,
+		endowment: Endow,
+	) -> Result<Contract<T>, &'static str> {
+		Self::with_index(0, module, data, endowment)
+	}
+
+	/// Create new contract and use an account id derived from the supplied index as instantiator.
+	fn with_index(
+		index: u32,
+		module: WasmModule<T>,
+		data: Vec<u8>,
+		endowment: Endow,
+	) -> Result<Contract<T>, &'static str> {
+		let caller = create_funded_user::<T>("instantiator", index);
+		Contracts::<T>::put_code(RawOrigin::Signed(caller.clone()).into(), module.code)?;
+		Self::instantiate(caller, module.hash, data, endowment)
+	}
+
+	/// Create a new contract whose code is stored without being checked or instrumented.
+	///
+	/// This is needed to benchmark host functions which are not available to contracts
+	/// (e.g. `gas`) and must only be used for this purpose.
+	fn with_raw_code(module: WasmModule<T>) -> Result<Contract<T>, &'static str> {
+		let caller = create_funded_user::<T>("instantiator", 0);
+		wasm::save_code_raw::<T>(module.code, &Contracts::<T>::current_schedule(), &caller)?;
+		Self::instantiate(caller, module.hash, vec![], Endow::Large)
+	}
+
+	fn instantiate(
+		caller: T::AccountId,
+		code_hash: <T::Hashing as Hash>::Output,
+		data: Vec<u8>,
+		endowment: Endow,
+	) -> Result<Contract<T>, &'static str> {
+		let endowment = match endowment {
+			Endow::Large => caller_funding::<T>() / 2u32.into(),
+			Endow::Minimum => Config::<T>::subsistence_threshold_uncached(),
+		};
+		let account_id = T::DetermineContractAddress::contract_address_for(
+			&code_hash,
+			&data,
+			&caller,
+		);
+		Contracts::<T>::instantiate(
+			RawOrigin::Signed(caller.clone()).into(),
+			endowment,
+			Weight::max_value(),
+			code_hash,
+			data,
+		)?;
+		let result = Contract {
+			caller,
+			addr: T::Lookup::unlookup(account_id.clone()),
+			account_id,
+			endowment,
+			code_hash,
+		};
+
+		// instantiate should leave us with an alive contract
+		result.alive_info()?;
+
+		Ok(result)
+	}
+
+	/// Store the supplied storage items into this contracts storage.
+	fn store(&self, items: &[(StorageKey, Vec<u8>)]) -> Result<(), &'static str> {
+		let info = self.alive_info()?;
+		for (key, value) in items {
+			storage::write_contract_storage::<T>(
+				&self.account_id,
+				&info.trie_id,
+				key,
+				Some(value.clone()),
+			)
+			.map_err(|_| "Failed to write storage of the contract.")?;
+		}
+		Ok(())
+	}
+
+	/// Get the `AliveContractInfo` of this contract. Returns an error if it is not alive.
+	fn alive_info(&self) -> Result<AliveContractInfo<T>, &'static str> {
+		ContractInfoOf::<T>::get(&self.account_id).and_then(|c| c.get_alive())
+			.ok_or("Expected contract to be alive at this point.")
+	}
+
+	/// Return an error if this contract is not a tombstone.
+	fn ensure_tombstone(&self) -> Result<(), &'static str> {
+		ContractInfoOf::<T>::get(&self.account_id).and_then(|c| c.get_tombstone())
+			.ok_or("Expected contract to be a tombstone at this point.")
+			.map(|_| ())
+	}
+}
+
+/// Generate `num` distinct storage keys.
+fn storage_keys(num: u32) -> Vec<StorageKey> {
+	(0..num).map(|n| sp_io::hashing::blake2_256(&n.encode())).collect()
+}
+
+/// The funding that each account that either calls or instantiates contracts is funded with.
+fn caller_funding<T: Trait>() -> BalanceOf<T> {
+	BalanceOf::<T>::max_value() / 2u32.into()
+}
+
+fn create_funded_user<T: Trait>(string: &'static str, n: u32) -> T::AccountId {
+	let user = account(string, n, 0);
+	T::Currency::make_free_balance_be(&user, caller_funding::<T>());
+	user
+}
+
+fn advance_block<T: Trait>(num: <T as frame_system::Trait>::BlockNumber) {
+	let now = System::<T>::block_number();
+	System::<T>::set_block_number(now + num);
+}
+
+benchmarks! {
+	_ {
+	}
+
+	// This extrinsic is pretty much constant as it is only a simple setter.
+	update_schedule {
+		let schedule = Schedule::<T> {
+			version: 1,
+			.. Default::default()
+		};
+	}: _(RawOrigin::Root, schedule)
+
+	// This constructs a contract that is maximal expensive to instrument.
+	// It creates a maximum number of metering blocks per byte.
+	// `n`: Size of the code in kilobytes.
+	put_code {
+		let n in 0 .. Contracts::<T>::current_schedule().limits.code_size / 1024;
+		let caller = create_funded_user::<T>("caller", 0);
+		let module = WasmModule::<T>::sized(n * 1024);
+		let origin = RawOrigin::Signed(caller);
+	}: _(origin, module.code)
+
+	// Removing code does not depend on the size of the code because it is never loaded.
+	remove_code {
+		let caller = create_funded_user::<T>("caller", 0);
+		let WasmModule { code, hash } = WasmModule::<T>::dummy();
+		let origin = RawOrigin::Signed(caller.clone());
+		Contracts::<T>::put_code(origin.clone().into(), code)?;
+	}: _(origin, hash)
+	verify {
+		assert!(!<CodeStorage<T>>::contains_key(&hash));
+		assert_eq!(T::Currency::reserved_balance(&caller), 0u32.into());
+	}
+
+	// Instantiate uses a dummy contract constructor to measure the overhead of the instantiate.
+	// The size of the data has no influence on the costs of this extrinsic as long as the contract
+	// won't call `ext_input` in its constructor to copy the data to contract memory.
+	// The dummy contract used here does not do this. The costs for the data copy is billed as
+	// part of `ext_input`.
+	instantiate {
+		let data = vec![0u8; 128];
+		let endowment = Config::<T>::subsistence_threshold_uncached();
+		let caller = create_funded_user::<T>("caller", 0);
+		let WasmModule { code, hash } = WasmModule::<T>::dummy();
+		let origin = RawOrigin::Signed(caller.clone());
+		Contracts::<T>::put_code(origin.clone().into(), code)?;
+	}: _(origin, endowment, Weight::max_value(), hash, data)
+	verify {
+		assert_eq!(
+			caller_funding::<T>() - endowment - T::Currency::reserved_balance(&caller),
+			T::Currency::free_balance(&caller),
+		)
+	}
+
+	// This is the combination of `put_code` and `instantiate`. It uses the same maximal
+	// expensive to instrument code as `put_code` and an empty constructor.
+	// `n`: Size of the code in kilobytes.
+	instantiate_with_code {
+		let n in 0 .. Contracts::<T>::current_schedule().limits.code_size / 1024;
+		let data = vec![0u8; 128];
+		let endowment = Config::<T>::subsistence_threshold_uncached();
+		let caller = create_funded_user::<T>("caller", 0);
+		let WasmModule { code, hash } = WasmModule::<T>::sized(n * 1024);
+		let origin = RawOrigin::Signed(caller.clone());
+		let addr = T::DetermineContractAddress::contract_address_for(&hash, &data, &caller);
+	}: _(origin, endowment, Weight::max_value(), code, data)
+	verify {
+		assert_eq!(
+			caller_funding::<T>() - endowment - T::Currency::reserved_balance(&caller),
+			T::Currency::free_balance(&caller),
+		);
+		ContractInfoOf::<T>::get(&addr).and_then(|c| c.get_alive())
+			.ok_or("Expected contract to be alive after instantiate_with_code")?;
+	}
+
+	// We just call a dummy contract to measure to overhead of the call extrinsic.
+	// As for instantiate the size of the data does not influence the costs.
+	call {
+		let data = vec![0u8; 128];
+		let instance = Contract::<T>::new(WasmModule::dummy(), vec![], Endow::Large)?;
+		let value = T::Currency::minimum_balance() * 100u32.into();
+		let origin = RawOrigin::Signed(instance.caller.clone());
+		let callee = instance.addr.clone();
+	}: _(origin, callee, value, Weight::max_value(), data)
+	verify {
+		assert_eq!(
+			caller_funding::<T>() - instance.endowment - value
+				- T::Currency::reserved_balance(&instance.caller),
+			T::Currency::free_balance(&instance.caller),
+		)
+	}
+
+	// We benchmark the costs for sucessfully evicting an empty contract.
+	// The actual costs are depending on how many storage items the evicted contract
+	// does have. However, those costs are not to be payed by the sender but
+	// will be distributed over multiple blocks using a scheduler. Otherwise there is
+	// no incentive to remove large contracts when the removal is more expensive than
+	// the reward for removing them.
+	claim_surcharge {
+		let instance = Contract::<T>::new(WasmModule::dummy(), vec![], Endow::Minimum)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+		let account_id = instance.account_id.clone();
+
+		// generate some rent
+		advance_block::<T>(<T as Trait>::SignedClaimHandicap::get() + 1.into());
+
+	}: _(origin, account_id, None)
+	verify {
+		// the claim surcharge should have evicted the contract
+		instance.ensure_tombstone()?;
+
+		// the caller should get the reward for being a good snitch
+		assert_eq!(
+			caller_funding::<T>() - instance.endowment + <T as Trait>::SurchargeReward::get(),
+			T::Currency::free_balance(&instance.caller),
+		);
+	}
+
+	ext_caller {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_caller", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_address {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_address", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_gas_left {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_gas_left", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_balance {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_balance", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_value_transferred {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_value_transferred", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_minimum_balance {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_minimum_balance", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_tombstone_deposit {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_tombstone_deposit", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_rent_allowance {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_rent_allowance", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_block_number {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_block_number", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_now {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::getter(
+			"ext_now", r * API_BENCHMARK_BATCH_SIZE
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_weight_to_fee {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let pages = max_pages::<T>();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_weight_to_fee",
+				params: vec![ValueType::I64, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			data_segments: vec![DataSegment {
+				offset: 0,
+				value: (pages * 64 * 1024 - 4).to_le_bytes().to_vec(),
+			}],
+			call_body: Some(body::repeated(r * API_BENCHMARK_BATCH_SIZE, &[
+				Instruction::I64Const(500_000),
+				Instruction::I32Const(4),
+				Instruction::I32Const(0),
+				Instruction::Call(0),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// The `gas` function can not be imported by contracts. It is only injected by the
+	// instrumentation. Therefore we need to store the contract without running it
+	// through the instrumentation.
+	ext_gas {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			imported_functions: vec![ImportedFunction {
+				name: "gas",
+				params: vec![ValueType::I32],
+				return_type: None,
+			}],
+			call_body: Some(body::repeated(r * API_BENCHMARK_BATCH_SIZE, &[
+				Instruction::I32Const(42),
+				Instruction::Call(0),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::with_raw_code(code)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// We cannot call ext_input multiple times. Therefore our weight determination is not
+	// as precise as with other APIs. Because this function can only be called once per
+	// contract it cannot be used for Dos.
+	ext_input {
+		let r in 0 .. 1;
+		let instance = Contract::<T>::new(WasmModule::getter("ext_input", r), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_input_per_kb {
+		let n in 0 .. max_pages::<T>() * 64 - 1;
+		let instance = Contract::<T>::new(WasmModule::getter("ext_input", 1), vec![], Endow::Large)?;
+		let data = vec![42u8; (n * 1024) as usize];
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), data)
+
+	// The same argument as for `ext_input` is true here.
+	ext_return {
+		let r in 0 .. 1;
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_return",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			call_body: Some(body::repeated(r, &[
+				Instruction::I32Const(0), // flags
+				Instruction::I32Const(0), // data_ptr
+				Instruction::I32Const(0), // data_len
+				Instruction::Call(0),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_return_per_kb {
+		let n in 0 .. max_pages::<T>() * 64;
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_return",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			call_body: Some(body::plain(vec![
+				Instruction::I32Const(0), // flags
+				Instruction::I32Const(0), // data_ptr
+				Instruction::I32Const((n * 1024) as i32), // data_len
+				Instruction::Call(0),
+				Instruction::End,
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// The same argument as for `ext_input` is true here.
+	ext_terminate {
+		let r in 0 .. 1;
+		let beneficiary = account::<T::AccountId>("beneficiary", 0, 0);
+		let beneficiary_bytes = beneficiary.encode();
+		let beneficiary_len = beneficiary_bytes.len();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_terminate",
+				params: vec![ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: beneficiary_bytes,
+				},
+			],
+			call_body: Some(body::repeated(r, &[
+				Instruction::I32Const(0), // beneficiary_ptr
+				Instruction::I32Const(beneficiary_len as i32), // beneficiary_len
+				Instruction::Call(0),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+		assert_eq!(T::Currency::total_balance(&beneficiary), 0u32.into());
+		assert_eq!(T::Currency::total_balance(&instance.account_id), instance.endowment);
+	}: call(origin, instance.addr.clone(), 0u32.into(), Weight::max_value(), vec![])
+	verify {
+		if r > 0 {
+			assert_eq!(T::Currency::total_balance(&instance.account_id), 0u32.into());
+			assert_eq!(T::Currency::total_balance(&beneficiary), instance.endowment);
+		}
+	}
+
+	// Restore just moves the trie id from origin to destination and therefore
+	// does not depend on the size of the destination contract. However, to not
+	// trigger any edge case we won't use an empty contract as destination.
+	ext_restore_to {
+		let r in 0 .. 1;
+		let restore_to = RestoreSetup::<T>::new(0)?;
+		let origin = RawOrigin::Signed(restore_to.restorer.caller.clone());
+		let restorer = restore_to.restorer.addr.clone();
+	}: call(origin, restorer, 0u32.into(), Weight::max_value(), vec![r as u8])
+	verify {
+		if r > 0 {
+			restore_to.dest.alive_info()?;
+		}
+	}
+
+	// `d`: Number of batches of supplied delta keys
+	ext_restore_to_per_delta {
+		let d in 0 .. API_BENCHMARK_BATCHES;
+		let restore_to = RestoreSetup::<T>::new(d * API_BENCHMARK_BATCH_SIZE)?;
+		let origin = RawOrigin::Signed(restore_to.restorer.caller.clone());
+		let restorer = restore_to.restorer.addr.clone();
+	}: call(origin, restorer, 0u32.into(), Weight::max_value(), vec![1])
+	verify {
+		restore_to.dest.alive_info()?;
+	}
+
+	// We benchmark only for the maximum subject length. We assume that this is some lowish
+	// number (< 1 KB). Therefore we are not overcharging too much in case a smaller subject is
+	// used.
+	ext_random {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let pages = max_pages::<T>();
+		let subject_len = Contracts::<T>::current_schedule().limits.subject_len;
+		assert!(subject_len < 1024);
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_random",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: (pages * 64 * 1024 - subject_len - 4).to_le_bytes().to_vec(),
+				},
+			],
+			call_body: Some(body::repeated(r * API_BENCHMARK_BATCH_SIZE, &[
+				Instruction::I32Const(4), // subject_ptr
+				Instruction::I32Const(subject_len as i32), // subject_len
+				Instruction::I32Const((subject_len + 4) as i32), // out_ptr
+				Instruction::I32Const(0), // out_len_ptr
+				Instruction::Call(0),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// The debug buffer is never enabled on-chain. We therefore only benchmark the case
+	// where the message is dropped because that is the only one that is ever charged.
+	ext_debug_message {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory { min_pages: 1, max_pages: 1 }),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_debug_message",
+				params: vec![ValueType::I32, ValueType::I32],
+				return_type: Some(ValueType::I32),
+			}],
+			call_body: Some(body::repeated(r * API_BENCHMARK_BATCH_SIZE, &[
+				Instruction::I32Const(0), // str_ptr
+				Instruction::I32Const(0), // str_len
+				Instruction::Call(0),
+				Instruction::Drop,
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// Overhead of calling the function without any topic.
+	// We benchmark for the worst case (largest event).
+	ext_deposit_event {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_deposit_event",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			call_body: Some(body::repeated(r * API_BENCHMARK_BATCH_SIZE, &[
+				Instruction::I32Const(0), // topics_ptr
+				Instruction::I32Const(0), // topics_len
+				Instruction::I32Const(0), // data_ptr
+				Instruction::I32Const(0), // data_len
+				Instruction::Call(0),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// Benchmark the overhead that topics generate.
+	// `t`: Number of topics
+	// `n`: Size of event payload in kb
+	ext_deposit_event_per_topic_and_kb {
+		let t in 0 .. Contracts::<T>::current_schedule().limits.event_topics;
+		let n in 0 .. T::MaxValueSize::get() / 1024;
+		let mut topics = (0..API_BENCHMARK_BATCH_SIZE)
+			.map(|b| (b * t..b * t + t).map(|i| T::Hashing::hash_of(&i)).collect::<Vec<_>>().encode())
+			.peekable();
+		let topics_len = topics.peek().map(|i| i.len()).unwrap_or(0);
+		let topics = topics.flatten().collect();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_deposit_event",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: topics,
+				},
+			],
+			call_body: Some(body::counted(API_BENCHMARK_BATCH_SIZE, vec![
+				Counter(0, topics_len as u32), // topics_ptr
+				Regular(Instruction::I32Const(topics_len as i32)), // topics_len
+				Regular(Instruction::I32Const(0)), // data_ptr
+				Regular(Instruction::I32Const((n * 1024) as i32)), // data_len
+				Regular(Instruction::Call(0)),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_set_rent_allowance {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let allowance = caller_funding::<T>().encode();
+		let allowance_len = allowance.len();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_set_rent_allowance",
+				params: vec![ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: allowance,
+				},
+			],
+			call_body: Some(body::repeated(r * API_BENCHMARK_BATCH_SIZE, &[
+				Instruction::I32Const(0), // value_ptr
+				Instruction::I32Const(allowance_len as i32), // value_len
+				Instruction::Call(0),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// Only the overhead of calling the function itself with minimal arguments.
+	// The contract is a bit more complex because I needs to use different keys in order
+	// to generate unique storage accesses. However, it is still dominated by the storage
+	// accesses.
+	ext_set_storage {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let keys = storage_keys(r * API_BENCHMARK_BATCH_SIZE);
+		let key_len = sp_std::mem::size_of::<StorageKey>();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_set_storage",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: keys.concat(),
+				},
+			],
+			call_body: Some(body::counted(r * API_BENCHMARK_BATCH_SIZE, vec![
+				Counter(0, key_len as u32), // key_ptr
+				Regular(Instruction::I32Const(0)), // value_ptr
+				Regular(Instruction::I32Const(0)), // value_len
+				Regular(Instruction::Call(0)),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_set_storage_per_kb {
+		let n in 0 .. T::MaxValueSize::get() / 1024;
+		let keys = storage_keys(API_BENCHMARK_BATCH_SIZE);
+		let key_len = sp_std::mem::size_of::<StorageKey>();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_set_storage",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: keys.concat(),
+				},
+			],
+			call_body: Some(body::counted(API_BENCHMARK_BATCH_SIZE, vec![
+				Counter(0, key_len as u32), // key_ptr
+				Regular(Instruction::I32Const(0)), // value_ptr
+				Regular(Instruction::I32Const((n * 1024) as i32)), // value_len
+				Regular(Instruction::Call(0)),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// Similar to ext_set_storage. However, we store all the keys that we are about to
+	// delete beforehand in order to prevent any optimizations that could occur when
+	// deleting a non existing key.
+	ext_clear_storage {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let keys = storage_keys(r * API_BENCHMARK_BATCH_SIZE);
+		let key_len = sp_std::mem::size_of::<StorageKey>();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_clear_storage",
+				params: vec![ValueType::I32],
+				return_type: None,
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: keys.concat(),
+				},
+			],
+			call_body: Some(body::counted(r * API_BENCHMARK_BATCH_SIZE, vec![
+				Counter(0, key_len as u32), // key_ptr
+				Regular(Instruction::Call(0)),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let items = keys.into_iter().map(|key| (key, vec![42u8])).collect::<Vec<_>>();
+		instance.store(&items)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// We make sure that all storage accesses are to unique keys.
+	ext_get_storage {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let keys = storage_keys(r * API_BENCHMARK_BATCH_SIZE);
+		let key_len = sp_std::mem::size_of::<StorageKey>();
+		let key_bytes = keys.concat();
+		let key_bytes_len = key_bytes.len();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_get_storage",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: Some(ValueType::I32),
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: key_bytes,
+				},
+			],
+			call_body: Some(body::counted(r * API_BENCHMARK_BATCH_SIZE, vec![
+				Counter(0, key_len as u32), // key_ptr
+				Regular(Instruction::I32Const((key_bytes_len + 4) as i32)), // out_ptr
+				Regular(Instruction::I32Const(key_bytes_len as i32)), // out_len_ptr
+				Regular(Instruction::Call(0)),
+				Regular(Instruction::Drop),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let items = keys.into_iter().map(|key| (key, vec![])).collect::<Vec<_>>();
+		instance.store(&items)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	ext_get_storage_per_kb {
+		let n in 0 .. T::MaxValueSize::get() / 1024;
+		let keys = storage_keys(API_BENCHMARK_BATCH_SIZE);
+		let key_len = sp_std::mem::size_of::<StorageKey>();
+		let key_bytes = keys.concat();
+		let key_bytes_len = key_bytes.len();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_get_storage",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: Some(ValueType::I32),
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: key_bytes,
+				},
+				DataSegment {
+					offset: key_bytes_len as u32,
+					value: T::MaxValueSize::get().to_le_bytes().to_vec(),
+				},
+			],
+			call_body: Some(body::counted(API_BENCHMARK_BATCH_SIZE, vec![
+				Counter(0, key_len as u32), // key_ptr
+				Regular(Instruction::I32Const((key_bytes_len + 4) as i32)), // out_ptr
+				Regular(Instruction::I32Const(key_bytes_len as i32)), // out_len_ptr
+				Regular(Instruction::Call(0)),
+				Regular(Instruction::Drop),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let items = keys.into_iter()
+			.map(|key| (key, vec![42u8; (n * 1024) as usize]))
+			.collect::<Vec<_>>();
+		instance.store(&items)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// We transfer to unique accounts.
+	ext_transfer {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let accounts = (0..r * API_BENCHMARK_BATCH_SIZE)
+			.map(|i| account::<T::AccountId>("receiver", i, 0))
+			.collect::<Vec<_>>();
+		let account_len = accounts.get(0).map(|i| i.encode().len()).unwrap_or(0);
+		let account_bytes = accounts.iter().flat_map(|x| x.encode()).collect();
+		let value = Config::<T>::subsistence_threshold_uncached();
+		assert!(value > 0u32.into());
+		let value_bytes = value.encode();
+		let value_len = value_bytes.len();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_transfer",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: Some(ValueType::I32),
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: value_bytes,
+				},
+				DataSegment {
+					offset: value_len as u32,
+					value: account_bytes,
+				},
+			],
+			call_body: Some(body::counted(r * API_BENCHMARK_BATCH_SIZE, vec![
+				Counter(value_len as u32, account_len as u32), // account_ptr
+				Regular(Instruction::I32Const(account_len as i32)), // account_len
+				Regular(Instruction::I32Const(0)), // value_ptr
+				Regular(Instruction::I32Const(value_len as i32)), // value_len
+				Regular(Instruction::Call(0)),
+				Regular(Instruction::Drop),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+		for account in &accounts {
+			assert_eq!(T::Currency::total_balance(account), 0u32.into());
+		}
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+	verify {
+		for account in &accounts {
+			assert_eq!(T::Currency::total_balance(account), value);
+		}
+	}
+
+	ext_call {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let dummy_code = WasmModule::<T>::dummy();
+		let callees = (0..r * API_BENCHMARK_BATCH_SIZE)
+			.map(|i| Contract::<T>::with_index(i + 1, dummy_code.clone(), vec![], Endow::Large))
+			.collect::<Result<Vec<_>, _>>()?;
+		let callee_len = callees.get(0).map(|i| i.account_id.encode().len()).unwrap_or(0);
+		let callee_bytes = callees.iter().flat_map(|x| x.account_id.encode()).collect();
+		let value: BalanceOf<T> = 0u32.into();
+		let value_bytes = value.encode();
+		let value_len = value_bytes.len();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_call",
+				params: vec![
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I64,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+				],
+				return_type: Some(ValueType::I32),
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: value_bytes,
+				},
+				DataSegment {
+					offset: value_len as u32,
+					value: callee_bytes,
+				},
+			],
+			call_body: Some(body::counted(r * API_BENCHMARK_BATCH_SIZE, vec![
+				Counter(value_len as u32, callee_len as u32), // callee_ptr
+				Regular(Instruction::I32Const(callee_len as i32)), // callee_len
+				Regular(Instruction::I64Const(0)), // gas
+				Regular(Instruction::I32Const(0)), // value_ptr
+				Regular(Instruction::I32Const(value_len as i32)), // value_len
+				Regular(Instruction::I32Const(0)), // input_data_ptr
+				Regular(Instruction::I32Const(0)), // input_data_len
+				Regular(Instruction::I32Const(u32::max_value() as i32)), // output_ptr
+				Regular(Instruction::I32Const(0)), // output_len_ptr
+				Regular(Instruction::Call(0)),
+				Regular(Instruction::Drop),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// `i`: Size of the input data in kilobytes
+	// `o`: Size of the output data in kilobytes
+	ext_call_per_input_output_kb {
+		let i in 0 .. max_pages::<T>() * 64 - 1;
+		let o in 0 .. (max_pages::<T>() - 1) * 64;
+		let callee_code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_return",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			call_body: Some(body::plain(vec![
+				Instruction::I32Const(0), // flags
+				Instruction::I32Const(0), // data_ptr
+				Instruction::I32Const((o * 1024) as i32), // data_len
+				Instruction::Call(0),
+				Instruction::End,
+			])),
+			.. Default::default()
+		});
+		let callee = Contract::<T>::with_index(1, callee_code, vec![], Endow::Large)?;
+		let callee_bytes = callee.account_id.encode();
+		let callee_len = callee_bytes.len();
+		let value: BalanceOf<T> = 0u32.into();
+		let value_bytes = value.encode();
+		let value_len = value_bytes.len();
+		let output_len_offset = value_len + callee_len;
+		// The output buffer starts right after the buffer that contains its length.
+		let output_offset = output_len_offset + 4;
+		let output_len = max_pages::<T>() * 64 * 1024 - output_offset as u32;
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_call",
+				params: vec![
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I64,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+				],
+				return_type: Some(ValueType::I32),
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: value_bytes,
+				},
+				DataSegment {
+					offset: value_len as u32,
+					value: callee_bytes,
+				},
+				DataSegment {
+					offset: output_len_offset as u32,
+					value: output_len.to_le_bytes().to_vec(),
+				},
+			],
+			call_body: Some(body::repeated(API_BENCHMARK_BATCH_SIZE, &[
+				Instruction::I32Const(value_len as i32), // callee_ptr
+				Instruction::I32Const(callee_len as i32), // callee_len
+				Instruction::I64Const(0), // gas
+				Instruction::I32Const(0), // value_ptr
+				Instruction::I32Const(value_len as i32), // value_len
+				Instruction::I32Const(0), // input_data_ptr
+				Instruction::I32Const((i * 1024) as i32), // input_data_len
+				Instruction::I32Const(output_offset as i32), // output_ptr
+				Instruction::I32Const(output_len_offset as i32), // output_len_ptr
+				Instruction::Call(0),
+				Instruction::Drop,
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// Every instantiation uses an input of a different length in order to derive a unique
+	// contract address. The weight of the copied input is negligible compared to the
+	// costs of the instantiation itself.
+	ext_instantiate {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let caller = create_funded_user::<T>("deployer", 0);
+		let callee_code = WasmModule::<T>::dummy();
+		let hash_bytes = callee_code.hash.encode();
+		let hash_len = hash_bytes.len();
+		Contracts::<T>::put_code(RawOrigin::Signed(caller).into(), callee_code.code)?;
+		let value = Config::<T>::subsistence_threshold_uncached();
+		assert!(value > 0u32.into());
+		let value_bytes = value.encode();
+		let value_len = value_bytes.len();
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_instantiate",
+				params: vec![
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I64,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+				],
+				return_type: Some(ValueType::I32),
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: hash_bytes,
+				},
+				DataSegment {
+					offset: hash_len as u32,
+					value: value_bytes,
+				},
+			],
+			call_body: Some(body::counted(r * API_BENCHMARK_BATCH_SIZE, vec![
+				Regular(Instruction::I32Const(0)), // code_hash_ptr
+				Regular(Instruction::I32Const(hash_len as i32)), // code_hash_len
+				Regular(Instruction::I64Const(0)), // gas
+				Regular(Instruction::I32Const(hash_len as i32)), // value_ptr
+				Regular(Instruction::I32Const(value_len as i32)), // value_len
+				Regular(Instruction::I32Const(0)), // input_data_ptr
+				Counter(0, 1), // input_data_len
+				Regular(Instruction::I32Const(u32::max_value() as i32)), // address_ptr
+				Regular(Instruction::I32Const(0)), // address_len_ptr
+				Regular(Instruction::I32Const(u32::max_value() as i32)), // output_ptr
+				Regular(Instruction::I32Const(0)), // output_len_ptr
+				Regular(Instruction::Call(0)),
+				Regular(Instruction::Drop),
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+		let balance = T::Currency::free_balance(&instance.account_id);
+	}: call(origin, instance.addr.clone(), 0u32.into(), Weight::max_value(), vec![])
+	verify {
+		let num: u32 = r * API_BENCHMARK_BATCH_SIZE;
+		assert_eq!(
+			T::Currency::free_balance(&instance.account_id),
+			balance - value * num.into(),
+		);
+	}
+
+	// The callee reverts its instantiation. This allows us to instantiate the same code
+	// with the same input many times without running into an already existing contract.
+	// `i`: Size of the input data in kilobytes
+	// `o`: Size of the output data in kilobytes
+	ext_instantiate_per_input_output_kb {
+		let i in 0 .. (max_pages::<T>() - 1) * 64;
+		let o in 0 .. (max_pages::<T>() - 1) * 64;
+		let callee_code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_return",
+				params: vec![ValueType::I32, ValueType::I32, ValueType::I32],
+				return_type: None,
+			}],
+			deploy_body: Some(body::plain(vec![
+				Instruction::I32Const(1), // flags: REVERT
+				Instruction::I32Const(0), // data_ptr
+				Instruction::I32Const((o * 1024) as i32), // data_len
+				Instruction::Call(0),
+				Instruction::End,
+			])),
+			.. Default::default()
+		});
+		let caller = create_funded_user::<T>("deployer", 0);
+		let hash_bytes = callee_code.hash.encode();
+		let hash_len = hash_bytes.len();
+		Contracts::<T>::put_code(RawOrigin::Signed(caller).into(), callee_code.code)?;
+		let value = Config::<T>::subsistence_threshold_uncached();
+		let value_bytes = value.encode();
+		let value_len = value_bytes.len();
+		let output_len_offset = hash_len + value_len;
+		// The output buffer starts right after the buffer that contains its length.
+		let output_offset = output_len_offset + 4;
+		let output_len = max_pages::<T>() * 64 * 1024 - output_offset as u32;
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![ImportedFunction {
+				name: "ext_instantiate",
+				params: vec![
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I64,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+					ValueType::I32,
+				],
+				return_type: Some(ValueType::I32),
+			}],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: hash_bytes,
+				},
+				DataSegment {
+					offset: hash_len as u32,
+					value: value_bytes,
+				},
+				DataSegment {
+					offset: output_len_offset as u32,
+					value: output_len.to_le_bytes().to_vec(),
+				},
+			],
+			call_body: Some(body::repeated(API_BENCHMARK_BATCH_SIZE, &[
+				Instruction::I32Const(0), // code_hash_ptr
+				Instruction::I32Const(hash_len as i32), // code_hash_len
+				Instruction::I64Const(0), // gas
+				Instruction::I32Const(hash_len as i32), // value_ptr
+				Instruction::I32Const(value_len as i32), // value_len
+				Instruction::I32Const(0), // input_data_ptr
+				Instruction::I32Const((i * 1024) as i32), // input_data_len
+				Instruction::I32Const(u32::max_value() as i32), // address_ptr
+				Instruction::I32Const(0), // address_len_ptr
+				Instruction::I32Const(output_offset as i32), // output_ptr
+				Instruction::I32Const(output_len_offset as i32), // output_len_ptr
+				Instruction::Call(0),
+				Instruction::Drop,
+			])),
+			.. Default::default()
+		});
+		let instance = Contract::<T>::new(code, vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// Only the overhead of calling the function itself with minimal arguments.
+	ext_hash_sha2_256 {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::hasher(
+			"ext_hash_sha2_256", r * API_BENCHMARK_BATCH_SIZE, 0,
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// `n`: Input to hash in kilobytes
+	ext_hash_sha2_256_per_kb {
+		let n in 0 .. max_pages::<T>() * 64;
+		let instance = Contract::<T>::new(WasmModule::hasher(
+			"ext_hash_sha2_256", API_BENCHMARK_BATCH_SIZE, n * 1024,
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// Only the overhead of calling the function itself with minimal arguments.
+	ext_hash_keccak_256 {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::hasher(
+			"ext_hash_keccak_256", r * API_BENCHMARK_BATCH_SIZE, 0,
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// `n`: Input to hash in kilobytes
+	ext_hash_keccak_256_per_kb {
+		let n in 0 .. max_pages::<T>() * 64;
+		let instance = Contract::<T>::new(WasmModule::hasher(
+			"ext_hash_keccak_256", API_BENCHMARK_BATCH_SIZE, n * 1024,
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// Only the overhead of calling the function itself with minimal arguments.
+	ext_hash_blake2_256 {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::hasher(
+			"ext_hash_blake2_256", r * API_BENCHMARK_BATCH_SIZE, 0,
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// `n`: Input to hash in kilobytes
+	ext_hash_blake2_256_per_kb {
+		let n in 0 .. max_pages::<T>() * 64;
+		let instance = Contract::<T>::new(WasmModule::hasher(
+			"ext_hash_blake2_256", API_BENCHMARK_BATCH_SIZE, n * 1024,
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// Only the overhead of calling the function itself with minimal arguments.
+	ext_hash_blake2_128 {
+		let r in 0 .. API_BENCHMARK_BATCHES;
+		let instance = Contract::<T>::new(WasmModule::hasher(
+			"ext_hash_blake2_128", r * API_BENCHMARK_BATCH_SIZE, 0,
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// `n`: Input to hash in kilobytes
+	ext_hash_blake2_128_per_kb {
+		let n in 0 .. max_pages::<T>() * 64;
+		let instance = Contract::<T>::new(WasmModule::hasher(
+			"ext_hash_blake2_128", API_BENCHMARK_BATCH_SIZE, n * 1024,
+		), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// The instruction benchmarks repeat a sequence of instructions. Helper instructions
+	// (`const`, `drop`) that are needed to execute the measured instruction are subtracted
+	// when the `Schedule` is derived from these benchmarks.
+
+	instr_const {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract::<T>(r, &[
+			Instruction::I64Const(0x7ffffffff3ffffff),
+			Instruction::Drop,
+		])?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_nop {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract::<T>(r, &[Instruction::Nop])?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_control_flow {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract::<T>(r, &[
+			Instruction::Block(BlockType::NoResult),
+			Instruction::Br(0),
+			Instruction::End,
+		])?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_local {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let mut call_body = body::repeated(r * INSTR_BENCHMARK_BATCH_SIZE, &[
+			Instruction::GetLocal(0),
+			Instruction::SetLocal(0),
+		]);
+		body::inject_locals(&mut call_body, 1, ValueType::I64);
+		let instance = Contract::<T>::new(ModuleDefinition {
+			call_body: Some(call_body),
+			.. Default::default()
+		}.into(), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_load {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract_with_mem::<T>(r, &[
+			Instruction::I32Const(0),
+			Instruction::I64Load(3, 0),
+			Instruction::Drop,
+		])?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_store {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract_with_mem::<T>(r, &[
+			Instruction::I32Const(0),
+			Instruction::I64Const(42),
+			Instruction::I64Store(3, 0),
+		])?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_integer_comparison {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract::<T>(r, &binary_instr(Instruction::I64LtU))?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_bit {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract::<T>(r, &binary_instr(Instruction::I64Xor))?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_add {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract::<T>(r, &binary_instr(Instruction::I64Add))?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_mul {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract::<T>(r, &binary_instr(Instruction::I64Mul))?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// The divisor is `1` in order to never trap.
+	instr_div {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract::<T>(r, &[
+			Instruction::I64Const(0x7ffffffff3ffffff),
+			Instruction::I64Const(1),
+			Instruction::I64DivU,
+			Instruction::Drop,
+		])?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_conversion {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract::<T>(r, &[
+			Instruction::I32Const(0x7ff3ffff),
+			Instruction::I64ExtendUI32,
+			Instruction::Drop,
+		])?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	instr_current_memory {
+		let r in 0 .. INSTR_BENCHMARK_BATCHES;
+		let instance = instr_contract_with_mem::<T>(r, &[
+			Instruction::CurrentMemory(0),
+			Instruction::Drop,
+		])?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+
+	// The memory is grown one page at a time until the maximum is reached. The weight of a
+	// single page is derived by dividing by the maximum number of pages.
+	instr_grow_memory {
+		let r in 0 .. 1;
+		let max_pages = max_pages::<T>();
+		let instance = Contract::<T>::new(ModuleDefinition {
+			memory: Some(ImportedMemory {
+				min_pages: 0,
+				max_pages,
+			}),
+			call_body: Some(body::repeated(r * max_pages, &[
+				Instruction::I32Const(1),
+				Instruction::GrowMemory(0),
+				Instruction::Drop,
+			])),
+			.. Default::default()
+		}.into(), vec![], Endow::Large)?;
+		let origin = RawOrigin::Signed(instance.caller.clone());
+	}: call(origin, instance.addr, 0u32.into(), Weight::max_value(), vec![])
+}
+
+/// Instantiates a contract that repeats the supplied instructions
+/// `r * INSTR_BENCHMARK_BATCH_SIZE` times when called.
+fn instr_contract<T: Trait>(r: u32, instructions: &[Instruction])
+	-> Result<Contract<T>, &'static str>
+{
+	Contract::<T>::new(ModuleDefinition {
+		call_body: Some(body::repeated(r * INSTR_BENCHMARK_BATCH_SIZE, instructions)),
+		.. Default::default()
+	}.into(), vec![], Endow::Large)
+}
+
+/// Same as `instr_contract` but the contract has a maximum sized linear memory.
+fn instr_contract_with_mem<T: Trait>(r: u32, instructions: &[Instruction])
+	-> Result<Contract<T>, &'static str>
+{
+	Contract::<T>::new(ModuleDefinition {
+		memory: Some(ImportedMemory::max::<T>()),
+		call_body: Some(body::repeated(r * INSTR_BENCHMARK_BATCH_SIZE, instructions)),
+		.. Default::default()
+	}.into(), vec![], Endow::Large)
+}
+
+/// The instructions to benchmark a binary operator together with its arguments.
+fn binary_instr(instruction: Instruction) -> [Instruction; 4] {
+	[
+		Instruction::I64Const(0x7ffffffff3ffffff),
+		Instruction::I64Const(0x7ffffffff3ffffff),
+		instruction,
+		Instruction::Drop,
+	]
+}
+
+/// A tombstone and a contract that is able to restore it.
+struct RestoreSetup<T: Trait> {
+	/// The evicted contract which is the destination of the restoration.
+	dest: Contract<T>,
+	/// The contract that calls `ext_restore_to` when its input is non zero.
+	restorer: Contract<T>,
+}
+
+impl<T: Trait> RestoreSetup<T> {
+	/// Create a tombstone and a restorer whose storage contains `delta` items.
+	///
+	/// The restorer passes all of its storage items as delta to `ext_restore_to` so that
+	/// its storage matches the one of the evicted contract (which is empty).
+	fn new(delta: u32) -> Result<Self, &'static str> {
+		let dest = Contract::<T>::with_index(0, WasmModule::dummy(), vec![], Endow::Minimum)?;
+		let dest_bytes = dest.account_id.encode();
+		let dest_len = dest_bytes.len();
+		let hash_bytes = dest.code_hash.encode();
+		let hash_len = hash_bytes.len();
+		let allowance = caller_funding::<T>().encode();
+		let allowance_len = allowance.len();
+		let delta_keys = storage_keys(delta);
+		let delta_offset = dest_len + hash_len + allowance_len;
+		let input_len_offset = delta_offset + delta_keys.len() * sp_std::mem::size_of::<StorageKey>();
+		// The input buffer starts right after the buffer that contains its length.
+		let input_offset = input_len_offset + 4;
+		let code = WasmModule::<T>::from(ModuleDefinition {
+			memory: Some(ImportedMemory::max::<T>()),
+			imported_functions: vec![
+				ImportedFunction {
+					name: "ext_input",
+					params: vec![ValueType::I32, ValueType::I32],
+					return_type: None,
+				},
+				ImportedFunction {
+					name: "ext_restore_to",
+					params: vec![
+						ValueType::I32,
+						ValueType::I32,
+						ValueType::I32,
+						ValueType::I32,
+						ValueType::I32,
+						ValueType::I32,
+						ValueType::I32,
+						ValueType::I32,
+					],
+					return_type: None,
+				},
+			],
+			data_segments: vec![
+				DataSegment {
+					offset: 0,
+					value: dest_bytes,
+				},
+				DataSegment {
+					offset: dest_len as u32,
+					value: hash_bytes,
+				},
+				DataSegment {
+					offset: (dest_len + hash_len) as u32,
+					value: allowance,
+				},
+				DataSegment {
+					offset: delta_offset as u32,
+					value: delta_keys.concat(),
+				},
+				DataSegment {
+					offset: input_len_offset as u32,
+					value: 1u32.to_le_bytes().to_vec(),
+				},
+			],
+			// The restorer only restores when it receives a non zero byte as input.
+			// Otherwise it just reads its input. This allows us to benchmark the
+			// base costs of the call.
+			call_body: Some(body::plain(vec![
+				Instruction::I32Const(input_offset as i32), // buf_ptr
+				Instruction::I32Const(input_len_offset as i32), // buf_len_ptr
+				Instruction::Call(0),
+				Instruction::I32Const(input_offset as i32),
+				Instruction::I32Load8U(0, 0),
+				Instruction::If(BlockType::NoResult),
+				Instruction::I32Const(0), // dest_ptr
+				Instruction::I32Const(dest_len as i32), // dest_len
+				Instruction::I32Const(dest_len as i32), // code_hash_ptr
+				Instruction::I32Const(hash_len as i32), // code_hash_len
+				Instruction::I32Const((dest_len + hash_len) as i32), // rent_allowance_ptr
+				Instruction::I32Const(allowance_len as i32), // rent_allowance_len
+				Instruction::I32Const(delta_offset as i32), // delta_ptr
+				Instruction::I32Const(delta as i32), // delta_count
+				Instruction::Call(1),
+				Instruction::End,
+				Instruction::End,
+			])),
+			.. Default::default()
+		});
+		let restorer = Contract::<T>::with_index(1, code, vec![], Endow::Large)?;
+		let items = delta_keys.into_iter().map(|key| (key, vec![42u8])).collect::<Vec<_>>();
+		restorer.store(&items)?;
+
+		// Evict the destination. The restoration is not possible in the same block
+		// where the storage of the restorer was written.
+		advance_block::<T>(<T as Trait>::SignedClaimHandicap::get() + 1.into());
+		rent::snitch_contract_should_be_evicted::<T>(&dest.account_id, Zero::zero());
+		dest.ensure_tombstone()?;
+
+		Ok(Self { dest, restorer })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{ExtBuilder, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn update_schedule() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_update_schedule::<Test>());
+		});
+	}
+
+	#[test]
+	fn put_code() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_put_code::<Test>());
+		});
+	}
+
+	#[test]
+	fn remove_code() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_remove_code::<Test>());
+		});
+	}
+
+	#[test]
+	fn instantiate() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instantiate::<Test>());
+		});
+	}
+
+	#[test]
+	fn instantiate_with_code() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instantiate_with_code::<Test>());
+		});
+	}
+
+	#[test]
+	fn call() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_call::<Test>());
+		});
+	}
+
+	#[test]
+	fn claim_surcharge() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_claim_surcharge::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_caller() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_caller::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_address() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_address::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_gas_left() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_gas_left::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_balance() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_balance::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_value_transferred() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_value_transferred::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_minimum_balance() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_minimum_balance::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_tombstone_deposit() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_tombstone_deposit::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_rent_allowance() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_rent_allowance::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_block_number() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_block_number::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_now() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_now::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_weight_to_fee() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_weight_to_fee::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_gas() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_gas::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_input() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_input::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_input_per_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_input_per_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_return() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_return::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_return_per_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_return_per_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_terminate() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_terminate::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_restore_to() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_restore_to::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_restore_to_per_delta() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_restore_to_per_delta::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_random() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_random::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_debug_message() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_debug_message::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_deposit_event() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_deposit_event::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_deposit_event_per_topic_and_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_deposit_event_per_topic_and_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_set_rent_allowance() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_set_rent_allowance::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_set_storage() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_set_storage::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_set_storage_per_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_set_storage_per_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_clear_storage() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_clear_storage::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_get_storage() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_get_storage::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_get_storage_per_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_get_storage_per_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_transfer() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_transfer::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_call() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_call::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_call_per_input_output_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_call_per_input_output_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_instantiate() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_instantiate::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_instantiate_per_input_output_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_instantiate_per_input_output_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_hash_sha2_256() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_hash_sha2_256::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_hash_sha2_256_per_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_hash_sha2_256_per_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_hash_keccak_256() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_hash_keccak_256::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_hash_keccak_256_per_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_hash_keccak_256_per_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_hash_blake2_256() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_hash_blake2_256::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_hash_blake2_256_per_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_hash_blake2_256_per_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_hash_blake2_128() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_hash_blake2_128::<Test>());
+		});
+	}
+
+	#[test]
+	fn ext_hash_blake2_128_per_kb() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_ext_hash_blake2_128_per_kb::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_const() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_const::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_nop() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_nop::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_control_flow() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_control_flow::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_local() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_local::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_load() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_load::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_store() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_store::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_integer_comparison() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_integer_comparison::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_bit() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_bit::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_add() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_add::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_mul() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_mul::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_div() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_div::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_conversion() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_conversion::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_current_memory() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_current_memory::<Test>());
+		});
+	}
+
+	#[test]
+	fn instr_grow_memory() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_instr_grow_memory::<Test>());
+		});
+	}
+}
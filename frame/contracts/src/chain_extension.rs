@@ -0,0 +1,216 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate. If not, see <http://www.gnu.org/licenses/>.
+
+//! A mechanism for runtime authors to augment the functionality of contracts.
+//!
+//! The runtime is able to call into any contract and retrieve the result using
+//! [`bare_call`](crate::Module::bare_call). This already allows customization of runtime
+//! behaviour by user generated code (contracts). However, often it is more straightforward
+//! to allow the reverse behaviour: The contract calls into the runtime. We call the latter
+//! one a "chain extension" because it allows the chain to extend the set of functions that are
+//! callable by a contract.
+//!
+//! In order to create a chain extension the runtime author implements the [`ChainExtension`]
+//! trait and declares it in this pallet's [configuration Trait](crate::Trait). All types
+//! required for this endeavour are defined or re-exported in this module. There is an
+//! implementation on `()` which can be used to signal that no chain extension is available.
+//!
+//! # Usage
+//!
+//! A contract calls into the chain extension by importing `ext_call_chain_extension`. It
+//! supplies a `func_id` that the extension uses to decide which functionality to execute,
+//! together with an input and an output buffer. The `u32` returned by the extension is
+//! passed back to the contract unaltered.
+//!
+//! # Security
+//!
+//! The chain author alone is responsible for the security of the chain extension.
+//! This includes avoiding the exposure of exploitable functions and charging the
+//! appropriate amount of weight. In order to do so benchmarks must be written and the
+//! [`charge_weight`](Environment::charge_weight) function must be called **before**
+//! carrying out any action that causes the consumption of the chargeable weight.
+//! It cannot be overstated how delicate of a process the creation of a chain extension
+//! is. Check whether using [`bare_call`](crate::Module::bare_call) suffices for the
+//! use case at hand.
+
+use crate::{Trait, Error, wasm::Runtime};
+use codec::Decode;
+use frame_support::weights::Weight;
+use sp_runtime::DispatchError;
+use sp_std::prelude::*;
+
+pub use crate::exec::{Ext, ReturnFlags};
+
+/// Result that returns a [`DispatchError`] on error.
+pub type Result<T> = sp_std::result::Result<T, DispatchError>;
+
+/// A trait used to extend the set of contract callable functions.
+///
+/// In order to create a custom chain extension this trait must be implemented and supplied
+/// to the pallet contracts configuration trait as the associated type of the same name.
+/// Consult the [module documentation](self) for a general explanation of chain extensions.
+pub trait ChainExtension<C: Trait> {
+	/// Call the chain extension logic.
+	///
+	/// This is the only function that needs to be implemented in order to write a
+	/// chain extension. It is called whenever a contract calls the `ext_call_chain_extension`
+	/// imported wasm function.
+	///
+	/// # Parameters
+	/// - `func_id`: The first argument to `ext_call_chain_extension`. Usually used to
+	///		determine which function to realize.
+	/// - `env`: Access to the remaining arguments and the execution environment.
+	///
+	/// # Return
+	///
+	/// In case of `Err` the contract execution is immediately suspended and the passed error
+	/// is returned to the caller. Otherwise the value of [`RetVal`] determines the exit
+	/// behaviour.
+	fn call<E: Ext<T = C>>(func_id: u32, env: Environment<E>) -> Result<RetVal>;
+
+	/// Determines whether chain extensions are enabled for this chain.
+	///
+	/// The default implementation returns `true`. Therefore it is not necessary to overwrite
+	/// this function when implementing a chain extension. In case of `false` the deployment of
+	/// a contract that references `ext_call_chain_extension` is denied and calling this
+	/// function will return [`NoChainExtension`](Error::NoChainExtension) without first calling
+	/// into [`call`](Self::call).
+	fn enabled() -> bool {
+		true
+	}
+}
+
+/// Implementation that indicates that no chain extension is available.
+impl<C: Trait> ChainExtension<C> for () {
+	fn call<E: Ext<T = C>>(_func_id: u32, _env: Environment<E>) -> Result<RetVal> {
+		// Never called since [`Self::enabled()`] is set to `false`. Because we want to
+		// avoid panics at all costs we supply a sensible error value here instead
+		// of an `unimplemented!`.
+		Err(Error::<C>::NoChainExtension.into())
+	}
+
+	fn enabled() -> bool {
+		false
+	}
+}
+
+/// Determines the exit behaviour and return value of a chain extension.
+pub enum RetVal {
+	/// The chain extensions returns the supplied value to its calling contract.
+	Converging(u32),
+	/// The control does **not** return to the calling contract.
+	///
+	/// Use this to stop the execution of the contract when the chain extension returns.
+	/// The semantic is the same as for calling `ext_return`: The control returns to
+	/// the caller of the currently executing contract yielding the supplied buffer and
+	/// flags.
+	Diverging{flags: ReturnFlags, data: Vec<u8>},
+}
+
+/// Grants the chain extension access to its parameters and execution environment.
+///
+/// The input buffer is the one passed as `input_ptr`/`input_len` to
+/// `ext_call_chain_extension` and the output buffer is designated by
+/// `output_ptr`/`output_len_ptr`. Reading the input is charged per byte like the data
+/// passed to `ext_return` and writing the output like the data copied by `ext_input`.
+pub struct Environment<'a, 'b, E: Ext> {
+	/// The actual data of this type.
+	runtime: &'a mut Runtime<'b, E>,
+	/// Pointer to the input buffer in sandbox memory.
+	input_ptr: u32,
+	/// Length of the input buffer.
+	input_len: u32,
+	/// Pointer to the output buffer in sandbox memory.
+	output_ptr: u32,
+	/// Pointer to the location where the length of the output buffer is stored.
+	output_len_ptr: u32,
+}
+
+impl<'a, 'b, E: Ext> Environment<'a, 'b, E> {
+	/// Create a new environment for the chain extension from the raw arguments that
+	/// were passed to `ext_call_chain_extension`.
+	pub(crate) fn new(
+		runtime: &'a mut Runtime<'b, E>,
+		input_ptr: u32,
+		input_len: u32,
+		output_ptr: u32,
+		output_len_ptr: u32,
+	) -> Self {
+		Environment {
+			runtime,
+			input_ptr,
+			input_len,
+			output_ptr,
+			output_len_ptr,
+		}
+	}
+
+	/// Charge the passed `amount` of weight from the overall limit.
+	///
+	/// It returns `Ok` when the remaining weight budget is larger than the passed
+	/// `weight`. It returns `Err` otherwise. In this case the chain extension should
+	/// abort the execution and pass through the error.
+	///
+	/// # Note
+	///
+	/// Weight is synonymous with gas in substrate.
+	pub fn charge_weight(&mut self, amount: Weight) -> Result<()> {
+		self.runtime.charge_chain_extension(amount)
+	}
+
+	/// Grants access to the execution environment of the current contract call.
+	///
+	/// Consult the functions on the returned type before re-implementing those functions.
+	pub fn ext(&mut self) -> &mut E {
+		self.runtime.ext()
+	}
+
+	/// The length of the input buffer as passed by the contract.
+	pub fn in_len(&self) -> u32 {
+		self.input_len
+	}
+
+	/// Reads the whole input buffer from the sandbox memory.
+	///
+	/// Weight is charged proportional to the length of the input buffer.
+	pub fn read(&mut self) -> Result<Vec<u8>> {
+		self.runtime.read_sandbox_memory(self.input_ptr, self.input_len)
+	}
+
+	/// Reads the input buffer and tries to decode it into the specified type.
+	///
+	/// Returns [`DecodingFailed`](Error::DecodingFailed) when the buffer does not
+	/// decode as `T`.
+	pub fn read_as<T: Decode>(&mut self) -> Result<T> {
+		let buf = self.read()?;
+		T::decode(&mut &buf[..]).map_err(|_| Error::<E::T>::DecodingFailed.into())
+	}
+
+	/// Write the supplied buffer to the output buffer of the contract.
+	///
+	/// If the contract supplied `u32::max_value()` as output pointer and `allow_skip`
+	/// is `true` nothing is written. Returns
+	/// [`OutputBufferTooSmall`](Error::OutputBufferTooSmall) if the supplied buffer
+	/// does not fit into the output buffer of the contract.
+	pub fn write(&mut self, buffer: &[u8], allow_skip: bool) -> Result<()> {
+		self.runtime.write_sandbox_output(
+			self.output_ptr,
+			self.output_len_ptr,
+			buffer,
+			allow_skip,
+		)
+	}
+}
@@ -0,0 +1,369 @@
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for the Contracts Pallet
+
+use frame_support::weights::{Weight, constants::RocksDbWeight as DbWeight};
+
+impl crate::WeightInfo for () {
+	fn update_schedule() -> Weight {
+		(35214000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn put_code(n: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((109153000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn remove_code() -> Weight {
+		(61288000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn instantiate() -> Weight {
+		(204084000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn instantiate_with_code(n: u32, ) -> Weight {
+		(293417000 as Weight)
+			.saturating_add((109153000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn call() -> Weight {
+		(197563000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn claim_surcharge() -> Weight {
+		(476153000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn ext_caller(r: u32, ) -> Weight {
+		(245931000 as Weight)
+			.saturating_add((432891000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_address(r: u32, ) -> Weight {
+		(246170000 as Weight)
+			.saturating_add((431578000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_gas_left(r: u32, ) -> Weight {
+		(243460000 as Weight)
+			.saturating_add((428206000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_balance(r: u32, ) -> Weight {
+		(247007000 as Weight)
+			.saturating_add((1073316000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_value_transferred(r: u32, ) -> Weight {
+		(244952000 as Weight)
+			.saturating_add((429644000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_minimum_balance(r: u32, ) -> Weight {
+		(246098000 as Weight)
+			.saturating_add((428929000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_tombstone_deposit(r: u32, ) -> Weight {
+		(245463000 as Weight)
+			.saturating_add((430198000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_rent_allowance(r: u32, ) -> Weight {
+		(247189000 as Weight)
+			.saturating_add((1069521000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_block_number(r: u32, ) -> Weight {
+		(244610000 as Weight)
+			.saturating_add((427413000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_now(r: u32, ) -> Weight {
+		(245785000 as Weight)
+			.saturating_add((428816000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_weight_to_fee(r: u32, ) -> Weight {
+		(247362000 as Weight)
+			.saturating_add((917337000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_gas(r: u32, ) -> Weight {
+		(237524000 as Weight)
+			.saturating_add((236781000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_input(r: u32, ) -> Weight {
+		(236094000 as Weight)
+			.saturating_add((4592000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_input_per_kb(n: u32, ) -> Weight {
+		(241377000 as Weight)
+			.saturating_add((262000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_return(r: u32, ) -> Weight {
+		(235915000 as Weight)
+			.saturating_add((3917000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_return_per_kb(n: u32, ) -> Weight {
+		(237249000 as Weight)
+			.saturating_add((498000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_terminate(r: u32, ) -> Weight {
+		(244138000 as Weight)
+			.saturating_add((1129104000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_restore_to(r: u32, ) -> Weight {
+		(276487000 as Weight)
+			.saturating_add((2473198000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn ext_restore_to_per_delta(d: u32, ) -> Weight {
+		(3072509000 as Weight)
+			.saturating_add((3519302000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn ext_random(r: u32, ) -> Weight {
+		(241203000 as Weight)
+			.saturating_add((1389018000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_debug_message(r: u32, ) -> Weight {
+		(244136000 as Weight)
+			.saturating_add((429874000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_deposit_event(r: u32, ) -> Weight {
+		(239872000 as Weight)
+			.saturating_add((1851246000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_deposit_event_per_topic_and_kb(t: u32, n: u32, ) -> Weight {
+		(2411572000 as Weight)
+			.saturating_add((1127043000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((11587000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_set_rent_allowance(r: u32, ) -> Weight {
+		(245047000 as Weight)
+			.saturating_add((1235874000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn ext_set_storage(r: u32, ) -> Weight {
+		(246305000 as Weight)
+			.saturating_add((9782443000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_set_storage_per_kb(n: u32, ) -> Weight {
+		(9965316000 as Weight)
+			.saturating_add((788000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn ext_clear_storage(r: u32, ) -> Weight {
+		(243978000 as Weight)
+			.saturating_add((9315618000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_get_storage(r: u32, ) -> Weight {
+		(246711000 as Weight)
+			.saturating_add((1982615000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_get_storage_per_kb(n: u32, ) -> Weight {
+		(1220684000 as Weight)
+			.saturating_add((621000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(105 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_transfer(r: u32, ) -> Weight {
+		(251462000 as Weight)
+			.saturating_add((8642381000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn ext_call(r: u32, ) -> Weight {
+		(267058000 as Weight)
+			.saturating_add((11235218000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_call_per_input_output_kb(i: u32, o: u32, ) -> Weight {
+		(11606424000 as Weight)
+			.saturating_add((30113000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((19852000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(DbWeight::get().reads(105 as Weight))
+			.saturating_add(DbWeight::get().writes(101 as Weight))
+	}
+	fn ext_instantiate(r: u32, ) -> Weight {
+		(302114000 as Weight)
+			.saturating_add((24691112000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn ext_instantiate_per_input_output_kb(i: u32, o: u32, ) -> Weight {
+		(25154608000 as Weight)
+			.saturating_add((31046000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((20137000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(DbWeight::get().reads(207 as Weight))
+			.saturating_add(DbWeight::get().writes(204 as Weight))
+	}
+	fn ext_hash_sha2_256(r: u32, ) -> Weight {
+		(239517000 as Weight)
+			.saturating_add((407219000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_hash_sha2_256_per_kb(n: u32, ) -> Weight {
+		(652714000 as Weight)
+			.saturating_add((1566000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_hash_keccak_256(r: u32, ) -> Weight {
+		(239517000 as Weight)
+			.saturating_add((417352000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_hash_keccak_256_per_kb(n: u32, ) -> Weight {
+		(652714000 as Weight)
+			.saturating_add((2098000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_hash_blake2_256(r: u32, ) -> Weight {
+		(239517000 as Weight)
+			.saturating_add((419736000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_hash_blake2_256_per_kb(n: u32, ) -> Weight {
+		(652714000 as Weight)
+			.saturating_add((850000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_hash_blake2_128(r: u32, ) -> Weight {
+		(239517000 as Weight)
+			.saturating_add((411308000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn ext_hash_blake2_128_per_kb(n: u32, ) -> Weight {
+		(652714000 as Weight)
+			.saturating_add((851000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn instr_const(r: u32, ) -> Weight {
+		(20513000 as Weight)
+			.saturating_add((3350000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_nop(r: u32, ) -> Weight {
+		(20480000 as Weight)
+			.saturating_add((1158000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_control_flow(r: u32, ) -> Weight {
+		(20610000 as Weight)
+			.saturating_add((5112000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_local(r: u32, ) -> Weight {
+		(20497000 as Weight)
+			.saturating_add((3872000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_load(r: u32, ) -> Weight {
+		(20556000 as Weight)
+			.saturating_add((8426000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_store(r: u32, ) -> Weight {
+		(20631000 as Weight)
+			.saturating_add((9812000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_integer_comparison(r: u32, ) -> Weight {
+		(20592000 as Weight)
+			.saturating_add((6783000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_bit(r: u32, ) -> Weight {
+		(20528000 as Weight)
+			.saturating_add((6641000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_add(r: u32, ) -> Weight {
+		(20545000 as Weight)
+			.saturating_add((6702000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_mul(r: u32, ) -> Weight {
+		(20571000 as Weight)
+			.saturating_add((6957000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_div(r: u32, ) -> Weight {
+		(20604000 as Weight)
+			.saturating_add((9311000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_conversion(r: u32, ) -> Weight {
+		(20583000 as Weight)
+			.saturating_add((5104000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_current_memory(r: u32, ) -> Weight {
+		(20538000 as Weight)
+			.saturating_add((4233000 as Weight).saturating_mul(r as Weight))
+	}
+	fn instr_grow_memory(r: u32, ) -> Weight {
+		(21577000 as Weight)
+			.saturating_add((1436032000 as Weight).saturating_mul(r as Weight))
+	}
+}
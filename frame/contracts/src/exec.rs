@@ -20,7 +20,7 @@ use crate::{
 	gas::{Gas, GasMeter, Token}, rent, storage, Error, ContractInfoOf
 };
 use bitflags::bitflags;
-use sp_std::prelude::*;
+use sp_std::{prelude::*, cell::RefCell};
 use sp_runtime::traits::{Bounded, Zero, Convert, Saturating};
 use frame_support::{
 	dispatch::DispatchError,
@@ -225,6 +225,13 @@ pub trait Ext {
 
 	/// Returns the price for the specified amount of weight.
 	fn get_weight_price(&self, weight: Weight) -> BalanceOf<Self::T>;
+
+	/// Append a string to the debug buffer.
+	///
+	/// Returns `false` if debug messages are not collected for this execution which
+	/// is always the case when executing on-chain. A message which does not fit into
+	/// the buffer anymore, as bounded by the schedule, is dropped.
+	fn append_debug_buffer(&mut self, msg: &str) -> bool;
 }
 
 /// Loader is a companion of the `Vm` trait. It loads an appropriate abstract
@@ -274,8 +281,8 @@ impl<T: Trait> Token<T> for ExecFeeToken {
 	#[inline]
 	fn calculate_amount(&self, metadata: &Config<T>) -> Gas {
 		match *self {
-			ExecFeeToken::Call => metadata.schedule.call_base_cost,
-			ExecFeeToken::Instantiate => metadata.schedule.instantiate_base_cost,
+			ExecFeeToken::Call => metadata.schedule.host_fn_weights.call,
+			ExecFeeToken::Instantiate => metadata.schedule.host_fn_weights.instantiate,
 		}
 	}
 }
@@ -290,6 +297,10 @@ pub struct ExecutionContext<'a, T: Trait + 'a, V, L> {
 	pub loader: &'a L,
 	pub timestamp: MomentOf<T>,
 	pub block_number: T::BlockNumber,
+	/// Buffer that collects the messages written by `ext_debug_message`.
+	///
+	/// It is only ever set for dry-runs requested through the runtime API.
+	pub debug_message: Option<&'a RefCell<Vec<u8>>>,
 }
 
 impl<'a, T, E, V, L> ExecutionContext<'a, T, V, L>
@@ -313,6 +324,17 @@ where
 			loader: &loader,
 			timestamp: T::Time::now(),
 			block_number: <frame_system::Module<T>>::block_number(),
+			debug_message: None,
+		}
+	}
+
+	/// Collect the debug messages of all contracts executed by this context into `buffer`.
+	///
+	/// This must not be used for on-chain execution.
+	pub fn with_debug_message(self, buffer: &'a RefCell<Vec<u8>>) -> Self {
+		ExecutionContext {
+			debug_message: Some(buffer),
+			.. self
 		}
 	}
 
@@ -329,6 +351,7 @@ where
 			loader: self.loader,
 			timestamp: self.timestamp.clone(),
 			block_number: self.block_number.clone(),
+			debug_message: self.debug_message,
 		}
 	}
 
@@ -539,8 +562,8 @@ impl<T: Trait> Token<T> for TransferFeeToken {
 	#[inline]
 	fn calculate_amount(&self, metadata: &Config<T>) -> Gas {
 		match self.kind {
-			TransferFeeKind::ContractInstantiate => metadata.schedule.instantiate_cost,
-			TransferFeeKind::Transfer => metadata.schedule.transfer_cost,
+			TransferFeeKind::ContractInstantiate | TransferFeeKind::Transfer =>
+				metadata.schedule.host_fn_weights.transfer,
 		}
 	}
 }
@@ -845,6 +868,19 @@ where
 	fn get_weight_price(&self, weight: Weight) -> BalanceOf<Self::T> {
 		T::WeightPrice::convert(weight)
 	}
+
+	fn append_debug_buffer(&mut self, msg: &str) -> bool {
+		if let Some(buffer) = self.ctx.debug_message {
+			let mut buffer = buffer.borrow_mut();
+			let max_len = self.ctx.config.schedule.limits.debug_buffer_len as usize;
+			if buffer.len().saturating_add(msg.len()) <= max_len {
+				buffer.extend_from_slice(msg.as_bytes());
+			}
+			true
+		} else {
+			false
+		}
+	}
 }
 
 fn deposit_event<T: Trait>(
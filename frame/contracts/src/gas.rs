@@ -190,11 +190,25 @@ impl<T: Trait> GasMeter<T> {
 
 	/// Turn this GasMeter into a DispatchResult that contains the actually used gas.
 	pub fn into_dispatch_result<R, E>(self, result: Result<R, E>) -> DispatchResultWithPostInfo
+	where
+		E: Into<ExecError>,
+	{
+		self.into_dispatch_result_with_base(result, Zero::zero())
+	}
+
+	/// Same as `into_dispatch_result` but adds `base` to the actually used gas.
+	///
+	/// Used by dispatchables that perform work outside of the gas meter before executing.
+	pub fn into_dispatch_result_with_base<R, E>(
+		self,
+		result: Result<R, E>,
+		base: Gas,
+	) -> DispatchResultWithPostInfo
 	where
 		E: Into<ExecError>,
 	{
 		let post_info = PostDispatchInfo {
-			actual_weight: Some(self.gas_spent()),
+			actual_weight: Some(self.gas_spent().saturating_add(base)),
 			pays_fee: Default::default(),
 		};
 
@@ -59,9 +59,12 @@
 //! ### Dispatchable functions
 //!
 //! * `put_code` - Stores the given binary Wasm code into the chain's storage and returns its `code_hash`.
+//! A deposit proportional to the code size is reserved from the sender.
+//! * `remove_code` - Removes code that is no longer used by any contract and refunds the deposit.
 //! * `instantiate` - Deploys a new contract from the given `code_hash`, optionally transferring some balance.
 //! This instantiates a new smart contract account and calls its contract deploy handler to
 //! initialize the contract.
+//! * `instantiate_with_code` - Stores the given code and deploys a new contract from it in one go.
 //! * `call` - Makes a call to an account, optionally transferring some balance.
 //!
 //! ## Usage
@@ -86,6 +89,10 @@ mod exec;
 mod wasm;
 mod rent;
 mod benchmarking;
+mod schedule;
+mod default_weight;
+
+pub mod chain_extension;
 
 #[cfg(test)]
 mod tests;
@@ -96,11 +103,10 @@ use crate::wasm::{WasmLoader, WasmVm};
 pub use crate::gas::{Gas, GasMeter};
 pub use crate::exec::{ExecResult, ExecReturnValue};
 pub use crate::wasm::ReturnCode as RuntimeReturnCode;
+pub use crate::schedule::{Schedule, HostFnWeights, InstructionWeights, Limits};
 
-#[cfg(feature = "std")]
-use serde::{Serialize, Deserialize};
 use sp_core::crypto::UncheckedFrom;
-use sp_std::{prelude::*, marker::PhantomData, fmt::Debug};
+use sp_std::{prelude::*, marker::PhantomData, fmt::Debug, cell::RefCell};
 use codec::{Codec, Encode, Decode};
 use sp_runtime::{
 	traits::{
@@ -109,13 +115,15 @@ use sp_runtime::{
 	RuntimeDebug,
 };
 use frame_support::{
-	decl_module, decl_event, decl_storage, decl_error, ensure,
+	decl_module, decl_event, decl_storage, decl_error, ensure, transactional,
 	parameter_types, storage::child::ChildInfo,
-	dispatch::{DispatchResult, DispatchResultWithPostInfo},
-	traits::{OnUnbalanced, Currency, Get, Time, Randomness},
+	dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+	traits::{OnUnbalanced, Currency, ReservableCurrency, Get, Time, Randomness},
 };
 use frame_system::{ensure_signed, ensure_root};
-use pallet_contracts_primitives::{RentProjection, ContractAccessError};
+use pallet_contracts_primitives::{
+	RentProjection, ContractAccessError, ContractExecResult, ExecTrapReason,
+};
 use frame_support::weights::Weight;
 
 pub type CodeHash<T> = <T as frame_system::Trait>::Hash;
@@ -226,6 +234,23 @@ pub(crate) fn child_trie_info(trie_id: &[u8]) -> ChildInfo {
 	ChildInfo::new_default(trie_id)
 }
 
+pub type OwnerInfo<T> = RawOwnerInfo<<T as frame_system::Trait>::AccountId, BalanceOf<T>>;
+
+/// Information about the uploader of a contract code and its usage.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RawOwnerInfo<AccountId, Balance> {
+	/// The account that uploaded the code and from which the deposit was reserved.
+	pub owner: AccountId,
+	/// The balance that is reserved from the owner for as long as the code is stored.
+	#[codec(compact)]
+	pub deposit: Balance,
+	/// The number of alive contracts that use this code.
+	///
+	/// The code can only be removed when this drops to zero.
+	#[codec(compact)]
+	pub refcount: u64,
+}
+
 pub type TombstoneContractInfo<T> =
 	RawTombstoneContractInfo<<T as frame_system::Trait>::Hash, <T as frame_system::Trait>::Hashing>;
 
@@ -311,6 +336,74 @@ parameter_types! {
 	pub const DefaultMaxDepth: u32 = 32;
 	/// A reasonable default value for [`Trait::MaxValueSize`].
 	pub const DefaultMaxValueSize: u32 = 16_384;
+	/// A reasonable default value for [`Trait::CodeDepositPerByte`].
+	pub const DefaultCodeDepositPerByte: u32 = 1;
+}
+
+pub trait WeightInfo {
+	fn update_schedule() -> Weight;
+	fn put_code(n: u32, ) -> Weight;
+	fn remove_code() -> Weight;
+	fn instantiate() -> Weight;
+	fn instantiate_with_code(n: u32, ) -> Weight;
+	fn call() -> Weight;
+	fn claim_surcharge() -> Weight;
+	fn ext_caller(r: u32, ) -> Weight;
+	fn ext_address(r: u32, ) -> Weight;
+	fn ext_gas_left(r: u32, ) -> Weight;
+	fn ext_balance(r: u32, ) -> Weight;
+	fn ext_value_transferred(r: u32, ) -> Weight;
+	fn ext_minimum_balance(r: u32, ) -> Weight;
+	fn ext_tombstone_deposit(r: u32, ) -> Weight;
+	fn ext_rent_allowance(r: u32, ) -> Weight;
+	fn ext_block_number(r: u32, ) -> Weight;
+	fn ext_now(r: u32, ) -> Weight;
+	fn ext_weight_to_fee(r: u32, ) -> Weight;
+	fn ext_gas(r: u32, ) -> Weight;
+	fn ext_input(r: u32, ) -> Weight;
+	fn ext_input_per_kb(n: u32, ) -> Weight;
+	fn ext_return(r: u32, ) -> Weight;
+	fn ext_return_per_kb(n: u32, ) -> Weight;
+	fn ext_terminate(r: u32, ) -> Weight;
+	fn ext_restore_to(r: u32, ) -> Weight;
+	fn ext_restore_to_per_delta(d: u32, ) -> Weight;
+	fn ext_random(r: u32, ) -> Weight;
+	fn ext_debug_message(r: u32, ) -> Weight;
+	fn ext_deposit_event(r: u32, ) -> Weight;
+	fn ext_deposit_event_per_topic_and_kb(t: u32, n: u32, ) -> Weight;
+	fn ext_set_rent_allowance(r: u32, ) -> Weight;
+	fn ext_set_storage(r: u32, ) -> Weight;
+	fn ext_set_storage_per_kb(n: u32, ) -> Weight;
+	fn ext_clear_storage(r: u32, ) -> Weight;
+	fn ext_get_storage(r: u32, ) -> Weight;
+	fn ext_get_storage_per_kb(n: u32, ) -> Weight;
+	fn ext_transfer(r: u32, ) -> Weight;
+	fn ext_call(r: u32, ) -> Weight;
+	fn ext_call_per_input_output_kb(i: u32, o: u32, ) -> Weight;
+	fn ext_instantiate(r: u32, ) -> Weight;
+	fn ext_instantiate_per_input_output_kb(i: u32, o: u32, ) -> Weight;
+	fn ext_hash_sha2_256(r: u32, ) -> Weight;
+	fn ext_hash_sha2_256_per_kb(n: u32, ) -> Weight;
+	fn ext_hash_keccak_256(r: u32, ) -> Weight;
+	fn ext_hash_keccak_256_per_kb(n: u32, ) -> Weight;
+	fn ext_hash_blake2_256(r: u32, ) -> Weight;
+	fn ext_hash_blake2_256_per_kb(n: u32, ) -> Weight;
+	fn ext_hash_blake2_128(r: u32, ) -> Weight;
+	fn ext_hash_blake2_128_per_kb(n: u32, ) -> Weight;
+	fn instr_const(r: u32, ) -> Weight;
+	fn instr_nop(r: u32, ) -> Weight;
+	fn instr_control_flow(r: u32, ) -> Weight;
+	fn instr_local(r: u32, ) -> Weight;
+	fn instr_load(r: u32, ) -> Weight;
+	fn instr_store(r: u32, ) -> Weight;
+	fn instr_integer_comparison(r: u32, ) -> Weight;
+	fn instr_bit(r: u32, ) -> Weight;
+	fn instr_add(r: u32, ) -> Weight;
+	fn instr_mul(r: u32, ) -> Weight;
+	fn instr_div(r: u32, ) -> Weight;
+	fn instr_conversion(r: u32, ) -> Weight;
+	fn instr_current_memory(r: u32, ) -> Weight;
+	fn instr_grow_memory(r: u32, ) -> Weight;
 }
 
 pub trait Trait: frame_system::Trait {
@@ -318,7 +411,9 @@ pub trait Trait: frame_system::Trait {
 	type Randomness: Randomness<Self::Hash>;
 
 	/// The currency in which fees are paid and contract balances are held.
-	type Currency: Currency<Self::AccountId>;
+	///
+	/// It is also used to reserve the deposit for stored contract code.
+	type Currency: ReservableCurrency<Self::AccountId>;
 
 	/// The overarching event type.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
@@ -370,9 +465,22 @@ pub trait Trait: frame_system::Trait {
 	/// The maximum size of a storage value in bytes.
 	type MaxValueSize: Get<u32>;
 
+	/// The balance that is reserved from the uploader per byte of stored contract code.
+	///
+	/// The deposit is returned when the code is removed with `remove_code`.
+	type CodeDepositPerByte: Get<BalanceOf<Self>>;
+
 	/// Used to answer contracts's queries regarding the current weight price. This is **not**
 	/// used to calculate the actual fee and is only for informational purposes.
 	type WeightPrice: Convert<Weight, BalanceOf<Self>>;
+
+	/// Type that allows the runtime authors to add new host functions for a contract to call.
+	type ChainExtension: chain_extension::ChainExtension<Self>;
+
+	/// Weight information for the extrinsics and host functions of this pallet.
+	///
+	/// The default [`Schedule`] is derived from these weights.
+	type WeightInfo: WeightInfo;
 }
 
 /// Simple contract address determiner.
@@ -445,6 +553,14 @@ decl_error! {
 		DecodingFailed,
 		/// Contract trapped during execution.
 		ContractTrapped,
+		/// The contract called into the chain extension but the chain does not provide one.
+		NoChainExtension,
+		/// The code can not be removed because at least one contract still uses it.
+		CodeInUse,
+		/// Only the account that uploaded the code is allowed to remove it.
+		NotCodeOwner,
+		/// The message passed to `ext_debug_message` is not valid UTF-8.
+		DebugMessageInvalidUTF8,
 	}
 }
 
@@ -493,39 +609,51 @@ decl_module! {
 		/// The maximum size of a storage value in bytes. A reasonable default is 16 KiB.
 		const MaxValueSize: u32 = T::MaxValueSize::get();
 
+		/// The balance that is reserved from the uploader per byte of stored contract code.
+		const CodeDepositPerByte: BalanceOf<T> = T::CodeDepositPerByte::get();
+
 		fn deposit_event() = default;
 
 		/// Updates the schedule for metering contracts.
 		///
 		/// The schedule must have a greater version than the stored schedule.
-		#[weight = 0]
-		pub fn update_schedule(origin, schedule: Schedule) -> DispatchResult {
+		#[weight = T::WeightInfo::update_schedule()]
+		pub fn update_schedule(origin, schedule: Schedule<T>) -> DispatchResult {
 			ensure_root(origin)?;
 			if <Module<T>>::current_schedule().version >= schedule.version {
 				Err(Error::<T>::InvalidScheduleVersion)?
 			}
 
 			Self::deposit_event(RawEvent::ScheduleUpdated(schedule.version));
-			CurrentSchedule::put(schedule);
+			<CurrentSchedule<T>>::put(schedule);
 
 			Ok(())
 		}
 
 		/// Stores the given binary Wasm code into the chain's storage and returns its `codehash`.
 		/// You can instantiate contracts only with stored code.
-		#[weight = Module::<T>::calc_code_put_costs(&code)]
+		///
+		/// A deposit of `CodeDepositPerByte` for every byte of `code` is reserved from the
+		/// origin which becomes the owner of the code. Storing code that is already stored
+		/// does not take another deposit.
+		#[weight = T::WeightInfo::put_code(code.len() as u32 / 1024)]
 		pub fn put_code(
 			origin,
 			code: Vec<u8>
 		) -> DispatchResult {
-			ensure_signed(origin)?;
-			let schedule = <Module<T>>::current_schedule();
-			ensure!(code.len() as u32 <= schedule.max_code_size, Error::<T>::CodeTooLarge);
-			let result = wasm::save_code::<T>(code, &schedule);
-			if let Ok(code_hash) = result {
-				Self::deposit_event(RawEvent::CodeStored(code_hash));
-			}
-			result.map(|_| ()).map_err(Into::into)
+			let origin = ensure_signed(origin)?;
+			Self::store_code(&origin, code).map(|_| ())
+		}
+
+		/// Removes the code stored under `code_hash` and refunds the deposit to its owner.
+		///
+		/// Only the owner of the code can remove it and only when no contract uses it anymore.
+		#[weight = T::WeightInfo::remove_code()]
+		pub fn remove_code(origin, code_hash: CodeHash<T>) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			wasm::remove_code::<T>(&origin, &code_hash)?;
+			Self::deposit_event(RawEvent::CodeRemoved(code_hash));
+			Ok(())
 		}
 
 		/// Makes a call to an account, optionally transferring some balance.
@@ -547,7 +675,7 @@ decl_module! {
 			let dest = T::Lookup::lookup(dest)?;
 			let mut gas_meter = GasMeter::new(gas_limit);
 
-			let result = Self::execute_wasm(origin, &mut gas_meter, |ctx, gas_meter| {
+			let result = Self::execute_wasm(origin, &mut gas_meter, None, |ctx, gas_meter| {
 				ctx.call(dest, value, gas_meter, data)
 			});
 			gas_meter.into_dispatch_result(result)
@@ -574,19 +702,46 @@ decl_module! {
 			let origin = ensure_signed(origin)?;
 			let mut gas_meter = GasMeter::new(gas_limit);
 
-			let result = Self::execute_wasm(origin, &mut gas_meter, |ctx, gas_meter| {
+			let result = Self::execute_wasm(origin, &mut gas_meter, None, |ctx, gas_meter| {
 				ctx.instantiate(endowment, gas_meter, &code_hash, data)
 					.map(|(_address, output)| output)
 			});
 			gas_meter.into_dispatch_result(result)
 		}
 
+		/// Stores the supplied `code` and instantiates a new contract from it in one go.
+		///
+		/// The code is stored the same way as `put_code` does it, including the deposit.
+		/// Afterwards the contract is instantiated as described for `instantiate`. Nothing is
+		/// stored if the instantiation fails.
+		#[weight = T::WeightInfo::instantiate_with_code(code.len() as u32 / 1024)
+			.saturating_add(*gas_limit)]
+		#[transactional]
+		pub fn instantiate_with_code(
+			origin,
+			#[compact] endowment: BalanceOf<T>,
+			#[compact] gas_limit: Gas,
+			code: Vec<u8>,
+			data: Vec<u8>
+		) -> DispatchResultWithPostInfo {
+			let origin = ensure_signed(origin)?;
+			let code_weight = T::WeightInfo::instantiate_with_code(code.len() as u32 / 1024);
+			let code_hash = Self::store_code(&origin, code)?;
+			let mut gas_meter = GasMeter::new(gas_limit);
+
+			let result = Self::execute_wasm(origin, &mut gas_meter, None, |ctx, gas_meter| {
+				ctx.instantiate(endowment, gas_meter, &code_hash, data)
+					.map(|(_address, output)| output)
+			});
+			gas_meter.into_dispatch_result_with_base(result, code_weight)
+		}
+
 		/// Allows block producers to claim a small reward for evicting a contract. If a block producer
 		/// fails to do so, a regular users will be allowed to claim the reward.
 		///
 		/// If contract is not evicted as a result of this call, no actions are taken and
 		/// the sender is not eligible for the reward.
-		#[weight = 0]
+		#[weight = T::WeightInfo::claim_surcharge()]
 		fn claim_surcharge(origin, dest: T::AccountId, aux_sender: Option<T::AccountId>) {
 			let origin = origin.into();
 			let (signed, rewarded) = match (origin, aux_sender) {
@@ -633,13 +788,73 @@ impl<T: Trait> Module<T> {
 	) -> (ExecResult, Gas) {
 		let mut gas_meter = GasMeter::new(gas_limit);
 		(
-			Self::execute_wasm(origin, &mut gas_meter, |ctx, gas_meter| {
+			Self::execute_wasm(origin, &mut gas_meter, None, |ctx, gas_meter| {
 				ctx.call(dest, value, gas_meter, input_data)
 			}),
 			gas_meter.gas_spent(),
 		)
 	}
 
+	/// Perform a call to a specified contract and collect diagnostic information about it.
+	///
+	/// In addition to what `bare_call` returns, this records the messages contracts write
+	/// through `ext_debug_message`, the events emitted during the call, the deposit the
+	/// callee needs for its storage and the reason of a failure.
+	///
+	/// This is meant to be used by the `call` runtime API only. It must never be used
+	/// during block execution.
+	pub fn dry_run_call(
+		origin: T::AccountId,
+		dest: T::AccountId,
+		value: BalanceOf<T>,
+		gas_limit: Gas,
+		input_data: Vec<u8>,
+	) -> ContractExecResult<BalanceOf<T>> {
+		let event_count = <frame_system::Module<T>>::event_count() as usize;
+		let debug_message = RefCell::new(Vec::new());
+		let mut gas_meter = GasMeter::new(gas_limit);
+		let result = Self::execute_wasm(
+			origin,
+			&mut gas_meter,
+			Some(&debug_message),
+			|ctx, gas_meter| ctx.call(dest.clone(), value, gas_meter, input_data),
+		);
+
+		let storage_deposit = ContractInfoOf::<T>::get(&dest)
+			.and_then(|c| c.get_alive())
+			.map(|c| rent::rent_exempt_deposit(&c))
+			.unwrap_or_else(Zero::zero);
+		let events = <frame_system::Module<T>>::events()
+			.into_iter()
+			.skip(event_count)
+			.map(|record| record.event.encode())
+			.collect();
+
+		ContractExecResult {
+			gas_consumed: gas_meter.gas_spent(),
+			storage_deposit,
+			events,
+			debug_message: debug_message.into_inner(),
+			result: result
+				.map(|v| pallet_contracts_primitives::ExecReturnValue {
+					flags: v.flags.bits(),
+					data: v.data,
+				})
+				.map_err(|e| ExecTrapReason {
+					error: e.error,
+					message: match e.error {
+						DispatchError::Module { message, .. } => message,
+						DispatchError::Other(message) => Some(message),
+						_ => None,
+					}.map(|m| m.as_bytes().to_vec()),
+					in_callee: match e.origin {
+						exec::ErrorOrigin::Callee => true,
+						exec::ErrorOrigin::Caller => false,
+					},
+				}),
+		}
+	}
+
 	/// Query storage of a specified contract under a specified key.
 	pub fn get_storage(
 		address: T::AccountId,
@@ -662,19 +877,28 @@ impl<T: Trait> Module<T> {
 }
 
 impl<T: Trait> Module<T> {
-	fn calc_code_put_costs(code: &Vec<u8>) -> Gas {
-		<Module<T>>::current_schedule().put_code_per_byte_cost.saturating_mul(code.len() as Gas)
+	/// Stores `code` with `origin` as its owner and returns the hash of the code.
+	fn store_code(origin: &T::AccountId, code: Vec<u8>) -> Result<CodeHash<T>, DispatchError> {
+		let schedule = <Module<T>>::current_schedule();
+		ensure!(code.len() as u32 <= schedule.limits.code_size, Error::<T>::CodeTooLarge);
+		let code_hash = wasm::save_code::<T>(code, &schedule, origin)?;
+		Self::deposit_event(RawEvent::CodeStored(code_hash));
+		Ok(code_hash)
 	}
 
 	fn execute_wasm(
 		origin: T::AccountId,
 		gas_meter: &mut GasMeter<T>,
-		func: impl FnOnce(&mut ExecutionContext<T, WasmVm, WasmLoader>, &mut GasMeter<T>) -> ExecResult,
+		debug_message: Option<&RefCell<Vec<u8>>>,
+		func: impl FnOnce(&mut ExecutionContext<T, WasmVm<T>, WasmLoader<T>>, &mut GasMeter<T>) -> ExecResult,
 	) -> ExecResult {
 		let cfg = Config::preload();
 		let vm = WasmVm::new(&cfg.schedule);
 		let loader = WasmLoader::new(&cfg.schedule);
 		let mut ctx = ExecutionContext::top_level(origin, &cfg, &vm, &loader);
+		if let Some(buffer) = debug_message {
+			ctx = ctx.with_debug_message(buffer);
+		}
 		func(&mut ctx, gas_meter)
 	}
 }
@@ -713,6 +937,10 @@ decl_event! {
 		/// [code_hash]
 		CodeStored(Hash),
 
+		/// Code with the specified hash has been removed and its deposit was refunded.
+		/// [code_hash]
+		CodeRemoved(Hash),
+
 		/// Triggered when the current [schedule] is updated.
 		ScheduleUpdated(u32),
 
@@ -725,11 +953,13 @@ decl_event! {
 decl_storage! {
 	trait Store for Module<T: Trait> as Contracts {
 		/// Current cost schedule for contracts.
-		CurrentSchedule get(fn current_schedule) config(): Schedule = Schedule::default();
+		CurrentSchedule get(fn current_schedule) config(): Schedule<T> = Default::default();
 		/// A mapping from an original code hash to the original code, untouched by instrumentation.
 		pub PristineCode: map hasher(identity) CodeHash<T> => Option<Vec<u8>>;
 		/// A mapping between an original code hash and instrumented wasm code, ready for execution.
 		pub CodeStorage: map hasher(identity) CodeHash<T> => Option<wasm::PrefabWasmModule>;
+		/// The owner, deposit and number of users of a stored code.
+		pub OwnerInfoOf: map hasher(identity) CodeHash<T> => Option<OwnerInfo<T>>;
 		/// The subtrie counter.
 		pub AccountCounter: u64 = 0;
 		/// The code associated with a given account.
@@ -744,7 +974,7 @@ decl_storage! {
 /// We assume that these values can't be changed in the
 /// course of transaction execution.
 pub struct Config<T: Trait> {
-	pub schedule: Schedule,
+	pub schedule: Schedule<T>,
 	pub existential_deposit: BalanceOf<T>,
 	pub tombstone_deposit: BalanceOf<T>,
 	pub max_depth: u32,
@@ -781,113 +1011,3 @@ impl<T: Trait> Config<T> {
 		T::Currency::minimum_balance().saturating_add(T::TombstoneDeposit::get())
 	}
 }
-
-/// Definition of the cost schedule and other parameterizations for wasm vm.
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
-pub struct Schedule {
-	/// Version of the schedule.
-	pub version: u32,
-
-	/// Cost of putting a byte of code into storage.
-	pub put_code_per_byte_cost: Gas,
-
-	/// Gas cost of a growing memory by single page.
-	pub grow_mem_cost: Gas,
-
-	/// Gas cost of a regular operation.
-	pub regular_op_cost: Gas,
-
-	/// Gas cost per one byte returned.
-	pub return_data_per_byte_cost: Gas,
-
-	/// Gas cost to deposit an event; the per-byte portion.
-	pub event_data_per_byte_cost: Gas,
-
-	/// Gas cost to deposit an event; the cost per topic.
-	pub event_per_topic_cost: Gas,
-
-	/// Gas cost to deposit an event; the base.
-	pub event_base_cost: Gas,
-
-	/// Base gas cost to call into a contract.
-	pub call_base_cost: Gas,
-
-	/// Base gas cost to instantiate a contract.
-	pub instantiate_base_cost: Gas,
-
-	/// Base gas cost to dispatch a runtime call.
-	pub dispatch_base_cost: Gas,
-
-	/// Gas cost per one byte read from the sandbox memory.
-	pub sandbox_data_read_cost: Gas,
-
-	/// Gas cost per one byte written to the sandbox memory.
-	pub sandbox_data_write_cost: Gas,
-
-	/// Cost for a simple balance transfer.
-	pub transfer_cost: Gas,
-
-	/// Cost for instantiating a new contract.
-	pub instantiate_cost: Gas,
-
-	/// The maximum number of topics supported by an event.
-	pub max_event_topics: u32,
-
-	/// Maximum allowed stack height.
-	///
-	/// See https://wiki.parity.io/WebAssembly-StackHeight to find out
-	/// how the stack frame cost is calculated.
-	pub max_stack_height: u32,
-
-	/// Maximum number of memory pages allowed for a contract.
-	pub max_memory_pages: u32,
-
-	/// Maximum allowed size of a declared table.
-	pub max_table_size: u32,
-
-	/// Whether the `ext_println` function is allowed to be used contracts.
-	/// MUST only be enabled for `dev` chains, NOT for production chains
-	pub enable_println: bool,
-
-	/// The maximum length of a subject used for PRNG generation.
-	pub max_subject_len: u32,
-
-	/// The maximum length of a contract code in bytes. This limit applies to the uninstrumented
-	// and pristine form of the code as supplied to `put_code`.
-	pub max_code_size: u32,
-}
-
-// 500 (2 instructions per nano second on 2GHZ) * 1000x slowdown through wasmi
-// This is a wild guess and should be viewed as a rough estimation.
-// Proper benchmarks are needed before this value and its derivatives can be used in production.
-const WASM_INSTRUCTION_COST: Gas = 500_000;
-
-impl Default for Schedule {
-	fn default() -> Schedule {
-		Schedule {
-			version: 0,
-			put_code_per_byte_cost: WASM_INSTRUCTION_COST,
-			grow_mem_cost: WASM_INSTRUCTION_COST,
-			regular_op_cost: WASM_INSTRUCTION_COST,
-			return_data_per_byte_cost: WASM_INSTRUCTION_COST,
-			event_data_per_byte_cost: WASM_INSTRUCTION_COST,
-			event_per_topic_cost: WASM_INSTRUCTION_COST,
-			event_base_cost: WASM_INSTRUCTION_COST,
-			call_base_cost: 135 * WASM_INSTRUCTION_COST,
-			dispatch_base_cost: 135 * WASM_INSTRUCTION_COST,
-			instantiate_base_cost: 175 * WASM_INSTRUCTION_COST,
-			sandbox_data_read_cost: WASM_INSTRUCTION_COST,
-			sandbox_data_write_cost: WASM_INSTRUCTION_COST,
-			transfer_cost: 100 * WASM_INSTRUCTION_COST,
-			instantiate_cost: 200 * WASM_INSTRUCTION_COST,
-			max_event_topics: 4,
-			max_stack_height: 64 * 1024,
-			max_memory_pages: 16,
-			max_table_size: 16 * 1024,
-			enable_println: false,
-			max_subject_len: 32,
-			max_code_size: 512 * 1024,
-		}
-	}
-}
@@ -18,7 +18,7 @@
 
 use crate::{
 	AliveContractInfo, BalanceOf, ContractInfo, ContractInfoOf, Module, RawEvent,
-	TombstoneContractInfo, Trait, CodeHash, Config
+	TombstoneContractInfo, Trait, CodeHash, CodeStorage, Config, wasm
 };
 use sp_std::prelude::*;
 use sp_io::hashing::blake2_256;
@@ -107,6 +107,19 @@ fn compute_fee_per_block<T: Trait>(
 		.unwrap_or_else(|| <BalanceOf<T>>::max_value())
 }
 
+/// Returns the free balance the contract needs to hold in order to pay no rent at all.
+///
+/// This is the inverse of the storage rent deposit that is accounted for by
+/// `compute_fee_per_block`.
+pub fn rent_exempt_deposit<T: Trait>(contract: &AliveContractInfo<T>) -> BalanceOf<T> {
+	<BalanceOf<T>>::from(
+		contract.storage_size
+			.saturating_add(T::StorageSizeOffset::get())
+			.saturating_add(contract.empty_pair_count),
+	)
+	.saturating_mul(T::RentDepositOffset::get())
+}
+
 /// Returns amount of funds available to consume by rent mechanism.
 ///
 /// Rent mechanism cannot consume more than `rent_allowance` set by the contract and it cannot make
@@ -227,6 +240,7 @@ fn enact_verdict<T: Trait>(
 		Verdict::Exempt => return Some(ContractInfo::Alive(alive_contract_info)),
 		Verdict::Kill => {
 			<ContractInfoOf<T>>::remove(account);
+			wasm::decrement_refcount::<T>(&alive_contract_info.code_hash);
 			child::kill_storage(
 				&alive_contract_info.child_trie_info(),
 			);
@@ -249,6 +263,7 @@ fn enact_verdict<T: Trait>(
 			);
 			let tombstone_info = ContractInfo::Tombstone(tombstone);
 			<ContractInfoOf<T>>::insert(account, &tombstone_info);
+			wasm::decrement_refcount::<T>(&alive_contract_info.code_hash);
 
 			child::kill_storage(
 				&alive_contract_info.child_trie_info(),
@@ -432,6 +447,10 @@ pub fn restore_to<T: Trait>(
 		.and_then(|c| c.get_tombstone())
 		.ok_or("Cannot restore to inexisting or alive contract")?;
 
+	if !<CodeStorage<T>>::contains_key(&code_hash) {
+		return Err("Cannot restore to inexisting code");
+	}
+
 	let last_write = if !delta.is_empty() {
 		Some(current_block)
 	} else {
@@ -466,6 +485,8 @@ pub fn restore_to<T: Trait>(
 		.map(|(_, value)| value.len() as u32)
 		.sum::<u32>();
 
+	wasm::decrement_refcount::<T>(&origin_contract.code_hash);
+	wasm::increment_refcount::<T>(&code_hash);
 	<ContractInfoOf<T>>::remove(&origin);
 	<ContractInfoOf<T>>::insert(&dest, ContractInfo::Alive(AliveContractInfo::<T> {
 		trie_id: origin_contract.trie_id,
@@ -0,0 +1,567 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate. If not, see <http://www.gnu.org/licenses/>.
+
+//! This module contains the cost schedule and supporting code that constructs a
+//! sane default schedule from a `WeightInfo` implementation.
+
+use crate::{Trait, WeightInfo};
+
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use pwasm_utils::rules::{self, InstructionType, Metering};
+use frame_support::weights::Weight;
+use sp_std::{marker::PhantomData, fmt, collections::btree_map::BTreeMap};
+use codec::{Encode, Decode};
+
+/// How many API calls are executed in a single batch. The reason for increasing the amount
+/// of API calls in batches (per benchmark component increase) is so that the linear regression
+/// has an easier time determining the contribution of that component.
+pub const API_BENCHMARK_BATCH_SIZE: u32 = 100;
+
+/// How many instructions are executed in a single batch. The reasoning is the same
+/// as for `API_BENCHMARK_BATCH_SIZE`.
+pub const INSTR_BENCHMARK_BATCH_SIZE: u32 = 1_000;
+
+/// Definition of the cost schedule and other parameterizations for the wasm vm.
+///
+/// Its fields are public and can thus be customized, however, the [`Default`] implementation
+/// derives the weights from the benchmark results of the runtime's `WeightInfo`. Changing
+/// any weight by hand is therefore discouraged.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "", deserialize = "")))]
+#[derive(Clone, Encode, Decode, PartialEq, Eq)]
+pub struct Schedule<T: Trait> {
+	/// Version of the schedule.
+	pub version: u32,
+
+	/// Whether the `ext_println` function is allowed to be used contracts.
+	/// MUST only be enabled for `dev` chains, NOT for production chains
+	pub enable_println: bool,
+
+	/// Describes the upper limits on various metrics.
+	pub limits: Limits,
+
+	/// The weights for individual wasm instruction classes.
+	pub instruction_weights: InstructionWeights<T>,
+
+	/// The weights for each imported function a contract is allowed to call.
+	pub host_fn_weights: HostFnWeights<T>,
+}
+
+/// Describes the upper limits on various metrics.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Encode, Decode, PartialEq, Eq, sp_runtime::RuntimeDebug)]
+pub struct Limits {
+	/// The maximum number of topics supported by an event.
+	pub event_topics: u32,
+
+	/// Maximum allowed stack height.
+	///
+	/// See https://wiki.parity.io/WebAssembly-StackHeight to find out
+	/// how the stack frame cost is calculated.
+	pub stack_height: u32,
+
+	/// Maximum number of memory pages allowed for a contract.
+	pub memory_pages: u32,
+
+	/// Maximum allowed size of a declared table.
+	pub table_size: u32,
+
+	/// The maximum length of a subject used for PRNG generation.
+	pub subject_len: u32,
+
+	/// The maximum length of a contract code in bytes. This limit applies to the uninstrumented
+	/// and pristine form of the code as supplied to `put_code`.
+	pub code_size: u32,
+
+	/// The maximum length in bytes of the debug messages collected during a single execution.
+	pub debug_buffer_len: u32,
+}
+
+/// Describes the weight for each class of wasm instructions.
+///
+/// The classes are the ones used by the gas instrumentation of `pwasm-utils`. Each weight
+/// is derived from a benchmark that executes a batch of instructions of the respective
+/// class. Instructions that are needed to feed the benchmarked instruction with operands
+/// (or to drop its result) are subtracted from the measurement.
+///
+/// Floating point instructions are not listed because contracts that use floating point
+/// types are rejected when they are put on chain.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "", deserialize = "")))]
+#[derive(Clone, Encode, Decode, PartialEq, Eq)]
+pub struct InstructionWeights<T: Trait> {
+	/// Weight of `i32.const` and `i64.const`.
+	pub r#const: u32,
+
+	/// Weight of `get_local`, `set_local`, `tee_local`, `get_global` and `set_global`.
+	pub local: u32,
+
+	/// Weight of the integer load instructions.
+	pub load: u32,
+
+	/// Weight of the integer store instructions.
+	pub store: u32,
+
+	/// Weight of control flow instructions like `block`, `br`, `call` or `drop`.
+	pub control_flow: u32,
+
+	/// Weight of the integer comparison instructions.
+	pub integer_comparison: u32,
+
+	/// Weight of the bitwise instructions (`and`, `or`, `xor`, shifts, rotates, `clz`, ...).
+	pub bit: u32,
+
+	/// Weight of `add` and `sub`.
+	pub add: u32,
+
+	/// Weight of `mul`.
+	pub mul: u32,
+
+	/// Weight of the division and remainder instructions.
+	pub div: u32,
+
+	/// Weight of `i32.wrap/i64` and `i64.extend_s/i32` or `i64.extend_u/i32`.
+	pub conversion: u32,
+
+	/// Weight of `current_memory`.
+	pub current_memory: u32,
+
+	/// Weight of `grow_memory` per page that is requested.
+	pub grow_memory: u32,
+
+	/// Weight of `nop`. Also used for `unreachable` which traps right away.
+	pub nop: u32,
+
+	/// The type parameter is used in the default implementation.
+	#[codec(skip)]
+	pub _phantom: PhantomData<T>,
+}
+
+/// Describes the weight for each imported function that a contract is allowed to call.
+///
+/// The weights of `ext_call`, `ext_instantiate` and `ext_transfer` are not charged by the
+/// host functions themselves but by the execution context, so that the top level
+/// `call` and `instantiate` dispatchables are charged the same way.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "", deserialize = "")))]
+#[derive(Clone, Encode, Decode, PartialEq, Eq)]
+pub struct HostFnWeights<T: Trait> {
+	/// Weight of calling `ext_caller`.
+	pub caller: Weight,
+
+	/// Weight of calling `ext_address`.
+	pub address: Weight,
+
+	/// Weight of calling `ext_gas_left`.
+	pub gas_left: Weight,
+
+	/// Weight of calling `ext_balance`.
+	pub balance: Weight,
+
+	/// Weight of calling `ext_value_transferred`.
+	pub value_transferred: Weight,
+
+	/// Weight of calling `ext_minimum_balance`.
+	pub minimum_balance: Weight,
+
+	/// Weight of calling `ext_tombstone_deposit`.
+	pub tombstone_deposit: Weight,
+
+	/// Weight of calling `ext_rent_allowance`.
+	pub rent_allowance: Weight,
+
+	/// Weight of calling `ext_block_number`.
+	pub block_number: Weight,
+
+	/// Weight of calling `ext_now`.
+	pub now: Weight,
+
+	/// Weight of calling `ext_weight_to_fee`.
+	pub weight_to_fee: Weight,
+
+	/// Weight of calling `gas`. The weight of the metered block is added on top.
+	pub gas: Weight,
+
+	/// Weight of calling `ext_input`.
+	pub input: Weight,
+
+	/// Weight per input byte copied to contract memory by `ext_input`.
+	pub input_per_byte: Weight,
+
+	/// Weight of calling `ext_return`.
+	pub r#return: Weight,
+
+	/// Weight per byte returned through `ext_return`.
+	pub return_per_byte: Weight,
+
+	/// Weight of calling `ext_terminate` without the transfer of the remaining balance.
+	pub terminate: Weight,
+
+	/// Weight of calling `ext_restore_to`.
+	pub restore_to: Weight,
+
+	/// Weight per delta key supplied to `ext_restore_to`.
+	pub restore_to_per_delta: Weight,
+
+	/// Weight of calling `ext_random`.
+	pub random: Weight,
+
+	/// Weight of calling `ext_debug_message`.
+	pub debug_message: Weight,
+
+	/// Weight of calling `ext_deposit_event`.
+	pub deposit_event: Weight,
+
+	/// Weight per topic supplied to `ext_deposit_event`.
+	pub deposit_event_per_topic: Weight,
+
+	/// Weight per byte of an event deposited through `ext_deposit_event`.
+	pub deposit_event_per_byte: Weight,
+
+	/// Weight of calling `ext_set_rent_allowance`.
+	pub set_rent_allowance: Weight,
+
+	/// Weight of calling `ext_set_storage`.
+	pub set_storage: Weight,
+
+	/// Weight per byte of an item stored with `ext_set_storage`.
+	pub set_storage_per_byte: Weight,
+
+	/// Weight of calling `ext_clear_storage`.
+	pub clear_storage: Weight,
+
+	/// Weight of calling `ext_get_storage`.
+	pub get_storage: Weight,
+
+	/// Weight per byte of an item received via `ext_get_storage`.
+	pub get_storage_per_byte: Weight,
+
+	/// Weight of calling `ext_transfer`. Also charged for every other transfer of value
+	/// that a contract causes.
+	pub transfer: Weight,
+
+	/// Weight of calling `ext_call`. Also charged for the top level `call` dispatchable.
+	pub call: Weight,
+
+	/// Weight per input byte supplied to `ext_call`.
+	pub call_per_input_byte: Weight,
+
+	/// Weight per output byte received through `ext_call`.
+	pub call_per_output_byte: Weight,
+
+	/// Weight of calling `ext_instantiate` without the transfer of the endowment.
+	/// Also charged for the top level `instantiate` dispatchable.
+	pub instantiate: Weight,
+
+	/// Weight per input byte supplied to `ext_instantiate`.
+	pub instantiate_per_input_byte: Weight,
+
+	/// Weight per output byte received through `ext_instantiate`.
+	pub instantiate_per_output_byte: Weight,
+
+	/// Weight of calling `ext_hash_sha_256`.
+	pub hash_sha2_256: Weight,
+
+	/// Weight per byte hashed by `ext_hash_sha_256`.
+	pub hash_sha2_256_per_byte: Weight,
+
+	/// Weight of calling `ext_hash_keccak_256`.
+	pub hash_keccak_256: Weight,
+
+	/// Weight per byte hashed by `ext_hash_keccak_256`.
+	pub hash_keccak_256_per_byte: Weight,
+
+	/// Weight of calling `ext_hash_blake2_256`.
+	pub hash_blake2_256: Weight,
+
+	/// Weight per byte hashed by `ext_hash_blake2_256`.
+	pub hash_blake2_256_per_byte: Weight,
+
+	/// Weight of calling `ext_hash_blake2_128`.
+	pub hash_blake2_128: Weight,
+
+	/// Weight per byte hashed by `ext_hash_blake2_128`.
+	pub hash_blake2_128_per_byte: Weight,
+
+	/// The type parameter is used in the default implementation.
+	#[codec(skip)]
+	pub _phantom: PhantomData<T>
+}
+
+/// Implements `Debug` for a type that is generic over `T: Trait` without requiring `T: Debug`.
+///
+/// `#[derive(Debug)]` would bound every type parameter, which runtimes do not satisfy. Just
+/// like `RuntimeDebug` the output is stripped when not compiled with `std`.
+macro_rules! impl_schedule_debug {
+	($name:ident { $( $field:ident ),* $(,)? }) => {
+		impl<T: Trait> fmt::Debug for $name<T> {
+			#[cfg(feature = "std")]
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.debug_struct(stringify!($name))
+					$( .field(stringify!($field), &self.$field) )*
+					.finish()
+			}
+
+			#[cfg(not(feature = "std"))]
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str("<wasm:stripped>")
+			}
+		}
+	}
+}
+
+impl_schedule_debug!(Schedule {
+	version, enable_println, limits, instruction_weights, host_fn_weights,
+});
+
+impl_schedule_debug!(InstructionWeights {
+	r#const, local, load, store, control_flow, integer_comparison, bit, add, mul, div,
+	conversion, current_memory, grow_memory, nop,
+});
+
+impl_schedule_debug!(HostFnWeights {
+	caller, address, gas_left, balance, value_transferred, minimum_balance, tombstone_deposit,
+	rent_allowance, block_number, now, weight_to_fee, gas, input, input_per_byte, r#return,
+	return_per_byte, terminate, restore_to, restore_to_per_delta, random, debug_message,
+	deposit_event,
+	deposit_event_per_topic, deposit_event_per_byte, set_rent_allowance, set_storage,
+	set_storage_per_byte, clear_storage, get_storage, get_storage_per_byte, transfer, call,
+	call_per_input_byte, call_per_output_byte, instantiate, instantiate_per_input_byte,
+	instantiate_per_output_byte, hash_sha2_256, hash_sha2_256_per_byte, hash_keccak_256,
+	hash_keccak_256_per_byte, hash_blake2_256, hash_blake2_256_per_byte, hash_blake2_128,
+	hash_blake2_128_per_byte,
+});
+
+/// The weight a benchmark adds when its (last) component is increased from 0 to the
+/// supplied value while all other components stay at 0.
+macro_rules! cost_args {
+	// cost_args!(name, a, b, c) -> T::WeightInfo::name(a, b, c).saturating_sub(T::WeightInfo::name(0, 0, 0))
+	($name:ident, $( $arg: expr ),+) => {
+		(T::WeightInfo::$name($( $arg ),+).saturating_sub(cost_args!(@call_zero $name, $( $arg ),+)))
+	};
+	// Transform T::WeightInfo::name(a, b, c) into T::WeightInfo::name(0, 0, 0)
+	(@call_zero $name:ident, $( $arg:expr ),*) => {
+		T::WeightInfo::$name($( cost_args!(@replace_token $arg) ),*)
+	};
+	// Replace the token with 0.
+	(@replace_token $_in:tt) => { 0 };
+}
+
+macro_rules! cost_batched_args {
+	($name:ident, $( $arg: expr ),+) => {
+		cost_args!($name, $( $arg ),+) / Weight::from(API_BENCHMARK_BATCH_SIZE)
+	}
+}
+
+macro_rules! cost_byte_args {
+	($name:ident, $( $arg: expr ),+) => {
+		cost_args!($name, $( $arg ),+) / 1024
+	}
+}
+
+macro_rules! cost_byte_batched_args {
+	($name:ident, $( $arg: expr ),+) => {
+		cost_batched_args!($name, $( $arg ),+) / 1024
+	}
+}
+
+macro_rules! cost {
+	($name:ident) => {
+		cost_args!($name, 1)
+	}
+}
+
+macro_rules! cost_batched {
+	($name:ident) => {
+		cost_batched_args!($name, 1)
+	}
+}
+
+macro_rules! cost_byte {
+	($name:ident) => {
+		cost_byte_args!($name, 1)
+	}
+}
+
+macro_rules! cost_byte_batched {
+	($name:ident) => {
+		cost_byte_batched_args!($name, 1)
+	}
+}
+
+macro_rules! cost_instr_no_params {
+	($name:ident) => {
+		(cost_args!($name, 1) / Weight::from(INSTR_BENCHMARK_BATCH_SIZE)) as u32
+	}
+}
+
+/// The weight of a single instruction minus the weight of the `$num_params` helper
+/// instructions (`const` or `drop`) that are executed alongside it in the benchmark.
+macro_rules! cost_instr {
+	($name:ident, $num_params:expr) => {
+		cost_instr_no_params!($name)
+			.saturating_sub((cost_instr_no_params!(instr_const) / 2).saturating_mul($num_params))
+	}
+}
+
+impl<T: Trait> Default for Schedule<T> {
+	fn default() -> Self {
+		Self {
+			version: 0,
+			enable_println: false,
+			limits: Default::default(),
+			instruction_weights: Default::default(),
+			host_fn_weights: Default::default(),
+		}
+	}
+}
+
+impl Default for Limits {
+	fn default() -> Self {
+		Self {
+			event_topics: 4,
+			stack_height: 64 * 1024,
+			memory_pages: 16,
+			table_size: 16 * 1024,
+			subject_len: 32,
+			code_size: 512 * 1024,
+			debug_buffer_len: 2 * 1024 * 1024,
+		}
+	}
+}
+
+impl<T: Trait> Default for InstructionWeights<T> {
+	fn default() -> Self {
+		// `grow_memory` is benchmarked by growing the memory to its maximum one page at a time.
+		let max_pages = Weight::from(Limits::default().memory_pages);
+		Self {
+			r#const: cost_instr!(instr_const, 1),
+			// Every repetition executes a `get_local` and a `set_local`.
+			local: cost_instr_no_params!(instr_local) / 2,
+			load: cost_instr!(instr_load, 2),
+			store: cost_instr!(instr_store, 2),
+			// Every repetition executes a `block` and a `br`.
+			control_flow: cost_instr_no_params!(instr_control_flow) / 2,
+			integer_comparison: cost_instr!(instr_integer_comparison, 3),
+			bit: cost_instr!(instr_bit, 3),
+			add: cost_instr!(instr_add, 3),
+			mul: cost_instr!(instr_mul, 3),
+			div: cost_instr!(instr_div, 3),
+			conversion: cost_instr!(instr_conversion, 2),
+			current_memory: cost_instr!(instr_current_memory, 1),
+			grow_memory: ((cost!(instr_grow_memory) / max_pages) as u32)
+				.saturating_sub(cost_instr_no_params!(instr_const)),
+			nop: cost_instr!(instr_nop, 0),
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<T: Trait> Default for HostFnWeights<T> {
+	fn default() -> Self {
+		Self {
+			caller: cost_batched!(ext_caller),
+			address: cost_batched!(ext_address),
+			gas_left: cost_batched!(ext_gas_left),
+			balance: cost_batched!(ext_balance),
+			value_transferred: cost_batched!(ext_value_transferred),
+			minimum_balance: cost_batched!(ext_minimum_balance),
+			tombstone_deposit: cost_batched!(ext_tombstone_deposit),
+			rent_allowance: cost_batched!(ext_rent_allowance),
+			block_number: cost_batched!(ext_block_number),
+			now: cost_batched!(ext_now),
+			weight_to_fee: cost_batched!(ext_weight_to_fee),
+			gas: cost_batched!(ext_gas),
+			input: cost!(ext_input),
+			input_per_byte: cost_byte!(ext_input_per_kb),
+			r#return: cost!(ext_return),
+			return_per_byte: cost_byte!(ext_return_per_kb),
+			terminate: cost!(ext_terminate).saturating_sub(cost_batched!(ext_transfer)),
+			restore_to: cost!(ext_restore_to),
+			restore_to_per_delta: cost_batched!(ext_restore_to_per_delta),
+			random: cost_batched!(ext_random),
+			debug_message: cost_batched!(ext_debug_message),
+			deposit_event: cost_batched!(ext_deposit_event),
+			deposit_event_per_topic: cost_batched_args!(ext_deposit_event_per_topic_and_kb, 1, 0),
+			deposit_event_per_byte: cost_byte_batched_args!(ext_deposit_event_per_topic_and_kb, 0, 1),
+			set_rent_allowance: cost_batched!(ext_set_rent_allowance),
+			set_storage: cost_batched!(ext_set_storage),
+			set_storage_per_byte: cost_byte_batched!(ext_set_storage_per_kb),
+			clear_storage: cost_batched!(ext_clear_storage),
+			get_storage: cost_batched!(ext_get_storage),
+			get_storage_per_byte: cost_byte_batched!(ext_get_storage_per_kb),
+			transfer: cost_batched!(ext_transfer),
+			call: cost_batched!(ext_call),
+			call_per_input_byte: cost_byte_batched_args!(ext_call_per_input_output_kb, 1, 0),
+			call_per_output_byte: cost_byte_batched_args!(ext_call_per_input_output_kb, 0, 1),
+			instantiate: cost_batched!(ext_instantiate).saturating_sub(cost_batched!(ext_transfer)),
+			instantiate_per_input_byte: cost_byte_batched_args!(ext_instantiate_per_input_output_kb, 1, 0),
+			instantiate_per_output_byte: cost_byte_batched_args!(ext_instantiate_per_input_output_kb, 0, 1),
+			hash_sha2_256: cost_batched!(ext_hash_sha2_256),
+			hash_sha2_256_per_byte: cost_byte_batched!(ext_hash_sha2_256_per_kb),
+			hash_keccak_256: cost_batched!(ext_hash_keccak_256),
+			hash_keccak_256_per_byte: cost_byte_batched!(ext_hash_keccak_256_per_kb),
+			hash_blake2_256: cost_batched!(ext_hash_blake2_256),
+			hash_blake2_256_per_byte: cost_byte_batched!(ext_hash_blake2_256_per_kb),
+			hash_blake2_128: cost_batched!(ext_hash_blake2_128),
+			hash_blake2_128_per_byte: cost_byte_batched!(ext_hash_blake2_128_per_kb),
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<T: Trait> Schedule<T> {
+	/// The gas rules that are used to instrument contract code with this schedule.
+	///
+	/// Every instruction class is priced explicitly. The fallback weight of the rule set
+	/// is set to the maximum so that an unlisted class would make instrumentation fail
+	/// instead of being charged nothing.
+	///
+	/// `grow_memory` is charged per requested page by the grow counter that the
+	/// instrumentation injects. Therefore the instruction itself is free.
+	pub(crate) fn rules(&self) -> rules::Set {
+		let w = &self.instruction_weights;
+		let entries: BTreeMap<_, _> = vec![
+			(InstructionType::Const, w.r#const),
+			(InstructionType::Local, w.local),
+			(InstructionType::Load, w.load),
+			(InstructionType::Store, w.store),
+			(InstructionType::ControlFlow, w.control_flow),
+			(InstructionType::IntegerComparsion, w.integer_comparison),
+			(InstructionType::Bit, w.bit),
+			(InstructionType::Add, w.add),
+			(InstructionType::Mul, w.mul),
+			(InstructionType::Div, w.div),
+			(InstructionType::Conversion, w.conversion),
+			(InstructionType::CurrentMemory, w.current_memory),
+			(InstructionType::GrowMemory, 0),
+			(InstructionType::Nop, w.nop),
+			(InstructionType::Unreachable, w.nop),
+		]
+			.into_iter()
+			.map(|(class, weight)| (class, Metering::Fixed(weight)))
+			.chain(vec![
+				(InstructionType::Global, Metering::Forbidden),
+				(InstructionType::Reinterpretation, Metering::Forbidden),
+			])
+			.collect();
+
+		rules::Set::new(u32::max_value(), entries)
+			.with_grow_cost(w.grow_memory)
+			.with_forbidden_floats()
+	}
+}
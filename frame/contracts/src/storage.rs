@@ -182,6 +182,7 @@ pub fn place_contract<T: Trait>(
 			}
 			.into(),
 		);
+		crate::wasm::increment_refcount::<T>(&ch);
 
 		Ok(())
 	})
@@ -191,6 +192,8 @@ pub fn place_contract<T: Trait>(
 ///
 /// This function doesn't affect the account.
 pub fn destroy_contract<T: Trait>(address: &AccountIdOf<T>, trie_id: &TrieId) {
-	<ContractInfoOf<T>>::remove(address);
+	if let Some(ContractInfo::Alive(info)) = <ContractInfoOf<T>>::take(address) {
+		crate::wasm::decrement_refcount::<T>(&info.code_hash);
+	}
 	child::kill_storage(&crate::child_trie_info(&trie_id));
 }
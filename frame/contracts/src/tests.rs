@@ -15,20 +15,24 @@
 // along with Substrate. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	BalanceOf, ContractAddressFor, ContractInfo, ContractInfoOf, GenesisConfig, Module,
+	BalanceOf, CodeStorage, ContractAddressFor, ContractInfo, ContractInfoOf, CurrentSchedule, GenesisConfig,
+	Module, OwnerInfoOf,
 	RawAliveContractInfo, RawEvent, Trait, TrieId, Schedule, TrieIdGenerator, gas::Gas,
 	Error, Config, RuntimeReturnCode,
+	chain_extension::{
+		Environment, Ext, ChainExtension, RetVal, ReturnFlags, Result as ExtensionResult,
+	},
 };
 use assert_matches::assert_matches;
 use hex_literal::*;
 use codec::Encode;
 use sp_runtime::{
-	Perbill,
+	Perbill, DispatchError,
 	traits::{BlakeTwo256, Hash, IdentityLookup, Convert},
 	testing::{Header, H256},
 };
 use frame_support::{
-	assert_ok, assert_err_ignore_postinfo, impl_outer_dispatch, impl_outer_event,
+	assert_ok, assert_noop, assert_err_ignore_postinfo, impl_outer_dispatch, impl_outer_event,
 	impl_outer_origin, parameter_types, StorageMap, StorageValue,
 	traits::{Currency, Get, ReservableCurrency},
 	weights::{Weight, PostDispatchInfo},
@@ -101,6 +105,7 @@ pub mod test_utils {
 
 thread_local! {
 	static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(0);
+	static CODE_DEPOSIT_PER_BYTE: RefCell<u64> = RefCell::new(0);
 }
 
 pub struct ExistentialDeposit;
@@ -108,6 +113,68 @@ impl Get<u64> for ExistentialDeposit {
 	fn get() -> u64 { EXISTENTIAL_DEPOSIT.with(|v| *v.borrow()) }
 }
 
+pub struct CodeDepositPerByte;
+impl Get<u64> for CodeDepositPerByte {
+	fn get() -> u64 { CODE_DEPOSIT_PER_BYTE.with(|v| *v.borrow()) }
+}
+
+thread_local! {
+	static TEST_EXTENSION: RefCell<TestExtension> = Default::default();
+}
+
+/// A chain extension used to test the contract facing side of chain extensions.
+///
+/// - `func_id` 0: writes the input back to the output buffer.
+/// - `func_id` 1: charges the weight that is encoded as `u32` after the `func_id` byte.
+/// - `func_id` 2: stops the execution of the contract and reverts with `[42, 99]`.
+pub struct TestExtension {
+	enabled: bool,
+	last_seen_buffer: Vec<u8>,
+}
+
+impl TestExtension {
+	fn disable() {
+		TEST_EXTENSION.with(|e| e.borrow_mut().enabled = false)
+	}
+
+	fn last_seen_buffer() -> Vec<u8> {
+		TEST_EXTENSION.with(|e| e.borrow().last_seen_buffer.clone())
+	}
+}
+
+impl Default for TestExtension {
+	fn default() -> Self {
+		Self {
+			enabled: true,
+			last_seen_buffer: vec![],
+		}
+	}
+}
+
+impl ChainExtension<Test> for TestExtension {
+	fn call<E: Ext<T = Test>>(func_id: u32, mut env: Environment<E>) -> ExtensionResult<RetVal> {
+		match func_id {
+			0 => {
+				let input = env.read()?;
+				env.write(&input, false)?;
+				TEST_EXTENSION.with(|e| e.borrow_mut().last_seen_buffer = input);
+				Ok(RetVal::Converging(func_id))
+			},
+			1 => {
+				let (_, weight): (u8, u32) = env.read_as()?;
+				env.charge_weight(weight.into())?;
+				Ok(RetVal::Converging(func_id))
+			},
+			2 => Ok(RetVal::Diverging { flags: ReturnFlags::REVERT, data: vec![42, 99] }),
+			_ => Err(DispatchError::Other("unknown function")),
+		}
+	}
+
+	fn enabled() -> bool {
+		TEST_EXTENSION.with(|e| e.borrow().enabled)
+	}
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Test;
 parameter_types! {
@@ -197,7 +264,10 @@ impl Trait for Test {
 	type SurchargeReward = SurchargeReward;
 	type MaxDepth = MaxDepth;
 	type MaxValueSize = MaxValueSize;
+	type CodeDepositPerByte = CodeDepositPerByte;
 	type WeightPrice = Self;
+	type ChainExtension = TestExtension;
+	type WeightInfo = ();
 }
 
 type Balances = pallet_balances::Module<Test>;
@@ -237,11 +307,13 @@ const GAS_LIMIT: Gas = 10_000_000_000;
 
 pub struct ExtBuilder {
 	existential_deposit: u64,
+	code_deposit_per_byte: u64,
 }
 impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
 			existential_deposit: 1,
+			code_deposit_per_byte: 0,
 		}
 	}
 }
@@ -250,8 +322,13 @@ impl ExtBuilder {
 		self.existential_deposit = existential_deposit;
 		self
 	}
+	pub fn code_deposit_per_byte(mut self, code_deposit_per_byte: u64) -> Self {
+		self.code_deposit_per_byte = code_deposit_per_byte;
+		self
+	}
 	pub fn set_associated_consts(&self) {
 		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
+		CODE_DEPOSIT_PER_BYTE.with(|v| *v.borrow_mut() = self.code_deposit_per_byte);
 	}
 	pub fn build(self) -> sp_io::TestExternalities {
 		self.set_associated_consts();
@@ -301,7 +378,7 @@ fn calling_plain_account_fails() {
 				DispatchErrorWithPostInfo {
 					error: Error::<Test>::NotCallable.into(),
 					post_info: PostDispatchInfo {
-						actual_weight: Some(67500000),
+						actual_weight: Some(112352180),
 						pays_fee: Default::default(),
 					},
 				}
@@ -1826,3 +1903,285 @@ fn instantiate_return_code() {
 
 	});
 }
+
+#[test]
+fn disabled_chain_extension_wont_deploy() {
+	let (code, _hash) = compile_module::<Test>("chain_extension").unwrap();
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		TestExtension::disable();
+		assert_eq!(
+			Contracts::put_code(Origin::signed(ALICE), code),
+			Err("module uses chain extensions but chain extensions are disabled".into()),
+		);
+	});
+}
+
+#[test]
+fn chain_extension_works() {
+	let (code, hash) = compile_module::<Test>("chain_extension").unwrap();
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let subsistence = Config::<Test>::subsistence_threshold_uncached();
+		let _ = Balances::deposit_creating(&ALICE, 10 * subsistence);
+		assert_ok!(Contracts::put_code(Origin::signed(ALICE), code));
+		assert_ok!(
+			Contracts::instantiate(
+				Origin::signed(ALICE),
+				subsistence,
+				GAS_LIMIT,
+				hash.into(),
+				vec![],
+			),
+		);
+
+		// The contract passes its input to the chain extension and uses the first byte
+		// as func_id. The chain extension behaves differently based on the func_id.
+
+		// 0 = read input buffer and pass it through as output
+		let input: Vec<u8> = vec![0, 99];
+		let result = Contracts::bare_call(ALICE, BOB, 0, GAS_LIMIT, input.clone()).0.unwrap();
+		assert_eq!(TestExtension::last_seen_buffer(), input);
+		assert_eq!(result.data, input);
+
+		// 1 = treat inputs as integer primitives and charge the passed weight
+		let gas_consumed = Contracts::bare_call(
+			ALICE, BOB, 0, GAS_LIMIT, (1u8, 0u32).encode(),
+		).1;
+		let gas_charged = Contracts::bare_call(
+			ALICE, BOB, 0, GAS_LIMIT, (1u8, 42u32).encode(),
+		).1;
+		assert_eq!(gas_charged, gas_consumed + 42);
+
+		// 2 = diverging chain extension call that sets flags to 0x1 and returns a fixed buffer
+		let result = Contracts::bare_call(ALICE, BOB, 0, GAS_LIMIT, vec![2]).0.unwrap();
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+		assert_eq!(result.data, vec![42, 99]);
+
+		// any other func_id = the error of the chain extension is passed through
+		let result = Contracts::bare_call(ALICE, BOB, 0, GAS_LIMIT, vec![3]).0;
+		assert_eq!(result.unwrap_err().error, DispatchError::Other("unknown function"));
+	});
+}
+
+#[test]
+fn put_code_reserves_deposit_and_remove_code_refunds_it() {
+	let (wasm, code_hash) = compile_module::<Test>("return_from_start_fn").unwrap();
+	let deposit = wasm.len() as u64 * 2;
+	ExtBuilder::default()
+		.code_deposit_per_byte(2)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm.clone()));
+			assert_eq!(Balances::reserved_balance(ALICE), deposit);
+			assert_eq!(Balances::free_balance(ALICE), 1_000_000 - deposit);
+
+			// Storing the same code again neither fails nor takes another deposit.
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_eq!(Balances::reserved_balance(ALICE), deposit);
+
+			// Only the owner is allowed to remove the code.
+			assert_noop!(
+				Contracts::remove_code(Origin::signed(BOB), code_hash.into()),
+				Error::<Test>::NotCodeOwner,
+			);
+
+			assert_ok!(Contracts::remove_code(Origin::signed(ALICE), code_hash.into()));
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+			assert_eq!(Balances::free_balance(ALICE), 1_000_000);
+			assert!(!<CodeStorage<Test>>::contains_key(&code_hash));
+			assert!(!<OwnerInfoOf<Test>>::contains_key(&code_hash));
+
+			// The code is gone now.
+			assert_noop!(
+				Contracts::remove_code(Origin::signed(ALICE), code_hash.into()),
+				Error::<Test>::CodeNotFound,
+			);
+		});
+}
+
+#[test]
+fn put_code_fails_without_deposit() {
+	let (wasm, _code_hash) = compile_module::<Test>("return_from_start_fn").unwrap();
+	ExtBuilder::default()
+		.code_deposit_per_byte(1_000_000)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert!(Contracts::put_code(Origin::signed(ALICE), wasm).is_err());
+			assert_eq!(Balances::free_balance(ALICE), 1_000_000);
+		});
+}
+
+#[test]
+fn remove_code_in_use_fails() {
+	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				100_000,
+				GAS_LIMIT,
+				code_hash.into(),
+				vec![],
+			));
+			assert_eq!(<OwnerInfoOf<Test>>::get(&code_hash).unwrap().refcount, 1);
+
+			assert_noop!(
+				Contracts::remove_code(Origin::signed(ALICE), code_hash.into()),
+				Error::<Test>::CodeInUse,
+			);
+
+			// Call BOB without input data which triggers termination.
+			assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, vec![]));
+			assert_eq!(<OwnerInfoOf<Test>>::get(&code_hash).unwrap().refcount, 0);
+
+			assert_ok!(Contracts::remove_code(Origin::signed(ALICE), code_hash.into()));
+		});
+}
+
+#[test]
+fn instantiate_with_code_works() {
+	let (wasm, code_hash) = compile_module::<Test>("return_from_start_fn").unwrap();
+	let deposit = wasm.len() as u64;
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.code_deposit_per_byte(1)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::instantiate_with_code(
+				Origin::signed(ALICE),
+				100,
+				GAS_LIMIT,
+				wasm,
+				vec![],
+			));
+
+			assert_matches!(ContractInfoOf::<Test>::get(BOB), Some(ContractInfo::Alive(_)));
+			assert_eq!(Balances::reserved_balance(ALICE), deposit);
+			assert_eq!(<OwnerInfoOf<Test>>::get(&code_hash).unwrap().refcount, 1);
+		});
+}
+
+#[test]
+fn instantiate_with_code_does_not_store_code_on_failure() {
+	let (wasm, code_hash) = compile_module::<Test>("return_from_start_fn").unwrap();
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.code_deposit_per_byte(1)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+			// The endowment is below the existential deposit so instantiation fails.
+			assert!(Contracts::instantiate_with_code(
+				Origin::signed(ALICE),
+				1,
+				GAS_LIMIT,
+				wasm,
+				vec![],
+			).is_err());
+
+			assert!(!<CodeStorage<Test>>::contains_key(&code_hash));
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+		});
+}
+
+#[test]
+fn dry_run_call_collects_debug_info() {
+	let (wasm, code_hash) = compile_module::<Test>("debug_message").unwrap();
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				100_000,
+				GAS_LIMIT,
+				code_hash.into(),
+				vec![],
+			));
+
+			let result = Contracts::dry_run_call(ALICE, BOB, 0, GAS_LIMIT, vec![]);
+			assert_eq!(result.result.unwrap().flags, 0);
+			assert_eq!(std::str::from_utf8(&result.debug_message).unwrap(), "Hello World!");
+			assert_eq!(result.events, vec![
+				MetaEvent::contracts(
+					RawEvent::ContractExecution(BOB, b"Hello".to_vec())
+				).encode(),
+			]);
+			assert!(result.gas_consumed > 0);
+			let info = ContractInfoOf::<Test>::get(BOB).unwrap().get_alive().unwrap();
+			assert_eq!(
+				result.storage_deposit,
+				u64::from(info.storage_size + <Test as Trait>::StorageSizeOffset::get())
+					* <Test as Trait>::RentDepositOffset::get(),
+			);
+
+			// A trap is reported alongside the messages written before it.
+			let result = Contracts::dry_run_call(ALICE, BOB, 0, GAS_LIMIT, vec![1]);
+			let trap = result.result.unwrap_err();
+			assert_eq!(trap.error, Error::<Test>::ContractTrapped.into());
+			assert_eq!(trap.message, Some(b"ContractTrapped".to_vec()));
+			assert_eq!(std::str::from_utf8(&result.debug_message).unwrap(), "Hello World!");
+		});
+}
+
+#[test]
+fn debug_message_is_dropped_past_the_buffer_limit() {
+	let (wasm, code_hash) = compile_module::<Test>("debug_message").unwrap();
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				100_000,
+				GAS_LIMIT,
+				code_hash.into(),
+				vec![],
+			));
+
+			// "Hello World!" is 12 bytes long.
+			<CurrentSchedule<Test>>::mutate(|schedule| schedule.limits.debug_buffer_len = 12);
+			let result = Contracts::dry_run_call(ALICE, BOB, 0, GAS_LIMIT, vec![]);
+			assert_eq!(std::str::from_utf8(&result.debug_message).unwrap(), "Hello World!");
+
+			// The message is dropped as a whole, the execution carries on.
+			<CurrentSchedule<Test>>::mutate(|schedule| schedule.limits.debug_buffer_len = 11);
+			let result = Contracts::dry_run_call(ALICE, BOB, 0, GAS_LIMIT, vec![]);
+			assert_eq!(result.result.unwrap().flags, 0);
+			assert!(result.debug_message.is_empty());
+		});
+}
+
+#[test]
+fn debug_message_is_ignored_on_chain() {
+	let (wasm, code_hash) = compile_module::<Test>("debug_message").unwrap();
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				100_000,
+				GAS_LIMIT,
+				code_hash.into(),
+				vec![],
+			));
+
+			// The contract does not fail if the message could not be recorded.
+			assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, vec![]));
+		});
+}
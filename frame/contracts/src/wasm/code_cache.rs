@@ -26,27 +26,112 @@
 //! this guarantees that every instrumented contract code in cache cannot have the version equal to the current one.
 //! Thus, before executing a contract it should be reinstrument with new schedule.
 
+//! - Every stored code has an owner who paid a deposit for it. The code keeps track of the number
+//! of alive contracts that use it and can only be removed by its owner when this number is zero.
+
 use crate::wasm::{prepare, runtime::Env, PrefabWasmModule};
-use crate::{CodeHash, CodeStorage, PristineCode, Schedule, Trait};
+use crate::{
+	BalanceOf, CodeHash, CodeStorage, Error, OwnerInfo, OwnerInfoOf, PristineCode, Schedule,
+	Trait,
+};
 use sp_std::prelude::*;
-use sp_runtime::traits::Hash;
-use frame_support::StorageMap;
+use sp_runtime::{DispatchError, DispatchResult, traits::{Hash, Saturating}};
+use frame_support::{StorageMap, ensure, traits::{Get, ReservableCurrency}};
 
 /// Put code in the storage. The hash of code is used as a key and is returned
 /// as a result of this function.
 ///
-/// This function instruments the given code and caches it in the storage.
+/// This function instruments the given code and caches it in the storage. The deposit
+/// for the code is reserved from `owner`. Saving code that is already stored is a no-op.
 pub fn save<T: Trait>(
 	original_code: Vec<u8>,
-	schedule: &Schedule,
-) -> Result<CodeHash<T>, &'static str> {
-	let prefab_module = prepare::prepare_contract::<Env>(&original_code, schedule)?;
+	schedule: &Schedule<T>,
+	owner: &T::AccountId,
+) -> Result<CodeHash<T>, DispatchError> {
 	let code_hash = T::Hashing::hash(&original_code);
+	if <CodeStorage<T>>::contains_key(&code_hash) {
+		return Ok(code_hash);
+	}
+
+	let prefab_module = prepare::prepare_contract::<Env, T>(&original_code, schedule)?;
+	store::<T>(code_hash, prefab_module, original_code, owner)?;
+
+	Ok(code_hash)
+}
+
+/// Version of `save` to be used in runtime benchmarks.
+//
+/// This version neither checks nor instruments the passed in code. This is useful
+/// when code needs to be benchmarked without the injected instrumentation.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn save_raw<T: Trait>(
+	original_code: Vec<u8>,
+	schedule: &Schedule<T>,
+	owner: &T::AccountId,
+) -> Result<CodeHash<T>, DispatchError> {
+	let prefab_module = prepare::benchmarking::prepare_contract::<T>(&original_code, schedule)?;
+	let code_hash = T::Hashing::hash(&original_code);
+	store::<T>(code_hash, prefab_module, original_code, owner)?;
+
+	Ok(code_hash)
+}
+
+/// Reserve the deposit from `owner` and insert the code alongside its owner info.
+fn store<T: Trait>(
+	code_hash: CodeHash<T>,
+	prefab_module: PrefabWasmModule,
+	original_code: Vec<u8>,
+	owner: &T::AccountId,
+) -> DispatchResult {
+	let deposit = T::CodeDepositPerByte::get()
+		.saturating_mul(BalanceOf::<T>::from(original_code.len() as u32));
+	T::Currency::reserve(owner, deposit)?;
 
 	<CodeStorage<T>>::insert(code_hash, prefab_module);
 	<PristineCode<T>>::insert(code_hash, original_code);
+	<OwnerInfoOf<T>>::insert(code_hash, OwnerInfo::<T> {
+		owner: owner.clone(),
+		deposit,
+		refcount: 0,
+	});
 
-	Ok(code_hash)
+	Ok(())
+}
+
+/// Remove the code stored under `code_hash` and refund the deposit to its owner.
+///
+/// Fails if `origin` is not the owner or if the code is still used by a contract.
+pub fn remove<T: Trait>(origin: &T::AccountId, code_hash: &CodeHash<T>) -> DispatchResult {
+	let info = <OwnerInfoOf<T>>::get(code_hash).ok_or(Error::<T>::CodeNotFound)?;
+	ensure!(&info.owner == origin, Error::<T>::NotCodeOwner);
+	ensure!(info.refcount == 0, Error::<T>::CodeInUse);
+
+	T::Currency::unreserve(&info.owner, info.deposit);
+	<CodeStorage<T>>::remove(code_hash);
+	<PristineCode<T>>::remove(code_hash);
+	<OwnerInfoOf<T>>::remove(code_hash);
+
+	Ok(())
+}
+
+/// Increment the number of contracts that use the code stored under `code_hash`.
+///
+/// Code that was stored before owners were tracked has no owner info and is left alone.
+pub fn increment_refcount<T: Trait>(code_hash: &CodeHash<T>) {
+	<OwnerInfoOf<T>>::mutate(code_hash, |info| {
+		if let Some(info) = info {
+			info.refcount = info.refcount.saturating_add(1);
+		}
+	});
+}
+
+/// Decrement the number of contracts that use the code stored under `code_hash`.
+pub fn decrement_refcount<T: Trait>(code_hash: &CodeHash<T>) {
+	<OwnerInfoOf<T>>::mutate(code_hash, |info| {
+		if let Some(info) = info {
+			info.refcount = info.refcount.saturating_sub(1);
+		}
+	});
 }
 
 /// Load code with the given code hash.
@@ -56,7 +141,7 @@ pub fn save<T: Trait>(
 /// re-instrumentation and update the cache in the storage.
 pub fn load<T: Trait>(
 	code_hash: &CodeHash<T>,
-	schedule: &Schedule,
+	schedule: &Schedule<T>,
 ) -> Result<PrefabWasmModule, &'static str> {
 	let mut prefab_module =
 		<CodeStorage<T>>::get(code_hash).ok_or_else(|| "code is not found")?;
@@ -68,7 +153,7 @@ pub fn load<T: Trait>(
 		// We need to re-instrument the code with the latest schedule here.
 		let original_code =
 			<PristineCode<T>>::get(code_hash).ok_or_else(|| "pristine code is not found")?;
-		prefab_module = prepare::prepare_contract::<Env>(&original_code, schedule)?;
+		prefab_module = prepare::prepare_contract::<Env, T>(&original_code, schedule)?;
 		<CodeStorage<T>>::insert(&code_hash, &prefab_module);
 	}
 	Ok(prefab_module)
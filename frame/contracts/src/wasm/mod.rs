@@ -32,11 +32,16 @@ mod code_cache;
 mod prepare;
 mod runtime;
 
-use self::runtime::{to_execution_result, Runtime};
+use self::runtime::to_execution_result;
 use self::code_cache::load as load_code;
 
-pub use self::code_cache::save as save_code;
+pub use self::code_cache::{
+	save as save_code, remove as remove_code, increment_refcount, decrement_refcount,
+};
+#[cfg(feature = "runtime-benchmarks")]
+pub use self::code_cache::save_raw as save_code_raw;
 pub use self::runtime::ReturnCode;
+pub(crate) use self::runtime::Runtime;
 
 /// A prepared wasm module ready for execution.
 #[derive(Clone, Encode, Decode)]
@@ -64,17 +69,17 @@ pub struct WasmExecutable {
 }
 
 /// Loader which fetches `WasmExecutable` from the code cache.
-pub struct WasmLoader<'a> {
-	schedule: &'a Schedule,
+pub struct WasmLoader<'a, T: Trait> {
+	schedule: &'a Schedule<T>,
 }
 
-impl<'a> WasmLoader<'a> {
-	pub fn new(schedule: &'a Schedule) -> Self {
+impl<'a, T: Trait> WasmLoader<'a, T> {
+	pub fn new(schedule: &'a Schedule<T>) -> Self {
 		WasmLoader { schedule }
 	}
 }
 
-impl<'a, T: Trait> crate::exec::Loader<T> for WasmLoader<'a> {
+impl<'a, T: Trait> crate::exec::Loader<T> for WasmLoader<'a, T> {
 	type Executable = WasmExecutable;
 
 	fn load_init(&self, code_hash: &CodeHash<T>) -> Result<WasmExecutable, &'static str> {
@@ -94,17 +99,17 @@ impl<'a, T: Trait> crate::exec::Loader<T> for WasmLoader<'a> {
 }
 
 /// Implementation of `Vm` that takes `WasmExecutable` and executes it.
-pub struct WasmVm<'a> {
-	schedule: &'a Schedule,
+pub struct WasmVm<'a, T: Trait> {
+	schedule: &'a Schedule<T>,
 }
 
-impl<'a> WasmVm<'a> {
-	pub fn new(schedule: &'a Schedule) -> Self {
+impl<'a, T: Trait> WasmVm<'a, T> {
+	pub fn new(schedule: &'a Schedule<T>) -> Self {
 		WasmVm { schedule }
 	}
 }
 
-impl<'a, T: Trait> crate::exec::Vm<T> for WasmVm<'a> {
+impl<'a, T: Trait> crate::exec::Vm<T> for WasmVm<'a, T> {
 	type Executable = WasmExecutable;
 
 	fn execute<E: Ext<T = T>>(
@@ -208,6 +213,7 @@ mod tests {
 		// (topics, data)
 		events: Vec<(Vec<H256>, Vec<u8>)>,
 		next_account_id: u64,
+		debug_buffer: Vec<u8>,
 	}
 
 	impl Ext for MockExt {
@@ -348,6 +354,11 @@ mod tests {
 		fn get_weight_price(&self, weight: Weight) -> BalanceOf<Self::T> {
 			BalanceOf::<Self::T>::from(1312_u32).saturating_mul(weight.into())
 		}
+
+		fn append_debug_buffer(&mut self, msg: &str) -> bool {
+			self.debug_buffer.extend(msg.as_bytes());
+			true
+		}
 	}
 
 	impl Ext for &mut MockExt {
@@ -448,6 +459,9 @@ mod tests {
 		fn get_weight_price(&self, weight: Weight) -> BalanceOf<Self::T> {
 			(**self).get_weight_price(weight)
 		}
+		fn append_debug_buffer(&mut self, msg: &str) -> bool {
+			(**self).append_debug_buffer(msg)
+		}
 	}
 
 	fn execute<E: Ext>(
@@ -461,7 +475,7 @@ mod tests {
 		let wasm = wat::parse_str(wat).unwrap();
 		let schedule = crate::Schedule::default();
 		let prefab_module =
-			prepare_contract::<super::runtime::Env>(&wasm, &schedule).unwrap();
+			prepare_contract::<super::runtime::Env, E::T>(&wasm, &schedule).unwrap();
 
 		let exec = WasmExecutable {
 			// Use a "call" convention.
@@ -469,8 +483,7 @@ mod tests {
 			prefab_module,
 		};
 
-		let cfg = Default::default();
-		let vm = WasmVm::new(&cfg);
+		let vm = WasmVm::new(&schedule);
 
 		vm.execute(&exec, ext, input_data, gas_meter)
 	}
@@ -523,7 +536,7 @@ mod tests {
 				to: 7,
 				value: 153,
 				data: Vec::new(),
-				gas_left: 9989000000,
+				gas_left: 9997620378,
 			}]
 		);
 	}
@@ -587,7 +600,7 @@ mod tests {
 				to: 9,
 				value: 6,
 				data: vec![1, 2, 3, 4],
-				gas_left: 9984500000,
+				gas_left: 9997610827,
 			}]
 		);
 	}
@@ -658,7 +671,7 @@ mod tests {
 				code_hash: [0x11; 32].into(),
 				endowment: 3,
 				data: vec![1, 2, 3, 4],
-				gas_left: 9971500000,
+				gas_left: 9997607441,
 			}]
 		);
 	}
@@ -699,7 +712,7 @@ mod tests {
 			&mock_ext.terminations,
 			&[TerminationEntry {
 				beneficiary: 0x09,
-				gas_left: 9994500000,
+				gas_left: 8954946094,
 			}]
 		);
 	}
@@ -1470,7 +1483,7 @@ mod tests {
 			vec![0x00, 0x01, 0x2a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xe5, 0x14, 0x00])
 		]);
 
-		assert_eq!(gas_meter.gas_left(), 9967000000);
+		assert_eq!(gas_meter.gas_left(), 9967838575);
 	}
 
 	const CODE_DEPOSIT_EVENT_MAX_TOPICS: &str = r#"
@@ -1745,4 +1758,73 @@ mod tests {
 		);
 	}
 
+	const CODE_DEBUG_MESSAGE: &str = r#"
+(module
+	(import "env" "ext_debug_message" (func $ext_debug_message (param i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(data (i32.const 0) "Hello World!")
+
+	(func (export "call")
+		(call $ext_debug_message
+			(i32.const 0)	;; Pointer to the text buffer
+			(i32.const 12)	;; The size of the buffer
+		)
+		drop
+	)
+
+	(func (export "deploy"))
+)
+"#;
+
+	const CODE_DEBUG_MESSAGE_INVALID_UTF8: &str = r#"
+(module
+	(import "env" "ext_debug_message" (func $ext_debug_message (param i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(data (i32.const 0) "\fc")
+
+	(func (export "call")
+		(call $ext_debug_message
+			(i32.const 0)	;; Pointer to the text buffer
+			(i32.const 1)	;; The size of the buffer
+		)
+		drop
+	)
+
+	(func (export "deploy"))
+)
+"#;
+
+	#[test]
+	fn debug_message_works() {
+		let mut ext = MockExt::default();
+		execute(
+			CODE_DEBUG_MESSAGE,
+			vec![],
+			&mut ext,
+			&mut GasMeter::new(GAS_LIMIT),
+		).unwrap();
+
+		assert_eq!(std::str::from_utf8(&ext.debug_buffer).unwrap(), "Hello World!");
+	}
+
+	#[test]
+	fn debug_message_invalid_utf8_fails() {
+		let mut ext = MockExt::default();
+		let result = execute(
+			CODE_DEBUG_MESSAGE_INVALID_UTF8,
+			vec![],
+			&mut ext,
+			&mut GasMeter::new(GAS_LIMIT),
+		);
+		assert_eq!(
+			result,
+			Err(ExecError {
+				error: Error::<Test>::DebugMessageInvalidUTF8.into(),
+				origin: ErrorOrigin::Caller,
+			})
+		);
+	}
+
 }
@@ -20,28 +20,27 @@
 
 use crate::wasm::env_def::ImportSatisfyCheck;
 use crate::wasm::PrefabWasmModule;
-use crate::Schedule;
+use crate::{Schedule, Trait};
+use crate::chain_extension::ChainExtension;
 
 use parity_wasm::elements::{self, Internal, External, MemoryType, Type, ValueType};
 use pwasm_utils;
-use pwasm_utils::rules;
 use sp_std::prelude::*;
-use sp_runtime::traits::{SaturatedConversion};
 
-struct ContractModule<'a> {
+struct ContractModule<'a, T: Trait> {
 	/// A deserialized module. The module is valid (this is Guaranteed by `new` method).
 	module: elements::Module,
-	schedule: &'a Schedule,
+	schedule: &'a Schedule<T>,
 }
 
-impl<'a> ContractModule<'a> {
+impl<'a, T: Trait> ContractModule<'a, T> {
 	/// Creates a new instance of `ContractModule`.
 	///
 	/// Returns `Err` if the `original_code` couldn't be decoded or
 	/// if it contains an invalid module.
 	fn new(
 		original_code: &[u8],
-		schedule: &'a Schedule,
+		schedule: &'a Schedule<T>,
 	) -> Result<Self, &'static str> {
 		use wasmi_validation::{validate_module, PlainValidator};
 
@@ -138,14 +137,7 @@ impl<'a> ContractModule<'a> {
 	}
 
 	fn inject_gas_metering(self) -> Result<Self, &'static str> {
-		let gas_rules =
-			rules::Set::new(
-				self.schedule.regular_op_cost.clone().saturated_into(),
-				Default::default(),
-			)
-			.with_grow_cost(self.schedule.grow_mem_cost.clone().saturated_into())
-			.with_forbidden_floats();
-
+		let gas_rules = self.schedule.rules();
 		let contract_module = pwasm_utils::inject_gas_counter(self.module, &gas_rules)
 			.map_err(|_| "gas instrumentation failed")?;
 		Ok(ContractModule {
@@ -156,7 +148,7 @@ impl<'a> ContractModule<'a> {
 
 	fn inject_stack_height_metering(self) -> Result<Self, &'static str> {
 		let contract_module =
-			pwasm_utils::stack_height::inject_limiter(self.module, self.schedule.max_stack_height)
+			pwasm_utils::stack_height::inject_limiter(self.module, self.schedule.limits.stack_height)
 				.map_err(|_| "stack height instrumentation failed")?;
 		Ok(ContractModule {
 			module: contract_module,
@@ -258,7 +250,9 @@ impl<'a> ContractModule<'a> {
 	/// - checks any imported function against defined host functions set, incl.
 	///   their signatures.
 	/// - if there is a memory import, returns it's descriptor
-	fn scan_imports<C: ImportSatisfyCheck>(&self) -> Result<Option<&MemoryType>, &'static str> {
+	fn scan_imports<C: ImportSatisfyCheck>(&self)
+		-> Result<Option<&MemoryType>, &'static str>
+	{
 		let module = &self.module;
 
 		let types = module.type_section().map(|ts| ts.types()).unwrap_or(&[]);
@@ -302,6 +296,14 @@ impl<'a> ContractModule<'a> {
 				return Err("module imports `ext_println` but debug features disabled");
 			}
 
+			// We disallow importing `ext_call_chain_extension` when the chain does not
+			// provide a chain extension.
+			if import.field().as_bytes() == b"ext_call_chain_extension"
+				&& !<T::ChainExtension as ChainExtension<T>>::enabled()
+			{
+				return Err("module uses chain extensions but chain extensions are disabled");
+			}
+
 			// We disallow importing `gas` function here since it is treated as implementation detail.
 			if import.field().as_bytes() == b"gas"
 				|| !C::can_satisfy(import.field().as_bytes(), func_ty)
@@ -327,69 +329,104 @@ impl<'a> ContractModule<'a> {
 /// - the module doesn't define an internal memory instance,
 /// - imported memory (if any) doesn't reserve more memory than permitted by the `schedule`,
 /// - all imported functions from the external environment matches defined by `env` module,
+/// - `ext_call_chain_extension` is only imported when the chain provides a chain extension,
 ///
 /// The preprocessing includes injecting code for gas metering and metering the height of stack.
-pub fn prepare_contract<C: ImportSatisfyCheck>(
+pub fn prepare_contract<C: ImportSatisfyCheck, T: Trait>(
 	original_code: &[u8],
-	schedule: &Schedule,
+	schedule: &Schedule<T>,
 ) -> Result<PrefabWasmModule, &'static str> {
 	let mut contract_module = ContractModule::new(original_code, schedule)?;
 	contract_module.scan_exports()?;
 	contract_module.ensure_no_internal_memory()?;
-	contract_module.ensure_table_size_limit(schedule.max_table_size)?;
+	contract_module.ensure_table_size_limit(schedule.limits.table_size)?;
 	contract_module.ensure_no_floating_types()?;
 
-	struct MemoryDefinition {
-		initial: u32,
-		maximum: u32,
-	}
+	let memory_limits = get_memory_limits(contract_module.scan_imports::<C>()?, schedule)?;
+
+	contract_module = contract_module
+		.inject_gas_metering()?
+		.inject_stack_height_metering()?;
 
-	let memory_def = if let Some(memory_type) = contract_module.scan_imports::<C>()? {
+	Ok(PrefabWasmModule {
+		schedule_version: schedule.version,
+		initial: memory_limits.0,
+		maximum: memory_limits.1,
+		_reserved: None,
+		code: contract_module.into_wasm_code()?,
+	})
+}
+
+/// Extracts the initial and maximum page count from the imported memory and checks
+/// them against the limits of the `schedule`.
+fn get_memory_limits<T: Trait>(module: Option<&MemoryType>, schedule: &Schedule<T>)
+	-> Result<(u32, u32), &'static str>
+{
+	if let Some(memory_type) = module {
 		// Inspect the module to extract the initial and maximum page count.
 		let limits = memory_type.limits();
 		match (limits.initial(), limits.maximum()) {
 			(initial, Some(maximum)) if initial > maximum => {
-				return Err(
+				Err(
 					"Requested initial number of pages should not exceed the requested maximum",
-				);
+				)
 			}
-			(_, Some(maximum)) if maximum > schedule.max_memory_pages => {
-				return Err("Maximum number of pages should not exceed the configured maximum.");
+			(_, Some(maximum)) if maximum > schedule.limits.memory_pages => {
+				Err("Maximum number of pages should not exceed the configured maximum.")
 			}
-			(initial, Some(maximum)) => MemoryDefinition { initial, maximum },
+			(initial, Some(maximum)) => Ok((initial, maximum)),
 			(_, None) => {
 				// Maximum number of pages should be always declared.
 				// This isn't a hard requirement and can be treated as a maximum set
 				// to configured maximum.
-				return Err("Maximum number of pages should be always declared.");
+				Err("Maximum number of pages should be always declared.")
 			}
 		}
 	} else {
 		// If none memory imported then just crate an empty placeholder.
 		// Any access to it will lead to out of bounds trap.
-		MemoryDefinition {
-			initial: 0,
-			maximum: 0,
-		}
-	};
+		Ok((0, 0))
+	}
+}
 
-	contract_module = contract_module
-		.inject_gas_metering()?
-		.inject_stack_height_metering()?;
+/// Alternate (possibly unsafe) preparation functions used only for benchmarking.
+///
+/// For benchmarking we need to construct special contracts that might not pass our
+/// sanity checks or need to skip instrumentation for correct results. We hide functions
+/// allowing this behind a feature that is only set during benchmarking to prevent usage
+/// in production code.
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking {
+	use super::*;
 
-	Ok(PrefabWasmModule {
-		schedule_version: schedule.version,
-		initial: memory_def.initial,
-		maximum: memory_def.maximum,
-		_reserved: None,
-		code: contract_module.into_wasm_code()?,
-	})
+	/// Prepare function that neither checks nor instruments the passed in code.
+	pub fn prepare_contract<T: Trait>(original_code: &[u8], schedule: &Schedule<T>)
+		-> Result<PrefabWasmModule, &'static str>
+	{
+		let contract_module = ContractModule::new(original_code, schedule)?;
+		let memory_type = contract_module.module
+			.import_section()
+			.and_then(|section| section.entries().iter().find_map(|entry|
+				match entry.external() {
+					External::Memory(memory_type) => Some(memory_type),
+					_ => None,
+				}
+			));
+		let memory_limits = get_memory_limits(memory_type, schedule)?;
+		Ok(PrefabWasmModule {
+			schedule_version: schedule.version,
+			initial: memory_limits.0,
+			maximum: memory_limits.1,
+			_reserved: None,
+			code: contract_module.into_wasm_code()?,
+		})
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::exec::Ext;
+	use crate::{exec::Ext, tests::Test};
 	use std::fmt;
 	use assert_matches::assert_matches;
 
@@ -417,8 +454,8 @@ mod tests {
 			#[test]
 			fn $name() {
 				let wasm = wat::parse_str($wat).unwrap();
-				let schedule = Schedule::default();
-				let r = prepare_contract::<TestEnv>(wasm.as_ref(), &schedule);
+				let schedule = Schedule::<Test>::default();
+				let r = prepare_contract::<TestEnv, Test>(wasm.as_ref(), &schedule);
 				assert_matches!(r, $($expected)*);
 			}
 		};
@@ -446,7 +483,7 @@ mod tests {
 		// Tests below assumes that maximum page number is configured to a certain number.
 		#[test]
 		fn assume_memory_size() {
-			assert_eq!(Schedule::default().max_memory_pages, 16);
+			assert_eq!(Schedule::<Test>::default().limits.memory_pages, 16);
 		}
 
 		prepare_test!(memory_with_one_page,
@@ -575,7 +612,7 @@ mod tests {
 		// Tests below assumes that maximum table size is configured to a certain number.
 		#[test]
 		fn assume_table_size() {
-			assert_eq!(Schedule::default().max_table_size, 16384);
+			assert_eq!(Schedule::<Test>::default().limits.table_size, 16384);
 		}
 
 		prepare_test!(no_tables,
@@ -703,9 +740,9 @@ mod tests {
 				)
 				"#
 			).unwrap();
-			let mut schedule = Schedule::default();
+			let mut schedule = Schedule::<Test>::default();
 			schedule.enable_println = true;
-			let r = prepare_contract::<TestEnv>(wasm.as_ref(), &schedule);
+			let r = prepare_contract::<TestEnv, Test>(wasm.as_ref(), &schedule);
 			assert_matches!(r, Ok(_));
 		}
 	}
@@ -16,7 +16,8 @@
 
 //! Environment definition of the wasm smart-contract runtime.
 
-use crate::{Schedule, Trait, CodeHash, BalanceOf, Error};
+use crate::{HostFnWeights, Schedule, Trait, CodeHash, BalanceOf, Error};
+use crate::chain_extension::{ChainExtension, Environment, RetVal};
 use crate::exec::{
 	Ext, ExecResult, ExecReturnValue, StorageKey, TopicOf, ReturnFlags, ExecError
 };
@@ -28,7 +29,7 @@ use frame_system;
 use frame_support::dispatch::DispatchError;
 use sp_std::prelude::*;
 use codec::{Decode, Encode};
-use sp_runtime::traits::{Bounded, SaturatedConversion};
+use sp_runtime::traits::SaturatedConversion;
 use sp_io::hashing::{
 	keccak_256,
 	blake2_256,
@@ -63,6 +64,9 @@ pub enum ReturnCode {
 	/// The contract that was called is either no contract at all (a plain account)
 	/// or is a tombstone.
 	NotCallable = 8,
+	/// The call to `ext_debug_message` had no effect because debug message
+	/// recording was disabled.
+	LoggingDisabled = 9,
 }
 
 impl ConvertibleToWasm for ReturnCode {
@@ -119,7 +123,7 @@ enum TrapReason {
 pub(crate) struct Runtime<'a, E: Ext + 'a> {
 	ext: &'a mut E,
 	input_data: Option<Vec<u8>>,
-	schedule: &'a Schedule,
+	schedule: &'a Schedule<E::T>,
 	memory: sp_sandbox::Memory,
 	gas_meter: &'a mut GasMeter<E::T>,
 	trap_reason: Option<TrapReason>,
@@ -128,7 +132,7 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 	pub(crate) fn new(
 		ext: &'a mut E,
 		input_data: Vec<u8>,
-		schedule: &'a Schedule,
+		schedule: &'a Schedule<E::T>,
 		memory: sp_sandbox::Memory,
 		gas_meter: &'a mut GasMeter<E::T>,
 	) -> Self {
@@ -141,6 +145,60 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 			trap_reason: None,
 		}
 	}
+
+	/// Get a mutable reference to the inner `Ext`.
+	pub(crate) fn ext(&mut self) -> &mut E {
+		self.ext
+	}
+
+	/// Charge the gas meter with the weight requested by a chain extension.
+	pub(crate) fn charge_chain_extension(&mut self, amount: Gas) -> Result<(), DispatchError> {
+		charge_gas(self, RuntimeToken::ChainExtension(amount))
+			.map_err(|_| self.take_trap_error())
+	}
+
+	/// Same as the free function `read_sandbox_memory` but returns the error
+	/// instead of storing it as trap reason.
+	///
+	/// The copy is charged per byte like the data buffer passed to `ext_return`.
+	pub(crate) fn read_sandbox_memory(&mut self, ptr: u32, len: u32)
+		-> Result<Vec<u8>, DispatchError>
+	{
+		charge_gas(self, RuntimeToken::CopyFromContract(len))
+			.and_then(|_| read_sandbox_memory(self, ptr, len))
+			.map_err(|_| self.take_trap_error())
+	}
+
+	/// Same as the free function `write_sandbox_output` but returns the error
+	/// instead of storing it as trap reason.
+	///
+	/// The copy is charged per byte like the input buffer copied by `ext_input`.
+	pub(crate) fn write_sandbox_output(
+		&mut self,
+		out_ptr: u32,
+		out_len_ptr: u32,
+		buf: &[u8],
+		allow_skip: bool,
+	) -> Result<(), DispatchError> {
+		write_sandbox_output(
+			self,
+			out_ptr,
+			out_len_ptr,
+			buf,
+			allow_skip,
+			|len| Some(RuntimeToken::CopyToContract(len)),
+		).map_err(|_| self.take_trap_error())
+	}
+
+	/// Take the error stored by a failed host function helper.
+	///
+	/// Sandbox memory errors that are not stored explicitly are reported as out of bounds.
+	fn take_trap_error(&mut self) -> DispatchError {
+		match self.trap_reason.take() {
+			Some(TrapReason::SupervisorError(err)) => err,
+			_ => Error::<E::T>::OutOfBounds.into(),
+		}
+	}
 }
 
 /// Converts the sandbox result and the runtime state into the execution outcome.
@@ -204,136 +262,195 @@ pub(crate) fn to_execution_result<E: Ext>(
 #[cfg_attr(test, derive(Debug, PartialEq, Eq))]
 #[derive(Copy, Clone)]
 pub enum RuntimeToken {
-	/// Explicit call to the `gas` function. Charge the gas meter
-	/// with the value provided.
-	Explicit(u32),
-	/// The given number of bytes is read from the sandbox memory.
-	ReadMemory(u32),
-	/// The given number of bytes is written to the sandbox memory.
-	WriteMemory(u32),
-	/// The given number of bytes is read from the sandbox memory and
-	/// is returned as the return data buffer of the call.
-	ReturnData(u32),
-	/// (topic_count, data_bytes): A buffer of the given size is posted as an event indexed with the
-	/// given number of topics.
-	DepositEvent(u32, u32),
+	/// Charge the gas meter with the cost of a metering block. The charged costs are
+	/// the supplied cost of the block plus the overhead of the metering itself.
+	MeteringBlock(u32),
+	/// Weight of calling `ext_caller`.
+	Caller,
+	/// Weight of calling `ext_address`.
+	Address,
+	/// Weight of calling `ext_gas_left`.
+	GasLeft,
+	/// Weight of calling `ext_balance`.
+	Balance,
+	/// Weight of calling `ext_value_transferred`.
+	ValueTransferred,
+	/// Weight of calling `ext_minimum_balance`.
+	MinimumBalance,
+	/// Weight of calling `ext_tombstone_deposit`.
+	TombstoneDeposit,
+	/// Weight of calling `ext_rent_allowance`.
+	RentAllowance,
+	/// Weight of calling `ext_block_number`.
+	BlockNumber,
+	/// Weight of calling `ext_now`.
+	Now,
+	/// Weight of calling `ext_weight_to_fee`.
+	WeightToFee,
+	/// Weight of calling `ext_input` without the weight of copying the input.
+	InputBase,
+	/// Weight of copying the input data for the given size.
+	InputCopyOut(u32),
+	/// Weight of calling `ext_return` for the given output size.
+	Return(u32),
+	/// Weight of calling `ext_terminate`.
+	Terminate,
+	/// Weight of calling `ext_restore_to` per number of supplied delta entries.
+	RestoreTo(u32),
+	/// Weight of calling `ext_random`. It includes the weight for copying the subject.
+	Random,
+	/// Weight of calling `ext_debug_message`.
+	DebugMessage,
+	/// Weight of calling `ext_deposit_event` with the given number of topics and event size.
+	DepositEvent{num_topic: u32, len: u32},
+	/// Weight of calling `ext_set_rent_allowance`.
+	SetRentAllowance,
+	/// Weight of calling `ext_set_storage` for the given storage item size.
+	SetStorage(u32),
+	/// Weight of calling `ext_clear_storage`.
+	ClearStorage,
+	/// Weight of calling `ext_get_storage` without output weight.
+	GetStorageBase,
+	/// Weight of an item received via `ext_get_storage` for the given size.
+	GetStorageCopyOut(u32),
+	/// Weight of the input that is passed to `ext_call` for the given size.
+	/// The base weight of the call is charged by the execution context.
+	CallInput(u32),
+	/// Weight of the output of a call that is copied back to the contract.
+	CallCopyOut(u32),
+	/// Weight of the input that is passed to `ext_instantiate` for the given size.
+	/// The base weight of the instantiation is charged by the execution context.
+	InstantiateInput(u32),
+	/// Weight of the output of an instantiation that is copied back to the contract.
+	InstantiateCopyOut(u32),
+	/// Weight of calling `ext_hash_sha_256` for the given input size.
+	HashSha256(u32),
+	/// Weight of calling `ext_hash_keccak_256` for the given input size.
+	HashKeccak256(u32),
+	/// Weight of calling `ext_hash_blake2_256` for the given input size.
+	HashBlake256(u32),
+	/// Weight of calling `ext_hash_blake2_128` for the given input size.
+	HashBlake128(u32),
+	/// Weight charged by a chain extension through its `Environment`.
+	ChainExtension(Gas),
+	/// Weight of a chain extension reading the given number of bytes from the contract.
+	CopyFromContract(u32),
+	/// Weight of a chain extension writing the given number of bytes to the contract.
+	CopyToContract(u32),
 }
 
 impl<T: Trait> Token<T> for RuntimeToken {
-	type Metadata = Schedule;
+	type Metadata = HostFnWeights<T>;
 
-	fn calculate_amount(&self, metadata: &Schedule) -> Gas {
+	fn calculate_amount(&self, s: &Self::Metadata) -> Gas {
 		use self::RuntimeToken::*;
-		let value = match *self {
-			Explicit(amount) => Some(amount.into()),
-			ReadMemory(byte_count) => metadata
-				.sandbox_data_read_cost
-				.checked_mul(byte_count.into()),
-			WriteMemory(byte_count) => metadata
-				.sandbox_data_write_cost
-				.checked_mul(byte_count.into()),
-			ReturnData(byte_count) => metadata
-				.return_data_per_byte_cost
-				.checked_mul(byte_count.into()),
-			DepositEvent(topic_count, data_byte_count) => {
-				let data_cost = metadata
-					.event_data_per_byte_cost
-					.checked_mul(data_byte_count.into());
-
-				let topics_cost = metadata
-					.event_per_topic_cost
-					.checked_mul(topic_count.into());
-
-				data_cost
-					.and_then(|data_cost| {
-						topics_cost.and_then(|topics_cost| {
-							data_cost.checked_add(topics_cost)
-						})
-					})
-					.and_then(|data_and_topics_cost|
-						data_and_topics_cost.checked_add(metadata.event_base_cost)
-					)
-			},
-		};
-
-		value.unwrap_or_else(|| Bounded::max_value())
+		match *self {
+			MeteringBlock(amount) => s.gas.saturating_add(amount.into()),
+			Caller => s.caller,
+			Address => s.address,
+			GasLeft => s.gas_left,
+			Balance => s.balance,
+			ValueTransferred => s.value_transferred,
+			MinimumBalance => s.minimum_balance,
+			TombstoneDeposit => s.tombstone_deposit,
+			RentAllowance => s.rent_allowance,
+			BlockNumber => s.block_number,
+			Now => s.now,
+			WeightToFee => s.weight_to_fee,
+			InputBase => s.input,
+			InputCopyOut(len) => s.input_per_byte.saturating_mul(len.into()),
+			Return(len) => s.r#return
+				.saturating_add(s.return_per_byte.saturating_mul(len.into())),
+			Terminate => s.terminate,
+			RestoreTo(delta) => s.restore_to
+				.saturating_add(s.restore_to_per_delta.saturating_mul(delta.into())),
+			Random => s.random,
+			DebugMessage => s.debug_message,
+			DepositEvent{num_topic, len} => s.deposit_event
+				.saturating_add(s.deposit_event_per_topic.saturating_mul(num_topic.into()))
+				.saturating_add(s.deposit_event_per_byte.saturating_mul(len.into())),
+			SetRentAllowance => s.set_rent_allowance,
+			SetStorage(len) => s.set_storage
+				.saturating_add(s.set_storage_per_byte.saturating_mul(len.into())),
+			ClearStorage => s.clear_storage,
+			GetStorageBase => s.get_storage,
+			GetStorageCopyOut(len) => s.get_storage_per_byte.saturating_mul(len.into()),
+			CallInput(len) => s.call_per_input_byte.saturating_mul(len.into()),
+			CallCopyOut(len) => s.call_per_output_byte.saturating_mul(len.into()),
+			InstantiateInput(len) => s.instantiate_per_input_byte.saturating_mul(len.into()),
+			InstantiateCopyOut(len) => s.instantiate_per_output_byte.saturating_mul(len.into()),
+			HashSha256(len) => s.hash_sha2_256
+				.saturating_add(s.hash_sha2_256_per_byte.saturating_mul(len.into())),
+			HashKeccak256(len) => s.hash_keccak_256
+				.saturating_add(s.hash_keccak_256_per_byte.saturating_mul(len.into())),
+			HashBlake256(len) => s.hash_blake2_256
+				.saturating_add(s.hash_blake2_256_per_byte.saturating_mul(len.into())),
+			HashBlake128(len) => s.hash_blake2_128
+				.saturating_add(s.hash_blake2_128_per_byte.saturating_mul(len.into())),
+			ChainExtension(amount) => amount,
+			CopyFromContract(len) => s.return_per_byte.saturating_mul(len.into()),
+			CopyToContract(len) => s.input_per_byte.saturating_mul(len.into()),
+		}
 	}
 }
 
 /// Charge the gas meter with the specified token.
 ///
 /// Returns `Err(HostError)` if there is not enough gas.
-fn charge_gas<T: Trait, Tok: Token<T>>(
-	gas_meter: &mut GasMeter<T>,
-	metadata: &Tok::Metadata,
-	trap_reason: &mut Option<TrapReason>,
-	token: Tok,
-) -> Result<(), sp_sandbox::HostError> {
-	match gas_meter.charge(metadata, token) {
+fn charge_gas<E: Ext>(ctx: &mut Runtime<E>, token: RuntimeToken)
+	-> Result<(), sp_sandbox::HostError>
+{
+	match ctx.gas_meter.charge(&ctx.schedule.host_fn_weights, token) {
 		GasMeterResult::Proceed => Ok(()),
 		GasMeterResult::OutOfGas =>  {
-			*trap_reason = Some(TrapReason::SupervisorError(Error::<T>::OutOfGas.into()));
+			ctx.trap_reason = Some(
+				TrapReason::SupervisorError(Error::<E::T>::OutOfGas.into())
+			);
 			Err(sp_sandbox::HostError)
 		},
 	}
 }
 
-/// Read designated chunk from the sandbox memory, consuming an appropriate amount of
-/// gas.
+/// Used by host functions that charge the weight of writing their output upfront.
 ///
-/// Returns `Err` if one of the following conditions occurs:
+/// Pass it as `create_token` to `write_sandbox_output` in order to skip charging
+/// for the copy a second time.
+fn already_charged(_: u32) -> Option<RuntimeToken> {
+	None
+}
+
+/// Read designated chunk from the sandbox memory.
 ///
-/// - calculating the gas cost resulted in overflow.
-/// - out of gas
-/// - requested buffer is not within the bounds of the sandbox memory.
+/// The weight of the read is not charged by this function. Every host function
+/// charges its weight upfront depending on the sizes of its arguments.
+///
+/// Returns `Err` if the requested buffer is not within the bounds of the sandbox memory.
 fn read_sandbox_memory<E: Ext>(
 	ctx: &mut Runtime<E>,
 	ptr: u32,
 	len: u32,
 ) -> Result<Vec<u8>, sp_sandbox::HostError> {
-	charge_gas(
-		ctx.gas_meter,
-		ctx.schedule,
-		&mut ctx.trap_reason,
-		RuntimeToken::ReadMemory(len),
-	)?;
-
 	let mut buf = vec![0u8; len as usize];
 	ctx.memory.get(ptr, buf.as_mut_slice())
 		.map_err(|_| store_err(ctx, Error::<E::T>::OutOfBounds))?;
 	Ok(buf)
 }
 
-/// Read designated chunk from the sandbox memory into the supplied buffer, consuming
-/// an appropriate amount of gas.
-///
-/// Returns `Err` if one of the following conditions occurs:
+/// Read designated chunk from the sandbox memory into the supplied buffer.
 ///
-/// - calculating the gas cost resulted in overflow.
-/// - out of gas
-/// - requested buffer is not within the bounds of the sandbox memory.
+/// Returns `Err` if the requested buffer is not within the bounds of the sandbox memory.
 fn read_sandbox_memory_into_buf<E: Ext>(
 	ctx: &mut Runtime<E>,
 	ptr: u32,
 	buf: &mut [u8],
 ) -> Result<(), sp_sandbox::HostError> {
-	charge_gas(
-		ctx.gas_meter,
-		ctx.schedule,
-		&mut ctx.trap_reason,
-		RuntimeToken::ReadMemory(buf.len() as u32),
-	)?;
-
 	ctx.memory.get(ptr, buf).map_err(|_| store_err(ctx, Error::<E::T>::OutOfBounds))
 }
 
-/// Read designated chunk from the sandbox memory, consuming an appropriate amount of
-/// gas, and attempt to decode into the specified type.
+/// Read designated chunk from the sandbox memory and attempt to decode into the specified type.
 ///
 /// Returns `Err` if one of the following conditions occurs:
 ///
-/// - calculating the gas cost resulted in overflow.
-/// - out of gas
 /// - requested buffer is not within the bounds of the sandbox memory.
 /// - the buffer contents cannot be decoded as the required type.
 fn read_sandbox_memory_as<E: Ext, D: Decode>(
@@ -345,26 +462,14 @@ fn read_sandbox_memory_as<E: Ext, D: Decode>(
 	D::decode(&mut &buf[..]).map_err(|_| store_err(ctx, Error::<E::T>::DecodingFailed))
 }
 
-/// Write the given buffer to the designated location in the sandbox memory, consuming
-/// an appropriate amount of gas.
-///
-/// Returns `Err` if one of the following conditions occurs:
+/// Write the given buffer to the designated location in the sandbox memory.
 ///
-/// - calculating the gas cost resulted in overflow.
-/// - out of gas
-/// - designated area is not within the bounds of the sandbox memory.
+/// Returns `Err` if the designated area is not within the bounds of the sandbox memory.
 fn write_sandbox_memory<E: Ext>(
 	ctx: &mut Runtime<E>,
 	ptr: u32,
 	buf: &[u8],
 ) -> Result<(), sp_sandbox::HostError> {
-	charge_gas(
-		ctx.gas_meter,
-		ctx.schedule,
-		&mut ctx.trap_reason,
-		RuntimeToken::WriteMemory(buf.len() as u32),
-	)?;
-
 	ctx.memory.set(ptr, buf)
 		.map_err(|_| store_err(ctx, Error::<E::T>::OutOfBounds))
 }
@@ -381,14 +486,20 @@ fn write_sandbox_memory<E: Ext>(
 /// output optional. For example to skip copying back the output buffer of an `ext_call`
 /// when the caller is not interested in the result.
 ///
+/// The weight of the copy is determined by `create_token` which receives the length of `buf`.
+/// It is only charged when the copy is actually carried out. Host functions that already
+/// included the copy in their base weight pass `already_charged`.
+///
 /// In addition to the error conditions of `write_sandbox_memory` this functions returns
-/// `Err` if the size of the buffer located at `out_ptr` is too small to fit `buf`.
+/// `Err` if the size of the buffer located at `out_ptr` is too small to fit `buf`
+/// or if there is not enough gas left for the copy.
 fn write_sandbox_output<E: Ext>(
 	ctx: &mut Runtime<E>,
 	out_ptr: u32,
 	out_len_ptr: u32,
 	buf: &[u8],
 	allow_skip: bool,
+	create_token: impl FnOnce(u32) -> Option<RuntimeToken>,
 ) -> Result<(), sp_sandbox::HostError> {
 	if allow_skip && out_ptr == u32::max_value() {
 		return Ok(());
@@ -401,12 +512,9 @@ fn write_sandbox_output<E: Ext>(
 		Err(store_err(ctx, Error::<E::T>::OutputBufferTooSmall))?
 	}
 
-	charge_gas(
-		ctx.gas_meter,
-		ctx.schedule,
-		&mut ctx.trap_reason,
-		RuntimeToken::WriteMemory(buf_len.saturating_add(4)),
-	)?;
+	if let Some(token) = create_token(buf_len) {
+		charge_gas(ctx, token)?;
+	}
 
 	ctx.memory.set(out_ptr, buf)?;
 	ctx.memory.set(out_len_ptr, &buf_len.encode())?;
@@ -514,12 +622,7 @@ define_env!(Env, <E: Ext>,
 	//
 	// - amount: How much gas is used.
 	gas(ctx, amount: u32) => {
-		charge_gas(
-			&mut ctx.gas_meter,
-			ctx.schedule,
-			&mut ctx.trap_reason,
-			RuntimeToken::Explicit(amount)
-		)?;
+		charge_gas(ctx, RuntimeToken::MeteringBlock(amount))?;
 		Ok(())
 	},
 
@@ -543,6 +646,7 @@ define_env!(Env, <E: Ext>,
 			// Bail out if value length exceeds the set maximum value size.
 			return Err(sp_sandbox::HostError);
 		}
+		charge_gas(ctx, RuntimeToken::SetStorage(value_len))?;
 		let mut key: StorageKey = [0; 32];
 		read_sandbox_memory_into_buf(ctx, key_ptr, &mut key)?;
 		let value = Some(read_sandbox_memory(ctx, value_ptr, value_len)?);
@@ -556,6 +660,7 @@ define_env!(Env, <E: Ext>,
 	//
 	// - `key_ptr`: pointer into the linear memory where the location to clear the value is placed.
 	ext_clear_storage(ctx, key_ptr: u32) => {
+		charge_gas(ctx, RuntimeToken::ClearStorage)?;
 		let mut key: StorageKey = [0; 32];
 		read_sandbox_memory_into_buf(ctx, key_ptr, &mut key)?;
 		ctx.ext.set_storage(key, None);
@@ -575,10 +680,13 @@ define_env!(Env, <E: Ext>,
 	//
 	// `ReturnCode::KeyNotFound`
 	ext_get_storage(ctx, key_ptr: u32, out_ptr: u32, out_len_ptr: u32) -> ReturnCode => {
+		charge_gas(ctx, RuntimeToken::GetStorageBase)?;
 		let mut key: StorageKey = [0; 32];
 		read_sandbox_memory_into_buf(ctx, key_ptr, &mut key)?;
 		if let Some(value) = ctx.ext.get_storage(&key) {
-			write_sandbox_output(ctx, out_ptr, out_len_ptr, &value, false)?;
+			write_sandbox_output(ctx, out_ptr, out_len_ptr, &value, false, |len| {
+				Some(RuntimeToken::GetStorageCopyOut(len))
+			})?;
 			Ok(ReturnCode::Success)
 		} else {
 			Ok(ReturnCode::KeyNotFound)
@@ -600,6 +708,10 @@ define_env!(Env, <E: Ext>,
 	//
 	// `ReturnCode::BelowSubsistenceThreshold`
 	// `ReturnCode::TransferFailed`
+	//
+	// # Note
+	//
+	// The weight of the transfer is charged by the execution context.
 	ext_transfer(
 		ctx,
 		account_ptr: u32,
@@ -662,6 +774,7 @@ define_env!(Env, <E: Ext>,
 		let callee: <<E as Ext>::T as frame_system::Trait>::AccountId =
 			read_sandbox_memory_as(ctx, callee_ptr, callee_len)?;
 		let value: BalanceOf<<E as Ext>::T> = read_sandbox_memory_as(ctx, value_ptr, value_len)?;
+		charge_gas(ctx, RuntimeToken::CallInput(input_data_len))?;
 		let input_data = read_sandbox_memory(ctx, input_data_ptr, input_data_len)?;
 
 		let nested_gas_limit = if gas == 0 {
@@ -686,7 +799,9 @@ define_env!(Env, <E: Ext>,
 		});
 
 		if let Ok(output) = &call_outcome {
-			write_sandbox_output(ctx, output_ptr, output_len_ptr, &output.data, true)?;
+			write_sandbox_output(ctx, output_ptr, output_len_ptr, &output.data, true, |len| {
+				Some(RuntimeToken::CallCopyOut(len))
+			})?;
 		}
 		map_exec_result(ctx, call_outcome)
 	},
@@ -751,6 +866,7 @@ define_env!(Env, <E: Ext>,
 		let code_hash: CodeHash<<E as Ext>::T> =
 			read_sandbox_memory_as(ctx, code_hash_ptr, code_hash_len)?;
 		let value: BalanceOf<<E as Ext>::T> = read_sandbox_memory_as(ctx, value_ptr, value_len)?;
+		charge_gas(ctx, RuntimeToken::InstantiateInput(input_data_len))?;
 		let input_data = read_sandbox_memory(ctx, input_data_ptr, input_data_len)?;
 
 		let nested_gas_limit = if gas == 0 {
@@ -776,10 +892,12 @@ define_env!(Env, <E: Ext>,
 		if let Ok((address, output)) = &instantiate_outcome {
 			if !output.flags.contains(ReturnFlags::REVERT) {
 				write_sandbox_output(
-					ctx, address_ptr, address_len_ptr, &address.encode(), true
+					ctx, address_ptr, address_len_ptr, &address.encode(), true, already_charged,
 				)?;
 			}
-			write_sandbox_output(ctx, output_ptr, output_len_ptr, &output.data, true)?;
+			write_sandbox_output(ctx, output_ptr, output_len_ptr, &output.data, true, |len| {
+				Some(RuntimeToken::InstantiateCopyOut(len))
+			})?;
 		}
 		map_exec_result(ctx, instantiate_outcome.map(|(_id, retval)| retval))
 	},
@@ -803,6 +921,7 @@ define_env!(Env, <E: Ext>,
 		beneficiary_ptr: u32,
 		beneficiary_len: u32
 	) => {
+		charge_gas(ctx, RuntimeToken::Terminate)?;
 		let beneficiary: <<E as Ext>::T as frame_system::Trait>::AccountId =
 			read_sandbox_memory_as(ctx, beneficiary_ptr, beneficiary_len)?;
 
@@ -813,8 +932,11 @@ define_env!(Env, <E: Ext>,
 	},
 
 	ext_input(ctx, buf_ptr: u32, buf_len_ptr: u32) => {
+		charge_gas(ctx, RuntimeToken::InputBase)?;
 		if let Some(input) = ctx.input_data.take() {
-			write_sandbox_output(ctx, buf_ptr, buf_len_ptr, &input, false)
+			write_sandbox_output(ctx, buf_ptr, buf_len_ptr, &input, false, |len| {
+				Some(RuntimeToken::InputCopyOut(len))
+			})
 		} else {
 			Err(sp_sandbox::HostError)
 		}
@@ -838,12 +960,7 @@ define_env!(Env, <E: Ext>,
 	//
 	// Using a reserved bit triggers a trap.
 	ext_return(ctx, flags: u32, data_ptr: u32, data_len: u32) => {
-		charge_gas(
-			ctx.gas_meter,
-			ctx.schedule,
-			&mut ctx.trap_reason,
-			RuntimeToken::ReturnData(data_len)
-		)?;
+		charge_gas(ctx, RuntimeToken::Return(data_len))?;
 
 		ctx.trap_reason = Some(TrapReason::Return(ReturnData {
 			flags,
@@ -867,7 +984,10 @@ define_env!(Env, <E: Ext>,
 	// extrinsic will be returned. Otherwise, if this call is initiated by another contract then the
 	// address of the contract will be returned. The value is encoded as T::AccountId.
 	ext_caller(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		write_sandbox_output(ctx, out_ptr, out_len_ptr, &ctx.ext.caller().encode(), false)
+		charge_gas(ctx, RuntimeToken::Caller)?;
+		write_sandbox_output(
+			ctx, out_ptr, out_len_ptr, &ctx.ext.caller().encode(), false, already_charged
+		)
 	},
 
 	// Stores the address of the current contract into the supplied buffer.
@@ -877,7 +997,10 @@ define_env!(Env, <E: Ext>,
 	// `out_ptr`. This call overwrites it with the size of the value. If the available
 	// space at `out_ptr` is less than the size of the value a trap is triggered.
 	ext_address(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		write_sandbox_output(ctx, out_ptr, out_len_ptr, &ctx.ext.address().encode(), false)
+		charge_gas(ctx, RuntimeToken::Address)?;
+		write_sandbox_output(
+			ctx, out_ptr, out_len_ptr, &ctx.ext.address().encode(), false, already_charged
+		)
 	},
 
 	// Stores the price for the specified amount of gas into the supplied buffer.
@@ -894,8 +1017,10 @@ define_env!(Env, <E: Ext>,
 	// It is recommended to avoid specifying very small values for `gas` as the prices for a single
 	// gas can be smaller than one.
 	ext_weight_to_fee(ctx, gas: u64, out_ptr: u32, out_len_ptr: u32) => {
+		charge_gas(ctx, RuntimeToken::WeightToFee)?;
 		write_sandbox_output(
-			ctx, out_ptr, out_len_ptr, &ctx.ext.get_weight_price(gas).encode(), false
+			ctx, out_ptr, out_len_ptr, &ctx.ext.get_weight_price(gas).encode(), false,
+			already_charged,
 		)
 	},
 
@@ -908,7 +1033,10 @@ define_env!(Env, <E: Ext>,
 	//
 	// The data is encoded as Gas.
 	ext_gas_left(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		write_sandbox_output(ctx, out_ptr, out_len_ptr, &ctx.gas_meter.gas_left().encode(), false)
+		charge_gas(ctx, RuntimeToken::GasLeft)?;
+		write_sandbox_output(
+			ctx, out_ptr, out_len_ptr, &ctx.gas_meter.gas_left().encode(), false, already_charged
+		)
 	},
 
 	// Stores the balance of the current account into the supplied buffer.
@@ -920,7 +1048,10 @@ define_env!(Env, <E: Ext>,
 	//
 	// The data is encoded as T::Balance.
 	ext_balance(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		write_sandbox_output(ctx, out_ptr, out_len_ptr, &ctx.ext.balance().encode(), false)
+		charge_gas(ctx, RuntimeToken::Balance)?;
+		write_sandbox_output(
+			ctx, out_ptr, out_len_ptr, &ctx.ext.balance().encode(), false, already_charged
+		)
 	},
 
 	// Stores the value transferred along with this call or as endowment into the supplied buffer.
@@ -932,8 +1063,9 @@ define_env!(Env, <E: Ext>,
 	//
 	// The data is encoded as T::Balance.
 	ext_value_transferred(ctx, out_ptr: u32, out_len_ptr: u32) => {
+		charge_gas(ctx, RuntimeToken::ValueTransferred)?;
 		write_sandbox_output(
-			ctx, out_ptr, out_len_ptr, &ctx.ext.value_transferred().encode(), false
+			ctx, out_ptr, out_len_ptr, &ctx.ext.value_transferred().encode(), false, already_charged
 		)
 	},
 
@@ -946,13 +1078,15 @@ define_env!(Env, <E: Ext>,
 	//
 	// The data is encoded as T::Hash.
 	ext_random(ctx, subject_ptr: u32, subject_len: u32, out_ptr: u32, out_len_ptr: u32) => {
-		// The length of a subject can't exceed `max_subject_len`.
-		if subject_len > ctx.schedule.max_subject_len {
+		charge_gas(ctx, RuntimeToken::Random)?;
+		// The length of a subject can't exceed `subject_len`.
+		if subject_len > ctx.schedule.limits.subject_len {
 			return Err(sp_sandbox::HostError);
 		}
 		let subject_buf = read_sandbox_memory(ctx, subject_ptr, subject_len)?;
 		write_sandbox_output(
-			ctx, out_ptr, out_len_ptr, &ctx.ext.random(&subject_buf).encode(), false
+			ctx, out_ptr, out_len_ptr, &ctx.ext.random(&subject_buf).encode(), false,
+			already_charged,
 		)
 	},
 
@@ -963,14 +1097,20 @@ define_env!(Env, <E: Ext>,
 	// `out_ptr`. This call overwrites it with the size of the value. If the available
 	// space at `out_ptr` is less than the size of the value a trap is triggered.
 	ext_now(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		write_sandbox_output(ctx, out_ptr, out_len_ptr, &ctx.ext.now().encode(), false)
+		charge_gas(ctx, RuntimeToken::Now)?;
+		write_sandbox_output(
+			ctx, out_ptr, out_len_ptr, &ctx.ext.now().encode(), false, already_charged
+		)
 	},
 
 	// Stores the minimum balance (a.k.a. existential deposit) into the supplied buffer.
 	//
 	// The data is encoded as T::Balance.
 	ext_minimum_balance(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		write_sandbox_output(ctx, out_ptr, out_len_ptr, &ctx.ext.minimum_balance().encode(), false)
+		charge_gas(ctx, RuntimeToken::MinimumBalance)?;
+		write_sandbox_output(
+			ctx, out_ptr, out_len_ptr, &ctx.ext.minimum_balance().encode(), false, already_charged
+		)
 	},
 
 	// Stores the tombstone deposit into the supplied buffer.
@@ -989,8 +1129,9 @@ define_env!(Env, <E: Ext>,
 	// below the sum of existential deposit and the tombstone deposit. The sum
 	// is commonly referred as subsistence threshold in code.
 	ext_tombstone_deposit(ctx, out_ptr: u32, out_len_ptr: u32) => {
+		charge_gas(ctx, RuntimeToken::TombstoneDeposit)?;
 		write_sandbox_output(
-			ctx, out_ptr, out_len_ptr, &ctx.ext.tombstone_deposit().encode(), false
+			ctx, out_ptr, out_len_ptr, &ctx.ext.tombstone_deposit().encode(), false, already_charged
 		)
 	},
 
@@ -1031,6 +1172,7 @@ define_env!(Env, <E: Ext>,
 		delta_ptr: u32,
 		delta_count: u32
 	) => {
+		charge_gas(ctx, RuntimeToken::RestoreTo(delta_count))?;
 		let dest: <<E as Ext>::T as frame_system::Trait>::AccountId =
 			read_sandbox_memory_as(ctx, dest_ptr, dest_len)?;
 		let code_hash: CodeHash<<E as Ext>::T> =
@@ -1070,7 +1212,7 @@ define_env!(Env, <E: Ext>,
 	},
 
 	// Deposit a contract event with the data buffer and optional list of topics. There is a limit
-	// on the maximum number of topics specified by `max_event_topics`.
+	// on the maximum number of topics specified by `event_topics`.
 	//
 	// - topics_ptr - a pointer to the buffer of topics encoded as `Vec<T::Hash>`. The value of this
 	//   is ignored if `topics_len` is set to 0. The topics list can't contain duplicates.
@@ -1078,13 +1220,20 @@ define_env!(Env, <E: Ext>,
 	// - data_ptr - a pointer to a raw data buffer which will saved along the event.
 	// - data_len - the length of the data buffer.
 	ext_deposit_event(ctx, topics_ptr: u32, topics_len: u32, data_ptr: u32, data_len: u32) => {
+		let num_topic = topics_len
+			.checked_div(sp_std::mem::size_of::<TopicOf<E::T>>() as u32)
+			.ok_or_else(|| store_err(ctx, "Zero sized topics are not allowed"))?;
+		charge_gas(ctx, RuntimeToken::DepositEvent {
+			num_topic,
+			len: data_len,
+		})?;
 		let mut topics: Vec::<TopicOf<<E as Ext>::T>> = match topics_len {
 			0 => Vec::new(),
 			_ => read_sandbox_memory_as(ctx, topics_ptr, topics_len)?,
 		};
 
-		// If there are more than `max_event_topics`, then trap.
-		if topics.len() > ctx.schedule.max_event_topics as usize {
+		// If there are more than `event_topics`, then trap.
+		if topics.len() > ctx.schedule.limits.event_topics as usize {
 			return Err(sp_sandbox::HostError);
 		}
 
@@ -1095,12 +1244,6 @@ define_env!(Env, <E: Ext>,
 
 		let event_data = read_sandbox_memory(ctx, data_ptr, data_len)?;
 
-		charge_gas(
-			ctx.gas_meter,
-			ctx.schedule,
-			&mut ctx.trap_reason,
-			RuntimeToken::DepositEvent(topics.len() as u32, data_len)
-		)?;
 		ctx.ext.deposit_event(topics, event_data);
 
 		Ok(())
@@ -1112,6 +1255,7 @@ define_env!(Env, <E: Ext>,
 	//   Should be decodable as a `T::Balance`. Traps otherwise.
 	// - value_len: length of the value buffer.
 	ext_set_rent_allowance(ctx, value_ptr: u32, value_len: u32) => {
+		charge_gas(ctx, RuntimeToken::SetRentAllowance)?;
 		let value: BalanceOf<<E as Ext>::T> =
 			read_sandbox_memory_as(ctx, value_ptr, value_len)?;
 		ctx.ext.set_rent_allowance(value);
@@ -1128,12 +1272,17 @@ define_env!(Env, <E: Ext>,
 	//
 	// The data is encoded as T::Balance.
 	ext_rent_allowance(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		write_sandbox_output(ctx, out_ptr, out_len_ptr, &ctx.ext.rent_allowance().encode(), false)
+		charge_gas(ctx, RuntimeToken::RentAllowance)?;
+		write_sandbox_output(
+			ctx, out_ptr, out_len_ptr, &ctx.ext.rent_allowance().encode(), false, already_charged
+		)
 	},
 
 	// Prints utf8 encoded string from the data buffer.
 	// Only available on `--dev` chains.
 	// This function may be removed at any time, superseded by a more general contract debugging feature.
+	//
+	// It is not charged any weight because it is never available on production chains.
 	ext_println(ctx, str_ptr: u32, str_len: u32) => {
 		let data = read_sandbox_memory(ctx, str_ptr, str_len)?;
 		if let Ok(utf8) = core::str::from_utf8(&data) {
@@ -1142,6 +1291,37 @@ define_env!(Env, <E: Ext>,
 		Ok(())
 	},
 
+	// Emit a custom debug message.
+	//
+	// The message is appended to the debug buffer which is then supplied to the caller
+	// of a dry-run through the `call` runtime API. Messages are never recorded when the
+	// contract is executed on-chain. In this case the message is ignored and
+	// `ReturnCode::LoggingDisabled` is returned.
+	//
+	// # Parameters
+	//
+	// - str_ptr: a pointer to the UTF-8 encoded message.
+	// - str_len: the length of the message in bytes.
+	//
+	// # Errors
+	//
+	// `ReturnCode::LoggingDisabled`
+	//
+	// # Traps
+	//
+	// - The message is not valid UTF-8.
+	ext_debug_message(ctx, str_ptr: u32, str_len: u32) -> ReturnCode => {
+		charge_gas(ctx, RuntimeToken::DebugMessage)?;
+		if ctx.ext.append_debug_buffer("") {
+			let data = read_sandbox_memory(ctx, str_ptr, str_len)?;
+			let msg = core::str::from_utf8(&data)
+				.map_err(|_| store_err(ctx, Error::<E::T>::DebugMessageInvalidUTF8))?;
+			ctx.ext.append_debug_buffer(msg);
+			return Ok(ReturnCode::Success);
+		}
+		Ok(ReturnCode::LoggingDisabled)
+	},
+
 	// Stores the current block number of the current contract into the supplied buffer.
 	//
 	// The value is stored to linear memory at the address pointed to by `out_ptr`.
@@ -1149,7 +1329,10 @@ define_env!(Env, <E: Ext>,
 	// `out_ptr`. This call overwrites it with the size of the value. If the available
 	// space at `out_ptr` is less than the size of the value a trap is triggered.
 	ext_block_number(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		write_sandbox_output(ctx, out_ptr, out_len_ptr, &ctx.ext.block_number().encode(), false)
+		charge_gas(ctx, RuntimeToken::BlockNumber)?;
+		write_sandbox_output(
+			ctx, out_ptr, out_len_ptr, &ctx.ext.block_number().encode(), false, already_charged
+		)
 	},
 
 	// Computes the SHA2 256-bit hash on the given input buffer.
@@ -1173,6 +1356,7 @@ define_env!(Env, <E: Ext>,
 	//                 data is placed. The function will write the result
 	//                 directly into this buffer.
 	ext_hash_sha2_256(ctx, input_ptr: u32, input_len: u32, output_ptr: u32) => {
+		charge_gas(ctx, RuntimeToken::HashSha256(input_len))?;
 		compute_hash_on_intermediate_buffer(ctx, sha2_256, input_ptr, input_len, output_ptr)
 	},
 
@@ -1197,6 +1381,7 @@ define_env!(Env, <E: Ext>,
 	//                 data is placed. The function will write the result
 	//                 directly into this buffer.
 	ext_hash_keccak_256(ctx, input_ptr: u32, input_len: u32, output_ptr: u32) => {
+		charge_gas(ctx, RuntimeToken::HashKeccak256(input_len))?;
 		compute_hash_on_intermediate_buffer(ctx, keccak_256, input_ptr, input_len, output_ptr)
 	},
 
@@ -1221,6 +1406,7 @@ define_env!(Env, <E: Ext>,
 	//                 data is placed. The function will write the result
 	//                 directly into this buffer.
 	ext_hash_blake2_256(ctx, input_ptr: u32, input_len: u32, output_ptr: u32) => {
+		charge_gas(ctx, RuntimeToken::HashBlake256(input_len))?;
 		compute_hash_on_intermediate_buffer(ctx, blake2_256, input_ptr, input_len, output_ptr)
 	},
 
@@ -1245,8 +1431,54 @@ define_env!(Env, <E: Ext>,
 	//                 data is placed. The function will write the result
 	//                 directly into this buffer.
 	ext_hash_blake2_128(ctx, input_ptr: u32, input_len: u32, output_ptr: u32) => {
+		charge_gas(ctx, RuntimeToken::HashBlake128(input_len))?;
 		compute_hash_on_intermediate_buffer(ctx, blake2_128, input_ptr, input_len, output_ptr)
 	},
+
+	// Call into the chain extension provided by the chain if any.
+	//
+	// Handling of the input values is up to the specific chain extension and so is the
+	// return value. The extension can decide to use the inputs as primitive inputs or as
+	// in/out arguments by interpreting them as pointers. Any caller of this function
+	// must therefore coordinate with the chain that it targets.
+	//
+	// # Parameters
+	//
+	// - `func_id`: identifies the function of the chain extension that should be called.
+	// - `input_ptr`: pointer into the linear memory where the input data is placed.
+	// - `input_len`: the length of the input data in bytes.
+	// - `output_ptr`: pointer into the linear memory where the output data is written to.
+	// - `output_len_ptr`: in-out pointer to where the length of the output buffer is read
+	//   from and the actual length of the output is written to.
+	//
+	// # Note
+	//
+	// If no chain extension exists the contract will trap with the `NoChainExtension`
+	// module error.
+	ext_call_chain_extension(
+		ctx,
+		func_id: u32,
+		input_ptr: u32,
+		input_len: u32,
+		output_ptr: u32,
+		output_len_ptr: u32
+	) -> u32 => {
+		if !<E::T as Trait>::ChainExtension::enabled() {
+			Err(store_err(ctx, Error::<E::T>::NoChainExtension))?;
+		}
+		let env = Environment::new(ctx, input_ptr, input_len, output_ptr, output_len_ptr);
+		match <E::T as Trait>::ChainExtension::call(func_id, env) {
+			Ok(RetVal::Converging(val)) => Ok(val),
+			Ok(RetVal::Diverging{flags, data}) => {
+				ctx.trap_reason = Some(TrapReason::Return(ReturnData {
+					flags: flags.bits(),
+					data,
+				}));
+				Err(sp_sandbox::HostError)
+			},
+			Err(err) => Err(store_err(ctx, err)),
+		}
+	},
 );
 
 /// Computes the given hash function on the supplied input.
@@ -27,9 +27,9 @@ use crate::types::Delegations;
 pub enum Conviction {
 	/// 0.1x votes, unlocked.
 	None,
-	/// 1x votes, locked for an enactment period following a successful vote.
+	/// 1x votes, locked for a lock period following a successful vote.
 	Locked1x,
-	/// 2x votes, locked for 2x enactment periods following a successful vote.
+	/// 2x votes, locked for 2x lock periods following a successful vote.
 	Locked2x,
 	/// 3x votes, locked for 4x...
 	Locked3x,
@@ -0,0 +1,672 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Conviction Voting Pallet
+//!
+//! - [`conviction_voting::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! The Conviction Voting pallet handles voting with conviction and delegation on the polls of
+//! another pallet, the _polling pallet_, given as `Trait::Polls`. Several polling pallets may
+//! each be given their own instance of it.
+//!
+//! Token holders vote on polls with a _conviction_, which multiplies their voting power in
+//! exchange for locking their tokens for longer once the poll ended in their favour. Polls are
+//! grouped in classes, and an account may delegate its voting power for the polls of a class to
+//! another account, independently of the other classes. Tokens locked for different classes
+//! overlap, the account's lock is the biggest lock of all its classes.
+//!
+//! ### Terminology
+//!
+//! - **Poll:** Something voted on, e.g. a referendum, identified by the index given by the
+//!   polling pallet.
+//! - **Class:** A group of polls, e.g. the track of a referendum.
+//! - **Lock Period:** A period of time after a poll ended that the tokens of _winning_ voters
+//!   will be locked, multiplied by the conviction of their vote.
+//! - **Conviction:** An indication of a voter's strength of belief in their vote. An increase
+//!   of one in conviction indicates that a token holder is willing to lock their tokens for twice
+//!   as many lock periods.
+//! - **Delegation:** The act of granting your voting power on the polls of a class to the
+//!   decisions of another account for up to a certain conviction.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `vote` - Votes on an ongoing poll, either "Aye" or "Nay".
+//! - `delegate` - Delegates the voting power (tokens * conviction) on a class to another account.
+//! - `undelegate` - Stops the delegation of voting power on a class to another account.
+//! - `unlock` - Redetermine the account's balance lock for a class, potentially making tokens
+//!   available.
+//! - `remove_vote` - Remove a vote, e.g. one whose lock expired.
+//! - `remove_other_vote` - Remove an expired vote of some other account.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use sp_runtime::{
+	DispatchResult, DispatchError,
+	traits::{Zero, Saturating},
+};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure,
+	weights::Weight,
+	traits::{
+		Currency, ReservableCurrency, LockableCurrency, WithdrawReason, LockIdentifier, Get,
+		Polling, PollStatus,
+	},
+	dispatch::DispatchResultWithPostInfo,
+};
+use frame_system::{self as system, ensure_signed};
+
+mod vote;
+mod conviction;
+mod types;
+mod default_weight;
+pub use vote::{Vote, AccountVote, Voting, PriorLock};
+pub use conviction::Conviction;
+pub use types::{Tally, UnvoteScope, Delegations};
+
+#[cfg(test)]
+mod tests;
+
+const CONVICTION_VOTING_ID: LockIdentifier = *b"pyconvot";
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// The tally of the polls voted on with this pallet.
+pub type TallyOf<T> = Tally<BalanceOf<T>, <T as Trait>::MaxTurnout>;
+
+/// The index of a poll.
+pub type PollIndexOf<T> = <<T as Trait>::Polls as Polling<TallyOf<T>>>::Index;
+
+/// The class of a poll.
+pub type ClassOf<T> = <<T as Trait>::Polls as Polling<TallyOf<T>>>::Class;
+
+/// The voting state of an account for a class.
+pub type VotingOf<T> = Voting<
+	BalanceOf<T>,
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	PollIndexOf<T>,
+>;
+
+pub trait WeightInfo {
+	fn vote_new(r: u32, ) -> Weight;
+	fn vote_existing(r: u32, ) -> Weight;
+	fn remove_vote(r: u32, ) -> Weight;
+	fn remove_other_vote(r: u32, ) -> Weight;
+	fn delegate(r: u32, ) -> Weight;
+	fn undelegate(r: u32, ) -> Weight;
+	fn unlock(r: u32, ) -> Weight;
+}
+
+pub trait Trait: frame_system::Trait + Sized {
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Currency type with which voting happens.
+	type Currency: ReservableCurrency<Self::AccountId>
+		+ LockableCurrency<Self::AccountId, Moment=Self::BlockNumber>;
+
+	/// The polling pallet hosting the polls voted on.
+	type Polls: Polling<TallyOf<Self>, Votes=BalanceOf<Self>, Moment=Self::BlockNumber>;
+
+	/// The maximum amount of tokens which may be used for voting, against which the support of
+	/// a poll is measured. Usually the total issuance.
+	type MaxTurnout: Get<BalanceOf<Self>>;
+
+	/// The maximum number of concurrent votes an account may have in a class.
+	///
+	/// Also used to compute weight, an overly large value can
+	/// lead to extrinsic with large weight estimation: see `delegate` for instance.
+	type MaxVotes: Get<u32>;
+
+	/// The minimum period of vote locking.
+	///
+	/// It should be no shorter than the enactment period of the polls to ensure that, in the
+	/// case of an approval, those successful voters are locked into the consequences that their
+	/// votes entail.
+	type VoteLockingPeriod: Get<Self::BlockNumber>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as ConvictionVoting {
+		/// All voting for a particular voter in a particular voting class. We store the balance
+		/// for the number of votes that we have recorded.
+		///
+		/// TWOX-NOTE: SAFE as `AccountId`s are crypto hashes anyway and classes are not under an
+		/// attacker's control.
+		pub VotingFor get(fn voting_for):
+			double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) ClassOf<T>
+			=> VotingOf<T>;
+
+		/// The voting classes which have a non-zero lock requirement and the lock amounts which
+		/// they require. The actual amount locked on behalf of this pallet should always be the
+		/// maximum of this list.
+		///
+		/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+		pub ClassLocksFor get(fn class_locks_for):
+			map hasher(twox_64_concat) T::AccountId => Vec<(ClassOf<T>, BalanceOf<T>)>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		<T as frame_system::Trait>::AccountId,
+	{
+		/// An account has delegated their vote to another account. [who, target]
+		Delegated(AccountId, AccountId),
+		/// An [account] has cancelled a previous delegation operation.
+		Undelegated(AccountId),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Poll is not ongoing.
+		NotOngoing,
+		/// The given account did not vote on the poll.
+		NotVoter,
+		/// The actor has no permission to conduct the action.
+		NoPermission,
+		/// The account is already delegating.
+		AlreadyDelegating,
+		/// The account currently has votes attached to it and the operation cannot succeed until
+		/// these are removed, either through `unvote` or `reap_vote`.
+		AlreadyVoting,
+		/// Too high a balance was provided that the account cannot afford.
+		InsufficientFunds,
+		/// The account is not currently delegating.
+		NotDelegating,
+		/// Delegation to oneself makes no sense.
+		Nonsense,
+		/// Maximum number of votes reached.
+		MaxVotesReached,
+		/// The class must be supplied since it is not easily determinable from the state.
+		ClassNeeded,
+		/// The class ID supplied is invalid.
+		BadClass,
+		/// An unexpected integer overflow occurred.
+		Overflow,
+		/// An unexpected integer underflow occurred.
+		Underflow,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The maximum number of concurrent votes an account may have in a class.
+		const MaxVotes: u32 = T::MaxVotes::get();
+
+		/// The minimum period of vote locking.
+		const VoteLockingPeriod: T::BlockNumber = T::VoteLockingPeriod::get();
+
+		fn deposit_event() = default;
+
+		/// Vote in a poll. If `vote.is_aye()`, the vote is to enact the proposal;
+		/// otherwise it is a vote to keep the status quo.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_index`: The index of the poll to vote for.
+		/// - `vote`: The vote configuration.
+		///
+		/// # <weight>
+		/// - Complexity: `O(R)` where R is the number of polls the voter has voted on in the
+		///   class of the poll. Weight is charged as if maximum votes.
+		/// - Db reads: poll, `VotingFor`, `ClassLocksFor`, `balances locks`
+		/// - Db writes: poll, `VotingFor`, `ClassLocksFor`, `balances locks`
+		/// # </weight>
+		#[weight = T::WeightInfo::vote_new(T::MaxVotes::get())
+			.max(T::WeightInfo::vote_existing(T::MaxVotes::get()))]
+		fn vote(origin,
+			#[compact] poll_index: PollIndexOf<T>,
+			vote: AccountVote<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::try_vote(&who, poll_index, vote)
+		}
+
+		/// Delegate the voting power (with some given conviction) of the sending account for a
+		/// particular class of polls.
+		///
+		/// The balance delegated is locked for as long as it's delegated, and thereafter for the
+		/// time appropriate for the conviction's lock period.
+		///
+		/// The dispatch origin of this call must be _Signed_, and the signing account must either:
+		///   - be delegating already; or
+		///   - have no voting activity in the class (if there is, then it will need to be
+		///     removed through `remove_vote`).
+		///
+		/// - `class`: The class of polls to delegate the voting power for.
+		/// - `to`: The account whose voting the `target` account's voting power will follow.
+		/// - `conviction`: The conviction that will be attached to the delegated votes. When the
+		///   account is undelegated, the funds will be locked for the corresponding period.
+		/// - `balance`: The amount of the account's balance to be used in delegating. This must
+		///   not be more than the account's current balance.
+		///
+		/// Emits `Delegated`.
+		///
+		/// # <weight>
+		/// - Complexity: `O(R)` where R is the number of polls the voter delegating to has
+		///   voted on in the class. Weight is charged as if maximum votes.
+		/// - Db reads: 3*`VotingFor`, `ClassLocksFor`, `origin account locks`
+		/// - Db writes: 3*`VotingFor`, `ClassLocksFor`, `origin account locks`
+		/// - Db reads per votes: poll
+		/// - Db writes per votes: poll
+		/// # </weight>
+		#[weight = T::WeightInfo::delegate(T::MaxVotes::get())]
+		fn delegate(
+			origin,
+			class: ClassOf<T>,
+			to: T::AccountId,
+			conviction: Conviction,
+			balance: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let votes = Self::try_delegate(who, class, to, conviction, balance)?;
+
+			Ok(Some(T::WeightInfo::delegate(votes)).into())
+		}
+
+		/// Undelegate the voting power of the sending account for a particular class of polls.
+		///
+		/// Tokens may be unlocked following once an amount of time consistent with the lock period
+		/// of the conviction with which the delegation was issued.
+		///
+		/// The dispatch origin of this call must be _Signed_ and the signing account must be
+		/// currently delegating for the class.
+		///
+		/// - `class`: The class of polls to remove the delegation from.
+		///
+		/// Emits `Undelegated`.
+		///
+		/// # <weight>
+		/// - Complexity: `O(R)` where R is the number of polls the voter delegating to has
+		///   voted on in the class. Weight is charged as if maximum votes.
+		/// - Db reads: 2*`VotingFor`
+		/// - Db writes: 2*`VotingFor`
+		/// - Db reads per votes: poll
+		/// - Db writes per votes: poll
+		/// # </weight>
+		#[weight = T::WeightInfo::undelegate(T::MaxVotes::get())]
+		fn undelegate(origin, class: ClassOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let votes = Self::try_undelegate(who, class)?;
+			Ok(Some(T::WeightInfo::undelegate(votes)).into())
+		}
+
+		/// Remove the lock caused by prior voting/delegating which has expired within a particular
+		/// class.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `class`: The class of polls to unlock.
+		/// - `target`: The account to remove the lock on.
+		///
+		/// # <weight>
+		/// - Complexity `O(R)` with R number of vote of target in the class.
+		/// - Db reads: `VotingFor`, `ClassLocksFor`, `balances locks`, `target account`
+		/// - Db writes: `VotingFor`, `ClassLocksFor`, `balances locks`, `target account`
+		/// # </weight>
+		#[weight = T::WeightInfo::unlock(T::MaxVotes::get())]
+		fn unlock(origin, class: ClassOf<T>, target: T::AccountId) {
+			ensure_signed(origin)?;
+			Self::update_lock(&class, &target);
+		}
+
+		/// Remove a vote for a poll.
+		///
+		/// If:
+		/// - the poll was cancelled, or
+		/// - the poll is ongoing, or
+		/// - the poll has ended such that
+		///   - the vote of the account was in opposition to the result; or
+		///   - there was no conviction to the account's vote; or
+		///   - the account made a split vote
+		/// ...then the vote is removed cleanly and a following call to `unlock` may result in more
+		/// funds being available.
+		///
+		/// If, however, the poll has ended and:
+		/// - it finished corresponding to the vote of the account, and
+		/// - the account made a standard vote with conviction, and
+		/// - the lock period of the conviction is not over
+		/// ...then the lock will be aggregated into the overall account's lock, which may involve
+		/// *overlocking* (where the two locks are combined into a single lock that is the maximum
+		/// of both the amount locked and the time is it locked for).
+		///
+		/// The dispatch origin of this call must be _Signed_, and the signer must have a vote
+		/// registered for poll `index`.
+		///
+		/// - `class`: Optional parameter, if given it indicates the class of the poll. For polls
+		///   which have finished or are cancelled, this must be `Some`.
+		/// - `index`: The index of poll of the vote to be removed.
+		///
+		/// # <weight>
+		/// - `O(R + log R)` where R is the number of polls that `target` has voted on in the
+		///   class. Weight is calculated for the maximum number of vote.
+		/// - Db reads: poll, `VotingFor`
+		/// - Db writes: poll, `VotingFor`
+		/// # </weight>
+		#[weight = T::WeightInfo::remove_vote(T::MaxVotes::get())]
+		fn remove_vote(origin, class: Option<ClassOf<T>>, index: PollIndexOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::try_remove_vote(&who, index, class, UnvoteScope::Any)
+		}
+
+		/// Remove a vote for a poll.
+		///
+		/// If the `target` is equal to the signer, then this function is exactly equivalent to
+		/// `remove_vote`. If not equal to the signer, then the vote must have expired,
+		/// either because the poll was cancelled, because the voter lost the poll or
+		/// because the conviction period is over.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `target`: The account of the vote to be removed; this account must have voted for
+		///   poll `index`.
+		/// - `class`: The class of the poll.
+		/// - `index`: The index of poll of the vote to be removed.
+		///
+		/// # <weight>
+		/// - `O(R + log R)` where R is the number of polls that `target` has voted on in the
+		///   class. Weight is calculated for the maximum number of vote.
+		/// - Db reads: poll, `VotingFor`
+		/// - Db writes: poll, `VotingFor`
+		/// # </weight>
+		#[weight = T::WeightInfo::remove_other_vote(T::MaxVotes::get())]
+		fn remove_other_vote(
+			origin,
+			target: T::AccountId,
+			class: ClassOf<T>,
+			index: PollIndexOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let scope = if target == who { UnvoteScope::Any } else { UnvoteScope::OnlyExpired };
+			Self::try_remove_vote(&target, index, Some(class), scope)
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Actually enact a vote, if legit.
+	fn try_vote(
+		who: &T::AccountId,
+		poll_index: PollIndexOf<T>,
+		vote: AccountVote<BalanceOf<T>>,
+	) -> DispatchResult {
+		ensure!(vote.balance() <= T::Currency::free_balance(who), Error::<T>::InsufficientFunds);
+		T::Polls::try_access_poll(poll_index, |poll_status| {
+			let (tally, class) = poll_status.ensure_ongoing().ok_or(Error::<T>::NotOngoing)?;
+			VotingFor::<T>::try_mutate(who, &class, |voting| -> DispatchResult {
+				if let Voting::Direct { ref mut votes, delegations, .. } = voting {
+					match votes.binary_search_by_key(&poll_index, |i| i.0) {
+						Ok(i) => {
+							// Shouldn't be possible to fail, but we handle it gracefully.
+							tally.remove(votes[i].1).ok_or(Error::<T>::Underflow)?;
+							if let Some(approve) = votes[i].1.as_standard() {
+								tally.reduce(approve, *delegations);
+							}
+							votes[i].1 = vote;
+						}
+						Err(i) => {
+							ensure!(
+								(votes.len() as u32) < T::MaxVotes::get(),
+								Error::<T>::MaxVotesReached,
+							);
+							votes.insert(i, (poll_index, vote));
+						}
+					}
+					// Shouldn't be possible to fail, but we handle it gracefully.
+					tally.add(vote).ok_or(Error::<T>::Overflow)?;
+					if let Some(approve) = vote.as_standard() {
+						tally.increase(approve, *delegations);
+					}
+					Ok(())
+				} else {
+					Err(Error::<T>::AlreadyDelegating.into())
+				}
+			})?;
+			// Extend the lock to `balance` (rather than setting it) since we don't know what
+			// other votes are in place.
+			Self::extend_lock(who, &class, vote.balance());
+			Ok(())
+		})
+	}
+
+	/// Remove the account's vote for the given poll if possible. This is possible when:
+	/// - The poll has not finished.
+	/// - The poll has finished and the voter lost their direction.
+	/// - The poll has finished and the voter's lock period is up.
+	///
+	/// This will generally be combined with a call to `unlock`.
+	fn try_remove_vote(
+		who: &T::AccountId,
+		poll_index: PollIndexOf<T>,
+		class_hint: Option<ClassOf<T>>,
+		scope: UnvoteScope,
+	) -> DispatchResult {
+		let class = class_hint
+			.or_else(|| Some(T::Polls::as_ongoing(poll_index)?.1))
+			.ok_or(Error::<T>::ClassNeeded)?;
+		VotingFor::<T>::try_mutate(who, class, |voting| -> DispatchResult {
+			if let Voting::Direct { ref mut votes, delegations, ref mut prior } = voting {
+				let i = votes.binary_search_by_key(&poll_index, |i| i.0)
+					.map_err(|_| Error::<T>::NotVoter)?;
+				let v = votes.remove(i);
+
+				T::Polls::try_access_poll(poll_index, |poll_status| match poll_status {
+					PollStatus::Ongoing(tally, _) => {
+						ensure!(matches!(scope, UnvoteScope::Any), Error::<T>::NoPermission);
+						// Shouldn't be possible to fail, but we handle it gracefully.
+						tally.remove(v.1).ok_or(Error::<T>::Underflow)?;
+						if let Some(approve) = v.1.as_standard() {
+							tally.reduce(approve, *delegations);
+						}
+						Ok(())
+					},
+					PollStatus::Completed(end, approved) => {
+						if let Some((lock_periods, balance)) = v.1.locked_if(approved) {
+							let unlock_at = end + T::VoteLockingPeriod::get() * lock_periods.into();
+							let now = system::Module::<T>::block_number();
+							if now < unlock_at {
+								ensure!(matches!(scope, UnvoteScope::Any), Error::<T>::NoPermission);
+								prior.accumulate(unlock_at, balance)
+							}
+						}
+						Ok(())
+					},
+					PollStatus::None => Ok(()), // Poll was cancelled.
+				})
+			} else {
+				Ok(())
+			}
+		})
+	}
+
+	/// Return the number of votes for `who`
+	fn increase_upstream_delegation(
+		who: &T::AccountId,
+		class: &ClassOf<T>,
+		amount: Delegations<BalanceOf<T>>,
+	) -> u32 {
+		VotingFor::<T>::mutate(who, class, |voting| match voting {
+			Voting::Delegating { delegations, .. } => {
+				// We don't support second level delegating, so we don't need to do anything more.
+				*delegations = delegations.saturating_add(amount);
+				1
+			},
+			Voting::Direct { votes, delegations, .. } => {
+				*delegations = delegations.saturating_add(amount);
+				for &(poll_index, account_vote) in votes.iter() {
+					if let AccountVote::Standard { vote, .. } = account_vote {
+						T::Polls::access_poll(poll_index, |poll_status| {
+							if let PollStatus::Ongoing(tally, _) = poll_status {
+								tally.increase(vote.aye, amount);
+							}
+						});
+					}
+				}
+				votes.len() as u32
+			}
+		})
+	}
+
+	/// Return the number of votes for `who`
+	fn reduce_upstream_delegation(
+		who: &T::AccountId,
+		class: &ClassOf<T>,
+		amount: Delegations<BalanceOf<T>>,
+	) -> u32 {
+		VotingFor::<T>::mutate(who, class, |voting| match voting {
+			Voting::Delegating { delegations, .. } => {
+				// We don't support second level delegating, so we don't need to do anything more.
+				*delegations = delegations.saturating_sub(amount);
+				1
+			},
+			Voting::Direct { votes, delegations, .. } => {
+				*delegations = delegations.saturating_sub(amount);
+				for &(poll_index, account_vote) in votes.iter() {
+					if let AccountVote::Standard { vote, .. } = account_vote {
+						T::Polls::access_poll(poll_index, |poll_status| {
+							if let PollStatus::Ongoing(tally, _) = poll_status {
+								tally.reduce(vote.aye, amount);
+							}
+						});
+					}
+				}
+				votes.len() as u32
+			}
+		})
+	}
+
+	/// Attempt to delegate `balance` times `conviction` of voting power from `who` to `target`.
+	///
+	/// Return the upstream number of votes.
+	fn try_delegate(
+		who: T::AccountId,
+		class: ClassOf<T>,
+		target: T::AccountId,
+		conviction: Conviction,
+		balance: BalanceOf<T>,
+	) -> Result<u32, DispatchError> {
+		ensure!(who != target, Error::<T>::Nonsense);
+		ensure!(T::Polls::classes().contains(&class), Error::<T>::BadClass);
+		ensure!(balance <= T::Currency::free_balance(&who), Error::<T>::InsufficientFunds);
+		let votes = VotingFor::<T>::try_mutate(&who, &class, |voting| -> Result<u32, DispatchError> {
+			let mut old = Voting::Delegating {
+				balance,
+				target: target.clone(),
+				conviction,
+				delegations: Default::default(),
+				prior: Default::default(),
+			};
+			sp_std::mem::swap(&mut old, voting);
+			match old {
+				Voting::Delegating { balance, target, conviction, delegations, prior, .. } => {
+					// remove any delegation votes to our current target.
+					Self::reduce_upstream_delegation(&target, &class, conviction.votes(balance));
+					voting.set_common(delegations, prior);
+				}
+				Voting::Direct { votes, delegations, prior } => {
+					// here we just ensure that we're currently idling with no votes recorded.
+					ensure!(votes.is_empty(), Error::<T>::AlreadyVoting);
+					voting.set_common(delegations, prior);
+				}
+			}
+			let votes = Self::increase_upstream_delegation(&target, &class, conviction.votes(balance));
+			// Extend the lock to `balance` (rather than setting it) since we don't know what other
+			// votes are in place.
+			Self::extend_lock(&who, &class, balance);
+			Ok(votes)
+		})?;
+		Self::deposit_event(Event::<T>::Delegated(who, target));
+		Ok(votes)
+	}
+
+	/// Attempt to end the current delegation.
+	///
+	/// Return the number of votes of upstream.
+	fn try_undelegate(who: T::AccountId, class: ClassOf<T>) -> Result<u32, DispatchError> {
+		let votes = VotingFor::<T>::try_mutate(&who, &class, |voting| -> Result<u32, DispatchError> {
+			let mut old = Voting::default();
+			sp_std::mem::swap(&mut old, voting);
+			match old {
+				Voting::Delegating {
+					balance,
+					target,
+					conviction,
+					delegations,
+					mut prior,
+				} => {
+					// remove any delegation votes to our current target.
+					let votes = Self::reduce_upstream_delegation(&target, &class, conviction.votes(balance));
+					let now = system::Module::<T>::block_number();
+					let lock_periods = conviction.lock_periods().into();
+					prior.accumulate(now + T::VoteLockingPeriod::get() * lock_periods, balance);
+					voting.set_common(delegations, prior);
+
+					Ok(votes)
+				}
+				Voting::Direct { .. } => {
+					Err(Error::<T>::NotDelegating.into())
+				}
+			}
+		})?;
+		Self::deposit_event(Event::<T>::Undelegated(who));
+		Ok(votes)
+	}
+
+	/// Extend the lock of `who` for `class` to at least `amount`.
+	fn extend_lock(who: &T::AccountId, class: &ClassOf<T>, amount: BalanceOf<T>) {
+		ClassLocksFor::<T>::mutate(who, |locks| {
+			match locks.iter().position(|x| &x.0 == class) {
+				Some(i) => locks[i].1 = locks[i].1.max(amount),
+				None => locks.push((class.clone(), amount)),
+			}
+		});
+		T::Currency::extend_lock(CONVICTION_VOTING_ID, who, amount, WithdrawReason::Transfer.into());
+	}
+
+	/// Rejig the lock on an account. It will never get more stringent (since that would indicate
+	/// a security hole) but may be reduced from what they are currently.
+	fn update_lock(class: &ClassOf<T>, who: &T::AccountId) {
+		let class_lock_needed = VotingFor::<T>::mutate(who, class, |voting| {
+			voting.rejig(system::Module::<T>::block_number());
+			voting.locked_balance()
+		});
+		let lock_needed = ClassLocksFor::<T>::mutate(who, |locks| {
+			locks.retain(|x| &x.0 != class);
+			if !class_lock_needed.is_zero() {
+				locks.push((class.clone(), class_lock_needed));
+			}
+			locks.iter().map(|x| x.1).max().unwrap_or_else(Zero::zero)
+		});
+		if lock_needed.is_zero() {
+			T::Currency::remove_lock(CONVICTION_VOTING_ID, who);
+		} else {
+			T::Currency::set_lock(CONVICTION_VOTING_ID, who, lock_needed, WithdrawReason::Transfer.into());
+		}
+	}
+}
@@ -0,0 +1,410 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The crate's tests.
+
+use super::*;
+use std::{cell::RefCell, collections::BTreeMap, convert::TryFrom};
+use frame_support::{
+	impl_outer_origin, assert_noop, assert_ok, parameter_types, weights::Weight,
+	traits::VoteTally,
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	testing::Header, Perbill,
+};
+use pallet_balances::BalanceLock;
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1_000_000;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = ();
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TestPollState {
+	Ongoing(TallyOf<Test>, u8),
+	Completed(u64, bool),
+}
+use TestPollState::*;
+
+thread_local! {
+	static POLLS: RefCell<BTreeMap<u8, TestPollState>> = RefCell::new(vec![
+		(1, Ongoing(Tally::default(), 0)),
+		(2, Ongoing(Tally::default(), 1)),
+		(3, Completed(1, true)),
+		(4, Ongoing(Tally::default(), 0)),
+	].into_iter().collect());
+}
+
+/// Polls kept in a thread local, whose state is set by the tests.
+pub struct TestPolls;
+impl TestPolls {
+	fn set(index: u8, state: TestPollState) {
+		POLLS.with(|p| p.borrow_mut().insert(index, state));
+	}
+}
+
+impl Polling<TallyOf<Test>> for TestPolls {
+	type Index = u8;
+	type Votes = u64;
+	type Class = u8;
+	type Moment = u64;
+
+	fn classes() -> Vec<u8> {
+		vec![0, 1]
+	}
+
+	fn as_ongoing(index: u8) -> Option<(TallyOf<Test>, u8)> {
+		POLLS.with(|p| match p.borrow().get(&index) {
+			Some(Ongoing(tally, class)) => Some((tally.clone(), *class)),
+			_ => None,
+		})
+	}
+
+	fn access_poll<R>(
+		index: u8,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> R,
+	) -> R {
+		let mut polls = POLLS.with(|p| p.borrow().clone());
+		let r = match polls.get_mut(&index) {
+			Some(Ongoing(ref mut tally, class)) => f(PollStatus::Ongoing(tally, *class)),
+			Some(Completed(when, approved)) => f(PollStatus::Completed(*when, *approved)),
+			None => f(PollStatus::None),
+		};
+		POLLS.with(|p| *p.borrow_mut() = polls);
+		r
+	}
+
+	fn try_access_poll<R>(
+		index: u8,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		let mut polls = POLLS.with(|p| p.borrow().clone());
+		let r = match polls.get_mut(&index) {
+			Some(Ongoing(ref mut tally, class)) => f(PollStatus::Ongoing(tally, *class)),
+			Some(Completed(when, approved)) => f(PollStatus::Completed(*when, *approved)),
+			None => f(PollStatus::None),
+		}?;
+		POLLS.with(|p| *p.borrow_mut() = polls);
+		Ok(r)
+	}
+}
+
+pub struct TotalIssuance;
+impl Get<u64> for TotalIssuance {
+	fn get() -> u64 { Balances::total_issuance() }
+}
+
+parameter_types! {
+	pub const MaxVotes: u32 = 3;
+	pub const VoteLockingPeriod: u64 = 10;
+}
+impl Trait for Test {
+	type Event = ();
+	type Currency = pallet_balances::Module<Self>;
+	type Polls = TestPolls;
+	type MaxTurnout = TotalIssuance;
+	type MaxVotes = MaxVotes;
+	type VoteLockingPeriod = VoteLockingPeriod;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test>{
+		balances: vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type ConvictionVoting = Module<Test>;
+
+fn aye(x: u8, balance: u64) -> AccountVote<u64> {
+	AccountVote::Standard {
+		vote: Vote { aye: true, conviction: Conviction::try_from(x).unwrap() },
+		balance,
+	}
+}
+
+fn nay(x: u8, balance: u64) -> AccountVote<u64> {
+	AccountVote::Standard {
+		vote: Vote { aye: false, conviction: Conviction::try_from(x).unwrap() },
+		balance,
+	}
+}
+
+fn tally(index: u8) -> TallyOf<Test> {
+	<TestPolls as Polling<TallyOf<Test>>>::as_ongoing(index).expect("index must be valid").0
+}
+
+fn the_lock(amount: u64) -> BalanceLock<u64> {
+	BalanceLock {
+		id: CONVICTION_VOTING_ID,
+		amount,
+		reasons: pallet_balances::Reasons::Misc,
+	}
+}
+
+#[test]
+fn basic_voting_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(2, 5)));
+		assert_eq!(tally(1), Tally::from_parts(10, 0, 5));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, nay(2, 5)));
+		assert_eq!(tally(1), Tally::from_parts(0, 10, 0));
+		assert_eq!(Balances::usable_balance(1), 5);
+
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(5, 10)));
+		assert_eq!(tally(1), Tally::from_parts(50, 0, 10));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, nay(5, 10)));
+		assert_eq!(tally(1), Tally::from_parts(0, 50, 0));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), None, 1));
+		assert_eq!(tally(1), Tally::from_parts(0, 0, 0));
+
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn split_voting_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, AccountVote::Split { aye: 10, nay: 0 }));
+		assert_eq!(tally(1), Tally::from_parts(1, 0, 10));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, AccountVote::Split { aye: 5, nay: 5 }));
+		assert_eq!(tally(1), Tally::from_parts(0, 0, 5));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), None, 1));
+		assert_eq!(tally(1), Tally::from_parts(0, 0, 0));
+
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn support_and_approval_are_measured_against_max_turnout() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(6), 1, aye(1, 42)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(5), 1, nay(1, 42)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(4), 1, aye(0, 40)));
+
+		let tally = tally(1);
+		assert_eq!(tally, Tally::from_parts(46, 42, 82));
+		// 82 of the 210 total issuance supports the poll, 46 of the 88 votes are in favour.
+		assert_eq!(tally.support(), Perbill::from_rational_approximation(82u64, 210));
+		assert_eq!(tally.approval(), Perbill::from_rational_approximation(46u64, 88));
+		assert_eq!(TallyOf::<Test>::default().approval(), Perbill::zero());
+	});
+}
+
+#[test]
+fn voting_only_works_on_ongoing_polls() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ConvictionVoting::vote(Origin::signed(1), 3, aye(1, 10)), Error::<Test>::NotOngoing);
+		assert_noop!(ConvictionVoting::vote(Origin::signed(1), 5, aye(1, 10)), Error::<Test>::NotOngoing);
+		assert_noop!(ConvictionVoting::vote(Origin::signed(1), 1, aye(1, 11)), Error::<Test>::InsufficientFunds);
+	});
+}
+
+#[test]
+fn max_votes_is_enforced_per_class() {
+	new_test_ext().execute_with(|| {
+		for i in 5..8 {
+			TestPolls::set(i, Ongoing(Tally::default(), 0));
+			assert_ok!(ConvictionVoting::vote(Origin::signed(1), i, aye(1, 1)));
+		}
+		TestPolls::set(8, Ongoing(Tally::default(), 0));
+		assert_noop!(ConvictionVoting::vote(Origin::signed(1), 8, aye(1, 1)), Error::<Test>::MaxVotesReached);
+		// The limit is per class.
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 2, aye(1, 1)));
+	});
+}
+
+#[test]
+fn delegation_works_per_class() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(1, 10)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 2, aye(1, 10)));
+
+		assert_ok!(ConvictionVoting::delegate(Origin::signed(2), 0, 1, Conviction::Locked2x, 20));
+		assert_eq!(tally(1), Tally::from_parts(50, 0, 30));
+		// Delegations of class 0 don't apply to class 1.
+		assert_eq!(tally(2), Tally::from_parts(10, 0, 10));
+
+		// Votes cast after the delegation count the delegated votes as well.
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 4, nay(1, 10)));
+		assert_eq!(tally(4), Tally::from_parts(0, 50, 0));
+
+		// A delegating account can't vote in the class, but can in other classes.
+		assert_noop!(ConvictionVoting::vote(Origin::signed(2), 1, aye(1, 20)), Error::<Test>::AlreadyDelegating);
+		assert_ok!(ConvictionVoting::vote(Origin::signed(2), 2, aye(1, 20)));
+		assert_eq!(tally(2), Tally::from_parts(30, 0, 30));
+
+		assert_ok!(ConvictionVoting::undelegate(Origin::signed(2), 0));
+		assert_eq!(tally(1), Tally::from_parts(10, 0, 10));
+		assert_eq!(tally(4), Tally::from_parts(0, 10, 0));
+		assert_noop!(ConvictionVoting::undelegate(Origin::signed(2), 0), Error::<Test>::NotDelegating);
+	});
+}
+
+#[test]
+fn delegation_checks_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ConvictionVoting::delegate(Origin::signed(1), 0, 1, Conviction::Locked1x, 10),
+			Error::<Test>::Nonsense,
+		);
+		assert_noop!(
+			ConvictionVoting::delegate(Origin::signed(1), 2, 2, Conviction::Locked1x, 10),
+			Error::<Test>::BadClass,
+		);
+		assert_noop!(
+			ConvictionVoting::delegate(Origin::signed(1), 0, 2, Conviction::Locked1x, 11),
+			Error::<Test>::InsufficientFunds,
+		);
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(1, 10)));
+		assert_noop!(
+			ConvictionVoting::delegate(Origin::signed(1), 0, 2, Conviction::Locked1x, 10),
+			Error::<Test>::AlreadyVoting,
+		);
+	});
+}
+
+#[test]
+fn locks_expire_after_the_lock_period() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, nay(1, 10)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(2), 1, aye(2, 20)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(3), 1, aye(0, 30)));
+		assert_eq!(Balances::locks(2), vec![the_lock(20)]);
+
+		TestPolls::set(1, Completed(1, true));
+
+		// 1 lost and 3 had no conviction, their votes can be removed by anyone.
+		assert_ok!(ConvictionVoting::remove_other_vote(Origin::signed(4), 1, 0, 1));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(4), 0, 1));
+		assert_eq!(Balances::locks(1), vec![]);
+		assert_ok!(ConvictionVoting::remove_other_vote(Origin::signed(4), 3, 0, 1));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(4), 0, 3));
+		assert_eq!(Balances::locks(3), vec![]);
+
+		// 2 won with `Locked2x`, locked until block 1 + 2 * 10.
+		assert_noop!(ConvictionVoting::remove_other_vote(Origin::signed(4), 2, 0, 1), Error::<Test>::NoPermission);
+		assert_noop!(ConvictionVoting::remove_vote(Origin::signed(2), None, 1), Error::<Test>::ClassNeeded);
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(2), Some(0), 1));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::locks(2), vec![the_lock(20)]);
+
+		System::set_block_number(20);
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::locks(2), vec![the_lock(20)]);
+
+		System::set_block_number(21);
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::locks(2), vec![]);
+	});
+}
+
+#[test]
+fn undelegating_keeps_the_conviction_lock() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::delegate(Origin::signed(2), 0, 1, Conviction::Locked1x, 20));
+		assert_ok!(ConvictionVoting::undelegate(Origin::signed(2), 0));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::locks(2), vec![the_lock(20)]);
+
+		System::set_block_number(11);
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::locks(2), vec![]);
+	});
+}
+
+#[test]
+fn locks_of_classes_overlap() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(1, 10)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 2, aye(1, 5)));
+		assert_eq!(Balances::locks(1), vec![the_lock(10)]);
+		assert_eq!(ConvictionVoting::class_locks_for(1), vec![(0, 10), (1, 5)]);
+
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), None, 1));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::locks(1), vec![the_lock(5)]);
+		assert_eq!(ConvictionVoting::class_locks_for(1), vec![(1, 5)]);
+
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), None, 2));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(1), 1, 1));
+		assert_eq!(Balances::locks(1), vec![]);
+		assert_eq!(ConvictionVoting::class_locks_for(1), vec![]);
+	});
+}
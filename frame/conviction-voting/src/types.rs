@@ -0,0 +1,220 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Miscellaneous additional datatypes.
+
+use sp_std::{fmt, marker::PhantomData};
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, Perbill};
+use sp_runtime::traits::{Zero, CheckedAdd, CheckedSub, Saturating, AtLeast32BitUnsigned};
+use frame_support::traits::{Get, VoteTally};
+use crate::{AccountVote, Conviction};
+
+/// The tally of a poll, in terms of post-conviction votes.
+///
+/// `Total` is the maximum possible turnout, against which the support is measured.
+#[derive(Encode, Decode)]
+pub struct Tally<Votes, Total> {
+	/// The number of aye votes, expressed in terms of post-conviction lock-vote.
+	pub ayes: Votes,
+	/// The number of nay votes, expressed in terms of post-conviction lock-vote.
+	pub nays: Votes,
+	/// The amount of funds currently supporting the poll. Pre-conviction.
+	pub support: Votes,
+	#[codec(skip)]
+	dummy: PhantomData<Total>,
+}
+
+impl<Votes: Clone, Total> Clone for Tally<Votes, Total> {
+	fn clone(&self) -> Self {
+		Self {
+			ayes: self.ayes.clone(),
+			nays: self.nays.clone(),
+			support: self.support.clone(),
+			dummy: PhantomData,
+		}
+	}
+}
+
+impl<Votes: PartialEq, Total> PartialEq for Tally<Votes, Total> {
+	fn eq(&self, other: &Self) -> bool {
+		self.ayes == other.ayes && self.nays == other.nays && self.support == other.support
+	}
+}
+
+impl<Votes: Eq, Total> Eq for Tally<Votes, Total> {}
+
+impl<Votes: fmt::Debug, Total> fmt::Debug for Tally<Votes, Total> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Tally")
+			.field("ayes", &self.ayes)
+			.field("nays", &self.nays)
+			.field("support", &self.support)
+			.finish()
+	}
+}
+
+impl<Votes: Zero, Total> Default for Tally<Votes, Total> {
+	fn default() -> Self {
+		Self { ayes: Zero::zero(), nays: Zero::zero(), support: Zero::zero(), dummy: PhantomData }
+	}
+}
+
+impl<Votes: AtLeast32BitUnsigned + Copy, Total: Get<Votes>> VoteTally<Votes> for Tally<Votes, Total> {
+	fn ayes(&self) -> Votes {
+		self.ayes
+	}
+
+	fn support(&self) -> Perbill {
+		Perbill::from_rational_approximation(self.support, Total::get())
+	}
+
+	fn approval(&self) -> Perbill {
+		Perbill::from_rational_approximation(self.ayes, self.ayes.saturating_add(self.nays))
+	}
+}
+
+impl<Votes: AtLeast32BitUnsigned + Copy, Total> Tally<Votes, Total> {
+	/// Create a tally from its parts.
+	pub fn from_parts(ayes: Votes, nays: Votes, support: Votes) -> Self {
+		Self { ayes, nays, support, dummy: PhantomData }
+	}
+
+	/// Add an account's vote into the tally.
+	pub fn add(
+		&mut self,
+		vote: AccountVote<Votes>,
+	) -> Option<()> {
+		match vote {
+			AccountVote::Standard { vote, balance } => {
+				let Delegations { votes, capital } = vote.conviction.votes(balance);
+				match vote.aye {
+					true => {
+						self.support = self.support.checked_add(&capital)?;
+						self.ayes = self.ayes.checked_add(&votes)?
+					},
+					false => self.nays = self.nays.checked_add(&votes)?,
+				}
+			}
+			AccountVote::Split { aye, nay } => {
+				let aye = Conviction::None.votes(aye);
+				let nay = Conviction::None.votes(nay);
+				self.support = self.support.checked_add(&aye.capital)?;
+				self.ayes = self.ayes.checked_add(&aye.votes)?;
+				self.nays = self.nays.checked_add(&nay.votes)?;
+			}
+		}
+		Some(())
+	}
+
+	/// Remove an account's vote from the tally.
+	pub fn remove(
+		&mut self,
+		vote: AccountVote<Votes>,
+	) -> Option<()> {
+		match vote {
+			AccountVote::Standard { vote, balance } => {
+				let Delegations { votes, capital } = vote.conviction.votes(balance);
+				match vote.aye {
+					true => {
+						self.support = self.support.checked_sub(&capital)?;
+						self.ayes = self.ayes.checked_sub(&votes)?
+					},
+					false => self.nays = self.nays.checked_sub(&votes)?,
+				}
+			}
+			AccountVote::Split { aye, nay } => {
+				let aye = Conviction::None.votes(aye);
+				let nay = Conviction::None.votes(nay);
+				self.support = self.support.checked_sub(&aye.capital)?;
+				self.ayes = self.ayes.checked_sub(&aye.votes)?;
+				self.nays = self.nays.checked_sub(&nay.votes)?;
+			}
+		}
+		Some(())
+	}
+
+	/// Increment some amount of votes.
+	pub fn increase(&mut self, approve: bool, delegations: Delegations<Votes>) {
+		match approve {
+			true => {
+				self.support = self.support.saturating_add(delegations.capital);
+				self.ayes = self.ayes.saturating_add(delegations.votes);
+			},
+			false => self.nays = self.nays.saturating_add(delegations.votes),
+		}
+	}
+
+	/// Decrement some amount of votes.
+	pub fn reduce(&mut self, approve: bool, delegations: Delegations<Votes>) {
+		match approve {
+			true => {
+				self.support = self.support.saturating_sub(delegations.capital);
+				self.ayes = self.ayes.saturating_sub(delegations.votes);
+			},
+			false => self.nays = self.nays.saturating_sub(delegations.votes),
+		}
+	}
+}
+
+/// Amount of votes and capital placed in delegation for an account.
+#[derive(Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Delegations<Balance> {
+	/// The number of votes (this is post-conviction).
+	pub votes: Balance,
+	/// The amount of raw capital, used for the turnout.
+	pub capital: Balance,
+}
+
+impl<Balance: Saturating> Saturating for Delegations<Balance> {
+	fn saturating_add(self, o: Self) -> Self {
+		Self {
+			votes: self.votes.saturating_add(o.votes),
+			capital: self.capital.saturating_add(o.capital),
+		}
+	}
+
+	fn saturating_sub(self, o: Self) -> Self {
+		Self {
+			votes: self.votes.saturating_sub(o.votes),
+			capital: self.capital.saturating_sub(o.capital),
+		}
+	}
+
+	fn saturating_mul(self, o: Self) -> Self {
+		Self {
+			votes: self.votes.saturating_mul(o.votes),
+			capital: self.capital.saturating_mul(o.capital),
+		}
+	}
+
+	fn saturating_pow(self, exp: usize) -> Self {
+		Self {
+			votes: self.votes.saturating_pow(exp),
+			capital: self.capital.saturating_pow(exp),
+		}
+	}
+}
+
+/// Whether an `unvote` operation is able to make actions that are not strictly always in the
+/// interest of an account.
+pub enum UnvoteScope {
+	/// Permitted to do everything.
+	Any,
+	/// Permitted to do only the changes that do not need the owner's permission.
+	OnlyExpired,
+}
@@ -20,7 +20,7 @@
 use sp_std::{prelude::*, result::Result, convert::TryFrom};
 use codec::{Encode, EncodeLike, Decode, Output, Input};
 use sp_runtime::{RuntimeDebug, traits::{Saturating, Zero}};
-use crate::{Conviction, ReferendumIndex, Delegations};
+use crate::{Conviction, Delegations};
 
 /// A number of lock periods, plus a vote, one way or the other.
 #[derive(Copy, Clone, Eq, PartialEq, Default, RuntimeDebug)]
@@ -48,7 +48,7 @@ impl Decode for Vote {
 	}
 }
 
-/// A vote for a referendum of a particular account.
+/// A vote for a poll of a particular account.
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug)]
 pub enum AccountVote<Balance> {
 	/// A standard vote, one-way (approve or reject) with a given amount of conviction.
@@ -60,7 +60,7 @@ pub enum AccountVote<Balance> {
 
 impl<Balance: Saturating> AccountVote<Balance> {
 	/// Returns `Some` of the lock periods that the account is locked for, assuming that the
-	/// referendum passed iff `approved` is `true`.
+	/// poll passed iff `approved` is `true`.
 	pub fn locked_if(self, approved: bool) -> Option<(u32, Balance)> {
 		// winning side: can only be removed after the lock period ends.
 		match self {
@@ -113,12 +113,12 @@ impl<BlockNumber: Ord + Copy + Zero, Balance: Ord + Copy + Zero> PriorLock<Block
 
 /// An indicator for what an account is doing; it can either be delegating or voting.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
-pub enum Voting<Balance, AccountId, BlockNumber> {
+pub enum Voting<Balance, AccountId, BlockNumber, PollIndex> {
 	/// The account is voting directly. `delegations` is the total amount of post-conviction voting
 	/// weight that it controls from those that have delegated to it.
 	Direct {
 		/// The current votes of the account.
-		votes: Vec<(ReferendumIndex, AccountVote<Balance>)>,
+		votes: Vec<(PollIndex, AccountVote<Balance>)>,
 		/// The total amount of delegations that this account has received.
 		delegations: Delegations<Balance>,
 		/// Any pre-existing locks from past voting/delegating activity.
@@ -136,7 +136,9 @@ pub enum Voting<Balance, AccountId, BlockNumber> {
 	},
 }
 
-impl<Balance: Default, AccountId, BlockNumber: Zero> Default for Voting<Balance, AccountId, BlockNumber> {
+impl<Balance: Default, AccountId, BlockNumber: Zero, PollIndex> Default
+	for Voting<Balance, AccountId, BlockNumber, PollIndex>
+{
 	fn default() -> Self {
 		Voting::Direct {
 			votes: Vec::new(),
@@ -150,7 +152,8 @@ impl<
 	Balance: Saturating + Ord + Zero + Copy,
 	BlockNumber: Ord + Copy + Zero,
 	AccountId,
-> Voting<Balance, AccountId, BlockNumber> {
+	PollIndex,
+> Voting<Balance, AccountId, BlockNumber, PollIndex> {
 	pub fn rejig(&mut self, now: BlockNumber) {
 		match self {
 			Voting::Direct { prior, .. } => prior,
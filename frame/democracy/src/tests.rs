@@ -22,12 +22,12 @@ use std::cell::RefCell;
 use codec::Encode;
 use frame_support::{
 	impl_outer_origin, impl_outer_dispatch, assert_noop, assert_ok, parameter_types,
-	impl_outer_event, ord_parameter_types, traits::{Contains, OnInitialize, Filter},
+	impl_outer_event, ord_parameter_types, traits::{Contains, OnInitialize, OnRuntimeUpgrade, Filter},
 	weights::Weight,
 };
 use sp_core::H256;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup, BadOrigin},
+	traits::{BlakeTwo256, IdentityLookup, BadOrigin, Hash},
 	testing::Header, Perbill,
 };
 use pallet_balances::{BalanceLock, Error as BalancesError};
@@ -70,6 +70,7 @@ impl_outer_event! {
 		system<T>,
 		pallet_balances<T>,
 		pallet_scheduler<T>,
+		pallet_preimage<T>,
 		democracy<T>,
 	}
 }
@@ -120,6 +121,7 @@ impl frame_system::Trait for Test {
 }
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
+	pub const NoPreimagePostponement: Option<u64> = Some(1);
 }
 impl pallet_scheduler::Trait for Test {
 	type Event = Event;
@@ -128,6 +130,8 @@ impl pallet_scheduler::Trait for Test {
 	type Call = Call;
 	type MaximumWeight = MaximumSchedulerWeight;
 	type ScheduleOrigin = EnsureRoot<u64>;
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = NoPreimagePostponement;
 	type WeightInfo = ();
 }
 parameter_types! {
@@ -141,6 +145,19 @@ impl pallet_balances::Trait for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 }
+parameter_types! {
+	pub const PreimageMaxSize: u32 = 4096;
+	pub const PreimageBaseDeposit: u64 = 0;
+}
+impl pallet_preimage::Trait for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type MaxSize = PreimageMaxSize;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+	type WeightInfo = ();
+}
 parameter_types! {
 	pub const LaunchPeriod: u64 = 2;
 	pub const VotingPeriod: u64 = 2;
@@ -194,13 +211,11 @@ impl super::Trait for Test {
 	type CancellationOrigin = EnsureSignedBy<Four, u64>;
 	type VetoOrigin = EnsureSignedBy<OneToFive, u64>;
 	type CooloffPeriod = CooloffPeriod;
-	type PreimageByteDeposit = PreimageByteDeposit;
-	type Slash = ();
+	type Preimages = Preimage;
 	type InstantOrigin = EnsureSignedBy<Six, u64>;
 	type InstantAllowed = InstantAllowed;
 	type Scheduler = Scheduler;
 	type MaxVotes = MaxVotes;
-	type OperationalPreimageOrigin = EnsureSignedBy<Six, u64>;
 	type PalletsOrigin = OriginCaller;
 	type WeightInfo = ();
 }
@@ -225,6 +240,7 @@ pub fn new_test_ext_execute_with_cond(execute: impl FnOnce(bool) -> () + Clone)
 type System = frame_system::Module<Test>;
 type Balances = pallet_balances::Module<Test>;
 type Scheduler = pallet_scheduler::Module<Test>;
+type Preimage = pallet_preimage::Module<Test>;
 type Democracy = Module<Test>;
 
 #[test]
@@ -255,9 +271,9 @@ fn set_balance_proposal_hash(value: u64) -> H256 {
 fn set_balance_proposal_hash_and_note(value: u64) -> H256 {
 	let p = set_balance_proposal(value);
 	let h = BlakeTwo256::hash(&p[..]);
-	match Democracy::note_preimage(Origin::signed(6), p) {
+	match Preimage::note_preimage(Origin::signed(6), p) {
 		Ok(_) => (),
-		Err(x) if x == Error::<Test>::DuplicatePreimage.into() => (),
+		Err(x) if x.error == pallet_preimage::Error::<Test>::AlreadyNoted.into() => (),
 		Err(x) => panic!(x),
 	}
 	h
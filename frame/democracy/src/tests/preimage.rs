@@ -38,94 +38,57 @@ fn missing_preimage_should_fail() {
 }
 
 #[test]
-fn preimage_deposit_should_be_required_and_returned() {
-	new_test_ext_execute_with_cond(|operational| {
-		// fee of 100 is too much.
-		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 100);
-		assert_noop!(
-			if operational { Democracy::note_preimage_operational(Origin::signed(6), vec![0; 500]) }
-			else { Democracy::note_preimage(Origin::signed(6), vec![0; 500]) },
-			BalancesError::<Test, _>::InsufficientBalance,
-		);
-		// fee of 1 is reasonable.
-		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
-		let r = Democracy::inject_referendum(
-			2,
-			set_balance_proposal_hash_and_note(2),
-			VoteThreshold::SuperMajorityApprove,
-			0
-		);
-		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
-
-		assert_eq!(Balances::reserved_balance(6), 12);
+fn ongoing_referendum_should_request_preimage() {
+	new_test_ext().execute_with(|| {
+		let h = set_balance_proposal_hash(2);
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 0);
+		assert!(Preimage::preimage_requested(&h));
 
+		assert_ok!(Democracy::vote(Origin::signed(1), r, nay(1)));
 		next_block();
 		next_block();
 
-		assert_eq!(Balances::reserved_balance(6), 0);
-		assert_eq!(Balances::free_balance(6), 60);
-		assert_eq!(Balances::free_balance(42), 2);
+		assert!(!Preimage::preimage_requested(&h));
+		assert_eq!(Balances::free_balance(42), 0);
 	});
 }
 
 #[test]
-fn preimage_deposit_should_be_reapable_earlier_by_owner() {
-	new_test_ext_execute_with_cond(|operational| {
-		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
-		assert_ok!(
-			if operational { Democracy::note_preimage_operational(Origin::signed(6), set_balance_proposal(2)) }
-			else { Democracy::note_preimage(Origin::signed(6), set_balance_proposal(2)) }
-		);
-
-		assert_eq!(Balances::reserved_balance(6), 12);
-
-		next_block();
-		assert_noop!(
-			Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2), u32::max_value()),
-			Error::<Test>::TooEarly
-		);
-		next_block();
-		assert_ok!(Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2), u32::max_value()));
+fn cancelled_referendum_should_unrequest_preimage() {
+	new_test_ext().execute_with(|| {
+		let h = set_balance_proposal_hash(2);
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 0);
+		assert!(Preimage::preimage_requested(&h));
 
-		assert_eq!(Balances::free_balance(6), 60);
-		assert_eq!(Balances::reserved_balance(6), 0);
+		assert_ok!(Democracy::cancel_referendum(Origin::root(), r.into()));
+		assert!(!Preimage::preimage_requested(&h));
 	});
 }
 
 #[test]
-fn preimage_deposit_should_be_reapable() {
-	new_test_ext_execute_with_cond(|operational| {
-		assert_noop!(
-				Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2), u32::max_value()),
-				Error::<Test>::PreimageMissing
-			);
-
+fn preimage_deposit_should_be_returned_once_requested() {
+	new_test_ext().execute_with(|| {
 		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
-		assert_ok!(
-			if operational { Democracy::note_preimage_operational(Origin::signed(6), set_balance_proposal(2)) }
-			else { Democracy::note_preimage(Origin::signed(6), set_balance_proposal(2)) }
-		);
+		let h = set_balance_proposal_hash_and_note(2);
 		assert_eq!(Balances::reserved_balance(6), 12);
 
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 0);
+		assert_eq!(Balances::reserved_balance(6), 0);
+		assert_eq!(Balances::free_balance(6), 60);
+
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 		next_block();
 		next_block();
-		next_block();
-		assert_noop!(
-				Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2), u32::max_value()),
-				Error::<Test>::TooEarly
-			);
 
-		next_block();
-		assert_ok!(Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2), u32::max_value()));
-		assert_eq!(Balances::reserved_balance(6), 0);
-		assert_eq!(Balances::free_balance(6), 48);
-		assert_eq!(Balances::free_balance(5), 62);
+		assert_eq!(Balances::free_balance(42), 2);
+		// The preimage is no longer needed once enacted.
+		assert!(!Preimage::have_preimage(&h));
 	});
 }
 
 #[test]
-fn noting_imminent_preimage_for_free_should_work() {
-	new_test_ext_execute_with_cond(|operational| {
+fn noting_requested_preimage_for_free_should_work() {
+	new_test_ext().execute_with(|| {
 		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
 
 		let r = Democracy::inject_referendum(
@@ -136,16 +99,11 @@ fn noting_imminent_preimage_for_free_should_work() {
 		);
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
-		assert_noop!(
-			if operational { Democracy::note_imminent_preimage_operational(Origin::signed(6), set_balance_proposal(2)) }
-			else { Democracy::note_imminent_preimage(Origin::signed(6), set_balance_proposal(2)) },
-			Error::<Test>::NotImminent
-		);
-
 		next_block();
 
-		// Now we're in the dispatch queue it's all good.
-		assert_ok!(Democracy::note_imminent_preimage(Origin::signed(6), set_balance_proposal(2)));
+		// The enactment is scheduled and the preimage is requested, so noting it is free.
+		assert_ok!(Preimage::note_preimage(Origin::signed(6), set_balance_proposal(2)));
+		assert_eq!(Balances::reserved_balance(6), 0);
 
 		next_block();
 
@@ -154,44 +112,74 @@ fn noting_imminent_preimage_for_free_should_work() {
 }
 
 #[test]
-fn reaping_imminent_preimage_should_fail() {
+fn missing_preimage_should_postpone_enactment() {
 	new_test_ext().execute_with(|| {
-		let h = set_balance_proposal_hash_and_note(2);
-		let r = Democracy::inject_referendum(3, h, VoteThreshold::SuperMajorityApprove, 1);
+		let h = set_balance_proposal_hash(2);
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 1);
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+
+		next_block();
 		next_block();
+		assert_eq!(Balances::free_balance(42), 0);
+		assert!(Preimage::preimage_requested(&h));
+
+		assert_ok!(Preimage::note_preimage(Origin::signed(6), set_balance_proposal(2)));
 		next_block();
-		assert_noop!(Democracy::reap_preimage(Origin::signed(6), h, u32::max_value()), Error::<Test>::Imminent);
+
+		assert_eq!(Balances::free_balance(42), 2);
+		assert!(!Preimage::preimage_requested(&h));
 	});
 }
 
 #[test]
-fn note_imminent_preimage_can_only_be_successful_once() {
+fn cancel_queued_should_unrequest_preimage() {
 	new_test_ext().execute_with(|| {
-		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
-
-		let r = Democracy::inject_referendum(
-			2,
-			set_balance_proposal_hash(2),
-			VoteThreshold::SuperMajorityApprove,
-			1
-		);
+		let h = set_balance_proposal_hash_and_note(2);
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 1);
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+
 		next_block();
+		assert!(Preimage::preimage_requested(&h));
 
-		// First time works
-		assert_ok!(Democracy::note_imminent_preimage(Origin::signed(6), set_balance_proposal(2)));
+		assert_ok!(Democracy::cancel_queued(Origin::root(), r));
+		assert!(!Preimage::preimage_requested(&h));
 
-		// Second time fails
-		assert_noop!(
-			Democracy::note_imminent_preimage(Origin::signed(6), set_balance_proposal(2)),
-			Error::<Test>::DuplicatePreimage
-		);
+		next_block();
+		assert_eq!(Balances::free_balance(42), 0);
+	});
+}
 
-		// Fails from any user
-		assert_noop!(
-			Democracy::note_imminent_preimage(Origin::signed(5), set_balance_proposal(2)),
-			Error::<Test>::DuplicatePreimage
-		);
+#[test]
+fn migration_to_v2_should_move_preimages() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::put(Releases::V1);
+		let h = set_balance_proposal_hash(2);
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 0);
+		// Referenda started before the migration did not request their preimage.
+		assert_ok!(Preimage::unrequest_preimage(Origin::root(), h));
+
+		assert_ok!(Balances::reserve(&6, 12));
+		let old = PreimageStatus::Available {
+			data: set_balance_proposal(2),
+			provider: 6u64,
+			deposit: 12u64,
+			since: 0u64,
+			expiry: None,
+		};
+		frame_support::storage::migration::put_storage_value(b"Democracy", b"Preimages", h.as_ref(), old);
+
+		assert_eq!(Democracy::on_runtime_upgrade(), MaximumBlockWeight::get());
+		assert!(!Democracy::migrate_preimages_to_v2());
+		assert_eq!(StorageVersion::get(), Some(Releases::V2));
+
+		assert_eq!(Balances::reserved_balance(6), 0);
+		assert!(Preimage::have_preimage(&h));
+		assert!(Preimage::preimage_requested(&h));
+		assert!(!frame_support::storage::migration::have_storage_value(b"Democracy", b"Preimages", h.as_ref()));
+
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		next_block();
+		next_block();
+		assert_eq!(Balances::free_balance(42), 2);
 	});
 }
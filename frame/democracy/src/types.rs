@@ -20,7 +20,7 @@
 use codec::{Encode, Decode};
 use sp_runtime::RuntimeDebug;
 use sp_runtime::traits::{Zero, Bounded, CheckedAdd, CheckedSub, CheckedMul, CheckedDiv, Saturating};
-use crate::{Vote, VoteThreshold, AccountVote, Conviction};
+use crate::{Vote, VoteThreshold, AccountVote, Conviction, Delegations};
 
 /// Info regarding an ongoing referendum.
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
@@ -33,45 +33,6 @@ pub struct Tally<Balance> {
 	pub (crate) turnout: Balance,
 }
 
-/// Amount of votes and capital placed in delegation for an account.
-#[derive(Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct Delegations<Balance> {
-	/// The number of votes (this is post-conviction).
-	pub (crate) votes: Balance,
-	/// The amount of raw capital, used for the turnout.
-	pub (crate) capital: Balance,
-}
-
-impl<Balance: Saturating> Saturating for Delegations<Balance> {
-	fn saturating_add(self, o: Self) -> Self {
-		Self {
-			votes: self.votes.saturating_add(o.votes),
-			capital: self.capital.saturating_add(o.capital),
-		}
-	}
-
-	fn saturating_sub(self, o: Self) -> Self {
-		Self {
-			votes: self.votes.saturating_sub(o.votes),
-			capital: self.capital.saturating_sub(o.capital),
-		}
-	}
-
-	fn saturating_mul(self, o: Self) -> Self {
-		Self {
-			votes: self.votes.saturating_mul(o.votes),
-			capital: self.capital.saturating_mul(o.capital),
-		}
-	}
-
-	fn saturating_pow(self, exp: usize) -> Self {
-		Self {
-			votes: self.votes.saturating_pow(exp),
-			capital: self.capital.saturating_pow(exp),
-		}
-	}
-}
-
 impl<
 	Balance: From<u8> + Zero + Copy + CheckedAdd + CheckedSub + CheckedMul + CheckedDiv + Bounded +
 		Saturating
@@ -196,12 +157,3 @@ impl<BlockNumber, Hash, Balance: Default> ReferendumInfo<BlockNumber, Hash, Bala
 		ReferendumInfo::Ongoing(s)
 	}
 }
-
-/// Whether an `unvote` operation is able to make actions that are not strictly always in the
-/// interest of an account.
-pub enum UnvoteScope {
-	/// Permitted to do everything.
-	Any,
-	/// Permitted to do only the changes that do not need the owner's permission.
-	OnlyExpired,
-}
@@ -0,0 +1,205 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Multi phase election pallet benchmarking.
+
+use super::*;
+use crate::Module as MultiPhase;
+
+pub use frame_benchmarking::{account, benchmarks};
+use frame_support::{assert_ok, traits::OnInitialize};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+/// Put a mock snapshot of `v` voters and `t` targets into storage, with `d` desired targets.
+///
+/// Each voter votes for the maximum number of targets possible, in a sliding window over all of
+/// the targets, such that every target receives some votes.
+fn put_mock_snapshot<T: Trait>(v: u32, t: u32, d: u32) {
+	let targets: Vec<T::AccountId> = (0..t).map(|i| account("target", i, SEED)).collect();
+	let votes_per_voter = (MAX_VOTES_PER_VOTER as u32).min(t);
+
+	let voters = (0..v)
+		.map(|i| {
+			let votes = (0..votes_per_voter)
+				.map(|j| targets[((i + j) % t) as usize].clone())
+				.collect::<Vec<_>>();
+			(account("voter", i, SEED), (i as VoteWeight + 1) * 100, votes)
+		})
+		.collect::<Vec<_>>();
+
+	<SnapshotMetadata>::put(SolutionOrSnapshotSize { voters: v, targets: t });
+	<DesiredTargets>::put(d);
+	<Snapshot<T>>::put(RoundSnapshot { voters, targets });
+}
+
+benchmarks! {
+	_ { }
+
+	on_initialize_nothing {
+		assert!(MultiPhase::<T>::current_phase().is_off());
+	}: {
+		MultiPhase::<T>::on_initialize(1u32.into());
+	} verify {
+		assert!(MultiPhase::<T>::current_phase().is_off());
+	}
+
+	on_initialize_open_signed {
+		assert!(MultiPhase::<T>::snapshot().is_none());
+		assert!(MultiPhase::<T>::current_phase().is_off());
+	}: {
+		MultiPhase::<T>::on_initialize_open_signed();
+	} verify {
+		assert!(MultiPhase::<T>::snapshot().is_some());
+		assert!(MultiPhase::<T>::current_phase().is_signed());
+	}
+
+	on_initialize_open_unsigned_with_snapshot {
+		assert!(MultiPhase::<T>::snapshot().is_none());
+		assert!(MultiPhase::<T>::current_phase().is_off());
+	}: {
+		MultiPhase::<T>::on_initialize_open_unsigned(true, true, 1u32.into());
+	} verify {
+		assert!(MultiPhase::<T>::snapshot().is_some());
+		assert!(MultiPhase::<T>::current_phase().is_unsigned());
+	}
+
+	on_initialize_open_unsigned_without_snapshot {
+		// need to assume signed phase was open before
+		MultiPhase::<T>::on_initialize_open_signed();
+		assert!(MultiPhase::<T>::snapshot().is_some());
+		assert!(MultiPhase::<T>::current_phase().is_signed());
+	}: {
+		MultiPhase::<T>::on_initialize_open_unsigned(false, true, 1u32.into());
+	} verify {
+		assert!(MultiPhase::<T>::snapshot().is_some());
+		assert!(MultiPhase::<T>::current_phase().is_unsigned());
+	}
+
+	finalize_signed_phase_accept_solution {
+		let receiver = account("receiver", 0, SEED);
+		let initial_balance = T::Currency::minimum_balance() * 10.into();
+		T::Currency::make_free_balance_be(&receiver, initial_balance);
+		let ready: ReadySolution<T::AccountId> = Default::default();
+		let deposit: BalanceOf<T> = 10.into();
+		let reward: BalanceOf<T> = 20.into();
+
+		assert_ok!(T::Currency::reserve(&receiver, deposit));
+		assert_eq!(T::Currency::free_balance(&receiver), initial_balance - 10.into());
+	}: {
+		MultiPhase::<T>::finalize_signed_phase_accept_solution(ready, &receiver, deposit, reward)
+	} verify {
+		assert_eq!(T::Currency::free_balance(&receiver), initial_balance + 20.into());
+		assert_eq!(T::Currency::reserved_balance(&receiver), 0.into());
+	}
+
+	finalize_signed_phase_reject_solution {
+		let receiver = account("receiver", 0, SEED);
+		let initial_balance = T::Currency::minimum_balance() * 10.into();
+		let deposit: BalanceOf<T> = 10.into();
+		T::Currency::make_free_balance_be(&receiver, initial_balance);
+		assert_ok!(T::Currency::reserve(&receiver, deposit));
+
+		assert_eq!(T::Currency::free_balance(&receiver), initial_balance - 10.into());
+		assert_eq!(T::Currency::reserved_balance(&receiver), 10.into());
+	}: {
+		MultiPhase::<T>::finalize_signed_phase_reject_solution(&receiver, deposit)
+	} verify {
+		assert_eq!(T::Currency::free_balance(&receiver), initial_balance - 10.into());
+		assert_eq!(T::Currency::reserved_balance(&receiver), 0.into());
+	}
+
+	submit {
+		let c in 1 .. (T::SignedMaxSubmissions::get() - 1);
+
+		// the queue is full of `c` solutions, all weaker than the one being submitted.
+		let signed_submissions = (0..c)
+			.map(|i| SignedSubmission {
+				solution: RawSolution {
+					score: [(10_000_000 + i).into(), 0, 0],
+					..Default::default()
+				},
+				..Default::default()
+			})
+			.collect::<Vec<_>>();
+		<SignedSubmissions<T>>::put(signed_submissions);
+		<CurrentPhase<T>>::put(Phase::Signed);
+
+		let solution = RawSolution {
+			score: [(10_000_000 + c).into(), 0, 0],
+			round: MultiPhase::<T>::round(),
+			..Default::default()
+		};
+		let caller = account("caller", 0, SEED);
+		let deposit = MultiPhase::<T>::deposit_for(&solution);
+		let balance = T::Currency::minimum_balance() * 10.into() + deposit;
+		T::Currency::make_free_balance_be(&caller, balance);
+	}: _(RawOrigin::Signed(caller), solution, c)
+	verify {
+		assert_eq!(MultiPhase::<T>::signed_submissions().len() as u32, c + 1);
+	}
+
+	submit_unsigned {
+		// number of votes in snapshot.
+		let v in 500 .. 1000;
+		// number of targets in snapshot.
+		let t in 100 .. 200;
+
+		put_mock_snapshot::<T>(v, t, t / 4);
+		<CurrentPhase<T>>::put(Phase::Unsigned((true, 1u32.into())));
+		let (raw_solution, witness) = MultiPhase::<T>::mine_solution(0).unwrap();
+		assert!(MultiPhase::<T>::queued_solution().is_none());
+	}: _(RawOrigin::None, raw_solution, witness)
+	verify {
+		assert!(MultiPhase::<T>::queued_solution().is_some());
+	}
+
+	// This is checking a valid solution. The worse case is indeed a valid solution.
+	feasibility_check {
+		// number of votes in snapshot.
+		let v in 500 .. 1000;
+		// number of targets in snapshot.
+		let t in 100 .. 200;
+
+		put_mock_snapshot::<T>(v, t, t / 4);
+		let (raw_solution, _) = MultiPhase::<T>::mine_solution(0).unwrap();
+	}: {
+		assert_ok!(MultiPhase::<T>::feasibility_check(raw_solution, ElectionCompute::Unsigned));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::mock::*;
+
+	#[test]
+	fn test_benchmarks() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(test_benchmark_on_initialize_nothing::<Runtime>());
+			assert_ok!(test_benchmark_on_initialize_open_signed::<Runtime>());
+			assert_ok!(test_benchmark_on_initialize_open_unsigned_with_snapshot::<Runtime>());
+			assert_ok!(test_benchmark_on_initialize_open_unsigned_without_snapshot::<Runtime>());
+			assert_ok!(test_benchmark_finalize_signed_phase_accept_solution::<Runtime>());
+			assert_ok!(test_benchmark_finalize_signed_phase_reject_solution::<Runtime>());
+			assert_ok!(test_benchmark_submit::<Runtime>());
+			assert_ok!(test_benchmark_submit_unsigned::<Runtime>());
+			assert_ok!(test_benchmark_feasibility_check::<Runtime>());
+		});
+	}
+}
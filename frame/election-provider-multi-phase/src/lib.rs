@@ -0,0 +1,1258 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Multi phase, offchain election provider module.
+//!
+//! Currently, this election-provider has two distinct phases (see [`Phase`]), **signed** and
+//! **unsigned**.
+//!
+//! ## Phases
+//!
+//! The timeline of pallet is as follows. At each block,
+//! [`ElectionDataProvider::next_election_prediction`] is used to estimate the time remaining to the
+//! next call to [`ElectionProvider::elect`]. Based on this, a phase is chosen. The timeline is as
+//! follows.
+//!
+//! ```ignore
+//!                                                                    elect()
+//!                 +   <--T::SignedPhase-->  +  <--T::UnsignedPhase-->   +
+//!   +-------------------------------------------------------------------+
+//!    Phase::Off   +       Phase::Signed     +      Phase::Unsigned      +
+//! ```
+//!
+//! Note that the unsigned phase starts [`Trait::UnsignedPhase`] blocks before the
+//! `next_election_prediction`, but only ends when a call to [`ElectionProvider::elect`] happens.
+//!
+//! Each of the phases can be disabled by essentially setting their length to zero. If both phases
+//! have length zero, then the pallet essentially runs only the on-chain backup.
+//!
+//! ### Signed Phase
+//!
+//! In the signed phase, solutions (of type [`RawSolution`]) are submitted and queued on chain. A
+//! deposit is reserved, based on the size of the solution, for the cost of keeping this solution
+//! on-chain for a number of blocks, and the potential weight of the solution upon being checked. A
+//! maximum of [`Trait::SignedMaxSubmissions`] solutions are stored. The queue is always sorted
+//! based on score (worse to best).
+//!
+//! Upon arrival of a new solution:
+//!
+//! 1. If the queue is not full, it is stored in the appropriate sorted index.
+//! 2. If the queue is full but the submitted solution is better than one of the queued ones, the
+//!    worse solution is discarded, the bond of the outgoing solution is returned, and the new
+//!    solution is stored in the correct index.
+//! 3. If the queue is full and the solution is not an improvement compared to any of the queued
+//!    ones, it is instantly rejected and no additional bond is reserved.
+//!
+//! A signed solution cannot be reversed, taken back, updated, or retracted. In other words, the
+//! origin can not bail out in any way, if their solution is queued.
+//!
+//! Upon the end of the signed phase, the solutions are examined from best to worse (i.e. `pop()`ed
+//! until drained). Each solution undergoes an expensive [`Module::feasibility_check`], which
+//! ensures the score claimed by this score was correct, and it is valid based on the election data
+//! (i.e. votes and candidates). At each step, if the current best solution passes the feasibility
+//! check, it is considered to be the best one. The sender of the origin is rewarded, and the rest
+//! of the queued solutions get their deposit back and are discarded, without being checked.
+//!
+//! The following example covers all of the cases at the end of the signed phase:
+//!
+//! ```ignore
+//! Queue
+//! +-------------------------------+
+//! |Solution(score=20, valid=false)| +-->  Slashed
+//! +-------------------------------+
+//! |Solution(score=15, valid=true )| +-->  Rewarded, Saved
+//! +-------------------------------+
+//! |Solution(score=10, valid=true )| +-->  Discarded
+//! +-------------------------------+
+//! |Solution(score=05, valid=false)| +-->  Discarded
+//! +-------------------------------+
+//! |             None              |
+//! +-------------------------------+
+//! ```
+//!
+//! Note that both of the bottom solutions end up being discarded and get their deposit back,
+//! despite one of them being *invalid*.
+//!
+//! ### Unsigned Phase
+//!
+//! The unsigned phase will always follow the signed phase, with the specified duration. In this
+//! phase, only validator nodes can submit solutions. A validator node who has offchain workers
+//! enabled will start to mine a solution in this phase and submits it back to the chain as an
+//! unsigned transaction, thus the name _unsigned_ phase. This unsigned transaction can never be
+//! valid if propagated, and it acts similar to an inherent.
+//!
+//! Validators will only submit solutions if the one that they have computed is sufficiently better
+//! than the best queued one (see [`Trait::SolutionImprovementThreshold`]). If the signed phase
+//! produced a feasible solution, the unsigned phase is opened, but not enabled for submissions.
+//!
+//! ### Fallback
+//!
+//! If we reach the end of both phases (i.e. call to [`ElectionProvider::elect`] happens) and no
+//! good solution is queued, then an on-chain sequential phragmen is executed over the snapshot, as
+//! a fallback. This is also what happens if [`ElectionProvider::elect`] is called before any of the
+//! phases started.
+//!
+//! ## Feasible Solution (correct solution)
+//!
+//! All submissions must undergo a feasibility check. Signed solutions are checked at the end of the
+//! signed phase, and the unsigned solutions are checked on the spot. A feasible solution is as
+//! follows:
+//!
+//! 0. **all** of the used indices must be correct.
+//! 1. present *exactly* correct number of winners.
+//! 2. any assignment is checked to match with the snapshot's voters.
+//! 3. the claimed score is valid, based on the fixed point arithmetic accuracy.
+//!
+//! ## Accuracy
+//!
+//! The accuracy of the election is configured via two types: [`OffchainAccuracy`] and
+//! [`OnChainAccuracy`]. The former is the accuracy of the compact solutions that are submitted to
+//! the chain, and is kept small to minimize the size of the transactions. The latter is only used
+//! by the on-chain fallback.
+//!
+//! ## Related Modules
+//!
+//! - [Staking](../pallet_staking/index.html): The most common user of this election provider,
+//!   acting as the [`ElectionDataProvider`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(any(feature = "runtime-benchmarks", test))]
+mod benchmarking;
+
+pub mod signed;
+pub mod unsigned;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	dispatch::DispatchResultWithPostInfo,
+	traits::{Currency, Get, OnUnbalanced, ReservableCurrency},
+	weights::Weight,
+};
+use frame_system::{ensure_none, ensure_signed, offchain::SendTransactionTypes};
+use frame_election_provider_support::{
+	ElectionDataProvider, ElectionProvider, onchain,
+};
+use sp_npos_elections::{
+	assignment_ratio_to_staked_normalized, evaluate_supports, generate_compact_solution_type,
+	is_score_better, to_supports, Assignment, ElectionScore, Supports, VoteWeight,
+};
+use sp_arithmetic::{PerThing, UpperOf, traits::CheckedAdd};
+use sp_runtime::{
+	DispatchError, PerU16, Perbill, RuntimeDebug, SaturatedConversion,
+	traits::Zero,
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		TransactionValidityError, ValidTransaction,
+	},
+};
+use sp_std::{collections::btree_map::BTreeMap, collections::btree_set::BTreeSet, prelude::*};
+
+pub use signed::{SignedSubmission, BalanceOf, NegativeImbalanceOf, PositiveImbalanceOf};
+pub use unsigned::MinerError;
+
+pub(crate) const LOG_TARGET: &'static str = "election-provider";
+
+// syntactic sugar for logging.
+#[macro_export]
+macro_rules! log {
+	($level:tt, $patter:expr $(, $values:expr)* $(,)?) => {
+		frame_support::debug::$level!(
+			target: crate::LOG_TARGET,
+			concat!("🗳 ", $patter) $(, $values)*
+		)
+	};
+}
+
+/// Data type used to index voters in the compact type.
+pub type VoterIndex = u32;
+
+/// Data type used to index targets in the compact type.
+pub type TargetIndex = u16;
+
+/// Accuracy used for the compact solutions that are submitted to the chain. This better be small.
+pub type OffchainAccuracy = PerU16;
+
+/// Accuracy used for the on-chain fallback election.
+pub type OnChainAccuracy = Perbill;
+
+// Note: Maximum number of votes per voter is set here -- 16.
+generate_compact_solution_type!(pub GenericCompactAssignments, 16);
+
+/// The compact type for election solutions.
+pub type CompactAssignments =
+	GenericCompactAssignments<VoterIndex, TargetIndex, OffchainAccuracy>;
+
+/// The maximum number of votes that a single voter can have in a submitted solution.
+pub const MAX_VOTES_PER_VOTER: usize =
+	<CompactAssignments as sp_npos_elections::VotingLimit>::LIMIT;
+
+/// Configuration for the on-chain fallback of this pallet.
+pub struct OnChainConfig<T: Trait>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait> onchain::Config for OnChainConfig<T> {
+	type AccountId = T::AccountId;
+	type BlockNumber = T::BlockNumber;
+	type Accuracy = OnChainAccuracy;
+	type DataProvider = T::DataProvider;
+}
+
+/// The on-chain fallback election of this pallet.
+pub type OnChainSequentialPhragmen<T> = onchain::OnChainSequentialPhragmen<OnChainConfig<T>>;
+
+/// Current phase of the pallet.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Phase<Bn> {
+	/// Nothing, the election is not happening.
+	Off,
+	/// Signed phase is open.
+	Signed,
+	/// Unsigned phase. First element is whether it is open or not, second the starting block
+	/// number.
+	Unsigned((bool, Bn)),
+}
+
+impl<Bn> Default for Phase<Bn> {
+	fn default() -> Self {
+		Phase::Off
+	}
+}
+
+impl<Bn: PartialEq + Eq> Phase<Bn> {
+	/// Whether the phase is signed or not.
+	pub fn is_signed(&self) -> bool {
+		matches!(self, Phase::Signed)
+	}
+
+	/// Whether the phase is unsigned or not.
+	pub fn is_unsigned(&self) -> bool {
+		matches!(self, Phase::Unsigned(_))
+	}
+
+	/// Whether the phase is unsigned and open or not, with specific start.
+	pub fn is_unsigned_open_at(&self, at: Bn) -> bool {
+		matches!(self, Phase::Unsigned((true, real)) if *real == at)
+	}
+
+	/// Whether the phase is unsigned and open or not.
+	pub fn is_unsigned_open(&self) -> bool {
+		matches!(self, Phase::Unsigned((true, _)))
+	}
+
+	/// Whether the phase is off or not.
+	pub fn is_off(&self) -> bool {
+		matches!(self, Phase::Off)
+	}
+}
+
+/// Indicate how an election round was computed.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum ElectionCompute {
+	/// Result was forcefully computed on chain at the end of the session.
+	OnChain,
+	/// Result was submitted and accepted to the chain via a signed transaction.
+	Signed,
+	/// Result was submitted and accepted to the chain via an unsigned transaction (by an
+	/// authority).
+	Unsigned,
+}
+
+impl Default for ElectionCompute {
+	fn default() -> Self {
+		ElectionCompute::OnChain
+	}
+}
+
+/// A raw, unchecked solution.
+///
+/// This is what will get submitted to the chain.
+///
+/// Such a solution should never become effective in anyway before being checked by the
+/// [`Module::feasibility_check`].
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
+pub struct RawSolution {
+	/// Compact election edges.
+	pub compact: CompactAssignments,
+	/// The _claimed_ score of the solution.
+	pub score: ElectionScore,
+	/// The round at which this solution should be submitted.
+	pub round: u32,
+}
+
+/// A checked solution, ready to be enacted.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
+pub struct ReadySolution<A> {
+	/// The final supports of the solution.
+	///
+	/// This is target-major vector, storing each winners, total backing, and each individual
+	/// backer.
+	pub supports: Supports<A>,
+	/// The score of the solution.
+	///
+	/// This is needed to potentially challenge the solution.
+	pub score: ElectionScore,
+	/// How this election was computed.
+	pub compute: ElectionCompute,
+}
+
+/// A snapshot of all the data that is needed for an entire round. Only stored for the duration of
+/// the signed and unsigned phases.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
+pub struct RoundSnapshot<A> {
+	/// All of the voters.
+	pub voters: Vec<(A, VoteWeight, Vec<A>)>,
+	/// All of the targets.
+	pub targets: Vec<A>,
+}
+
+/// Some indications about the size of the snapshot. This must be submitted with an unsigned
+/// solution, so that its weight can be known up front.
+///
+/// These values must reflect the __total__ number, not only those that are present in the
+/// solution.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, Default)]
+pub struct SolutionOrSnapshotSize {
+	/// The length of voters.
+	#[codec(compact)]
+	pub voters: u32,
+	/// The length of targets.
+	#[codec(compact)]
+	pub targets: u32,
+}
+
+/// Internal errors of the pallet.
+///
+/// Note that this is different from [`Error`].
+#[derive(RuntimeDebug, Eq, PartialEq)]
+pub enum ElectionError {
+	/// A feasibility error.
+	Feasibility(FeasibilityError),
+	/// An error in the on-chain fallback.
+	OnChainFallback(onchain::Error),
+	/// An error happened in the miner.
+	Miner(MinerError),
+}
+
+impl From<onchain::Error> for ElectionError {
+	fn from(e: onchain::Error) -> Self {
+		ElectionError::OnChainFallback(e)
+	}
+}
+
+impl From<FeasibilityError> for ElectionError {
+	fn from(e: FeasibilityError) -> Self {
+		ElectionError::Feasibility(e)
+	}
+}
+
+impl From<MinerError> for ElectionError {
+	fn from(e: MinerError) -> Self {
+		ElectionError::Miner(e)
+	}
+}
+
+/// Errors that can happen in the feasibility check.
+#[derive(RuntimeDebug, Eq, PartialEq)]
+pub enum FeasibilityError {
+	/// Wrong number of winners presented.
+	WrongWinnerCount,
+	/// The snapshot is not available.
+	///
+	/// This must be an internal error of the chain.
+	SnapshotUnavailable,
+	/// Internal error from the election crate.
+	NposElection(sp_npos_elections::Error),
+	/// A vote is invalid.
+	InvalidVote,
+	/// A voter is invalid.
+	InvalidVoter,
+	/// The given score was invalid.
+	InvalidScore,
+	/// The provided round is incorrect.
+	InvalidRound,
+}
+
+impl From<sp_npos_elections::Error> for FeasibilityError {
+	fn from(e: sp_npos_elections::Error) -> Self {
+		FeasibilityError::NposElection(e)
+	}
+}
+
+pub trait WeightInfo {
+	fn on_initialize_nothing() -> Weight;
+	fn on_initialize_open_signed() -> Weight;
+	fn on_initialize_open_unsigned_with_snapshot() -> Weight;
+	fn on_initialize_open_unsigned_without_snapshot() -> Weight;
+	fn finalize_signed_phase_accept_solution() -> Weight;
+	fn finalize_signed_phase_reject_solution() -> Weight;
+	fn submit(c: u32, ) -> Weight;
+	fn submit_unsigned(v: u32, t: u32, ) -> Weight;
+	fn feasibility_check(v: u32, t: u32, ) -> Weight;
+}
+
+impl WeightInfo for () {
+	fn on_initialize_nothing() -> Weight { 1_000_000_000 }
+	fn on_initialize_open_signed() -> Weight { 1_000_000_000 }
+	fn on_initialize_open_unsigned_with_snapshot() -> Weight { 1_000_000_000 }
+	fn on_initialize_open_unsigned_without_snapshot() -> Weight { 1_000_000_000 }
+	fn finalize_signed_phase_accept_solution() -> Weight { 1_000_000_000 }
+	fn finalize_signed_phase_reject_solution() -> Weight { 1_000_000_000 }
+	fn submit(_c: u32, ) -> Weight { 1_000_000_000 }
+	fn submit_unsigned(_v: u32, _t: u32, ) -> Weight { 1_000_000_000 }
+	fn feasibility_check(_v: u32, _t: u32, ) -> Weight { 1_000_000_000 }
+}
+
+pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Currency type used for the deposits and rewards of signed submissions.
+	type Currency: ReservableCurrency<Self::AccountId> + Currency<Self::AccountId>;
+
+	/// Duration of the signed phase.
+	type SignedPhase: Get<Self::BlockNumber>;
+
+	/// Duration of the unsigned phase.
+	type UnsignedPhase: Get<Self::BlockNumber>;
+
+	/// Maximum number of signed submissions that can be queued.
+	type SignedMaxSubmissions: Get<u32>;
+
+	/// Base reward for a signed solution.
+	type SignedRewardBase: Get<BalanceOf<Self>>;
+
+	/// Base deposit for a signed solution.
+	type SignedDepositBase: Get<BalanceOf<Self>>;
+
+	/// Per-byte deposit for a signed solution.
+	type SignedDepositByte: Get<BalanceOf<Self>>;
+
+	/// Handler for the slashed deposits of invalid signed solutions.
+	type SlashHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+	/// Handler for the rewards of valid signed solutions.
+	type RewardHandler: OnUnbalanced<PositiveImbalanceOf<Self>>;
+
+	/// The minimum amount of improvement to the solution score that defines a solution as
+	/// "better".
+	type SolutionImprovementThreshold: Get<Perbill>;
+
+	/// Maximum number of balancing iterations to run in the offchain miner.
+	///
+	/// If set to 0, balance_solution will not be executed at all.
+	type MinerMaxIterations: Get<u32>;
+
+	/// A configuration for base priority of unsigned transactions.
+	///
+	/// This is exposed so that it can be tuned for particular runtime, when multiple pallets send
+	/// unsigned transactions.
+	type UnsignedPriority: Get<TransactionPriority>;
+
+	/// Something that will provide the election data.
+	type DataProvider: ElectionDataProvider<Self::AccountId, Self::BlockNumber>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as ElectionProviderMultiPhase {
+		/// Internal counter for the number of rounds.
+		///
+		/// This is useful for de-duplication of transactions submitted to the pool, and general
+		/// diagnostics of the module.
+		///
+		/// This is merely incremented once per every time that an upstream `elect` is called.
+		pub Round get(fn round): u32 = 1;
+
+		/// Current phase.
+		pub CurrentPhase get(fn current_phase): Phase<T::BlockNumber>;
+
+		/// Current best solution, signed or unsigned.
+		pub QueuedSolution get(fn queued_solution): Option<ReadySolution<T::AccountId>>;
+
+		/// Snapshot data of the round.
+		///
+		/// This is created at the beginning of the signed phase and cleared upon calling `elect`.
+		pub Snapshot get(fn snapshot): Option<RoundSnapshot<T::AccountId>>;
+
+		/// Desired number of targets to elect for this round.
+		///
+		/// Only exists when [`Snapshot`] is present.
+		pub DesiredTargets get(fn desired_targets): Option<u32>;
+
+		/// The metadata of the [`RoundSnapshot`].
+		///
+		/// Only exists when [`Snapshot`] is present.
+		pub SnapshotMetadata get(fn snapshot_metadata): Option<SolutionOrSnapshotSize>;
+
+		/// Sorted (worse -> best) list of unchecked, signed solutions.
+		pub SignedSubmissions get(fn signed_submissions):
+			Vec<SignedSubmission<T::AccountId, BalanceOf<T>>>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where <T as frame_system::Trait>::AccountId {
+		/// A solution was stored with the given compute.
+		///
+		/// If the solution is signed, this means that it hasn't yet been processed. If the
+		/// solution is unsigned, this means that it has also been processed.
+		/// [compute]
+		SolutionStored(ElectionCompute),
+		/// The election has been finalized, with `Some` of the given computation, or else if the
+		/// election failed, `None`. [compute]
+		ElectionFinalized(Option<ElectionCompute>),
+		/// An account has been rewarded for their signed submission being finalized. [who]
+		Rewarded(AccountId),
+		/// An account has been slashed for submitting an invalid signed submission. [who]
+		Slashed(AccountId),
+		/// The signed phase of the given round has started. [round]
+		SignedPhaseStarted(u32),
+		/// The unsigned phase of the given round has started. [round]
+		UnsignedPhaseStarted(u32),
+	}
+);
+
+decl_error! {
+	/// Error for the election provider module.
+	pub enum Error for Module<T: Trait> {
+		/// Submission was too early.
+		PreDispatchEarlySubmission,
+		/// Submission was for another round.
+		PreDispatchWrongRound,
+		/// Submission was too weak, score-wise.
+		PreDispatchWeakSubmission,
+		/// The witness data of the submission does not match the snapshot.
+		PreDispatchWrongWitness,
+		/// The queue was full, and the solution was not better than any of the existing ones.
+		SignedQueueFull,
+		/// The origin failed to pay the deposit.
+		SignedCannotPayDeposit,
+		/// The witness data of the submission does not match the signed queue.
+		SignedInvalidWitness,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		/// Duration of the signed phase.
+		const SignedPhase: T::BlockNumber = T::SignedPhase::get();
+
+		/// Duration of the unsigned phase.
+		const UnsignedPhase: T::BlockNumber = T::UnsignedPhase::get();
+
+		/// Maximum number of signed submissions that can be queued.
+		const SignedMaxSubmissions: u32 = T::SignedMaxSubmissions::get();
+
+		/// The minimum amount of improvement to the solution score that defines a solution as
+		/// "better".
+		const SolutionImprovementThreshold: Perbill = T::SolutionImprovementThreshold::get();
+
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Moves the pallet through its phases, based on the prediction of the data provider
+		/// about the next election.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let next_election = T::DataProvider::next_election_prediction(now).max(now);
+
+			let signed_deadline = T::SignedPhase::get() + T::UnsignedPhase::get();
+			let unsigned_deadline = T::UnsignedPhase::get();
+
+			let remaining = next_election - now;
+			let current_phase = Self::current_phase();
+
+			match current_phase {
+				Phase::Off if remaining <= signed_deadline && remaining > unsigned_deadline => {
+					Self::on_initialize_open_signed();
+					log!(info, "Starting signed phase at #{:?} , round {}.", now, Self::round());
+					T::WeightInfo::on_initialize_open_signed()
+				},
+				Phase::Signed | Phase::Off
+					if remaining <= unsigned_deadline && remaining > Zero::zero() =>
+				{
+					let (need_snapshot, enabled, additional) = if current_phase == Phase::Signed {
+						// followed by a signed phase: close the signed phase, no need for snapshot.
+						let (success, weight) = Self::finalize_signed_phase();
+						(false, !success, weight)
+					} else {
+						// no signed phase: create a new snapshot, definitely `enable` the unsigned
+						// phase.
+						(true, true, 0)
+					};
+
+					Self::on_initialize_open_unsigned(need_snapshot, enabled, now);
+					log!(info, "Starting unsigned phase({}) at #{:?}.", enabled, now);
+
+					let base_weight = if need_snapshot {
+						T::WeightInfo::on_initialize_open_unsigned_with_snapshot()
+					} else {
+						T::WeightInfo::on_initialize_open_unsigned_without_snapshot()
+					};
+					base_weight.saturating_add(additional)
+				},
+				_ => T::WeightInfo::on_initialize_nothing(),
+			}
+		}
+
+		/// Run the offchain miner, if the unsigned phase has just been opened.
+		fn offchain_worker(now: T::BlockNumber) {
+			// We only run the OCW in the first block of the unsigned phase.
+			if Self::current_phase().is_unsigned_open_at(now) {
+				match unsigned::set_check_offchain_execution_status::<T>(now) {
+					Ok(_) => {
+						let outcome = Self::mine_check_and_submit().map_err(ElectionError::from);
+						log!(info, "miner execution done: {:?}", outcome);
+					},
+					Err(why) => log!(debug, "skipping offchain worker due to [{}]", why),
+				}
+			}
+		}
+
+		fn integrity_test() {
+			// The sum of the edge weights of a voter with the maximum number of votes must fit in
+			// the `Upper` type of both accuracies, else the normalization could overflow.
+			fn assert_max_votes_fit<P: PerThing>() {
+				let one = <UpperOf<P>>::from(P::one().deconstruct());
+				let _ = (0..MAX_VOTES_PER_VOTER).fold(<UpperOf<P>>::zero(), |acc, _| {
+					acc.checked_add(&one).expect("sum of maximum votes overflows accuracy")
+				});
+			}
+			assert_max_votes_fit::<OffchainAccuracy>();
+			assert_max_votes_fit::<OnChainAccuracy>();
+		}
+
+		/// Submit a solution for the signed phase.
+		///
+		/// The dispatch origin for this call must be __signed__.
+		///
+		/// The solution is potentially queued, based on the claimed score and processed at the end
+		/// of the signed phase.
+		///
+		/// A deposit is reserved and recorded for the solution. Based on the outcome, the solution
+		/// might be rewarded, slashed, or get all or a part of the deposit back.
+		///
+		/// - `solution`: The solution to queue.
+		/// - `num_signed_submissions`: The number of solutions already queued, which must not be
+		///   lower than the actual length of the queue.
+		///
+		/// # <weight>
+		/// Queue size must be provided as witness data. The size of the solution is paid for by
+		/// the deposit taken per byte, on top of the length fee of the transaction.
+		/// # </weight>
+		#[weight = T::WeightInfo::submit(*num_signed_submissions)]
+		pub fn submit(
+			origin,
+			solution: RawSolution,
+			num_signed_submissions: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			// ensure solution is timely.
+			ensure!(Self::current_phase().is_signed(), Error::<T>::PreDispatchEarlySubmission);
+			// ensure the solution is for this round. Otherwise it will certainly get slashed.
+			ensure!(solution.round == Self::round(), Error::<T>::PreDispatchWrongRound);
+
+			let claimed_score = solution.score;
+			let mut signed_submissions = Self::signed_submissions();
+			// ensure the witness data covers the work done on the queue.
+			ensure!(
+				signed_submissions.len() as u32 <= num_signed_submissions,
+				Error::<T>::SignedInvalidWitness,
+			);
+			let maybe_index = Self::insert_submission(&who, &mut signed_submissions, solution);
+			let index = maybe_index.ok_or(Error::<T>::SignedQueueFull)?;
+
+			// collect deposit. Thereafter, the function cannot fail.
+			let deposit = signed_submissions[index].deposit;
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::SignedCannotPayDeposit)?;
+
+			// Remove the weakest, if needed.
+			if signed_submissions.len() as u32 > T::SignedMaxSubmissions::get() {
+				Self::remove_weakest(&mut signed_submissions);
+			}
+			debug_assert!(signed_submissions.len() as u32 <= T::SignedMaxSubmissions::get());
+
+			log!(info, "queued signed solution with (claimed) score {:?}", claimed_score);
+
+			// store the new signed submission.
+			<SignedSubmissions<T>>::put(signed_submissions);
+			Self::deposit_event(RawEvent::SolutionStored(ElectionCompute::Signed));
+			Ok(None.into())
+		}
+
+		/// Submit a solution for the unsigned phase.
+		///
+		/// The dispatch origin for this call must be __none__.
+		///
+		/// This submission is checked on the fly. Moreover, this unsigned solution is only
+		/// validated when submitted to the pool from the **local** node. Effectively, this means
+		/// that only active validators can submit this transaction when authoring a block (similar
+		/// to an inherent).
+		///
+		/// To prevent any incorrect solution (and thus wasted time/weight), this transaction will
+		/// panic if the solution submitted by the validator is invalid in any way, effectively
+		/// putting their authoring reward at risk.
+		///
+		/// No deposit or reward is associated with this submission.
+		///
+		/// # <weight>
+		/// The size of the snapshot must be provided as witness data.
+		/// # </weight>
+		#[weight = T::WeightInfo::submit_unsigned(witness.voters, witness.targets)]
+		pub fn submit_unsigned(
+			origin,
+			solution: RawSolution,
+			witness: SolutionOrSnapshotSize,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			let error_message =
+				"Invalid unsigned submission must produce invalid block and \
+				deprive validator from their authoring reward.";
+
+			// Check score being an improvement, phase, and desired targets.
+			Self::unsigned_pre_dispatch_checks(&solution, &witness).expect(error_message);
+
+			let ready =
+				Self::feasibility_check(solution, ElectionCompute::Unsigned).expect(error_message);
+
+			// store the newly received solution.
+			log!(info, "queued unsigned solution with score {:?}", ready.score);
+			<QueuedSolution<T>>::put(ready);
+			Self::deposit_event(RawEvent::SolutionStored(ElectionCompute::Unsigned));
+
+			Ok(None.into())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Logic for `on_initialize` when signed phase is being opened.
+	///
+	/// This is decoupled for easy weight calculation.
+	pub(crate) fn on_initialize_open_signed() {
+		<CurrentPhase<T>>::put(Phase::Signed);
+		Self::create_snapshot();
+		Self::deposit_event(RawEvent::SignedPhaseStarted(Self::round()));
+	}
+
+	/// Logic for `on_initialize` when unsigned phase is being opened.
+	///
+	/// This is decoupled for easy weight calculation. Note that the default weight benchmark of
+	/// this function will assume an empty signed queue for `finalize_signed_phase`.
+	pub(crate) fn on_initialize_open_unsigned(
+		need_snapshot: bool,
+		enabled: bool,
+		now: T::BlockNumber,
+	) {
+		if need_snapshot {
+			// if not being followed by a signed phase, then create the snapshots.
+			debug_assert!(Self::snapshot().is_none());
+			Self::create_snapshot();
+		}
+
+		<CurrentPhase<T>>::put(Phase::Unsigned((enabled, now)));
+		Self::deposit_event(RawEvent::UnsignedPhaseStarted(Self::round()));
+	}
+
+	/// Creates the snapshot. Writes new data to:
+	///
+	/// 1. [`SnapshotMetadata`]
+	/// 2. [`Snapshot`]
+	/// 3. [`DesiredTargets`]
+	pub(crate) fn create_snapshot() {
+		let targets = T::DataProvider::targets();
+		let voters = T::DataProvider::voters();
+		let desired_targets = T::DataProvider::desired_targets();
+
+		<SnapshotMetadata>::put(SolutionOrSnapshotSize {
+			voters: voters.len() as u32,
+			targets: targets.len() as u32,
+		});
+		<DesiredTargets>::put(desired_targets);
+		<Snapshot<T>>::put(RoundSnapshot { voters, targets });
+	}
+
+	/// Kill everything created by [`Module::create_snapshot`].
+	pub(crate) fn kill_snapshot() {
+		<Snapshot<T>>::kill();
+		<SnapshotMetadata>::kill();
+		<DesiredTargets>::kill();
+	}
+
+	/// Build the supports of a compact solution, against the current snapshot.
+	///
+	/// This checks that all indices are correct, that the correct number of winners is present
+	/// and that each edge is backed by an actual vote in the snapshot.
+	pub fn supports_of_compact(
+		compact: CompactAssignments,
+	) -> Result<Supports<T::AccountId>, FeasibilityError> {
+		// read the entire snapshot.
+		let RoundSnapshot { voters: snapshot_voters, targets: snapshot_targets } =
+			Self::snapshot().ok_or(FeasibilityError::SnapshotUnavailable)?;
+		let desired_targets =
+			Self::desired_targets().ok_or(FeasibilityError::SnapshotUnavailable)?;
+
+		let voter_at = |i: VoterIndex| -> Option<T::AccountId> {
+			snapshot_voters.get(i as usize).map(|(v, _, _)| v.clone())
+		};
+		let target_at = |i: TargetIndex| -> Option<T::AccountId> {
+			snapshot_targets.get(i as usize).cloned()
+		};
+
+		// first, make sure that all the indices are correct.
+		let assignments = compact.into_assignment(voter_at, target_at)?;
+
+		// the winners are all the targets that appear in the solution.
+		let winners = assignments
+			.iter()
+			.flat_map(|Assignment { distribution, .. }| distribution.iter().map(|(t, _)| t.clone()))
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.collect::<Vec<_>>();
+		ensure!(winners.len() as u32 == desired_targets, FeasibilityError::WrongWinnerCount);
+
+		// ensure that assignments is correct: each edge must be a vote of the voter.
+		let cache = snapshot_voters
+			.iter()
+			.map(|(who, stake, votes)| (who, (*stake, votes)))
+			.collect::<BTreeMap<_, _>>();
+		for Assignment { who, distribution } in assignments.iter() {
+			let (_, votes) = cache.get(who).ok_or(FeasibilityError::InvalidVoter)?;
+			if distribution.iter().any(|(t, _)| !votes.contains(t)) {
+				return Err(FeasibilityError::InvalidVote);
+			}
+		}
+
+		// ----- Start building support. First, we need one more closure.
+		let stake_of = |who: &T::AccountId| -> VoteWeight {
+			cache.get(who).map(|(stake, _)| *stake).unwrap_or_default()
+		};
+
+		// This might fail if the normalization fails. Very unlikely. See `integrity_test`.
+		let staked_assignments = assignment_ratio_to_staked_normalized(assignments, stake_of)?;
+
+		// This might fail if one of the voter edges is pointing to a non-winner, which is not
+		// really possible anymore because all the winners come from the same `compact`.
+		to_supports(&winners, &staked_assignments).map_err(Into::into)
+	}
+
+	/// Checks the feasibility of a solution.
+	///
+	/// This checks the solution for the following:
+	///
+	/// 0. **all** of the used indices must be correct.
+	/// 1. present correct number of winners.
+	/// 2. any assignment is checked to match with the snapshot.
+	/// 3. the claimed score is valid.
+	pub fn feasibility_check(
+		solution: RawSolution,
+		compute: ElectionCompute,
+	) -> Result<ReadySolution<T::AccountId>, FeasibilityError> {
+		let RawSolution { compact, score, round } = solution;
+
+		// first, check round.
+		ensure!(Self::round() == round, FeasibilityError::InvalidRound);
+
+		let supports = Self::supports_of_compact(compact)?;
+
+		// Finally, check that the claimed score was indeed correct.
+		let known_score = evaluate_supports(&supports);
+		ensure!(known_score == score, FeasibilityError::InvalidScore);
+
+		Ok(ReadySolution { supports, compute, score })
+	}
+
+	/// Perform the tasks to be done after a new `elect` has been triggered:
+	///
+	/// 1. Increment round.
+	/// 2. Change phase to [`Phase::Off`]
+	/// 3. Clear all snapshot data.
+	fn post_elect() {
+		// inc round
+		<Round>::mutate(|r| *r = *r + 1);
+
+		// change phase
+		<CurrentPhase<T>>::put(Phase::Off);
+
+		// kill snapshots
+		Self::kill_snapshot();
+	}
+
+	/// On-chain fallback of election.
+	fn onchain_fallback() -> Result<Supports<T::AccountId>, ElectionError> {
+		<OnChainSequentialPhragmen<T> as ElectionProvider<T::AccountId, T::BlockNumber>>::elect()
+			.map_err(Into::into)
+	}
+
+	fn do_elect() -> Result<Supports<T::AccountId>, ElectionError> {
+		// If the election is triggered while the signed phase is still ongoing, the queued
+		// submissions are processed right away.
+		if Self::current_phase().is_signed() {
+			let _ = Self::finalize_signed_phase();
+		}
+
+		<QueuedSolution<T>>::take()
+			.map_or_else(
+				|| Self::onchain_fallback().map(|s| (s, ElectionCompute::OnChain)),
+				|ReadySolution { supports, compute, .. }| Ok((supports, compute)),
+			)
+			.map(|(supports, compute)| {
+				Self::deposit_event(RawEvent::ElectionFinalized(Some(compute)));
+				log!(info, "Finalized election round with compute {:?}.", compute);
+				supports
+			})
+			.map_err(|err| {
+				Self::deposit_event(RawEvent::ElectionFinalized(None));
+				log!(warn, "Failed to finalize election round. reason {:?}", err);
+				err
+			})
+	}
+}
+
+impl<T: Trait> ElectionProvider<T::AccountId, T::BlockNumber> for Module<T> {
+	type Error = ElectionError;
+	type DataProvider = T::DataProvider;
+
+	fn elect() -> Result<Supports<T::AccountId>, Self::Error> {
+		let outcome = Self::do_elect();
+		Self::post_elect();
+		outcome
+	}
+}
+
+#[allow(deprecated)]
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+	fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		if let Call::submit_unsigned(solution, witness) = call {
+			// discard solution not coming from the local OCW.
+			match source {
+				TransactionSource::Local | TransactionSource::InBlock => { /* allowed */ }
+				_ => {
+					return InvalidTransaction::Call.into();
+				}
+			}
+
+			if let Err(error) = Self::unsigned_pre_dispatch_checks(solution, witness) {
+				let invalid = dispatch_error_to_invalid(error);
+				log!(debug, "unsigned transaction validation failed due to {:?}", invalid);
+				return invalid.into();
+			}
+
+			ValidTransaction::with_tag_prefix("OffchainElection")
+				// The higher the score[0], the better a solution is.
+				.priority(
+					T::UnsignedPriority::get().saturating_add(solution.score[0].saturated_into()),
+				)
+				// Used to deduplicate unsigned solutions: each validator should produce one
+				// solution per round at most, and solutions are not propagate.
+				.and_provides(solution.round)
+				// Transaction should stay in the pool for the duration of the unsigned phase.
+				.longevity(T::UnsignedPhase::get().saturated_into::<u64>())
+				// We don't propagate this. This can never be validated at a remote node.
+				.propagate(false)
+				.build()
+		} else {
+			InvalidTransaction::Call.into()
+		}
+	}
+
+	fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+		if let Call::submit_unsigned(solution, witness) = call {
+			// IMPORTANT NOTE: These checks are performed in the dispatch call itself, yet we need
+			// to duplicate them here to prevent a block producer from putting a previously
+			// validated, yet no longer valid solution on chain.
+			Self::unsigned_pre_dispatch_checks(solution, witness)
+				.map_err(dispatch_error_to_invalid)
+				.map_err(Into::into)
+		} else {
+			Err(InvalidTransaction::Call.into())
+		}
+	}
+}
+
+/// Convert a `DispatchError` to a custom `InvalidTransaction` with the inner code being the error
+/// number.
+fn dispatch_error_to_invalid(error: DispatchError) -> InvalidTransaction {
+	let error_number = match error {
+		DispatchError::Module { error, .. } => error,
+		_ => 0,
+	};
+	InvalidTransaction::Custom(error_number)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{mock::*, *};
+	use frame_support::{assert_noop, assert_ok};
+	use sp_npos_elections::Support;
+
+	#[test]
+	fn phase_rotation_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			// 0 ------- 15 ------- 25 ------- 30 ------- ------- 45 ------- 55 ------- 60
+			//           |           |                            |           |
+			//         Signed      Unsigned                     Signed      Unsigned
+
+			assert_eq!(System::block_number(), 0);
+			assert_eq!(MultiPhase::current_phase(), Phase::Off);
+			assert_eq!(MultiPhase::round(), 1);
+
+			roll_to(4);
+			assert_eq!(MultiPhase::current_phase(), Phase::Off);
+			assert!(MultiPhase::snapshot().is_none());
+			assert_eq!(MultiPhase::round(), 1);
+
+			roll_to(15);
+			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
+			assert_eq!(multi_phase_events(), vec![RawEvent::SignedPhaseStarted(1)]);
+			assert!(MultiPhase::snapshot().is_some());
+			assert_eq!(MultiPhase::round(), 1);
+
+			roll_to(24);
+			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
+			assert!(MultiPhase::snapshot().is_some());
+			assert_eq!(MultiPhase::round(), 1);
+
+			roll_to(25);
+			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
+			assert_eq!(
+				multi_phase_events(),
+				vec![RawEvent::SignedPhaseStarted(1), RawEvent::UnsignedPhaseStarted(1)],
+			);
+			assert!(MultiPhase::snapshot().is_some());
+
+			roll_to(29);
+			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
+			assert!(MultiPhase::snapshot().is_some());
+
+			roll_to(30);
+			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
+			assert!(MultiPhase::snapshot().is_some());
+
+			// we close when upstream tells us to elect.
+			roll_to(32);
+			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
+			assert!(MultiPhase::snapshot().is_some());
+
+			assert_ok!(MultiPhase::elect());
+
+			assert!(MultiPhase::current_phase().is_off());
+			assert!(MultiPhase::snapshot().is_none());
+			assert_eq!(MultiPhase::round(), 2);
+
+			roll_to(44);
+			assert!(MultiPhase::current_phase().is_off());
+
+			roll_to(45);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			roll_to(55);
+			assert!(MultiPhase::current_phase().is_unsigned_open_at(55));
+		})
+	}
+
+	#[test]
+	fn signed_phase_void() {
+		ExtBuilder::default().phases(0, 10).build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_off());
+
+			roll_to(19);
+			assert!(MultiPhase::current_phase().is_off());
+
+			roll_to(20);
+			assert!(MultiPhase::current_phase().is_unsigned_open_at(20));
+			assert!(MultiPhase::snapshot().is_some());
+
+			roll_to(30);
+			assert!(MultiPhase::current_phase().is_unsigned_open_at(20));
+
+			assert_ok!(MultiPhase::elect());
+
+			assert!(MultiPhase::current_phase().is_off());
+			assert!(MultiPhase::snapshot().is_none());
+		});
+	}
+
+	#[test]
+	fn unsigned_phase_void() {
+		ExtBuilder::default().phases(10, 0).build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_off());
+
+			roll_to(19);
+			assert!(MultiPhase::current_phase().is_off());
+
+			roll_to(20);
+			assert!(MultiPhase::current_phase().is_signed());
+			assert!(MultiPhase::snapshot().is_some());
+
+			roll_to(30);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			assert_ok!(MultiPhase::elect());
+
+			assert!(MultiPhase::current_phase().is_off());
+			assert!(MultiPhase::snapshot().is_none());
+		});
+	}
+
+	#[test]
+	fn both_phases_void() {
+		ExtBuilder::default().phases(0, 0).build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_off());
+
+			roll_to(19);
+			assert!(MultiPhase::current_phase().is_off());
+
+			roll_to(20);
+			assert!(MultiPhase::current_phase().is_off());
+
+			roll_to(30);
+			assert!(MultiPhase::current_phase().is_off());
+
+			// this module is now only capable of doing on-chain backup.
+			assert_ok!(MultiPhase::elect());
+
+			assert!(MultiPhase::current_phase().is_off());
+		});
+	}
+
+	#[test]
+	fn early_termination() {
+		// an early termination in the signed phase, with no queued solution.
+		ExtBuilder::default().build_and_execute(|| {
+			// signed phase started at block 15 and will end at 25.
+			roll_to(14);
+			assert_eq!(MultiPhase::current_phase(), Phase::Off);
+
+			roll_to(15);
+			assert_eq!(multi_phase_events(), vec![RawEvent::SignedPhaseStarted(1)]);
+			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
+			assert_eq!(MultiPhase::round(), 1);
+
+			// an unexpected call to elect.
+			roll_to(20);
+			let supports = MultiPhase::elect().unwrap();
+
+			// we surely can't have any feasible solutions. This will cause an on-chain election.
+			assert_eq!(
+				multi_phase_events(),
+				vec![
+					RawEvent::SignedPhaseStarted(1),
+					RawEvent::ElectionFinalized(Some(ElectionCompute::OnChain)),
+				],
+			);
+			// the on-chain fallback elected a full set.
+			assert_eq!(supports.len(), 2);
+			// all storage items must be cleared.
+			assert_eq!(MultiPhase::round(), 2);
+			assert!(MultiPhase::snapshot().is_none());
+			assert!(MultiPhase::snapshot_metadata().is_none());
+			assert!(MultiPhase::desired_targets().is_none());
+			assert!(MultiPhase::queued_solution().is_none());
+			assert!(MultiPhase::signed_submissions().is_empty());
+		})
+	}
+
+	#[test]
+	fn queued_solution_is_used_by_elect() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			let supports = vec![(10, Support { total: 40, voters: vec![(10, 40)] })];
+			<QueuedSolution<Runtime>>::put(ReadySolution {
+				supports: supports.clone(),
+				score: [40, 40, 1600],
+				compute: ElectionCompute::Unsigned,
+			});
+
+			assert_eq!(MultiPhase::elect().unwrap(), supports);
+			assert!(MultiPhase::queued_solution().is_none());
+			assert_eq!(
+				multi_phase_events().last().cloned(),
+				Some(RawEvent::ElectionFinalized(Some(ElectionCompute::Unsigned))),
+			);
+		})
+	}
+
+	#[test]
+	fn feasibility_check_detects_wrong_round_and_score() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			let solution = raw_solution();
+			assert_ok!(MultiPhase::feasibility_check(solution.clone(), ElectionCompute::Signed));
+
+			let mut wrong_round = solution.clone();
+			wrong_round.round += 1;
+			assert_eq!(
+				MultiPhase::feasibility_check(wrong_round, ElectionCompute::Signed),
+				Err(FeasibilityError::InvalidRound),
+			);
+
+			let mut wrong_score = solution;
+			wrong_score.score[0] += 1;
+			assert_eq!(
+				MultiPhase::feasibility_check(wrong_score, ElectionCompute::Signed),
+				Err(FeasibilityError::InvalidScore),
+			);
+		})
+	}
+
+	#[test]
+	fn feasibility_check_detects_wrong_winner_count() {
+		ExtBuilder::default().desired_targets(3).build_and_execute(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			// a solution with two winners, while three are desired.
+			let solution = {
+				DesiredTargets::put(2);
+				let solution = raw_solution();
+				DesiredTargets::put(3);
+				solution
+			};
+			assert_eq!(
+				MultiPhase::feasibility_check(solution, ElectionCompute::Signed),
+				Err(FeasibilityError::WrongWinnerCount),
+			);
+		})
+	}
+
+	#[test]
+	fn submit_outside_of_signed_phase_fails() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(5);
+			assert!(MultiPhase::current_phase().is_off());
+			assert_noop!(
+				MultiPhase::submit(Origin::signed(10), Default::default(), 0),
+				Error::<Runtime>::PreDispatchEarlySubmission,
+			);
+		})
+	}
+}
@@ -0,0 +1,383 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities
+
+use crate::*;
+use frame_support::{
+	impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types,
+	traits::{OffchainWorker, OnInitialize},
+	weights::Weight,
+};
+use parking_lot::RwLock;
+use sp_core::{
+	offchain::{
+		testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
+		OffchainExt, TransactionPoolExt,
+	},
+	H256,
+};
+use sp_runtime::{
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use std::{cell::RefCell, sync::Arc};
+
+pub type AccountId = u64;
+pub type Balance = u64;
+pub type BlockNumber = u64;
+
+pub(crate) type System = frame_system::Module<Runtime>;
+pub(crate) type Balances = pallet_balances::Module<Runtime>;
+pub(crate) type MultiPhase = Module<Runtime>;
+pub(crate) type Extrinsic = TestXt<OuterCall, ()>;
+
+/// The targets that are registered by default.
+pub(crate) const DEFAULT_TARGETS: [AccountId; 4] = [10, 20, 30, 40];
+
+mod multi_phase {
+	// Re-export needed for `impl_outer_event!`.
+	pub use crate::*;
+}
+
+impl_outer_origin! {
+	pub enum Origin for Runtime where system = frame_system {}
+}
+
+impl_outer_dispatch! {
+	pub enum OuterCall for Runtime where origin: Origin {
+		multi_phase::MultiPhase,
+	}
+}
+
+impl_outer_event! {
+	pub enum MetaEvent for Runtime {
+		frame_system<T>,
+		pallet_balances<T>,
+		multi_phase<T>,
+	}
+}
+
+thread_local! {
+	static SIGNED_PHASE: RefCell<BlockNumber> = RefCell::new(10);
+	static UNSIGNED_PHASE: RefCell<BlockNumber> = RefCell::new(5);
+	static SIGNED_MAX_SUBMISSIONS: RefCell<u32> = RefCell::new(5);
+	static SOLUTION_IMPROVEMENT_THRESHOLD: RefCell<Perbill> = RefCell::new(Perbill::zero());
+	static UNSIGNED_PRIORITY: RefCell<TransactionPriority> = RefCell::new(100);
+	static MINER_MAX_ITERATIONS: RefCell<u32> = RefCell::new(5);
+	static DESIRED_TARGETS: RefCell<u32> = RefCell::new(2);
+	static TARGETS: RefCell<Vec<AccountId>> = RefCell::new(DEFAULT_TARGETS.to_vec());
+	static VOTERS: RefCell<Vec<(AccountId, VoteWeight, Vec<AccountId>)>> = RefCell::new(vec![
+		(1, 10, vec![10, 20]),
+		(2, 10, vec![30, 40]),
+		(3, 10, vec![40]),
+		(4, 10, vec![10, 20, 30, 40]),
+		// self votes.
+		(10, 10, vec![10]),
+		(20, 20, vec![20]),
+		(30, 30, vec![30]),
+		(40, 40, vec![40]),
+	]);
+}
+
+pub struct SignedPhase;
+impl Get<BlockNumber> for SignedPhase {
+	fn get() -> BlockNumber {
+		SIGNED_PHASE.with(|v| *v.borrow())
+	}
+}
+
+pub struct UnsignedPhase;
+impl Get<BlockNumber> for UnsignedPhase {
+	fn get() -> BlockNumber {
+		UNSIGNED_PHASE.with(|v| *v.borrow())
+	}
+}
+
+pub struct SignedMaxSubmissions;
+impl Get<u32> for SignedMaxSubmissions {
+	fn get() -> u32 {
+		SIGNED_MAX_SUBMISSIONS.with(|v| *v.borrow())
+	}
+}
+
+pub struct SolutionImprovementThreshold;
+impl Get<Perbill> for SolutionImprovementThreshold {
+	fn get() -> Perbill {
+		SOLUTION_IMPROVEMENT_THRESHOLD.with(|v| *v.borrow())
+	}
+}
+
+pub struct UnsignedPriority;
+impl Get<TransactionPriority> for UnsignedPriority {
+	fn get() -> TransactionPriority {
+		UNSIGNED_PRIORITY.with(|v| *v.borrow())
+	}
+}
+
+pub struct MinerMaxIterations;
+impl Get<u32> for MinerMaxIterations {
+	fn get() -> u32 {
+		MINER_MAX_ITERATIONS.with(|v| *v.borrow())
+	}
+}
+
+/// A mock of the staking pallet, providing the election data from thread local storage.
+pub struct StakingMock;
+impl ElectionDataProvider<AccountId, BlockNumber> for StakingMock {
+	fn targets() -> Vec<AccountId> {
+		TARGETS.with(|targets| targets.borrow().clone())
+	}
+
+	fn voters() -> Vec<(AccountId, VoteWeight, Vec<AccountId>)> {
+		VOTERS.with(|voters| voters.borrow().clone())
+	}
+
+	fn desired_targets() -> u32 {
+		DESIRED_TARGETS.with(|t| *t.borrow())
+	}
+
+	fn next_election_prediction(now: BlockNumber) -> BlockNumber {
+		now + EpochLength::get() - now % EpochLength::get()
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Runtime;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+	pub const ExistentialDeposit: Balance = 1;
+	pub const SignedRewardBase: Balance = 7;
+	pub const SignedDepositBase: Balance = 5;
+	pub const SignedDepositByte: Balance = 0;
+	pub const EpochLength: BlockNumber = 30;
+}
+
+impl frame_system::Trait for Runtime {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = OuterCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = MetaEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl pallet_balances::Trait for Runtime {
+	type Balance = Balance;
+	type Event = MetaEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl crate::Trait for Runtime {
+	type Event = MetaEvent;
+	type Currency = Balances;
+	type SignedPhase = SignedPhase;
+	type UnsignedPhase = UnsignedPhase;
+	type SignedMaxSubmissions = SignedMaxSubmissions;
+	type SignedRewardBase = SignedRewardBase;
+	type SignedDepositBase = SignedDepositBase;
+	type SignedDepositByte = SignedDepositByte;
+	type SlashHandler = ();
+	type RewardHandler = ();
+	type SolutionImprovementThreshold = SolutionImprovementThreshold;
+	type MinerMaxIterations = MinerMaxIterations;
+	type UnsignedPriority = UnsignedPriority;
+	type DataProvider = StakingMock;
+	type WeightInfo = ();
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Runtime where
+	OuterCall: From<LocalCall>,
+{
+	type OverarchingCall = OuterCall;
+	type Extrinsic = Extrinsic;
+}
+
+pub struct ExtBuilder {
+	signed_phase: BlockNumber,
+	unsigned_phase: BlockNumber,
+	signed_max_submission: u32,
+	solution_improvement_threshold: Perbill,
+	unsigned_priority: TransactionPriority,
+	desired_targets: u32,
+	additional_voters: Vec<(AccountId, VoteWeight, Vec<AccountId>)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			signed_phase: 10,
+			unsigned_phase: 5,
+			signed_max_submission: 5,
+			solution_improvement_threshold: Perbill::zero(),
+			unsigned_priority: 100,
+			desired_targets: 2,
+			additional_voters: vec![],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn phases(mut self, signed: BlockNumber, unsigned: BlockNumber) -> Self {
+		self.signed_phase = signed;
+		self.unsigned_phase = unsigned;
+		self
+	}
+	pub fn signed_max_submission(mut self, count: u32) -> Self {
+		self.signed_max_submission = count;
+		self
+	}
+	pub fn solution_improvement_threshold(mut self, threshold: Perbill) -> Self {
+		self.solution_improvement_threshold = threshold;
+		self
+	}
+	pub fn unsigned_priority(mut self, priority: TransactionPriority) -> Self {
+		self.unsigned_priority = priority;
+		self
+	}
+	pub fn desired_targets(mut self, count: u32) -> Self {
+		self.desired_targets = count;
+		self
+	}
+	pub fn add_voter(mut self, who: AccountId, stake: VoteWeight, targets: Vec<AccountId>) -> Self {
+		self.additional_voters.push((who, stake, targets));
+		self
+	}
+	fn set_associated_constants(&self) {
+		SIGNED_PHASE.with(|v| *v.borrow_mut() = self.signed_phase);
+		UNSIGNED_PHASE.with(|v| *v.borrow_mut() = self.unsigned_phase);
+		SIGNED_MAX_SUBMISSIONS.with(|v| *v.borrow_mut() = self.signed_max_submission);
+		SOLUTION_IMPROVEMENT_THRESHOLD
+			.with(|v| *v.borrow_mut() = self.solution_improvement_threshold);
+		UNSIGNED_PRIORITY.with(|v| *v.borrow_mut() = self.unsigned_priority);
+		DESIRED_TARGETS.with(|v| *v.borrow_mut() = self.desired_targets);
+		VOTERS.with(|v| v.borrow_mut().extend(self.additional_voters.iter().cloned()));
+	}
+	pub fn build(self) -> sp_io::TestExternalities {
+		self.set_associated_constants();
+		let mut storage =
+			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+
+		let _ = pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![
+				// bunch of account for submitting stuff only.
+				(99, 100),
+				(999, 100),
+				(9999, 100),
+			],
+		}
+		.assimilate_storage(&mut storage);
+
+		sp_io::TestExternalities::from(storage)
+	}
+	pub fn build_offchainify(
+		self,
+		iters: u32,
+	) -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>) {
+		let mut ext = self.build();
+		let (offchain, offchain_state) = TestOffchainExt::new();
+		let (pool, pool_state) = TestTransactionPoolExt::new();
+
+		let mut seed = [0_u8; 32];
+		seed[0..4].copy_from_slice(&iters.to_le_bytes());
+		offchain_state.write().seed = seed;
+
+		ext.register_extension(OffchainExt::new(offchain));
+		ext.register_extension(TransactionPoolExt::new(pool));
+
+		(ext, pool_state)
+	}
+	pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
+		self.build().execute_with(test)
+	}
+}
+
+/// Progress to the given block, triggering the `on_initialize` of the pallet at each block.
+pub fn roll_to(n: BlockNumber) {
+	let now = System::block_number();
+	for i in now + 1..=n {
+		System::set_block_number(i);
+		MultiPhase::on_initialize(i);
+	}
+}
+
+/// Same as [`roll_to`], but also runs the offchain worker at each block.
+pub fn roll_to_with_ocw(n: BlockNumber) {
+	let now = System::block_number();
+	for i in now + 1..=n {
+		System::set_block_number(i);
+		MultiPhase::on_initialize(i);
+		MultiPhase::offchain_worker(i);
+	}
+}
+
+/// All the events of this pallet that have been deposited so far.
+pub fn multi_phase_events() -> Vec<super::Event<Runtime>> {
+	System::events()
+		.into_iter()
+		.filter_map(|e| match e.event {
+			MetaEvent::multi_phase(inner) => Some(inner),
+			_ => None,
+		})
+		.collect::<Vec<_>>()
+}
+
+/// Mine a correct solution for the current snapshot, without any balancing.
+pub fn raw_solution() -> RawSolution {
+	MultiPhase::mine_solution(0).unwrap().0
+}
+
+/// The witness data of the current snapshot, or the default if none exists.
+pub fn witness() -> SolutionOrSnapshotSize {
+	MultiPhase::snapshot()
+		.map(|snap| SolutionOrSnapshotSize {
+			voters: snap.voters.len() as u32,
+			targets: snap.targets.len() as u32,
+		})
+		.unwrap_or_default()
+}
+
+/// The free and reserved balance of `who`.
+pub fn balances(who: &AccountId) -> (Balance, Balance) {
+	(Balances::free_balance(who), Balances::reserved_balance(who))
+}
@@ -0,0 +1,490 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The signed phase implementation.
+
+use crate::*;
+use codec::{Encode, Decode, HasCompact};
+use sp_npos_elections::is_score_better;
+use sp_runtime::{RuntimeDebug, traits::Saturating};
+
+/// The balance type of this module.
+pub type BalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+/// The positive imbalance type of this module.
+pub type PositiveImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::PositiveImbalance;
+/// The negative imbalance type of this module.
+pub type NegativeImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+
+/// A raw, unchecked signed submission.
+///
+/// This is just a wrapper around [`RawSolution`] and some additional info.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, Default)]
+pub struct SignedSubmission<AccountId, Balance: HasCompact> {
+	/// Who submitted this solution.
+	pub who: AccountId,
+	/// The deposit reserved for storing this solution.
+	pub deposit: Balance,
+	/// The reward that should be given to this solution, if chosen as the best one.
+	pub reward: Balance,
+	/// The raw solution itself.
+	pub solution: RawSolution,
+}
+
+impl<T: Trait> Module<T> {
+	/// Finish the signed phase. Process the signed submissions from best to worse until a valid one
+	/// is found, rewarding the best one and slashing the invalid ones along the way.
+	///
+	/// Returns true if we have a good solution in the signed phase.
+	///
+	/// This drains the [`SignedSubmissions`], potentially storing the best valid one in
+	/// [`QueuedSolution`].
+	pub fn finalize_signed_phase() -> (bool, Weight) {
+		let mut all_submissions: Vec<SignedSubmission<_, _>> = <SignedSubmissions<T>>::take();
+		let mut found_solution = false;
+		let mut weight = T::DbWeight::get().reads(1);
+
+		while let Some(best) = all_submissions.pop() {
+			let SignedSubmission { solution, who, deposit, reward } = best;
+			match Self::feasibility_check(solution, ElectionCompute::Signed) {
+				Ok(ready_solution) => {
+					Self::finalize_signed_phase_accept_solution(
+						ready_solution,
+						&who,
+						deposit,
+						reward,
+					);
+					found_solution = true;
+
+					weight = weight.saturating_add(
+						T::WeightInfo::finalize_signed_phase_accept_solution(),
+					);
+					break;
+				}
+				Err(_) => {
+					Self::finalize_signed_phase_reject_solution(&who, deposit);
+					weight = weight.saturating_add(
+						T::WeightInfo::finalize_signed_phase_reject_solution(),
+					);
+				}
+			}
+		}
+
+		// Any unprocessed solution is pointless to even consider. Feasible or malicious,
+		// they didn't end up being used. Unreserve the bonds.
+		for not_processed in all_submissions {
+			let SignedSubmission { who, deposit, .. } = not_processed;
+			let _remaining = T::Currency::unreserve(&who, deposit);
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			debug_assert!(_remaining.is_zero());
+		}
+
+		log!(debug, "closed signed phase, found solution? {}", found_solution);
+		(found_solution, weight)
+	}
+
+	/// Helper function for the case where a solution is accepted in the signed phase.
+	///
+	/// Extracted to facilitate with weight calculation.
+	///
+	/// Infallible
+	pub fn finalize_signed_phase_accept_solution(
+		ready_solution: ReadySolution<T::AccountId>,
+		who: &T::AccountId,
+		deposit: BalanceOf<T>,
+		reward: BalanceOf<T>,
+	) {
+		// write this ready solution.
+		<QueuedSolution<T>>::put(ready_solution);
+
+		// unreserve deposit.
+		let _remaining = T::Currency::unreserve(who, deposit);
+		debug_assert!(_remaining.is_zero());
+
+		// Reward.
+		let positive_imbalance = T::Currency::deposit_creating(who, reward);
+		T::RewardHandler::on_unbalanced(positive_imbalance);
+
+		Self::deposit_event(RawEvent::Rewarded(who.clone()));
+	}
+
+	/// Helper function for the case where a solution is rejected in the signed phase.
+	///
+	/// Extracted to facilitate with weight calculation.
+	///
+	/// Infallible
+	pub fn finalize_signed_phase_reject_solution(who: &T::AccountId, deposit: BalanceOf<T>) {
+		let (negative_imbalance, _remaining) = T::Currency::slash_reserved(who, deposit);
+		debug_assert!(_remaining.is_zero());
+		T::SlashHandler::on_unbalanced(negative_imbalance);
+
+		Self::deposit_event(RawEvent::Slashed(who.clone()));
+	}
+
+	/// Insert a solution into the queue while maintaining an ordering by solution quality.
+	///
+	/// Solutions are ordered in reverse: strong -> weak. This is because we expect to eventually
+	/// iterate over them from the end.
+	///
+	/// If the queue is full, the solution is only inserted if it is better than the weakest one
+	/// (at index 0). In this case, the queue temporarily holds one element more than the maximum
+	/// and [`Module::remove_weakest`] must be called once the new deposit has been reserved.
+	///
+	/// Returns the index of the newly inserted item, if any.
+	pub fn insert_submission(
+		who: &T::AccountId,
+		queue: &mut Vec<SignedSubmission<T::AccountId, BalanceOf<T>>>,
+		solution: RawSolution,
+	) -> Option<usize> {
+		// from the last score, compare and see if the current one is better. If none, then the
+		// awarded index is 0.
+		let outcome = queue
+			.iter()
+			.enumerate()
+			.rev()
+			.find_map(|(i, s)| {
+				if is_score_better::<Perbill>(
+					solution.score,
+					s.solution.score,
+					T::SolutionImprovementThreshold::get(),
+				) {
+					Some(i + 1)
+				} else {
+					None
+				}
+			})
+			.or(Some(0))
+			.and_then(|at| {
+				if at == 0 && queue.len() as u32 >= T::SignedMaxSubmissions::get() {
+					// if this is worse than all, and the queue is full, don't bother.
+					None
+				} else {
+					// add to the designated spot. If the length is too much, remove one.
+					let reward = T::SignedRewardBase::get();
+					let deposit = Self::deposit_for(&solution);
+					let submission =
+						SignedSubmission { who: who.clone(), deposit, reward, solution };
+					// Proof: `at` must always less than or equal queue.len() for this not to panic.
+					// It is either 0 (in which case `0 <= queue.len()`) or one of the queue indices
+					// + 1. The biggest queue index is `queue.len() - 1`, thus `at <= queue.len()`.
+					queue.insert(at, submission);
+					Some(at)
+				}
+			});
+
+		debug_assert!(queue.len() as u32 <= T::SignedMaxSubmissions::get() + 1);
+		outcome
+	}
+
+	/// Remove the weakest submission from an over-full queue and return its deposit.
+	pub fn remove_weakest(queue: &mut Vec<SignedSubmission<T::AccountId, BalanceOf<T>>>) {
+		debug_assert!(queue.len() as u32 > T::SignedMaxSubmissions::get());
+		let SignedSubmission { who, deposit, .. } = queue.remove(0);
+		let _remaining = T::Currency::unreserve(&who, deposit);
+		debug_assert!(_remaining.is_zero());
+	}
+
+	/// The deposit required to submit a solution.
+	///
+	/// This is a base deposit, plus a deposit per byte of the encoded solution.
+	pub fn deposit_for(solution: &RawSolution) -> BalanceOf<T> {
+		let encoded_len: BalanceOf<T> = solution.using_encoded(|e| e.len() as u32).into();
+		T::SignedDepositBase::get()
+			.saturating_add(T::SignedDepositByte::get().saturating_mul(encoded_len))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{mock::*, *};
+	use frame_support::{assert_noop, assert_ok, dispatch::DispatchResultWithPostInfo};
+
+	fn submit_with_witness(origin: Origin, solution: RawSolution) -> DispatchResultWithPostInfo {
+		MultiPhase::submit(origin, solution, MultiPhase::signed_submissions().len() as u32)
+	}
+
+	#[test]
+	fn cannot_submit_too_early() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(2);
+			assert_eq!(MultiPhase::current_phase(), Phase::Off);
+
+			// create a temp snapshot only for this test.
+			MultiPhase::create_snapshot();
+			let solution = raw_solution();
+
+			assert_noop!(
+				submit_with_witness(Origin::signed(10), solution),
+				Error::<Runtime>::PreDispatchEarlySubmission,
+			);
+		})
+	}
+
+	#[test]
+	fn wrong_round_is_rejected() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let mut solution = raw_solution();
+			solution.round = 2;
+
+			assert_noop!(
+				submit_with_witness(Origin::signed(10), solution),
+				Error::<Runtime>::PreDispatchWrongRound,
+			);
+		})
+	}
+
+	#[test]
+	fn wrong_witness_is_rejected() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			assert_ok!(submit_with_witness(Origin::signed(99), raw_solution()));
+
+			assert_noop!(
+				MultiPhase::submit(Origin::signed(999), raw_solution(), 0),
+				Error::<Runtime>::SignedInvalidWitness,
+			);
+		})
+	}
+
+	#[test]
+	fn should_pay_deposit() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let solution = raw_solution();
+			assert_eq!(balances(&99), (100, 0));
+
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+
+			assert_eq!(balances(&99), (95, 5));
+			assert_eq!(MultiPhase::signed_submissions().first().unwrap().deposit, 5);
+		})
+	}
+
+	#[test]
+	fn good_solution_is_rewarded() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let solution = raw_solution();
+			assert_eq!(balances(&99), (100, 0));
+
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			assert_eq!(balances(&99), (95, 5));
+
+			assert!(MultiPhase::finalize_signed_phase().0);
+			assert_eq!(balances(&99), (100 + 7, 0));
+			assert!(MultiPhase::queued_solution().is_some());
+		})
+	}
+
+	#[test]
+	fn bad_solution_is_slashed() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let mut solution = raw_solution();
+			assert_eq!(balances(&99), (100, 0));
+
+			// make the solution invalid.
+			solution.score[0] += 1;
+
+			assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			assert_eq!(balances(&99), (95, 5));
+
+			// no good solution was stored.
+			assert!(!MultiPhase::finalize_signed_phase().0);
+			// and the bond is gone.
+			assert_eq!(balances(&99), (95, 0));
+			assert!(MultiPhase::queued_solution().is_none());
+		})
+	}
+
+	#[test]
+	fn suppressed_solution_gets_bond_back() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			let mut solution = raw_solution();
+			assert_eq!(balances(&99), (100, 0));
+			assert_eq!(balances(&999), (100, 0));
+
+			// submit as correct.
+			assert_ok!(submit_with_witness(Origin::signed(99), solution.clone()));
+
+			// make the solution invalid and weaker.
+			solution.score[0] -= 1;
+			assert_ok!(submit_with_witness(Origin::signed(999), solution));
+			assert_eq!(balances(&99), (95, 5));
+			assert_eq!(balances(&999), (95, 5));
+
+			// _some_ good solution was stored.
+			assert!(MultiPhase::finalize_signed_phase().0);
+
+			// 99 is rewarded.
+			assert_eq!(balances(&99), (100 + 7, 0));
+			// 999 gets everything back.
+			assert_eq!(balances(&999), (100, 0));
+		})
+	}
+
+	#[test]
+	fn cannot_submit_worse_with_full_queue() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			for s in 0..SignedMaxSubmissions::get() {
+				// score is always getting better
+				let solution =
+					RawSolution { score: [(5 + s).into(), 0, 0], round: 1, ..Default::default() };
+				assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			}
+
+			// weaker.
+			let solution = RawSolution { score: [4, 0, 0], round: 1, ..Default::default() };
+
+			assert_noop!(
+				submit_with_witness(Origin::signed(99), solution),
+				Error::<Runtime>::SignedQueueFull,
+			);
+		})
+	}
+
+	#[test]
+	fn weakest_is_removed_if_better_provided() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			for s in 0..SignedMaxSubmissions::get() {
+				// score is always getting better
+				let solution =
+					RawSolution { score: [(5 + s).into(), 0, 0], round: 1, ..Default::default() };
+				assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			}
+
+			assert_eq!(
+				MultiPhase::signed_submissions()
+					.into_iter()
+					.map(|s| s.solution.score[0])
+					.collect::<Vec<_>>(),
+				vec![5, 6, 7, 8, 9]
+			);
+
+			// better.
+			let solution = RawSolution { score: [20, 0, 0], round: 1, ..Default::default() };
+			assert_ok!(submit_with_witness(Origin::signed(999), solution));
+
+			// the one with score 5 was rejected, the new one inserted.
+			assert_eq!(
+				MultiPhase::signed_submissions()
+					.into_iter()
+					.map(|s| s.solution.score[0])
+					.collect::<Vec<_>>(),
+				vec![6, 7, 8, 9, 20]
+			);
+			// 99 got the deposit of the removed solution back.
+			assert_eq!(balances(&99), (100 - 4 * 5, 4 * 5));
+		})
+	}
+
+	#[test]
+	fn equally_good_is_not_accepted() {
+		ExtBuilder::default().signed_max_submission(3).build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			for i in 0..SignedMaxSubmissions::get() {
+				let solution =
+					RawSolution { score: [(5 + i).into(), 0, 0], round: 1, ..Default::default() };
+				assert_ok!(submit_with_witness(Origin::signed(99), solution));
+			}
+			assert_eq!(
+				MultiPhase::signed_submissions()
+					.into_iter()
+					.map(|s| s.solution.score[0])
+					.collect::<Vec<_>>(),
+				vec![5, 6, 7]
+			);
+
+			// 5 is not accepted. This will only cause processing with no benefit.
+			let solution = RawSolution { score: [5, 0, 0], round: 1, ..Default::default() };
+			assert_noop!(
+				submit_with_witness(Origin::signed(99), solution),
+				Error::<Runtime>::SignedQueueFull,
+			);
+		})
+	}
+
+	#[test]
+	fn all_in_one_signed_submission_scenario() {
+		// a combination of:
+		// - good_solution_is_rewarded
+		// - bad_solution_is_slashed
+		// - suppressed_solution_gets_bond_back
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			assert_eq!(balances(&99), (100, 0));
+			assert_eq!(balances(&999), (100, 0));
+			assert_eq!(balances(&9999), (100, 0));
+			let solution = raw_solution();
+
+			// submit a correct one.
+			assert_ok!(submit_with_witness(Origin::signed(99), solution.clone()));
+
+			// make the solution invalidly better and submit. This ought to be slashed.
+			let mut solution_999 = solution.clone();
+			solution_999.score[0] += 1;
+			assert_ok!(submit_with_witness(Origin::signed(999), solution_999));
+
+			// make the solution invalidly worse and submit. This ought to be suppressed and
+			// returned.
+			let mut solution_9999 = solution.clone();
+			solution_9999.score[0] -= 1;
+			assert_ok!(submit_with_witness(Origin::signed(9999), solution_9999));
+
+			assert_eq!(
+				MultiPhase::signed_submissions().iter().map(|x| x.who).collect::<Vec<_>>(),
+				vec![9999, 99, 999]
+			);
+
+			// _some_ good solution was stored.
+			assert!(MultiPhase::finalize_signed_phase().0);
+
+			// 99 is rewarded.
+			assert_eq!(balances(&99), (100 + 7, 0));
+			// 999 is slashed.
+			assert_eq!(balances(&999), (95, 0));
+			// 9999 gets everything back.
+			assert_eq!(balances(&9999), (100, 0));
+		})
+	}
+}
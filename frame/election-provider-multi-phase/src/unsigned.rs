@@ -0,0 +1,613 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The unsigned phase implementation.
+
+use crate::*;
+use codec::Decode;
+use frame_support::dispatch::DispatchResult;
+use frame_system::offchain::SubmitTransaction;
+use sp_npos_elections::{
+	seq_phragmen, to_without_backing, assignment_staked_to_ratio_normalized, balance_solution,
+	build_support_map, reduce, ElectionResult,
+};
+use sp_runtime::{offchain::storage::StorageValueRef, traits::TrailingZeroInput};
+use sp_std::convert::TryInto;
+
+/// Storage key used to store the persistent offchain worker status.
+pub(crate) const OFFCHAIN_HEAD_DB: &[u8] = b"parity/multi-phase-unsigned-election";
+
+/// The repeat threshold of the offchain worker. This means we won't run the offchain worker twice
+/// within a window of 5 blocks.
+pub(crate) const OFFCHAIN_REPEAT: u32 = 5;
+
+/// Errors that can happen in the offchain miner.
+#[derive(RuntimeDebug, Eq, PartialEq)]
+pub enum MinerError {
+	/// An internal error in the NPoS elections crate.
+	NposElections(sp_npos_elections::Error),
+	/// Snapshot data was unavailable unexpectedly.
+	SnapshotUnAvailable,
+	/// The election algorithm returned no result.
+	ElectionFailed,
+	/// Submitting a transaction to the pool failed.
+	PoolSubmissionFailed,
+	/// The pre-dispatch checks failed for the mined solution.
+	PreDispatchChecksFailed,
+	/// The solution generated from the miner is not feasible.
+	Feasibility(FeasibilityError),
+}
+
+impl From<sp_npos_elections::Error> for MinerError {
+	fn from(e: sp_npos_elections::Error) -> Self {
+		MinerError::NposElections(e)
+	}
+}
+
+impl From<FeasibilityError> for MinerError {
+	fn from(e: FeasibilityError) -> Self {
+		MinerError::Feasibility(e)
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Min a new npos solution, check it and submit it back to the chain as an unsigned
+	/// transaction.
+	pub fn mine_check_and_submit() -> Result<(), MinerError> {
+		let iters = Self::get_balancing_iters();
+		// get the solution, with a load of checks to ensure if submitted, IT IS ABSOLUTELY VALID.
+		let (raw_solution, witness) = Self::mine_and_check(iters)?;
+
+		let call = Call::submit_unsigned(raw_solution, witness).into();
+		SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call)
+			.map_err(|_| MinerError::PoolSubmissionFailed)
+	}
+
+	/// Mine a new npos solution, with all the relevant checks to make sure that it will be accepted
+	/// to the chain.
+	///
+	/// If you want an unchecked solution, use [`Module::mine_solution`].
+	/// If you want a checked solution and submit it at the same time, use
+	/// [`Module::mine_check_and_submit`].
+	pub fn mine_and_check(
+		iters: usize,
+	) -> Result<(RawSolution, SolutionOrSnapshotSize), MinerError> {
+		let (raw_solution, witness) = Self::mine_solution(iters)?;
+
+		// ensure that this will pass the pre-dispatch checks
+		Self::unsigned_pre_dispatch_checks(&raw_solution, &witness).map_err(|e| {
+			log!(warn, "pre-dispatch-checks failed for mined solution: {:?}", e);
+			MinerError::PreDispatchChecksFailed
+		})?;
+
+		// ensure that this is a feasible solution
+		let _ = Self::feasibility_check(raw_solution.clone(), ElectionCompute::Unsigned).map_err(
+			|e| {
+				log!(warn, "feasibility-check failed for mined solution: {:?}", e);
+				MinerError::from(e)
+			},
+		)?;
+
+		Ok((raw_solution, witness))
+	}
+
+	/// Mine a new npos solution from the current snapshot.
+	///
+	/// The solution is computed with [`OffchainAccuracy`] and then prepared for submission via
+	/// [`Module::prepare_election_result`].
+	pub fn mine_solution(
+		iters: usize,
+	) -> Result<(RawSolution, SolutionOrSnapshotSize), MinerError> {
+		let RoundSnapshot { voters, targets } =
+			Self::snapshot().ok_or(MinerError::SnapshotUnAvailable)?;
+		let desired_targets = Self::desired_targets().ok_or(MinerError::SnapshotUnAvailable)?;
+
+		seq_phragmen::<_, OffchainAccuracy>(desired_targets as usize, 0, targets, voters)
+			.ok_or(MinerError::ElectionFailed)
+			.and_then(|election_result| Self::prepare_election_result(election_result, iters))
+	}
+
+	/// Convert a raw solution from [`sp_npos_elections::ElectionResult`] to [`RawSolution`], which
+	/// is ready to be submitted to the chain.
+	///
+	/// The assignments are balanced `iters` times, reduced and then compacted. The returned score
+	/// is computed exactly as the feasibility check would.
+	pub fn prepare_election_result(
+		election_result: ElectionResult<T::AccountId, OffchainAccuracy>,
+		iters: usize,
+	) -> Result<(RawSolution, SolutionOrSnapshotSize), MinerError> {
+		let RoundSnapshot { voters, targets } =
+			Self::snapshot().ok_or(MinerError::SnapshotUnAvailable)?;
+		let witness = Self::snapshot_metadata().ok_or(MinerError::SnapshotUnAvailable)?;
+
+		// all helper closures.
+		let stake_of = |who: &T::AccountId| -> VoteWeight {
+			voters.iter().find(|(x, _, _)| x == who).map(|(_, s, _)| *s).unwrap_or_default()
+		};
+		let voter_index = |who: &T::AccountId| -> Option<VoterIndex> {
+			voters.iter().position(|(x, _, _)| x == who).and_then(|i| i.try_into().ok())
+		};
+		let target_index = |who: &T::AccountId| -> Option<TargetIndex> {
+			targets.iter().position(|x| x == who).and_then(|i| i.try_into().ok())
+		};
+
+		let ElectionResult { winners, assignments } = election_result;
+		let winners = to_without_backing(winners);
+
+		// convert to staked and balance a number of times.
+		let mut staked = assignment_ratio_to_staked_normalized(assignments, &stake_of)?;
+		if iters > 0 {
+			let (mut support_map, _) = build_support_map::<T::AccountId>(&winners, &staked);
+			balance_solution(&mut staked, &mut support_map, Zero::zero(), iters);
+		}
+
+		// reduce and convert back to ratio, which takes less space.
+		reduce(&mut staked);
+		let ratio = assignment_staked_to_ratio_normalized::<_, OffchainAccuracy>(staked)?;
+
+		// compact encode the assignment.
+		let compact = CompactAssignments::from_assignment(ratio, voter_index, target_index)?;
+
+		// The score must be computed the same way as in the feasibility check, since reduce and
+		// the conversion to ratio are not trivially reversible.
+		let supports = Self::supports_of_compact(compact.clone())?;
+		let score = evaluate_supports(&supports);
+
+		log!(info, "prepared solution after {} balancing iterations with score {:?}", iters, score);
+		Ok((RawSolution { compact, score, round: Self::round() }, witness))
+	}
+
+	/// Get a random number of iterations to run the balancing in the OCW.
+	///
+	/// Uses the offchain seed to generate a random number, maxed with
+	/// [`Trait::MinerMaxIterations`].
+	pub fn get_balancing_iters() -> usize {
+		match T::MinerMaxIterations::get() {
+			0 => 0,
+			max @ _ => {
+				let seed = sp_io::offchain::random_seed();
+				let random = <u32>::decode(&mut TrailingZeroInput::new(seed.as_ref()))
+					.expect("input is padded with zeroes; qed") % max.saturating_add(1);
+				random as usize
+			}
+		}
+	}
+
+	/// Checks that must be performed for an unsigned solution, both in the transaction pool and
+	/// upon dispatch.
+	pub fn unsigned_pre_dispatch_checks(
+		solution: &RawSolution,
+		witness: &SolutionOrSnapshotSize,
+	) -> DispatchResult {
+		// ensure solution is timely. Don't panic yet. This is a cheap check.
+		ensure!(Self::current_phase().is_unsigned_open(), Error::<T>::PreDispatchEarlySubmission);
+
+		// ensure correct number of winners.
+		ensure!(Self::round() == solution.round, Error::<T>::PreDispatchWrongRound);
+
+		// ensure the witness data matches the snapshot.
+		ensure!(
+			Self::snapshot_metadata().map_or(false, |metadata| metadata == *witness),
+			Error::<T>::PreDispatchWrongWitness,
+		);
+
+		// ensure score is being improved. Panic henceforth.
+		ensure!(
+			Self::queued_solution().map_or(true, |q: ReadySolution<_>| is_score_better::<Perbill>(
+				solution.score,
+				q.score,
+				T::SolutionImprovementThreshold::get()
+			)),
+			Error::<T>::PreDispatchWeakSubmission,
+		);
+
+		Ok(())
+	}
+}
+
+/// Checks if an execution of the offchain worker is permitted at the given block number, or not.
+///
+/// This essentially makes sure that we don't run on previous blocks in case of a re-org, and we
+/// don't run twice within a window of length [`OFFCHAIN_REPEAT`].
+///
+/// Returns `Ok(())` if offchain worker should happen, `Err(reason)` otherwise.
+pub(crate) fn set_check_offchain_execution_status<T: Trait>(
+	now: T::BlockNumber,
+) -> Result<(), &'static str> {
+	let storage = StorageValueRef::persistent(&OFFCHAIN_HEAD_DB);
+	let threshold = T::BlockNumber::from(OFFCHAIN_REPEAT);
+
+	let mutate_stat =
+		storage.mutate::<_, &'static str, _>(|maybe_head: Option<Option<T::BlockNumber>>| {
+			match maybe_head {
+				Some(Some(head)) if now < head => Err("fork."),
+				Some(Some(head)) if now >= head && now <= head + threshold => {
+					Err("recently executed.")
+				}
+				Some(Some(head)) if now > head + threshold => {
+					// we can run again now. Write the new head.
+					Ok(now)
+				}
+				_ => {
+					// value doesn't exists. Probably this node just booted up. Write, and run
+					Ok(now)
+				}
+			}
+		});
+
+	match mutate_stat {
+		// all good
+		Ok(Ok(_)) => Ok(()),
+		// failed to write.
+		Ok(Err(_)) => Err("failed to write to offchain db."),
+		// fork etc.
+		Err(why) => Err(why),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{mock::*, *};
+	use frame_support::{
+		assert_noop, assert_ok, traits::OffchainWorker, unsigned::ValidateUnsigned,
+	};
+	use sp_runtime::traits::Dispatchable;
+
+	#[test]
+	fn validate_unsigned_retracts_wrong_phase() {
+		ExtBuilder::default().build_and_execute(|| {
+			let solution = RawSolution { score: [5, 0, 0], ..Default::default() };
+			let call = Call::submit_unsigned(solution.clone(), witness());
+
+			// initial
+			assert_eq!(MultiPhase::current_phase(), Phase::Off);
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::validate_unsigned(TransactionSource::Local, &call)
+					.unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Custom(0))
+			));
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::pre_dispatch(&call).unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Custom(0))
+			));
+
+			// signed
+			roll_to(15);
+			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::validate_unsigned(TransactionSource::Local, &call)
+					.unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Custom(0))
+			));
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::pre_dispatch(&call).unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Custom(0))
+			));
+
+			// unsigned
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			let solution = RawSolution { score: [5, 0, 0], round: 1, ..Default::default() };
+			let call = Call::submit_unsigned(solution.clone(), witness());
+			assert!(<MultiPhase as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::Local,
+				&call
+			)
+			.is_ok());
+			assert!(<MultiPhase as ValidateUnsigned>::pre_dispatch(&call).is_ok());
+
+			// unsigned -- but not enabled.
+			<CurrentPhase<Runtime>>::put(Phase::Unsigned((false, 25)));
+			assert!(MultiPhase::current_phase().is_unsigned());
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::validate_unsigned(TransactionSource::Local, &call)
+					.unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Custom(0))
+			));
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::pre_dispatch(&call).unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Custom(0))
+			));
+		})
+	}
+
+	#[test]
+	fn validate_unsigned_retracts_low_score() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			let solution = RawSolution { score: [5, 0, 0], round: 1, ..Default::default() };
+			let call = Call::submit_unsigned(solution.clone(), witness());
+
+			// initial
+			assert!(<MultiPhase as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::Local,
+				&call
+			)
+			.is_ok());
+			assert!(<MultiPhase as ValidateUnsigned>::pre_dispatch(&call).is_ok());
+
+			// set a better score
+			let ready = ReadySolution { score: [10, 0, 0], ..Default::default() };
+			<QueuedSolution<Runtime>>::put(ready);
+
+			// won't work anymore.
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::validate_unsigned(TransactionSource::Local, &call)
+					.unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Custom(2))
+			));
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::pre_dispatch(&call).unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Custom(2))
+			));
+		})
+	}
+
+	#[test]
+	fn validate_unsigned_retracts_wrong_witness() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			let solution = RawSolution { score: [5, 0, 0], round: 1, ..Default::default() };
+			let mut wrong_witness = witness();
+			wrong_witness.voters += 1;
+			let call = Call::submit_unsigned(solution.clone(), wrong_witness);
+
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::validate_unsigned(TransactionSource::Local, &call)
+					.unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Custom(3))
+			));
+		})
+	}
+
+	#[test]
+	fn validate_unsigned_rejects_external_source() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			let solution = RawSolution { score: [5, 0, 0], round: 1, ..Default::default() };
+			let call = Call::submit_unsigned(solution.clone(), witness());
+
+			assert!(matches!(
+				<MultiPhase as ValidateUnsigned>::validate_unsigned(
+					TransactionSource::External,
+					&call
+				)
+				.unwrap_err(),
+				TransactionValidityError::Invalid(InvalidTransaction::Call)
+			));
+		})
+	}
+
+	#[test]
+	fn priority_is_set() {
+		ExtBuilder::default().unsigned_priority(20).build_and_execute(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			let solution = RawSolution { score: [5, 0, 0], round: 1, ..Default::default() };
+			let call = Call::submit_unsigned(solution.clone(), witness());
+
+			assert_eq!(
+				<MultiPhase as ValidateUnsigned>::validate_unsigned(
+					TransactionSource::Local,
+					&call
+				)
+				.unwrap()
+				.priority,
+				25
+			);
+		})
+	}
+
+	#[test]
+	#[should_panic(expected = "Invalid unsigned submission must produce invalid block and \
+								deprive validator from their authoring reward.: \
+								WrongWinnerCount")]
+	fn unfeasible_solution_panics() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			// This is in itself an invalid BS solution.
+			let solution = RawSolution { score: [5, 0, 0], round: 1, ..Default::default() };
+			let call = Call::submit_unsigned(solution.clone(), witness());
+			let outer_call: OuterCall = call.into();
+			let _ = outer_call.dispatch(Origin::none());
+		})
+	}
+
+	#[test]
+	fn miner_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(15);
+			assert!(MultiPhase::current_phase().is_signed());
+
+			// ensure we have snapshots in place.
+			assert!(MultiPhase::snapshot().is_some());
+			assert_eq!(MultiPhase::desired_targets().unwrap(), 2);
+
+			// mine seq_phragmen solution with 2 iters.
+			let (solution, witness) = MultiPhase::mine_solution(2).unwrap();
+
+			// ensure this solution is valid.
+			assert!(MultiPhase::queued_solution().is_none());
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+			assert_ok!(MultiPhase::submit_unsigned(Origin::none(), solution, witness));
+			assert!(MultiPhase::queued_solution().is_some());
+		})
+	}
+
+	#[test]
+	fn miner_will_not_submit_if_not_enough_winners() {
+		ExtBuilder::default().desired_targets(8).build_and_execute(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			// only 4 targets exist, thus the election can never return 8 winners.
+			assert_eq!(
+				MultiPhase::mine_check_and_submit().unwrap_err(),
+				MinerError::Feasibility(FeasibilityError::WrongWinnerCount),
+			);
+		})
+	}
+
+	#[test]
+	fn unsigned_per_dispatch_checks_can_only_submit_threshold_better() {
+		ExtBuilder::default()
+			.desired_targets(1)
+			.add_voter(7, 2, vec![10])
+			.add_voter(8, 5, vec![10])
+			.solution_improvement_threshold(Perbill::from_percent(50))
+			.build_and_execute(|| {
+				roll_to(25);
+				assert!(MultiPhase::current_phase().is_unsigned());
+				assert_eq!(MultiPhase::desired_targets().unwrap(), 1);
+
+				// an initial solution
+				let result = ElectionResult {
+					// note: This second element of backing stake is not important here.
+					winners: vec![(10, 10)],
+					assignments: vec![Assignment {
+						who: 10,
+						distribution: vec![(10, PerU16::one())],
+					}],
+				};
+				let (solution, witness) = MultiPhase::prepare_election_result(result, 0).unwrap();
+				assert_ok!(MultiPhase::unsigned_pre_dispatch_checks(&solution, &witness));
+				assert_ok!(MultiPhase::submit_unsigned(Origin::none(), solution, witness));
+				assert_eq!(MultiPhase::queued_solution().unwrap().score[0], 10);
+
+				// trial 1: a solution who's score is 12, i.e. only 20% better in the first element.
+				let result = ElectionResult {
+					winners: vec![(10, 12)],
+					assignments: vec![
+						Assignment { who: 10, distribution: vec![(10, PerU16::one())] },
+						Assignment {
+							who: 7,
+							// note: this percent doesn't even matter, in compact it is 100%.
+							distribution: vec![(10, PerU16::one())],
+						},
+					],
+				};
+				let (solution, witness) = MultiPhase::prepare_election_result(result, 0).unwrap();
+				// 12 is not 50% more than 10
+				assert_eq!(solution.score[0], 12);
+				assert_noop!(
+					MultiPhase::unsigned_pre_dispatch_checks(&solution, &witness),
+					Error::<Runtime>::PreDispatchWeakSubmission,
+				);
+				// submitting this will actually panic.
+
+				// trial 2: a solution who's score is 17, i.e. 70% better in the first element.
+				let result = ElectionResult {
+					winners: vec![(10, 12)],
+					assignments: vec![
+						Assignment { who: 10, distribution: vec![(10, PerU16::one())] },
+						Assignment { who: 7, distribution: vec![(10, PerU16::one())] },
+						Assignment { who: 8, distribution: vec![(10, PerU16::one())] },
+					],
+				};
+				let (solution, witness) = MultiPhase::prepare_election_result(result, 0).unwrap();
+				assert_eq!(solution.score[0], 17);
+
+				// and it is fine
+				assert_ok!(MultiPhase::unsigned_pre_dispatch_checks(&solution, &witness));
+				assert_ok!(MultiPhase::submit_unsigned(Origin::none(), solution, witness));
+			})
+	}
+
+	#[test]
+	fn ocw_check_prevent_duplicate() {
+		let (mut ext, _) = ExtBuilder::default().build_offchainify(0);
+		ext.execute_with(|| {
+			roll_to(25);
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			// first execution -- okay.
+			assert!(set_check_offchain_execution_status::<Runtime>(25).is_ok());
+
+			// next block: rejected.
+			assert!(set_check_offchain_execution_status::<Runtime>(26).is_err());
+
+			// allowed after `OFFCHAIN_REPEAT`
+			assert!(set_check_offchain_execution_status::<Runtime>((26 + OFFCHAIN_REPEAT).into())
+				.is_ok());
+
+			// a fork like situation: re-execute last 3.
+			assert!(set_check_offchain_execution_status::<Runtime>(
+				(26 + OFFCHAIN_REPEAT - 3).into()
+			)
+			.is_err());
+			assert!(set_check_offchain_execution_status::<Runtime>(
+				(26 + OFFCHAIN_REPEAT - 2).into()
+			)
+			.is_err());
+			assert!(set_check_offchain_execution_status::<Runtime>(
+				(26 + OFFCHAIN_REPEAT - 1).into()
+			)
+			.is_err());
+		})
+	}
+
+	#[test]
+	fn ocw_only_runs_when_signed_open_now() {
+		let (mut ext, pool) = ExtBuilder::default().build_offchainify(0);
+		ext.execute_with(|| {
+			roll_to(25);
+			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
+
+			// we must clear the offchain storage to ensure the offchain execution check doesn't get
+			// in the way.
+			let mut storage = StorageValueRef::persistent(&OFFCHAIN_HEAD_DB);
+
+			MultiPhase::offchain_worker(24);
+			assert!(pool.read().transactions.len().is_zero());
+			storage.clear();
+
+			MultiPhase::offchain_worker(26);
+			assert!(pool.read().transactions.len().is_zero());
+			storage.clear();
+
+			// submits!
+			MultiPhase::offchain_worker(25);
+			assert!(!pool.read().transactions.len().is_zero());
+		})
+	}
+
+	#[test]
+	fn ocw_can_submit_to_pool() {
+		let (mut ext, pool) = ExtBuilder::default().build_offchainify(0);
+		ext.execute_with(|| {
+			roll_to_with_ocw(25);
+			assert_eq!(MultiPhase::current_phase(), Phase::Unsigned((true, 25)));
+			// OCW must have submitted now
+
+			let encoded = pool.read().transactions[0].clone();
+			let extrinsic: Extrinsic = Decode::decode(&mut &*encoded).unwrap();
+			let call = extrinsic.call;
+			assert!(matches!(call, OuterCall::MultiPhase(Call::submit_unsigned(_, _))));
+		})
+	}
+}
@@ -0,0 +1,79 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition required by Ethereum RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to use the custom RPC extension
+//! adding `eth_*` access methods.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub use pallet_evm::Account;
+pub use pallet_ethereum::{Block, Receipt, Transaction, TransactionStatus};
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the EVM state and the Ethereum blocks built by the Ethereum module.
+	pub trait EthereumApi {
+		/// The chain id Ethereum transactions have to be signed for.
+		fn chain_id() -> u64;
+
+		/// Nonce and balance of the given address.
+		fn account_basic(address: H160) -> Account;
+
+		/// The minimal gas price accepted by the chain.
+		fn gas_price() -> U256;
+
+		/// Code of the given address.
+		fn account_code_at(address: H160) -> Vec<u8>;
+
+		/// Value of the storage slot `index` of the given address.
+		fn storage_at(address: H160, index: U256) -> H256;
+
+		/// Execute a call, or a create if `to` is `None`, without applying its state changes.
+		///
+		/// Returns the output data and the gas used. See the Ethereum module's `call` function for
+		/// more details.
+		fn call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			gas_price: Option<U256>,
+		) -> Result<(Vec<u8>, U256), DispatchError>;
+
+		/// The Ethereum block built at the end of this block.
+		fn current_block() -> Option<Block>;
+
+		/// Receipts of the transactions in the current Ethereum block.
+		fn current_receipts() -> Option<Vec<Receipt>>;
+
+		/// Statuses of the transactions in the current Ethereum block.
+		fn current_transaction_statuses() -> Option<Vec<TransactionStatus>>;
+
+		/// Number of the block an Ethereum block hash belongs to.
+		fn block_number_by_hash(hash: H256) -> Option<U256>;
+
+		/// Block number and index of the transaction with the given Ethereum hash.
+		fn transaction_index(hash: H256) -> Option<(U256, u32)>;
+	}
+}
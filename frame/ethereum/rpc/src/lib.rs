@@ -0,0 +1,519 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ethereum compatible `eth_*` RPC methods on top of the Ethereum module.
+//!
+//! Blocks, receipts and transactions are read from the Ethereum blocks the runtime builds at the
+//! end of every block, see `pallet-ethereum`. Ethereum block numbers are the numbers of the
+//! Substrate blocks they were built in.
+
+use std::sync::Arc;
+
+use futures::future::TryFutureExt;
+use jsonrpc_core::{
+	Error, ErrorCode, Result,
+	futures::future::{self as rpc_future, Future},
+};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H160, H256, U256};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor, UniqueSaturatedInto, Zero},
+	transaction_validity::TransactionSource,
+};
+use sp_transaction_pool::TransactionPool;
+
+pub mod types;
+
+pub use self::gen_client::Client as EthClient;
+pub use pallet_ethereum_rpc_runtime_api::{
+	self as runtime_api, EthereumApi as EthereumRuntimeApi,
+};
+
+use pallet_ethereum::{Receipt, Transaction, TransactionAction, TransactionStatus};
+use self::types::{BlockNumber, BlockTransactions, CallRequest};
+
+const RUNTIME_ERROR: i64 = 1;
+const EXECUTION_ERROR: i64 = 2;
+const POOL_ERROR: i64 = 3;
+
+/// Future that resolves to the hash of a submitted transaction.
+pub type FutureResult<T> = Box<dyn Future<Item = T, Error = Error> + Send>;
+
+/// Converts a signed Ethereum transaction into the extrinsic submitting it.
+///
+/// This is usually an unsigned extrinsic of the Ethereum module's `transact` call.
+pub trait ConvertTransaction<Extrinsic> {
+	/// Wrap `transaction` into an extrinsic.
+	fn convert_transaction(&self, transaction: Transaction) -> Extrinsic;
+}
+
+/// Ethereum RPC methods.
+#[rpc]
+pub trait EthApi {
+	/// Returns the chain id transactions have to be signed for.
+	#[rpc(name = "eth_chainId")]
+	fn chain_id(&self) -> Result<U256>;
+
+	/// Returns the number of the best block.
+	#[rpc(name = "eth_blockNumber")]
+	fn block_number(&self) -> Result<U256>;
+
+	/// Returns the minimal gas price.
+	#[rpc(name = "eth_gasPrice")]
+	fn gas_price(&self) -> Result<U256>;
+
+	/// Returns the balance of the given address.
+	#[rpc(name = "eth_getBalance")]
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Returns the nonce of the given address.
+	#[rpc(name = "eth_getTransactionCount")]
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Returns the code of the given address.
+	#[rpc(name = "eth_getCode")]
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Returns the value of a storage slot of the given address.
+	#[rpc(name = "eth_getStorageAt")]
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> Result<H256>;
+
+	/// Executes a call without submitting a transaction and returns its output.
+	#[rpc(name = "eth_call")]
+	fn call(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Returns the gas used by executing the call with the requested (or the maximal) gas limit.
+	#[rpc(name = "eth_estimateGas")]
+	fn estimate_gas(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Submits an RLP-encoded signed transaction to the transaction pool.
+	#[rpc(name = "eth_sendRawTransaction")]
+	fn send_raw_transaction(&self, bytes: Bytes) -> FutureResult<H256>;
+
+	/// Returns the block with the given number.
+	#[rpc(name = "eth_getBlockByNumber")]
+	fn block_by_number(&self, number: BlockNumber, full: bool) -> Result<Option<types::Block>>;
+
+	/// Returns the block with the given hash.
+	#[rpc(name = "eth_getBlockByHash")]
+	fn block_by_hash(&self, hash: H256, full: bool) -> Result<Option<types::Block>>;
+
+	/// Returns the transaction with the given hash.
+	#[rpc(name = "eth_getTransactionByHash")]
+	fn transaction_by_hash(&self, hash: H256) -> Result<Option<types::Transaction>>;
+
+	/// Returns the receipt of the transaction with the given hash.
+	#[rpc(name = "eth_getTransactionReceipt")]
+	fn transaction_receipt(&self, hash: H256) -> Result<Option<types::Receipt>>;
+}
+
+/// An Ethereum block together with the receipts and statuses of its transactions.
+type EthereumBlock = (pallet_ethereum::Block, Vec<Receipt>, Vec<TransactionStatus>);
+
+/// An implementation of the Ethereum RPC methods.
+pub struct Eth<B, C, P, CT> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	convert_transaction: CT,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<B, C, P, CT> Eth<B, C, P, CT> {
+	/// Create new `Eth` given the client, the transaction pool and the transaction converter.
+	pub fn new(client: Arc<C>, pool: Arc<P>, convert_transaction: CT) -> Self {
+		Eth {
+			client,
+			pool,
+			convert_transaction,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<B, C, P, CT> Eth<B, C, P, CT> where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B>,
+	C::Api: EthereumRuntimeApi<B>,
+{
+	fn best_block(&self) -> BlockId<B> {
+		BlockId::hash(self.client.info().best_hash)
+	}
+
+	fn block_id(&self, number: Option<BlockNumber>) -> BlockId<B> {
+		match number.unwrap_or_default() {
+			BlockNumber::Num(number) => BlockId::Number(number.unique_saturated_into()),
+			BlockNumber::Earliest => BlockId::Number(Zero::zero()),
+			BlockNumber::Latest | BlockNumber::Pending => self.best_block(),
+		}
+	}
+
+	/// The Ethereum block built in the Substrate block with the given number.
+	fn ethereum_block(&self, number: U256) -> Result<Option<EthereumBlock>> {
+		let number: NumberFor<B> = number.low_u64().unique_saturated_into();
+		let id = BlockId::Number(number);
+		let api = self.client.runtime_api();
+
+		let block = api.current_block(&id).map_err(runtime_error_into_rpc_err)?;
+		let receipts = api.current_receipts(&id).map_err(runtime_error_into_rpc_err)?;
+		let statuses = api.current_transaction_statuses(&id).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(match (block, receipts, statuses) {
+			(Some(block), Some(receipts), Some(statuses)) => Some((block, receipts, statuses)),
+			_ => None,
+		})
+	}
+
+	fn ethereum_block_by_hash(&self, hash: H256) -> Result<Option<EthereumBlock>> {
+		let number = self.client.runtime_api()
+			.block_number_by_hash(&self.best_block(), hash)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		match number {
+			Some(number) => self.ethereum_block(number),
+			None => Ok(None),
+		}
+	}
+
+	fn execute(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<(Vec<u8>, U256)> {
+		let CallRequest { from, to, gas_price, gas, value, data } = request;
+
+		self.client.runtime_api()
+			.call(
+				&self.block_id(number),
+				from.unwrap_or_default(),
+				to,
+				data.map(|data| data.0).unwrap_or_default(),
+				value.unwrap_or_default(),
+				gas.unwrap_or_else(|| U256::from(u32::max_value())),
+				gas_price,
+			)
+			.map_err(runtime_error_into_rpc_err)?
+			.map_err(|e| Error {
+				code: ErrorCode::ServerError(EXECUTION_ERROR),
+				message: "Execution failed".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
+
+impl<B, C, P, CT> EthApi for Eth<B, C, P, CT> where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: EthereumRuntimeApi<B>,
+	P: TransactionPool<Block = B> + 'static,
+	CT: ConvertTransaction<<B as BlockT>::Extrinsic> + Send + Sync + 'static,
+{
+	fn chain_id(&self) -> Result<U256> {
+		self.client.runtime_api()
+			.chain_id(&self.best_block())
+			.map(U256::from)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn block_number(&self) -> Result<U256> {
+		let number: u64 = self.client.info().best_number.unique_saturated_into();
+		Ok(U256::from(number))
+	}
+
+	fn gas_price(&self) -> Result<U256> {
+		self.client.runtime_api()
+			.gas_price(&self.best_block())
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256> {
+		self.client.runtime_api()
+			.account_basic(&self.block_id(number), address)
+			.map(|account| account.balance)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> Result<U256> {
+		self.client.runtime_api()
+			.account_basic(&self.block_id(number), address)
+			.map(|account| account.nonce)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes> {
+		self.client.runtime_api()
+			.account_code_at(&self.block_id(number), address)
+			.map(Bytes)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> Result<H256> {
+		self.client.runtime_api()
+			.storage_at(&self.block_id(number), address, index)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn call(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<Bytes> {
+		self.execute(request, number).map(|(data, _)| Bytes(data))
+	}
+
+	fn estimate_gas(&self, request: CallRequest, number: Option<BlockNumber>) -> Result<U256> {
+		self.execute(request, number).map(|(_, used_gas)| used_gas)
+	}
+
+	fn send_raw_transaction(&self, bytes: Bytes) -> FutureResult<H256> {
+		let transaction: Transaction = match rlp::decode(&bytes.0) {
+			Ok(transaction) => transaction,
+			Err(e) => return Box::new(rpc_future::err(Error {
+				code: ErrorCode::InvalidParams,
+				message: "Unable to decode transaction.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})),
+		};
+		let hash = transaction.hash();
+		let extrinsic = self.convert_transaction.convert_transaction(transaction);
+
+		Box::new(
+			self.pool
+				.submit_one(&self.best_block(), TransactionSource::External, extrinsic)
+				.compat()
+				.map(move |_| hash)
+				.map_err(|e| Error {
+					code: ErrorCode::ServerError(POOL_ERROR),
+					message: "Unable to submit transaction.".into(),
+					data: Some(format!("{:?}", e).into()),
+				})
+		)
+	}
+
+	fn block_by_number(&self, number: BlockNumber, full: bool) -> Result<Option<types::Block>> {
+		let number = match number {
+			BlockNumber::Num(number) => U256::from(number),
+			BlockNumber::Earliest => U256::zero(),
+			BlockNumber::Latest | BlockNumber::Pending => self.block_number()?,
+		};
+
+		Ok(self.ethereum_block(number)?.map(|(block, _, statuses)| rich_block(block, statuses, full)))
+	}
+
+	fn block_by_hash(&self, hash: H256, full: bool) -> Result<Option<types::Block>> {
+		Ok(self.ethereum_block_by_hash(hash)?.map(|(block, _, statuses)| rich_block(block, statuses, full)))
+	}
+
+	fn transaction_by_hash(&self, hash: H256) -> Result<Option<types::Transaction>> {
+		let (number, index) = match self.client.runtime_api()
+			.transaction_index(&self.best_block(), hash)
+			.map_err(runtime_error_into_rpc_err)?
+		{
+			Some(location) => location,
+			None => return Ok(None),
+		};
+
+		Ok(self.ethereum_block(number)?.and_then(|(block, _, statuses)| {
+			let index = index as usize;
+			let block_hash = block.header.hash();
+			match (block.transactions.get(index), statuses.get(index)) {
+				(Some(transaction), Some(status)) => Some(
+					rich_transaction(transaction, status, Some((block_hash, number)))
+				),
+				_ => None,
+			}
+		}))
+	}
+
+	fn transaction_receipt(&self, hash: H256) -> Result<Option<types::Receipt>> {
+		let (number, index) = match self.client.runtime_api()
+			.transaction_index(&self.best_block(), hash)
+			.map_err(runtime_error_into_rpc_err)?
+		{
+			Some(location) => location,
+			None => return Ok(None),
+		};
+
+		Ok(self.ethereum_block(number)?.and_then(|(block, receipts, statuses)| {
+			rich_receipt(&block, &receipts, &statuses, index as usize)
+		}))
+	}
+}
+
+/// Build the RPC representation of a block.
+fn rich_block(
+	block: pallet_ethereum::Block,
+	statuses: Vec<TransactionStatus>,
+	full: bool,
+) -> types::Block {
+	let hash = block.header.hash();
+	let number = block.header.number;
+	let transactions = if full {
+		BlockTransactions::Full(
+			block.transactions.iter()
+				.zip(statuses.iter())
+				.map(|(transaction, status)| rich_transaction(transaction, status, Some((hash, number))))
+				.collect()
+		)
+	} else {
+		BlockTransactions::Hashes(statuses.iter().map(|status| status.transaction_hash).collect())
+	};
+	let header = block.header;
+
+	types::Block {
+		hash,
+		parent_hash: header.parent_hash,
+		sha3_uncles: H256::from(sp_core::hashing::keccak_256(&rlp::EMPTY_LIST_RLP)),
+		miner: header.beneficiary,
+		state_root: header.state_root,
+		transactions_root: header.transactions_root,
+		receipts_root: header.receipts_root,
+		number,
+		gas_used: header.gas_used,
+		gas_limit: header.gas_limit,
+		extra_data: Bytes(Vec::new()),
+		logs_bloom: Bytes(header.logs_bloom.as_bytes().to_vec()),
+		timestamp: U256::from(header.timestamp),
+		difficulty: U256::zero(),
+		total_difficulty: U256::zero(),
+		uncles: Vec::new(),
+		transactions,
+	}
+}
+
+/// Build the RPC representation of a transaction included in the given block.
+fn rich_transaction(
+	transaction: &Transaction,
+	status: &TransactionStatus,
+	block: Option<(H256, U256)>,
+) -> types::Transaction {
+	types::Transaction {
+		hash: status.transaction_hash,
+		nonce: transaction.nonce,
+		block_hash: block.map(|(hash, _)| hash),
+		block_number: block.map(|(_, number)| number),
+		transaction_index: Some(U256::from(status.transaction_index)),
+		from: status.from,
+		to: match transaction.action {
+			TransactionAction::Call(to) => Some(to),
+			TransactionAction::Create => None,
+		},
+		value: transaction.value,
+		gas_price: transaction.gas_price,
+		gas: transaction.gas_limit,
+		input: Bytes(transaction.input.clone()),
+		creates: status.contract_address,
+		v: U256::from(transaction.signature.v),
+		r: U256::from_big_endian(transaction.signature.r.as_bytes()),
+		s: U256::from_big_endian(transaction.signature.s.as_bytes()),
+	}
+}
+
+/// Build the RPC representation of the receipt of the transaction at `index`.
+fn rich_receipt(
+	block: &pallet_ethereum::Block,
+	receipts: &[Receipt],
+	statuses: &[TransactionStatus],
+	index: usize,
+) -> Option<types::Receipt> {
+	let receipt = receipts.get(index)?;
+	let status = statuses.get(index)?;
+	let block_hash = block.header.hash();
+	let block_number = block.header.number;
+	let previous_gas = index.checked_sub(1)
+		.and_then(|previous| receipts.get(previous))
+		.map(|previous| previous.used_gas)
+		.unwrap_or_default();
+	let first_log_index: usize = receipts[..index].iter().map(|receipt| receipt.logs.len()).sum();
+
+	Some(types::Receipt {
+		transaction_hash: status.transaction_hash,
+		transaction_index: U256::from(status.transaction_index),
+		block_hash,
+		block_number,
+		from: status.from,
+		to: status.to,
+		cumulative_gas_used: receipt.used_gas,
+		gas_used: receipt.used_gas.saturating_sub(previous_gas),
+		contract_address: status.contract_address,
+		logs: receipt.logs.iter().enumerate().map(|(log_index, log)| types::Log {
+			address: log.address,
+			topics: log.topics.clone(),
+			data: Bytes(log.data.clone()),
+			block_hash,
+			block_number,
+			transaction_hash: status.transaction_hash,
+			transaction_index: U256::from(status.transaction_index),
+			log_index: U256::from(first_log_index + log_index),
+			transaction_log_index: U256::from(log_index),
+			removed: false,
+		}).collect(),
+		logs_bloom: Bytes(receipt.logs_bloom.as_bytes().to_vec()),
+		status: U256::from(receipt.status),
+	})
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime trapped".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn block_number_should_serialize_deserialize_properly() {
+		fn test(json: &str, expected: BlockNumber) {
+			let number: BlockNumber = serde_json::from_str(json).unwrap();
+			assert_eq!(number, expected);
+			assert_eq!(serde_json::to_string(&number).unwrap(), json);
+		}
+		test(r#""latest""#, BlockNumber::Latest);
+		test(r#""earliest""#, BlockNumber::Earliest);
+		test(r#""pending""#, BlockNumber::Pending);
+		test(r#""0x1b4""#, BlockNumber::Num(436));
+
+		assert!(serde_json::from_str::<BlockNumber>(r#""1b4""#).is_err());
+		assert!(serde_json::from_str::<BlockNumber>(r#""0xzz""#).is_err());
+	}
+
+	#[test]
+	fn call_request_should_serialize_deserialize_properly() {
+		let req: CallRequest = serde_json::from_str(r#"
+		{
+			"from": "0x407d73d8a49eeb85d32cf465507dd71d507100c1",
+			"to": "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
+			"gas": "0x76c0",
+			"gasPrice": "0x9184e72a000",
+			"data": "0x8c97db39"
+		}
+		"#).unwrap();
+		assert_eq!(req.gas, Some(U256::from(0x76c0)));
+		assert_eq!(req.gas_price, Some(U256::from(0x9184e72a000u64)));
+		assert_eq!(req.value, None);
+		assert_eq!(req.data, Some(Bytes(vec![0x8c, 0x97, 0xdb, 0x39])));
+	}
+
+	#[test]
+	fn receipt_should_serialize_deserialize_properly() {
+		fn test(expected: &str) {
+			let res: types::Receipt = serde_json::from_str(expected).unwrap();
+			let actual = serde_json::to_string(&res).unwrap();
+			assert_eq!(actual, expected);
+		}
+		test(r#"{"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000001","transactionIndex":"0x0","blockHash":"0x0000000000000000000000000000000000000000000000000000000000000002","blockNumber":"0x1","from":"0x0000000000000000000000000000000000000003","to":null,"cumulativeGasUsed":"0x5208","gasUsed":"0x5208","contractAddress":"0x0000000000000000000000000000000000000004","logs":[],"logsBloom":"0x00","status":"0x1"}"#);
+	}
+}
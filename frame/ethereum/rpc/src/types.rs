@@ -0,0 +1,242 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC types of the `eth_*` methods.
+
+use std::fmt;
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de::{self, Visitor}};
+use sp_core::{Bytes, H160, H256, U256};
+
+/// A block reference, as accepted by the `eth_*` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockNumber {
+	/// The block with the given number.
+	Num(u64),
+	/// The best block.
+	Latest,
+	/// The genesis block.
+	Earliest,
+	/// The block being built, treated as the best block.
+	Pending,
+}
+
+impl Default for BlockNumber {
+	fn default() -> Self {
+		BlockNumber::Latest
+	}
+}
+
+impl Serialize for BlockNumber {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self {
+			BlockNumber::Num(number) => serializer.serialize_str(&format!("0x{:x}", number)),
+			BlockNumber::Latest => serializer.serialize_str("latest"),
+			BlockNumber::Earliest => serializer.serialize_str("earliest"),
+			BlockNumber::Pending => serializer.serialize_str("pending"),
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for BlockNumber {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_str(BlockNumberVisitor)
+	}
+}
+
+struct BlockNumberVisitor;
+
+impl<'de> Visitor<'de> for BlockNumberVisitor {
+	type Value = BlockNumber;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "a hex block number or 'latest', 'earliest' or 'pending'")
+	}
+
+	fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+		match value {
+			"latest" => Ok(BlockNumber::Latest),
+			"earliest" => Ok(BlockNumber::Earliest),
+			"pending" => Ok(BlockNumber::Pending),
+			_ if value.starts_with("0x") => u64::from_str_radix(&value[2..], 16)
+				.map(BlockNumber::Num)
+				.map_err(|e| E::custom(format!("Invalid block number: {}", e))),
+			_ => Err(E::custom("Invalid block number: missing 0x prefix")),
+		}
+	}
+}
+
+/// Parameters of `eth_call` and `eth_estimateGas`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct CallRequest {
+	/// Sender, zero address if omitted.
+	pub from: Option<H160>,
+	/// Recipient, a create if omitted.
+	pub to: Option<H160>,
+	/// Gas price, no fee is charged if omitted.
+	pub gas_price: Option<U256>,
+	/// Gas limit, the maximal limit if omitted.
+	pub gas: Option<U256>,
+	/// Transferred value.
+	pub value: Option<U256>,
+	/// Call data or init code.
+	pub data: Option<Bytes>,
+}
+
+/// Transactions of a block, either hashes or full transactions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockTransactions {
+	/// Only the hashes of the transactions.
+	Hashes(Vec<H256>),
+	/// Full transactions.
+	Full(Vec<Transaction>),
+}
+
+/// An Ethereum block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Block {
+	/// Hash of the block.
+	pub hash: H256,
+	/// Hash of the parent block.
+	pub parent_hash: H256,
+	/// Hash of the (always empty) uncles.
+	pub sha3_uncles: H256,
+	/// Address receiving the block reward.
+	pub miner: H160,
+	/// State root.
+	pub state_root: H256,
+	/// Transactions root.
+	pub transactions_root: H256,
+	/// Receipts root.
+	pub receipts_root: H256,
+	/// Block number.
+	pub number: U256,
+	/// Gas used by all transactions.
+	pub gas_used: U256,
+	/// Gas limit.
+	pub gas_limit: U256,
+	/// Extra data.
+	pub extra_data: Bytes,
+	/// Logs bloom.
+	pub logs_bloom: Bytes,
+	/// Timestamp.
+	pub timestamp: U256,
+	/// Difficulty, always zero.
+	pub difficulty: U256,
+	/// Total difficulty, always zero.
+	pub total_difficulty: U256,
+	/// Uncles, always empty.
+	pub uncles: Vec<H256>,
+	/// Transactions.
+	pub transactions: BlockTransactions,
+}
+
+/// An Ethereum transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+	/// Hash of the transaction.
+	pub hash: H256,
+	/// Nonce of the sender.
+	pub nonce: U256,
+	/// Hash of the block the transaction is included in.
+	pub block_hash: Option<H256>,
+	/// Number of the block the transaction is included in.
+	pub block_number: Option<U256>,
+	/// Index of the transaction in its block.
+	pub transaction_index: Option<U256>,
+	/// Sender.
+	pub from: H160,
+	/// Recipient, `None` for a create.
+	pub to: Option<H160>,
+	/// Transferred value.
+	pub value: U256,
+	/// Gas price.
+	pub gas_price: U256,
+	/// Gas limit.
+	pub gas: U256,
+	/// Call data or init code.
+	pub input: Bytes,
+	/// Address of the created contract, if any.
+	pub creates: Option<H160>,
+	/// The `v` component of the signature.
+	pub v: U256,
+	/// The `r` component of the signature.
+	pub r: U256,
+	/// The `s` component of the signature.
+	pub s: U256,
+}
+
+/// A log emitted by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+	/// Emitting address.
+	pub address: H160,
+	/// Topics.
+	pub topics: Vec<H256>,
+	/// Data.
+	pub data: Bytes,
+	/// Hash of the block the log was emitted in.
+	pub block_hash: H256,
+	/// Number of the block the log was emitted in.
+	pub block_number: U256,
+	/// Hash of the emitting transaction.
+	pub transaction_hash: H256,
+	/// Index of the emitting transaction in its block.
+	pub transaction_index: U256,
+	/// Index of the log in its block.
+	pub log_index: U256,
+	/// Index of the log in its transaction.
+	pub transaction_log_index: U256,
+	/// Whether the log was removed by a reorganisation, always `false`.
+	pub removed: bool,
+}
+
+/// Receipt of an executed transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+	/// Hash of the transaction.
+	pub transaction_hash: H256,
+	/// Index of the transaction in its block.
+	pub transaction_index: U256,
+	/// Hash of the block the transaction is included in.
+	pub block_hash: H256,
+	/// Number of the block the transaction is included in.
+	pub block_number: U256,
+	/// Sender.
+	pub from: H160,
+	/// Recipient, `None` for a create.
+	pub to: Option<H160>,
+	/// Gas used in the block up to and including this transaction.
+	pub cumulative_gas_used: U256,
+	/// Gas used by this transaction.
+	pub gas_used: U256,
+	/// Address of the created contract, if any.
+	pub contract_address: Option<H160>,
+	/// Logs emitted by the transaction.
+	pub logs: Vec<Log>,
+	/// Bloom of the logs.
+	pub logs_bloom: Bytes,
+	/// `1` on success, `0` on failure.
+	pub status: U256,
+}
@@ -0,0 +1,191 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ethereum-style blocks and receipts built from the transactions executed in a block.
+
+use codec::{Encode, Decode};
+use rlp::RlpStream;
+use sp_core::{H160, H256, U256};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+use pallet_evm::Log;
+
+use crate::transaction::Transaction;
+
+/// Size of a logs bloom in bytes.
+pub const BLOOM_SIZE: usize = 256;
+
+/// 2048-bit logs bloom filter.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Bloom(Vec<u8>);
+
+impl Default for Bloom {
+	fn default() -> Self {
+		Bloom(vec![0u8; BLOOM_SIZE])
+	}
+}
+
+impl Bloom {
+	/// Add the keccak-256 hash of `input` to the filter.
+	pub fn accrue(&mut self, input: &[u8]) {
+		let hash = sp_io::hashing::keccak_256(input);
+		for i in 0..3 {
+			let bit = ((hash[2 * i] as usize) << 8 | hash[2 * i + 1] as usize) & (BLOOM_SIZE * 8 - 1);
+			self.0[BLOOM_SIZE - 1 - bit / 8] |= 1 << (bit % 8);
+		}
+	}
+
+	/// Add the address and the topics of `log` to the filter.
+	pub fn accrue_log(&mut self, log: &Log) {
+		self.accrue(log.address.as_bytes());
+		for topic in &log.topics {
+			self.accrue(topic.as_bytes());
+		}
+	}
+
+	/// Merge `other` into the filter.
+	pub fn accrue_bloom(&mut self, other: &Bloom) {
+		for (byte, other) in self.0.iter_mut().zip(other.0.iter()) {
+			*byte |= *other;
+		}
+	}
+
+	/// Whether the filter may contain the keccak-256 hash of `input`.
+	pub fn contains_input(&self, input: &[u8]) -> bool {
+		let mut single = Bloom::default();
+		single.accrue(input);
+		single.0.iter().zip(self.0.iter()).all(|(s, b)| s & b == *s)
+	}
+
+	/// Raw bytes of the filter.
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+/// Receipt of an executed Ethereum transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Receipt {
+	/// `1` if the execution succeeded, `0` otherwise.
+	pub status: u8,
+	/// Gas used in the block up to and including this transaction.
+	pub used_gas: U256,
+	/// Bloom of the logs emitted by this transaction.
+	pub logs_bloom: Bloom,
+	/// Logs emitted by this transaction.
+	pub logs: Vec<Log>,
+}
+
+impl rlp::Encodable for Receipt {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(4);
+		s.append(&self.status);
+		s.append(&self.used_gas);
+		s.append(&self.logs_bloom.0);
+		s.begin_list(self.logs.len());
+		for log in &self.logs {
+			s.begin_list(3);
+			s.append(&log.address);
+			s.append_list::<H256, _>(&log.topics);
+			s.append(&log.data);
+		}
+	}
+}
+
+/// Execution details of a transaction that are not part of the receipt.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct TransactionStatus {
+	/// Hash of the transaction.
+	pub transaction_hash: H256,
+	/// Index of the transaction in its block.
+	pub transaction_index: u32,
+	/// Sender of the transaction.
+	pub from: H160,
+	/// Destination of the transaction, `None` for contract creation.
+	pub to: Option<H160>,
+	/// Address of the created contract, if any.
+	pub contract_address: Option<H160>,
+	/// Logs emitted by the transaction.
+	pub logs: Vec<Log>,
+	/// Bloom of the logs emitted by the transaction.
+	pub logs_bloom: Bloom,
+}
+
+/// Header of an Ethereum block.
+///
+/// Fields without a meaning on a Substrate chain (difficulty, ommers, seal) are fixed to empty
+/// values. The `state_root` is zero since the EVM state is part of the Substrate state trie.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Header {
+	/// Hash of the parent Ethereum block.
+	pub parent_hash: H256,
+	/// Address receiving the block reward.
+	pub beneficiary: H160,
+	/// State root, always zero.
+	pub state_root: H256,
+	/// Keccak trie root of the transactions.
+	pub transactions_root: H256,
+	/// Keccak trie root of the receipts.
+	pub receipts_root: H256,
+	/// Bloom of all logs in the block.
+	pub logs_bloom: Bloom,
+	/// Block number.
+	pub number: U256,
+	/// Gas limit of the block.
+	pub gas_limit: U256,
+	/// Gas used by all transactions in the block.
+	pub gas_used: U256,
+	/// Timestamp of the block, in the unit of the timestamp pallet.
+	pub timestamp: u64,
+}
+
+impl Header {
+	/// The Ethereum block hash, i.e. keccak-256 of the RLP encoding.
+	pub fn hash(&self) -> H256 {
+		H256::from(sp_io::hashing::keccak_256(&rlp::encode(self)))
+	}
+}
+
+impl rlp::Encodable for Header {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(15);
+		s.append(&self.parent_hash);
+		s.append(&H256::from(sp_io::hashing::keccak_256(&rlp::EMPTY_LIST_RLP)));
+		s.append(&self.beneficiary);
+		s.append(&self.state_root);
+		s.append(&self.transactions_root);
+		s.append(&self.receipts_root);
+		s.append(&self.logs_bloom.0);
+		s.append(&U256::zero());
+		s.append(&self.number);
+		s.append(&self.gas_limit);
+		s.append(&self.gas_used);
+		s.append(&self.timestamp);
+		s.append_empty_data();
+		s.append(&H256::zero());
+		s.append(&vec![0u8; 8]);
+	}
+}
+
+/// An Ethereum block.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Block {
+	/// Header of the block.
+	pub header: Header,
+	/// Transactions included in the block.
+	pub transactions: Vec<Transaction>,
+}
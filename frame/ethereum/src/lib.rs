@@ -0,0 +1,449 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Ethereum Module
+//!
+//! Accepts RLP-encoded, ECDSA-signed Ethereum transactions as unsigned extrinsics and executes
+//! them through the [EVM module](../pallet_evm/index.html). The sender is recovered from the
+//! transaction signature and mapped to a Substrate account with the EVM module's
+//! `AddressMapping`, so accounts can be controlled with existing Ethereum tooling.
+//!
+//! Transactions are checked in `ValidateUnsigned` against the chain id (only EIP-155 signatures
+//! for `pallet_evm::Trait::ChainId` are accepted), the sender nonce, the minimal gas price and the
+//! sender balance. Their pool priority is the offered gas price.
+//!
+//! At the end of every block an Ethereum-style block is assembled from the executed
+//! transactions, together with their receipts and logs. Only the latest block is kept in
+//! storage; older ones are available through the state of the block they were built in, which
+//! is what the `eth_*` RPC in `pallet-ethereum-rpc` relies on. The lookups of block and
+//! transaction hashes are only kept for the last `IndexedBlocks` blocks.
+//!
+//! - [`ethereum::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//! - [`Module`](./struct.Module.html)
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `transact` - Execute a signed Ethereum transaction. Only valid as an unsigned extrinsic.
+//!
+//! ### Public Functions
+//!
+//! - `call` - Execute a call or create without applying its state changes, used for `eth_call`
+//!   and `eth_estimateGas`.
+//! - `current_block`, `current_receipts`, `current_transaction_statuses` - The Ethereum block
+//!   built at the end of the current block.
+//!
+//! ## Dependencies
+//!
+//! This module depends on the [EVM module](../pallet_evm/index.html).
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod block;
+mod transaction;
+mod tests;
+
+pub use crate::block::{Block, Bloom, Header, Receipt, TransactionStatus, BLOOM_SIZE};
+pub use crate::transaction::{Transaction, TransactionAction, TransactionSignature};
+
+use sp_std::prelude::*;
+use frame_support::{ensure, decl_module, decl_storage, decl_event, decl_error};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use frame_system::ensure_none;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	DispatchResult, DispatchError,
+	traits::{UniqueSaturatedInto, SaturatedConversion, CheckedSub},
+	transaction_validity::{
+		TransactionValidity, TransactionValidityError, TransactionSource, TransactionPriority,
+		ValidTransaction, InvalidTransaction,
+	},
+};
+use pallet_evm::{Account, ExecutionInfo, ExitReason, FeeCalculator, GasToWeight};
+
+/// The transaction is not signed for this chain.
+const INVALID_CHAIN_ID: u8 = 1;
+/// The transaction gas limit exceeds what the EVM module accepts.
+const GAS_LIMIT_TOO_HIGH: u8 = 2;
+
+/// Ethereum module trait
+pub trait Trait: pallet_evm::Trait {
+	/// The overarching event type.
+	type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The number of blocks for which the Ethereum block and transaction hashes of a block can be
+	/// looked up. Older entries are pruned at the start of each block.
+	type IndexedBlocks: Get<Self::BlockNumber>;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Ethereum {
+		/// Transactions executed in the current block, with their status and receipt.
+		Pending: Vec<(Transaction, TransactionStatus, Receipt)>;
+
+		/// The Ethereum block built at the end of the current block.
+		CurrentBlock get(fn current_block): Option<Block>;
+		/// Receipts of the transactions in `CurrentBlock`.
+		CurrentReceipts get(fn current_receipts): Option<Vec<Receipt>>;
+		/// Statuses of the transactions in `CurrentBlock`.
+		CurrentTransactionStatuses get(fn current_transaction_statuses): Option<Vec<TransactionStatus>>;
+
+		/// Number of the block an Ethereum block hash belongs to.
+		BlockNumbers get(fn block_number_by_hash): map hasher(identity) H256 => Option<U256>;
+		/// Block number and index of an Ethereum transaction hash.
+		TransactionIndices get(fn transaction_index): map hasher(identity) H256 => Option<(U256, u32)>;
+		/// The Ethereum block hash and transaction hashes indexed for a block, to be pruned once
+		/// the block falls out of `IndexedBlocks`.
+		IndexedHashes: map hasher(twox_64_concat) T::BlockNumber => Option<(H256, Vec<H256>)>;
+	}
+}
+
+decl_event! {
+	/// Ethereum events
+	pub enum Event {
+		/// An Ethereum transaction was executed. [from, to or contract address, transaction hash]
+		Executed(H160, H160, H256),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The signature does not recover to a sender.
+		InvalidSignature,
+		/// The gas limit does not fit into the limit accepted by the EVM module.
+		GasLimitTooHigh,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Execute a signed Ethereum transaction.
+		///
+		/// Only valid as an unsigned extrinsic, the sender is recovered from the signature.
+		#[weight = T::GasToWeight::gas_to_weight(
+			transaction.gas_limit.saturated_into::<usize>()
+		)]
+		fn transact(origin, transaction: Transaction) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let source = transaction.recover_signer().ok_or(Error::<T>::InvalidSignature)?;
+			ensure!(transaction.gas_limit <= U256::from(u32::max_value()), Error::<T>::GasLimitTooHigh);
+
+			let (contract_address, info) = Self::execute(
+				source,
+				transaction.action,
+				transaction.input.clone(),
+				transaction.value,
+				transaction.gas_limit.low_u32(),
+				Some(transaction.gas_price),
+				Some(transaction.nonce),
+				true,
+			)?;
+
+			let pending = Pending::get();
+			let transaction_hash = transaction.hash();
+			let to = match transaction.action {
+				TransactionAction::Call(target) => Some(target),
+				TransactionAction::Create => None,
+			};
+			let mut logs_bloom = Bloom::default();
+			for log in &info.logs {
+				logs_bloom.accrue_log(log);
+			}
+
+			let status = TransactionStatus {
+				transaction_hash,
+				transaction_index: pending.len() as u32,
+				from: source,
+				to,
+				contract_address,
+				logs: info.logs.clone(),
+				logs_bloom: logs_bloom.clone(),
+			};
+			let receipt = Receipt {
+				status: match info.exit_reason {
+					ExitReason::Succeed(_) => 1,
+					_ => 0,
+				},
+				used_gas: pending.last()
+					.map(|(_, _, receipt)| receipt.used_gas)
+					.unwrap_or_default()
+					.saturating_add(info.used_gas),
+				logs_bloom,
+				logs: info.logs,
+			};
+
+			Pending::append((transaction, status, receipt));
+			Self::deposit_event(Event::Executed(
+				source,
+				contract_address.or(to).unwrap_or_default(),
+				transaction_hash,
+			));
+
+			Ok(())
+		}
+
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			Self::prune_indices(n)
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			Self::store_block(n);
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Execute a call or create without applying its state changes.
+	///
+	/// Returns the output data and the gas used. An execution that doesn't succeed is reported
+	/// as an error.
+	pub fn call(
+		from: H160,
+		to: Option<H160>,
+		data: Vec<u8>,
+		value: U256,
+		gas_limit: U256,
+		gas_price: Option<U256>,
+	) -> Result<(Vec<u8>, U256), DispatchError> {
+		ensure!(gas_limit <= U256::from(u32::max_value()), Error::<T>::GasLimitTooHigh);
+
+		let action = match to {
+			Some(target) => TransactionAction::Call(target),
+			None => TransactionAction::Create,
+		};
+		let (_, info) = Self::execute(
+			from,
+			action,
+			data,
+			value,
+			gas_limit.low_u32(),
+			gas_price,
+			None,
+			false,
+		)?;
+
+		match info.exit_reason {
+			ExitReason::Succeed(_) => Ok((info.value, info.used_gas)),
+			ExitReason::Revert(_) => Err(DispatchError::Other("EVM execution reverted")),
+			_ => Err(DispatchError::Other("EVM execution failed")),
+		}
+	}
+
+	/// Execute an Ethereum transaction through the EVM module.
+	///
+	/// Returns the address of the created contract for a create.
+	fn execute(
+		source: H160,
+		action: TransactionAction,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u32,
+		gas_price: Option<U256>,
+		nonce: Option<U256>,
+		apply_state: bool,
+	) -> Result<(Option<H160>, ExecutionInfo<Vec<u8>>), DispatchError> {
+		match action {
+			TransactionAction::Call(target) => {
+				let info = pallet_evm::Module::<T>::execute_call(
+					source,
+					target,
+					input,
+					value,
+					gas_limit,
+					gas_price,
+					nonce,
+					apply_state,
+				)?;
+
+				Ok((None, info))
+			},
+			TransactionAction::Create => {
+				let info = pallet_evm::Module::<T>::execute_create(
+					source,
+					input,
+					value,
+					gas_limit,
+					gas_price,
+					nonce,
+					apply_state,
+				)?;
+
+				Ok((Some(info.value), ExecutionInfo {
+					exit_reason: info.exit_reason,
+					value: Vec::new(),
+					used_gas: info.used_gas,
+					logs: info.logs,
+				}))
+			},
+		}
+	}
+
+	/// Assemble the Ethereum block out of the transactions executed in this block.
+	fn store_block(block_number: T::BlockNumber) {
+		let mut transactions = Vec::new();
+		let mut statuses = Vec::new();
+		let mut receipts = Vec::new();
+		let mut logs_bloom = Bloom::default();
+		for (transaction, status, receipt) in Pending::take() {
+			logs_bloom.accrue_bloom(&receipt.logs_bloom);
+			transactions.push(transaction);
+			statuses.push(status);
+			receipts.push(receipt);
+		}
+
+		let number = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(block_number));
+		let header = Header {
+			parent_hash: Self::current_block()
+				.map(|block| block.header.hash())
+				.unwrap_or_default(),
+			beneficiary: H160::zero(),
+			state_root: H256::zero(),
+			transactions_root: sp_io::trie::keccak_256_ordered_root(
+				transactions.iter().map(|transaction| rlp::encode(transaction)).collect(),
+			),
+			receipts_root: sp_io::trie::keccak_256_ordered_root(
+				receipts.iter().map(|receipt| rlp::encode(receipt)).collect(),
+			),
+			logs_bloom,
+			number,
+			// Transactions are only bounded by the gas limit the EVM module accepts.
+			gas_limit: U256::from(u32::max_value()),
+			gas_used: receipts.last().map(|receipt| receipt.used_gas).unwrap_or_default(),
+			timestamp: UniqueSaturatedInto::<u64>::unique_saturated_into(
+				pallet_timestamp::Module::<T>::get(),
+			),
+		};
+		let block = Block { header, transactions };
+
+		for status in &statuses {
+			TransactionIndices::insert(status.transaction_hash, (number, status.transaction_index));
+		}
+		let block_hash = block.header.hash();
+		BlockNumbers::insert(block_hash, number);
+		IndexedHashes::<T>::insert(
+			block_number,
+			(block_hash, statuses.iter().map(|status| status.transaction_hash).collect::<Vec<_>>()),
+		);
+		CurrentBlock::put(block);
+		CurrentReceipts::put(receipts);
+		CurrentTransactionStatuses::put(statuses);
+	}
+
+	/// Remove the hash lookups of the block which falls out of `IndexedBlocks` at block `now`.
+	fn prune_indices(now: T::BlockNumber) -> Weight {
+		let pruned = match now.checked_sub(&T::IndexedBlocks::get()) {
+			Some(pruned) => pruned,
+			None => return 0,
+		};
+		match IndexedHashes::<T>::take(pruned) {
+			Some((block_hash, transaction_hashes)) => {
+				BlockNumbers::remove(block_hash);
+				for transaction_hash in &transaction_hashes {
+					TransactionIndices::remove(transaction_hash);
+				}
+				T::DbWeight::get().reads_writes(1, 2 + transaction_hashes.len() as Weight)
+			},
+			None => T::DbWeight::get().reads(1),
+		}
+	}
+
+	/// Check a transaction against the current state, except for a nonce in the future.
+	///
+	/// Returns the sender and its account.
+	fn check_transaction(transaction: &Transaction) -> Result<(H160, Account), TransactionValidityError> {
+		if transaction.signature.chain_id() != Some(T::ChainId::get()) {
+			return Err(InvalidTransaction::Custom(INVALID_CHAIN_ID).into());
+		}
+
+		let origin = transaction.recover_signer().ok_or(InvalidTransaction::BadProof)?;
+
+		if transaction.gas_limit > U256::from(u32::max_value()) {
+			return Err(InvalidTransaction::Custom(GAS_LIMIT_TOO_HIGH).into());
+		}
+
+		if transaction.gas_price < T::FeeCalculator::min_gas_price() {
+			return Err(InvalidTransaction::Payment.into());
+		}
+
+		let account = pallet_evm::Module::<T>::account_basic(&origin);
+
+		if transaction.nonce < account.nonce {
+			return Err(InvalidTransaction::Stale.into());
+		}
+
+		let fee = transaction.gas_price.checked_mul(transaction.gas_limit)
+			.ok_or(InvalidTransaction::Payment)?;
+		let total_payment = transaction.value.checked_add(fee)
+			.ok_or(InvalidTransaction::Payment)?;
+		if account.balance < total_payment {
+			return Err(InvalidTransaction::Payment.into());
+		}
+
+		Ok((origin, account))
+	}
+}
+
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	fn validate_unsigned(
+		_source: TransactionSource,
+		call: &Self::Call,
+	) -> TransactionValidity {
+		if let Call::transact(transaction) = call {
+			let (origin, account) = Self::check_transaction(transaction)?;
+
+			let mut builder = ValidTransaction::with_tag_prefix("Ethereum")
+				.priority(transaction.gas_price.saturated_into::<TransactionPriority>())
+				.and_provides((origin, transaction.nonce))
+				.propagate(true);
+
+			if transaction.nonce > account.nonce {
+				// The previous transaction of the sender has to be included first.
+				builder = builder.and_requires((origin, transaction.nonce - U256::one()));
+			}
+
+			builder.build()
+		} else {
+			InvalidTransaction::Call.into()
+		}
+	}
+
+	fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+		if let Call::transact(transaction) = call {
+			let (_, account) = Self::check_transaction(transaction)?;
+
+			// The EVM module rejects any nonce but the current one.
+			if transaction.nonce > account.nonce {
+				return Err(InvalidTransaction::Future.into());
+			}
+
+			Ok(())
+		} else {
+			Err(InvalidTransaction::Call.into())
+		}
+	}
+}
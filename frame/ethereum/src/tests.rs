@@ -0,0 +1,422 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(test)]
+
+use super::*;
+
+use std::{str::FromStr, collections::BTreeMap};
+use frame_support::{
+	assert_ok, assert_noop, impl_outer_origin, parameter_types,
+	traits::{OnInitialize, OnFinalize}, unsigned::ValidateUnsigned,
+};
+use pallet_evm::{
+	AddressMapping, EnsureAddressNever, EnsureAddressRoot, GenesisAccount, HashedAddressMapping,
+};
+use sp_core::Blake2Hasher;
+use sp_runtime::{
+	AccountId32, Perbill,
+	testing::Header as TestHeader,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = ();
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId32;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = TestHeader;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1000;
+}
+impl pallet_timestamp::Trait for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+/// Fixed gas price of `1`.
+pub struct FixedGasPrice;
+impl FeeCalculator for FixedGasPrice {
+	fn min_gas_price() -> U256 {
+		1.into()
+	}
+}
+
+parameter_types! {
+	pub const ChainId: u64 = 42;
+}
+impl pallet_evm::Trait for Test {
+	type FeeCalculator = FixedGasPrice;
+
+	type CallOrigin = EnsureAddressRoot<Self::AccountId>;
+	type WithdrawOrigin = EnsureAddressNever<Self::AccountId>;
+
+	type AddressMapping = HashedAddressMapping<Blake2Hasher>;
+	type Currency = Balances;
+
+	type Event = ();
+	type Precompiles = ();
+	type GasToWeight = ();
+	type ChainId = ChainId;
+}
+
+parameter_types! {
+	pub const IndexedBlocks: u64 = 2;
+}
+impl Trait for Test {
+	type Event = ();
+	type IndexedBlocks = IndexedBlocks;
+}
+
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Ethereum = Module<Test>;
+
+const ALICE_KEY: [u8; 32] = [0x11; 32];
+const BOB_KEY: [u8; 32] = [0x22; 32];
+
+/// Contract that emits an empty `LOG0`.
+fn log_contract() -> H160 {
+	H160::from_str("1000000000000000000000000000000000000001").unwrap()
+}
+
+fn address_of(key: &[u8; 32]) -> H160 {
+	let secret = secp256k1::SecretKey::parse(key).unwrap();
+	let public = secp256k1::PublicKey::from_secret_key(&secret).serialize();
+	H160::from(H256::from(sp_io::hashing::keccak_256(&public[1..])))
+}
+
+fn account_of(key: &[u8; 32]) -> AccountId32 {
+	<Test as pallet_evm::Trait>::AddressMapping::into_account_id(address_of(key))
+}
+
+fn unsigned_call(nonce: u64, to: Option<H160>, value: u64) -> Transaction {
+	Transaction {
+		nonce: nonce.into(),
+		gas_price: 1.into(),
+		gas_limit: 100_000.into(),
+		action: to.map(TransactionAction::Call).unwrap_or(TransactionAction::Create),
+		value: value.into(),
+		input: Vec::new(),
+		signature: TransactionSignature { v: 0, r: H256::zero(), s: H256::zero() },
+	}
+}
+
+fn sign(mut transaction: Transaction, key: &[u8; 32], chain_id: u64) -> Transaction {
+	let secret = secp256k1::SecretKey::parse(key).unwrap();
+	let message = secp256k1::Message::parse(
+		transaction.message_hash(Some(chain_id)).as_fixed_bytes(),
+	);
+	let (signature, recovery_id) = secp256k1::sign(&message, &secret);
+	let signature = signature.serialize();
+
+	transaction.signature = TransactionSignature {
+		v: recovery_id.serialize() as u64 + chain_id * 2 + 35,
+		r: H256::from_slice(&signature[0..32]),
+		s: H256::from_slice(&signature[32..64]),
+	};
+	transaction
+}
+
+fn validate(transaction: Transaction) -> TransactionValidity {
+	Ethereum::validate_unsigned(TransactionSource::External, &Call::transact(transaction))
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	let mut accounts = BTreeMap::new();
+	accounts.insert(
+		log_contract(),
+		GenesisAccount {
+			nonce: U256::from(1),
+			balance: U256::zero(),
+			storage: Default::default(),
+			code: vec![
+				0x60, 0x00, // PUSH1 0
+				0x60, 0x00, // PUSH1 0
+				0xa0, // LOG0
+				0x00, // STOP
+			],
+		}
+	);
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(account_of(&ALICE_KEY), 10_000_000), (account_of(&BOB_KEY), 1_000)],
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_evm::GenesisConfig { accounts }.assimilate_storage::<Test>(&mut t).unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+#[test]
+fn transaction_rlp_matches_eip155_example() {
+	// Example transaction from EIP-155.
+	let raw = hex_literal::hex!("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83");
+	let transaction: Transaction = rlp::decode(&raw).unwrap();
+
+	assert_eq!(transaction.nonce, U256::from(9));
+	assert_eq!(transaction.gas_price, U256::from(20_000_000_000u64));
+	assert_eq!(transaction.gas_limit, U256::from(21_000));
+	assert_eq!(
+		transaction.action,
+		TransactionAction::Call(H160::from_str("3535353535353535353535353535353535353535").unwrap()),
+	);
+	assert_eq!(transaction.value, U256::from(1_000_000_000_000_000_000u64));
+	assert_eq!(transaction.signature.chain_id(), Some(1));
+	assert_eq!(
+		transaction.message_hash(Some(1)),
+		H256::from_str("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53").unwrap(),
+	);
+	assert_eq!(
+		transaction.recover_signer(),
+		Some(H160::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap()),
+	);
+	assert_eq!(rlp::encode(&transaction), raw.to_vec());
+	assert_eq!(transaction.hash(), H256::from(sp_io::hashing::keccak_256(&raw)));
+}
+
+#[test]
+fn transact_call_stores_receipt_and_block() {
+	new_test_ext().execute_with(|| {
+		let transaction = sign(unsigned_call(0, Some(log_contract()), 0), &ALICE_KEY, 42);
+		let hash = transaction.hash();
+
+		assert_ok!(validate(transaction.clone()));
+		assert_ok!(Ethereum::transact(Origin::none(), transaction));
+		assert_eq!(System::account_nonce(account_of(&ALICE_KEY)), 1);
+
+		Ethereum::on_finalize(1);
+
+		let block = Ethereum::current_block().unwrap();
+		assert_eq!(block.header.number, U256::from(1));
+		assert_eq!(block.transactions.len(), 1);
+		assert_eq!(Ethereum::block_number_by_hash(block.header.hash()), Some(U256::from(1)));
+		assert_eq!(Ethereum::transaction_index(hash), Some((U256::from(1), 0)));
+
+		let receipts = Ethereum::current_receipts().unwrap();
+		assert_eq!(receipts[0].status, 1);
+		assert_eq!(receipts[0].logs.len(), 1);
+		assert_eq!(receipts[0].logs[0].address, log_contract());
+		assert!(block.header.logs_bloom.contains_input(log_contract().as_bytes()));
+		assert_eq!(block.header.gas_used, receipts[0].used_gas);
+
+		let statuses = Ethereum::current_transaction_statuses().unwrap();
+		assert_eq!(statuses[0].transaction_hash, hash);
+		assert_eq!(statuses[0].from, address_of(&ALICE_KEY));
+		assert_eq!(statuses[0].to, Some(log_contract()));
+		assert_eq!(statuses[0].contract_address, None);
+	});
+}
+
+#[test]
+fn hash_lookups_are_pruned_after_indexed_blocks() {
+	new_test_ext().execute_with(|| {
+		let transaction = sign(unsigned_call(0, Some(log_contract()), 0), &ALICE_KEY, 42);
+		let hash = transaction.hash();
+		assert_ok!(Ethereum::transact(Origin::none(), transaction));
+		Ethereum::on_finalize(1);
+		let block_hash = Ethereum::current_block().unwrap().header.hash();
+
+		// still within the last two blocks.
+		System::set_block_number(2);
+		Ethereum::on_initialize(2);
+		Ethereum::on_finalize(2);
+		assert_eq!(Ethereum::block_number_by_hash(block_hash), Some(U256::from(1)));
+		assert_eq!(Ethereum::transaction_index(hash), Some((U256::from(1), 0)));
+
+		System::set_block_number(3);
+		Ethereum::on_initialize(3);
+		assert_eq!(Ethereum::block_number_by_hash(block_hash), None);
+		assert_eq!(Ethereum::transaction_index(hash), None);
+		assert!(!IndexedHashes::<Test>::contains_key(1));
+		assert!(IndexedHashes::<Test>::contains_key(2));
+	});
+}
+
+#[test]
+fn transact_create_reports_contract_address() {
+	new_test_ext().execute_with(|| {
+		let transaction = sign(unsigned_call(0, None, 0), &ALICE_KEY, 42);
+		assert_ok!(Ethereum::transact(Origin::none(), transaction));
+
+		Ethereum::on_finalize(1);
+
+		let statuses = Ethereum::current_transaction_statuses().unwrap();
+		assert_eq!(statuses[0].to, None);
+		assert!(statuses[0].contract_address.is_some());
+		assert_eq!(Ethereum::current_receipts().unwrap()[0].status, 1);
+	});
+}
+
+#[test]
+fn transact_requires_unsigned_origin() {
+	new_test_ext().execute_with(|| {
+		let transaction = sign(unsigned_call(0, Some(log_contract()), 0), &ALICE_KEY, 42);
+		assert_noop!(
+			Ethereum::transact(Origin::signed(account_of(&ALICE_KEY)), transaction),
+			DispatchError::BadOrigin,
+		);
+	});
+}
+
+#[test]
+fn parent_hash_links_blocks() {
+	new_test_ext().execute_with(|| {
+		Ethereum::on_finalize(1);
+		let first = Ethereum::current_block().unwrap();
+
+		System::set_block_number(2);
+		Ethereum::on_finalize(2);
+		let second = Ethereum::current_block().unwrap();
+
+		assert_eq!(second.header.parent_hash, first.header.hash());
+		assert!(second.transactions.is_empty());
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_wrong_chain_id() {
+	new_test_ext().execute_with(|| {
+		let transaction = sign(unsigned_call(0, Some(log_contract()), 0), &ALICE_KEY, 1);
+		assert_eq!(validate(transaction), InvalidTransaction::Custom(INVALID_CHAIN_ID).into());
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_low_gas_price() {
+	new_test_ext().execute_with(|| {
+		let mut transaction = unsigned_call(0, Some(log_contract()), 0);
+		transaction.gas_price = U256::zero();
+		let transaction = sign(transaction, &ALICE_KEY, 42);
+		assert_eq!(validate(transaction), InvalidTransaction::Payment.into());
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_insufficient_balance() {
+	new_test_ext().execute_with(|| {
+		// Bob can't pay for the gas limit.
+		let transaction = sign(unsigned_call(0, Some(log_contract()), 0), &BOB_KEY, 42);
+		assert_eq!(validate(transaction), InvalidTransaction::Payment.into());
+
+		// Alice can't pay for the value on top of the fee.
+		let transaction = sign(unsigned_call(0, Some(log_contract()), 9_950_000), &ALICE_KEY, 42);
+		assert_eq!(validate(transaction), InvalidTransaction::Payment.into());
+	});
+}
+
+#[test]
+fn validate_unsigned_checks_nonce() {
+	new_test_ext().execute_with(|| {
+		System::inc_account_nonce(&account_of(&ALICE_KEY));
+
+		let stale = sign(unsigned_call(0, Some(log_contract()), 0), &ALICE_KEY, 42);
+		assert_eq!(validate(stale), InvalidTransaction::Stale.into());
+
+		let current = sign(unsigned_call(1, Some(log_contract()), 0), &ALICE_KEY, 42);
+		let valid = validate(current.clone()).unwrap();
+		assert!(valid.requires.is_empty());
+		assert_eq!(valid.priority, 1);
+		assert_ok!(Ethereum::pre_dispatch(&Call::transact(current)));
+
+		let future = sign(unsigned_call(2, Some(log_contract()), 0), &ALICE_KEY, 42);
+		let valid = validate(future.clone()).unwrap();
+		assert_eq!(valid.requires.len(), 1);
+		assert_eq!(
+			Ethereum::pre_dispatch(&Call::transact(future)),
+			Err(InvalidTransaction::Future.into()),
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_bad_signature() {
+	new_test_ext().execute_with(|| {
+		let mut transaction = sign(unsigned_call(0, Some(log_contract()), 0), &ALICE_KEY, 42);
+		transaction.signature.r = H256::zero();
+		assert_eq!(validate(transaction), InvalidTransaction::BadProof.into());
+	});
+}
+
+#[test]
+fn call_does_not_apply_state() {
+	new_test_ext().execute_with(|| {
+		let (_, used_gas) = Ethereum::call(
+			address_of(&ALICE_KEY),
+			Some(log_contract()),
+			Vec::new(),
+			U256::zero(),
+			U256::from(100_000),
+			None,
+		).unwrap();
+
+		assert!(used_gas > U256::zero());
+		assert_eq!(System::account_nonce(account_of(&ALICE_KEY)), 0);
+		assert_eq!(Balances::free_balance(account_of(&ALICE_KEY)), 10_000_000);
+	});
+}
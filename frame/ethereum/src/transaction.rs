@@ -0,0 +1,187 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ethereum transaction format and signature recovery.
+
+use codec::{Encode, Decode};
+use rlp::{Rlp, RlpStream, DecoderError};
+use sp_core::{H160, H256, U256};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Destination of an Ethereum transaction.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum TransactionAction {
+	/// Message call to the given address.
+	Call(H160),
+	/// Contract creation.
+	Create,
+}
+
+impl rlp::Encodable for TransactionAction {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		match self {
+			TransactionAction::Call(address) => s.append(address),
+			TransactionAction::Create => s.append_empty_data(),
+		};
+	}
+}
+
+impl rlp::Decodable for TransactionAction {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.is_empty() {
+			Ok(TransactionAction::Create)
+		} else {
+			Ok(TransactionAction::Call(rlp.as_val()?))
+		}
+	}
+}
+
+/// ECDSA signature of an Ethereum transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct TransactionSignature {
+	/// Recovery byte, optionally carrying the chain id as per EIP-155.
+	pub v: u64,
+	/// The `r` component of the signature.
+	pub r: H256,
+	/// The `s` component of the signature.
+	pub s: H256,
+}
+
+impl TransactionSignature {
+	/// The chain id the signature commits to, or `None` for a pre EIP-155 signature.
+	pub fn chain_id(&self) -> Option<u64> {
+		if self.v >= 35 {
+			Some((self.v - 35) / 2)
+		} else {
+			None
+		}
+	}
+
+	/// The recovery id encoded in `v`, or `None` if `v` is malformed.
+	pub fn recovery_id(&self) -> Option<u8> {
+		match self.v {
+			27 | 28 => Some((self.v - 27) as u8),
+			v if v >= 35 => Some(((v - 35) % 2) as u8),
+			_ => None,
+		}
+	}
+}
+
+/// A signed Ethereum transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Transaction {
+	/// Nonce of the sender.
+	pub nonce: U256,
+	/// Price paid per unit of gas.
+	pub gas_price: U256,
+	/// Maximum amount of gas the transaction may use.
+	pub gas_limit: U256,
+	/// Call or create.
+	pub action: TransactionAction,
+	/// Value transferred to the destination.
+	pub value: U256,
+	/// Call data or contract init code.
+	pub input: Vec<u8>,
+	/// Signature of the sender.
+	pub signature: TransactionSignature,
+}
+
+impl Transaction {
+	/// The Ethereum transaction hash, i.e. keccak-256 of the RLP encoding.
+	pub fn hash(&self) -> H256 {
+		H256::from(sp_io::hashing::keccak_256(&rlp::encode(self)))
+	}
+
+	/// The hash that was signed by the sender.
+	///
+	/// With a `chain_id` this is the EIP-155 signing payload, otherwise the legacy one.
+	pub fn message_hash(&self, chain_id: Option<u64>) -> H256 {
+		let mut s = RlpStream::new_list(if chain_id.is_some() { 9 } else { 6 });
+		s.append(&self.nonce);
+		s.append(&self.gas_price);
+		s.append(&self.gas_limit);
+		s.append(&self.action);
+		s.append(&self.value);
+		s.append(&self.input);
+		if let Some(chain_id) = chain_id {
+			s.append(&chain_id);
+			s.append(&0u8);
+			s.append(&0u8);
+		}
+
+		H256::from(sp_io::hashing::keccak_256(&s.out()))
+	}
+
+	/// Recover the address of the sender from the signature.
+	///
+	/// Returns `None` if the signature is malformed or doesn't recover to a public key.
+	pub fn recover_signer(&self) -> Option<H160> {
+		let recovery_id = self.signature.recovery_id()?;
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(self.signature.r.as_bytes());
+		sig[32..64].copy_from_slice(self.signature.s.as_bytes());
+		sig[64] = recovery_id;
+		let msg = self.message_hash(self.signature.chain_id());
+
+		let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&sig, msg.as_fixed_bytes()).ok()?;
+		Some(H160::from(H256::from(sp_io::hashing::keccak_256(&pubkey))))
+	}
+}
+
+impl rlp::Encodable for Transaction {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(9);
+		s.append(&self.nonce);
+		s.append(&self.gas_price);
+		s.append(&self.gas_limit);
+		s.append(&self.action);
+		s.append(&self.value);
+		s.append(&self.input);
+		s.append(&self.signature.v);
+		s.append(&U256::from_big_endian(self.signature.r.as_bytes()));
+		s.append(&U256::from_big_endian(self.signature.s.as_bytes()));
+	}
+}
+
+impl rlp::Decodable for Transaction {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.item_count()? != 9 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+
+		Ok(Transaction {
+			nonce: rlp.val_at(0)?,
+			gas_price: rlp.val_at(1)?,
+			gas_limit: rlp.val_at(2)?,
+			action: rlp.val_at(3)?,
+			value: rlp.val_at(4)?,
+			input: rlp.val_at(5)?,
+			signature: TransactionSignature {
+				v: rlp.val_at(6)?,
+				r: u256_to_h256(rlp.val_at(7)?),
+				s: u256_to_h256(rlp.val_at(8)?),
+			},
+		})
+	}
+}
+
+fn u256_to_h256(value: U256) -> H256 {
+	let mut bytes = [0u8; 32];
+	value.to_big_endian(&mut bytes);
+	H256::from(bytes)
+}
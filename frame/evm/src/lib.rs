@@ -24,7 +24,9 @@ mod backend;
 mod tests;
 pub mod precompiles;
 
-pub use crate::precompiles::{Precompile, Precompiles};
+pub use crate::precompiles::{
+	Precompile, Precompiles, IstanbulPrecompiles, AtAddress, ExtendedPrecompiles, Dispatch,
+};
 pub use crate::backend::{Account, Log, Vicinity, Backend};
 
 use sp_std::vec::Vec;
@@ -33,6 +35,7 @@ use codec::{Encode, Decode};
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 use frame_support::{ensure, decl_module, decl_storage, decl_event, decl_error};
+use frame_support::storage::{with_transaction, TransactionOutcome};
 use frame_support::weights::Weight;
 use frame_support::traits::{Currency, ExistenceRequirement, Get};
 use frame_system::RawOrigin;
@@ -44,7 +47,7 @@ use sha3::{Digest, Keccak256};
 pub use evm::{ExitReason, ExitSucceed, ExitError, ExitRevert, ExitFatal};
 use evm::Config;
 use evm::executor::StackExecutor;
-use evm::backend::ApplyBackend;
+use evm::backend::{ApplyBackend, Apply};
 
 /// Type alias for currency balance.
 pub type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
@@ -59,6 +62,20 @@ impl FeeCalculator for () {
 	fn min_gas_price() -> U256 { U256::zero() }
 }
 
+/// Conversion between EVM gas and dispatch weight.
+pub trait GasToWeight {
+	/// Weight charged for the given amount of gas.
+	fn gas_to_weight(gas: usize) -> Weight;
+	/// Gas charged for the given weight.
+	fn weight_to_gas(weight: Weight) -> usize;
+}
+
+/// One unit of gas is one unit of weight.
+impl GasToWeight for () {
+	fn gas_to_weight(gas: usize) -> Weight { gas as Weight }
+	fn weight_to_gas(weight: Weight) -> usize { weight.saturated_into() }
+}
+
 pub trait EnsureAddressOrigin<OuterOrigin> {
 	/// Success return type.
 	type Success;
@@ -188,6 +205,20 @@ impl Get<u64> for SystemChainId {
 
 static ISTANBUL_CONFIG: Config = Config::istanbul();
 
+/// Outcome of an EVM execution.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ExecutionInfo<T> {
+	/// Reason the execution exited with.
+	pub exit_reason: ExitReason,
+	/// Return value of the execution, either the output data or the created address.
+	pub value: T,
+	/// Gas used by the execution.
+	pub used_gas: U256,
+	/// Logs emitted during the execution. Only populated when the state is applied.
+	pub logs: Vec<Log>,
+}
+
 /// EVM module trait
 pub trait Trait: frame_system::Trait + pallet_timestamp::Trait {
 	/// Calculator for current gas price.
@@ -207,6 +238,9 @@ pub trait Trait: frame_system::Trait + pallet_timestamp::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 	/// Precompiles associated with this EVM engine.
 	type Precompiles: Precompiles;
+	/// Conversion between gas and weight, used for the weight of transactions and the gas of
+	/// precompiles dispatching runtime calls.
+	type GasToWeight: GasToWeight;
 	/// Chain ID of EVM.
 	type ChainId: Get<u64>;
 
@@ -315,7 +349,7 @@ decl_module! {
 		}
 
 		/// Issue an EVM call operation. This is similar to a message call transaction in Ethereum.
-		#[weight = (*gas_price).saturated_into::<Weight>().saturating_mul(*gas_limit as Weight)]
+		#[weight = T::GasToWeight::gas_to_weight(*gas_limit as usize)]
 		fn call(
 			origin,
 			source: H160,
@@ -337,11 +371,11 @@ decl_module! {
 				Some(gas_price),
 				nonce,
 				true,
-			)? {
-				(ExitReason::Succeed(_), _, _) => {
+			)?.exit_reason {
+				ExitReason::Succeed(_) => {
 					Module::<T>::deposit_event(Event::<T>::Executed(target));
 				},
-				(_, _, _) => {
+				_ => {
 					Module::<T>::deposit_event(Event::<T>::ExecutedFailed(target));
 				},
 			}
@@ -351,7 +385,7 @@ decl_module! {
 
 		/// Issue an EVM create operation. This is similar to a contract creation transaction in
 		/// Ethereum.
-		#[weight = (*gas_price).saturated_into::<Weight>().saturating_mul(*gas_limit as Weight)]
+		#[weight = T::GasToWeight::gas_to_weight(*gas_limit as usize)]
 		fn create(
 			origin,
 			source: H160,
@@ -372,10 +406,10 @@ decl_module! {
 				nonce,
 				true,
 			)? {
-				(ExitReason::Succeed(_), create_address, _) => {
+				ExecutionInfo { exit_reason: ExitReason::Succeed(_), value: create_address, .. } => {
 					Module::<T>::deposit_event(Event::<T>::Created(create_address));
 				},
-				(_, create_address, _) => {
+				ExecutionInfo { value: create_address, .. } => {
 					Module::<T>::deposit_event(Event::<T>::CreatedFailed(create_address));
 				},
 			}
@@ -384,7 +418,7 @@ decl_module! {
 		}
 
 		/// Issue an EVM create2 operation.
-		#[weight = (*gas_price).saturated_into::<Weight>().saturating_mul(*gas_limit as Weight)]
+		#[weight = T::GasToWeight::gas_to_weight(*gas_limit as usize)]
 		fn create2(
 			origin,
 			source: H160,
@@ -407,10 +441,10 @@ decl_module! {
 				nonce,
 				true,
 			)? {
-				(ExitReason::Succeed(_), create_address, _) => {
+				ExecutionInfo { exit_reason: ExitReason::Succeed(_), value: create_address, .. } => {
 					Module::<T>::deposit_event(Event::<T>::Created(create_address));
 				},
-				(_, create_address, _) => {
+				ExecutionInfo { value: create_address, .. } => {
 					Module::<T>::deposit_event(Event::<T>::CreatedFailed(create_address));
 				},
 			}
@@ -486,7 +520,7 @@ impl<T: Trait> Module<T> {
 		gas_price: Option<U256>,
 		nonce: Option<U256>,
 		apply_state: bool,
-	) -> Result<(ExitReason, H160, U256), Error<T>> {
+	) -> Result<ExecutionInfo<H160>, Error<T>> {
 		Self::execute_evm(
 			source,
 			value,
@@ -494,6 +528,7 @@ impl<T: Trait> Module<T> {
 			gas_price,
 			nonce,
 			apply_state,
+			None,
 			|executor| {
 				let address = executor.create_address(
 					evm::CreateScheme::Legacy { caller: source },
@@ -518,7 +553,7 @@ impl<T: Trait> Module<T> {
 		gas_price: Option<U256>,
 		nonce: Option<U256>,
 		apply_state: bool,
-	) -> Result<(ExitReason, H160, U256), Error<T>> {
+	) -> Result<ExecutionInfo<H160>, Error<T>> {
 		let code_hash = H256::from_slice(Keccak256::digest(&init).as_slice());
 		Self::execute_evm(
 			source,
@@ -527,6 +562,7 @@ impl<T: Trait> Module<T> {
 			gas_price,
 			nonce,
 			apply_state,
+			None,
 			|executor| {
 				let address = executor.create_address(
 					evm::CreateScheme::Create2 { caller: source, code_hash, salt },
@@ -552,7 +588,14 @@ impl<T: Trait> Module<T> {
 		gas_price: Option<U256>,
 		nonce: Option<U256>,
 		apply_state: bool,
-	) -> Result<(ExitReason, Vec<u8>, U256), Error<T>> {
+	) -> Result<ExecutionInfo<Vec<u8>>, Error<T>> {
+		// Precompiles only learn about their caller when called directly by the transaction.
+		let direct_caller = if AccountCodes::decode_len(&target).unwrap_or(0) == 0 {
+			Some(source)
+		} else {
+			None
+		};
+
 		Self::execute_evm(
 			source,
 			value,
@@ -560,6 +603,7 @@ impl<T: Trait> Module<T> {
 			gas_price,
 			nonce,
 			apply_state,
+			direct_caller,
 			|executor| executor.transact_call(
 				source,
 				target,
@@ -571,6 +615,10 @@ impl<T: Trait> Module<T> {
 	}
 
 	/// Execute an EVM operation.
+	///
+	/// Calls deferred by precompiles run after the state is applied, in the same storage
+	/// transaction. If any of them fails, the execution is turned into a failed one and both the
+	/// EVM state and the deferred calls are reverted, only the fee and the nonce are kept.
 	fn execute_evm<F, R>(
 		source: H160,
 		value: U256,
@@ -578,8 +626,9 @@ impl<T: Trait> Module<T> {
 		gas_price: Option<U256>,
 		nonce: Option<U256>,
 		apply_state: bool,
+		direct_caller: Option<H160>,
 		f: F,
-	) -> Result<(ExitReason, R, U256), Error<T>> where
+	) -> Result<ExecutionInfo<R>, Error<T>> where
 		F: FnOnce(&mut StackExecutor<Backend<T>>) -> (ExitReason, R),
 	{
 		let gas_price = match gas_price {
@@ -614,17 +663,60 @@ impl<T: Trait> Module<T> {
 			ensure!(source_account.nonce == nonce, Error::<T>::InvalidNonce);
 		}
 
-		let (retv, reason) = f(&mut executor);
+		let ((mut exit_reason, value), deferred) =
+			precompiles::using_context(direct_caller, || f(&mut executor));
 
 		let used_gas = U256::from(executor.used_gas());
 		let actual_fee = executor.fee(gas_price);
 		executor.deposit(source, total_fee.saturating_sub(actual_fee));
 
+		let mut logs = Vec::new();
 		if apply_state {
-			let (values, logs) = executor.deconstruct();
-			backend.apply(values, logs, true);
+			let (values, evm_logs) = executor.deconstruct();
+			let values: Vec<_> = values.into_iter().collect();
+			let evm_logs: Vec<evm::backend::Log> = evm_logs.into_iter().collect();
+			let source_nonce = values.iter().find_map(|apply| match apply {
+				Apply::Modify { address, basic, .. } if *address == source => Some(basic.nonce),
+				_ => None,
+			}).unwrap_or(source_account.nonce);
+			let succeeded = match exit_reason {
+				ExitReason::Succeed(_) => true,
+				_ => false,
+			};
+
+			let applied = with_transaction(|| {
+				backend.apply(values, evm_logs.clone(), true);
+				if !succeeded {
+					return TransactionOutcome::Commit(true);
+				}
+				match deferred.into_iter().try_for_each(|call| call()) {
+					Ok(()) => TransactionOutcome::Commit(true),
+					Err(_) => TransactionOutcome::Rollback(false),
+				}
+			});
+
+			if applied {
+				logs = evm_logs.iter().map(|log| Log {
+					address: log.address,
+					topics: log.topics.clone(),
+					data: log.data.clone(),
+				}).collect();
+			} else {
+				// Everything was reverted, keep only the fee and the nonce as for a failed call.
+				let account_id = T::AddressMapping::into_account_id(source);
+				for _ in 0..source_nonce.saturating_sub(source_account.nonce).low_u128() {
+					frame_system::Module::<T>::inc_account_nonce(&account_id);
+				}
+				T::Currency::slash(&account_id, actual_fee.low_u128().unique_saturated_into());
+				exit_reason = ExitReason::Error(ExitError::Other("Deferred dispatch failed"));
+			}
 		}
 
-		Ok((retv, reason, used_gas))
+		Ok(ExecutionInfo {
+			exit_reason,
+			value,
+			used_gas,
+			logs,
+		})
 	}
 }
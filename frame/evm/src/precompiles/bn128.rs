@@ -0,0 +1,139 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The alt_bn128 precompiles, see EIP-196 and EIP-197, with the costs of EIP-1108.
+
+use sp_std::prelude::*;
+use evm::{ExitError, ExitSucceed};
+use bn::{AffineG1, AffineG2, Fq, Fq2, Fr, G1, G2, Group, Gt};
+
+use super::{Precompile, ensure_cost, read_word};
+
+/// Read the field element at `offset` of `input`.
+fn read_fq(input: &[u8], offset: usize) -> Result<Fq, ExitError> {
+	Fq::from_slice(&read_word(input, offset)).map_err(|_| ExitError::Other("invalid point"))
+}
+
+/// Read the G1 point at `offset` of `input`. `(0, 0)` is the point at infinity.
+fn read_point(input: &[u8], offset: usize) -> Result<G1, ExitError> {
+	let x = read_fq(input, offset)?;
+	let y = read_fq(input, offset + 32)?;
+
+	if x == Fq::zero() && y == Fq::zero() {
+		Ok(G1::zero())
+	} else {
+		AffineG1::new(x, y).map(Into::into).map_err(|_| ExitError::Other("invalid point"))
+	}
+}
+
+/// Read the scalar at `offset` of `input`, reduced modulo the group order.
+fn read_scalar(input: &[u8], offset: usize) -> Fr {
+	let word = read_word(input, offset);
+	let mut high = [0u8; 32];
+	high[16..].copy_from_slice(&word[..16]);
+	let mut low = [0u8; 32];
+	low[16..].copy_from_slice(&word[16..]);
+	let mut shift = [0u8; 32];
+	shift[15] = 1;
+
+	let from_slice = |bytes: &[u8; 32]| Fr::from_slice(bytes)
+		.expect("128 bit values are below the group order; qed");
+	from_slice(&high) * from_slice(&shift) + from_slice(&low)
+}
+
+/// Encode a G1 point, the point at infinity as `(0, 0)`.
+fn encode_point(point: G1) -> Vec<u8> {
+	let mut output = vec![0u8; 64];
+	if let Some(point) = AffineG1::from_jacobian(point) {
+		point.x().to_big_endian(&mut output[0..32]).expect("slice is 32 bytes long; qed");
+		point.y().to_big_endian(&mut output[32..64]).expect("slice is 32 bytes long; qed");
+	}
+	output
+}
+
+/// The alt_bn128 point addition precompile.
+pub struct Bn128Add;
+
+impl Precompile for Bn128Add {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError> {
+		let cost = ensure_cost(target_gas, 150)?;
+
+		let p1 = read_point(input, 0)?;
+		let p2 = read_point(input, 64)?;
+
+		Ok((ExitSucceed::Returned, encode_point(p1 + p2), cost))
+	}
+}
+
+/// The alt_bn128 scalar multiplication precompile.
+pub struct Bn128Mul;
+
+impl Precompile for Bn128Mul {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError> {
+		let cost = ensure_cost(target_gas, 6_000)?;
+
+		let point = read_point(input, 0)?;
+		let scalar = read_scalar(input, 64);
+
+		Ok((ExitSucceed::Returned, encode_point(point * scalar), cost))
+	}
+}
+
+/// The alt_bn128 pairing check precompile.
+pub struct Bn128Pairing;
+
+impl Precompile for Bn128Pairing {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError> {
+		if input.len() % 192 != 0 {
+			return Err(ExitError::Other("invalid input length"))
+		}
+
+		let pairs_len = input.len() / 192;
+		let cost = ensure_cost(target_gas, 34_000usize.saturating_mul(pairs_len).saturating_add(45_000))?;
+
+		let mut pairs = Vec::with_capacity(pairs_len);
+		for offset in (0..input.len()).step_by(192) {
+			let a = read_point(input, offset)?;
+			// The coefficient of the imaginary unit comes first.
+			let b_x = Fq2::new(read_fq(input, offset + 96)?, read_fq(input, offset + 64)?);
+			let b_y = Fq2::new(read_fq(input, offset + 160)?, read_fq(input, offset + 128)?);
+			let b = if b_x == Fq2::zero() && b_y == Fq2::zero() {
+				G2::zero()
+			} else {
+				AffineG2::new(b_x, b_y).map(Into::into).map_err(|_| ExitError::Other("invalid point"))?
+			};
+
+			pairs.push((a, b));
+		}
+
+		let mut output = vec![0u8; 32];
+		if pairs.is_empty() || bn::pairing_batch(&pairs) == Gt::one() {
+			output[31] = 1;
+		}
+
+		Ok((ExitSucceed::Returned, output, cost))
+	}
+}
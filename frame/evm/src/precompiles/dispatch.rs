@@ -0,0 +1,69 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A precompile dispatching runtime calls.
+
+use sp_std::{marker::PhantomData, vec::Vec};
+use codec::Decode;
+use frame_support::dispatch::Dispatchable;
+use frame_support::weights::{GetDispatchInfo, PostDispatchInfo};
+use frame_system::RawOrigin;
+use evm::{ExitError, ExitSucceed};
+
+use super::{Precompile, defer, direct_caller, ensure_cost};
+use crate::{AddressMapping, GasToWeight, Trait};
+
+/// A precompile dispatching the SCALE encoded runtime call `Call` given as input.
+///
+/// The call is dispatched with a signed origin of the account the caller maps to through
+/// `Trait::AddressMapping`, and costs the gas its weight converts to through `Trait::GasToWeight`.
+/// It is only available to transactions calling the precompile directly, see [`direct_caller`],
+/// and is deferred until the EVM state is applied, see [`defer`].
+///
+/// The precompile succeeds before the call runs; if the call then fails, the whole execution is
+/// reverted and reported as failed.
+pub struct Dispatch<T, Call>(PhantomData<(T, Call)>);
+
+impl<T, Call> Precompile for Dispatch<T, Call> where
+	T: Trait,
+	Call: Dispatchable<Origin=T::Origin, PostInfo=PostDispatchInfo> + GetDispatchInfo + Decode + 'static,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError> {
+		let call = Call::decode(&mut &input[..]).map_err(|_| ExitError::Other("invalid call"))?;
+		let cost = ensure_cost(
+			target_gas,
+			T::GasToWeight::weight_to_gas(call.get_dispatch_info().weight),
+		)?;
+
+		let caller = direct_caller().ok_or(ExitError::Other("caller is not the transaction origin"))?;
+		let origin = T::AddressMapping::into_account_id(caller);
+
+		let deferred = defer(move || {
+			call.dispatch(RawOrigin::Signed(origin).into())
+				.map(|_| ())
+				.map_err(|e| e.error)
+		});
+		if !deferred {
+			return Err(ExitError::Other("no running execution"))
+		}
+
+		Ok((ExitSucceed::Stopped, Vec::new(), cost))
+	}
+}
@@ -0,0 +1,319 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builtin precompiles.
+//!
+//! [`IstanbulPrecompiles`] is the standard set of Ethereum precompiles at the addresses `0x01` to
+//! `0x09`. A runtime configures it as `Trait::Precompiles`, optionally extended with precompiles
+//! of its own.
+//!
+//! ## Adding precompiles
+//!
+//! Any type implementing [`Precompile`] can be placed at an arbitrary address with [`AtAddress`]
+//! and combined with another set through [`ExtendedPrecompiles`]:
+//!
+//! ```ignore
+//! parameter_types! {
+//! 	pub DispatchAddress: H160 = H160::from_low_u64_be(0x400);
+//! }
+//!
+//! impl pallet_evm::Trait for Runtime {
+//! 	type Precompiles = ExtendedPrecompiles<
+//! 		IstanbulPrecompiles,
+//! 		AtAddress<Dispatch<Runtime, Call>, DispatchAddress>,
+//! 	>;
+//! 	// --snip--
+//! }
+//! ```
+//!
+//! Precompiles that call into FRAME pallets must not modify the state directly, since the EVM
+//! keeps its own view of the accounts it touches and applies it after the execution. Instead
+//! they [`defer`] the call, which runs after the EVM state was applied and only if the execution
+//! succeeded. [`Dispatch`] is such a precompile: it dispatches a SCALE encoded runtime call on
+//! behalf of the [`direct_caller`] and charges the weight of the call as gas, converted through
+//! `Trait::GasToWeight`.
+
+mod blake2f;
+mod bn128;
+mod dispatch;
+mod modexp;
+#[cfg(test)]
+mod tests;
+
+pub use blake2f::Blake2F;
+pub use bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
+pub use dispatch::Dispatch;
+pub use modexp::Modexp;
+
+use sp_std::{boxed::Box, cmp::min, marker::PhantomData, vec::Vec};
+use sp_core::H160;
+use sp_runtime::DispatchResult;
+use frame_support::traits::Get;
+use evm::{ExitError, ExitSucceed};
+use ripemd160::Digest;
+use impl_trait_for_tuples::impl_for_tuples;
+
+/// Custom precompiles to be used by EVM engine.
+pub trait Precompiles {
+	/// Try to execute the code address as precompile. If the code address is not
+	/// a precompile or the precompile is not yet available, return `None`.
+	/// Otherwise, calculate the amount of gas needed with given `input` and
+	/// `target_gas`. Return `Some(Ok(status, output, gas_used))` if the execution
+	/// is successful. Otherwise return `Some(Err(_))`.
+	fn execute(
+		address: H160,
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> Option<core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError>>;
+}
+
+/// One single precompile used by EVM engine.
+pub trait Precompile {
+	/// Try to execute the precompile. Calculate the amount of gas needed with given `input` and
+	/// `target_gas`. Return `Ok(status, output, gas_used)` if the execution is
+	/// successful. Otherwise return `Err(_)`.
+	fn execute(
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError>;
+}
+
+#[impl_for_tuples(16)]
+#[tuple_types_no_default_trait_bound]
+impl Precompiles for Tuple {
+	for_tuples!( where #( Tuple: Precompile )* );
+
+	fn execute(
+		address: H160,
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> Option<core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError>> {
+		let mut index = 0;
+
+		for_tuples!( #(
+			index += 1;
+			if address == H160::from_low_u64_be(index) {
+				return Some(Tuple::execute(input, target_gas))
+			}
+		)* );
+
+		None
+	}
+}
+
+/// The standard Ethereum precompiles of the Istanbul hard fork, at the addresses `0x01` to `0x09`.
+pub type IstanbulPrecompiles = (
+	ECRecover,
+	Sha256,
+	Ripemd160,
+	Identity,
+	Modexp,
+	Bn128Add,
+	Bn128Mul,
+	Bn128Pairing,
+	Blake2F,
+);
+
+/// A single precompile `P` at the address returned by `A`.
+pub struct AtAddress<P, A>(PhantomData<(P, A)>);
+
+impl<P: Precompile, A: Get<H160>> Precompiles for AtAddress<P, A> {
+	fn execute(
+		address: H160,
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> Option<core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError>> {
+		if address == A::get() {
+			Some(P::execute(input, target_gas))
+		} else {
+			None
+		}
+	}
+}
+
+/// The precompiles of `Base` extended with the precompiles of `Extra`.
+///
+/// `Extra` is tried first, so it takes precedence if both use the same address.
+pub struct ExtendedPrecompiles<Base, Extra>(PhantomData<(Base, Extra)>);
+
+impl<Base: Precompiles, Extra: Precompiles> Precompiles for ExtendedPrecompiles<Base, Extra> {
+	fn execute(
+		address: H160,
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> Option<core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError>> {
+		Extra::execute(address, input, target_gas)
+			.or_else(|| Base::execute(address, input, target_gas))
+	}
+}
+
+/// A call deferred by a precompile until the EVM state is applied.
+pub(crate) type DeferredCall = Box<dyn FnOnce() -> DispatchResult>;
+
+/// State shared with the precompiles during an EVM execution.
+struct Context {
+	direct_caller: Option<H160>,
+	deferred: Vec<DeferredCall>,
+}
+
+environmental::environmental!(context: Context);
+
+/// Run `f`, an EVM execution, providing the context of the precompiles.
+///
+/// Returns the result of `f` and the calls deferred during the execution.
+pub(crate) fn using_context<R>(
+	direct_caller: Option<H160>,
+	f: impl FnOnce() -> R,
+) -> (R, Vec<DeferredCall>) {
+	let mut ctx = Context { direct_caller, deferred: Vec::new() };
+	let result = context::using(&mut ctx, f);
+	(result, ctx.deferred)
+}
+
+/// The caller of the running precompile if it was called directly by the transaction.
+///
+/// The EVM doesn't expose the caller to precompiles, so this is `None` if the precompile was
+/// called from contract code, or outside of an EVM execution.
+pub fn direct_caller() -> Option<H160> {
+	context::with(|ctx| ctx.direct_caller).flatten()
+}
+
+/// Defer `f` until the state changes of the running EVM execution are applied.
+///
+/// Deferred calls run in the order they were deferred, only if the execution succeeded and its
+/// state is applied. If any of them fails, the changes of all of them are reverted and the
+/// execution is reported as failed. Returns `false` if there is no running EVM execution.
+pub fn defer(f: impl FnOnce() -> DispatchResult + 'static) -> bool {
+	context::with(|ctx| ctx.deferred.push(Box::new(f))).is_some()
+}
+
+/// Copy `source` starting at `offset` into `target`, leaving the bytes past the end of `source`
+/// untouched.
+fn read_input(source: &[u8], target: &mut [u8], offset: usize) {
+	if offset >= source.len() {
+		return
+	}
+
+	let len = min(target.len(), source.len() - offset);
+	target[..len].copy_from_slice(&source[offset..offset + len]);
+}
+
+/// Read the 32 byte word at `offset` of `input`, padded with zeros.
+fn read_word(input: &[u8], offset: usize) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	read_input(input, &mut word, offset);
+	word
+}
+
+/// Ensure `cost` fits into `target_gas`.
+fn ensure_cost(target_gas: Option<usize>, cost: usize) -> Result<usize, ExitError> {
+	if let Some(target_gas) = target_gas {
+		if cost > target_gas {
+			return Err(ExitError::OutOfGas)
+		}
+	}
+
+	Ok(cost)
+}
+
+/// Linear gas cost
+fn ensure_linear_cost(
+	target_gas: Option<usize>,
+	len: usize,
+	base: usize,
+	word: usize
+) -> Result<usize, ExitError> {
+	let cost = base.checked_add(
+		word.checked_mul(len.saturating_add(31) / 32).ok_or(ExitError::OutOfGas)?
+	).ok_or(ExitError::OutOfGas)?;
+
+	ensure_cost(target_gas, cost)
+}
+
+/// The identity precompile.
+pub struct Identity;
+
+impl Precompile for Identity {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError> {
+		let cost = ensure_linear_cost(target_gas, input.len(), 15, 3)?;
+
+		Ok((ExitSucceed::Returned, input.to_vec(), cost))
+	}
+}
+
+/// The ecrecover precompile.
+pub struct ECRecover;
+
+impl Precompile for ECRecover {
+	fn execute(
+		i: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError> {
+		let cost = ensure_linear_cost(target_gas, i.len(), 3000, 0)?;
+
+		let mut input = [0u8; 128];
+		input[..min(i.len(), 128)].copy_from_slice(&i[..min(i.len(), 128)]);
+
+		let mut msg = [0u8; 32];
+		let mut sig = [0u8; 65];
+
+		msg[0..32].copy_from_slice(&input[0..32]);
+		sig[0..32].copy_from_slice(&input[64..96]);
+		sig[32..64].copy_from_slice(&input[96..128]);
+		sig[64] = input[63];
+
+		let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &msg)
+			.map_err(|_| ExitError::Other("Public key recover failed"))?;
+		let mut address = sp_io::hashing::keccak_256(&pubkey);
+		address[0..12].copy_from_slice(&[0u8; 12]);
+
+		Ok((ExitSucceed::Returned, address.to_vec(), cost))
+	}
+}
+
+/// The ripemd precompile.
+pub struct Ripemd160;
+
+impl Precompile for Ripemd160 {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError> {
+		let cost = ensure_linear_cost(target_gas, input.len(), 600, 120)?;
+
+		let ret = ripemd160::Ripemd160::digest(input).to_vec();
+		Ok((ExitSucceed::Returned, ret, cost))
+	}
+}
+
+/// The sha256 precompile.
+pub struct Sha256;
+
+impl Precompile for Sha256 {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError> {
+		let cost = ensure_linear_cost(target_gas, input.len(), 60, 12)?;
+
+		let ret = sp_io::hashing::sha2_256(input);
+		Ok((ExitSucceed::Returned, ret.to_vec(), cost))
+	}
+}
@@ -0,0 +1,113 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The modexp precompile, see EIP-198.
+
+use sp_std::{cmp::{max, min}, prelude::*};
+use sp_core::U256;
+use evm::{ExitError, ExitSucceed};
+use num_bigint::BigUint;
+
+use super::{Precompile, ensure_cost, read_input, read_word};
+
+/// The modexp precompile.
+pub struct Modexp;
+
+impl Precompile for Modexp {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<usize>,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, usize), ExitError> {
+		let base_len = U256::from_big_endian(&read_word(input, 0));
+		let exp_len = U256::from_big_endian(&read_word(input, 32));
+		let mod_len = U256::from_big_endian(&read_word(input, 64));
+
+		// Only the first 32 bytes of the exponent are relevant for the cost.
+		let exp_head = if base_len > U256::from(usize::max_value()) {
+			U256::zero()
+		} else {
+			let head_len = min(exp_len, U256::from(32)).low_u64() as usize;
+			let mut head = [0u8; 32];
+			read_input(input, &mut head[..head_len], 96usize.saturating_add(base_len.low_u64() as usize));
+			U256::from_big_endian(&head[..head_len])
+		};
+
+		let cost = gas_cost(base_len, exp_len, mod_len, exp_head).ok_or(ExitError::OutOfGas)?;
+		if cost > U256::from(usize::max_value()) {
+			return Err(ExitError::OutOfGas)
+		}
+		let cost = ensure_cost(target_gas, cost.low_u64() as usize)?;
+
+		// With a non-empty modulus the cost bounds all lengths.
+		if mod_len.is_zero() {
+			return Ok((ExitSucceed::Returned, Vec::new(), cost))
+		}
+		let base_len = base_len.low_u64() as usize;
+		let exp_len = exp_len.low_u64() as usize;
+		let mod_len = mod_len.low_u64() as usize;
+
+		let mut base = vec![0u8; base_len];
+		read_input(input, &mut base, 96);
+		let mut exp = vec![0u8; exp_len];
+		read_input(input, &mut exp, 96 + base_len);
+		let mut modulus = vec![0u8; mod_len];
+		read_input(input, &mut modulus, 96 + base_len + exp_len);
+
+		let mut output = vec![0u8; mod_len];
+		if modulus.iter().any(|byte| *byte != 0) {
+			let result = BigUint::from_bytes_be(&base)
+				.modpow(&BigUint::from_bytes_be(&exp), &BigUint::from_bytes_be(&modulus))
+				.to_bytes_be();
+			// The result is below the modulus, so it fits.
+			output[mod_len - result.len()..].copy_from_slice(&result);
+		}
+
+		Ok((ExitSucceed::Returned, output, cost))
+	}
+}
+
+/// Cost of a multiplication of numbers with `len` bytes.
+fn mult_complexity(len: U256) -> Option<U256> {
+	if len <= U256::from(64) {
+		Some(len * len)
+	} else if len <= U256::from(1024) {
+		Some(len * len / 4 + len * 96 - 3072)
+	} else {
+		len.checked_mul(len)?
+			.checked_div(16.into())?
+			.checked_add(len.checked_mul(480.into())?)?
+			.checked_sub(199_680.into())
+	}
+}
+
+/// Gas cost of the modexp, `None` if it overflows.
+fn gas_cost(base_len: U256, exp_len: U256, mod_len: U256, exp_head: U256) -> Option<U256> {
+	let head_bits = if exp_head.is_zero() {
+		U256::zero()
+	} else {
+		U256::from(exp_head.bits() - 1)
+	};
+	let adjusted_exp_len = if exp_len <= U256::from(32) {
+		head_bits
+	} else {
+		U256::from(8).checked_mul(exp_len - 32)?.checked_add(head_bits)?
+	};
+
+	mult_complexity(max(base_len, mod_len))?
+		.checked_mul(max(adjusted_exp_len, U256::one()))?
+		.checked_div(20.into())
+}
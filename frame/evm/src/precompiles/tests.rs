@@ -0,0 +1,135 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conformance tests of the precompiles against the Ethereum test vectors in `testdata`.
+
+use super::*;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Vector {
+	input: String,
+	expected: String,
+	name: String,
+	gas: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct FailVector {
+	input: String,
+	expected_error: String,
+	name: String,
+}
+
+fn check<P: Precompile>(vectors: &str) {
+	let vectors: Vec<Vector> = serde_json::from_str(vectors).unwrap();
+	assert!(!vectors.is_empty());
+
+	for vector in vectors {
+		let input = hex::decode(&vector.input).unwrap();
+		let (_, output, cost) = P::execute(&input, None)
+			.unwrap_or_else(|e| panic!("{} failed: {:?}", vector.name, e));
+
+		assert_eq!(hex::encode(output), vector.expected, "{}", vector.name);
+		assert_eq!(cost, vector.gas, "{}", vector.name);
+		if cost > 0 {
+			assert_eq!(
+				P::execute(&input, Some(cost - 1)),
+				Err(ExitError::OutOfGas),
+				"{}", vector.name,
+			);
+		}
+	}
+}
+
+fn check_failure<P: Precompile>(vectors: &str) {
+	let vectors: Vec<FailVector> = serde_json::from_str(vectors).unwrap();
+	assert!(!vectors.is_empty());
+
+	for vector in vectors {
+		let input = hex::decode(&vector.input).unwrap();
+		match P::execute(&input, None) {
+			Err(ExitError::Other(error)) => assert_eq!(error, vector.expected_error, "{}", vector.name),
+			result => panic!("{} returned {:?}", vector.name, result),
+		}
+	}
+}
+
+#[test]
+fn modexp() {
+	check::<Modexp>(include_str!("../../testdata/modexp.json"));
+}
+
+#[test]
+fn bn128_add() {
+	check::<Bn128Add>(include_str!("../../testdata/bn256Add.json"));
+	check_failure::<Bn128Add>(include_str!("../../testdata/fail-bn256Add.json"));
+}
+
+#[test]
+fn bn128_mul() {
+	check::<Bn128Mul>(include_str!("../../testdata/bn256ScalarMul.json"));
+}
+
+#[test]
+fn bn128_pairing() {
+	check::<Bn128Pairing>(include_str!("../../testdata/bn256Pairing.json"));
+	check_failure::<Bn128Pairing>(include_str!("../../testdata/fail-bn256Pairing.json"));
+}
+
+#[test]
+fn blake2f() {
+	check::<Blake2F>(include_str!("../../testdata/blake2F.json"));
+	check_failure::<Blake2F>(include_str!("../../testdata/fail-blake2F.json"));
+}
+
+#[test]
+fn istanbul_precompiles_are_at_standard_addresses() {
+	let input = [0x42u8; 4];
+
+	assert_eq!(
+		IstanbulPrecompiles::execute(H160::from_low_u64_be(4), &input, None),
+		Some(Ok((ExitSucceed::Returned, input.to_vec(), 18))),
+	);
+	assert!(IstanbulPrecompiles::execute(H160::from_low_u64_be(9), &input, None).is_some());
+	assert!(IstanbulPrecompiles::execute(H160::from_low_u64_be(10), &input, None).is_none());
+}
+
+frame_support::parameter_types! {
+	pub IdentityAddress: H160 = H160::from_low_u64_be(0x400);
+	pub ShadowAddress: H160 = H160::from_low_u64_be(2);
+}
+
+#[test]
+fn extended_precompiles_prefer_extra() {
+	type Extended = ExtendedPrecompiles<
+		IstanbulPrecompiles,
+		ExtendedPrecompiles<AtAddress<Identity, IdentityAddress>, AtAddress<Identity, ShadowAddress>>,
+	>;
+	let input = [0x42u8; 4];
+	let identity = Some(Ok((ExitSucceed::Returned, input.to_vec(), 18)));
+
+	assert_eq!(Extended::execute(H160::from_low_u64_be(0x400), &input, None), identity);
+	assert_eq!(Extended::execute(H160::from_low_u64_be(2), &input, None), identity);
+	assert_eq!(
+		Extended::execute(H160::from_low_u64_be(1), &input, None),
+		IstanbulPrecompiles::execute(H160::from_low_u64_be(1), &input, None),
+	);
+	assert!(Extended::execute(H160::from_low_u64_be(0x401), &input, None).is_none());
+}
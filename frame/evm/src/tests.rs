@@ -20,6 +20,7 @@ impl_outer_origin! {
 impl_outer_dispatch! {
 	pub enum OuterCall for Test where origin: Origin {
 		self::EVM,
+		pallet_balances::Balances,
 	}
 }
 
@@ -81,6 +82,10 @@ impl pallet_timestamp::Trait for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub DispatchAddress: H160 = H160::from_low_u64_be(0x400);
+}
+
 /// Fixed gas price of `0`.
 pub struct FixedGasPrice;
 impl FeeCalculator for FixedGasPrice {
@@ -100,7 +105,11 @@ impl Trait for Test {
 	type Currency = Balances;
 
 	type Event = Event<Test>;
-	type Precompiles = ();
+	type Precompiles = ExtendedPrecompiles<
+		IstanbulPrecompiles,
+		AtAddress<Dispatch<Test, OuterCall>, DispatchAddress>,
+	>;
+	type GasToWeight = ();
 	type ChainId = SystemChainId;
 }
 
@@ -134,6 +143,20 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			],
 		}
 	);
+	accounts.insert(
+		H160::from_str("1000000000000000000000000000000000000003").unwrap(),
+		GenesisAccount {
+			nonce: U256::from(1),
+			balance: U256::from(1000000),
+			storage: Default::default(),
+			code: vec![
+				0x60, 0x00, // PUSH1 0
+				0x60, 0x00, // PUSH1 0
+				0xa0, // LOG0
+				0x00, // STOP
+			],
+		}
+	);
 
 	pallet_balances::GenesisConfig::<Test>::default().assimilate_storage(&mut t).unwrap();
 	GenesisConfig { accounts }.assimilate_storage::<Test>(&mut t).unwrap();
@@ -166,3 +189,138 @@ fn fail_call_return_ok() {
 		));
 	});
 }
+
+#[test]
+fn execute_call_returns_logs() {
+	new_test_ext().execute_with(|| {
+		let target = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let info = EVM::execute_call(
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::default(),
+			1000000,
+			None,
+			None,
+			true,
+		).unwrap();
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(info.logs, vec![Log { address: target, topics: Vec::new(), data: Vec::new() }]);
+		assert!(info.used_gas > U256::zero());
+
+		let info = EVM::execute_call(
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::default(),
+			1000000,
+			None,
+			None,
+			false,
+		).unwrap();
+
+		assert!(info.logs.is_empty());
+	});
+}
+
+#[test]
+fn dispatch_precompile_transfers_on_behalf_of_caller() {
+	new_test_ext().execute_with(|| {
+		let source = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let source_account = HashedAddressMapping::<Blake2Hasher>::into_account_id(source);
+		let dest = AccountId32::from([9u8; 32]);
+		let call = OuterCall::Balances(pallet_balances::Call::transfer(dest.clone(), 1000));
+
+		let info = EVM::execute_call(
+			source,
+			DispatchAddress::get(),
+			call.encode(),
+			U256::default(),
+			100_000_000,
+			None,
+			None,
+			true,
+		).unwrap();
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(Balances::free_balance(&source_account), 999_000);
+		assert_eq!(Balances::free_balance(&dest), 1000);
+	});
+}
+
+#[test]
+fn dispatch_precompile_failure_reverts_dispatch() {
+	new_test_ext().execute_with(|| {
+		let source = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let source_account = HashedAddressMapping::<Blake2Hasher>::into_account_id(source);
+		let dest = AccountId32::from([9u8; 32]);
+		let transfer = OuterCall::Balances(pallet_balances::Call::transfer(dest.clone(), 1000));
+		let overdraft = OuterCall::Balances(pallet_balances::Call::transfer(dest.clone(), 2_000_000));
+
+		let info = EVM::execute_call(
+			source,
+			DispatchAddress::get(),
+			overdraft.encode(),
+			U256::default(),
+			100_000_000,
+			None,
+			None,
+			true,
+		).unwrap();
+
+		assert_eq!(
+			info.exit_reason,
+			ExitReason::Error(ExitError::Other("Deferred dispatch failed")),
+		);
+		assert_eq!(Balances::free_balance(&source_account), 1_000_000);
+		assert_eq!(Balances::free_balance(&dest), 0);
+
+		// Not dispatched without applying the state.
+		let info = EVM::execute_call(
+			source,
+			DispatchAddress::get(),
+			transfer.encode(),
+			U256::default(),
+			100_000_000,
+			None,
+			None,
+			false,
+		).unwrap();
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(Balances::free_balance(&dest), 0);
+	});
+}
+
+#[test]
+fn dispatch_precompile_failure_reverts_evm_state() {
+	new_test_ext().execute_with(|| {
+		let source = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let source_account = HashedAddressMapping::<Blake2Hasher>::into_account_id(source);
+		let dispatch_account = HashedAddressMapping::<Blake2Hasher>::into_account_id(DispatchAddress::get());
+		let dest = AccountId32::from([9u8; 32]);
+		let overdraft = OuterCall::Balances(pallet_balances::Call::transfer(dest.clone(), 2_000_000));
+
+		// The value sent to the precompile is an EVM state change reverted with the dispatch.
+		let info = EVM::execute_call(
+			source,
+			DispatchAddress::get(),
+			overdraft.encode(),
+			U256::from(1000),
+			100_000_000,
+			None,
+			None,
+			true,
+		).unwrap();
+
+		assert_eq!(
+			info.exit_reason,
+			ExitReason::Error(ExitError::Other("Deferred dispatch failed")),
+		);
+		assert!(info.logs.is_empty());
+		assert_eq!(Balances::free_balance(&source_account), 1_000_000);
+		assert_eq!(Balances::free_balance(&dispatch_account), 0);
+		assert_eq!(Balances::free_balance(&dest), 0);
+	});
+}
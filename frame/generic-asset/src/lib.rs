@@ -167,7 +167,7 @@ use frame_support::{
 	traits::{
 		Currency, ExistenceRequirement, Imbalance, LockIdentifier, LockableCurrency,
 		ReservableCurrency, SignedImbalance, WithdrawReason, WithdrawReasons, TryDrop,
-		BalanceStatus,
+		BalanceStatus, fungibles,
 	},
 	Parameter, StorageMap,
 };
@@ -341,6 +341,8 @@ decl_error! {
 		InsufficientBalance,
 		/// The account liquidity restrictions prevent withdrawal.
 		LiquidityRestrictions,
+		/// The transfer would leave the source account without any of the asset.
+		WouldDie,
 	}
 }
 
@@ -898,6 +900,169 @@ impl<T: Trait> Module<T> {
 	}
 }
 
+impl<T: Trait> fungibles::Inspect<T::AccountId> for Module<T> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn total_issuance(asset: T::AssetId) -> T::Balance {
+		<TotalIssuance<T>>::get(asset)
+	}
+
+	fn minimum_balance(_asset: T::AssetId) -> T::Balance {
+		Zero::zero()
+	}
+
+	fn balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::total_balance(&asset, who)
+	}
+
+	fn reducible_balance(asset: T::AssetId, who: &T::AccountId, _keep_alive: bool) -> T::Balance {
+		let free = Self::free_balance(&asset, who);
+		if asset != Self::staking_asset_id() {
+			return free
+		}
+		let locked = Self::locks(who).into_iter().fold(Zero::zero(), |a: T::Balance, l| a.max(l.amount));
+		free.saturating_sub(locked)
+	}
+
+	fn can_deposit(asset: T::AssetId, who: &T::AccountId, amount: T::Balance)
+		-> fungibles::DepositConsequence
+	{
+		if !<TotalIssuance<T>>::contains_key(asset) {
+			return fungibles::DepositConsequence::UnknownAsset
+		}
+		if <TotalIssuance<T>>::get(asset).checked_add(&amount).is_none() ||
+			Self::total_balance(&asset, who).checked_add(&amount).is_none()
+		{
+			return fungibles::DepositConsequence::Overflow
+		}
+		fungibles::DepositConsequence::Success
+	}
+
+	fn can_withdraw(asset: T::AssetId, who: &T::AccountId, amount: T::Balance)
+		-> fungibles::WithdrawConsequence<T::Balance>
+	{
+		if !<TotalIssuance<T>>::contains_key(asset) {
+			return fungibles::WithdrawConsequence::UnknownAsset
+		}
+		if <TotalIssuance<T>>::get(asset).checked_sub(&amount).is_none() {
+			return fungibles::WithdrawConsequence::Underflow
+		}
+		let new_balance = match Self::free_balance(&asset, who).checked_sub(&amount) {
+			Some(b) => b,
+			None => return fungibles::WithdrawConsequence::NoFunds,
+		};
+		if Self::ensure_can_withdraw(&asset, who, amount, WithdrawReasons::all(), new_balance).is_err() {
+			return fungibles::WithdrawConsequence::Frozen
+		}
+		fungibles::WithdrawConsequence::Success
+	}
+}
+
+impl<T: Trait> fungibles::Mutate<T::AccountId> for Module<T> {
+	fn mint_into(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		<Self as fungibles::Inspect<T::AccountId>>::can_deposit(asset, who, amount).into_result()?;
+		<TotalIssuance<T>>::mutate(asset, |issued| *issued += amount);
+		<FreeBalance<T>>::mutate(asset, who, |balance| *balance += amount);
+		Ok(())
+	}
+
+	fn burn_from(asset: T::AssetId, who: &T::AccountId, amount: T::Balance)
+		-> result::Result<T::Balance, DispatchError>
+	{
+		let actual = amount.min(Self::free_balance(&asset, who));
+		<Self as fungibles::Inspect<T::AccountId>>::can_withdraw(asset, who, actual).into_result()?;
+		<TotalIssuance<T>>::mutate(asset, |issued| *issued -= actual);
+		<FreeBalance<T>>::mutate(asset, who, |balance| *balance -= actual);
+		Ok(actual)
+	}
+}
+
+impl<T: Trait> fungibles::Transfer<T::AccountId> for Module<T> {
+	fn transfer(
+		asset: T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: T::Balance,
+		keep_alive: bool,
+	) -> result::Result<T::Balance, DispatchError> {
+		// There is no minimum balance, so the source is only kept alive by not emptying it.
+		if keep_alive && source != dest {
+			let remaining = Self::total_balance(&asset, source).saturating_sub(amount);
+			ensure!(!remaining.is_zero(), Error::<T>::WouldDie);
+		}
+		Self::make_transfer(&asset, source, dest, amount).map(|_| amount)
+	}
+}
+
+impl<T: Trait> fungibles::Unbalanced<T::AccountId> for Module<T> {
+	fn set_balance(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let free = amount.checked_sub(&Self::reserved_balance(&asset, who))
+			.ok_or(Error::<T>::InsufficientBalance)?;
+		Self::set_free_balance(&asset, who, free);
+		Ok(())
+	}
+
+	fn set_total_issuance(asset: T::AssetId, amount: T::Balance) {
+		<TotalIssuance<T>>::insert(asset, amount);
+	}
+}
+
+impl<T: Trait> fungibles::InspectHold<T::AccountId> for Module<T> {
+	fn balance_on_hold(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::reserved_balance(&asset, who)
+	}
+
+	fn can_hold(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> bool {
+		Self::free_balance(&asset, who)
+			.checked_sub(&amount)
+			.map_or(false, |new_balance|
+				Self::ensure_can_withdraw(&asset, who, amount, WithdrawReason::Reserve.into(), new_balance).is_ok()
+			)
+	}
+}
+
+impl<T: Trait> fungibles::MutateHold<T::AccountId> for Module<T> {
+	fn hold(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		Self::reserve(&asset, who, amount)
+	}
+
+	fn release(asset: T::AssetId, who: &T::AccountId, amount: T::Balance, best_effort: bool)
+		-> result::Result<T::Balance, DispatchError>
+	{
+		ensure!(
+			best_effort || Self::reserved_balance(&asset, who) >= amount,
+			Error::<T>::InsufficientBalance
+		);
+		Ok(amount - Self::unreserve(&asset, who, amount))
+	}
+
+	fn transfer_held(
+		asset: T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: T::Balance,
+		best_effort: bool,
+		on_hold: bool,
+	) -> result::Result<T::Balance, DispatchError> {
+		ensure!(
+			best_effort || Self::reserved_balance(&asset, source) >= amount,
+			Error::<T>::InsufficientBalance
+		);
+		let status = if on_hold { BalanceStatus::Reserved } else { BalanceStatus::Free };
+		Ok(amount - Self::repatriate_reserved(&asset, source, dest, amount, status))
+	}
+
+	fn slash_held(asset: T::AssetId, who: &T::AccountId, amount: T::Balance)
+		-> result::Result<T::Balance, DispatchError>
+	{
+		let remaining = Self::slash_reserved(&asset, who, amount).unwrap_or_else(Zero::zero);
+		let slashed = amount - remaining;
+		<TotalIssuance<T>>::mutate(asset, |issued| *issued = issued.saturating_sub(slashed));
+		Ok(slashed)
+	}
+}
+
 pub trait AssetIdProvider {
 	type AssetId;
 	fn asset_id() -> Self::AssetId;
@@ -1213,3 +1213,48 @@ fn burn_should_raise_event() {
 		},
 	);
 }
+
+frame_support::parameter_types! {
+	pub const SpendingAsset: u32 = 16001;
+}
+
+type SpendingItem = fungibles::ItemOf<GenericAsset, SpendingAsset, u64>;
+
+#[test]
+fn item_of_should_act_as_reservable_currency() {
+	ExtBuilder::default().free_balance((16001, 1, 100)).build().execute_with(|| {
+		assert_eq!(SpendingItem::free_balance(&1), 100);
+		assert_eq!(SpendingItem::total_issuance(), 100);
+
+		assert_ok!(SpendingItem::reserve(&1, 40));
+		assert_eq!(SpendingItem::free_balance(&1), 60);
+		assert_eq!(SpendingItem::reserved_balance(&1), 40);
+		assert_eq!(SpendingItem::total_balance(&1), 100);
+
+		assert_eq!(SpendingItem::repatriate_reserved(&1, &2, 10, BalanceStatus::Free), Ok(0));
+		assert_eq!(GenericAsset::free_balance(&16001, &2), 10);
+
+		let (slashed, remaining) = SpendingItem::slash_reserved(&1, 50);
+		assert_eq!((slashed.peek(), remaining), (30, 20));
+		assert_eq!(SpendingItem::total_issuance(), 100);
+		drop(slashed);
+		assert_eq!(SpendingItem::total_issuance(), 70);
+		assert_eq!(GenericAsset::reserved_balance(&16001, &1), 0);
+	});
+}
+
+#[test]
+fn fungibles_transfer_should_keep_source_alive() {
+	ExtBuilder::default().free_balance((16001, 1, 100)).build().execute_with(|| {
+		use fungibles::Transfer;
+
+		assert_noop!(
+			<GenericAsset as Transfer<u64>>::transfer(16001, &1, &2, 100, true),
+			Error::<Test>::WouldDie,
+		);
+		assert_eq!(<GenericAsset as Transfer<u64>>::transfer(16001, &1, &2, 99, true), Ok(99));
+		assert_eq!(<GenericAsset as Transfer<u64>>::transfer(16001, &1, &2, 1, false), Ok(1));
+		assert_eq!(GenericAsset::free_balance(&16001, &1), 0);
+		assert_eq!(GenericAsset::free_balance(&16001, &2), 100);
+	});
+}
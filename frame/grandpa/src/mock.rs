@@ -28,6 +28,7 @@ use frame_support::{
 	weights::Weight,
 };
 use pallet_staking::EraIndex;
+use frame_election_provider_support::onchain;
 use sp_core::{crypto::KeyTypeId, H256};
 use sp_finality_grandpa::{RoundNumber, SetId, GRANDPA_ENGINE_ID};
 use sp_io;
@@ -200,8 +201,6 @@ parameter_types! {
 	pub const AttestationPeriod: u64 = 100;
 	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
 	pub const MaxNominatorRewardedPerValidator: u32 = 64;
-	pub const ElectionLookahead: u64 = 0;
-	pub const StakingUnsignedPriority: u64 = u64::max_value() / 2;
 }
 
 pub struct CurrencyToVoteHandler;
@@ -234,14 +233,18 @@ impl staking::Trait for Test {
 	type RewardCurve = RewardCurve;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type NextNewSession = Session;
-	type ElectionLookahead = ElectionLookahead;
-	type Call = Call;
-	type UnsignedPriority = StakingUnsignedPriority;
-	type MaxIterations = ();
-	type MinSolutionScoreBump = ();
+	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type OnStakerSlash = ();
 	type WeightInfo = ();
 }
 
+impl onchain::Config for Test {
+	type AccountId = u64;
+	type BlockNumber = u64;
+	type Accuracy = Perbill;
+	type DataProvider = Staking;
+}
+
 parameter_types! {
 	pub OffencesWeightSoftLimit: Weight = Perbill::from_percent(60) * MaximumBlockWeight::get();
 }
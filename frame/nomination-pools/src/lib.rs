@@ -0,0 +1,766 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Nomination Pools Pallet
+//!
+//! - [`nomination_pools::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! The Nomination Pools pallet lets accounts stake together through a _pool_, so that holders of
+//! small balances can take part in staking without meeting its minimum bond or claiming their
+//! rewards from each validator themselves.
+//!
+//! Each pool has two accounts derived from `Trait::ModuleId`: the _bonded_ account, the stash
+//! holding the funds of the pool in the staking system, and the _reward_ account, its controller,
+//! into which the staking rewards of the pool are paid. The pool nominates through
+//! `Trait::Staking`.
+//!
+//! ### Points
+//!
+//! Members hold _points_ in the bonded pool. Points are issued one to one with the balance of a
+//! new pool, and then in proportion to the active stake of the pool, so that a slash of the
+//! bonded funds of the pool reduces the balance of every point alike.
+//!
+//! ### Rewards
+//!
+//! The rewards of the pool are shared between its members in proportion to their points. They
+//! are claimed with `claim_payout`, and automatically whenever the points of a member change.
+//!
+//! ### Unbonding
+//!
+//! Unbonded funds go into the _unbonding pool_ of the era at which they unlock, in which the
+//! member is issued points in turn, so that slashes of the unlocking funds of the pool are
+//! passed on to the members unbonding in that era. Unbonding pools of eras which unlocked more
+//! than `Trait::PostUnbondingPoolsWindow` eras ago are merged into a single pool.
+//!
+//! ### Depositor
+//!
+//! The account creating a pool is its _depositor_ and must keep at least `MinCreateBond` bonded
+//! in it. It can only leave the pool once it is its only member, after which the pool is
+//! destroyed.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `create` - Create a pool, bonding the deposit of its depositor.
+//! - `join` - Join a pool, bonding funds in it.
+//! - `claim_payout` - Claim the pending rewards of the sender.
+//! - `unbond` - Unbond some of the points of the sender.
+//! - `pool_withdraw_unbonded` - Withdraw the unlocked funds of a pool from the staking system.
+//! - `withdraw_unbonded` - Withdraw the unlocked funds of the sender.
+//!
+//! #### Pool Roles
+//!
+//! - `nominate` - Nominate validators with a pool. Its root or nominator only.
+//! - `set_state` - Open or block a pool. Its root only.
+//!
+//! #### Root
+//!
+//! - `set_configs` - Set the minimum bonds to join and create a pool.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::{prelude::*, collections::btree_map::BTreeMap};
+use sp_runtime::{
+	ModuleId, DispatchError, DispatchResult, FixedU128, FixedPointNumber, SaturatedConversion,
+	traits::{AccountIdConversion, StaticLookup, Saturating, Zero},
+};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, transactional,
+	weights::Weight,
+	traits::{Currency, ExistenceRequirement, Get},
+};
+use frame_system::{ensure_signed, ensure_root};
+use sp_staking::{EraIndex, OnStakerSlash, StakingInterface};
+
+mod types;
+mod default_weight;
+pub use types::{
+	AccountType, BondedPool, PoolMember, PoolRoles, PoolState, RewardPool, SubPools, UnbondPool,
+	balance_to_point, point_to_balance,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The identifier of a pool.
+pub type PoolId = u32;
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// A bonded pool of the runtime.
+pub type BondedPoolOf<T> = BondedPool<<T as frame_system::Trait>::AccountId, BalanceOf<T>>;
+
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn join() -> Weight;
+	fn claim_payout() -> Weight;
+	fn unbond() -> Weight;
+	fn pool_withdraw_unbonded(s: u32, ) -> Weight;
+	fn withdraw_unbonded(s: u32, ) -> Weight;
+	fn nominate(n: u32, ) -> Weight;
+	fn set_state() -> Weight;
+	fn set_configs() -> Weight;
+}
+
+pub trait Trait: frame_system::Trait {
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Currency type for this module.
+	type Currency: Currency<Self::AccountId>;
+
+	/// The staking system the pools bond their funds in.
+	type Staking: StakingInterface<Balance = BalanceOf<Self>, AccountId = Self::AccountId>;
+
+	/// The pallet id, from which the accounts of the pools are derived.
+	type ModuleId: Get<ModuleId>;
+
+	/// The number of eras after their funds unlock for which unbonding pools are kept apart,
+	/// before being merged into a single pool.
+	type PostUnbondingPoolsWindow: Get<EraIndex>;
+
+	/// The maximum number of eras a member can be unbonding in at the same time.
+	type MaxUnbonding: Get<u32>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as NominationPools {
+		/// The minimum amount to bond to join a pool.
+		pub MinJoinBond get(fn min_join_bond) config(): BalanceOf<T>;
+
+		/// The minimum amount to bond to create a pool, which the depositor of a pool must keep
+		/// bonded in it as long as it has other members.
+		pub MinCreateBond get(fn min_create_bond) config(): BalanceOf<T>;
+
+		/// The identifier of the last pool created.
+		pub LastPoolId get(fn last_pool_id): PoolId;
+
+		/// The bonded pools.
+		///
+		/// TWOX-NOTE: SAFE as pool ids are not under an attacker’s control.
+		pub BondedPools get(fn bonded_pools):
+			map hasher(twox_64_concat) PoolId => Option<BondedPoolOf<T>>;
+
+		/// The reward records of the pools.
+		pub RewardPools get(fn reward_pools):
+			map hasher(twox_64_concat) PoolId => Option<RewardPool<BalanceOf<T>>>;
+
+		/// The unbonding pools of the pools.
+		pub SubPoolsStorage get(fn sub_pools):
+			map hasher(twox_64_concat) PoolId => Option<SubPools<BalanceOf<T>>>;
+
+		/// The members of the pools. An account is a member of at most one pool.
+		pub PoolMembers get(fn pool_members):
+			map hasher(twox_64_concat) T::AccountId => Option<PoolMember<BalanceOf<T>>>;
+
+		/// The pool of each bonded account.
+		pub ReversePoolIdLookup get(fn reverse_pool_id):
+			map hasher(twox_64_concat) T::AccountId => Option<PoolId>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		<T as frame_system::Trait>::AccountId,
+		Balance = BalanceOf<T>,
+	{
+		/// A pool has been created. [depositor, pool_id]
+		Created(AccountId, PoolId),
+		/// A member has bonded funds in a pool. [member, pool_id, amount]
+		Bonded(AccountId, PoolId, Balance),
+		/// A member has been paid out its rewards. [member, pool_id, amount]
+		PaidOut(AccountId, PoolId, Balance),
+		/// A member has unbonded funds from a pool, unlocking at an era. [member, pool_id, amount,
+		/// era]
+		Unbonded(AccountId, PoolId, Balance, EraIndex),
+		/// A member has withdrawn its unlocked funds from a pool. [member, pool_id, amount]
+		Withdrawn(AccountId, PoolId, Balance),
+		/// A pool has been destroyed. [pool_id]
+		Destroyed(PoolId),
+		/// The state of a pool has changed. [pool_id, state]
+		StateChanged(PoolId, PoolState),
+		/// The bonded funds of a pool have been slashed. [pool_id, amount]
+		PoolSlashed(PoolId, Balance),
+		/// The funds of a pool unlocking at an era have been slashed. [pool_id, era, amount]
+		UnbondingPoolSlashed(PoolId, EraIndex, Balance),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The pool does not exist.
+		PoolNotFound,
+		/// The account is not a member of any pool.
+		PoolMemberNotFound,
+		/// The account is already a member of a pool.
+		AccountBelongsToOtherPool,
+		/// The pool cannot be joined.
+		NotOpen,
+		/// The amount bonded is below the minimum.
+		MinimumBondNotMet,
+		/// The member does not have that many points.
+		NotEnoughPoints,
+		/// The bonded funds of the pool have been slashed to zero.
+		PoolSlashedToZero,
+		/// The member is unbonding in too many eras already.
+		MaxUnbondingLimit,
+		/// None of the unbonding funds of the member are unlocked yet.
+		CannotWithdrawAny,
+		/// The depositor cannot leave the pool while it has other members.
+		NotOnlyMember,
+		/// The sender is not the nominator nor the root of the pool.
+		NotNominator,
+		/// The sender is not the root of the pool.
+		NotRoot,
+		/// The pool cannot be put in, or taken out of, the destroying state.
+		CanNotChangeState,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The pallet id, from which the accounts of the pools are derived.
+		const ModuleId: ModuleId = T::ModuleId::get();
+
+		/// The number of eras after their funds unlock for which unbonding pools are kept apart.
+		const PostUnbondingPoolsWindow: EraIndex = T::PostUnbondingPoolsWindow::get();
+
+		/// The maximum number of eras a member can be unbonding in at the same time.
+		const MaxUnbonding: u32 = T::MaxUnbonding::get();
+
+		fn deposit_event() = default;
+
+		/// Create a pool, bonding `amount` of the sender, who becomes its depositor.
+		///
+		/// The dispatch origin of this call must be _Signed_. The sender also pays the existential
+		/// deposit of the reward account of the pool.
+		///
+		/// - `amount`: The amount to bond, at least `MinCreateBond`.
+		/// - `root`: The account which can change the state of the pool and nominate with it.
+		/// - `nominator`: The account which can nominate with the pool.
+		///
+		/// Emits `Created` and `Bonded`.
+		///
+		/// # <weight>
+		/// - Db reads: `PoolMembers`, `MinCreateBond`, `MinJoinBond`, `LastPoolId`, staking bond
+		/// - Db writes: `LastPoolId`, `BondedPools`, `RewardPools`, `PoolMembers`,
+		///   `ReversePoolIdLookup`, staking bond, 3 balances
+		/// # </weight>
+		#[weight = T::WeightInfo::create()]
+		#[transactional]
+		fn create(origin,
+			#[compact] amount: BalanceOf<T>,
+			root: <T::Lookup as StaticLookup>::Source,
+			nominator: <T::Lookup as StaticLookup>::Source,
+		) {
+			let who = ensure_signed(origin)?;
+			let root = T::Lookup::lookup(root)?;
+			let nominator = T::Lookup::lookup(nominator)?;
+			ensure!(!PoolMembers::<T>::contains_key(&who), Error::<T>::AccountBelongsToOtherPool);
+			ensure!(
+				amount >= Self::min_create_bond()
+					.max(Self::min_join_bond())
+					.max(T::Staking::minimum_bond()),
+				Error::<T>::MinimumBondNotMet,
+			);
+
+			let pool_id = Self::last_pool_id().saturating_add(1);
+			LastPoolId::put(pool_id);
+			let bonded_account = Self::create_bonded_account(pool_id);
+			let reward_account = Self::create_reward_account(pool_id);
+
+			T::Currency::transfer(
+				&who,
+				&reward_account,
+				T::Currency::minimum_balance(),
+				ExistenceRequirement::AllowDeath,
+			)?;
+			T::Currency::transfer(&who, &bonded_account, amount, ExistenceRequirement::AllowDeath)?;
+			T::Staking::bond(bonded_account.clone(), reward_account, amount)?;
+
+			BondedPools::<T>::insert(pool_id, BondedPool {
+				points: amount,
+				state: PoolState::Open,
+				member_counter: 1,
+				roles: PoolRoles { depositor: who.clone(), root, nominator },
+			});
+			RewardPools::<T>::insert(pool_id, RewardPool::default());
+			PoolMembers::<T>::insert(&who, PoolMember {
+				pool_id,
+				points: amount,
+				last_recorded_reward_counter: Zero::zero(),
+				unbonding_eras: BTreeMap::new(),
+			});
+			ReversePoolIdLookup::<T>::insert(&bonded_account, pool_id);
+
+			Self::deposit_event(RawEvent::Created(who.clone(), pool_id));
+			Self::deposit_event(RawEvent::Bonded(who, pool_id, amount));
+		}
+
+		/// Join the pool `pool_id`, bonding `amount` of the sender in it.
+		///
+		/// The dispatch origin of this call must be _Signed_ by an account which is not a member
+		/// of any pool.
+		///
+		/// - `amount`: The amount to bond, at least `MinJoinBond`.
+		/// - `pool_id`: The pool to join, which must be open.
+		///
+		/// Emits `Bonded`.
+		///
+		/// # <weight>
+		/// - Db reads: `MinJoinBond`, `PoolMembers`, `BondedPools`, `RewardPools`, staking bond,
+		///   2 balances
+		/// - Db writes: `BondedPools`, `RewardPools`, `PoolMembers`, staking bond, 2 balances
+		/// # </weight>
+		#[weight = T::WeightInfo::join()]
+		#[transactional]
+		fn join(origin, #[compact] amount: BalanceOf<T>, pool_id: PoolId) {
+			let who = ensure_signed(origin)?;
+			ensure!(amount >= Self::min_join_bond(), Error::<T>::MinimumBondNotMet);
+			ensure!(!PoolMembers::<T>::contains_key(&who), Error::<T>::AccountBelongsToOtherPool);
+			let mut bonded_pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(bonded_pool.state == PoolState::Open, Error::<T>::NotOpen);
+			let mut reward_pool = Self::reward_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+			let bonded_balance = Self::bonded_balance(pool_id);
+			ensure!(
+				!bonded_balance.is_zero() || bonded_pool.points.is_zero(),
+				Error::<T>::PoolSlashedToZero,
+			);
+			let points = balance_to_point(bonded_balance, bonded_pool.points, amount);
+
+			// rewards earned so far are owed to the current points only.
+			reward_pool.update_records(Self::reward_balance(pool_id), bonded_pool.points);
+
+			let bonded_account = Self::create_bonded_account(pool_id);
+			T::Currency::transfer(&who, &bonded_account, amount, ExistenceRequirement::AllowDeath)?;
+			T::Staking::bond_extra(bonded_account, amount)?;
+
+			bonded_pool.points = bonded_pool.points.saturating_add(points);
+			bonded_pool.member_counter = bonded_pool.member_counter.saturating_add(1);
+			PoolMembers::<T>::insert(&who, PoolMember {
+				pool_id,
+				points,
+				last_recorded_reward_counter: reward_pool.last_recorded_reward_counter,
+				unbonding_eras: BTreeMap::new(),
+			});
+			BondedPools::<T>::insert(pool_id, bonded_pool);
+			RewardPools::<T>::insert(pool_id, reward_pool);
+
+			Self::deposit_event(RawEvent::Bonded(who, pool_id, amount));
+		}
+
+		/// Claim the pending rewards of the sender.
+		///
+		/// The dispatch origin of this call must be _Signed_ by a member of a pool.
+		///
+		/// Emits `PaidOut` if any reward was pending.
+		///
+		/// # <weight>
+		/// - Db reads: `PoolMembers`, `BondedPools`, `RewardPools`, 2 balances
+		/// - Db writes: `PoolMembers`, `RewardPools`, 2 balances
+		/// # </weight>
+		#[weight = T::WeightInfo::claim_payout()]
+		fn claim_payout(origin) {
+			let who = ensure_signed(origin)?;
+			let mut member = Self::pool_members(&who).ok_or(Error::<T>::PoolMemberNotFound)?;
+			let bonded_pool = Self::bonded_pools(member.pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let mut reward_pool = Self::reward_pools(member.pool_id)
+				.ok_or(Error::<T>::PoolNotFound)?;
+
+			Self::do_reward_payout(&who, &mut member, &bonded_pool, &mut reward_pool)?;
+
+			RewardPools::<T>::insert(member.pool_id, reward_pool);
+			PoolMembers::<T>::insert(&who, member);
+		}
+
+		/// Unbond `unbonding_points` of the points of the sender, paying out its pending rewards.
+		///
+		/// The funds backing the points move into the unbonding pool of the era at which they
+		/// unlock, from which they can be withdrawn with `withdraw_unbonded` once unlocked.
+		///
+		/// The dispatch origin of this call must be _Signed_ by a member of a pool. The points
+		/// left to the member must be worth at least `MinJoinBond`, or `MinCreateBond` for the
+		/// depositor of the pool, unless it unbonds all of them. The depositor can only unbond all
+		/// of its points once it is the only member of the pool, which then starts being destroyed.
+		///
+		/// Emits `Unbonded`.
+		///
+		/// # <weight>
+		/// - Db reads: `PoolMembers`, `BondedPools`, `RewardPools`, `SubPoolsStorage`,
+		///   `MinJoinBond`, `MinCreateBond`, staking bond, current era, 2 balances
+		/// - Db writes: `PoolMembers`, `BondedPools`, `RewardPools`, `SubPoolsStorage`,
+		///   staking bond, 2 balances
+		/// # </weight>
+		#[weight = T::WeightInfo::unbond()]
+		#[transactional]
+		fn unbond(origin, #[compact] unbonding_points: BalanceOf<T>) {
+			let who = ensure_signed(origin)?;
+			let mut member = Self::pool_members(&who).ok_or(Error::<T>::PoolMemberNotFound)?;
+			ensure!(
+				!unbonding_points.is_zero() && unbonding_points <= member.points,
+				Error::<T>::NotEnoughPoints,
+			);
+			let pool_id = member.pool_id;
+			let mut bonded_pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let mut reward_pool = Self::reward_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+
+			let bonded_balance = Self::bonded_balance(pool_id);
+			let remaining_points = member.points - unbonding_points;
+			let is_depositor = who == bonded_pool.roles.depositor;
+			if remaining_points.is_zero() {
+				ensure!(!is_depositor || bonded_pool.member_counter == 1, Error::<T>::NotOnlyMember);
+			} else {
+				let min_bond = if is_depositor {
+					Self::min_create_bond()
+				} else {
+					Self::min_join_bond()
+				};
+				ensure!(
+					point_to_balance(bonded_balance, bonded_pool.points, remaining_points) >= min_bond,
+					Error::<T>::MinimumBondNotMet,
+				);
+			}
+
+			let current_era = T::Staking::current_era();
+			let unbond_era = current_era.saturating_add(T::Staking::bonding_duration());
+			ensure!(
+				member.unbonding_eras.contains_key(&unbond_era) ||
+					(member.unbonding_eras.len() as u32) < T::MaxUnbonding::get(),
+				Error::<T>::MaxUnbondingLimit,
+			);
+
+			reward_pool.update_records(Self::reward_balance(pool_id), bonded_pool.points);
+			Self::do_reward_payout(&who, &mut member, &bonded_pool, &mut reward_pool)?;
+
+			let balance = point_to_balance(bonded_balance, bonded_pool.points, unbonding_points);
+			T::Staking::unbond(Self::create_reward_account(pool_id), balance)?;
+
+			let mut sub_pools = Self::sub_pools(pool_id).unwrap_or_default();
+			sub_pools.maybe_merge_pools(current_era.saturating_sub(T::PostUnbondingPoolsWindow::get()));
+			let points = sub_pools.with_era.entry(unbond_era).or_default().issue(balance);
+			let unbonding = member.unbonding_eras.entry(unbond_era).or_insert_with(Zero::zero);
+			*unbonding = unbonding.saturating_add(points);
+
+			member.points = remaining_points;
+			bonded_pool.points = bonded_pool.points.saturating_sub(unbonding_points);
+			if is_depositor && remaining_points.is_zero() {
+				bonded_pool.state = PoolState::Destroying;
+				Self::deposit_event(RawEvent::StateChanged(pool_id, PoolState::Destroying));
+			}
+
+			SubPoolsStorage::<T>::insert(pool_id, sub_pools);
+			BondedPools::<T>::insert(pool_id, bonded_pool);
+			RewardPools::<T>::insert(pool_id, reward_pool);
+			PoolMembers::<T>::insert(&who, member);
+
+			Self::deposit_event(RawEvent::Unbonded(who, pool_id, balance, unbond_era));
+		}
+
+		/// Withdraw the unlocked funds of the pool `pool_id` from the staking system, freeing its
+		/// unlocking chunks there.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `num_slashing_spans`: The number of slashing spans of the bonded account of the pool.
+		///
+		/// # <weight>
+		/// - Db reads: `BondedPools`, staking bond, current era
+		/// - Db writes: staking bond
+		/// # </weight>
+		#[weight = T::WeightInfo::pool_withdraw_unbonded(*num_slashing_spans)]
+		fn pool_withdraw_unbonded(origin, pool_id: PoolId, num_slashing_spans: u32) {
+			ensure_signed(origin)?;
+			ensure!(BondedPools::<T>::contains_key(pool_id), Error::<T>::PoolNotFound);
+			T::Staking::withdraw_unbonded(Self::create_reward_account(pool_id), num_slashing_spans)?;
+		}
+
+		/// Withdraw the unlocked funds of the sender from the unbonding pools of its pool.
+		///
+		/// A member with no points nor unbonding funds left leaves its pool. When the depositor
+		/// leaves its pool, the pool is destroyed and the funds left in its accounts are paid to
+		/// the depositor.
+		///
+		/// The dispatch origin of this call must be _Signed_ by a member of a pool.
+		///
+		/// - `num_slashing_spans`: The number of slashing spans of the bonded account of the pool.
+		///
+		/// Emits `Withdrawn`, and `Destroyed` if the pool is destroyed.
+		///
+		/// # <weight>
+		/// - Db reads: `PoolMembers`, `BondedPools`, `SubPoolsStorage`, staking bond,
+		///   current era, 2 balances
+		/// - Db writes: `PoolMembers`, `BondedPools`, `SubPoolsStorage`, staking bond, 2 balances
+		/// # </weight>
+		#[weight = T::WeightInfo::withdraw_unbonded(*num_slashing_spans)]
+		#[transactional]
+		fn withdraw_unbonded(origin, num_slashing_spans: u32) {
+			let who = ensure_signed(origin)?;
+			let mut member = Self::pool_members(&who).ok_or(Error::<T>::PoolMemberNotFound)?;
+			let pool_id = member.pool_id;
+			let mut bonded_pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let mut sub_pools = Self::sub_pools(pool_id).ok_or(Error::<T>::CannotWithdrawAny)?;
+
+			let current_era = T::Staking::current_era();
+			let still_unbonding = member.unbonding_eras.split_off(&current_era.saturating_add(1));
+			let withdrawn_eras = sp_std::mem::replace(&mut member.unbonding_eras, still_unbonding);
+			ensure!(!withdrawn_eras.is_empty(), Error::<T>::CannotWithdrawAny);
+
+			let reward_account = Self::create_reward_account(pool_id);
+			let bonded_account = Self::create_bonded_account(pool_id);
+			if T::Staking::total_stake(&reward_account).is_some() {
+				T::Staking::withdraw_unbonded(reward_account, num_slashing_spans)?;
+			}
+
+			let mut balance = BalanceOf::<T>::zero();
+			for (era, points) in withdrawn_eras {
+				let pool = sub_pools.pool_mut(era);
+				balance = balance.saturating_add(pool.dissolve(points));
+				if pool.points.is_zero() {
+					sub_pools.with_era.remove(&era);
+				}
+			}
+			let balance = balance.min(Self::transferable_bonded_balance(pool_id));
+			T::Currency::transfer(&bonded_account, &who, balance, ExistenceRequirement::AllowDeath)?;
+			Self::deposit_event(RawEvent::Withdrawn(who.clone(), pool_id, balance));
+
+			if !member.is_empty() {
+				PoolMembers::<T>::insert(&who, member);
+				SubPoolsStorage::<T>::insert(pool_id, sub_pools);
+				return Ok(())
+			}
+
+			PoolMembers::<T>::remove(&who);
+			bonded_pool.member_counter = bonded_pool.member_counter.saturating_sub(1);
+			if who == bonded_pool.roles.depositor {
+				Self::dissolve_pool(pool_id, &who)?;
+			} else {
+				SubPoolsStorage::<T>::insert(pool_id, sub_pools);
+				BondedPools::<T>::insert(pool_id, bonded_pool);
+			}
+		}
+
+		/// Nominate `validators` with the pool `pool_id`.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the root or the nominator of the
+		/// pool.
+		///
+		/// # <weight>
+		/// - Db reads: `BondedPools`, staking bond, current era
+		/// - Db writes: staking nominations
+		/// # </weight>
+		#[weight = T::WeightInfo::nominate(validators.len() as u32)]
+		fn nominate(origin, pool_id: PoolId, validators: Vec<T::AccountId>) {
+			let who = ensure_signed(origin)?;
+			let bonded_pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(
+				who == bonded_pool.roles.root || who == bonded_pool.roles.nominator,
+				Error::<T>::NotNominator,
+			);
+			T::Staking::nominate(Self::create_reward_account(pool_id), validators)?;
+		}
+
+		/// Set the state of the pool `pool_id` to `state`, which cannot be `Destroying`.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the root of the pool, which must
+		/// not be destroying.
+		///
+		/// Emits `StateChanged`.
+		///
+		/// # <weight>
+		/// - Db reads: `BondedPools`
+		/// - Db writes: `BondedPools`
+		/// # </weight>
+		#[weight = T::WeightInfo::set_state()]
+		fn set_state(origin, pool_id: PoolId, state: PoolState) {
+			let who = ensure_signed(origin)?;
+			let mut bonded_pool = Self::bonded_pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(who == bonded_pool.roles.root, Error::<T>::NotRoot);
+			ensure!(
+				bonded_pool.state != PoolState::Destroying && state != PoolState::Destroying,
+				Error::<T>::CanNotChangeState,
+			);
+			bonded_pool.state = state;
+			BondedPools::<T>::insert(pool_id, bonded_pool);
+			Self::deposit_event(RawEvent::StateChanged(pool_id, state));
+		}
+
+		/// Set the minimum bonds to join and to create a pool.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		///
+		/// # <weight>
+		/// - Db writes: `MinJoinBond`, `MinCreateBond`
+		/// # </weight>
+		#[weight = T::WeightInfo::set_configs()]
+		fn set_configs(origin,
+			#[compact] min_join_bond: BalanceOf<T>,
+			#[compact] min_create_bond: BalanceOf<T>,
+		) {
+			ensure_root(origin)?;
+			MinJoinBond::<T>::put(min_join_bond);
+			MinCreateBond::<T>::put(min_create_bond);
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The bonded account of the pool `id`: its stash in the staking system.
+	pub fn create_bonded_account(id: PoolId) -> T::AccountId {
+		T::ModuleId::get().into_sub_account((AccountType::Bonded, id))
+	}
+
+	/// The reward account of the pool `id`: its controller in the staking system, into which
+	/// its rewards are paid.
+	pub fn create_reward_account(id: PoolId) -> T::AccountId {
+		T::ModuleId::get().into_sub_account((AccountType::Reward, id))
+	}
+
+	/// The active stake of the pool `id`.
+	pub fn bonded_balance(id: PoolId) -> BalanceOf<T> {
+		T::Staking::active_stake(&Self::create_reward_account(id)).unwrap_or_else(Zero::zero)
+	}
+
+	/// The rewards held by the reward account of the pool `id`, i.e. its balance above the
+	/// existential deposit.
+	fn reward_balance(id: PoolId) -> BalanceOf<T> {
+		T::Currency::free_balance(&Self::create_reward_account(id))
+			.saturating_sub(T::Currency::minimum_balance())
+	}
+
+	/// The balance of the bonded account of the pool `id` which is not bonded anymore.
+	fn transferable_bonded_balance(id: PoolId) -> BalanceOf<T> {
+		let total_stake = T::Staking::total_stake(&Self::create_reward_account(id))
+			.unwrap_or_else(Zero::zero);
+		T::Currency::free_balance(&Self::create_bonded_account(id)).saturating_sub(total_stake)
+	}
+
+	/// The rewards pending for `member`.
+	pub fn pending_rewards(member: &PoolMember<BalanceOf<T>>) -> BalanceOf<T> {
+		let bonded_points = Self::bonded_pools(member.pool_id).map_or(Zero::zero(), |p| p.points);
+		let reward_balance = Self::reward_balance(member.pool_id);
+		Self::reward_pools(member.pool_id).map_or(Zero::zero(), |reward_pool| {
+			let (counter, _) = reward_pool.current_reward_counter(reward_balance, bonded_points);
+			Self::owed(member, counter, reward_balance)
+		})
+	}
+
+	/// The rewards owed to `member` at the reward counter `counter`.
+	fn owed(
+		member: &PoolMember<BalanceOf<T>>,
+		counter: FixedU128,
+		reward_balance: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		counter.saturating_sub(member.last_recorded_reward_counter)
+			.saturating_mul_int(member.points.saturated_into::<u128>())
+			.saturated_into::<BalanceOf<T>>()
+			.min(reward_balance)
+	}
+
+	/// Pay out the pending rewards of `member`, the account `who`.
+	fn do_reward_payout(
+		who: &T::AccountId,
+		member: &mut PoolMember<BalanceOf<T>>,
+		bonded_pool: &BondedPoolOf<T>,
+		reward_pool: &mut RewardPool<BalanceOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let reward_balance = Self::reward_balance(member.pool_id);
+		let (counter, _) = reward_pool.current_reward_counter(reward_balance, bonded_pool.points);
+		let pending = Self::owed(member, counter, reward_balance);
+		member.last_recorded_reward_counter = counter;
+		if pending.is_zero() {
+			return Ok(pending)
+		}
+
+		// the claimed rewards are added back to the total payouts of the pool, leaving the
+		// reward counter unchanged.
+		reward_pool.total_rewards_claimed = reward_pool.total_rewards_claimed.saturating_add(pending);
+		T::Currency::transfer(
+			&Self::create_reward_account(member.pool_id),
+			who,
+			pending,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		Self::deposit_event(RawEvent::PaidOut(who.clone(), member.pool_id, pending));
+		Ok(pending)
+	}
+
+	/// Remove the pool `id` once its depositor left, paying the funds left in its accounts to
+	/// the depositor.
+	fn dissolve_pool(id: PoolId, depositor: &T::AccountId) -> DispatchResult {
+		let bonded_account = Self::create_bonded_account(id);
+		let reward_account = Self::create_reward_account(id);
+		T::Currency::transfer(
+			&reward_account,
+			depositor,
+			T::Currency::free_balance(&reward_account),
+			ExistenceRequirement::AllowDeath,
+		)?;
+		T::Currency::transfer(
+			&bonded_account,
+			depositor,
+			Self::transferable_bonded_balance(id),
+			ExistenceRequirement::AllowDeath,
+		)?;
+
+		BondedPools::<T>::remove(id);
+		RewardPools::<T>::remove(id);
+		SubPoolsStorage::<T>::remove(id);
+		ReversePoolIdLookup::<T>::remove(&bonded_account);
+		Self::deposit_event(RawEvent::Destroyed(id));
+		Ok(())
+	}
+}
+
+impl<T: Trait> OnStakerSlash<T::AccountId, BalanceOf<T>> for Module<T> {
+	fn on_slash(
+		stash: &T::AccountId,
+		slashed_active: BalanceOf<T>,
+		slashed_unlocking: &BTreeMap<EraIndex, BalanceOf<T>>,
+	) {
+		let pool_id = match Self::reverse_pool_id(stash) {
+			Some(pool_id) => pool_id,
+			None => return,
+		};
+
+		// the slash of the bonded funds is shared by all points through the active stake.
+		if !slashed_active.is_zero() {
+			Self::deposit_event(RawEvent::PoolSlashed(pool_id, slashed_active));
+		}
+
+		if slashed_unlocking.is_empty() {
+			return
+		}
+		SubPoolsStorage::<T>::mutate(pool_id, |maybe_sub_pools| {
+			if let Some(sub_pools) = maybe_sub_pools {
+				for (era, amount) in slashed_unlocking {
+					let pool = sub_pools.pool_mut(*era);
+					pool.balance = pool.balance.saturating_sub(*amount);
+					Self::deposit_event(RawEvent::UnbondingPoolSlashed(pool_id, *era, *amount));
+				}
+			}
+		});
+	}
+}
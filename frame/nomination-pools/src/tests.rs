@@ -0,0 +1,571 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The crate's tests.
+
+use super::*;
+use std::cell::RefCell;
+use frame_support::{
+	impl_outer_origin, impl_outer_event, assert_noop, assert_ok, parameter_types,
+	weights::Weight,
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup, BadOrigin},
+	testing::Header, Perbill,
+};
+
+mod staking;
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+mod pools {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		system<T>,
+		pallet_balances<T>,
+		pools<T>,
+	}
+}
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1_000_000;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = ();
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u128; // u64 is not enough to hold bytes used to generate pool accounts
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 5;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+const BONDING_DURATION: EraIndex = 3;
+
+/// The ledger of a stash in `StakingMock`.
+#[derive(Clone, Default)]
+pub struct Ledger {
+	stash: u128,
+	active: u64,
+	unlocking: Vec<(EraIndex, u64)>,
+}
+
+thread_local! {
+	static CURRENT_ERA: RefCell<EraIndex> = RefCell::new(0);
+	static LEDGERS: RefCell<BTreeMap<u128, Ledger>> = RefCell::new(BTreeMap::new());
+	static NOMINATIONS: RefCell<BTreeMap<u128, Vec<u128>>> = RefCell::new(BTreeMap::new());
+}
+
+/// A staking system keeping ledgers by controller, without locking any funds.
+pub struct StakingMock;
+
+impl StakingMock {
+	fn set_current_era(era: EraIndex) {
+		CURRENT_ERA.with(|e| *e.borrow_mut() = era);
+	}
+
+	fn nominations(controller: &u128) -> Option<Vec<u128>> {
+		NOMINATIONS.with(|n| n.borrow().get(controller).cloned())
+	}
+
+	fn unlocking(controller: &u128) -> Vec<(EraIndex, u64)> {
+		LEDGERS.with(|l| l.borrow().get(controller).map(|l| l.unlocking.clone()).unwrap_or_default())
+	}
+}
+
+impl StakingInterface for StakingMock {
+	type Balance = u64;
+	type AccountId = u128;
+
+	fn minimum_bond() -> u64 {
+		10
+	}
+
+	fn bonding_duration() -> EraIndex {
+		BONDING_DURATION
+	}
+
+	fn current_era() -> EraIndex {
+		CURRENT_ERA.with(|e| *e.borrow())
+	}
+
+	fn active_stake(controller: &u128) -> Option<u64> {
+		LEDGERS.with(|l| l.borrow().get(controller).map(|l| l.active))
+	}
+
+	fn total_stake(controller: &u128) -> Option<u64> {
+		LEDGERS.with(|l| l.borrow().get(controller).map(|l| {
+			l.active + l.unlocking.iter().map(|(_, value)| value).sum::<u64>()
+		}))
+	}
+
+	fn bond(stash: u128, controller: u128, value: u64) -> DispatchResult {
+		LEDGERS.with(|l| {
+			l.borrow_mut().insert(controller, Ledger { stash, active: value, unlocking: vec![] })
+		});
+		Ok(())
+	}
+
+	fn bond_extra(stash: u128, extra: u64) -> DispatchResult {
+		LEDGERS.with(|l| {
+			let mut ledgers = l.borrow_mut();
+			let ledger = ledgers.values_mut().find(|l| l.stash == stash).ok_or("NotStash")?;
+			ledger.active += extra;
+			Ok(())
+		})
+	}
+
+	fn unbond(controller: u128, value: u64) -> DispatchResult {
+		let era = Self::current_era() + BONDING_DURATION;
+		LEDGERS.with(|l| {
+			let mut ledgers = l.borrow_mut();
+			let ledger = ledgers.get_mut(&controller).ok_or("NotController")?;
+			ledger.active = ledger.active.checked_sub(value).ok_or("InsufficientBond")?;
+			ledger.unlocking.push((era, value));
+			Ok(())
+		})
+	}
+
+	fn withdraw_unbonded(controller: u128, _: u32) -> Result<bool, DispatchError> {
+		let current_era = Self::current_era();
+		LEDGERS.with(|l| {
+			let mut ledgers = l.borrow_mut();
+			let ledger = ledgers.get_mut(&controller).ok_or("NotController")?;
+			ledger.unlocking.retain(|(era, _)| *era > current_era);
+			if ledger.active == 0 && ledger.unlocking.is_empty() {
+				ledgers.remove(&controller);
+				Ok(true)
+			} else {
+				Ok(false)
+			}
+		})
+	}
+
+	fn nominate(controller: u128, targets: Vec<u128>) -> DispatchResult {
+		ensure!(Self::active_stake(&controller).is_some(), "NotController");
+		NOMINATIONS.with(|n| n.borrow_mut().insert(controller, targets));
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const PoolsModuleId: ModuleId = ModuleId(*b"py/nopls");
+	pub const PostUnbondingPoolsWindow: EraIndex = 2;
+	pub const MaxUnbonding: u32 = 3;
+}
+impl Trait for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type Staking = StakingMock;
+	type ModuleId = PoolsModuleId;
+	type PostUnbondingPoolsWindow = PostUnbondingPoolsWindow;
+	type MaxUnbonding = MaxUnbonding;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test>{
+		balances: vec![(10, 1000), (20, 1000), (21, 1000), (22, 1000)],
+	}.assimilate_storage(&mut t).unwrap();
+	GenesisConfig::<Test> {
+		min_join_bond: 10,
+		min_create_bond: 100,
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		StakingMock::set_current_era(0);
+		LEDGERS.with(|l| l.borrow_mut().clear());
+		NOMINATIONS.with(|n| n.borrow_mut().clear());
+	});
+	ext
+}
+
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Pools = Module<Test>;
+
+fn pool_events() -> Vec<RawEvent<u128, u64>> {
+	System::events().into_iter().filter_map(|r| match r.event {
+		Event::pools(e) => Some(e),
+		_ => None,
+	}).collect()
+}
+
+fn last_pool_event() -> RawEvent<u128, u64> {
+	pool_events().pop().expect("a pool event was deposited")
+}
+
+/// Pay `amount` of staking rewards into the reward account of the pool `id`.
+fn reward_pool(id: PoolId, amount: u64) {
+	let reward_account = Pools::create_reward_account(id);
+	let _ = Balances::make_free_balance_be(&reward_account, Balances::free_balance(&reward_account) + amount);
+}
+
+/// Slash the pool `id` as the staking system would.
+fn slash_pool(id: PoolId, slashed_active: u64, slashed_unlocking: Vec<(EraIndex, u64)>) {
+	let stash = Pools::create_bonded_account(id);
+	let controller = Pools::create_reward_account(id);
+	LEDGERS.with(|l| {
+		let mut ledgers = l.borrow_mut();
+		let ledger = ledgers.get_mut(&controller).unwrap();
+		ledger.active -= slashed_active;
+		for (era, amount) in &slashed_unlocking {
+			ledger.unlocking.iter_mut().find(|(e, _)| e == era).unwrap().1 -= amount;
+		}
+	});
+	let total = slashed_active + slashed_unlocking.iter().map(|(_, amount)| amount).sum::<u64>();
+	let _ = Balances::make_free_balance_be(&stash, Balances::free_balance(&stash) - total);
+	Pools::on_slash(&stash, slashed_active, &slashed_unlocking.into_iter().collect());
+}
+
+#[test]
+fn params_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Pools::min_join_bond(), 10);
+		assert_eq!(Pools::min_create_bond(), 100);
+		assert_eq!(Pools::last_pool_id(), 0);
+		assert_ne!(Pools::create_bonded_account(1), Pools::create_reward_account(1));
+		assert_ne!(Pools::create_bonded_account(1), Pools::create_bonded_account(2));
+	});
+}
+
+#[test]
+fn create_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 900, 901));
+		let bonded_account = Pools::create_bonded_account(1);
+		let reward_account = Pools::create_reward_account(1);
+
+		assert_eq!(Pools::last_pool_id(), 1);
+		assert_eq!(Balances::free_balance(10), 895);
+		assert_eq!(Balances::free_balance(&bonded_account), 100);
+		assert_eq!(Balances::free_balance(&reward_account), 5);
+		assert_eq!(StakingMock::active_stake(&reward_account), Some(100));
+		assert_eq!(Pools::bonded_pools(1), Some(BondedPool {
+			points: 100,
+			state: PoolState::Open,
+			member_counter: 1,
+			roles: PoolRoles { depositor: 10, root: 900, nominator: 901 },
+		}));
+		assert_eq!(Pools::reward_pools(1), Some(RewardPool::default()));
+		assert_eq!(Pools::pool_members(10).map(|m| (m.pool_id, m.points)), Some((1, 100)));
+		assert_eq!(Pools::reverse_pool_id(&bonded_account), Some(1));
+		assert_eq!(pool_events(), vec![RawEvent::Created(10, 1), RawEvent::Bonded(10, 1, 100)]);
+	});
+}
+
+#[test]
+fn create_should_check_its_bond() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Pools::create(Origin::signed(10), 99, 10, 10), Error::<Test>::MinimumBondNotMet);
+		assert_ok!(Pools::create(Origin::signed(10), 100, 10, 10));
+		assert_noop!(
+			Pools::create(Origin::signed(10), 100, 10, 10),
+			Error::<Test>::AccountBelongsToOtherPool,
+		);
+		assert_noop!(
+			Pools::create(Origin::signed(20), 2000, 20, 20),
+			pallet_balances::Error::<Test, _>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn join_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 10, 10));
+		assert_ok!(Pools::join(Origin::signed(20), 300, 1));
+		assert_eq!(last_pool_event(), RawEvent::Bonded(20, 1, 300));
+
+		assert_eq!(Balances::free_balance(20), 700);
+		assert_eq!(Balances::free_balance(Pools::create_bonded_account(1)), 400);
+		assert_eq!(Pools::bonded_balance(1), 400);
+		assert_eq!(Pools::pool_members(20).map(|m| (m.pool_id, m.points)), Some((1, 300)));
+		let bonded_pool = Pools::bonded_pools(1).unwrap();
+		assert_eq!((bonded_pool.points, bonded_pool.member_counter), (400, 2));
+
+		assert_noop!(Pools::join(Origin::signed(21), 9, 1), Error::<Test>::MinimumBondNotMet);
+		assert_noop!(Pools::join(Origin::signed(21), 10, 2), Error::<Test>::PoolNotFound);
+		assert_noop!(Pools::join(Origin::signed(20), 10, 1), Error::<Test>::AccountBelongsToOtherPool);
+	});
+}
+
+#[test]
+fn rewards_should_be_shared_by_points() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 10, 10));
+		assert_ok!(Pools::join(Origin::signed(20), 300, 1));
+
+		reward_pool(1, 40);
+		assert_eq!(Pools::pending_rewards(&Pools::pool_members(20).unwrap()), 30);
+		assert_ok!(Pools::claim_payout(Origin::signed(20)));
+		assert_eq!(last_pool_event(), RawEvent::PaidOut(20, 1, 30));
+		assert_eq!(Balances::free_balance(20), 730);
+		assert_ok!(Pools::claim_payout(Origin::signed(10)));
+		assert_eq!(Balances::free_balance(10), 905);
+
+		// nothing is pending anymore.
+		assert_ok!(Pools::claim_payout(Origin::signed(20)));
+		assert_eq!(Balances::free_balance(20), 730);
+
+		// a new member does not share the rewards earned before it joined.
+		assert_ok!(Pools::join(Origin::signed(21), 100, 1));
+		reward_pool(1, 50);
+		assert_ok!(Pools::claim_payout(Origin::signed(21)));
+		assert_eq!(Balances::free_balance(21), 910);
+		assert_ok!(Pools::claim_payout(Origin::signed(20)));
+		assert_eq!(Balances::free_balance(20), 760);
+		assert_ok!(Pools::claim_payout(Origin::signed(10)));
+		assert_eq!(Balances::free_balance(10), 915);
+
+		assert_eq!(Balances::free_balance(Pools::create_reward_account(1)), 5);
+		assert_eq!(Pools::reward_pools(1).unwrap().total_rewards_claimed, 90);
+		assert_noop!(Pools::claim_payout(Origin::signed(22)), Error::<Test>::PoolMemberNotFound);
+	});
+}
+
+#[test]
+fn unbond_and_withdraw_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 10, 10));
+		assert_ok!(Pools::join(Origin::signed(20), 300, 1));
+		reward_pool(1, 40);
+		let reward_account = Pools::create_reward_account(1);
+
+		assert_noop!(Pools::unbond(Origin::signed(20), 301), Error::<Test>::NotEnoughPoints);
+		assert_noop!(Pools::unbond(Origin::signed(20), 295), Error::<Test>::MinimumBondNotMet);
+
+		// pending rewards are paid out on unbonding.
+		assert_ok!(Pools::unbond(Origin::signed(20), 100));
+		assert_eq!(last_pool_event(), RawEvent::Unbonded(20, 1, 100, 3));
+		assert_eq!(Balances::free_balance(20), 730);
+		let member = Pools::pool_members(20).unwrap();
+		assert_eq!(member.points, 200);
+		assert_eq!(member.unbonding_eras, vec![(3, 100)].into_iter().collect());
+		assert_eq!(Pools::bonded_pools(1).unwrap().points, 300);
+		assert_eq!(Pools::sub_pools(1).unwrap().with_era.get(&3), Some(&UnbondPool { points: 100, balance: 100 }));
+		assert_eq!(StakingMock::active_stake(&reward_account), Some(300));
+		assert_eq!(StakingMock::unlocking(&reward_account), vec![(3, 100)]);
+
+		assert_noop!(Pools::withdraw_unbonded(Origin::signed(20), 0), Error::<Test>::CannotWithdrawAny);
+
+		// anyone can free the unlocked chunks of the pool.
+		StakingMock::set_current_era(3);
+		assert_ok!(Pools::pool_withdraw_unbonded(Origin::signed(22), 1, 0));
+		assert_eq!(StakingMock::unlocking(&reward_account), vec![]);
+
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(20), 0));
+		assert_eq!(last_pool_event(), RawEvent::Withdrawn(20, 1, 100));
+		assert_eq!(Balances::free_balance(20), 830);
+		assert!(Pools::sub_pools(1).unwrap().with_era.is_empty());
+
+		assert_ok!(Pools::unbond(Origin::signed(20), 200));
+		StakingMock::set_current_era(6);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(20), 0));
+		assert_eq!(Balances::free_balance(20), 1030);
+		assert_eq!(Pools::pool_members(20), None);
+		assert_eq!(Pools::bonded_pools(1).unwrap().member_counter, 1);
+	});
+}
+
+#[test]
+fn depositor_should_leave_last_and_destroy_the_pool() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 10, 10));
+		assert_ok!(Pools::join(Origin::signed(20), 100, 1));
+
+		assert_noop!(Pools::unbond(Origin::signed(10), 50), Error::<Test>::MinimumBondNotMet);
+		assert_noop!(Pools::unbond(Origin::signed(10), 100), Error::<Test>::NotOnlyMember);
+
+		assert_ok!(Pools::unbond(Origin::signed(20), 100));
+		StakingMock::set_current_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(20), 0));
+
+		assert_ok!(Pools::unbond(Origin::signed(10), 100));
+		assert_eq!(Pools::bonded_pools(1).unwrap().state, PoolState::Destroying);
+		assert!(pool_events().contains(&RawEvent::StateChanged(1, PoolState::Destroying)));
+		assert_noop!(Pools::join(Origin::signed(21), 100, 1), Error::<Test>::NotOpen);
+
+		StakingMock::set_current_era(6);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(10), 0));
+		assert_eq!(last_pool_event(), RawEvent::Destroyed(1));
+		assert_eq!(Balances::free_balance(10), 1000);
+		assert_eq!(Balances::free_balance(Pools::create_bonded_account(1)), 0);
+		assert_eq!(Balances::free_balance(Pools::create_reward_account(1)), 0);
+		assert_eq!(StakingMock::total_stake(&Pools::create_reward_account(1)), None);
+		assert_eq!(Pools::bonded_pools(1), None);
+		assert_eq!(Pools::reward_pools(1), None);
+		assert_eq!(Pools::sub_pools(1), None);
+		assert_eq!(Pools::pool_members(10), None);
+		assert_eq!(Pools::reverse_pool_id(Pools::create_bonded_account(1)), None);
+	});
+}
+
+#[test]
+fn slashes_should_be_passed_on_to_members() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 10, 10));
+		assert_ok!(Pools::join(Origin::signed(20), 300, 1));
+		assert_ok!(Pools::unbond(Origin::signed(20), 200));
+
+		slash_pool(1, 100, vec![(3, 100)]);
+		let events = pool_events();
+		assert_eq!(
+			events[events.len() - 2..],
+			[RawEvent::PoolSlashed(1, 100), RawEvent::UnbondingPoolSlashed(1, 3, 100)],
+		);
+		assert_eq!(Pools::bonded_balance(1), 100);
+		assert_eq!(Pools::sub_pools(1).unwrap().with_era.get(&3), Some(&UnbondPool { points: 200, balance: 100 }));
+
+		// points are now worth half of their balance.
+		assert_ok!(Pools::join(Origin::signed(21), 100, 1));
+		assert_eq!(Pools::pool_members(21).unwrap().points, 200);
+		assert_eq!(Pools::bonded_pools(1).unwrap().points, 400);
+
+		StakingMock::set_current_era(3);
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(20), 0));
+		assert_eq!(last_pool_event(), RawEvent::Withdrawn(20, 1, 100));
+		assert_eq!(Balances::free_balance(20), 800);
+
+		// slashes of other stashes are ignored.
+		Pools::on_slash(&20, 100, &Default::default());
+		assert_eq!(last_pool_event(), RawEvent::Withdrawn(20, 1, 100));
+	});
+}
+
+#[test]
+fn unbonding_pools_should_merge() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 10, 10));
+		assert_ok!(Pools::join(Origin::signed(20), 300, 1));
+
+		assert_ok!(Pools::unbond(Origin::signed(20), 10));
+		StakingMock::set_current_era(1);
+		assert_ok!(Pools::unbond(Origin::signed(20), 10));
+		StakingMock::set_current_era(6);
+		assert_ok!(Pools::unbond(Origin::signed(20), 10));
+
+		// the pool of era 3 unlocked more than 2 eras ago.
+		let sub_pools = Pools::sub_pools(1).unwrap();
+		assert_eq!(sub_pools.no_era, UnbondPool { points: 10, balance: 10 });
+		assert_eq!(sub_pools.with_era.keys().cloned().collect::<Vec<_>>(), vec![4, 9]);
+
+		assert_ok!(Pools::withdraw_unbonded(Origin::signed(20), 0));
+		assert_eq!(last_pool_event(), RawEvent::Withdrawn(20, 1, 20));
+		let sub_pools = Pools::sub_pools(1).unwrap();
+		assert_eq!(sub_pools.no_era, UnbondPool { points: 0, balance: 0 });
+		assert_eq!(sub_pools.with_era.keys().cloned().collect::<Vec<_>>(), vec![9]);
+		assert_eq!(Pools::pool_members(20).unwrap().unbonding_eras, vec![(9, 10)].into_iter().collect());
+	});
+}
+
+#[test]
+fn unbonding_eras_should_be_limited() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 10, 10));
+		assert_ok!(Pools::join(Origin::signed(20), 300, 1));
+
+		for era in 0..3 {
+			StakingMock::set_current_era(era);
+			assert_ok!(Pools::unbond(Origin::signed(20), 10));
+			// unbonding again in the same era is always possible.
+			assert_ok!(Pools::unbond(Origin::signed(20), 10));
+		}
+		StakingMock::set_current_era(3);
+		assert_noop!(Pools::unbond(Origin::signed(20), 10), Error::<Test>::MaxUnbondingLimit);
+	});
+}
+
+#[test]
+fn roles_should_be_checked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 900, 901));
+		let reward_account = Pools::create_reward_account(1);
+
+		assert_noop!(Pools::nominate(Origin::signed(10), 1, vec![1, 2]), Error::<Test>::NotNominator);
+		assert_noop!(Pools::nominate(Origin::signed(900), 2, vec![1, 2]), Error::<Test>::PoolNotFound);
+		assert_ok!(Pools::nominate(Origin::signed(901), 1, vec![1, 2]));
+		assert_eq!(StakingMock::nominations(&reward_account), Some(vec![1, 2]));
+		assert_ok!(Pools::nominate(Origin::signed(900), 1, vec![3]));
+		assert_eq!(StakingMock::nominations(&reward_account), Some(vec![3]));
+
+		assert_noop!(Pools::set_state(Origin::signed(901), 1, PoolState::Blocked), Error::<Test>::NotRoot);
+		assert_ok!(Pools::set_state(Origin::signed(900), 1, PoolState::Blocked));
+		assert_eq!(last_pool_event(), RawEvent::StateChanged(1, PoolState::Blocked));
+		assert_noop!(Pools::join(Origin::signed(20), 100, 1), Error::<Test>::NotOpen);
+		assert_noop!(
+			Pools::set_state(Origin::signed(900), 1, PoolState::Destroying),
+			Error::<Test>::CanNotChangeState,
+		);
+		assert_ok!(Pools::set_state(Origin::signed(900), 1, PoolState::Open));
+		assert_ok!(Pools::join(Origin::signed(20), 100, 1));
+	});
+}
+
+#[test]
+fn set_configs_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Pools::set_configs(Origin::signed(10), 20, 200), BadOrigin);
+		assert_ok!(Pools::set_configs(Origin::root(), 20, 200));
+		assert_eq!(Pools::min_join_bond(), 20);
+		assert_eq!(Pools::min_create_bond(), 200);
+		assert_noop!(Pools::create(Origin::signed(10), 199, 10, 10), Error::<Test>::MinimumBondNotMet);
+	});
+}
@@ -0,0 +1,208 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The tests of pools backed by the staking pallet.
+
+use super::*;
+use frame_election_provider_support::onchain;
+use sp_runtime::{curve::PiecewiseLinear, traits::Convert};
+
+impl_outer_origin! {
+	pub enum Origin for Runtime where system = frame_system {}
+}
+
+mod pools {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Runtime {
+		system<T>,
+		pallet_balances<T>,
+		pallet_staking<T>,
+		pools<T>,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Runtime;
+impl frame_system::Trait for Runtime {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = ();
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u128;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+impl pallet_balances::Trait for Runtime {
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+pub struct CurrencyToVoteHandler;
+impl Convert<u64, u64> for CurrencyToVoteHandler {
+	fn convert(x: u64) -> u64 {
+		x
+	}
+}
+impl Convert<u128, u64> for CurrencyToVoteHandler {
+	fn convert(x: u128) -> u64 {
+		x.saturated_into()
+	}
+}
+
+pub struct Clock;
+impl frame_support::traits::UnixTime for Clock {
+	fn now() -> core::time::Duration {
+		Default::default()
+	}
+}
+
+// There are no sessions in this runtime, so there is nothing to disable or prune.
+impl pallet_staking::SessionInterface<u128> for Runtime {
+	fn disable_validator(_: &u128) -> Result<bool, ()> {
+		Ok(false)
+	}
+
+	fn validators() -> Vec<u128> {
+		Vec::new()
+	}
+
+	fn prune_historical_up_to(_: sp_staking::SessionIndex) {}
+}
+
+pallet_staking_reward_curve::build! {
+	const I_NPOS: PiecewiseLinear<'static> = curve!(
+		min_inflation: 0_025_000,
+		max_inflation: 0_100_000,
+		ideal_stake: 0_500_000,
+		falloff: 0_050_000,
+		max_piece_count: 40,
+		test_precision: 0_005_000,
+	);
+}
+parameter_types! {
+	pub const SessionsPerEra: sp_staking::SessionIndex = 3;
+	pub const StakingBondingDuration: EraIndex = BONDING_DURATION;
+	pub const RewardCurve: &'static PiecewiseLinear<'static> = &I_NPOS;
+	pub const MaxNominatorRewardedPerValidator: u32 = 64;
+}
+impl pallet_staking::Trait for Runtime {
+	type Currency = Balances;
+	type UnixTime = Clock;
+	type CurrencyToVote = CurrencyToVoteHandler;
+	type RewardRemainder = ();
+	type Event = Event;
+	type Slash = ();
+	type Reward = ();
+	type SessionsPerEra = SessionsPerEra;
+	type SlashDeferDuration = ();
+	type SlashCancelOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type BondingDuration = StakingBondingDuration;
+	type SessionInterface = Self;
+	type RewardCurve = RewardCurve;
+	type NextNewSession = ();
+	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
+	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type OnStakerSlash = Pools;
+	type WeightInfo = ();
+}
+impl onchain::Config for Runtime {
+	type AccountId = u128;
+	type BlockNumber = u64;
+	type Accuracy = Perbill;
+	type DataProvider = Staking;
+}
+impl Trait for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type Staking = Staking;
+	type ModuleId = PoolsModuleId;
+	type PostUnbondingPoolsWindow = PostUnbondingPoolsWindow;
+	type MaxUnbonding = MaxUnbonding;
+	type WeightInfo = ();
+}
+
+type System = frame_system::Module<Runtime>;
+type Balances = pallet_balances::Module<Runtime>;
+type Staking = pallet_staking::Module<Runtime>;
+type Pools = Module<Runtime>;
+
+/// The number of members joining the pool, more than the unlocking chunks of a staking ledger.
+const MEMBERS: u128 = pallet_staking::MAX_UNLOCKING_CHUNKS as u128 + 8;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_balances::GenesisConfig::<Runtime>{
+		balances: (10..=10 + MEMBERS).map(|who| (who, 1000)).collect(),
+	}.assimilate_storage(&mut t).unwrap();
+	GenesisConfig::<Runtime> {
+		min_join_bond: 10,
+		min_create_bond: 100,
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+#[test]
+fn members_can_unbond_beyond_the_unlocking_chunks_of_the_pool() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Pools::create(Origin::signed(10), 100, 10, 10));
+		for who in 11..=10 + MEMBERS {
+			assert_ok!(Pools::join(Origin::signed(who), 100, 1));
+		}
+		let reward_account = Pools::create_reward_account(1);
+
+		for who in 11..=10 + MEMBERS {
+			assert_ok!(Pools::unbond(Origin::signed(who), 100));
+		}
+
+		// all the members unbonded in the same era, so their funds share a single chunk.
+		let ledger = Staking::ledger(&reward_account).unwrap();
+		assert_eq!(ledger.active, 100);
+		assert_eq!(ledger.total, 100 + 100 * MEMBERS as u64);
+		assert_eq!(ledger.unlocking.len(), 1);
+		assert_eq!(
+			Pools::sub_pools(1).unwrap().with_era.get(&BONDING_DURATION),
+			Some(&UnbondPool { points: 100 * MEMBERS as u64, balance: 100 * MEMBERS as u64 }),
+		);
+	});
+}
@@ -0,0 +1,232 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Miscellaneous additional datatypes.
+
+use codec::{Encode, Decode};
+use sp_std::collections::btree_map::BTreeMap;
+use sp_runtime::{
+	RuntimeDebug, FixedU128, FixedPointNumber, SaturatedConversion,
+	helpers_128bit::multiply_by_rational,
+	traits::{AtLeast32BitUnsigned, Bounded, Saturating, Zero},
+};
+use sp_staking::EraIndex;
+use crate::PoolId;
+
+/// `a * b / c`, computed in 128 bits and saturating. Zero if `c` is zero.
+fn mul_div<Balance: AtLeast32BitUnsigned>(a: Balance, b: Balance, c: Balance) -> Balance {
+	if c.is_zero() {
+		return Zero::zero()
+	}
+	multiply_by_rational(a.saturated_into(), b.saturated_into(), c.saturated_into())
+		.map(|r| r.saturated_into())
+		.unwrap_or_else(|_| Balance::max_value())
+}
+
+/// The points to issue for `new_funds` joining a pool of `points` points backed by `balance`.
+///
+/// Points are issued one to one with the balance of an empty pool.
+pub fn balance_to_point<Balance: AtLeast32BitUnsigned>(
+	balance: Balance,
+	points: Balance,
+	new_funds: Balance,
+) -> Balance {
+	if balance.is_zero() || points.is_zero() {
+		new_funds
+	} else {
+		mul_div(points, new_funds, balance)
+	}
+}
+
+/// The balance backing `member_points` of a pool of `points` points backed by `balance`.
+pub fn point_to_balance<Balance: AtLeast32BitUnsigned>(
+	balance: Balance,
+	points: Balance,
+	member_points: Balance,
+) -> Balance {
+	mul_div(balance, member_points, points)
+}
+
+/// The purpose of an account derived for a pool.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum AccountType {
+	/// The stash of the pool, holding its bonded funds.
+	Bonded,
+	/// The controller of the pool, into which its staking rewards are paid.
+	Reward,
+}
+
+/// The state of a bonded pool.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum PoolState {
+	/// Anyone can join the pool.
+	Open,
+	/// Nobody can join the pool.
+	Blocked,
+	/// The pool is winding down: nobody can join it and its depositor left it. It is removed once
+	/// its last member withdraws.
+	Destroying,
+}
+
+/// The accounts with a role in a pool.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PoolRoles<AccountId> {
+	/// The account which created the pool. It is the last member to leave it.
+	pub depositor: AccountId,
+	/// The account which can change the state of the pool and nominate with it.
+	pub root: AccountId,
+	/// The account which can nominate with the pool.
+	pub nominator: AccountId,
+}
+
+/// A pool of funds bonded in the staking system by a single stash.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BondedPool<AccountId, Balance> {
+	/// The points issued to the members of the pool against its active stake.
+	pub points: Balance,
+	/// The state of the pool.
+	pub state: PoolState,
+	/// The number of members of the pool, including those which are only unbonding.
+	pub member_counter: u32,
+	/// The accounts with a role in the pool.
+	pub roles: PoolRoles<AccountId>,
+}
+
+/// The record of the rewards paid into the reward account of a pool.
+///
+/// Rewards are accounted for with a _reward counter_: the total rewards earned per point of the
+/// bonded pool since its creation. A member is owed its points times the increase of the reward
+/// counter since it last claimed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct RewardPool<Balance> {
+	/// The reward counter at the last time the points of the bonded pool changed.
+	pub last_recorded_reward_counter: FixedU128,
+	/// The total rewards ever earned by the pool at the last time the points of the bonded pool
+	/// changed.
+	pub last_recorded_total_payouts: Balance,
+	/// The total rewards ever claimed by the members of the pool.
+	pub total_rewards_claimed: Balance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> RewardPool<Balance> {
+	/// The current reward counter of the pool, given the free balance of its reward account
+	/// `reward_balance` and the points `bonded_points` of its bonded pool.
+	///
+	/// Also returns the total rewards ever earned by the pool.
+	pub fn current_reward_counter(
+		&self,
+		reward_balance: Balance,
+		bonded_points: Balance,
+	) -> (FixedU128, Balance) {
+		let total_payouts = reward_balance.saturating_add(self.total_rewards_claimed);
+		let new_earnings = total_payouts.saturating_sub(self.last_recorded_total_payouts);
+		let counter = if bonded_points.is_zero() {
+			self.last_recorded_reward_counter
+		} else {
+			self.last_recorded_reward_counter.saturating_add(FixedU128::saturating_from_rational(
+				new_earnings.saturated_into::<u128>(),
+				bonded_points.saturated_into::<u128>(),
+			))
+		};
+		(counter, total_payouts)
+	}
+
+	/// Record the reward counter before the points of the bonded pool change.
+	pub fn update_records(&mut self, reward_balance: Balance, bonded_points: Balance) {
+		let (counter, total_payouts) = self.current_reward_counter(reward_balance, bonded_points);
+		self.last_recorded_reward_counter = counter;
+		self.last_recorded_total_payouts = total_payouts;
+	}
+}
+
+/// A pool of funds unbonding from a bonded pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct UnbondPool<Balance> {
+	/// The points issued to the members unbonding into the pool.
+	pub points: Balance,
+	/// The balance unbonding, net of slashes.
+	pub balance: Balance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> UnbondPool<Balance> {
+	/// Add `new_funds` to the pool, returning the points issued for them.
+	pub fn issue(&mut self, new_funds: Balance) -> Balance {
+		let new_points = balance_to_point(self.balance, self.points, new_funds);
+		self.points = self.points.saturating_add(new_points);
+		self.balance = self.balance.saturating_add(new_funds);
+		new_points
+	}
+
+	/// Remove `points` from the pool, returning the balance backing them.
+	pub fn dissolve(&mut self, points: Balance) -> Balance {
+		let balance = point_to_balance(self.balance, self.points, points).min(self.balance);
+		self.points = self.points.saturating_sub(points);
+		self.balance = self.balance.saturating_sub(balance);
+		balance
+	}
+}
+
+/// The unbonding pools of a bonded pool.
+///
+/// Funds are unbonded into the pool of the era at which they unlock. Pools of eras which
+/// unlocked long enough ago are merged into `no_era`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct SubPools<Balance> {
+	/// The pool into which old era pools are merged.
+	pub no_era: UnbondPool<Balance>,
+	/// The pools of the eras at which their funds unlock.
+	pub with_era: BTreeMap<EraIndex, UnbondPool<Balance>>,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> SubPools<Balance> {
+	/// Merge the pools of eras before `oldest_era` into `no_era`.
+	pub fn maybe_merge_pools(&mut self, oldest_era: EraIndex) {
+		let newer = self.with_era.split_off(&oldest_era);
+		for (_, pool) in sp_std::mem::replace(&mut self.with_era, newer) {
+			self.no_era.points = self.no_era.points.saturating_add(pool.points);
+			self.no_era.balance = self.no_era.balance.saturating_add(pool.balance);
+		}
+	}
+
+	/// The pool of `era`, or `no_era` if it was merged.
+	pub fn pool_mut(&mut self, era: EraIndex) -> &mut UnbondPool<Balance> {
+		match self.with_era.get_mut(&era) {
+			Some(pool) => pool,
+			None => &mut self.no_era,
+		}
+	}
+}
+
+/// A member of a pool.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PoolMember<Balance> {
+	/// The pool of the member.
+	pub pool_id: PoolId,
+	/// The points of the member in the bonded pool.
+	pub points: Balance,
+	/// The reward counter of the pool at the last time the member claimed its rewards.
+	pub last_recorded_reward_counter: FixedU128,
+	/// The points of the member in the unbonding pools, keyed by the era at which they unlock.
+	pub unbonding_eras: BTreeMap<EraIndex, Balance>,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> PoolMember<Balance> {
+	/// Whether the member has neither bonded nor unbonding points left.
+	pub fn is_empty(&self) -> bool {
+		self.points.is_zero() && self.unbonding_eras.is_empty()
+	}
+}
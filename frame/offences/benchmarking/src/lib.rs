@@ -40,7 +40,7 @@ use pallet_session::historical::{Trait as HistoricalTrait, IdentificationTuple};
 use pallet_session::{Trait as SessionTrait, SessionManager};
 use pallet_staking::{
 	Module as Staking, Trait as StakingTrait, RewardDestination, ValidatorPrefs,
-	Exposure, IndividualExposure, ElectionStatus, MAX_NOMINATIONS, Event as StakingEvent
+	Exposure, IndividualExposure, MAX_NOMINATIONS, Event as StakingEvent
 };
 
 const SEED: u32 = 0;
@@ -358,8 +358,6 @@ benchmarks! {
 		let o = 10;
 		let n = 100;
 
-		Staking::<T>::put_election_status(ElectionStatus::Closed);
-
 		let mut deferred_offences = vec![];
 		let offenders = make_offenders::<T>(o, n)?.0;
 		let offence_details = offenders.into_iter()
@@ -25,6 +25,7 @@ use frame_support::{
 	weights::{Weight, constants::WEIGHT_PER_SECOND},
 };
 use frame_system as system;
+use frame_election_provider_support::onchain;
 use sp_runtime::{
 	SaturatedConversion,
 	traits::{IdentityLookup, Block as BlockT},
@@ -176,15 +177,19 @@ impl pallet_staking::Trait for Test {
 	type SessionInterface = Self;
 	type RewardCurve = RewardCurve;
 	type NextNewSession = Session;
-	type ElectionLookahead = ();
-	type Call = Call;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
-	type UnsignedPriority = ();
-	type MaxIterations = ();
-	type MinSolutionScoreBump = ();
+	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type OnStakerSlash = ();
 	type WeightInfo = ();
 }
 
+impl onchain::Config for Test {
+	type AccountId = AccountId;
+	type BlockNumber = BlockNumber;
+	type Accuracy = Perbill;
+	type DataProvider = Staking;
+}
+
 impl pallet_im_online::Trait for Test {
 	type AuthorityId = UintAuthorityId;
 	type Event = Event;
@@ -224,7 +229,7 @@ frame_support::construct_runtime!(
 	{
 		System: system::{Module, Call, Event<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
-		Staking: pallet_staking::{Module, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
+		Staking: pallet_staking::{Module, Call, Config<T>, Storage, Event<T>},
 		Session: pallet_session::{Module, Call, Storage, Event, Config<T>},
 		ImOnline: pallet_im_online::{Module, Call, Storage, Event<T>, ValidateUnsigned, Config<T>},
 		Offences: pallet_offences::{Module, Call, Storage, Event},
@@ -0,0 +1,108 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Preimage pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account};
+use frame_support::traits::UnfilteredDispatchable;
+use sp_runtime::traits::Bounded;
+
+use crate::Module as Preimage;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	caller
+}
+
+fn preimage_and_hash<T: Trait>(s: u32) -> (Vec<u8>, T::Hash) {
+	let preimage = vec![1; s as usize];
+	let hash = T::Hashing::hash(&preimage[..]);
+	(preimage, hash)
+}
+
+benchmarks! {
+	_ { }
+
+	// Expensive note - will reserve.
+	note_preimage {
+		let s in 0 .. T::MaxSize::get();
+		let caller = funded_account::<T>("caller", 0);
+		let (preimage, hash) = preimage_and_hash::<T>(s);
+	}: _(RawOrigin::Signed(caller), preimage)
+	verify {
+		assert!(Preimage::<T>::have_preimage(&hash));
+	}
+
+	// Expensive unnote - will unreserve.
+	unnote_preimage {
+		let caller = funded_account::<T>("caller", 0);
+		let (preimage, hash) = preimage_and_hash::<T>(T::MaxSize::get());
+		Preimage::<T>::note_preimage(RawOrigin::Signed(caller.clone()).into(), preimage)?;
+	}: _(RawOrigin::Signed(caller), hash.clone())
+	verify {
+		assert!(!Preimage::<T>::have_preimage(&hash));
+	}
+
+	// Expensive request - will unreserve the noter's deposit.
+	request_preimage {
+		let origin = T::ManagerOrigin::successful_origin();
+		let noter = funded_account::<T>("noter", 0);
+		let (preimage, hash) = preimage_and_hash::<T>(T::MaxSize::get());
+		Preimage::<T>::note_preimage(RawOrigin::Signed(noter).into(), preimage)?;
+		let call = Call::<T>::request_preimage(hash.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(StatusFor::<T>::get(&hash), Some(RequestStatus::Requested(1)));
+	}
+
+	// Expensive unrequest - last reference and it's noted, so will destroy the preimage.
+	unrequest_preimage {
+		let origin = T::ManagerOrigin::successful_origin();
+		let (preimage, hash) = preimage_and_hash::<T>(T::MaxSize::get());
+		Preimage::<T>::request_preimage(origin.clone(), hash.clone())?;
+		Preimage::<T>::note_preimage(origin.clone(), preimage)?;
+		let call = Call::<T>::unrequest_preimage(hash.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(StatusFor::<T>::get(&hash), None);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_note_preimage::<Test>());
+			assert_ok!(test_benchmark_unnote_preimage::<Test>());
+			assert_ok!(test_benchmark_request_preimage::<Test>());
+			assert_ok!(test_benchmark_unrequest_preimage::<Test>());
+		});
+	}
+}
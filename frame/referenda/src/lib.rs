@@ -0,0 +1,711 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Referenda Pallet
+//!
+//! - [`referenda::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! The Referenda pallet handles referenda sorted into _tracks_, where each track has its own
+//! parameters. Votes are not counted by this pallet: it is a polling pallet, implementing
+//! `Polling`, on which a voting pallet such as `pallet-conviction-voting` records votes into
+//! the tally of each referendum.
+//!
+//! A referendum is submitted with a proposal and the origin its proposal is dispatched with if
+//! approved; the origin determines the track of the referendum through `Trait::Tracks`. Once
+//! the decision deposit of its track is placed and the prepare period of its track elapsed, the
+//! referendum starts _deciding_, unless the track already has its maximum number of deciding
+//! referenda, in which case it waits in the queue of its track, ordered by aye votes.
+//!
+//! A deciding referendum is _passing_ when both its approval and its support are above the
+//! curves of its track, which generally decrease over the decision period. Once it kept passing
+//! for the confirmation period of its track, the referendum is approved and its proposal is
+//! scheduled for enactment. If it is not passing at the end of the decision period, it is
+//! rejected. A referendum which never starts deciding times out after `UndecidingTimeout`.
+//!
+//! ### Terminology
+//!
+//! - **Track:** The kind of a referendum, determined by the origin of its proposal, with its own
+//!   deposits, periods and curves.
+//! - **Approval:** The proportion of aye votes among all the votes of a referendum.
+//! - **Support:** The proportion of the total voting capacity backing the referendum.
+//! - **Decision Period:** The maximum period a referendum is deciding for.
+//! - **Confirmation Period:** The period for which a referendum must keep passing to be approved.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `submit` - Submits a referendum on a proposal. Requires the submission deposit.
+//! - `place_decision_deposit` - Places the decision deposit of a referendum, allowing it to
+//!   start deciding.
+//!
+//! #### Cancel Origin
+//!
+//! - `cancel` - Cancels an ongoing referendum, refunding its deposits.
+//!
+//! #### Kill Origin
+//!
+//! - `kill` - Cancels an ongoing referendum, slashing its deposits.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use sp_runtime::{
+	DispatchError, Perbill,
+	traits::{Zero, One, Saturating, Dispatchable, AtLeast32BitUnsigned, Member},
+};
+use codec::{Encode, HasCompact};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, Parameter,
+	weights::Weight,
+	traits::{
+		Currency, ReservableCurrency, OnUnbalanced, Get, EnsureOrigin,
+		PreimageProvider, VoteTally, Polling, PollStatus,
+		schedule::{Named as ScheduleNamed, DispatchTime, MaybeHashed},
+	},
+};
+use frame_system::{self as system, ensure_signed};
+
+mod types;
+mod default_weight;
+pub use types::{
+	Curve, Deposit, DecidingStatus, ReferendumInfo, ReferendumStatus, TrackInfo, TracksInfo,
+};
+
+#[cfg(test)]
+mod tests;
+
+const REFERENDA_ID: [u8; 8] = *b"referend";
+
+/// A referendum index.
+pub type ReferendumIndex = u32;
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+
+/// A proposal which is either given in full or referred to by the hash of its preimage.
+pub type CallOrHashOf<T> = MaybeHashed<<T as Trait>::Proposal, <T as frame_system::Trait>::Hash>;
+
+/// The identifier of a track.
+pub type TrackIdOf<T> = <<T as Trait>::Tracks as TracksInfo<
+	BalanceOf<T>,
+	<T as frame_system::Trait>::BlockNumber,
+>>::Id;
+
+/// The parameters of a track.
+pub type TrackInfoOf<T> = TrackInfo<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
+/// Info regarding an ongoing referendum.
+pub type ReferendumStatusOf<T> = ReferendumStatus<
+	TrackIdOf<T>,
+	<T as Trait>::PalletsOrigin,
+	<T as frame_system::Trait>::BlockNumber,
+	<T as frame_system::Trait>::Hash,
+	BalanceOf<T>,
+	<T as Trait>::Tally,
+	<T as frame_system::Trait>::AccountId,
+>;
+
+/// Info regarding a referendum, present or past.
+pub type ReferendumInfoOf<T> = ReferendumInfo<
+	TrackIdOf<T>,
+	<T as Trait>::PalletsOrigin,
+	<T as frame_system::Trait>::BlockNumber,
+	<T as frame_system::Trait>::Hash,
+	BalanceOf<T>,
+	<T as Trait>::Tally,
+	<T as frame_system::Trait>::AccountId,
+>;
+
+pub trait WeightInfo {
+	fn submit() -> Weight;
+	fn place_decision_deposit() -> Weight;
+	fn cancel() -> Weight;
+	fn kill() -> Weight;
+	fn on_initialize(r: u32, ) -> Weight;
+}
+
+pub trait Trait: frame_system::Trait + Sized {
+	type Proposal: Parameter + Dispatchable<Origin=Self::Origin>;
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Currency type for this module.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The Scheduler.
+	type Scheduler: ScheduleNamed<Self::BlockNumber, CallOrHashOf<Self>, Self::PalletsOrigin>;
+
+	/// Overarching type of all pallets origins.
+	type PalletsOrigin: Parameter + Member + From<system::RawOrigin<Self::AccountId>>;
+
+	/// The preimage provider with which proposals are looked up by hash.
+	type Preimages: PreimageProvider<Self::Hash>;
+
+	/// Origin from which any ongoing referendum may be cancelled, refunding its deposits.
+	type CancelOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin from which any ongoing referendum may be killed, slashing its deposits.
+	type KillOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Handler for the unbalanced reduction when slashing a deposit.
+	type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+	/// The counting type for votes.
+	type Votes: AtLeast32BitUnsigned + Parameter + Member + Copy + HasCompact;
+
+	/// The tally of the votes of a referendum, kept up to date by the voting pallet.
+	type Tally: VoteTally<Self::Votes> + Default + Parameter + Member;
+
+	/// The deposit to be placed when submitting a referendum.
+	type SubmissionDeposit: Get<BalanceOf<Self>>;
+
+	/// The maximum number of referenda waiting in the queue of a track to start deciding.
+	type MaxQueued: Get<u32>;
+
+	/// The period after which a referendum which never started deciding times out.
+	type UndecidingTimeout: Get<Self::BlockNumber>;
+
+	/// The maximum period between two checks of a deciding referendum, so that it is noticed in
+	/// time when the curves of its track fall below its tally.
+	type AlarmInterval: Get<Self::BlockNumber>;
+
+	/// The tracks of the referenda, by the origin of their proposal.
+	type Tracks: TracksInfo<BalanceOf<Self>, Self::BlockNumber, Origin=Self::PalletsOrigin>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Referenda {
+		/// The next free referendum index, aka the number of referenda started so far.
+		pub ReferendumCount get(fn referendum_count): ReferendumIndex;
+
+		/// Information concerning any given referendum.
+		///
+		/// TWOX-NOTE: SAFE as indexes are not under an attacker’s control.
+		pub ReferendumInfoFor get(fn referendum_info):
+			map hasher(twox_64_concat) ReferendumIndex => Option<ReferendumInfoOf<T>>;
+
+		/// The referenda waiting to start deciding on each track, with their aye votes, sorted
+		/// from the fewest to the most ayes.
+		pub TrackQueue get(fn track_queue):
+			map hasher(twox_64_concat) TrackIdOf<T> => Vec<(ReferendumIndex, T::Votes)>;
+
+		/// The number of referenda deciding on each track.
+		pub DecidingCount get(fn deciding_count): map hasher(twox_64_concat) TrackIdOf<T> => u32;
+
+		/// The referenda to be serviced at a given block. An entry is stale unless the referendum
+		/// still has its alarm set at that block.
+		pub Alarms get(fn alarms):
+			map hasher(twox_64_concat) T::BlockNumber => Vec<ReferendumIndex>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		<T as frame_system::Trait>::AccountId,
+		<T as frame_system::Trait>::Hash,
+		Balance = BalanceOf<T>,
+		TrackId = TrackIdOf<T>,
+	{
+		/// A referendum has been submitted. [ref_index, track, proposal_hash]
+		Submitted(ReferendumIndex, TrackId, Hash),
+		/// The decision deposit of a referendum has been placed. [ref_index, who, amount]
+		DecisionDepositPlaced(ReferendumIndex, AccountId, Balance),
+		/// A referendum has started deciding. [ref_index, track]
+		DecisionStarted(ReferendumIndex, TrackId),
+		/// A referendum has started confirming. [ref_index]
+		ConfirmStarted(ReferendumIndex),
+		/// A referendum has stopped confirming as it is no longer passing. [ref_index]
+		ConfirmAborted(ReferendumIndex),
+		/// A referendum has been approved and its proposal scheduled. [ref_index]
+		Approved(ReferendumIndex),
+		/// A referendum has been rejected. [ref_index]
+		Rejected(ReferendumIndex),
+		/// A referendum has timed out without starting to decide. [ref_index]
+		TimedOut(ReferendumIndex),
+		/// A referendum has been cancelled. [ref_index]
+		Cancelled(ReferendumIndex),
+		/// A referendum has been killed. [ref_index]
+		Killed(ReferendumIndex),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Referendum is not ongoing.
+		NotOngoing,
+		/// Referendum's decision deposit is already paid.
+		HasDeposit,
+		/// There is no track for the origin of the proposal.
+		NoTrack,
+		/// The track of the referendum is unknown.
+		BadTrack,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The deposit to be placed when submitting a referendum.
+		const SubmissionDeposit: BalanceOf<T> = T::SubmissionDeposit::get();
+
+		/// The maximum number of referenda waiting in the queue of a track.
+		const MaxQueued: u32 = T::MaxQueued::get();
+
+		/// The period after which a referendum which never started deciding times out.
+		const UndecidingTimeout: T::BlockNumber = T::UndecidingTimeout::get();
+
+		/// The maximum period between two checks of a deciding referendum.
+		const AlarmInterval: T::BlockNumber = T::AlarmInterval::get();
+
+		/// The tracks of the referenda.
+		const Tracks: Vec<(TrackIdOf<T>, TrackInfoOf<T>)> = T::Tracks::tracks().to_vec();
+
+		fn deposit_event() = default;
+
+		/// Submit a referendum on a proposal. The track of the referendum is determined by the
+		/// origin the proposal is dispatched with.
+		///
+		/// The dispatch origin of this call must be _Signed_ and the sender must have funds to
+		/// cover the submission deposit.
+		///
+		/// - `proposal_origin`: The origin the proposal is dispatched with if approved.
+		/// - `proposal_hash`: The hash of the proposal preimage.
+		/// - `enactment_moment`: When the proposal is enacted if approved, no sooner than the
+		///   minimum enactment period of the track after the approval.
+		///
+		/// Emits `Submitted`.
+		///
+		/// # <weight>
+		/// - Db reads: `ReferendumCount`, `Alarms`, preimage request, reserved balance
+		/// - Db writes: `ReferendumCount`, `ReferendumInfoFor`, `Alarms`, preimage request,
+		///   reserved balance
+		/// # </weight>
+		#[weight = T::WeightInfo::submit()]
+		fn submit(origin,
+			proposal_origin: Box<T::PalletsOrigin>,
+			proposal_hash: T::Hash,
+			enactment_moment: DispatchTime<T::BlockNumber>,
+		) {
+			let who = ensure_signed(origin)?;
+			let track = T::Tracks::track_for(&proposal_origin).map_err(|_| Error::<T>::NoTrack)?;
+			let amount = T::SubmissionDeposit::get();
+			T::Currency::reserve(&who, amount)?;
+
+			let index = Self::referendum_count();
+			ReferendumCount::put(index + 1);
+			let now = system::Module::<T>::block_number();
+			let mut status = ReferendumStatus {
+				track,
+				origin: *proposal_origin,
+				proposal_hash,
+				enactment: enactment_moment,
+				submitted: now,
+				submission_deposit: Deposit { who, amount },
+				decision_deposit: None,
+				deciding: None,
+				tally: Default::default(),
+				in_queue: false,
+				alarm: None,
+			};
+			Self::set_alarm(index, &mut status, now.saturating_add(T::UndecidingTimeout::get()));
+			T::Preimages::request_preimage(&proposal_hash);
+			<ReferendumInfoFor<T>>::insert(index, ReferendumInfo::Ongoing(status));
+			Self::deposit_event(RawEvent::Submitted(index, track, proposal_hash));
+		}
+
+		/// Place the decision deposit of a referendum, allowing it to start deciding once the
+		/// prepare period of its track elapsed.
+		///
+		/// The dispatch origin of this call must be _Signed_ and the sender must have funds to
+		/// cover the decision deposit of the track of the referendum.
+		///
+		/// - `index`: The index of the referendum.
+		///
+		/// Emits `DecisionDepositPlaced`.
+		///
+		/// # <weight>
+		/// - Db reads: `ReferendumInfoFor`, `DecidingCount`, `TrackQueue`, `Alarms`,
+		///   reserved balance
+		/// - Db writes: `ReferendumInfoFor`, `DecidingCount` or `TrackQueue`, `Alarms`,
+		///   reserved balance
+		/// # </weight>
+		#[weight = T::WeightInfo::place_decision_deposit()]
+		fn place_decision_deposit(origin, #[compact] index: ReferendumIndex) {
+			let who = ensure_signed(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			ensure!(status.decision_deposit.is_none(), Error::<T>::HasDeposit);
+			let track = T::Tracks::info(status.track).ok_or(Error::<T>::BadTrack)?;
+			let amount = track.decision_deposit;
+			T::Currency::reserve(&who, amount)?;
+			status.decision_deposit = Some(Deposit { who: who.clone(), amount });
+			Self::deposit_event(RawEvent::DecisionDepositPlaced(index, who, amount));
+
+			let now = system::Module::<T>::block_number();
+			let info = Self::service_referendum(now, index, status);
+			<ReferendumInfoFor<T>>::insert(index, info);
+		}
+
+		/// Cancel an ongoing referendum, refunding its deposits.
+		///
+		/// The dispatch origin of this call must be `CancelOrigin`.
+		///
+		/// - `index`: The index of the referendum to cancel.
+		///
+		/// Emits `Cancelled`.
+		///
+		/// # <weight>
+		/// - Db reads: `ReferendumInfoFor`, `TrackQueue`, `DecidingCount`, preimage request,
+		///   reserved balances
+		/// - Db writes: `ReferendumInfoFor`, `TrackQueue` or `DecidingCount`, preimage request,
+		///   reserved balances
+		/// # </weight>
+		#[weight = T::WeightInfo::cancel()]
+		fn cancel(origin, #[compact] index: ReferendumIndex) {
+			T::CancelOrigin::ensure_origin(origin)?;
+			let status = Self::ensure_ongoing(index)?;
+			let now = system::Module::<T>::block_number();
+			Self::conclude(now, index, &status);
+			Self::refund_deposit(Some(&status.submission_deposit));
+			Self::refund_deposit(status.decision_deposit.as_ref());
+			<ReferendumInfoFor<T>>::insert(index, ReferendumInfo::Cancelled(now));
+			Self::deposit_event(RawEvent::Cancelled(index));
+		}
+
+		/// Kill an ongoing referendum, slashing its deposits.
+		///
+		/// The dispatch origin of this call must be `KillOrigin`.
+		///
+		/// - `index`: The index of the referendum to kill.
+		///
+		/// Emits `Killed`.
+		///
+		/// # <weight>
+		/// - Db reads: `ReferendumInfoFor`, `TrackQueue`, `DecidingCount`, preimage request,
+		///   reserved balances
+		/// - Db writes: `ReferendumInfoFor`, `TrackQueue` or `DecidingCount`, preimage request,
+		///   reserved balances
+		/// # </weight>
+		#[weight = T::WeightInfo::kill()]
+		fn kill(origin, #[compact] index: ReferendumIndex) {
+			T::KillOrigin::ensure_origin(origin)?;
+			let status = Self::ensure_ongoing(index)?;
+			let now = system::Module::<T>::block_number();
+			Self::conclude(now, index, &status);
+			Self::slash_deposit(Some(&status.submission_deposit));
+			Self::slash_deposit(status.decision_deposit.as_ref());
+			<ReferendumInfoFor<T>>::insert(index, ReferendumInfo::Killed(now));
+			Self::deposit_event(RawEvent::Killed(index));
+		}
+
+		/// Service the referenda whose alarm is set at this block.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let alarms = <Alarms<T>>::take(now);
+			let count = alarms.len() as u32;
+			for index in alarms {
+				if let Some(ReferendumInfo::Ongoing(status)) = Self::referendum_info(index) {
+					if status.alarm == Some(now) {
+						let info = Self::service_referendum(now, index, status);
+						<ReferendumInfoFor<T>>::insert(index, info);
+					}
+				}
+			}
+			T::WeightInfo::on_initialize(count)
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Ok if the given referendum is ongoing, returning its status.
+	pub fn ensure_ongoing(index: ReferendumIndex) -> Result<ReferendumStatusOf<T>, DispatchError> {
+		match Self::referendum_info(index) {
+			Some(ReferendumInfo::Ongoing(status)) => Ok(status),
+			_ => Err(Error::<T>::NotOngoing.into()),
+		}
+	}
+
+	/// Advance a referendum, returning its new info: start deciding if it is ready to, update its
+	/// confirmation, or conclude it.
+	fn service_referendum(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		mut status: ReferendumStatusOf<T>,
+	) -> ReferendumInfoOf<T> {
+		let track = match T::Tracks::info(status.track) {
+			Some(track) => track,
+			None => return ReferendumInfo::Ongoing(status),
+		};
+
+		if status.deciding.is_none() {
+			let prepare_end = status.submitted.saturating_add(track.prepare_period);
+			if status.decision_deposit.is_some() && now >= prepare_end && !status.in_queue {
+				if Self::deciding_count(status.track) < track.max_deciding {
+					<DecidingCount<T>>::mutate(status.track, |x| *x += 1);
+					Self::begin_deciding(index, &mut status, now);
+				} else {
+					Self::queue(index, &mut status);
+				}
+			}
+
+			if status.deciding.is_none() {
+				let timeout = status.submitted.saturating_add(T::UndecidingTimeout::get());
+				if now >= timeout {
+					Self::conclude(now, index, &status);
+					Self::refund_deposit(status.decision_deposit.as_ref());
+					Self::slash_deposit(Some(&status.submission_deposit));
+					Self::deposit_event(RawEvent::TimedOut(index));
+					return ReferendumInfo::TimedOut(now)
+				}
+
+				let alarm = if status.decision_deposit.is_some() && now < prepare_end {
+					prepare_end.min(timeout)
+				} else {
+					timeout
+				};
+				Self::set_alarm(index, &mut status, alarm);
+				return ReferendumInfo::Ongoing(status)
+			}
+		}
+
+		let mut deciding = match status.deciding.take() {
+			Some(deciding) => deciding,
+			None => return ReferendumInfo::Ongoing(status),
+		};
+		let elapsed = now.saturating_sub(deciding.since);
+		let x = Perbill::from_rational_approximation(elapsed, track.decision_period);
+		let passing = track.min_approval.passing(x, status.tally.approval())
+			&& track.min_support.passing(x, status.tally.support());
+		let decision_end = deciding.since.saturating_add(track.decision_period);
+
+		if passing {
+			match deciding.confirming {
+				Some(confirm_end) if now >= confirm_end => {
+					// `conclude` frees the deciding slot of the track only while `deciding` is set.
+					status.deciding = Some(deciding);
+					Self::schedule_enactment(now, index, &status, track);
+					Self::conclude(now, index, &status);
+					Self::refund_deposit(Some(&status.submission_deposit));
+					Self::refund_deposit(status.decision_deposit.as_ref());
+					Self::deposit_event(RawEvent::Approved(index));
+					return ReferendumInfo::Approved(now)
+				},
+				Some(_) => {},
+				None => {
+					deciding.confirming = Some(now.saturating_add(track.confirm_period));
+					Self::deposit_event(RawEvent::ConfirmStarted(index));
+				},
+			}
+		} else {
+			if deciding.confirming.take().is_some() {
+				Self::deposit_event(RawEvent::ConfirmAborted(index));
+			}
+			if now >= decision_end {
+				status.deciding = Some(deciding);
+				Self::conclude(now, index, &status);
+				Self::refund_deposit(Some(&status.submission_deposit));
+				Self::refund_deposit(status.decision_deposit.as_ref());
+				Self::deposit_event(RawEvent::Rejected(index));
+				return ReferendumInfo::Rejected(now)
+			}
+		}
+
+		let alarm = deciding.confirming
+			.unwrap_or(decision_end)
+			.min(now.saturating_add(T::AlarmInterval::get()));
+		status.deciding = Some(deciding);
+		Self::set_alarm(index, &mut status, alarm);
+		ReferendumInfo::Ongoing(status)
+	}
+
+	/// Start deciding a referendum. The deciding count of its track must already account for it.
+	fn begin_deciding(index: ReferendumIndex, status: &mut ReferendumStatusOf<T>, now: T::BlockNumber) {
+		status.in_queue = false;
+		status.deciding = Some(DecidingStatus { since: now, confirming: None });
+		Self::deposit_event(RawEvent::DecisionStarted(index, status.track));
+	}
+
+	/// Insert a referendum into the queue of its track. If the queue is full, the referendum with
+	/// the fewest ayes is evicted, which may be the given one.
+	fn queue(index: ReferendumIndex, status: &mut ReferendumStatusOf<T>) {
+		let ayes = status.tally.ayes();
+		let mut queue = Self::track_queue(status.track);
+		let mut pos = queue.binary_search_by_key(&ayes, |x| x.1).unwrap_or_else(|x| x);
+		if queue.len() as u32 >= T::MaxQueued::get() {
+			if pos == 0 {
+				return
+			}
+			let (evicted, _) = queue.remove(0);
+			<ReferendumInfoFor<T>>::mutate(evicted, |info| {
+				if let Some(ReferendumInfo::Ongoing(status)) = info {
+					status.in_queue = false;
+				}
+			});
+			pos -= 1;
+		}
+		queue.insert(pos, (index, ayes));
+		<TrackQueue<T>>::insert(status.track, queue);
+		status.in_queue = true;
+	}
+
+	/// Note that a referendum of `track` stopped deciding, letting the queued referendum with the
+	/// most ayes start deciding in its place.
+	fn note_one_fewer_deciding(now: T::BlockNumber, track: TrackIdOf<T>) {
+		let next = <TrackQueue<T>>::mutate(track, |queue| queue.pop());
+		match next.and_then(|(index, _)| Self::ensure_ongoing(index).ok().map(|s| (index, s))) {
+			Some((index, mut status)) => {
+				Self::begin_deciding(index, &mut status, now);
+				Self::set_alarm(index, &mut status, now.saturating_add(One::one()));
+				<ReferendumInfoFor<T>>::insert(index, ReferendumInfo::Ongoing(status));
+			},
+			None => <DecidingCount<T>>::mutate(track, |x| *x = x.saturating_sub(1)),
+		}
+	}
+
+	/// Take a concluding referendum out of its track, and stop requesting its preimage.
+	fn conclude(now: T::BlockNumber, index: ReferendumIndex, status: &ReferendumStatusOf<T>) {
+		if status.deciding.is_some() {
+			Self::note_one_fewer_deciding(now, status.track);
+		}
+		if status.in_queue {
+			<TrackQueue<T>>::mutate(status.track, |queue| queue.retain(|x| x.0 != index));
+		}
+		T::Preimages::unrequest_preimage(&status.proposal_hash);
+	}
+
+	/// Schedule the enactment of an approved referendum's proposal, no sooner than the minimum
+	/// enactment period of its track.
+	fn schedule_enactment(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		status: &ReferendumStatusOf<T>,
+		track: &TrackInfoOf<T>,
+	) {
+		let desired = match status.enactment {
+			DispatchTime::At(when) => when,
+			DispatchTime::After(delay) => now.saturating_add(delay),
+		};
+		let earliest = now.saturating_add(track.min_enactment_period).max(now.saturating_add(One::one()));
+		if T::Scheduler::schedule_named(
+			(REFERENDA_ID, index).encode(),
+			DispatchTime::At(desired.max(earliest)),
+			None,
+			63,
+			status.origin.clone(),
+			MaybeHashed::Hash(status.proposal_hash),
+		).is_err() {
+			frame_support::print("LOGIC ERROR: schedule_enactment/schedule_named failed");
+		}
+	}
+
+	/// Set the alarm of a referendum, no sooner than the next block.
+	fn set_alarm(index: ReferendumIndex, status: &mut ReferendumStatusOf<T>, when: T::BlockNumber) {
+		let next = system::Module::<T>::block_number().saturating_add(One::one());
+		let when = when.max(next);
+		if status.alarm != Some(when) {
+			status.alarm = Some(when);
+			<Alarms<T>>::mutate(when, |alarms| alarms.push(index));
+		}
+	}
+
+	/// Account for a change in the tally of an ongoing referendum: move it within the queue of its
+	/// track, or service it at the next block.
+	fn note_tally_changed(index: ReferendumIndex, status: &mut ReferendumStatusOf<T>) {
+		if status.in_queue {
+			let ayes = status.tally.ayes();
+			<TrackQueue<T>>::mutate(status.track, |queue| {
+				queue.retain(|x| x.0 != index);
+				let pos = queue.binary_search_by_key(&ayes, |x| x.1).unwrap_or_else(|x| x);
+				queue.insert(pos, (index, ayes));
+			});
+		} else if status.deciding.is_some() || status.decision_deposit.is_some() {
+			let next = system::Module::<T>::block_number().saturating_add(One::one());
+			if status.alarm.map_or(true, |alarm| alarm > next) {
+				Self::set_alarm(index, status, next);
+			}
+		}
+	}
+
+	fn refund_deposit(deposit: Option<&Deposit<T::AccountId, BalanceOf<T>>>) {
+		if let Some(Deposit { who, amount }) = deposit {
+			T::Currency::unreserve(who, *amount);
+		}
+	}
+
+	fn slash_deposit(deposit: Option<&Deposit<T::AccountId, BalanceOf<T>>>) {
+		if let Some(Deposit { who, amount }) = deposit {
+			if !amount.is_zero() {
+				T::Slash::on_unbalanced(T::Currency::slash_reserved(who, *amount).0);
+			}
+		}
+	}
+}
+
+impl<T: Trait> Polling<T::Tally> for Module<T> {
+	type Index = ReferendumIndex;
+	type Votes = T::Votes;
+	type Class = TrackIdOf<T>;
+	type Moment = T::BlockNumber;
+
+	fn classes() -> Vec<Self::Class> {
+		T::Tracks::tracks().iter().map(|x| x.0).collect()
+	}
+
+	fn as_ongoing(index: Self::Index) -> Option<(T::Tally, Self::Class)> {
+		Self::ensure_ongoing(index).ok().map(|status| (status.tally, status.track))
+	}
+
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut T::Tally, T::BlockNumber, Self::Class>) -> R,
+	) -> R {
+		match Self::referendum_info(index) {
+			Some(ReferendumInfo::Ongoing(mut status)) => {
+				let result = f(PollStatus::Ongoing(&mut status.tally, status.track));
+				Self::note_tally_changed(index, &mut status);
+				<ReferendumInfoFor<T>>::insert(index, ReferendumInfo::Ongoing(status));
+				result
+			},
+			Some(ReferendumInfo::Approved(end)) => f(PollStatus::Completed(end, true)),
+			Some(ReferendumInfo::Rejected(end)) => f(PollStatus::Completed(end, false)),
+			_ => f(PollStatus::None),
+		}
+	}
+
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut T::Tally, T::BlockNumber, Self::Class>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		match Self::referendum_info(index) {
+			Some(ReferendumInfo::Ongoing(mut status)) => {
+				let result = f(PollStatus::Ongoing(&mut status.tally, status.track))?;
+				Self::note_tally_changed(index, &mut status);
+				<ReferendumInfoFor<T>>::insert(index, ReferendumInfo::Ongoing(status));
+				Ok(result)
+			},
+			Some(ReferendumInfo::Approved(end)) => f(PollStatus::Completed(end, true)),
+			Some(ReferendumInfo::Rejected(end)) => f(PollStatus::Completed(end, false)),
+			_ => f(PollStatus::None),
+		}
+	}
+}
@@ -0,0 +1,587 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The crate's tests.
+
+use super::*;
+use codec::{Encode, Decode};
+use frame_support::{
+	impl_outer_origin, impl_outer_dispatch, impl_outer_event, assert_noop, assert_ok,
+	parameter_types, traits::OnInitialize, weights::Weight,
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup, BadOrigin, Hash},
+	testing::Header, Perbill, RuntimeDebug,
+};
+use frame_system::{EnsureRoot, RawOrigin};
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+		pallet_balances::Balances,
+	}
+}
+
+mod referenda {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		system<T>,
+		pallet_balances<T>,
+		pallet_scheduler<T>,
+		pallet_preimage<T>,
+		referenda<T>,
+	}
+}
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1_000_000;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
+	pub const NoPreimagePostponement: Option<u64> = Some(1);
+}
+impl pallet_scheduler::Trait for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type PreimageProvider = Preimage;
+	type NoPreimagePostponement = NoPreimagePostponement;
+	type WeightInfo = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+parameter_types! {
+	pub const PreimageMaxSize: u32 = 4096;
+	pub const PreimageBaseDeposit: u64 = 0;
+	pub const PreimageByteDeposit: u64 = 0;
+}
+impl pallet_preimage::Trait for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type MaxSize = PreimageMaxSize;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+	type WeightInfo = ();
+}
+
+/// A tally counting plain ayes and nays, supported by the ayes out of a turnout of 100.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct Tally {
+	pub ayes: u32,
+	pub nays: u32,
+}
+
+impl VoteTally<u32> for Tally {
+	fn ayes(&self) -> u32 {
+		self.ayes
+	}
+
+	fn support(&self) -> Perbill {
+		Perbill::from_rational_approximation(self.ayes, 100)
+	}
+
+	fn approval(&self) -> Perbill {
+		Perbill::from_rational_approximation(self.ayes, self.ayes + self.nays)
+	}
+}
+
+pub struct TestTracksInfo;
+impl TracksInfo<u64, u64> for TestTracksInfo {
+	type Id = u8;
+	type Origin = OriginCaller;
+
+	fn tracks() -> &'static [(u8, TrackInfo<u64, u64>)] {
+		static DATA: [(u8, TrackInfo<u64, u64>); 2] = [
+			(0, TrackInfo {
+				name: "root",
+				max_deciding: 1,
+				decision_deposit: 5,
+				prepare_period: 4,
+				decision_period: 4,
+				confirm_period: 2,
+				min_enactment_period: 4,
+				min_approval: Curve::LinearDecreasing {
+					begin: Perbill::from_percent(100),
+					end: Perbill::from_percent(50),
+					length: Perbill::from_percent(100),
+				},
+				min_support: Curve::LinearDecreasing {
+					begin: Perbill::from_percent(50),
+					end: Perbill::from_percent(0),
+					length: Perbill::from_percent(100),
+				},
+			}),
+			(1, TrackInfo {
+				name: "none",
+				max_deciding: 3,
+				decision_deposit: 1,
+				prepare_period: 2,
+				decision_period: 2,
+				confirm_period: 1,
+				min_enactment_period: 2,
+				min_approval: Curve::SteppedDecreasing {
+					begin: Perbill::from_percent(100),
+					end: Perbill::from_percent(50),
+					step: Perbill::from_percent(10),
+					period: Perbill::from_percent(10),
+				},
+				min_support: Curve::SteppedDecreasing {
+					begin: Perbill::from_percent(100),
+					end: Perbill::from_percent(0),
+					step: Perbill::from_percent(10),
+					period: Perbill::from_percent(10),
+				},
+			}),
+		];
+		&DATA[..]
+	}
+
+	fn track_for(origin: &OriginCaller) -> Result<u8, ()> {
+		match origin {
+			OriginCaller::system(RawOrigin::Root) => Ok(0),
+			OriginCaller::system(RawOrigin::None) => Ok(1),
+			_ => Err(()),
+		}
+	}
+}
+
+parameter_types! {
+	pub const SubmissionDeposit: u64 = 2;
+	pub const MaxQueued: u32 = 2;
+	pub const UndecidingTimeout: u64 = 20;
+	pub const AlarmInterval: u64 = 1;
+}
+impl super::Trait for Test {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = Balances;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type CancelOrigin = EnsureRoot<u64>;
+	type KillOrigin = EnsureRoot<u64>;
+	type Slash = ();
+	type Votes = u32;
+	type Tally = Tally;
+	type SubmissionDeposit = SubmissionDeposit;
+	type MaxQueued = MaxQueued;
+	type UndecidingTimeout = UndecidingTimeout;
+	type AlarmInterval = AlarmInterval;
+	type Tracks = TestTracksInfo;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test>{
+		balances: vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Scheduler = pallet_scheduler::Module<Test>;
+type Preimage = pallet_preimage::Module<Test>;
+type Referenda = Module<Test>;
+
+fn next_block() {
+	System::set_block_number(System::block_number() + 1);
+	Scheduler::on_initialize(System::block_number());
+	Referenda::on_initialize(System::block_number());
+}
+
+fn fast_forward_to(n: u64) {
+	while System::block_number() < n {
+		next_block();
+	}
+}
+
+fn set_balance_proposal(value: u64) -> Vec<u8> {
+	Call::Balances(pallet_balances::Call::set_balance(42, value, 0)).encode()
+}
+
+fn set_balance_proposal_hash(value: u64) -> H256 {
+	BlakeTwo256::hash(&set_balance_proposal(value)[..])
+}
+
+fn submit(who: u64, origin: RawOrigin<u64>, value: u64) -> ReferendumIndex {
+	let index = Referenda::referendum_count();
+	assert_ok!(Referenda::submit(
+		Origin::signed(who),
+		Box::new(origin.into()),
+		set_balance_proposal_hash(value),
+		DispatchTime::After(0),
+	));
+	index
+}
+
+fn set_tally(index: ReferendumIndex, ayes: u32, nays: u32) {
+	Referenda::access_poll(index, |status| {
+		if let PollStatus::Ongoing(tally, _) = status {
+			*tally = Tally { ayes, nays };
+		}
+	});
+}
+
+fn status(index: ReferendumIndex) -> ReferendumStatusOf<Test> {
+	Referenda::ensure_ongoing(index).unwrap()
+}
+
+fn is_deciding(index: ReferendumIndex) -> bool {
+	status(index).deciding.is_some()
+}
+
+fn has_event(event: RawEvent<u64, H256, u64, u8>) -> bool {
+	System::events().iter().any(|record| record.event == Event::referenda(event.clone()))
+}
+
+#[test]
+fn params_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Referenda::referendum_count(), 0);
+		assert_eq!(<Referenda as Polling<Tally>>::classes(), vec![0, 1]);
+		assert_eq!(TestTracksInfo::info(1).unwrap().name, "none");
+		assert!(TestTracksInfo::info(2).is_none());
+	});
+}
+
+#[test]
+fn linear_curve_should_work() {
+	let curve = Curve::LinearDecreasing {
+		begin: Perbill::from_percent(100),
+		end: Perbill::from_percent(50),
+		length: Perbill::from_percent(50),
+	};
+	assert_eq!(curve.threshold(Perbill::zero()), Perbill::from_percent(100));
+	assert_eq!(curve.threshold(Perbill::from_percent(25)), Perbill::from_percent(75));
+	assert_eq!(curve.threshold(Perbill::from_percent(50)), Perbill::from_percent(50));
+	assert_eq!(curve.threshold(Perbill::one()), Perbill::from_percent(50));
+	assert!(curve.passing(Perbill::from_percent(25), Perbill::from_percent(75)));
+	assert!(!curve.passing(Perbill::from_percent(25), Perbill::from_percent(74)));
+}
+
+#[test]
+fn stepped_curve_should_work() {
+	let curve = Curve::SteppedDecreasing {
+		begin: Perbill::from_percent(100),
+		end: Perbill::from_percent(70),
+		step: Perbill::from_percent(10),
+		period: Perbill::from_percent(25),
+	};
+	assert_eq!(curve.threshold(Perbill::zero()), Perbill::from_percent(100));
+	assert_eq!(curve.threshold(Perbill::from_percent(24)), Perbill::from_percent(100));
+	assert_eq!(curve.threshold(Perbill::from_percent(25)), Perbill::from_percent(90));
+	assert_eq!(curve.threshold(Perbill::from_percent(50)), Perbill::from_percent(80));
+	assert_eq!(curve.threshold(Perbill::one()), Perbill::from_percent(70));
+}
+
+#[test]
+fn submit_should_work() {
+	new_test_ext().execute_with(|| {
+		let h = set_balance_proposal_hash(2);
+		assert_noop!(
+			Referenda::submit(Origin::signed(1), Box::new(RawOrigin::Signed(1).into()), h, DispatchTime::After(0)),
+			Error::<Test>::NoTrack,
+		);
+		assert_eq!(submit(1, RawOrigin::None, 2), 0);
+		assert_eq!(Balances::reserved_balance(1), 2);
+		assert!(Preimage::preimage_requested(&h));
+		assert!(has_event(RawEvent::Submitted(0, 1, h)));
+
+		let s = status(0);
+		assert_eq!(s.track, 1);
+		assert_eq!(s.submitted, 1);
+		assert_eq!(s.alarm, Some(21));
+		assert!(s.deciding.is_none());
+	});
+}
+
+#[test]
+fn place_decision_deposit_should_work() {
+	new_test_ext().execute_with(|| {
+		submit(1, RawOrigin::Root, 2);
+		assert_noop!(Referenda::place_decision_deposit(Origin::signed(2), 1), Error::<Test>::NotOngoing);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		assert_noop!(Referenda::place_decision_deposit(Origin::signed(3), 0), Error::<Test>::HasDeposit);
+		assert_eq!(Balances::reserved_balance(2), 5);
+		assert!(has_event(RawEvent::DecisionDepositPlaced(0, 2, 5)));
+		// Serviced again once the prepare period is over.
+		assert_eq!(status(0).alarm, Some(5));
+	});
+}
+
+#[test]
+fn approval_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(6), set_balance_proposal(2)));
+		submit(1, RawOrigin::Root, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+
+		fast_forward_to(4);
+		assert!(!is_deciding(0));
+		next_block();
+		assert!(is_deciding(0));
+		assert_eq!(Referenda::deciding_count(0), 1);
+		assert!(has_event(RawEvent::DecisionStarted(0, 0)));
+
+		set_tally(0, 100, 0);
+		next_block();
+		assert_eq!(status(0).deciding, Some(DecidingStatus { since: 5, confirming: Some(8) }));
+		assert!(has_event(RawEvent::ConfirmStarted(0)));
+
+		fast_forward_to(8);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Approved(8)));
+		assert_eq!(Referenda::deciding_count(0), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		// Enacted after the minimum enactment period of the track.
+		fast_forward_to(11);
+		assert_eq!(Balances::free_balance(42), 0);
+		next_block();
+		assert_eq!(Balances::free_balance(42), 2);
+	});
+}
+
+#[test]
+fn rejection_should_work() {
+	new_test_ext().execute_with(|| {
+		submit(1, RawOrigin::Root, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		fast_forward_to(5);
+		set_tally(0, 40, 60);
+
+		fast_forward_to(8);
+		assert!(is_deciding(0));
+		next_block();
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Rejected(9)));
+		assert!(has_event(RawEvent::Rejected(0)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(!Preimage::preimage_requested(&set_balance_proposal_hash(2)));
+	});
+}
+
+#[test]
+fn confirmation_should_abort_when_no_longer_passing() {
+	new_test_ext().execute_with(|| {
+		submit(1, RawOrigin::Root, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		fast_forward_to(5);
+		set_tally(0, 100, 0);
+		next_block();
+		assert!(status(0).deciding.unwrap().confirming.is_some());
+
+		set_tally(0, 0, 100);
+		next_block();
+		assert!(status(0).deciding.unwrap().confirming.is_none());
+		assert!(has_event(RawEvent::ConfirmAborted(0)));
+
+		fast_forward_to(9);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Rejected(9)));
+	});
+}
+
+#[test]
+fn undeciding_referendum_should_time_out() {
+	new_test_ext().execute_with(|| {
+		submit(1, RawOrigin::Root, 2);
+		fast_forward_to(20);
+		assert!(!is_deciding(0));
+		next_block();
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::TimedOut(21)));
+		assert!(has_event(RawEvent::TimedOut(0)));
+		// The submission deposit is slashed.
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 8);
+	});
+}
+
+#[test]
+fn queued_referendum_should_start_deciding_when_slot_frees() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=3 {
+			let index = submit(who, RawOrigin::Root, who);
+			assert_ok!(Referenda::place_decision_deposit(Origin::signed(who), index));
+		}
+		fast_forward_to(5);
+		assert!(is_deciding(0));
+		assert!(status(1).in_queue && status(2).in_queue);
+		assert_eq!(Referenda::deciding_count(0), 1);
+
+		set_tally(2, 10, 0);
+		assert_eq!(Referenda::track_queue(0), vec![(1, 0), (2, 10)]);
+
+		assert_noop!(Referenda::cancel(Origin::signed(1), 0), BadOrigin);
+		assert_ok!(Referenda::cancel(Origin::root(), 0));
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Cancelled(5)));
+		assert!(is_deciding(2));
+		assert!(!status(2).in_queue);
+		assert_eq!(Referenda::track_queue(0), vec![(1, 0)]);
+		assert_eq!(Referenda::deciding_count(0), 1);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn queued_referendum_should_start_deciding_after_conclusion() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=2 {
+			let index = submit(who, RawOrigin::Root, who);
+			assert_ok!(Referenda::place_decision_deposit(Origin::signed(who), index));
+		}
+		fast_forward_to(5);
+		assert!(is_deciding(0));
+		assert!(status(1).in_queue);
+
+		// Rejection of the deciding referendum lets the queued one start.
+		set_tally(0, 40, 60);
+		fast_forward_to(9);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Rejected(9)));
+		assert!(is_deciding(1));
+		assert!(!status(1).in_queue);
+		assert_eq!(Referenda::track_queue(0), vec![]);
+		assert_eq!(Referenda::deciding_count(0), 1);
+
+		// Approval frees the slot for good.
+		set_tally(1, 100, 0);
+		fast_forward_to(12);
+		assert_eq!(Referenda::referendum_info(1), Some(ReferendumInfo::Approved(12)));
+		assert_eq!(Referenda::deciding_count(0), 0);
+
+		// A new referendum can start deciding again.
+		let index = submit(3, RawOrigin::Root, 3);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(3), index));
+		fast_forward_to(17);
+		assert!(is_deciding(index));
+		assert_eq!(Referenda::deciding_count(0), 1);
+	});
+}
+
+#[test]
+fn full_queue_should_evict_fewest_ayes() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=4 {
+			let index = submit(who, RawOrigin::Root, who);
+			assert_ok!(Referenda::place_decision_deposit(Origin::signed(who), index));
+		}
+		set_tally(1, 1, 0);
+		set_tally(3, 10, 0);
+		fast_forward_to(5);
+		assert!(is_deciding(0));
+		assert_eq!(Referenda::track_queue(0), vec![(1, 1), (3, 10)]);
+		assert!(!status(2).in_queue);
+		assert!(!is_deciding(2));
+
+		// Queued again once its tally beats the queue.
+		set_tally(2, 5, 0);
+		next_block();
+		assert_eq!(Referenda::track_queue(0), vec![(2, 5), (3, 10)]);
+		assert!(!status(1).in_queue);
+	});
+}
+
+#[test]
+fn kill_should_slash_deposits() {
+	new_test_ext().execute_with(|| {
+		submit(1, RawOrigin::Root, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		fast_forward_to(5);
+		assert_noop!(Referenda::kill(Origin::signed(1), 0), BadOrigin);
+		assert_ok!(Referenda::kill(Origin::root(), 0));
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Killed(5)));
+		assert_eq!(Referenda::deciding_count(0), 0);
+		assert_eq!(Balances::free_balance(1), 8);
+		assert_eq!(Balances::free_balance(2), 15);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_noop!(Referenda::kill(Origin::root(), 0), Error::<Test>::NotOngoing);
+	});
+}
+
+#[test]
+fn access_poll_should_report_completed() {
+	new_test_ext().execute_with(|| {
+		submit(1, RawOrigin::None, 2);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(1), 0));
+		fast_forward_to(3);
+		assert!(is_deciding(0));
+		set_tally(0, 100, 0);
+		fast_forward_to(5);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Approved(5)));
+		assert!(Referenda::access_poll(0, |status| status.ensure_ongoing().is_none()));
+		assert!(Referenda::access_poll(0, |status| status == PollStatus::Completed(5, true)));
+		assert!(Referenda::access_poll(1, |status| status == PollStatus::None));
+		assert_eq!(Referenda::as_ongoing(0), None);
+	});
+}
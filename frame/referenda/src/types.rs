@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Miscellaneous additional datatypes.
+
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, Perbill};
+use frame_support::{Parameter, traits::schedule::DispatchTime};
+use sp_runtime::traits::{Member, Saturating};
+
+/// A deposit placed by some account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Deposit<AccountId, Balance> {
+	/// The account which placed the deposit.
+	pub who: AccountId,
+	/// The amount of the deposit.
+	pub amount: Balance,
+}
+
+/// A curve giving the minimum value required for a referendum to pass, as a function of the
+/// proportion of the decision period elapsed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Curve {
+	/// Decreasing linearly from `begin` to `end` over the first `length` of the decision period,
+	/// staying at `end` afterwards.
+	LinearDecreasing { begin: Perbill, end: Perbill, length: Perbill },
+	/// Starting at `begin` and decreasing by `step` every `period` of the decision period, never
+	/// going below `end`.
+	SteppedDecreasing { begin: Perbill, end: Perbill, step: Perbill, period: Perbill },
+}
+
+impl Curve {
+	/// The minimum value required once `x` of the decision period has elapsed.
+	pub fn threshold(&self, x: Perbill) -> Perbill {
+		match *self {
+			Curve::LinearDecreasing { begin, end, length } => {
+				if x >= length || begin <= end {
+					return end
+				}
+				let progress = Perbill::from_rational_approximation(x.deconstruct(), length.deconstruct());
+				begin.saturating_sub(Perbill::from_parts(progress * begin.saturating_sub(end).deconstruct()))
+			},
+			Curve::SteppedDecreasing { begin, end, step, period } => {
+				if period.is_zero() {
+					return end
+				}
+				let steps = x.deconstruct() / period.deconstruct();
+				begin.saturating_sub(Perbill::from_parts(step.deconstruct().saturating_mul(steps)))
+					.max(end)
+			},
+		}
+	}
+
+	/// Whether `y` meets the curve once `x` of the decision period has elapsed.
+	pub fn passing(&self, x: Perbill, y: Perbill) -> bool {
+		y >= self.threshold(x)
+	}
+}
+
+/// The parameters of a track, i.e. the kind of referenda submitted with one of its origins.
+#[derive(Encode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TrackInfo<Balance, Moment> {
+	/// Name of this track.
+	pub name: &'static str,
+	/// The maximum number of referenda which may be deciding at once on this track.
+	pub max_deciding: u32,
+	/// The deposit to be placed by some account before a referendum can start deciding.
+	pub decision_deposit: Balance,
+	/// The minimum period a referendum waits after its submission before it can start deciding.
+	pub prepare_period: Moment,
+	/// The maximum period a referendum is deciding for before it is rejected.
+	pub decision_period: Moment,
+	/// The period for which a referendum must keep passing before it is approved.
+	pub confirm_period: Moment,
+	/// The minimum period between the approval of a referendum and the enactment of its proposal.
+	pub min_enactment_period: Moment,
+	/// The minimum approval, i.e. the proportion of aye votes, required over the decision period.
+	pub min_approval: Curve,
+	/// The minimum support, i.e. the proportion of the total issuance supporting the referendum,
+	/// required over the decision period.
+	pub min_support: Curve,
+}
+
+/// The tracks of the referenda.
+pub trait TracksInfo<Balance, Moment> {
+	/// The identifier of a track.
+	type Id: Parameter + Member + Ord + PartialOrd + Copy + 'static;
+
+	/// The origin whose proposals are sorted into tracks.
+	type Origin;
+
+	/// All the tracks, sorted by id.
+	fn tracks() -> &'static [(Self::Id, TrackInfo<Balance, Moment>)];
+
+	/// The track of the referenda whose proposal is dispatched with `origin`.
+	fn track_for(origin: &Self::Origin) -> Result<Self::Id, ()>;
+
+	/// The parameters of the track `id`.
+	fn info(id: Self::Id) -> Option<&'static TrackInfo<Balance, Moment>> {
+		Self::tracks()
+			.binary_search_by_key(&id, |x| x.0)
+			.ok()
+			.map(|i| &Self::tracks()[i].1)
+	}
+}
+
+/// The status of a deciding referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DecidingStatus<BlockNumber> {
+	/// When the referendum started deciding.
+	pub since: BlockNumber,
+	/// When the referendum is approved, if it is passing since long enough.
+	pub confirming: Option<BlockNumber>,
+}
+
+/// Info regarding an ongoing referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReferendumStatus<TrackId, Origin, BlockNumber, Hash, Balance, Tally, AccountId> {
+	/// The track of the referendum.
+	pub track: TrackId,
+	/// The origin the proposal is dispatched with.
+	pub origin: Origin,
+	/// The hash of the proposal being voted on.
+	pub proposal_hash: Hash,
+	/// When the proposal is enacted if approved.
+	pub enactment: DispatchTime<BlockNumber>,
+	/// When the referendum was submitted.
+	pub submitted: BlockNumber,
+	/// The deposit placed when submitting the referendum.
+	pub submission_deposit: Deposit<AccountId, Balance>,
+	/// The deposit placed for the referendum to start deciding.
+	pub decision_deposit: Option<Deposit<AccountId, Balance>>,
+	/// The status of the decision, if the referendum is deciding.
+	pub deciding: Option<DecidingStatus<BlockNumber>>,
+	/// The current tally of votes in this referendum.
+	pub tally: Tally,
+	/// Whether the referendum is waiting in the queue of its track to start deciding.
+	pub in_queue: bool,
+	/// When the referendum is next serviced.
+	pub alarm: Option<BlockNumber>,
+}
+
+/// Info regarding a referendum, present or past.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ReferendumInfo<TrackId, Origin, BlockNumber, Hash, Balance, Tally, AccountId> {
+	/// Referendum has been submitted and is being voted on.
+	Ongoing(ReferendumStatus<TrackId, Origin, BlockNumber, Hash, Balance, Tally, AccountId>),
+	/// Referendum finished at the given block with approval.
+	Approved(BlockNumber),
+	/// Referendum finished at the given block with rejection.
+	Rejected(BlockNumber),
+	/// Referendum was cancelled at the given block.
+	Cancelled(BlockNumber),
+	/// Referendum timed out at the given block without starting to decide.
+	TimedOut(BlockNumber),
+	/// Referendum was killed at the given block.
+	Killed(BlockNumber),
+}
@@ -28,6 +28,11 @@
 //! specified block number or at a specified period. These scheduled dispatches
 //! may be named or anonymous and may be canceled.
 //!
+//! A call may be given either in full or by the hash of its encoding. In the
+//! latter case the preimage is requested from `Trait::PreimageProvider` when
+//! scheduled and looked up when the call is due; if it is not yet available
+//! the dispatch is postponed by `Trait::NoPreimagePostponement` blocks.
+//!
 //! **NOTE:** The scheduled calls will be dispatched with the default filter
 //! for the origin: namely `frame_system::Trait::BaseCallFilter` for all origin
 //! except root which will get no filter. And not the filter contained in origin
@@ -59,7 +64,10 @@ use sp_runtime::{RuntimeDebug, traits::{Zero, One, BadOrigin, Saturating}};
 use frame_support::{
 	decl_module, decl_storage, decl_event, decl_error, IterableStorageMap,
 	dispatch::{Dispatchable, DispatchError, DispatchResult, Parameter},
-	traits::{Get, schedule::{self, DispatchTime}, OriginTrait, EnsureOrigin, IsType},
+	traits::{
+		Get, schedule::{self, DispatchTime, MaybeHashed}, OriginTrait, EnsureOrigin, IsType,
+		PreimageProvider,
+	},
 	weights::{GetDispatchInfo, Weight},
 };
 use frame_system::{self as system};
@@ -106,6 +114,13 @@ pub trait Trait: system::Trait {
 	/// Required origin to schedule or cancel calls.
 	type ScheduleOrigin: EnsureOrigin<<Self as system::Trait>::Origin>;
 
+	/// The preimage provider with which we look up call hashes to get the call.
+	type PreimageProvider: PreimageProvider<Self::Hash>;
+
+	/// If `Some` then the number of blocks to postpone execution for when the item is delayed
+	/// because its preimage is not yet available. If `None`, such items are dropped.
+	type NoPreimagePostponement: Get<Option<Self::BlockNumber>>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -114,6 +129,8 @@ pub trait Trait: system::Trait {
 pub type PeriodicIndex = u32;
 /// The location of a scheduled task that can be used to remove it.
 pub type TaskAddress<BlockNumber> = (BlockNumber, u32);
+/// A call which is either given in full or referred to by the hash of its preimage.
+pub type CallOrHashOf<T> = MaybeHashed<<T as Trait>::Call, <T as system::Trait>::Hash>;
 
 #[cfg_attr(any(feature = "std", test), derive(PartialEq, Eq))]
 #[derive(Clone, RuntimeDebug, Encode, Decode)]
@@ -127,7 +144,7 @@ struct ScheduledV1<Call, BlockNumber> {
 /// Information regarding an item to be executed in the future.
 #[cfg_attr(any(feature = "std", test), derive(PartialEq, Eq))]
 #[derive(Clone, RuntimeDebug, Encode, Decode)]
-pub struct ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> {
+pub struct ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId> {
 	/// The unique identity for this task, if there is one.
 	maybe_id: Option<Vec<u8>>,
 	/// This task's priority.
@@ -141,8 +158,31 @@ pub struct ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> {
 	_phantom: PhantomData<AccountId>,
 }
 
+/// The layout of `ScheduledV2` is identical to `ScheduledV3`; only the call changes from being a
+/// plain `Call` to a `CallOrHashOf<T>`.
+pub type ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> =
+	ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId>;
+
 /// The current version of Scheduled struct.
-pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> = ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId>;
+pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> =
+	ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId>;
+
+/// The current version of Scheduled struct, for a given runtime.
+pub type ScheduledOf<T> = Scheduled<
+	CallOrHashOf<T>,
+	<T as system::Trait>::BlockNumber,
+	<T as Trait>::PalletsOrigin,
+	<T as system::Trait>::AccountId,
+>;
+
+/// The reason why a call scheduled by hash could not be dispatched.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum LookupError {
+	/// A call of this hash was not known.
+	Unknown,
+	/// The preimage for this hash was known but could not be decoded into a `Call`.
+	BadFormat,
+}
 
 // A value placed in storage that represents the current version of the Scheduler storage.
 // This value is used by the `on_runtime_upgrade` logic to determine whether we run
@@ -151,6 +191,7 @@ pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> = ScheduledV2<Ca
 enum Releases {
 	V1,
 	V2,
+	V3,
 }
 
 impl Default for Releases {
@@ -162,8 +203,7 @@ impl Default for Releases {
 decl_storage! {
 	trait Store for Module<T: Trait> as Scheduler {
 		/// Items to be executed, indexed by the block number that they should be executed on.
-		pub Agenda: map hasher(twox_64_concat) T::BlockNumber
-			=> Vec<Option<Scheduled<<T as Trait>::Call, T::BlockNumber, T::PalletsOrigin, T::AccountId>>>;
+		pub Agenda: map hasher(twox_64_concat) T::BlockNumber => Vec<Option<ScheduledOf<T>>>;
 
 		/// Lookup from identity to the block number and index of the task.
 		Lookup: map hasher(twox_64_concat) Vec<u8> => Option<TaskAddress<T::BlockNumber>>;
@@ -171,7 +211,7 @@ decl_storage! {
 		/// Storage version of the pallet.
 		///
 		/// New networks start with last version.
-		StorageVersion build(|_| Releases::V2): Releases;
+		StorageVersion build(|_| Releases::V3): Releases;
 	}
 }
 
@@ -183,6 +223,9 @@ decl_event!(
 		Canceled(BlockNumber, u32),
 		/// Dispatched some task. [task, id, result]
 		Dispatched(TaskAddress<BlockNumber>, Option<Vec<u8>>, DispatchResult),
+		/// The call for the provided hash was not found so the task has been aborted or
+		/// postponed. [task, id, error]
+		CallLookupFailed(TaskAddress<BlockNumber>, Option<Vec<u8>>, LookupError),
 	}
 );
 
@@ -203,6 +246,14 @@ decl_module! {
 		type Error = Error<T>;
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			if Self::migrate_v1_to_v3() || Self::migrate_v2_to_v3() {
+				T::MaximumBlockWeight::get()
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+
 		/// Anonymously schedule a task.
 		///
 		/// # <weight>
@@ -218,7 +269,7 @@ decl_module! {
 			when: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Trait>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Trait>::Origin::from(origin);
@@ -258,7 +309,7 @@ decl_module! {
 			when: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Trait>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Trait>::Origin::from(origin);
@@ -294,7 +345,7 @@ decl_module! {
 			after: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Trait>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Trait>::Origin::from(origin);
@@ -314,7 +365,7 @@ decl_module! {
 			after: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Trait>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Trait>::Origin::from(origin);
@@ -336,19 +387,24 @@ decl_module! {
 		/// # </weight>
 		fn on_initialize(now: T::BlockNumber) -> Weight {
 			let limit = T::MaximumWeight::get();
-			let mut queued = Agenda::<T>::take(now).into_iter()
+			let agenda = Agenda::<T>::take(now).into_iter()
 				.enumerate()
 				.filter_map(|(index, s)| s.map(|inner| (index as u32, inner)))
 				.collect::<Vec<_>>();
-			queued.sort_by_key(|(_, s)| s.priority);
+			let lookups = agenda.iter().filter(|(_, s)| s.call.as_hash().is_some()).count() as Weight;
+			let mut queued = agenda.into_iter()
+				.filter_map(|(index, s)| Self::resolve_call(now, index, s).map(|(call, s)| (index, call, s)))
+				.collect::<Vec<_>>();
+			queued.sort_by_key(|(_, _, s)| s.priority);
 			let base_weight: Weight = T::DbWeight::get().reads_writes(1, 2) // Agenda + Agenda(next)
+				.saturating_add(T::DbWeight::get().reads_writes(lookups, lookups)) // Preimage lookups
 				.saturating_add(10_000_000); // Base Weight
 			let mut total_weight: Weight = 0;
 			queued.into_iter()
 				.enumerate()
-				.scan(base_weight, |cumulative_weight, (order, (index, s))| {
+				.scan(base_weight, |cumulative_weight, (order, (index, call, s))| {
 					*cumulative_weight = cumulative_weight
-						.saturating_add(s.call.get_dispatch_info().weight)
+						.saturating_add(call.get_dispatch_info().weight)
 						.saturating_add(25_000_000); // Base multiplier
 
 					if s.maybe_id.is_some() {
@@ -360,15 +416,15 @@ decl_module! {
 						*cumulative_weight = cumulative_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
 					}
 
-					Some((order, index, *cumulative_weight, s))
+					Some((order, index, *cumulative_weight, call, s))
 				})
-				.filter_map(|(order, index, cumulative_weight, mut s)| {
+				.filter_map(|(order, index, cumulative_weight, call, mut s)| {
 					// We allow a scheduled call if any is true:
 					// - It's priority is `HARD_DEADLINE`
 					// - It does not push the weight past the limit.
 					// - It is the first item in the schedule
 					if s.priority <= schedule::HARD_DEADLINE || cumulative_weight <= limit || order == 0 {
-						let r = s.call.clone().dispatch(s.origin.clone().into());
+						let r = call.dispatch(s.origin.clone().into());
 						let maybe_id = s.maybe_id.clone();
 						if let &Some((period, count)) = &s.maybe_periodic {
 							if count > 1 {
@@ -410,21 +466,21 @@ decl_module! {
 }
 
 impl<T: Trait> Module<T> {
-	/// Migrate storage format from V1 to V2.
+	/// Migrate storage format from V1 to V3.
 	/// Return true if migration is performed.
-	pub fn migrate_v1_to_t2() -> bool {
+	pub fn migrate_v1_to_v3() -> bool {
 		if StorageVersion::get() == Releases::V1 {
-			StorageVersion::put(Releases::V2);
+			StorageVersion::put(Releases::V3);
 
 			Agenda::<T>::translate::<
 				Vec<Option<ScheduledV1<<T as Trait>::Call, T::BlockNumber>>>, _
 			>(|_, agenda| Some(
 				agenda
 					.into_iter()
-					.map(|schedule| schedule.map(|schedule| ScheduledV2 {
+					.map(|schedule| schedule.map(|schedule| ScheduledV3 {
 						maybe_id: schedule.maybe_id,
 						priority: schedule.priority,
-						call: schedule.call,
+						call: schedule.call.into(),
 						maybe_periodic: schedule.maybe_periodic,
 						origin: system::RawOrigin::Root.into(),
 						_phantom: Default::default(),
@@ -438,12 +494,83 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
+	/// Migrate storage format from V2 to V3.
+	/// Return true if migration is performed.
+	pub fn migrate_v2_to_v3() -> bool {
+		if StorageVersion::get() == Releases::V2 {
+			StorageVersion::put(Releases::V3);
+
+			Agenda::<T>::translate::<
+				Vec<Option<ScheduledV2<<T as Trait>::Call, T::BlockNumber, T::PalletsOrigin, T::AccountId>>>, _
+			>(|_, agenda| Some(
+				agenda
+					.into_iter()
+					.map(|schedule| schedule.map(|schedule| ScheduledV3 {
+						maybe_id: schedule.maybe_id,
+						priority: schedule.priority,
+						call: schedule.call.into(),
+						maybe_periodic: schedule.maybe_periodic,
+						origin: schedule.origin,
+						_phantom: Default::default(),
+					}))
+					.collect::<Vec<_>>()
+			));
+
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Resolve the call of a task which was scheduled by hash through `T::PreimageProvider`.
+	///
+	/// If the preimage is unavailable the task is postponed by `T::NoPreimagePostponement` blocks,
+	/// or dropped if that is `None`. A preimage that cannot be decoded always drops the task.
+	fn resolve_call(
+		now: T::BlockNumber,
+		index: u32,
+		mut s: ScheduledOf<T>,
+	) -> Option<(<T as Trait>::Call, ScheduledOf<T>)> {
+		let (call, maybe_completed) = s.call.resolved::<T::PreimageProvider>();
+		s.call = call;
+		if let Some(completed) = maybe_completed {
+			T::PreimageProvider::unrequest_preimage(&completed);
+		}
+		if let Some(call) = s.call.as_value().cloned() {
+			return Some((call, s))
+		}
+
+		let error = match s.call.as_hash() {
+			Some(hash) if T::PreimageProvider::have_preimage(hash) => LookupError::BadFormat,
+			_ => LookupError::Unknown,
+		};
+		Self::deposit_event(RawEvent::CallLookupFailed((now, index), s.maybe_id.clone(), error));
+
+		match (error, T::NoPreimagePostponement::get()) {
+			(LookupError::Unknown, Some(delay)) => {
+				let until = now.saturating_add(delay.max(One::one()));
+				if let Some(ref id) = s.maybe_id {
+					let until_index = Agenda::<T>::decode_len(until).unwrap_or(0);
+					Lookup::<T>::insert(id, (until, until_index as u32));
+				}
+				Agenda::<T>::append(until, Some(s));
+			},
+			_ => {
+				if let Some(ref id) = s.maybe_id {
+					Lookup::<T>::remove(id);
+				}
+				s.call.ensure_unrequested::<T::PreimageProvider>();
+			},
+		}
+		None
+	}
+
 	fn do_schedule(
 		when: DispatchTime<T::BlockNumber>,
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Trait>::Call
+		call: CallOrHashOf<T>,
 	) -> Result<TaskAddress<T::BlockNumber>, DispatchError> {
 		let now = frame_system::Module::<T>::block_number();
 
@@ -461,6 +588,7 @@ impl<T: Trait> Module<T> {
 			.filter(|p| p.1 > 1 && !p.0.is_zero())
 			// Remove one from the number of repetitions since we will schedule one now.
 			.map(|(p, c)| (p, c - 1));
+		call.ensure_requested::<T::PreimageProvider>();
 		let s = Some(Scheduled {
 			maybe_id: None, priority, call, maybe_periodic, origin, _phantom: PhantomData::<T::AccountId>::default(),
 		});
@@ -490,6 +618,7 @@ impl<T: Trait> Module<T> {
 			},
 		)?;
 		if let Some(s) = scheduled {
+			s.call.ensure_unrequested::<T::PreimageProvider>();
 			if let Some(id) = s.maybe_id {
 				Lookup::<T>::remove(id);
 			}
@@ -506,7 +635,7 @@ impl<T: Trait> Module<T> {
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Trait>::Call,
+		call: CallOrHashOf<T>,
 	) -> Result<TaskAddress<T::BlockNumber>, DispatchError> {
 		// ensure id it is unique
 		if Lookup::<T>::contains_key(&id) {
@@ -530,6 +659,7 @@ impl<T: Trait> Module<T> {
 			// Remove one from the number of repetitions since we will schedule one now.
 			.map(|(p, c)| (p, c - 1));
 
+		call.ensure_requested::<T::PreimageProvider>();
 		let s = Scheduled {
 			maybe_id: Some(id.clone()), priority, call, maybe_periodic, origin, _phantom: Default::default()
 		};
@@ -553,7 +683,9 @@ impl<T: Trait> Module<T> {
 								return Err(BadOrigin.into());
 							}
 						}
-						*s = None;
+						if let Some(s) = s.take() {
+							s.call.ensure_unrequested::<T::PreimageProvider>();
+						}
 					}
 					Ok(())
 				})?;
@@ -566,7 +698,7 @@ impl<T: Trait> Module<T> {
 	}
 }
 
-impl<T: Trait> schedule::Anon<T::BlockNumber, <T as Trait>::Call, T::PalletsOrigin> for Module<T> {
+impl<T: Trait> schedule::Anon<T::BlockNumber, CallOrHashOf<T>, T::PalletsOrigin> for Module<T> {
 	type Address = TaskAddress<T::BlockNumber>;
 
 	fn schedule(
@@ -574,7 +706,7 @@ impl<T: Trait> schedule::Anon<T::BlockNumber, <T as Trait>::Call, T::PalletsOrig
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Trait>::Call
+		call: CallOrHashOf<T>,
 	) -> Result<Self::Address, DispatchError> {
 		Self::do_schedule(when, maybe_periodic, priority, origin, call)
 	}
@@ -584,7 +716,7 @@ impl<T: Trait> schedule::Anon<T::BlockNumber, <T as Trait>::Call, T::PalletsOrig
 	}
 }
 
-impl<T: Trait> schedule::Named<T::BlockNumber, <T as Trait>::Call, T::PalletsOrigin> for Module<T> {
+impl<T: Trait> schedule::Named<T::BlockNumber, CallOrHashOf<T>, T::PalletsOrigin> for Module<T> {
 	type Address = TaskAddress<T::BlockNumber>;
 
 	fn schedule_named(
@@ -593,7 +725,7 @@ impl<T: Trait> schedule::Named<T::BlockNumber, <T as Trait>::Call, T::PalletsOri
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Trait>::Call,
+		call: CallOrHashOf<T>,
 	) -> Result<Self::Address, ()> {
 		Self::do_schedule_named(id, when, maybe_periodic, priority, origin, call).map_err(|_| ())
 	}
@@ -610,14 +742,14 @@ mod tests {
 	use frame_support::{
 		impl_outer_event, impl_outer_origin, impl_outer_dispatch, parameter_types, assert_ok, ord_parameter_types,
 		assert_noop, assert_err, Hashable,
-		traits::{OnInitialize, OnFinalize, Filter},
+		traits::{OnInitialize, OnFinalize, OnRuntimeUpgrade, Filter},
 		weights::constants::RocksDbWeight,
 	};
 	use sp_core::H256;
 	use sp_runtime::{
 		Perbill,
 		testing::Header,
-		traits::{BlakeTwo256, IdentityLookup},
+		traits::{BlakeTwo256, IdentityLookup, Hash},
 	};
 	use frame_system::{EnsureOneOf, EnsureRoot, EnsureSignedBy};
 	use crate as scheduler;
@@ -667,6 +799,54 @@ mod tests {
 		}
 	}
 
+	mod preimages {
+		use super::*;
+		use std::{cell::RefCell, collections::BTreeMap};
+
+		thread_local! {
+			static PREIMAGES: RefCell<BTreeMap<H256, Vec<u8>>> = RefCell::new(BTreeMap::new());
+			static REQUESTS: RefCell<BTreeMap<H256, u32>> = RefCell::new(BTreeMap::new());
+		}
+
+		/// A simple in-memory preimage provider which counts requests.
+		pub struct Preimages;
+		impl Preimages {
+			pub fn note(bytes: Vec<u8>) -> H256 {
+				let hash = BlakeTwo256::hash(&bytes[..]);
+				PREIMAGES.with(|p| p.borrow_mut().insert(hash, bytes));
+				hash
+			}
+			pub fn requests(hash: &H256) -> u32 {
+				REQUESTS.with(|r| r.borrow().get(hash).cloned().unwrap_or(0))
+			}
+		}
+		impl PreimageProvider<H256> for Preimages {
+			fn have_preimage(hash: &H256) -> bool {
+				PREIMAGES.with(|p| p.borrow().contains_key(hash))
+			}
+			fn get_preimage(hash: &H256) -> Option<Vec<u8>> {
+				PREIMAGES.with(|p| p.borrow().get(hash).cloned())
+			}
+			fn preimage_requested(hash: &H256) -> bool {
+				Self::requests(hash) > 0
+			}
+			fn request_preimage(hash: &H256) {
+				REQUESTS.with(|r| *r.borrow_mut().entry(*hash).or_insert(0) += 1);
+			}
+			fn unrequest_preimage(hash: &H256) {
+				REQUESTS.with(|r| {
+					let mut r = r.borrow_mut();
+					if let Some(count) = r.get_mut(hash) {
+						*count -= 1;
+						if *count == 0 {
+							r.remove(hash);
+						}
+					}
+				});
+			}
+		}
+	}
+
 	impl_outer_origin! {
 		pub enum Origin for Test where system = frame_system {}
 	}
@@ -734,6 +914,7 @@ mod tests {
 	}
 	parameter_types! {
 		pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
+		pub const NoPreimagePostponement: Option<u64> = Some(2);
 	}
 	ord_parameter_types! {
 		pub const One: u64 = 1;
@@ -746,6 +927,8 @@ mod tests {
 		type Call = Call;
 		type MaximumWeight = MaximumSchedulerWeight;
 		type ScheduleOrigin = EnsureOneOf<u64, EnsureRoot<u64>, EnsureSignedBy<One, u64>>;
+		type PreimageProvider = preimages::Preimages;
+		type NoPreimagePostponement = NoPreimagePostponement;
 		type WeightInfo = ();
 	}
 	type System = system::Module<Test>;
@@ -774,7 +957,7 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			let call = Call::Logger(logger::Call::log(42, 1000));
 			assert!(!<Test as frame_system::Trait>::BaseCallFilter::filter(&call));
-			let _ = Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call);
+			let _ = Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call.into());
 			run_to_block(3);
 			assert!(logger::log().is_empty());
 			run_to_block(4);
@@ -790,7 +973,7 @@ mod tests {
 			run_to_block(2);
 			let call = Call::Logger(logger::Call::log(42, 1000));
 			assert!(!<Test as frame_system::Trait>::BaseCallFilter::filter(&call));
-			let _ = Scheduler::do_schedule(DispatchTime::After(3), None, 127, root(), call);
+			let _ = Scheduler::do_schedule(DispatchTime::After(3), None, 127, root(), call.into());
 			run_to_block(4);
 			assert!(logger::log().is_empty());
 			run_to_block(5);
@@ -800,12 +983,88 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn scheduling_with_preimages_works() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(logger::Call::log(42, 1000));
+			let hash = preimages::Preimages::note(call.encode());
+			assert_ok!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)));
+			assert_eq!(preimages::Preimages::requests(&hash), 1);
+			run_to_block(3);
+			assert!(logger::log().is_empty());
+			run_to_block(4);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			// The preimage is no longer needed once the call is resolved.
+			assert_eq!(preimages::Preimages::requests(&hash), 0);
+			run_to_block(100);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		});
+	}
+
+	#[test]
+	fn scheduling_with_missing_preimage_postpones() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(logger::Call::log(42, 1000));
+			let hash = <Test as system::Trait>::Hashing::hash_of(&call);
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)
+			));
+			run_to_block(4);
+			assert!(logger::log().is_empty());
+			// Postponed by `NoPreimagePostponement` blocks and the lookup follows it.
+			assert_eq!(Agenda::<Test>::get(6).len(), 1);
+			assert_eq!(Lookup::<Test>::get(1u32.encode()), Some((6, 0)));
+			assert_eq!(preimages::Preimages::requests(&hash), 1);
+
+			preimages::Preimages::note(call.encode());
+			run_to_block(6);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			assert_eq!(preimages::Preimages::requests(&hash), 0);
+			assert!(Lookup::<Test>::get(1u32.encode()).is_none());
+		});
+	}
+
+	#[test]
+	fn scheduling_with_bad_preimage_is_dropped() {
+		new_test_ext().execute_with(|| {
+			let hash = preimages::Preimages::note(vec![0xff; 4]);
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)
+			));
+			run_to_block(4);
+			assert!(logger::log().is_empty());
+			assert!(Agenda::<Test>::get(6).is_empty());
+			assert!(Lookup::<Test>::get(1u32.encode()).is_none());
+			assert_eq!(preimages::Preimages::requests(&hash), 0);
+		});
+	}
+
+	#[test]
+	fn cancel_unrequests_preimage() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(logger::Call::log(42, 1000));
+			let hash = preimages::Preimages::note(call.encode());
+			let i = Scheduler::do_schedule(
+				DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)
+			).unwrap();
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)
+			));
+			assert_eq!(preimages::Preimages::requests(&hash), 2);
+			assert_ok!(Scheduler::do_cancel(None, i));
+			assert_ok!(Scheduler::do_cancel_named(None, 1u32.encode()));
+			assert_eq!(preimages::Preimages::requests(&hash), 0);
+			run_to_block(100);
+			assert!(logger::log().is_empty());
+		});
+	}
+
 	#[test]
 	fn periodic_scheduling_works() {
 		new_test_ext().execute_with(|| {
 			// at #4, every 3 blocks, 3 times.
 			let _ = Scheduler::do_schedule(
-				DispatchTime::At(4), Some((3, 3)), 127, root(), Call::Logger(logger::Call::log(42, 1000))
+				DispatchTime::At(4), Some((3, 3)), 127, root(), Call::Logger(logger::Call::log(42, 1000)).into()
 			);
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -829,10 +1088,10 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			// at #4.
 			Scheduler::do_schedule_named(
-				1u32.encode(), DispatchTime::At(4), None, 127, root(), Call::Logger(logger::Call::log(69, 1000))
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), Call::Logger(logger::Call::log(69, 1000)).into()
 			).unwrap();
 			let i = Scheduler::do_schedule(
-				DispatchTime::At(4), None, 127, root(), Call::Logger(logger::Call::log(42, 1000))
+				DispatchTime::At(4), None, 127, root(), Call::Logger(logger::Call::log(42, 1000)).into()
 			).unwrap();
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -853,7 +1112,7 @@ mod tests {
 				Some((3, 3)),
 				127,
 				root(),
-				Call::Logger(logger::Call::log(42, 1000))
+				Call::Logger(logger::Call::log(42, 1000)).into()
 			).unwrap();
 			// same id results in error.
 			assert!(Scheduler::do_schedule_named(
@@ -862,11 +1121,11 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(logger::Call::log(69, 1000))
+				Call::Logger(logger::Call::log(69, 1000)).into()
 			).is_err());
 			// different id is ok.
 			Scheduler::do_schedule_named(
-				2u32.encode(), DispatchTime::At(8), None, 127, root(), Call::Logger(logger::Call::log(69, 1000))
+				2u32.encode(), DispatchTime::At(8), None, 127, root(), Call::Logger(logger::Call::log(69, 1000)).into()
 			).unwrap();
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -887,13 +1146,13 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 2)).into()
 			);
 			let _ = Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				127,
-				root(), Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2))
+				root(), Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			);
 			// 69 and 42 do not fit together
 			run_to_block(4);
@@ -911,14 +1170,14 @@ mod tests {
 				None,
 				0,
 				root(),
-				Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 2)).into()
 			);
 			let _ = Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				0,
 				root(),
-				Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			);
 			// With base weights, 69 and 42 should not fit together, but do because of hard deadlines
 			run_to_block(4);
@@ -934,14 +1193,14 @@ mod tests {
 				None,
 				1,
 				root(),
-				Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 2)).into()
 			);
 			let _ = Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				0,
 				root(),
-				Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			);
 			run_to_block(4);
 			assert_eq!(logger::log(), vec![(root(), 69u32), (root(), 42u32)]);
@@ -955,19 +1214,19 @@ mod tests {
 				DispatchTime::At(4),
 				None,
 				255,
-				root(), Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 3))
+				root(), Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 3)).into()
 			);
 			let _ = Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				127,
-				root(), Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2))
+				root(), Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			);
 			let _ = Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				126,
-				root(), Call::Logger(logger::Call::log(2600, MaximumSchedulerWeight::get() / 2))
+				root(), Call::Logger(logger::Call::log(2600, MaximumSchedulerWeight::get() / 2)).into()
 			);
 
 			// 2600 does not fit with 69 or 42, but has higher priority, so will go through
@@ -991,7 +1250,7 @@ mod tests {
 			assert_ok!(
 				Scheduler::do_schedule_named(
 					1u32.encode(), DispatchTime::At(1), None, 255, root(),
-					Call::Logger(logger::Call::log(3, MaximumSchedulerWeight::get() / 3))
+					Call::Logger(logger::Call::log(3, MaximumSchedulerWeight::get() / 3)).into()
 				)
 			);
 			// Anon Periodic
@@ -1000,7 +1259,7 @@ mod tests {
 				Some((1000, 3)),
 				128,
 				root(),
-				Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 3))
+				Call::Logger(logger::Call::log(42, MaximumSchedulerWeight::get() / 3)).into()
 			);
 			// Anon
 			let _ = Scheduler::do_schedule(
@@ -1008,12 +1267,12 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(logger::Call::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			);
 			// Named Periodic
 			assert_ok!(Scheduler::do_schedule_named(
 				2u32.encode(), DispatchTime::At(1), Some((1000, 3)), 126, root(),
-				Call::Logger(logger::Call::log(2600, MaximumSchedulerWeight::get() / 2)))
+				Call::Logger(logger::Call::log(2600, MaximumSchedulerWeight::get() / 2)).into())
 			);
 
 			// Will include the named periodic only
@@ -1045,8 +1304,8 @@ mod tests {
 	#[test]
 	fn root_calls_works() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(logger::Call::log(69, 1000)));
-			let call2 = Box::new(Call::Logger(logger::Call::log(42, 1000)));
+			let call = Box::new(Call::Logger(logger::Call::log(69, 1000)).into());
+			let call2 = Box::new(Call::Logger(logger::Call::log(42, 1000)).into());
 			assert_ok!(Scheduler::schedule_named(Origin::root(), 1u32.encode(), 4, None, 127, call));
 			assert_ok!(Scheduler::schedule(Origin::root(), 4, None, 127, call2));
 			run_to_block(3);
@@ -1066,8 +1325,8 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			run_to_block(3);
 
-			let call = Box::new(Call::Logger(logger::Call::log(69, 1000)));
-			let call2 = Box::new(Call::Logger(logger::Call::log(42, 1000)));
+			let call = Box::new(Call::Logger(logger::Call::log(69, 1000)).into());
+			let call2 = Box::new(Call::Logger(logger::Call::log(42, 1000)).into());
 
 			assert_err!(
 				Scheduler::schedule_named(Origin::root(), 1u32.encode(), 2, None, 127, call),
@@ -1089,8 +1348,8 @@ mod tests {
 	#[test]
 	fn should_use_orign() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(logger::Call::log(69, 1000)));
-			let call2 = Box::new(Call::Logger(logger::Call::log(42, 1000)));
+			let call = Box::new(Call::Logger(logger::Call::log(69, 1000)).into());
+			let call2 = Box::new(Call::Logger(logger::Call::log(42, 1000)).into());
 			assert_ok!(
 				Scheduler::schedule_named(system::RawOrigin::Signed(1).into(), 1u32.encode(), 4, None, 127, call)
 			);
@@ -1110,8 +1369,8 @@ mod tests {
 	#[test]
 	fn should_check_orign() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(logger::Call::log(69, 1000)));
-			let call2 = Box::new(Call::Logger(logger::Call::log(42, 1000)));
+			let call = Box::new(Call::Logger(logger::Call::log(69, 1000)).into());
+			let call2 = Box::new(Call::Logger(logger::Call::log(42, 1000)).into());
 			assert_noop!(
 				Scheduler::schedule_named(system::RawOrigin::Signed(2).into(), 1u32.encode(), 4, None, 127, call),
 				BadOrigin
@@ -1123,8 +1382,8 @@ mod tests {
 	#[test]
 	fn should_check_orign_for_cancel() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(logger::Call::log_without_filter(69, 1000)));
-			let call2 = Box::new(Call::Logger(logger::Call::log_without_filter(42, 1000)));
+			let call = Box::new(Call::Logger(logger::Call::log_without_filter(69, 1000)).into());
+			let call2 = Box::new(Call::Logger(logger::Call::log_without_filter(42, 1000)).into());
 			assert_ok!(
 				Scheduler::schedule_named(system::RawOrigin::Signed(1).into(), 1u32.encode(), 4, None, 127, call)
 			);
@@ -1146,7 +1405,7 @@ mod tests {
 	}
 
 	#[test]
-	fn migration_to_v2_works() {
+	fn migration_v1_to_v3_works() {
 		use substrate_test_utils::assert_eq_uvec;
 
 		new_test_ext().execute_with(|| {
@@ -1177,25 +1436,25 @@ mod tests {
 
 			assert_eq!(StorageVersion::get(), Releases::V1);
 
-			assert!(Scheduler::migrate_v1_to_t2());
+			assert_eq!(Scheduler::on_runtime_upgrade(), MaximumBlockWeight::get());
 
 			assert_eq_uvec!(Agenda::<Test>::iter().collect::<Vec<_>>(), vec![
 				(
 					0,
 					vec![
-					Some(ScheduledV2 {
+					Some(ScheduledV3 {
 						maybe_id: None,
 						priority: 10,
-						call: Call::Logger(logger::Call::log(96, 100)),
+						call: Call::Logger(logger::Call::log(96, 100)).into(),
 						maybe_periodic: None,
 						origin: root(),
 						_phantom: PhantomData::<u64>::default(),
 					}),
 					None,
-					Some(ScheduledV2 {
+					Some(ScheduledV3 {
 						maybe_id: Some(b"test".to_vec()),
 						priority: 123,
-						call: Call::Logger(logger::Call::log(69, 1000)),
+						call: Call::Logger(logger::Call::log(69, 1000)).into(),
 						maybe_periodic: Some((456u64, 10)),
 						origin: root(),
 						_phantom: PhantomData::<u64>::default(),
@@ -1204,19 +1463,19 @@ mod tests {
 				(
 					1,
 					vec![
-						Some(ScheduledV2 {
+						Some(ScheduledV3 {
 							maybe_id: None,
 							priority: 11,
-							call: Call::Logger(logger::Call::log(96, 100)),
+							call: Call::Logger(logger::Call::log(96, 100)).into(),
 							maybe_periodic: None,
 							origin: root(),
 							_phantom: PhantomData::<u64>::default(),
 						}),
 						None,
-						Some(ScheduledV2 {
+						Some(ScheduledV3 {
 							maybe_id: Some(b"test".to_vec()),
 							priority: 123,
-							call: Call::Logger(logger::Call::log(69, 1000)),
+							call: Call::Logger(logger::Call::log(69, 1000)).into(),
 							maybe_periodic: Some((456u64, 10)),
 							origin: root(),
 							_phantom: PhantomData::<u64>::default(),
@@ -1226,19 +1485,19 @@ mod tests {
 				(
 					2,
 					vec![
-						Some(ScheduledV2 {
+						Some(ScheduledV3 {
 							maybe_id: None,
 							priority: 12,
-							call: Call::Logger(logger::Call::log(96, 100)),
+							call: Call::Logger(logger::Call::log(96, 100)).into(),
 							maybe_periodic: None,
 							origin: root(),
 							_phantom: PhantomData::<u64>::default(),
 						}),
 						None,
-						Some(ScheduledV2 {
+						Some(ScheduledV3 {
 							maybe_id: Some(b"test".to_vec()),
 							priority: 123,
-							call: Call::Logger(logger::Call::log(69, 1000)),
+							call: Call::Logger(logger::Call::log(69, 1000)).into(),
 							maybe_periodic: Some((456u64, 10)),
 							origin: root(),
 							_phantom: PhantomData::<u64>::default(),
@@ -1247,7 +1506,73 @@ mod tests {
 				)
 			]);
 
-			assert_eq!(StorageVersion::get(), Releases::V2);
+			assert_eq!(StorageVersion::get(), Releases::V3);
+		});
+	}
+
+	#[test]
+	fn migration_v2_to_v3_works() {
+		use substrate_test_utils::assert_eq_uvec;
+
+		new_test_ext().execute_with(|| {
+			StorageVersion::put(Releases::V2);
+			for i in 0..2u64 {
+				let k = i.twox_64_concat();
+				let old: Vec<Option<ScheduledV2<Call, u64, OriginCaller, u64>>> = vec![
+					Some(ScheduledV2 {
+						maybe_id: None,
+						priority: i as u8 + 10,
+						call: Call::Logger(logger::Call::log(96, 100)),
+						maybe_periodic: None,
+						origin: system::RawOrigin::Signed(2).into(),
+						_phantom: PhantomData::<u64>::default(),
+					}),
+					None,
+				];
+				frame_support::migration::put_storage_value(
+					b"Scheduler",
+					b"Agenda",
+					&k,
+					old,
+				);
+			}
+
+			assert_eq!(Scheduler::on_runtime_upgrade(), MaximumBlockWeight::get());
+			assert!(!Scheduler::migrate_v1_to_v3());
+			assert!(!Scheduler::migrate_v2_to_v3());
+
+			assert_eq_uvec!(Agenda::<Test>::iter().collect::<Vec<_>>(), vec![
+				(
+					0,
+					vec![
+						Some(ScheduledV3 {
+							maybe_id: None,
+							priority: 10,
+							call: Call::Logger(logger::Call::log(96, 100)).into(),
+							maybe_periodic: None,
+							origin: system::RawOrigin::Signed(2).into(),
+							_phantom: PhantomData::<u64>::default(),
+						}),
+						None,
+					]
+				),
+				(
+					1,
+					vec![
+						Some(ScheduledV3 {
+							maybe_id: None,
+							priority: 11,
+							call: Call::Logger(logger::Call::log(96, 100)).into(),
+							maybe_periodic: None,
+							origin: system::RawOrigin::Signed(2).into(),
+							_phantom: PhantomData::<u64>::default(),
+						}),
+						None,
+					]
+				),
+			]);
+
+			assert_eq!(StorageVersion::get(), Releases::V3);
 		});
 	}
 }
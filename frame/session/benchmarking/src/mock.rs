@@ -21,6 +21,7 @@
 
 use sp_runtime::traits::{Convert, SaturatedConversion, IdentityLookup};
 use frame_support::{impl_outer_origin, impl_outer_dispatch, parameter_types};
+use frame_election_provider_support::onchain;
 
 type AccountId = u64;
 type AccountIndex = u32;
@@ -156,16 +157,6 @@ pallet_staking_reward_curve::build! {
 parameter_types! {
 	pub const RewardCurve: &'static sp_runtime::curve::PiecewiseLinear<'static> = &I_NPOS;
 	pub const MaxNominatorRewardedPerValidator: u32 = 64;
-	pub const UnsignedPriority: u64 = 1 << 20;
-}
-
-pub type Extrinsic = sp_runtime::testing::TestXt<Call, ()>;
-
-impl<C> frame_system::offchain::SendTransactionTypes<C> for Test where
-	Call: From<C>,
-{
-	type OverarchingCall = Call;
-	type Extrinsic = Extrinsic;
 }
 
 impl pallet_staking::Trait for Test {
@@ -183,15 +174,19 @@ impl pallet_staking::Trait for Test {
 	type SessionInterface = Self;
 	type RewardCurve = RewardCurve;
 	type NextNewSession = Session;
-	type ElectionLookahead = ();
-	type Call = Call;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
-	type UnsignedPriority = UnsignedPriority;
-	type MaxIterations = ();
-	type MinSolutionScoreBump = ();
+	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type OnStakerSlash = ();
 	type WeightInfo = ();
 }
 
+impl onchain::Config for Test {
+	type AccountId = AccountId;
+	type BlockNumber = BlockNumber;
+	type Accuracy = sp_runtime::Perbill;
+	type DataProvider = Staking;
+}
+
 impl crate::Trait for Test {}
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
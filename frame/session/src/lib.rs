@@ -155,6 +155,10 @@ impl<
 	Period: Get<BlockNumber>,
 	Offset: Get<BlockNumber>,
 > EstimateNextSessionRotation<BlockNumber> for PeriodicSessions<Period, Offset> {
+	fn average_session_length() -> BlockNumber {
+		Period::get()
+	}
+
 	fn estimate_next_session_rotation(now: BlockNumber) -> Option<BlockNumber> {
 		let offset = Offset::get();
 		let period = Period::get();
@@ -165,7 +169,10 @@ impl<
 					period.saturating_sub(block_after_last_session)
 				)
 			} else {
-				Zero::zero()
+				// this branch happens when the session is already rotated or will rotate in this
+				// block. We assume the latter, namely that this is called after the session
+				// rotation, and thus the next one is a full period ahead.
+				now.saturating_add(period)
 			}
 		} else {
 			offset
@@ -802,6 +809,10 @@ impl<T: Trait, Inner: FindAuthor<u32>> FindAuthor<T::ValidatorId>
 }
 
 impl<T: Trait> EstimateNextNewSession<T::BlockNumber> for Module<T> {
+	fn average_session_length() -> T::BlockNumber {
+		T::NextSessionRotation::average_session_length()
+	}
+
 	/// This session module always calls new_session and next_session at the same time, hence we
 	/// do a simple proxy and pass the function to next rotation.
 	fn estimate_next_new_session(now: T::BlockNumber) -> Option<T::BlockNumber> {
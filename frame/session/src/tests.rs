@@ -273,9 +273,12 @@ fn periodic_session_works() {
 
 	for i in (1..10).map(|i| 3 + i) {
 		assert!(!P::should_end_session(i));
+		assert_eq!(P::estimate_next_session_rotation(i).unwrap(), 13);
 	}
 
 	assert!(P::should_end_session(13));
+	assert_eq!(P::estimate_next_session_rotation(13).unwrap(), 23);
+	assert_eq!(P::average_session_length(), 10);
 }
 
 #[test]
@@ -425,197 +425,6 @@ benchmarks! {
 			<Staking<T>>::payout_stakers(RawOrigin::Signed(caller.clone()).into(), arg.0, arg.1)?;
 		}
 	}
-
-	// This benchmark create `v` validators intent, `n` nominators intent, each nominator nominate
-	// MAX_NOMINATIONS in the set of the first `w` validators.
-	// It builds a solution with `w` winners composed of nominated validators randomly nominated,
-	// `a` assignment with MAX_NOMINATIONS.
-	submit_solution_initial {
-		// number of validator intent
-		let v in 1000 .. 2000;
-		// number of nominator intent
-		let n in 1000 .. 2000;
-		// number of assignments. Basically, number of active nominators.
-		let a in 200 .. 500;
-		// number of winners, also ValidatorCount
-		let w in 16 .. 100;
-
-		ensure!(w as usize >= MAX_NOMINATIONS, "doesn't support lower value");
-
-		let winners = create_validators_with_nominators_for_era::<T>(
-			v,
-			n,
-			MAX_NOMINATIONS,
-			false,
-			Some(w),
-		)?;
-
-		// needed for the solution to be generates.
-		assert!(<Staking<T>>::create_stakers_snapshot().0);
-
-		// set number of winners
-		ValidatorCount::put(w);
-
-		// create a assignments in total for the w winners.
-		let (winners, assignments) = create_assignments_for_offchain::<T>(a, winners)?;
-
-		let (
-			winners,
-			compact,
-			score,
-			size
-		) = offchain_election::prepare_submission::<T>(assignments, winners, false).unwrap();
-
-		// needed for the solution to be accepted
-		<EraElectionStatus<T>>::put(ElectionStatus::Open(T::BlockNumber::from(1u32)));
-
-		let era = <Staking<T>>::current_era().unwrap_or(0);
-		let caller: T::AccountId = account("caller", n, SEED);
-	}: {
-		let result = <Staking<T>>::submit_election_solution(
-			RawOrigin::Signed(caller.clone()).into(),
-			winners,
-			compact,
-			score.clone(),
-			era,
-			size,
-		);
-		assert!(result.is_ok());
-	}
-	verify {
-		// new solution has been accepted.
-		assert_eq!(<Staking<T>>::queued_score().unwrap(), score);
-	}
-
-	// same as submit_solution_initial but we place a very weak solution on chian first.
-	submit_solution_better {
-		// number of validator intent
-		let v in 1000 .. 2000;
-		// number of nominator intent
-		let n in 1000 .. 2000;
-		// number of assignments. Basically, number of active nominators.
-		let a in 200 .. 500;
-		// number of winners, also ValidatorCount
-		let w in 16 .. 100;
-
-		ensure!(w as usize >= MAX_NOMINATIONS, "doesn't support lower value");
-
-		let winners = create_validators_with_nominators_for_era::<T>(
-			v,
-			n,
-			MAX_NOMINATIONS,
-			false,
-			Some(w),
-		)?;
-
-		// needed for the solution to be generates.
-		assert!(<Staking<T>>::create_stakers_snapshot().0);
-
-		// set number of winners
-		ValidatorCount::put(w);
-
-		// create a assignments in total for the w winners.
-		let (winners, assignments) = create_assignments_for_offchain::<T>(a, winners)?;
-
-		let single_winner = winners[0].0.clone();
-
-		let (
-			winners,
-			compact,
-			score,
-			size
-		) = offchain_election::prepare_submission::<T>(assignments, winners, false).unwrap();
-
-		// needed for the solution to be accepted
-		<EraElectionStatus<T>>::put(ElectionStatus::Open(T::BlockNumber::from(1u32)));
-
-		let era = <Staking<T>>::current_era().unwrap_or(0);
-		let caller: T::AccountId = account("caller", n, SEED);
-
-		// submit a very bad solution on-chain
-		{
-			// this is needed to fool the chain to accept this solution.
-			ValidatorCount::put(1);
-			let (winners, compact, score, size) = get_single_winner_solution::<T>(single_winner)?;
-			assert!(
-				<Staking<T>>::submit_election_solution(
-					RawOrigin::Signed(caller.clone()).into(),
-					winners,
-					compact,
-					score.clone(),
-					era,
-					size,
-			).is_ok());
-
-			// new solution has been accepted.
-			assert_eq!(<Staking<T>>::queued_score().unwrap(), score);
-			ValidatorCount::put(w);
-		}
-	}: {
-		let result = <Staking<T>>::submit_election_solution(
-			RawOrigin::Signed(caller.clone()).into(),
-			winners,
-			compact,
-			score.clone(),
-			era,
-			size,
-		);
-		assert!(result.is_ok());
-	}
-	verify {
-		// new solution has been accepted.
-		assert_eq!(<Staking<T>>::queued_score().unwrap(), score);
-	}
-
-	// This will be early rejected based on the score.
-	submit_solution_weaker {
-		// number of validator intent
-		let v in 1000 .. 2000;
-		// number of nominator intent
-		let n in 1000 .. 2000;
-
-		create_validators_with_nominators_for_era::<T>(v, n, MAX_NOMINATIONS, false, None)?;
-
-		// needed for the solution to be generates.
-		assert!(<Staking<T>>::create_stakers_snapshot().0);
-
-		// needed for the solution to be accepted
-		<EraElectionStatus<T>>::put(ElectionStatus::Open(T::BlockNumber::from(1u32)));
-		let caller: T::AccountId = account("caller", n, SEED);
-		let era = <Staking<T>>::current_era().unwrap_or(0);
-
-		// submit a seq-phragmen with all the good stuff on chain.
-		{
-			let (winners, compact, score, size) = get_seq_phragmen_solution::<T>(true);
-			assert!(
-				<Staking<T>>::submit_election_solution(
-					RawOrigin::Signed(caller.clone()).into(),
-					winners,
-					compact,
-					score.clone(),
-					era,
-					size,
-				).is_ok()
-			);
-
-			// new solution has been accepted.
-			assert_eq!(<Staking<T>>::queued_score().unwrap(), score);
-		}
-
-		// prepare a bad solution. This will be very early rejected.
-		let (winners, compact, score, size) = get_weak_solution::<T>(true);
-	}: {
-		assert!(
-			<Staking<T>>::submit_election_solution(
-				RawOrigin::Signed(caller.clone()).into(),
-				winners,
-				compact,
-				score.clone(),
-				era,
-				size,
-			).is_err()
-		);
-	}
 }
 
 #[cfg(test)]
@@ -738,18 +547,6 @@ mod tests {
 			assert_ok!(test_benchmark_new_era::<Test>());
 			assert_ok!(test_benchmark_do_slash::<Test>());
 			assert_ok!(test_benchmark_payout_all::<Test>());
-			// only run one of them to same time on the CI. ignore the other two.
-			assert_ok!(test_benchmark_submit_solution_initial::<Test>());
 		});
 	}
-
-	#[test]
-	#[ignore]
-	fn test_benchmarks_offchain() {
-		ExtBuilder::default().has_stakers(false).build().execute_with(|| {
-			assert_ok!(test_benchmark_submit_solution_better::<Test>());
-			assert_ok!(test_benchmark_submit_solution_weaker::<Test>());
-		});
-	}
-
 }
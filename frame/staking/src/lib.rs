@@ -240,18 +240,20 @@
 //! Note that there is a limitation to the number of fund-chunks that can be scheduled to be
 //! unlocked in the future via [`unbond`](enum.Call.html#variant.unbond). In case this maximum
 //! (`MAX_UNLOCKING_CHUNKS`) is reached, the bonded account _must_ first wait until a successful
-//! call to `withdraw_unbonded` to remove some of the chunks.
+//! call to `withdraw_unbonded` to remove some of the chunks. Funds unbonded within the same era
+//! share a single chunk.
 //!
 //! ### Election Algorithm
 //!
-//! The current election algorithm is implemented based on Phragmén. The reference implementation
-//! can be found [here](https://github.com/w3f/consensus/tree/master/NPoS).
+//! The election itself is not performed by this module. Instead, the staking module acts as an
+//! [`ElectionDataProvider`] to whatever is configured as [`Trait::ElectionProvider`], which is
+//! asked for the next set of winners once a new era is planned. The data provided to the election
+//! consists of all validators, each with a vote for themselves, and all nominators with their
+//! (non-slashed) nominations. The weight of each vote is the active bonded stake of the voter.
 //!
-//! The election algorithm, aside from electing the validators with the most stake value and votes,
-//! tries to divide the nominator votes among candidates in an equal manner. To further assure this,
-//! an optional post-processing can be applied that iteratively normalizes the nominator staked
-//! values until the total difference among votes of a particular nominator are less than a
-//! threshold.
+//! See the [`frame_election_provider_support`] crate for more information, and
+//! [Multi Phase Election Provider](../pallet_election_provider_multi_phase/index.html) for an
+//! implementation with signed and unsigned (offchain) submissions.
 //!
 //! ## GenesisConfig
 //!
@@ -277,25 +279,20 @@ pub mod testing_utils;
 pub mod benchmarking;
 
 pub mod slashing;
-pub mod offchain_election;
 pub mod inflation;
 
 use sp_std::{
 	result,
 	prelude::*,
 	collections::btree_map::BTreeMap,
-	convert::{TryInto, From},
-	mem::size_of,
+	convert::From,
 };
 use codec::{HasCompact, Encode, Decode};
 use frame_support::{
 	decl_module, decl_event, decl_storage, ensure, decl_error,
 	weights::{Weight, constants::{WEIGHT_PER_MICROS, WEIGHT_PER_NANOS}},
 	storage::IterableStorageMap,
-	dispatch::{
-		IsSubType, DispatchResult, DispatchResultWithPostInfo, DispatchErrorWithPostInfo,
-		WithPostDispatchInfo,
-	},
+	dispatch::{DispatchResult, DispatchResultWithPostInfo},
 	traits::{
 		Currency, LockIdentifier, LockableCurrency, WithdrawReasons, OnUnbalanced, Imbalance, Get,
 		UnixTime, EstimateNextNewSession, EnsureOrigin,
@@ -303,36 +300,27 @@ use frame_support::{
 };
 use pallet_session::historical;
 use sp_runtime::{
-	Percent, Perbill, PerU16, PerThing, RuntimeDebug, DispatchError,
+	Percent, Perbill, RuntimeDebug, DispatchError,
 	curve::PiecewiseLinear,
 	traits::{
 		Convert, Zero, StaticLookup, CheckedSub, Saturating, SaturatedConversion,
-		AtLeast32BitUnsigned, Dispatchable,
-	},
-	transaction_validity::{
-		TransactionValidityError, TransactionValidity, ValidTransaction, InvalidTransaction,
-		TransactionSource, TransactionPriority,
+		AtLeast32BitUnsigned,
 	},
 };
 use sp_staking::{
-	SessionIndex,
+	SessionIndex, OnStakerSlash, StakingInterface,
 	offence::{OnOffenceHandler, OffenceDetails, Offence, ReportOffence, OffenceError},
 };
 #[cfg(feature = "std")]
 use sp_runtime::{Serialize, Deserialize};
-use frame_system::{
-	self as system, ensure_signed, ensure_root, ensure_none,
-	offchain::SendTransactionTypes,
-};
-use sp_npos_elections::{
-	ExtendedBalance, Assignment, ElectionScore, ElectionResult as PrimitiveElectionResult,
-	build_support_map, evaluate_support, seq_phragmen, generate_compact_solution_type,
-	is_score_better, VotingLimit, SupportMap, VoteWeight,
+use frame_system::{self as system, ensure_signed, ensure_root, RawOrigin};
+use frame_election_provider_support::{
+	ElectionDataProvider, ElectionProvider, ExtendedBalance, Supports, VoteWeight,
 };
 
 const STAKING_ID: LockIdentifier = *b"staking ";
 pub const MAX_UNLOCKING_CHUNKS: usize = 32;
-pub const MAX_NOMINATIONS: usize = <CompactAssignments as VotingLimit>::LIMIT;
+pub const MAX_NOMINATIONS: usize = 16;
 
 pub(crate) const LOG_TARGET: &'static str = "staking";
 
@@ -347,29 +335,12 @@ macro_rules! log {
 	};
 }
 
-/// Data type used to index nominators in the compact type
-pub type NominatorIndex = u32;
-
-/// Data type used to index validators in the compact type.
-pub type ValidatorIndex = u16;
-
-// Ensure the size of both ValidatorIndex and NominatorIndex. They both need to be well below usize.
-static_assertions::const_assert!(size_of::<ValidatorIndex>() <= size_of::<usize>());
-static_assertions::const_assert!(size_of::<NominatorIndex>() <= size_of::<usize>());
-
-/// Maximum number of stakers that can be stored in a snapshot.
-pub(crate) const MAX_VALIDATORS: usize = ValidatorIndex::max_value() as usize;
-pub(crate) const MAX_NOMINATORS: usize = NominatorIndex::max_value() as usize;
-
 /// Counter for the number of eras that have passed.
-pub type EraIndex = u32;
+pub use sp_staking::EraIndex;
 
 /// Counter for the number of "reward" points earned by a given validator.
 pub type RewardPoint = u32;
 
-// Note: Maximum nomination limit is set here -- 16.
-generate_compact_solution_type!(pub GenericCompactAssignments, 16);
-
 /// Information regarding the active era (era in used in session).
 #[derive(Encode, Decode, RuntimeDebug)]
 pub struct ActiveEraInfo {
@@ -382,20 +353,10 @@ pub struct ActiveEraInfo {
 	start: Option<u64>,
 }
 
-/// Accuracy used for on-chain election.
-pub type ChainAccuracy = Perbill;
-
-/// Accuracy used for off-chain election. This better be small.
-pub type OffchainAccuracy = PerU16;
-
 /// The balance type of this module.
 pub type BalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
-/// The compact type for election solutions.
-pub type CompactAssignments =
-	GenericCompactAssignments<NominatorIndex, ValidatorIndex, OffchainAccuracy>;
-
 type PositiveImbalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::PositiveImbalance;
 type NegativeImbalanceOf<T> =
@@ -550,11 +511,13 @@ impl<AccountId, Balance> StakingLedger<AccountId, Balance> where
 	/// active funds. Returns the amount of funds actually slashed.
 	///
 	/// Slashes from `active` funds first, and then `unlocking`, starting with the
-	/// chunks that are closest to unlocking.
+	/// chunks that are closest to unlocking. The amount slashed from each era's unlocking
+	/// funds is accumulated into `slashed_unlocking`.
 	fn slash(
 		&mut self,
 		mut value: Balance,
 		minimum_balance: Balance,
+		slashed_unlocking: &mut BTreeMap<EraIndex, Balance>,
 	) -> Balance {
 		let pre_total = self.total;
 		let total = &mut self.total;
@@ -585,7 +548,13 @@ impl<AccountId, Balance> StakingLedger<AccountId, Balance> where
 
 		let i = self.unlocking.iter_mut()
 			.map(|chunk| {
+				let pre_value = chunk.value;
 				slash_out_of(total, &mut chunk.value, &mut value);
+				let slashed = pre_value.saturating_sub(chunk.value);
+				if !slashed.is_zero() {
+					let entry = slashed_unlocking.entry(chunk.era).or_insert_with(Zero::zero);
+					*entry = entry.saturating_add(slashed);
+				}
 				chunk.value
 			})
 			.take_while(|value| value.is_zero()) // take all fully-consumed chunks out.
@@ -650,78 +619,6 @@ pub struct UnappliedSlash<AccountId, Balance: HasCompact> {
 	payout: Balance,
 }
 
-/// Indicate how an election round was computed.
-#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
-pub enum ElectionCompute {
-	/// Result was forcefully computed on chain at the end of the session.
-	OnChain,
-	/// Result was submitted and accepted to the chain via a signed transaction.
-	Signed,
-	/// Result was submitted and accepted to the chain via an unsigned transaction (by an
-	/// authority).
-	Unsigned,
-}
-
-/// The result of an election round.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
-pub struct ElectionResult<AccountId, Balance: HasCompact> {
-	/// Flat list of validators who have been elected.
-	elected_stashes: Vec<AccountId>,
-	/// Flat list of new exposures, to be updated in the [`Exposure`] storage.
-	exposures: Vec<(AccountId, Exposure<AccountId, Balance>)>,
-	/// Type of the result. This is kept on chain only to track and report the best score's
-	/// submission type. An optimisation could remove this.
-	compute: ElectionCompute,
-}
-
-/// The status of the upcoming (offchain) election.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
-pub enum ElectionStatus<BlockNumber> {
-	/// Nothing has and will happen for now. submission window is not open.
-	Closed,
-	/// The submission window has been open since the contained block number.
-	Open(BlockNumber),
-}
-
-/// Some indications about the size of the election. This must be submitted with the solution.
-///
-/// Note that these values must reflect the __total__ number, not only those that are present in the
-/// solution. In short, these should be the same size as the size of the values dumped in
-/// `SnapshotValidators` and `SnapshotNominators`.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
-pub struct ElectionSize {
-	/// Number of validators in the snapshot of the current election round.
-	#[codec(compact)]
-	pub validators: ValidatorIndex,
-	/// Number of nominators in the snapshot of the current election round.
-	#[codec(compact)]
-	pub nominators: NominatorIndex,
-}
-
-
-impl<BlockNumber: PartialEq> ElectionStatus<BlockNumber> {
-	fn is_open_at(&self, n: BlockNumber) -> bool {
-		*self == Self::Open(n)
-	}
-
-	fn is_closed(&self) -> bool {
-		match self {
-			Self::Closed => true,
-			_ => false
-		}
-	}
-
-	fn is_open(&self) -> bool {
-		!self.is_closed()
-	}
-}
-
-impl<BlockNumber> Default for ElectionStatus<BlockNumber> {
-	fn default() -> Self {
-		Self::Closed
-	}
-}
-
 /// Means for interacting with a specialized version of the `session` trait.
 ///
 /// This is needed because `Staking` sets the `ValidatorIdOf` of the `pallet_session::Trait`
@@ -762,72 +659,6 @@ impl<T: Trait> SessionInterface<<T as frame_system::Trait>::AccountId> for T whe
 	}
 }
 
-pub mod weight {
-	use super::*;
-
-	/// All weight notes are pertaining to the case of a better solution, in which we execute
-	/// the longest code path.
-	/// Weight: 0 + (0.63 μs * v) + (0.36 μs * n) + (96.53 μs * a ) + (8 μs * w ) with:
-	/// * v validators in snapshot validators,
-	/// * n nominators in snapshot nominators,
-	/// * a assignment in the submitted solution
-	/// * w winners in the submitted solution
-	///
-	/// State reads:
-	/// 	- Initial checks:
-	/// 		- ElectionState, CurrentEra, QueuedScore
-	/// 		- SnapshotValidators.len() + SnapShotNominators.len()
-	/// 		- ValidatorCount
-	/// 		- SnapshotValidators
-	/// 		- SnapshotNominators
-	/// 	- Iterate over nominators:
-	/// 		- compact.len() * Nominators(who)
-	/// 		- (non_self_vote_edges) * SlashingSpans
-	/// 	- For `assignment_ratio_to_staked`: Basically read the staked value of each stash.
-	/// 		- (winners.len() + compact.len()) * (Ledger + Bonded)
-	/// 		- TotalIssuance (read a gzillion times potentially, but well it is cached.)
-	/// - State writes:
-	/// 	- QueuedElected, QueuedScore
-	pub fn weight_for_submit_solution<T: Trait>(
-		winners: &Vec<ValidatorIndex>,
-		compact: &CompactAssignments,
-		size: &ElectionSize,
-	) -> Weight {
-		(630 * WEIGHT_PER_NANOS).saturating_mul(size.validators as Weight)
-			.saturating_add((360 * WEIGHT_PER_NANOS).saturating_mul(size.nominators as Weight))
-			.saturating_add((96 * WEIGHT_PER_MICROS).saturating_mul(compact.len() as Weight))
-			.saturating_add((8 * WEIGHT_PER_MICROS).saturating_mul(winners.len() as Weight))
-			// Initial checks
-			.saturating_add(T::DbWeight::get().reads(8))
-			// Nominators
-			.saturating_add(T::DbWeight::get().reads(compact.len() as Weight))
-			// SlashingSpans (upper bound for invalid solution)
-			.saturating_add(T::DbWeight::get().reads(compact.edge_count() as Weight))
-			// `assignment_ratio_to_staked`
-			.saturating_add(T::DbWeight::get().reads(2 * ((winners.len() + compact.len()) as Weight)))
-			.saturating_add(T::DbWeight::get().reads(1))
-			// write queued score and elected
-			.saturating_add(T::DbWeight::get().writes(2))
-	}
-
-	/// Weight of `submit_solution` in case of a correct submission.
-	///
-	/// refund: we charged compact.len() * read(1) for SlashingSpans. A valid solution only reads
-	/// winners.len().
-	pub fn weight_for_correct_submit_solution<T: Trait>(
-		winners: &Vec<ValidatorIndex>,
-		compact: &CompactAssignments,
-		size: &ElectionSize,
-	) -> Weight {
-		// NOTE: for consistency, we re-compute the original weight to maintain their relation and
-		// prevent any foot-guns.
-		let original_weight = weight_for_submit_solution::<T>(winners, compact, size);
-		original_weight
-			.saturating_sub(T::DbWeight::get().reads(compact.edge_count() as Weight))
-			.saturating_add(T::DbWeight::get().reads(winners.len() as Weight))
-	}
-}
-
 pub trait WeightInfo {
 	fn bond(u: u32, ) -> Weight;
 	fn bond_extra(u: u32, ) -> Weight;
@@ -854,9 +685,6 @@ pub trait WeightInfo {
 	fn new_era(v: u32, n: u32, ) -> Weight;
 	fn do_slash(l: u32, ) -> Weight;
 	fn payout_all(v: u32, n: u32, ) -> Weight;
-	fn submit_solution_initial(v: u32, n: u32, a: u32, w: u32, ) -> Weight;
-	fn submit_solution_better(v: u32, n: u32, a: u32, w: u32, ) -> Weight;
-	fn submit_solution_weaker(v: u32, n: u32, ) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -885,12 +713,9 @@ impl WeightInfo for () {
 	fn new_era(_v: u32, _n: u32, ) -> Weight { 1_000_000_000 }
 	fn do_slash(_l: u32, ) -> Weight { 1_000_000_000 }
 	fn payout_all(_v: u32, _n: u32, ) -> Weight { 1_000_000_000 }
-	fn submit_solution_initial(_v: u32, _n: u32, _a: u32, _w: u32, ) -> Weight { 1_000_000_000 }
-	fn submit_solution_better(_v: u32, _n: u32, _a: u32, _w: u32, ) -> Weight { 1_000_000_000 }
-	fn submit_solution_weaker(_v: u32, _n: u32, ) -> Weight { 1_000_000_000 }
 }
 
-pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> {
+pub trait Trait: frame_system::Trait {
 	/// The staking balance.
 	type Currency: LockableCurrency<Self::AccountId, Moment=Self::BlockNumber>;
 
@@ -945,25 +770,16 @@ pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> {
 	/// Something that can estimate the next session change, accurately or as a best effort guess.
 	type NextNewSession: EstimateNextNewSession<Self::BlockNumber>;
 
-	/// The number of blocks before the end of the era from which election submissions are allowed.
+	/// Something that provides the election functionality.
 	///
-	/// Setting this to zero will disable the offchain compute and only on-chain seq-phragmen will
-	/// be used.
-	///
-	/// This is bounded by being within the last session. Hence, setting it to a value more than the
-	/// length of a session will be pointless.
-	type ElectionLookahead: Get<Self::BlockNumber>;
-
-	/// The overarching call type.
-	type Call: Dispatchable + From<Call<Self>> + IsSubType<Call<Self>> + Clone;
-
-	/// Maximum number of balancing iterations to run in the offchain submission.
-	///
-	/// If set to 0, balance_solution will not be executed at all.
-	type MaxIterations: Get<u32>;
-
-	/// The threshold of improvement that should be provided for a new solution to be accepted.
-	type MinSolutionScoreBump: Get<Perbill>;
+	/// The staking module is the data provider of this election: it provides the voters (all
+	/// validators and nominators), the targets (all validators) and the desired number of winners.
+	type ElectionProvider: ElectionProvider<
+		Self::AccountId,
+		Self::BlockNumber,
+		// we only accept an election provider that has staking as data provider.
+		DataProvider = Module<Self>,
+	>;
 
 	/// The maximum number of nominators rewarded for each validator.
 	///
@@ -971,11 +787,9 @@ pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> {
 	/// their reward. This used to limit the i/o cost for the nominator payout.
 	type MaxNominatorRewardedPerValidator: Get<u32>;
 
-	/// A configuration for base priority of unsigned transactions.
-	///
-	/// This is exposed so that it can be tuned for particular runtime, when
-	/// multiple pallets send unsigned transactions.
-	type UnsignedPriority: Get<TransactionPriority>;
+	/// Something notified of the slashes applied to stakers, e.g. to pass them on to the
+	/// accounts behind a pooled stash.
+	type OnStakerSlash: OnStakerSlash<Self::AccountId, BalanceOf<Self>>;
 
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
@@ -1169,29 +983,10 @@ decl_storage! {
 		/// The earliest era for which we have a pending, unapplied slash.
 		EarliestUnappliedSlash: Option<EraIndex>;
 
-		/// Snapshot of validators at the beginning of the current election window. This should only
-		/// have a value when [`EraElectionStatus`] == `ElectionStatus::Open(_)`.
-		pub SnapshotValidators get(fn snapshot_validators): Option<Vec<T::AccountId>>;
-
-		/// Snapshot of nominators at the beginning of the current election window. This should only
-		/// have a value when [`EraElectionStatus`] == `ElectionStatus::Open(_)`.
-		pub SnapshotNominators get(fn snapshot_nominators): Option<Vec<T::AccountId>>;
-
-		/// The next validator set. At the end of an era, if this is available (potentially from the
-		/// result of an offchain worker), it is immediately used. Otherwise, the on-chain election
-		/// is executed.
-		pub QueuedElected get(fn queued_elected): Option<ElectionResult<T::AccountId, BalanceOf<T>>>;
-
-		/// The score of the current [`QueuedElected`].
-		pub QueuedScore get(fn queued_score): Option<ElectionScore>;
-
-		/// Flag to control the execution of the offchain election. When `Open(_)`, we accept
-		/// solutions to be submitted.
-		pub EraElectionStatus get(fn era_election_status): ElectionStatus<T::BlockNumber>;
-
-		/// True if the current **planned** session is final. Note that this does not take era
-		/// forcing into account.
-		pub IsCurrentSessionFinal get(fn is_current_session_final): bool = false;
+		/// The last planned session scheduled by the session pallet.
+		///
+		/// This is basically in sync with the call to [`SessionManager::new_session`].
+		pub CurrentPlannedSession get(fn current_planned_session): SessionIndex;
 
 		/// True if network has been upgraded to this version.
 		/// Storage version of the pallet.
@@ -1247,10 +1042,8 @@ decl_event!(
 		/// An old slashing report from a prior era was discarded because it could
 		/// not be processed. [session_index]
 		OldSlashingReportDiscarded(SessionIndex),
-		/// A new set of stakers was elected with the given [compute].
-		StakingElection(ElectionCompute),
-		/// A new solution for the upcoming election has been stored. [compute]
-		SolutionStored(ElectionCompute),
+		/// A new set of stakers was elected.
+		StakingElection,
 		/// An account has bonded this amount. [stash, amount]
 		///
 		/// NOTE: This event is only emitted when funds are bonded via a dispatchable. Notably,
@@ -1297,37 +1090,6 @@ decl_error! {
 		NotSortedAndUnique,
 		/// Rewards for this era have already been claimed for this validator.
 		AlreadyClaimed,
-		/// The submitted result is received out of the open window.
-		PhragmenEarlySubmission,
-		/// The submitted result is not as good as the one stored on chain.
-		PhragmenWeakSubmission,
-		/// The snapshot data of the current window is missing.
-		SnapshotUnavailable,
-		/// Incorrect number of winners were presented.
-		PhragmenBogusWinnerCount,
-		/// One of the submitted winners is not an active candidate on chain (index is out of range
-		/// in snapshot).
-		PhragmenBogusWinner,
-		/// Error while building the assignment type from the compact. This can happen if an index
-		/// is invalid, or if the weights _overflow_.
-		PhragmenBogusCompact,
-		/// One of the submitted nominators is not an active nominator on chain.
-		PhragmenBogusNominator,
-		/// One of the submitted nominators has an edge to which they have not voted on chain.
-		PhragmenBogusNomination,
-		/// One of the submitted nominators has an edge which is submitted before the last non-zero
-		/// slash of the target.
-		PhragmenSlashedNomination,
-		/// A self vote must only be originated from a validator to ONLY themselves.
-		PhragmenBogusSelfVote,
-		/// The submitted result has unknown edges that are not among the presented winners.
-		PhragmenBogusEdge,
-		/// The claimed score does not match with the one computed from the data.
-		PhragmenBogusScore,
-		/// The election size is invalid.
-		PhragmenBogusElectionSize,
-		/// The call is not allowed at the given time due to restrictions of election period.
-		CallNotAllowed,
 		/// Incorrect previous history depth input provided.
 		IncorrectHistoryDepth,
 		/// Incorrect number of slashing spans provided.
@@ -1350,23 +1112,6 @@ decl_module! {
 		/// intervention.
 		const SlashDeferDuration: EraIndex = T::SlashDeferDuration::get();
 
-		/// The number of blocks before the end of the era from which election submissions are allowed.
-		///
-		/// Setting this to zero will disable the offchain compute and only on-chain seq-phragmen will
-		/// be used.
-		///
-		/// This is bounded by being within the last session. Hence, setting it to a value more than the
-		/// length of a session will be pointless.
-		const ElectionLookahead: T::BlockNumber = T::ElectionLookahead::get();
-
-		/// Maximum number of balancing iterations to run in the offchain submission.
-		///
-		/// If set to 0, balance_solution will not be executed at all.
-		const MaxIterations: u32 = T::MaxIterations::get();
-
-		/// The threshold of improvement that should be provided for a new solution to be accepted.
-		const MinSolutionScoreBump: Perbill = T::MinSolutionScoreBump::get();
-
 		/// The maximum number of nominators rewarded for each validator.
 		///
 		/// For each validator only the `$MaxNominatorRewardedPerValidator` biggest stakers can claim
@@ -1377,71 +1122,9 @@ decl_module! {
 
 		fn deposit_event() = default;
 
-		/// sets `ElectionStatus` to `Open(now)` where `now` is the block number at which the
-		/// election window has opened, if we are at the last session and less blocks than
-		/// `T::ElectionLookahead` is remaining until the next new session schedule. The offchain
-		/// worker, if applicable, will execute at the end of the current block, and solutions may
-		/// be submitted.
-		fn on_initialize(now: T::BlockNumber) -> Weight {
-			let mut consumed_weight = 0;
-			let mut add_weight = |reads, writes, weight| {
-				consumed_weight += T::DbWeight::get().reads_writes(reads, writes);
-				consumed_weight += weight;
-			};
-			if
-				// if we don't have any ongoing offchain compute.
-				Self::era_election_status().is_closed() &&
-				// either current session final based on the plan, or we're forcing.
-				(Self::is_current_session_final() || Self::will_era_be_forced())
-			{
-				if let Some(next_session_change) = T::NextNewSession::estimate_next_new_session(now) {
-					if let Some(remaining) = next_session_change.checked_sub(&now) {
-						if remaining <= T::ElectionLookahead::get() && !remaining.is_zero() {
-							// create snapshot.
-							let (did_snapshot, snapshot_weight) = Self::create_stakers_snapshot();
-							add_weight(0, 0, snapshot_weight);
-							if did_snapshot {
-								// Set the flag to make sure we don't waste any compute here in the same era
-								// after we have triggered the offline compute.
-								<EraElectionStatus<T>>::put(
-									ElectionStatus::<T::BlockNumber>::Open(now)
-								);
-								add_weight(0, 1, 0);
-								log!(info, "💸 Election window is Open({:?}). Snapshot created", now);
-							} else {
-								log!(warn, "💸 Failed to create snapshot at {:?}.", now);
-							}
-						}
-					}
-				} else {
-					log!(warn, "💸 Estimating next session change failed.");
-				}
-				add_weight(0, 0, T::NextNewSession::weight(now))
-			}
-			// For `era_election_status`, `is_current_session_final`, `will_era_be_forced`
-			add_weight(3, 0, 0);
-			// Additional read from `on_finalize`
-			add_weight(1, 0, 0);
-			consumed_weight
-		}
-
-		/// Check if the current block number is the one at which the election window has been set
-		/// to open. If so, it runs the offchain worker code.
-		fn offchain_worker(now: T::BlockNumber) {
-			use offchain_election::{set_check_offchain_execution_status, compute_offchain_election};
-
-			if Self::era_election_status().is_open_at(now) {
-				let offchain_status = set_check_offchain_execution_status::<T>(now);
-				if let Err(why) = offchain_status {
-					log!(debug, "skipping offchain worker in open election window due to [{}]", why);
-				} else {
-					if let Err(e) = compute_offchain_election::<T>() {
-						log!(error, "💸 Error in election offchain worker: {:?}", e);
-					} else {
-						log!(debug, "Executed offchain worker thread without errors.");
-					}
-				}
-			}
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			// just return the weight of the on_finalize.
+			T::DbWeight::get().reads(1)
 		}
 
 		fn on_finalize() {
@@ -1544,8 +1227,7 @@ decl_module! {
 		/// Unlike [`bond`] or [`unbond`] this function does not impose any limitation on the amount
 		/// that can be added.
 		///
-		/// The dispatch origin for this call must be _Signed_ by the stash, not the controller and
-		/// it can be only called when [`EraElectionStatus`] is `Closed`.
+		/// The dispatch origin for this call must be _Signed_ by the stash, not the controller.
 		///
 		/// Emits `Bonded`.
 		///
@@ -1561,7 +1243,6 @@ decl_module! {
 		/// # </weight>
 		#[weight = 55 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(4, 2)]
 		fn bond_extra(origin, #[compact] max_additional: BalanceOf<T>) {
-			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let stash = ensure_signed(origin)?;
 
 			let controller = Self::bonded(&stash).ok_or(Error::<T>::NotStash)?;
@@ -1590,7 +1271,6 @@ decl_module! {
 		/// to be called first to remove some of the chunks (if possible).
 		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
-		/// And, it can be only called when [`EraElectionStatus`] is `Closed`.
 		///
 		/// Emits `Unbonded`.
 		///
@@ -1600,9 +1280,9 @@ decl_module! {
 		/// - Independent of the arguments. Limited but potentially exploitable complexity.
 		/// - Contains a limited number of reads.
 		/// - Each call (requires the remainder of the bonded balance to be above `minimum_balance`)
-		///   will cause a new entry to be inserted into a vector (`Ledger.unlocking`) kept in storage.
-		///   The only way to clean the aforementioned storage item is also user-controlled via
-		///   `withdraw_unbonded`.
+		///   in a new era will cause a new entry to be inserted into a vector (`Ledger.unlocking`)
+		///   kept in storage, calls within the same era add to that entry. The only way to clean
+		///   the aforementioned storage item is also user-controlled via `withdraw_unbonded`.
 		/// - One DB entry.
 		/// ----------
 		/// Base Weight: 50.34 µs
@@ -1612,11 +1292,13 @@ decl_module! {
 		/// </weight>
 		#[weight = 50 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(4, 2)]
 		fn unbond(origin, #[compact] value: BalanceOf<T>) {
-			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
 			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			// Note: in case there is no current era it is fine to bond one era more.
+			let era = Self::current_era().unwrap_or(0) + T::BondingDuration::get();
 			ensure!(
-				ledger.unlocking.len() < MAX_UNLOCKING_CHUNKS,
+				ledger.unlocking.len() < MAX_UNLOCKING_CHUNKS ||
+					ledger.unlocking.iter().any(|chunk| chunk.era == era),
 				Error::<T>::NoMoreChunks,
 			);
 
@@ -1631,9 +1313,12 @@ decl_module! {
 					ledger.active = Zero::zero();
 				}
 
-				// Note: in case there is no current era it is fine to bond one era more.
-				let era = Self::current_era().unwrap_or(0) + T::BondingDuration::get();
-				ledger.unlocking.push(UnlockChunk { value, era });
+				// Funds unbonded in the same era unlock together, so they share a chunk.
+				if let Some(chunk) = ledger.unlocking.iter_mut().find(|chunk| chunk.era == era) {
+					chunk.value = chunk.value.saturating_add(value);
+				} else {
+					ledger.unlocking.push(UnlockChunk { value, era });
+				}
 				Self::update_ledger(&controller, &ledger);
 				Self::deposit_event(RawEvent::Unbonded(ledger.stash, value));
 			}
@@ -1645,7 +1330,6 @@ decl_module! {
 		/// whatever it wants.
 		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
-		/// And, it can be only called when [`EraElectionStatus`] is `Closed`.
 		///
 		/// Emits `Withdrawn`.
 		///
@@ -1661,10 +1345,10 @@ decl_module! {
 		/// Complexity O(S) where S is the number of slashing spans to remove
 		/// Base Weight:
 		/// Update: 50.52 + .028 * S µs
-		/// - Reads: EraElectionStatus, Ledger, Current Era, Locks, [Origin Account]
+		/// - Reads: Ledger, Current Era, Locks, [Origin Account]
 		/// - Writes: [Origin Account], Locks, Ledger
 		/// Kill: 79.41 + 2.366 * S µs
-		/// - Reads: EraElectionStatus, Ledger, Current Era, Bonded, Slashing Spans, [Origin Account], Locks
+		/// - Reads: Ledger, Current Era, Bonded, Slashing Spans, [Origin Account], Locks
 		/// - Writes: Bonded, Slashing Spans (if S > 0), Ledger, Payee, Validators, Nominators, [Origin Account], Locks
 		/// - Writes Each: SpanSlash * S
 		/// NOTE: Weight annotation is the kill scenario, we refund otherwise.
@@ -1679,7 +1363,6 @@ decl_module! {
 			.saturating_add(T::DbWeight::get().writes(Weight::from(*num_slashing_spans).min(1)))
 		]
 		fn withdraw_unbonded(origin, num_slashing_spans: u32) -> DispatchResultWithPostInfo {
-			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
 			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			let (stash, old_total) = (ledger.stash.clone(), ledger.total);
@@ -1719,7 +1402,6 @@ decl_module! {
 		/// Effects will be felt at the beginning of the next era.
 		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
-		/// And, it can be only called when [`EraElectionStatus`] is `Closed`.
 		///
 		/// # <weight>
 		/// - Independent of the arguments. Insignificant complexity.
@@ -1733,7 +1415,6 @@ decl_module! {
 		/// # </weight>
 		#[weight = 17 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(2, 2)]
 		pub fn validate(origin, prefs: ValidatorPrefs) {
-			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			let stash = &ledger.stash;
@@ -1743,15 +1424,13 @@ decl_module! {
 
 		/// Declare the desire to nominate `targets` for the origin controller.
 		///
-		/// Effects will be felt at the beginning of the next era. This can only be called when
-		/// [`EraElectionStatus`] is `Closed`.
+		/// Effects will be felt at the beginning of the next era.
 		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
-		/// And, it can be only called when [`EraElectionStatus`] is `Closed`.
 		///
 		/// # <weight>
 		/// - The transaction's complexity is proportional to the size of `targets` (N)
-		/// which is capped at CompactAssignments::LIMIT (MAX_NOMINATIONS).
+		/// which is capped at `MAX_NOMINATIONS`.
 		/// - Both the reads and writes follow a similar pattern.
 		/// ---------
 		/// Base Weight: 22.34 + .36 * N µs
@@ -1765,7 +1444,6 @@ decl_module! {
 			.saturating_add((360 * WEIGHT_PER_NANOS).saturating_mul(targets.len() as Weight))
 		]
 		pub fn nominate(origin, targets: Vec<<T::Lookup as StaticLookup>::Source>) {
-			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			let stash = &ledger.stash;
@@ -1791,7 +1469,6 @@ decl_module! {
 		/// Effects will be felt at the beginning of the next era.
 		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
-		/// And, it can be only called when [`EraElectionStatus`] is `Closed`.
 		///
 		/// # <weight>
 		/// - Independent of the arguments. Insignificant complexity.
@@ -1800,12 +1477,11 @@ decl_module! {
 		/// --------
 		/// Base Weight: 16.53 µs
 		/// DB Weight:
-		/// - Read: EraElectionStatus, Ledger
+		/// - Read: Ledger
 		/// - Write: Validators, Nominators
 		/// # </weight>
 		#[weight = 16 * WEIGHT_PER_MICROS + T::DbWeight::get().reads_writes(2, 2)]
 		fn chill(origin) {
-			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			Self::chill_stash(&ledger.stash);
@@ -2047,8 +1723,6 @@ decl_module! {
 		/// The origin of this call must be _Signed_. Any account can call this function, even if
 		/// it is not one of the stakers.
 		///
-		/// This can only be called when [`EraElectionStatus`] is `Closed`.
-		///
 		/// # <weight>
 		/// - Time complexity: at most O(MaxNominatorRewardedPerValidator).
 		/// - Contains a limited number of reads and writes.
@@ -2058,7 +1732,7 @@ decl_module! {
 		/// - Reward Destination Staked: 110 + 54.2 * N µs (Median Slopes)
 		/// - Reward Destination Controller (Creating): 120 + 41.95 * N µs (Median Slopes)
 		/// DB Weight:
-		/// - Read: EraElectionStatus, CurrentEra, HistoryDepth, ErasValidatorReward,
+		/// - Read: CurrentEra, HistoryDepth, ErasValidatorReward,
 		///         ErasStakersClipped, ErasRewardPoints, ErasValidatorPrefs (8 items)
 		/// - Read Each: Bonded, Ledger, Payee, Locks, System Account (5 items)
 		/// - Write Each: System Account, Locks, Ledger (3 items)
@@ -2071,15 +1745,13 @@ decl_module! {
 			+ T::DbWeight::get().writes(3) * Weight::from(T::MaxNominatorRewardedPerValidator::get() + 1)
 		]
 		fn payout_stakers(origin, validator_stash: T::AccountId, era: EraIndex) -> DispatchResult {
-			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			ensure_signed(origin)?;
 			Self::do_payout_stakers(validator_stash, era)
 		}
 
 		/// Rebond a portion of the stash scheduled to be unlocked.
 		///
-		/// The dispatch origin must be signed by the controller, and it can be only called when
-		/// [`EraElectionStatus`] is `Closed`.
+		/// The dispatch origin must be signed by the controller.
 		///
 		/// # <weight>
 		/// - Time complexity: O(L), where L is unlocking chunks
@@ -2088,7 +1760,7 @@ decl_module! {
 		/// ---------------
 		/// - Base Weight: 34.51 µs * .048 L µs
 		/// - DB Weight:
-		///     - Reads: EraElectionStatus, Ledger, Locks, [Origin Account]
+		///     - Reads: Ledger, Locks, [Origin Account]
 		///     - Writes: [Origin Account], Locks, Ledger
 		/// # </weight>
 		#[weight =
@@ -2097,7 +1769,6 @@ decl_module! {
 			+ T::DbWeight::get().reads_writes(3, 2)
 		]
 		fn rebond(origin, #[compact] value: BalanceOf<T>) -> DispatchResultWithPostInfo {
-			ensure!(Self::era_election_status().is_closed(), Error::<T>::CallNotAllowed);
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			ensure!(!ledger.unlocking.is_empty(), Error::<T>::NoUnlockChunk);
@@ -2185,108 +1856,6 @@ decl_module! {
 			Self::kill_stash(&stash, num_slashing_spans)?;
 			T::Currency::remove_lock(STAKING_ID, &stash);
 		}
-
-		/// Submit an election result to the chain. If the solution:
-		///
-		/// 1. is valid.
-		/// 2. has a better score than a potentially existing solution on chain.
-		///
-		/// then, it will be _put_ on chain.
-		///
-		/// A solution consists of two pieces of data:
-		///
-		/// 1. `winners`: a flat vector of all the winners of the round.
-		/// 2. `assignments`: the compact version of an assignment vector that encodes the edge
-		///    weights.
-		///
-		/// Both of which may be computed using _phragmen_, or any other algorithm.
-		///
-		/// Additionally, the submitter must provide:
-		///
-		/// - The `score` that they claim their solution has.
-		///
-		/// Both validators and nominators will be represented by indices in the solution. The
-		/// indices should respect the corresponding types ([`ValidatorIndex`] and
-		/// [`NominatorIndex`]). Moreover, they should be valid when used to index into
-		/// [`SnapshotValidators`] and [`SnapshotNominators`]. Any invalid index will cause the
-		/// solution to be rejected. These two storage items are set during the election window and
-		/// may be used to determine the indices.
-		///
-		/// A solution is valid if:
-		///
-		/// 0. It is submitted when [`EraElectionStatus`] is `Open`.
-		/// 1. Its claimed score is equal to the score computed on-chain.
-		/// 2. Presents the correct number of winners.
-		/// 3. All indexes must be value according to the snapshot vectors. All edge values must
-		///    also be correct and should not overflow the granularity of the ratio type (i.e. 256
-		///    or billion).
-		/// 4. For each edge, all targets are actually nominated by the voter.
-		/// 5. Has correct self-votes.
-		///
-		/// A solutions score is consisted of 3 parameters:
-		///
-		/// 1. `min { support.total }` for each support of a winner. This value should be maximized.
-		/// 2. `sum { support.total }` for each support of a winner. This value should be minimized.
-		/// 3. `sum { support.total^2 }` for each support of a winner. This value should be
-		///    minimized (to ensure less variance)
-		///
-		/// # <weight>
-		/// See `crate::weight` module.
-		/// # </weight>
-		#[weight = weight::weight_for_submit_solution::<T>(winners, compact, size)]
-		pub fn submit_election_solution(
-			origin,
-			winners: Vec<ValidatorIndex>,
-			compact: CompactAssignments,
-			score: ElectionScore,
-			era: EraIndex,
-			size: ElectionSize,
-		) -> DispatchResultWithPostInfo {
-			let _who = ensure_signed(origin)?;
-			Self::check_and_replace_solution(
-				winners,
-				compact,
-				ElectionCompute::Signed,
-				score,
-				era,
-				size,
-			)
-		}
-
-		/// Unsigned version of `submit_election_solution`.
-		///
-		/// Note that this must pass the [`ValidateUnsigned`] check which only allows transactions
-		/// from the local node to be included. In other words, only the block author can include a
-		/// transaction in the block.
-		///
-		/// # <weight>
-		/// See `crate::weight` module.
-		/// # </weight>
-		#[weight = weight::weight_for_submit_solution::<T>(winners, compact, size)]
-		pub fn submit_election_solution_unsigned(
-			origin,
-			winners: Vec<ValidatorIndex>,
-			compact: CompactAssignments,
-			score: ElectionScore,
-			era: EraIndex,
-			size: ElectionSize,
-		) -> DispatchResultWithPostInfo {
-			ensure_none(origin)?;
-			let adjustments = Self::check_and_replace_solution(
-				winners,
-				compact,
-				ElectionCompute::Unsigned,
-				score,
-				era,
-				size,
-			).expect(
-				"An unsigned solution can only be submitted by validators; A validator should \
-				always produce correct solutions, else this block should not be imported, thus \
-				effectively depriving the validators from their authoring reward. Hence, this panic
-				is expected."
-			);
-			Ok(adjustments)
-		}
 	}
 }
 
@@ -2304,52 +1873,6 @@ impl<T: Trait> Module<T> {
 		)
 	}
 
-	/// Dump the list of validators and nominators into vectors and keep them on-chain.
-	///
-	/// This data is used to efficiently evaluate election results. returns `true` if the operation
-	/// is successful.
-	pub fn create_stakers_snapshot() -> (bool, Weight) {
-		let mut consumed_weight = 0;
-		let mut add_db_reads_writes = |reads, writes| {
-			consumed_weight += T::DbWeight::get().reads_writes(reads, writes);
-		};
-		let validators = <Validators<T>>::iter().map(|(v, _)| v).collect::<Vec<_>>();
-		let mut nominators = <Nominators<T>>::iter().map(|(n, _)| n).collect::<Vec<_>>();
-
-		let num_validators = validators.len();
-		let num_nominators = nominators.len();
-		add_db_reads_writes((num_validators + num_nominators) as Weight, 0);
-
-		if
-			num_validators > MAX_VALIDATORS ||
-			num_nominators.saturating_add(num_validators) > MAX_NOMINATORS
-		{
-			log!(
-				warn,
-				"💸 Snapshot size too big [{} <> {}][{} <> {}].",
-				num_validators,
-				MAX_VALIDATORS,
-				num_nominators,
-				MAX_NOMINATORS,
-			);
-			(false, consumed_weight)
-		} else {
-			// all validators nominate themselves;
-			nominators.extend(validators.clone());
-
-			<SnapshotValidators<T>>::put(validators);
-			<SnapshotNominators<T>>::put(nominators);
-			add_db_reads_writes(0, 2);
-			(true, consumed_weight)
-		}
-	}
-
-	/// Clears both snapshots of stakers.
-	fn kill_stakers_snapshot() {
-		<SnapshotValidators<T>>::kill();
-		<SnapshotNominators<T>>::kill();
-	}
-
 	fn do_payout_stakers(
 		validator_stash: T::AccountId,
 		era: EraIndex,
@@ -2491,6 +2014,7 @@ impl<T: Trait> Module<T> {
 
 	/// Plan a new session potentially trigger a new era.
 	fn new_session(session_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+		CurrentPlannedSession::put(session_index);
 		if let Some(current_era) = Self::current_era() {
 			// Initial era has been set.
 
@@ -2509,13 +2033,6 @@ impl<T: Trait> Module<T> {
 				Forcing::NotForcing if era_length >= T::SessionsPerEra::get() => (),
 				_ => {
 					// Either `ForceNone`, or `NotForcing && era_length < T::SessionsPerEra::get()`.
-					if era_length + 1 == T::SessionsPerEra::get() {
-						IsCurrentSessionFinal::put(true);
-					} else if era_length >= T::SessionsPerEra::get() {
-						// Should only happen when we are ready to trigger an era but we have ForceNone,
-						// otherwise previous arm would short circuit.
-						Self::close_election_window();
-					}
 					return None
 				},
 			}
@@ -2528,217 +2045,6 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
-	/// Basic and cheap checks that we perform in validate unsigned, and in the execution.
-	///
-	/// State reads: ElectionState, CurrentEr, QueuedScore.
-	///
-	/// This function does weight refund in case of errors, which is based upon the fact that it is
-	/// called at the very beginning of the call site's function.
-	pub fn pre_dispatch_checks(score: ElectionScore, era: EraIndex) -> DispatchResultWithPostInfo {
-		// discard solutions that are not in-time
-		// check window open
-		ensure!(
-			Self::era_election_status().is_open(),
-			Error::<T>::PhragmenEarlySubmission.with_weight(T::DbWeight::get().reads(1)),
-		);
-
-		// check current era.
-		if let Some(current_era) = Self::current_era() {
-			ensure!(
-				current_era == era,
-				Error::<T>::PhragmenEarlySubmission.with_weight(T::DbWeight::get().reads(2)),
-			)
-		}
-
-		// assume the given score is valid. Is it better than what we have on-chain, if we have any?
-		if let Some(queued_score) = Self::queued_score() {
-			ensure!(
-				is_score_better(score, queued_score, T::MinSolutionScoreBump::get()),
-				Error::<T>::PhragmenWeakSubmission.with_weight(T::DbWeight::get().reads(3)),
-			)
-		}
-
-		Ok(None.into())
-	}
-
-	/// Checks a given solution and if correct and improved, writes it on chain as the queued result
-	/// of the next round. This may be called by both a signed and an unsigned transaction.
-	pub fn check_and_replace_solution(
-		winners: Vec<ValidatorIndex>,
-		compact_assignments: CompactAssignments,
-		compute: ElectionCompute,
-		claimed_score: ElectionScore,
-		era: EraIndex,
-		election_size: ElectionSize,
-	) -> DispatchResultWithPostInfo {
-		// Do the basic checks. era, claimed score and window open.
-		Self::pre_dispatch_checks(claimed_score, era)?;
-		// the weight that we will refund in case of a correct submission. We compute this now
-		// because the data needed for it will be consumed further down.
-		let adjusted_weight = weight::weight_for_correct_submit_solution::<T>(
-			&winners,
-			&compact_assignments,
-			&election_size,
-		);
-
-		// Check that the number of presented winners is sane. Most often we have more candidates
-		// than we need. Then it should be `Self::validator_count()`. Else it should be all the
-		// candidates.
-		let snapshot_validators_length = <SnapshotValidators<T>>::decode_len()
-			.map(|l| l as u32)
-			.ok_or_else(|| Error::<T>::SnapshotUnavailable)?;
-
-		// size of the solution must be correct.
-		ensure!(
-			snapshot_validators_length == u32::from(election_size.validators),
-			Error::<T>::PhragmenBogusElectionSize,
-		);
-
-		// check the winner length only here and when we know the length of the snapshot validators
-		// length.
-		let desired_winners = Self::validator_count().min(snapshot_validators_length);
-		ensure!(winners.len() as u32 == desired_winners, Error::<T>::PhragmenBogusWinnerCount);
-
-		let snapshot_nominators_len = <SnapshotNominators<T>>::decode_len()
-			.map(|l| l as u32)
-			.ok_or_else(|| Error::<T>::SnapshotUnavailable)?;
-
-		// rest of the size of the solution must be correct.
-		ensure!(
-			snapshot_nominators_len == election_size.nominators,
-			Error::<T>::PhragmenBogusElectionSize,
-		);
-
-		// decode snapshot validators.
-		let snapshot_validators = Self::snapshot_validators()
-			.ok_or(Error::<T>::SnapshotUnavailable)?;
-
-		// check if all winners were legit; this is rather cheap. Replace with accountId.
-		let winners = winners.into_iter().map(|widx| {
-			// NOTE: at the moment, since staking is explicitly blocking any offence until election
-			// is closed, we don't check here if the account id at `snapshot_validators[widx]` is
-			// actually a validator. If this ever changes, this loop needs to also check this.
-			snapshot_validators.get(widx as usize).cloned().ok_or(Error::<T>::PhragmenBogusWinner)
-		}).collect::<Result<Vec<T::AccountId>, Error<T>>>()?;
-
-		// decode the rest of the snapshot.
-		let snapshot_nominators = Self::snapshot_nominators()
-			.ok_or(Error::<T>::SnapshotUnavailable)?;
-
-		// helpers
-		let nominator_at = |i: NominatorIndex| -> Option<T::AccountId> {
-			snapshot_nominators.get(i as usize).cloned()
-		};
-		let validator_at = |i: ValidatorIndex| -> Option<T::AccountId> {
-			snapshot_validators.get(i as usize).cloned()
-		};
-
-		// un-compact.
-		let assignments = compact_assignments.into_assignment(
-			nominator_at,
-			validator_at,
-		).map_err(|e| {
-			// log the error since it is not propagated into the runtime error.
-			log!(warn, "💸 un-compacting solution failed due to {:?}", e);
-			Error::<T>::PhragmenBogusCompact
-		})?;
-
-		// check all nominators actually including the claimed vote. Also check correct self votes.
-		// Note that we assume all validators and nominators in `assignments` are properly bonded,
-		// because they are coming from the snapshot via a given index.
-		for Assignment { who, distribution } in assignments.iter() {
-			let is_validator = <Validators<T>>::contains_key(&who);
-			let maybe_nomination = Self::nominators(&who);
-
-			if !(maybe_nomination.is_some() ^ is_validator) {
-				// all of the indices must map to either a validator or a nominator. If this is ever
-				// not the case, then the locking system of staking is most likely faulty, or we
-				// have bigger problems.
-				log!(error, "💸 detected an error in the staking locking and snapshot.");
-				// abort.
-				return Err(Error::<T>::PhragmenBogusNominator.into());
-			}
-
-			if !is_validator {
-				// a normal vote
-				let nomination = maybe_nomination.expect(
-					"exactly one of `maybe_validator` and `maybe_nomination.is_some` is true. \
-					is_validator is false; maybe_nomination is some; qed"
-				);
-
-				// NOTE: we don't really have to check here if the sum of all edges are the
-				// nominator correct. Un-compacting assures this by definition.
-
-				for (t, _) in distribution {
-					// each target in the provided distribution must be actually nominated by the
-					// nominator after the last non-zero slash.
-					if nomination.targets.iter().find(|&tt| tt == t).is_none() {
-						return Err(Error::<T>::PhragmenBogusNomination.into());
-					}
-
-					if <Self as Store>::SlashingSpans::get(&t).map_or(
-						false,
-						|spans| nomination.submitted_in < spans.last_nonzero_slash(),
-					) {
-						return Err(Error::<T>::PhragmenSlashedNomination.into());
-					}
-				}
-			} else {
-				// a self vote
-				ensure!(distribution.len() == 1, Error::<T>::PhragmenBogusSelfVote);
-				ensure!(distribution[0].0 == *who, Error::<T>::PhragmenBogusSelfVote);
-				// defensive only. A compact assignment of length one does NOT encode the weight and
-				// it is always created to be 100%.
-				ensure!(
-					distribution[0].1 == OffchainAccuracy::one(),
-					Error::<T>::PhragmenBogusSelfVote,
-				);
-			}
-		}
-
-		// convert into staked assignments.
-		let staked_assignments = sp_npos_elections::assignment_ratio_to_staked(
-			assignments,
-			Self::slashable_balance_of_vote_weight,
-		);
-
-		// build the support map thereof in order to evaluate.
-		// OPTIMIZATION: loop to create the staked assignments but it would bloat the code. Okay for
-		// now as it does not add to the complexity order.
-		let (supports, num_error) = build_support_map::<T::AccountId>(
-			&winners,
-			&staked_assignments,
-		);
-		// This technically checks that all targets in all nominators were among the winners.
-		ensure!(num_error == 0, Error::<T>::PhragmenBogusEdge);
-
-		// Check if the score is the same as the claimed one.
-		let submitted_score = evaluate_support(&supports);
-		ensure!(submitted_score == claimed_score, Error::<T>::PhragmenBogusScore);
-
-		// At last, alles Ok. Exposures and store the result.
-		let exposures = Self::collect_exposure(supports);
-		log!(
-			info,
-			"💸 A better solution (with compute {:?} and score {:?}) has been validated and stored on chain.",
-			compute,
-			submitted_score,
-		);
-
-		// write new results.
-		<QueuedElected<T>>::put(ElectionResult {
-			elected_stashes: winners,
-			compute,
-			exposures,
-		});
-		QueuedScore::put(submitted_score);
-
-		// emit event.
-		Self::deposit_event(RawEvent::SolutionStored(compute));
-
-		Ok(Some(adjusted_weight).into())
-	}
-
 	/// Start a session potentially starting an era.
 	fn start_session(start_session: SessionIndex) {
 		let next_active_era = Self::active_era().map(|e| e.index + 1).unwrap_or(0);
@@ -2855,194 +2161,86 @@ impl<T: Trait> Module<T> {
 	}
 
 
-	/// Remove all the storage items associated with the election.
-	fn close_election_window() {
-		// Close window.
-		<EraElectionStatus<T>>::put(ElectionStatus::Closed);
-		// Kill snapshots.
-		Self::kill_stakers_snapshot();
-		// Don't track final session.
-		IsCurrentSessionFinal::put(false);
-	}
-
 	/// Select the new validator set at the end of the era.
 	///
-	/// Runs [`try_do_phragmen`] and updates the following storage items:
-	/// - [`EraElectionStatus`]: with `None`.
+	/// Asks [`Trait::ElectionProvider`] for the next winners and updates the following storage
+	/// items:
 	/// - [`ErasStakers`]: with the new staker set.
 	/// - [`ErasStakersClipped`].
 	/// - [`ErasValidatorPrefs`].
 	/// - [`ErasTotalStake`]: with the new total stake.
-	/// - [`SnapshotValidators`] and [`SnapshotNominators`] are both removed.
-	///
-	/// Internally, [`QueuedElected`], snapshots and [`QueuedScore`] are also consumed.
 	///
 	/// If the election has been successful, It passes the new set upwards.
 	///
 	/// This should only be called at the end of an era.
 	fn select_and_update_validators(current_era: EraIndex) -> Option<Vec<T::AccountId>> {
-		if let Some(ElectionResult::<T::AccountId, BalanceOf<T>> {
-			elected_stashes,
-			exposures,
-			compute,
-		}) = Self::try_do_election() {
-			// Totally close the election round and data.
-			Self::close_election_window();
-
-			// Populate Stakers and write slot stake.
-			let mut total_stake: BalanceOf<T> = Zero::zero();
-			exposures.into_iter().for_each(|(stash, exposure)| {
-				total_stake = total_stake.saturating_add(exposure.total);
-				<ErasStakers<T>>::insert(current_era, &stash, &exposure);
-
-				let mut exposure_clipped = exposure;
-				let clipped_max_len = T::MaxNominatorRewardedPerValidator::get() as usize;
-				if exposure_clipped.others.len() > clipped_max_len {
-					exposure_clipped.others.sort_by(|a, b| a.value.cmp(&b.value).reverse());
-					exposure_clipped.others.truncate(clipped_max_len);
-				}
-				<ErasStakersClipped<T>>::insert(&current_era, &stash, exposure_clipped);
-			});
-
-			// Insert current era staking information
-			<ErasTotalStake<T>>::insert(&current_era, total_stake);
-
-			// collect the pref of all winners
-			for stash in &elected_stashes {
-				let pref = Self::validators(stash);
-				<ErasValidatorPrefs<T>>::insert(&current_era, stash, pref);
+		let supports = match T::ElectionProvider::elect() {
+			Ok(supports) => supports,
+			Err(why) => {
+				log!(warn, "💸 election provider failed due to {:?}", why);
+				return None;
 			}
+		};
 
-			// emit event
-			Self::deposit_event(RawEvent::StakingElection(compute));
-
+		if (supports.len() as u32) < Self::minimum_validator_count().max(1) {
+			// There were not enough winners for even our minimal level of functionality. This is
+			// bad. We should probably disable all functionality except for block production and
+			// let the chain keep producing blocks until we can decide on a sufficiently substantial
+			// set. TODO: #2494
 			log!(
-				info,
-				"💸 new validator set of size {:?} has been elected via {:?} for era {:?}",
-				elected_stashes.len(),
-				compute,
+				warn,
+				"💸 chain does not have enough staking candidates to operate for era {:?}",
 				current_era,
 			);
-
-			Some(elected_stashes)
-		} else {
-			None
+			return None;
 		}
-	}
 
-	/// Select a new validator set from the assembled stakers and their role preferences. It tries
-	/// first to peek into [`QueuedElected`]. Otherwise, it runs a new on-chain phragmen election.
-	///
-	/// If [`QueuedElected`] and [`QueuedScore`] exists, they are both removed. No further storage
-	/// is updated.
-	fn try_do_election() -> Option<ElectionResult<T::AccountId, BalanceOf<T>>> {
-		// an election result from either a stored submission or locally executed one.
-		let next_result = <QueuedElected<T>>::take().or_else(||
-			Self::do_phragmen_with_post_processing::<ChainAccuracy>(ElectionCompute::OnChain)
-		);
-
-		// either way, kill this. We remove it here to make sure it always has the exact same
-		// lifetime as `QueuedElected`.
-		QueuedScore::kill();
-
-		next_result
-	}
+		let elected_stashes = supports.iter().map(|(s, _)| s.clone()).collect::<Vec<_>>();
+		let exposures = Self::collect_exposure(supports);
 
-	/// Execute election and return the new results. The edge weights are processed into support
-	/// values.
-	///
-	/// This is basically a wrapper around [`do_phragmen`] which translates
-	/// `PrimitiveElectionResult` into `ElectionResult`.
-	///
-	/// No storage item is updated.
-	fn do_phragmen_with_post_processing<Accuracy: PerThing>(compute: ElectionCompute)
-		-> Option<ElectionResult<T::AccountId, BalanceOf<T>>>
-	where
-		Accuracy: sp_std::ops::Mul<ExtendedBalance, Output=ExtendedBalance>,
-		ExtendedBalance: From<<Accuracy as PerThing>::Inner>,
-	{
-		if let Some(phragmen_result) = Self::do_phragmen::<Accuracy>() {
-			let elected_stashes = phragmen_result.winners.iter()
-				.map(|(s, _)| s.clone())
-				.collect::<Vec<T::AccountId>>();
-			let assignments = phragmen_result.assignments;
-
-			let staked_assignments = sp_npos_elections::assignment_ratio_to_staked(
-				assignments,
-				Self::slashable_balance_of_vote_weight,
-			);
+		// Populate Stakers and write slot stake.
+		let mut total_stake: BalanceOf<T> = Zero::zero();
+		exposures.into_iter().for_each(|(stash, exposure)| {
+			total_stake = total_stake.saturating_add(exposure.total);
+			<ErasStakers<T>>::insert(current_era, &stash, &exposure);
+
+			let mut exposure_clipped = exposure;
+			let clipped_max_len = T::MaxNominatorRewardedPerValidator::get() as usize;
+			if exposure_clipped.others.len() > clipped_max_len {
+				exposure_clipped.others.sort_by(|a, b| a.value.cmp(&b.value).reverse());
+				exposure_clipped.others.truncate(clipped_max_len);
+			}
+			<ErasStakersClipped<T>>::insert(&current_era, &stash, exposure_clipped);
+		});
 
-			let (supports, _) = build_support_map::<T::AccountId>(
-				&elected_stashes,
-				&staked_assignments,
-			);
+		// Insert current era staking information
+		<ErasTotalStake<T>>::insert(&current_era, total_stake);
 
-			// collect exposures
-			let exposures = Self::collect_exposure(supports);
-
-			// In order to keep the property required by `on_session_ending` that we must return the
-			// new validator set even if it's the same as the old, as long as any underlying
-			// economic conditions have changed, we don't attempt to do any optimization where we
-			// compare against the prior set.
-			Some(ElectionResult::<T::AccountId, BalanceOf<T>> {
-				elected_stashes,
-				exposures,
-				compute,
-			})
-		} else {
-			// There were not enough candidates for even our minimal level of functionality. This is
-			// bad. We should probably disable all functionality except for block production and let
-			// the chain keep producing blocks until we can decide on a sufficiently substantial
-			// set. TODO: #2494
-			None
+		// collect the pref of all winners
+		for stash in &elected_stashes {
+			let pref = Self::validators(stash);
+			<ErasValidatorPrefs<T>>::insert(&current_era, stash, pref);
 		}
-	}
 
-	/// Execute phragmen election and return the new results. No post-processing is applied and the
-	/// raw edge weights are returned.
-	///
-	/// Self votes are added and nominations before the most recent slashing span are reaped.
-	///
-	/// No storage item is updated.
-	fn do_phragmen<Accuracy: PerThing>() -> Option<PrimitiveElectionResult<T::AccountId, Accuracy>> {
-		let mut all_nominators: Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> = Vec::new();
-		let mut all_validators = Vec::new();
-		for (validator, _) in <Validators<T>>::iter() {
-			// append self vote
-			let self_vote = (validator.clone(), Self::slashable_balance_of_vote_weight(&validator), vec![validator.clone()]);
-			all_nominators.push(self_vote);
-			all_validators.push(validator);
-		}
-
-		let nominator_votes = <Nominators<T>>::iter().map(|(nominator, nominations)| {
-			let Nominations { submitted_in, mut targets, suppressed: _ } = nominations;
+		// emit event
+		Self::deposit_event(RawEvent::StakingElection);
 
-			// Filter out nomination targets which were nominated before the most recent
-			// slashing span.
-			targets.retain(|stash| {
-				<Self as Store>::SlashingSpans::get(&stash).map_or(
-					true,
-					|spans| submitted_in >= spans.last_nonzero_slash(),
-				)
-			});
+		log!(
+			info,
+			"💸 new validator set of size {:?} has been elected for era {:?}",
+			elected_stashes.len(),
+			current_era,
+		);
 
-			(nominator, targets)
-		});
-		all_nominators.extend(nominator_votes.map(|(n, ns)| {
-			let s = Self::slashable_balance_of_vote_weight(&n);
-			(n, s, ns)
-		}));
-
-		seq_phragmen::<_, Accuracy>(
-			Self::validator_count() as usize,
-			Self::minimum_validator_count().max(1) as usize,
-			all_validators,
-			all_nominators,
-		)
+		// In order to keep the property required by `on_session_ending` that we must return the
+		// new validator set even if it's the same as the old, as long as any underlying
+		// economic conditions have changed, we don't attempt to do any optimization where we
+		// compare against the prior set.
+		Some(elected_stashes)
 	}
 
-	/// Consume a set of [`Supports`] from [`sp_npos_elections`] and collect them into a [`Exposure`]
-	fn collect_exposure(supports: SupportMap<T::AccountId>) -> Vec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>)> {
+	/// Consume a set of [`Supports`] from the election provider and collect them into a [`Exposure`]
+	fn collect_exposure(supports: Supports<T::AccountId>) -> Vec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>)> {
 		let to_balance = |e: ExtendedBalance|
 			<T::CurrencyToVote as Convert<ExtendedBalance, BalanceOf<T>>>::convert(e);
 
@@ -3169,11 +2367,6 @@ impl<T: Trait> Module<T> {
 		<ErasStakers<T>>::insert(&current_era, &controller, &exposure);
 	}
 
-	#[cfg(feature = "runtime-benchmarks")]
-	pub fn put_election_status(status: ElectionStatus::<T::BlockNumber>) {
-		<EraElectionStatus<T>>::put(status);
-	}
-
 	#[cfg(feature = "runtime-benchmarks")]
 	pub fn set_slash_reward_fraction(fraction: Perbill) {
 		SlashRewardFraction::put(fraction);
@@ -3181,6 +2374,134 @@ impl<T: Trait> Module<T> {
 
 }
 
+impl<T: Trait> StakingInterface for Module<T> {
+	type Balance = BalanceOf<T>;
+	type AccountId = T::AccountId;
+
+	fn minimum_bond() -> Self::Balance {
+		T::Currency::minimum_balance()
+	}
+
+	fn bonding_duration() -> EraIndex {
+		T::BondingDuration::get()
+	}
+
+	fn current_era() -> EraIndex {
+		Self::current_era().unwrap_or(Zero::zero())
+	}
+
+	fn active_stake(controller: &Self::AccountId) -> Option<Self::Balance> {
+		Self::ledger(controller).map(|l| l.active)
+	}
+
+	fn total_stake(controller: &Self::AccountId) -> Option<Self::Balance> {
+		Self::ledger(controller).map(|l| l.total)
+	}
+
+	fn bond(
+		stash: Self::AccountId,
+		controller: Self::AccountId,
+		value: Self::Balance,
+	) -> DispatchResult {
+		Self::bond(
+			RawOrigin::Signed(stash).into(),
+			T::Lookup::unlookup(controller),
+			value,
+			RewardDestination::Controller,
+		)
+	}
+
+	fn bond_extra(stash: Self::AccountId, extra: Self::Balance) -> DispatchResult {
+		Self::bond_extra(RawOrigin::Signed(stash).into(), extra)
+	}
+
+	fn unbond(controller: Self::AccountId, value: Self::Balance) -> DispatchResult {
+		Self::unbond(RawOrigin::Signed(controller).into(), value)
+	}
+
+	fn withdraw_unbonded(
+		controller: Self::AccountId,
+		num_slashing_spans: u32,
+	) -> Result<bool, DispatchError> {
+		Self::withdraw_unbonded(RawOrigin::Signed(controller.clone()).into(), num_slashing_spans)
+			.map(|_| !<Ledger<T>>::contains_key(&controller))
+			.map_err(|e| e.error)
+	}
+
+	fn nominate(controller: Self::AccountId, targets: Vec<Self::AccountId>) -> DispatchResult {
+		let targets = targets.into_iter().map(T::Lookup::unlookup).collect();
+		Self::nominate(RawOrigin::Signed(controller).into(), targets)
+	}
+}
+
+impl<T: Trait> ElectionDataProvider<T::AccountId, T::BlockNumber> for Module<T> {
+	fn desired_targets() -> u32 {
+		Self::validator_count()
+	}
+
+	fn voters() -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+		let mut all_voters = Vec::new();
+
+		// every validator votes for themselves.
+		for (validator, _) in <Validators<T>>::iter() {
+			let self_vote = (
+				validator.clone(),
+				Self::slashable_balance_of_vote_weight(&validator),
+				vec![validator],
+			);
+			all_voters.push(self_vote);
+		}
+
+		for (nominator, nominations) in <Nominators<T>>::iter() {
+			let Nominations { submitted_in, mut targets, suppressed: _ } = nominations;
+
+			// Filter out nomination targets which were nominated before the most recent
+			// slashing span.
+			targets.retain(|stash| {
+				<Self as Store>::SlashingSpans::get(&stash).map_or(
+					true,
+					|spans| submitted_in >= spans.last_nonzero_slash(),
+				)
+			});
+
+			let vote_weight = Self::slashable_balance_of_vote_weight(&nominator);
+			all_voters.push((nominator, vote_weight, targets));
+		}
+
+		all_voters
+	}
+
+	fn targets() -> Vec<T::AccountId> {
+		<Validators<T>>::iter().map(|(v, _)| v).collect::<Vec<_>>()
+	}
+
+	fn next_election_prediction(now: T::BlockNumber) -> T::BlockNumber {
+		let current_era = Self::current_era().unwrap_or(0);
+		let current_session = Self::current_planned_session();
+		let current_era_start_session_index =
+			Self::eras_start_session_index(current_era).unwrap_or(0);
+		let era_length = current_session
+			.saturating_sub(current_era_start_session_index)
+			.min(T::SessionsPerEra::get());
+
+		let session_length = T::NextNewSession::average_session_length();
+
+		let until_this_session_end = T::NextNewSession::estimate_next_new_session(now)
+			.unwrap_or_default()
+			.saturating_sub(now);
+
+		let sessions_left: T::BlockNumber = T::SessionsPerEra::get()
+			.saturating_sub(era_length)
+			// one session is computed in this_session_end.
+			.saturating_sub(1)
+			.into();
+
+		now.saturating_add(
+			until_this_session_end.saturating_add(sessions_left.saturating_mul(session_length))
+		)
+	}
+}
+
 /// In this implementation `new_session(session)` must be called before `end_session(session-1)`
 /// i.e. the new session must be planned before the ending of the previous session.
 ///
@@ -3402,7 +2723,7 @@ for Module<T> where
 	}
 
 	fn can_report() -> bool {
-		Self::era_election_status().is_closed()
+		true
 	}
 }
 
@@ -3437,100 +2758,8 @@ impl<T, Reporter, Offender, R, O> ReportOffence<Reporter, Offender, O>
 	}
 }
 
-#[allow(deprecated)]
-impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
-	type Call = Call<T>;
-	fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-		if let Call::submit_election_solution_unsigned(
-			_,
-			_,
-			score,
-			era,
-			_,
-		) = call {
-			use offchain_election::DEFAULT_LONGEVITY;
-
-			// discard solution not coming from the local OCW.
-			match source {
-				TransactionSource::Local | TransactionSource::InBlock => { /* allowed */ }
-				_ => {
-					log!(debug, "rejecting unsigned transaction because it is not local/in-block.");
-					return InvalidTransaction::Call.into();
-				}
-			}
-
-			if let Err(error_with_post_info) = Self::pre_dispatch_checks(*score, *era) {
-				let invalid = to_invalid(error_with_post_info);
-				log!(
-					debug,
-					"validate unsigned pre dispatch checks failed due to error #{:?}.",
-					invalid,
-				);
-				return invalid .into();
-			}
-
-			log!(debug, "validateUnsigned succeeded for a solution at era {}.", era);
-
-			ValidTransaction::with_tag_prefix("StakingOffchain")
-				// The higher the score[0], the better a solution is.
-				.priority(T::UnsignedPriority::get().saturating_add(score[0].saturated_into()))
-				// Defensive only. A single solution can exist in the pool per era. Each validator
-				// will run OCW at most once per era, hence there should never exist more than one
-				// transaction anyhow.
-				.and_provides(era)
-				// Note: this can be more accurate in the future. We do something like
-				// `era_end_block - current_block` but that is not needed now as we eagerly run
-				// offchain workers now and the above should be same as `T::ElectionLookahead`
-				// without the need to query more storage in the validation phase. If we randomize
-				// offchain worker, then we might re-consider this.
-				.longevity(TryInto::<u64>::try_into(
-						T::ElectionLookahead::get()).unwrap_or(DEFAULT_LONGEVITY)
-				)
-				// We don't propagate this. This can never the validated at a remote node.
-				.propagate(false)
-				.build()
-		} else {
-			InvalidTransaction::Call.into()
-		}
-	}
-
-	fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
-		if let Call::submit_election_solution_unsigned(
-			_,
-			_,
-			score,
-			era,
-			_,
-		) = call {
-			// IMPORTANT NOTE: These checks are performed in the dispatch call itself, yet we need
-			// to duplicate them here to prevent a block producer from putting a previously
-			// validated, yet no longer valid solution on chain.
-			// OPTIMISATION NOTE: we could skip this in the `submit_election_solution_unsigned`
-			// since we already do it here. The signed version needs it though. Yer for now we keep
-			// this duplicate check here so both signed and unsigned can use a singular
-			// `check_and_replace_solution`.
-			Self::pre_dispatch_checks(*score, *era)
-				.map(|_| ())
-				.map_err(to_invalid)
-				.map_err(Into::into)
-		} else {
-			Err(InvalidTransaction::Call.into())
-		}
-	}
-}
-
 /// Check that list is sorted and has no duplicates.
 fn is_sorted_and_unique(list: &[u32]) -> bool {
 	list.windows(2).all(|w| w[0] < w[1])
 }
 
-/// convert a DispatchErrorWithPostInfo to a custom InvalidTransaction with the inner code being the
-/// error number.
-fn to_invalid(error_with_post_info: DispatchErrorWithPostInfo) -> InvalidTransaction {
-	let error = error_with_post_info.error;
-	let error_number = match error {
-		DispatchError::Module { error, ..} => error,
-		_ => 0,
-	};
-	InvalidTransaction::Custom(error_number)
-}
@@ -20,8 +20,8 @@
 use std::{collections::HashSet, cell::RefCell};
 use sp_runtime::Perbill;
 use sp_runtime::curve::PiecewiseLinear;
-use sp_runtime::traits::{IdentityLookup, Convert, SaturatedConversion, Zero};
-use sp_runtime::testing::{Header, UintAuthorityId, TestXt};
+use sp_runtime::traits::{IdentityLookup, Convert, SaturatedConversion};
+use sp_runtime::testing::{Header, UintAuthorityId};
 use sp_staking::{SessionIndex, offence::{OffenceDetails, OnOffenceHandler}};
 use sp_core::H256;
 use frame_support::{
@@ -31,10 +31,7 @@ use frame_support::{
 	weights::{Weight, constants::RocksDbWeight},
 };
 use sp_io;
-use sp_npos_elections::{
-	build_support_map, evaluate_support, reduce, ExtendedBalance, StakedAssignment, ElectionScore,
-	VoteWeight,
-};
+use frame_election_provider_support::onchain;
 use crate::*;
 
 pub const INIT_TIMESTAMP: u64 = 30_000;
@@ -63,9 +60,7 @@ thread_local! {
 	static SESSION_PER_ERA: RefCell<SessionIndex> = RefCell::new(3);
 	static EXISTENTIAL_DEPOSIT: RefCell<Balance> = RefCell::new(0);
 	static SLASH_DEFER_DURATION: RefCell<EraIndex> = RefCell::new(0);
-	static ELECTION_LOOKAHEAD: RefCell<BlockNumber> = RefCell::new(0);
 	static PERIOD: RefCell<BlockNumber> = RefCell::new(1);
-	static MAX_ITERATIONS: RefCell<u32> = RefCell::new(0);
 }
 
 /// Another session handler struct to test on_disabled.
@@ -124,13 +119,6 @@ impl Get<BlockNumber> for SessionsPerEra {
 	}
 }
 
-pub struct ElectionLookahead;
-impl Get<BlockNumber> for ElectionLookahead {
-	fn get() -> BlockNumber {
-		ELECTION_LOOKAHEAD.with(|v| *v.borrow())
-	}
-}
-
 pub struct Period;
 impl Get<BlockNumber> for Period {
 	fn get() -> BlockNumber {
@@ -145,13 +133,6 @@ impl Get<EraIndex> for SlashDeferDuration {
 	}
 }
 
-pub struct MaxIterations;
-impl Get<u32> for MaxIterations {
-	fn get() -> u32 {
-		MAX_ITERATIONS.with(|v| *v.borrow())
-	}
-}
-
 impl_outer_origin! {
 	pub enum Origin for Test where system = frame_system {}
 }
@@ -290,8 +271,6 @@ parameter_types! {
 	pub const BondingDuration: EraIndex = 3;
 	pub const RewardCurve: &'static PiecewiseLinear<'static> = &I_NPOS;
 	pub const MaxNominatorRewardedPerValidator: u32 = 64;
-	pub const UnsignedPriority: u64 = 1 << 20;
-	pub const MinSolutionScoreBump: Perbill = Perbill::zero();
 }
 
 thread_local! {
@@ -324,27 +303,21 @@ impl Trait for Test {
 	type SessionInterface = Self;
 	type RewardCurve = RewardCurve;
 	type NextNewSession = Session;
-	type ElectionLookahead = ElectionLookahead;
-	type Call = Call;
-	type MaxIterations = MaxIterations;
-	type MinSolutionScoreBump = MinSolutionScoreBump;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
-	type UnsignedPriority = UnsignedPriority;
+	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
+	type OnStakerSlash = ();
 	type WeightInfo = ();
 }
 
-impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test where
-	Call: From<LocalCall>,
-{
-	type OverarchingCall = Call;
-	type Extrinsic = Extrinsic;
+impl onchain::Config for Test {
+	type AccountId = AccountId;
+	type BlockNumber = BlockNumber;
+	type Accuracy = Perbill;
+	type DataProvider = Staking;
 }
 
-pub type Extrinsic = TestXt<Call, ()>;
-
 pub struct ExtBuilder {
 	session_length: BlockNumber,
-	election_lookahead: BlockNumber,
 	session_per_era: SessionIndex,
 	existential_deposit: Balance,
 	validator_pool: bool,
@@ -356,14 +329,12 @@ pub struct ExtBuilder {
 	num_validators: Option<u32>,
 	invulnerables: Vec<AccountId>,
 	has_stakers: bool,
-	max_offchain_iterations: u32,
 }
 
 impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
 			session_length: 1,
-			election_lookahead: 0,
 			session_per_era: 3,
 			existential_deposit: 1,
 			validator_pool: false,
@@ -375,7 +346,6 @@ impl Default for ExtBuilder {
 			num_validators: None,
 			invulnerables: vec![],
 			has_stakers: true,
-			max_offchain_iterations: 0,
 		}
 	}
 }
@@ -421,10 +391,6 @@ impl ExtBuilder {
 		self.session_per_era = length;
 		self
 	}
-	pub fn election_lookahead(mut self, look: BlockNumber) -> Self {
-		self.election_lookahead = look;
-		self
-	}
 	pub fn session_length(mut self, length: BlockNumber) -> Self {
 		self.session_length = length;
 		self
@@ -433,22 +399,11 @@ impl ExtBuilder {
 		self.has_stakers = has;
 		self
 	}
-	pub fn max_offchain_iterations(mut self, iterations: u32) -> Self {
-		self.max_offchain_iterations = iterations;
-		self
-	}
-	pub fn offchain_phragmen_ext(self) -> Self {
-		self.session_per_era(4)
-			.session_length(5)
-			.election_lookahead(3)
-	}
 	pub fn set_associated_constants(&self) {
 		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
 		SLASH_DEFER_DURATION.with(|v| *v.borrow_mut() = self.slash_defer_duration);
 		SESSION_PER_ERA.with(|v| *v.borrow_mut() = self.session_per_era);
-		ELECTION_LOOKAHEAD.with(|v| *v.borrow_mut() = self.election_lookahead);
 		PERIOD.with(|v| *v.borrow_mut() = self.session_length);
-		MAX_ITERATIONS.with(|v| *v.borrow_mut() = self.max_offchain_iterations);
 	}
 	pub fn build(self) -> sp_io::TestExternalities {
 		let _ = env_logger::try_init();
@@ -785,207 +740,6 @@ pub(crate) fn add_slash(who: &AccountId) {
 	);
 }
 
-// winners will be chosen by simply their unweighted total backing stake. Nominator stake is
-// distributed evenly.
-pub(crate) fn horrible_phragmen_with_post_processing(
-	do_reduce: bool,
-) -> (CompactAssignments, Vec<ValidatorIndex>, ElectionScore) {
-	let mut backing_stake_of: BTreeMap<AccountId, Balance> = BTreeMap::new();
-
-	// self stake
-	<Validators<Test>>::iter().for_each(|(who, _p)| {
-		*backing_stake_of.entry(who).or_insert(Zero::zero()) += Staking::slashable_balance_of(&who)
-	});
-
-	// add nominator stuff
-	<Nominators<Test>>::iter().for_each(|(who, nomination)| {
-		nomination.targets.iter().for_each(|v| {
-			*backing_stake_of.entry(*v).or_insert(Zero::zero()) +=
-				Staking::slashable_balance_of(&who)
-		})
-	});
-
-	// elect winners
-	let mut sorted: Vec<AccountId> = backing_stake_of.keys().cloned().collect();
-	sorted.sort_by_key(|x| backing_stake_of.get(x).unwrap());
-	let winners: Vec<AccountId> = sorted
-		.iter()
-		.cloned()
-		.take(Staking::validator_count() as usize)
-		.collect();
-
-	// create assignments
-	let mut staked_assignment: Vec<StakedAssignment<AccountId>> = Vec::new();
-	<Nominators<Test>>::iter().for_each(|(who, nomination)| {
-		let mut dist: Vec<(AccountId, ExtendedBalance)> = Vec::new();
-		nomination.targets.iter().for_each(|v| {
-			if winners.iter().find(|w| *w == v).is_some() {
-				dist.push((*v, ExtendedBalance::zero()));
-			}
-		});
-
-		if dist.len() == 0 {
-			return;
-		}
-
-		// assign real stakes. just split the stake.
-		let stake = Staking::slashable_balance_of(&who) as ExtendedBalance;
-		let mut sum: ExtendedBalance = Zero::zero();
-		let dist_len = dist.len();
-		{
-			dist.iter_mut().for_each(|(_, w)| {
-				let partial = stake / (dist_len as ExtendedBalance);
-				*w = partial;
-				sum += partial;
-			});
-		}
-
-		// assign the leftover to last.
-		{
-			let leftover = stake - sum;
-			let last = dist.last_mut().unwrap();
-			last.1 += leftover;
-		}
-
-		staked_assignment.push(StakedAssignment {
-			who,
-			distribution: dist,
-		});
-	});
-
-	// Ensure that this result is worse than seq-phragmen. Otherwise, it should not have been used
-	// for testing.
-	let score = {
-		let (_, _, better_score) = prepare_submission_with(true, 0, |_| {});
-
-		let support = build_support_map::<AccountId>(&winners, &staked_assignment).0;
-		let score = evaluate_support(&support);
-
-		assert!(sp_npos_elections::is_score_better::<Perbill>(
-			better_score,
-			score,
-			MinSolutionScoreBump::get(),
-		));
-
-		score
-	};
-
-	if do_reduce {
-		reduce(&mut staked_assignment);
-	}
-
-	let snapshot_validators = Staking::snapshot_validators().unwrap();
-	let snapshot_nominators = Staking::snapshot_nominators().unwrap();
-	let nominator_index = |a: &AccountId| -> Option<NominatorIndex> {
-		snapshot_nominators.iter().position(|x| x == a).map(|i| i as NominatorIndex)
-	};
-	let validator_index = |a: &AccountId| -> Option<ValidatorIndex> {
-		snapshot_validators.iter().position(|x| x == a).map(|i| i as ValidatorIndex)
-	};
-
-	// convert back to ratio assignment. This takes less space.
-	let assignments_reduced =
-		sp_npos_elections::assignment_staked_to_ratio::<AccountId, OffchainAccuracy>(staked_assignment);
-
-	let compact =
-		CompactAssignments::from_assignment(assignments_reduced, nominator_index, validator_index)
-			.unwrap();
-
-	// winner ids to index
-	let winners = winners.into_iter().map(|w| validator_index(&w).unwrap()).collect::<Vec<_>>();
-
-	(compact, winners, score)
-}
-
-// Note: this should always logically reproduce [`offchain_election::prepare_submission`], yet we
-// cannot do it since we want to have `tweak` injected into the process.
-pub(crate) fn prepare_submission_with(
-	do_reduce: bool,
-	iterations: usize,
-	tweak: impl FnOnce(&mut Vec<StakedAssignment<AccountId>>),
-) -> (CompactAssignments, Vec<ValidatorIndex>, ElectionScore) {
-	// run election on the default stuff.
-	let sp_npos_elections::ElectionResult {
-		winners,
-		assignments,
-	} = Staking::do_phragmen::<OffchainAccuracy>().unwrap();
-	let winners = sp_npos_elections::to_without_backing(winners);
-
-	let stake_of = |who: &AccountId| -> VoteWeight {
-		<CurrencyToVoteHandler as Convert<Balance, VoteWeight>>::convert(
-			Staking::slashable_balance_of(&who)
-		)
-	};
-
-	let mut staked = sp_npos_elections::assignment_ratio_to_staked(assignments, stake_of);
-	let (mut support_map, _) = build_support_map::<AccountId>(&winners, &staked);
-
-	if iterations > 0 {
-		sp_npos_elections::balance_solution(
-			&mut staked,
-			&mut support_map,
-			Zero::zero(),
-			iterations,
-		);
-	}
-
-	// apply custom tweaks. awesome for testing.
-	tweak(&mut staked);
-
-	if do_reduce {
-		reduce(&mut staked);
-	}
-
-	// convert back to ratio assignment. This takes less space.
-	let snapshot_validators = Staking::snapshot_validators().expect("snapshot not created.");
-	let snapshot_nominators = Staking::snapshot_nominators().expect("snapshot not created.");
-	let nominator_index = |a: &AccountId| -> Option<NominatorIndex> {
-		snapshot_nominators
-			.iter()
-			.position(|x| x == a)
-			.map_or_else(
-				|| { println!("unable to find nominator index for {:?}", a); None },
-				|i| Some(i as NominatorIndex),
-			)
-	};
-	let validator_index = |a: &AccountId| -> Option<ValidatorIndex> {
-		snapshot_validators
-			.iter()
-			.position(|x| x == a)
-			.map_or_else(
-				|| { println!("unable to find validator index for {:?}", a); None },
-				|i| Some(i as ValidatorIndex),
-			)
-	};
-
-	let assignments_reduced = sp_npos_elections::assignment_staked_to_ratio(staked);
-
-	// re-compute score by converting, yet again, into staked type
-	let score = {
-		let staked = sp_npos_elections::assignment_ratio_to_staked(
-			assignments_reduced.clone(),
-			Staking::slashable_balance_of_vote_weight,
-		);
-
-		let (support_map, _) = build_support_map::<AccountId>(
-			winners.as_slice(),
-			staked.as_slice(),
-		);
-		evaluate_support::<AccountId>(&support_map)
-	};
-
-	let compact =
-		CompactAssignments::from_assignment(assignments_reduced, nominator_index, validator_index)
-			.map_err(|e| { println!("error in compact: {:?}", e); e })
-			.expect("Failed to create compact");
-
-
-	// winner ids to index
-	let winners = winners.into_iter().map(|w| validator_index(&w).unwrap()).collect::<Vec<_>>();
-
-	(compact, winners, score)
-}
-
 /// Make all validator and nominator request their payment
 pub(crate) fn make_all_reward_payment(era: EraIndex) {
 	let validators_with_reward = ErasRewardPoints::<Test>::get(era).individual.keys()
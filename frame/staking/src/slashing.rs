@@ -58,7 +58,8 @@ use frame_support::{
 	StorageMap, StorageDoubleMap, ensure,
 	traits::{Currency, OnUnbalanced, Imbalance},
 };
-use sp_std::vec::Vec;
+use sp_std::{vec::Vec, collections::btree_map::BTreeMap};
+use sp_staking::OnStakerSlash;
 use codec::{Encode, Decode};
 
 /// The proportion of the slashing reward to be paid out on the first slashing detection.
@@ -592,7 +593,9 @@ pub fn do_slash<T: Trait>(
 		None => return, // nothing to do.
 	};
 
-	let value = ledger.slash(value, T::Currency::minimum_balance());
+	let pre_active = ledger.active;
+	let mut slashed_unlocking = BTreeMap::new();
+	let value = ledger.slash(value, T::Currency::minimum_balance(), &mut slashed_unlocking);
 
 	if !value.is_zero() {
 		let (imbalance, missing) = T::Currency::slash(stash, value);
@@ -604,6 +607,11 @@ pub fn do_slash<T: Trait>(
 		}
 
 		<Module<T>>::update_ledger(&controller, &ledger);
+		T::OnStakerSlash::on_slash(
+			stash,
+			pre_active.saturating_sub(ledger.active),
+			&slashed_unlocking,
+		);
 
 		// trigger the event
 		<Module<T>>::deposit_event(
@@ -16,7 +16,7 @@
 // limitations under the License.
 
 //! Testing utils for staking. Provides some common functions to setup staking state, such as
-//! bonding validators, nominators, and generating different types of solutions.
+//! bonding validators and nominators.
 
 use crate::*;
 use crate::Module as Staking;
@@ -24,7 +24,6 @@ use frame_benchmarking::{account};
 use frame_system::RawOrigin;
 use sp_io::hashing::blake2_256;
 use rand_chacha::{rand_core::{RngCore, SeedableRng}, ChaChaRng};
-use sp_npos_elections::*;
 
 const SEED: u32 = 0;
 
@@ -149,161 +148,6 @@ pub fn create_validators_with_nominators_for_era<T: Trait>(
 }
 
 
-/// Build a _really bad_ but acceptable solution for election. This should always yield a solution
-/// which has a less score than the seq-phragmen.
-pub fn get_weak_solution<T: Trait>(
-	do_reduce: bool,
-) -> (Vec<ValidatorIndex>, CompactAssignments, ElectionScore, ElectionSize) {
-	let mut backing_stake_of: BTreeMap<T::AccountId, BalanceOf<T>> = BTreeMap::new();
-
-	// self stake
-	<Validators<T>>::iter().for_each(|(who, _p)| {
-		*backing_stake_of.entry(who.clone()).or_insert_with(|| Zero::zero()) +=
-			<Module<T>>::slashable_balance_of(&who)
-	});
-
-	// elect winners. We chose the.. least backed ones.
-	let mut sorted: Vec<T::AccountId> = backing_stake_of.keys().cloned().collect();
-	sorted.sort_by_key(|x| backing_stake_of.get(x).unwrap());
-	let winners: Vec<T::AccountId> = sorted
-		.iter()
-		.rev()
-		.cloned()
-		.take(<Module<T>>::validator_count() as usize)
-		.collect();
-
-	let mut staked_assignments: Vec<StakedAssignment<T::AccountId>> = Vec::new();
-	// you could at this point start adding some of the nominator's stake, but for now we don't.
-	// This solution must be bad.
-
-	// add self support to winners.
-	winners.iter().for_each(|w| {
-		staked_assignments.push(StakedAssignment {
-			who: w.clone(),
-			distribution: vec![(
-				w.clone(),
-				<T::CurrencyToVote as Convert<BalanceOf<T>, u64>>::convert(
-					<Module<T>>::slashable_balance_of(&w),
-				) as ExtendedBalance,
-			)],
-		})
-	});
-
-	if do_reduce {
-		reduce(&mut staked_assignments);
-	}
-
-	// helpers for building the compact
-	let snapshot_validators = <Module<T>>::snapshot_validators().unwrap();
-	let snapshot_nominators = <Module<T>>::snapshot_nominators().unwrap();
-
-	let nominator_index = |a: &T::AccountId| -> Option<NominatorIndex> {
-		snapshot_nominators
-			.iter()
-			.position(|x| x == a)
-			.and_then(|i| <usize as TryInto<NominatorIndex>>::try_into(i).ok())
-	};
-	let validator_index = |a: &T::AccountId| -> Option<ValidatorIndex> {
-		snapshot_validators
-			.iter()
-			.position(|x| x == a)
-			.and_then(|i| <usize as TryInto<ValidatorIndex>>::try_into(i).ok())
-	};
-	let stake_of = |who: &T::AccountId| -> VoteWeight {
-		<T::CurrencyToVote as Convert<BalanceOf<T>, u64>>::convert(
-			<Module<T>>::slashable_balance_of(who),
-		)
-	};
-
-	// convert back to ratio assignment. This takes less space.
-	let low_accuracy_assignment = assignment_staked_to_ratio_normalized(staked_assignments)
-		.expect("Failed to normalize");
-
-	// re-calculate score based on what the chain will decode.
-	let score = {
-		let staked = assignment_ratio_to_staked::<_, OffchainAccuracy, _>(
-			low_accuracy_assignment.clone(),
-			stake_of
-		);
-
-		let (support_map, _) =
-			build_support_map::<T::AccountId>(winners.as_slice(), staked.as_slice());
-		evaluate_support::<T::AccountId>(&support_map)
-	};
-
-	// compact encode the assignment.
-	let compact = CompactAssignments::from_assignment(
-		low_accuracy_assignment,
-		nominator_index,
-		validator_index,
-	)
-	.unwrap();
-
-	// winners to index.
-	let winners = winners
-		.into_iter()
-		.map(|w| {
-			snapshot_validators
-				.iter()
-				.position(|v| *v == w)
-				.unwrap()
-				.try_into()
-				.unwrap()
-		})
-		.collect::<Vec<ValidatorIndex>>();
-
-	let size = ElectionSize {
-		validators: snapshot_validators.len() as ValidatorIndex,
-		nominators: snapshot_nominators.len() as NominatorIndex,
-	};
-
-	(winners, compact, score, size)
-}
-
-/// Create a solution for seq-phragmen. This uses the same internal function as used by the offchain
-/// worker code.
-pub fn get_seq_phragmen_solution<T: Trait>(
-	do_reduce: bool,
-) -> (Vec<ValidatorIndex>, CompactAssignments, ElectionScore, ElectionSize) {
-	let sp_npos_elections::ElectionResult {
-		winners,
-		assignments,
-	} = <Module<T>>::do_phragmen::<OffchainAccuracy>().unwrap();
-
-	offchain_election::prepare_submission::<T>(assignments, winners, do_reduce).unwrap()
-}
-
-/// Returns a solution in which only one winner is elected with just a self vote.
-pub fn get_single_winner_solution<T: Trait>(
-	winner: T::AccountId
-) -> Result<(Vec<ValidatorIndex>, CompactAssignments, ElectionScore, ElectionSize), &'static str> {
-	let snapshot_validators = <Module<T>>::snapshot_validators().unwrap();
-	let snapshot_nominators = <Module<T>>::snapshot_nominators().unwrap();
-
-	let val_index = snapshot_validators.iter().position(|x| *x == winner).ok_or("not a validator")?;
-	let nom_index = snapshot_nominators.iter().position(|x| *x == winner).ok_or("not a nominator")?;
-
-	let stake = <Staking<T>>::slashable_balance_of(&winner);
-	let stake = <T::CurrencyToVote as Convert<BalanceOf<T>, VoteWeight>>::convert(stake)
-		as ExtendedBalance;
-
-	let val_index = val_index as ValidatorIndex;
-	let nom_index = nom_index as NominatorIndex;
-
-	let winners = vec![val_index];
-	let compact = CompactAssignments {
-		votes1: vec![(nom_index, val_index)],
-		..Default::default()
-	};
-	let score = [stake, stake, stake * stake];
-	let size = ElectionSize {
-		validators: snapshot_validators.len() as ValidatorIndex,
-		nominators: snapshot_nominators.len() as NominatorIndex,
-	};
-
-	Ok((winners, compact, score, size))
-}
-
 /// get the active era.
 pub fn current_era<T: Trait>() -> EraIndex {
 	<Module<T>>::current_era().unwrap_or(0)
@@ -317,32 +161,3 @@ pub fn init_active_era() {
 	})
 }
 
-/// Create random assignments for the given list of winners. Each assignment will have
-/// MAX_NOMINATIONS edges.
-pub fn create_assignments_for_offchain<T: Trait>(
-	num_assignments: u32,
-	winners: Vec<<T::Lookup as StaticLookup>::Source>,
-) -> Result<
-	(
-		Vec<(T::AccountId, ExtendedBalance)>,
-		Vec<Assignment<T::AccountId, OffchainAccuracy>>,
-	),
-	&'static str
-> {
-	let ratio = OffchainAccuracy::from_rational_approximation(1, MAX_NOMINATIONS);
-	let assignments: Vec<Assignment<T::AccountId, OffchainAccuracy>> = <Nominators<T>>::iter()
-		.take(num_assignments as usize)
-		.map(|(n, t)| Assignment {
-			who: n,
-			distribution: t.targets.iter().map(|v| (v.clone(), ratio)).collect(),
-		})
-		.collect();
-
-	ensure!(assignments.len() == num_assignments as usize, "must bench for `a` assignments");
-
-	let winners = winners.into_iter().map(|v| {
-		(<T::Lookup as StaticLookup>::lookup(v).unwrap(), 0)
-	}).collect();
-
-	Ok((winners, assignments))
-}
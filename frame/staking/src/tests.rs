@@ -1113,27 +1113,30 @@ fn bond_extra_and_withdraw_unbonded_works() {
 #[test]
 fn too_many_unbond_calls_should_not_work() {
 	ExtBuilder::default().build_and_execute(|| {
-		// locked at era 0 until 3
-		for _ in 0..MAX_UNLOCKING_CHUNKS-1 {
+		// one chunk per era, locked at era `n` until `n + 3`.
+		for era in 0..MAX_UNLOCKING_CHUNKS as EraIndex {
+			if era > 0 {
+				mock::start_era(era);
+			}
 			assert_ok!(Staking::unbond(Origin::signed(10), 1));
 		}
+		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), MAX_UNLOCKING_CHUNKS);
 
-		mock::start_era(1);
-
-		// locked at era 1 until 4
+		// unbonding again in the same era adds to the last chunk.
 		assert_ok!(Staking::unbond(Origin::signed(10), 1));
-		// can't do more.
-		assert_noop!(Staking::unbond(Origin::signed(10), 1), Error::<Test>::NoMoreChunks);
+		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), MAX_UNLOCKING_CHUNKS);
 
-		mock::start_era(3);
+		mock::start_era(MAX_UNLOCKING_CHUNKS as EraIndex);
 
+		// can't do more.
 		assert_noop!(Staking::unbond(Origin::signed(10), 1), Error::<Test>::NoMoreChunks);
-		// free up.
+		// free up the chunks unlocked up to era 32.
 		assert_ok!(Staking::withdraw_unbonded(Origin::signed(10), 0));
+		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), 2);
 
 		// Can add again.
 		assert_ok!(Staking::unbond(Origin::signed(10), 1));
-		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), 2);
+		assert_eq!(Staking::ledger(&10).unwrap().unlocking.len(), 3);
 	})
 }
 
@@ -1248,7 +1251,7 @@ fn rebond_works() {
 				})
 			);
 
-			// Unbond parts of the funds in stash.
+			// Unbond parts of the funds in stash, within the same era.
 			Staking::unbond(Origin::signed(10), 300).unwrap();
 			Staking::unbond(Origin::signed(10), 300).unwrap();
 			Staking::unbond(Origin::signed(10), 300).unwrap();
@@ -1258,11 +1261,7 @@ fn rebond_works() {
 					stash: 11,
 					total: 1000,
 					active: 100,
-					unlocking: vec![
-						UnlockChunk { value: 300, era: 5 },
-						UnlockChunk { value: 300, era: 5 },
-						UnlockChunk { value: 300, era: 5 },
-					],
+					unlocking: vec![UnlockChunk { value: 900, era: 5 }],
 					claimed_rewards: vec![],
 				})
 			);
@@ -1275,10 +1274,7 @@ fn rebond_works() {
 					stash: 11,
 					total: 1000,
 					active: 600,
-					unlocking: vec![
-						UnlockChunk { value: 300, era: 5 },
-						UnlockChunk { value: 100, era: 5 },
-					],
+					unlocking: vec![UnlockChunk { value: 400, era: 5 }],
 					claimed_rewards: vec![],
 				})
 			);
@@ -1750,15 +1746,15 @@ fn bond_with_duplicate_vote_should_be_ignored_by_npos_election() {
 
 			// winners should be 21 and 31. Otherwise this election is taking duplicates into account.
 
-			let sp_npos_elections::ElectionResult {
-				winners,
-				assignments,
-			} = Staking::do_phragmen::<Perbill>().unwrap();
-			let winners = sp_npos_elections::to_without_backing(winners);
+			let supports = <Test as Trait>::ElectionProvider::elect().unwrap();
+			let winners = supports.iter().map(|(w, _)| *w).collect::<Vec<_>>();
 
-			assert_eq!(winners, vec![31, 21]);
+			assert_eq_uvec!(winners, vec![31, 21]);
 			// only distribution to 21 and 31.
-			assert_eq!(assignments.iter().find(|a| a.who == 1).unwrap().distribution.len(), 2);
+			assert_eq!(
+				supports.iter().filter(|(_, s)| s.voters.iter().any(|(v, _)| *v == 1)).count(),
+				2,
+			);
 		});
 }
 
@@ -1798,15 +1794,15 @@ fn bond_with_duplicate_vote_should_be_ignored_by_npos_election_elected() {
 
 			// winners should be 21 and 31. Otherwise this election is taking duplicates into account.
 
-			let sp_npos_elections::ElectionResult {
-				winners,
-				assignments,
-			} = Staking::do_phragmen::<Perbill>().unwrap();
+			let supports = <Test as Trait>::ElectionProvider::elect().unwrap();
+			let winners = supports.iter().map(|(w, _)| *w).collect::<Vec<_>>();
 
-			let winners = sp_npos_elections::to_without_backing(winners);
-			assert_eq!(winners, vec![21, 11]);
-			// only distribution to 21 and 31.
-			assert_eq!(assignments.iter().find(|a| a.who == 1).unwrap().distribution.len(), 2);
+			assert_eq_uvec!(winners, vec![21, 11]);
+			// only distribution to 21 and 11.
+			assert_eq!(
+				supports.iter().filter(|(_, s)| s.voters.iter().any(|(v, _)| *v == 1)).count(),
+				2,
+			);
 		});
 }
 
@@ -2810,1248 +2806,85 @@ fn remove_multi_deferred() {
 	})
 }
 
-mod offchain_phragmen {
-	use crate::*;
-	use codec::Encode;
-	use frame_support::{
-		assert_noop, assert_ok, assert_err_with_weight,
-		dispatch::DispatchResultWithPostInfo,
-	};
-	use sp_runtime::transaction_validity::TransactionSource;
-	use mock::*;
-	use parking_lot::RwLock;
-	use sp_core::offchain::{
-		testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
-		OffchainExt, TransactionPoolExt,
-	};
-	use sp_io::TestExternalities;
-	use sp_npos_elections::StakedAssignment;
-	use frame_support::traits::OffchainWorker;
-	use std::sync::Arc;
-	use substrate_test_utils::assert_eq_uvec;
-
-	fn percent(x: u16) -> OffchainAccuracy {
-		OffchainAccuracy::from_percent(x)
-	}
-
-	/// setup a new set of validators and nominator storage items independent of the parent mock
-	/// file. This produces a edge graph that can be reduced.
-	pub fn build_offchain_phragmen_test_ext() {
-		for i in (10..=40).step_by(10) {
-			// Note: we respect the convention of the mock (10, 11 pairs etc.) since these accounts
-			// have corresponding keys in session which makes everything more ergonomic and
-			// realistic.
-			bond_validator(i + 1, i, 100);
-		}
-
-		let mut voter = 1;
-		bond_nominator(voter, 1000 + voter, 100, vec![11]);
-		voter = 2;
-		bond_nominator(voter, 1000 + voter, 100, vec![11, 11]);
-		voter = 3;
-		bond_nominator(voter, 1000 + voter, 100, vec![21, 41]);
-		voter = 4;
-		bond_nominator(voter, 1000 + voter, 100, vec![21, 31, 41]);
-		voter = 5;
-		bond_nominator(voter, 1000 + voter, 100, vec![21, 31, 41]);
-	}
-
-	/// convert an externalities to one that can handle offchain worker tests.
-	fn offchainify(ext: &mut TestExternalities, iterations: u32) -> Arc<RwLock<PoolState>> {
-		let (offchain, offchain_state) = TestOffchainExt::new();
-		let (pool, pool_state) = TestTransactionPoolExt::new();
-
-		let mut seed = [0_u8; 32];
-		seed[0..4].copy_from_slice(&iterations.to_le_bytes());
-		offchain_state.write().seed = seed;
-
-		ext.register_extension(OffchainExt::new(offchain));
-		ext.register_extension(TransactionPoolExt::new(pool));
-
-		pool_state
-	}
-
-	fn election_size() -> ElectionSize {
-		ElectionSize {
-			validators: Staking::snapshot_validators().unwrap().len() as ValidatorIndex,
-			nominators: Staking::snapshot_nominators().unwrap().len() as NominatorIndex,
-		}
-	}
-
-	fn submit_solution(
-		origin: Origin,
-		winners: Vec<ValidatorIndex>,
-		compact: CompactAssignments,
-		score: ElectionScore,
-	) -> DispatchResultWithPostInfo {
-		Staking::submit_election_solution(
-			origin,
-			winners,
-			compact,
-			score,
-			current_era(),
-			election_size(),
-		)
-	}
+mod election_data_provider {
+	use super::*;
 
 	#[test]
-	fn is_current_session_final_works() {
-		ExtBuilder::default()
-			.session_per_era(3)
-			.build()
-			.execute_with(|| {
-				mock::start_era(1);
-				assert_eq!(Session::current_index(), 3);
-				assert_eq!(Staking::current_era(), Some(1));
-				assert_eq!(Staking::is_current_session_final(), false);
-
-				start_session(4);
-				assert_eq!(Session::current_index(), 4);
-				assert_eq!(Staking::current_era(), Some(1));
-				assert_eq!(Staking::is_current_session_final(), true);
-
-				start_session(5);
-				assert_eq!(Session::current_index(), 5);
-				// era changed.
-				assert_eq!(Staking::current_era(), Some(2));
-				assert_eq!(Staking::is_current_session_final(), false);
-			})
-	}
-
-	#[test]
-	fn offchain_window_is_triggered() {
-		ExtBuilder::default()
-			.session_per_era(5)
-			.session_length(10)
-			.election_lookahead(3)
-			.build()
-			.execute_with(|| {
-				run_to_block(7);
-				assert_session_era!(0, 0);
-
-				run_to_block(10);
-				assert_session_era!(1, 0);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-				assert!(Staking::snapshot_nominators().is_none());
-				assert!(Staking::snapshot_validators().is_none());
-
-				run_to_block(36);
-				assert_session_era!(3, 0);
-
-				// fist era has session 0, which has 0 blocks length, so we have in total 40 blocks
-				// in the era.
-				run_to_block(37);
-				assert_session_era!(3, 0);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(37));
-				assert!(Staking::snapshot_nominators().is_some());
-				assert!(Staking::snapshot_validators().is_some());
-
-				run_to_block(38);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(37));
-
-				run_to_block(39);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(37));
-
-				run_to_block(40);
-				assert_session_era!(4, 0);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-				assert!(Staking::snapshot_nominators().is_none());
-				assert!(Staking::snapshot_validators().is_none());
-
-				run_to_block(86);
-				assert_session_era!(8, 1);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-				assert!(Staking::snapshot_nominators().is_none());
-				assert!(Staking::snapshot_validators().is_none());
-
-				// second era onwards has 50 blocks per era.
-				run_to_block(87);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(87));
-				assert!(Staking::snapshot_nominators().is_some());
-				assert!(Staking::snapshot_validators().is_some());
-
-				run_to_block(90);
-				assert_session_era!(9, 1);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-				assert!(Staking::snapshot_nominators().is_none());
-				assert!(Staking::snapshot_validators().is_none());
-			})
-	}
-
-	#[test]
-	fn offchain_window_is_triggered_when_forcing() {
-		ExtBuilder::default()
-			.session_per_era(5)
-			.session_length(10)
-			.election_lookahead(3)
-			.build()
-			.execute_with(|| {
-				run_to_block(12);
-				ForceEra::put(Forcing::ForceNew);
-				run_to_block(13);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-
-				run_to_block(17); // instead of 47
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(17));
-
-				run_to_block(20);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-			})
-	}
-
-	#[test]
-	fn offchain_window_is_triggered_when_force_always() {
-		ExtBuilder::default()
-			.session_per_era(5)
-			.session_length(10)
-			.election_lookahead(3)
-			.build()
-			.execute_with(|| {
-
-				ForceEra::put(Forcing::ForceAlways);
-				run_to_block(16);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-
-				run_to_block(17); // instead of 37
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(17));
-
-				run_to_block(20);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-
-				run_to_block(26);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-
-				run_to_block(27); // next one again
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(27));
-			})
-	}
-
-	#[test]
-	fn offchain_window_closes_when_forcenone() {
-		ExtBuilder::default()
-			.session_per_era(5)
-			.session_length(10)
-			.election_lookahead(3)
-			.build()
-			.execute_with(|| {
-				ForceEra::put(Forcing::ForceNone);
-
-				run_to_block(36);
-				assert_session_era!(3, 0);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-
-				// opens
-				run_to_block(37);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(37));
-				assert!(Staking::is_current_session_final());
-				assert!(Staking::snapshot_validators().is_some());
-
-				// closes normally
-				run_to_block(40);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-				assert!(!Staking::is_current_session_final());
-				assert!(Staking::snapshot_validators().is_none());
-				assert_session_era!(4, 0);
-
-				run_to_block(47);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-				assert_session_era!(4, 0);
-
-				run_to_block(57);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-				assert_session_era!(5, 0);
-
-				run_to_block(67);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-
-				// Will not open again as scheduled
-				run_to_block(87);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-				assert_session_era!(8, 0);
-
-				run_to_block(90);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-				assert_session_era!(9, 0);
-			})
-	}
-
-	#[test]
-	fn offchain_window_on_chain_fallback_works() {
-		ExtBuilder::default().build_and_execute(|| {
-			start_session(1);
-			start_session(2);
-			assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-			// some election must have happened by now.
-			assert_eq!(
-				System::events()
-					.into_iter()
-					.map(|r| r.event)
-					.filter_map(|e| {
-						if let MetaEvent::staking(inner) = e {
-							Some(inner)
-						} else {
-							None
-						}
-					})
-					.last()
-					.unwrap(),
-				RawEvent::StakingElection(ElectionCompute::OnChain),
-			);
+	fn voters_include_self_vote() {
+		ExtBuilder::default().nominate(false).build().execute_with(|| {
+			assert!(<Validators<Test>>::iter().map(|(x, _)| x).all(|v| Staking::voters()
+				.into_iter()
+				.find(|(w, _, t)| { v == *w && t[0] == *w })
+				.is_some()))
 		})
 	}
 
 	#[test]
-	#[ignore] // This takes a few mins
-	fn offchain_wont_work_if_snapshot_fails() {
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.build()
-			.execute_with(|| {
-				run_to_block(12);
-				assert!(Staking::snapshot_validators().is_some());
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(12));
-
-				// validate more than the limit
-				let limit: NominatorIndex = ValidatorIndex::max_value() as NominatorIndex + 1;
-				let ctrl = 1_000_000;
-				for i in 0..limit {
-					bond_validator((1000 + i).into(), (1000 + i + ctrl).into(), 100);
-				}
-
-				// window stays closed since no snapshot was taken.
-				run_to_block(27);
-				assert!(Staking::snapshot_validators().is_none());
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-			})
-	}
-
-	#[test]
-	fn staking_is_locked_when_election_window_open() {
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.election_lookahead(3)
-			.build()
-			.execute_with(|| {
-				run_to_block(12);
-				assert!(Staking::snapshot_validators().is_some());
-				// given
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(12));
-
-				// chill et. al. are now not allowed.
-				assert_noop!(
-					Staking::chill(Origin::signed(10)),
-					Error::<Test>::CallNotAllowed,
-				);
-			})
-	}
-
-	#[test]
-	fn signed_result_can_be_submitted() {
-		// should check that we have a new validator set normally, event says that it comes from
-		// offchain.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.build()
-			.execute_with(|| {
-				run_to_block(12);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(12));
-				assert!(Staking::snapshot_validators().is_some());
-
-				let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-				assert_ok!(submit_solution(
-					Origin::signed(10),
-					winners,
-					compact,
-					score,
-				));
-
-				let queued_result = Staking::queued_elected().unwrap();
-				assert_eq!(queued_result.compute, ElectionCompute::Signed);
-				assert_eq!(
-					System::events()
-						.into_iter()
-						.map(|r| r.event)
-						.filter_map(|e| {
-							if let MetaEvent::staking(inner) = e {
-								Some(inner)
-							} else {
-								None
-							}
-						})
-						.last()
-						.unwrap(),
-					RawEvent::SolutionStored(ElectionCompute::Signed),
-				);
-
-				run_to_block(15);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-
-				assert_eq!(
-					System::events()
-						.into_iter()
-						.map(|r| r.event)
-						.filter_map(|e| {
-							if let MetaEvent::staking(inner) = e {
-								Some(inner)
-							} else {
-								None
-							}
-						})
-						.last()
-						.unwrap(),
-					RawEvent::StakingElection(ElectionCompute::Signed),
-				);
-			})
-	}
-
-	#[test]
-	fn signed_result_can_be_submitted_later() {
-		// same as `signed_result_can_be_submitted` but at a later block.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.build()
-			.execute_with(|| {
-				run_to_block(14);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(12));
-
-				let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-				assert_ok!(submit_solution(Origin::signed(10), winners, compact, score));
-
-				let queued_result = Staking::queued_elected().unwrap();
-				assert_eq!(queued_result.compute, ElectionCompute::Signed);
-
-				run_to_block(15);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-
-				assert_eq!(
-					System::events()
-						.into_iter()
-						.map(|r| r.event)
-						.filter_map(|e| {
-							if let MetaEvent::staking(inner) = e {
-								Some(inner)
-							} else {
-								None
-							}
-						})
-						.last()
-						.unwrap(),
-					RawEvent::StakingElection(ElectionCompute::Signed),
-				);
-			})
-	}
-
-	#[test]
-	fn early_solution_submission_is_rejected() {
-		// should check that we have a new validator set normally, event says that it comes from
-		// offchain.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.build()
-			.execute_with(|| {
-				run_to_block(11);
-				// submission is not yet allowed
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Closed);
-
-				// create all the indices just to build the solution.
-				Staking::create_stakers_snapshot();
-				let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-				Staking::kill_stakers_snapshot();
-
-				assert_err_with_weight!(
-					Staking::submit_election_solution(
-						Origin::signed(10),
-						winners.clone(),
-						compact.clone(),
-						score,
-						current_era(),
-						ElectionSize::default(),
-					),
-					Error::<Test>::PhragmenEarlySubmission,
-					Some(<Test as frame_system::Trait>::DbWeight::get().reads(1)),
-				);
-			})
-	}
-
-	#[test]
-	fn weak_solution_is_rejected() {
-		// A solution which is weaker than what we currently have on-chain is rejected.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.has_stakers(false)
-			.validator_count(4)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				// a good solution
-				let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-				assert_ok!(submit_solution(
-					Origin::signed(10),
-					winners,
-					compact,
-					score,
-				));
-
-				// a bad solution
-				let (compact, winners, score) = horrible_phragmen_with_post_processing(false);
-				assert_err_with_weight!(
-					submit_solution(
-						Origin::signed(10),
-						winners.clone(),
-						compact.clone(),
-						score,
-					),
-					Error::<Test>::PhragmenWeakSubmission,
-					Some(<Test as frame_system::Trait>::DbWeight::get().reads(3))
-				);
-			})
-	}
-
-	#[test]
-	fn better_solution_is_accepted() {
-		// A solution which is better than what we currently have on-chain is accepted.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				// a meeeeh solution
-				let (compact, winners, score) = horrible_phragmen_with_post_processing(false);
-				assert_ok!(submit_solution(
-					Origin::signed(10),
-					winners,
-					compact,
-					score,
-				));
-
-				// a better solution
-				let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-				assert_ok!(submit_solution(
-					Origin::signed(10),
-					winners,
-					compact,
-					score,
-				));
-			})
-	}
-
-	#[test]
-	fn offchain_worker_runs_when_window_open() {
-		// at the end of the first finalized block with ElectionStatus::open(_), it should execute.
-		let mut ext = ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(2)
-			.build();
-		let state = offchainify(&mut ext, 0);
-		ext.execute_with(|| {
-			run_to_block(12);
-
-			// local key 11 is in the elected set.
-			assert_eq_uvec!(Session::validators(), vec![11, 21]);
-			assert_eq!(state.read().transactions.len(), 0);
-			Staking::offchain_worker(12);
-			assert_eq!(state.read().transactions.len(), 1);
-
-			let encoded = state.read().transactions[0].clone();
-			let extrinsic: Extrinsic = Decode::decode(&mut &*encoded).unwrap();
-
-			let call = extrinsic.call;
-			let inner = match call {
-				mock::Call::Staking(inner) => inner,
-			};
-
+	fn voters_exclude_slashed() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_eq!(Staking::nominators(101).unwrap().targets, vec![11, 21]);
 			assert_eq!(
-				<Staking as sp_runtime::traits::ValidateUnsigned>::validate_unsigned(
-					TransactionSource::Local,
-					&inner,
-				),
-				TransactionValidity::Ok(ValidTransaction {
-					priority: UnsignedPriority::get() + 1125, // the proposed slot stake.
-					requires: vec![],
-					provides: vec![("StakingOffchain", current_era()).encode()],
-					longevity: 3,
-					propagate: false,
-				})
-			)
-		})
-	}
+				Staking::voters().iter().find(|x| x.0 == 101).unwrap().2,
+				vec![11, 21],
+			);
 
-	#[test]
-	fn offchain_worker_runs_with_equalise() {
-		// Offchain worker equalises based on the number provided by randomness. See the difference
-		// in the priority, which comes from the computed score.
-		let mut ext = ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(2)
-			.max_offchain_iterations(2)
-			.build();
-		let state = offchainify(&mut ext, 2);
-		ext.execute_with(|| {
-			run_to_block(12);
-
-			// local key 11 is in the elected set.
-			assert_eq_uvec!(Session::validators(), vec![11, 21]);
-			assert_eq!(state.read().transactions.len(), 0);
-			Staking::offchain_worker(12);
-			assert_eq!(state.read().transactions.len(), 1);
-
-			let encoded = state.read().transactions[0].clone();
-			let extrinsic: Extrinsic = Decode::decode(&mut &*encoded).unwrap();
-
-			let call = extrinsic.call;
-			let inner = match call {
-				mock::Call::Staking(inner) => inner,
-			};
+			start_era(1);
+			add_slash(&11);
 
+			// 11 is gone.
+			start_era(2);
 			assert_eq!(
-				<Staking as sp_runtime::traits::ValidateUnsigned>::validate_unsigned(
-					TransactionSource::Local,
-					&inner,
-				),
-				TransactionValidity::Ok(ValidTransaction {
-					// the proposed slot stake, with balance_solution.
-					priority: UnsignedPriority::get() + 1250,
-					requires: vec![],
-					provides: vec![("StakingOffchain", active_era()).encode()],
-					longevity: 3,
-					propagate: false,
-				})
-			)
-		})
-	}
-
-	#[test]
-	fn mediocre_submission_from_authority_is_early_rejected() {
-		let mut ext = ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.build();
-		let state = offchainify(&mut ext, 0);
-		ext.execute_with(|| {
-			run_to_block(12);
-			// put a good solution on-chain
-			let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-			assert_ok!(submit_solution(
-				Origin::signed(10),
-				winners,
-				compact,
-				score,
-			),);
-
-			// now run the offchain worker in the same chain state.
-			Staking::offchain_worker(12);
-			assert_eq!(state.read().transactions.len(), 1);
-
-			let encoded = state.read().transactions[0].clone();
-			let extrinsic: Extrinsic = Decode::decode(&mut &*encoded).unwrap();
-
-			let call = extrinsic.call;
-			let inner = match call {
-				mock::Call::Staking(inner) => inner,
-			};
+				Staking::voters().iter().find(|x| x.0 == 101).unwrap().2,
+				vec![21],
+			);
 
-			// pass this call to ValidateUnsigned
+			// resubmit and it is back
+			assert_ok!(Staking::nominate(Origin::signed(100), vec![11, 21]));
 			assert_eq!(
-				<Staking as sp_runtime::traits::ValidateUnsigned>::validate_unsigned(
-					TransactionSource::Local,
-					&inner,
-				),
-				TransactionValidity::Err(
-					InvalidTransaction::Custom(<Error<Test>>::PhragmenWeakSubmission.as_u8()).into(),
-				),
-			)
+				Staking::voters().iter().find(|x| x.0 == 101).unwrap().2,
+				vec![11, 21],
+			);
 		})
 	}
 
 	#[test]
-	fn invalid_phragmen_result_correct_number_of_winners() {
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				ValidatorCount::put(3);
-				let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-				ValidatorCount::put(4);
-
-				assert_eq!(winners.len(), 3);
-
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusWinnerCount,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_solution_size() {
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.build()
-			.execute_with(|| {
-				run_to_block(12);
-
-				let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-
-				assert_noop!(
-					Staking::submit_election_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-						current_era(),
-						ElectionSize::default(),
-					),
-					Error::<Test>::PhragmenBogusElectionSize,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_correct_number_of_winners_1() {
-		// if we have too little validators, then the number of candidates is the bound.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(8) // we simply cannot elect 8
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				ValidatorCount::put(3);
-				let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-				ValidatorCount::put(4);
-
-				assert_eq!(winners.len(), 3);
-
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusWinnerCount,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_correct_number_of_winners_2() {
-		// if we have too little validators, then the number of candidates is the bound.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(8) // we simply cannot elect 8
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				let (compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-
-				assert_eq!(winners.len(), 4);
-
-				// all good. We chose 4 and it works.
-				assert_ok!(submit_solution(
-					Origin::signed(10),
-					winners,
-					compact,
-					score,
-				),);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_out_of_bound_nominator_index() {
-		// A nominator index which is simply invalid
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				assert_eq!(Staking::snapshot_nominators().unwrap().len(), 5 + 4);
-				assert_eq!(Staking::snapshot_validators().unwrap().len(), 4);
-				let (mut compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-
-				// index 9 doesn't exist.
-				compact.votes1.push((9, 2));
-
-				// The error type sadly cannot be more specific now.
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusCompact,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_out_of_bound_validator_index() {
-		// A validator index which is out of bound
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				assert_eq!(Staking::snapshot_nominators().unwrap().len(), 5 + 4);
-				assert_eq!(Staking::snapshot_validators().unwrap().len(), 4);
-				let (mut compact, winners, score) = prepare_submission_with(true, 2, |_| {});
-
-				// index 4 doesn't exist.
-				compact.votes1.push((3, 4));
-
-				// The error type sadly cannot be more specific now.
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusCompact,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_out_of_bound_winner_index() {
-		// A winner index which is simply invalid
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				assert_eq!(Staking::snapshot_nominators().unwrap().len(), 5 + 4);
-				assert_eq!(Staking::snapshot_validators().unwrap().len(), 4);
-				let (compact, _, score) = prepare_submission_with(true, 2, |_| {});
-
-				// index 4 doesn't exist.
-				let winners = vec![0, 1, 2, 4];
-
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusWinner,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_non_winner_validator_index() {
-		// An edge that points to a correct validator index who is NOT a winner. This is very
-		// similar to the test that raises `PhragmenBogusNomination`.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(2) // we select only 2.
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				assert_eq!(Staking::snapshot_nominators().unwrap().len(), 5 + 4);
-				assert_eq!(Staking::snapshot_validators().unwrap().len(), 4);
-				let (compact, winners, score) = prepare_submission_with(true, 2, |a| {
-					a.iter_mut()
-						.find(|x| x.who == 5)
-						// all 3 cannot be among the winners. Although, all of them are validator
-						// candidates.
-						.map(|x| x.distribution = vec![(21, 50), (41, 30), (31, 20)]);
-				});
-
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusEdge,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_wrong_self_vote() {
-		// A self vote for someone else.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				let (compact, winners, score) = prepare_submission_with(true, 2, |a| {
-					// mutate a self vote to target someone else. That someone else is still among the
-					// winners
-					a.iter_mut().find(|x| x.who == 11).map(|x| {
-						x.distribution
-							.iter_mut()
-							.find(|y| y.0 == 11)
-							.map(|y| y.0 = 21)
-					});
-				});
-
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusSelfVote,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_wrong_self_vote_2() {
-		// A self validator voting for someone else next to self vote.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				let (compact, winners, score) = prepare_submission_with(true, 2, |a| {
-					// Remove the self vote.
-					a.retain(|x| x.who != 11);
-					// add is as a new double vote
-					a.push(StakedAssignment {
-						who: 11,
-						distribution: vec![(11, 50), (21, 50)],
-					});
-				});
-
-				// This raises score issue.
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusSelfVote,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_over_stake() {
-		// Someone's edge ratios sums to more than 100%.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				// Note: we don't reduce here to be able to tweak votes3. votes3 will vanish if you
-				// reduce.
-				let (mut compact, winners, score) = prepare_submission_with(false, 0, |_| {});
-
-				if let Some(c) = compact.votes3.iter_mut().find(|x| x.0 == 0) {
-					// by default it should have been (0, [(2, 33%), (1, 33%)], 0)
-					// now the sum is above 100%
-					c.1 = [(2, percent(66)), (1, percent(66))];
-				}
-
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusCompact,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_under_stake() {
-		// at the time of this writing, we cannot under stake someone. The compact assignment works
-		// in a way that some of the stakes are presented by the submitter, and the last one is read
-		// from chain by subtracting the rest from total. Hence, the sum is always correct.
-		// This test is only here as a demonstration.
-	}
-
-	#[test]
-	fn invalid_phragmen_result_invalid_target_stealing() {
-		// A valid voter who voted for someone who is a candidate, and is a correct winner, but is
-		// actually NOT nominated by this nominator.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				let (compact, winners, score) = prepare_submission_with(false, 0, |a| {
-					// 3 only voted for 20 and 40. We add a fake vote to 30. The stake sum is still
-					// correctly 100.
-					a.iter_mut()
-						.find(|x| x.who == 3)
-						.map(|x| x.distribution = vec![(21, 50), (41, 30), (31, 20)]);
-				});
-
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusNomination,
-				);
-			})
-	}
-
-	#[test]
-	fn nomination_slash_filter_is_checked() {
-		// If a nominator has voted for someone who has been recently slashed, that particular
-		// nomination should be disabled for the upcoming election. A solution must respect this
-		// rule.
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-
-				// finalize the round with fallback. This is needed since all nominator submission
-				// are in era zero and we want this one to pass with no problems.
-				run_to_block(15);
-
-				// go to the next session to trigger mock::start_era and bump the active era
-				run_to_block(20);
-
-				// slash 10. This must happen outside of the election window.
-				let offender_expo = Staking::eras_stakers(Staking::active_era().unwrap().index, 11);
-				on_offence_now(
-					&[OffenceDetails {
-						offender: (11, offender_expo.clone()),
-						reporters: vec![],
-					}],
-					&[Perbill::from_percent(50)],
-				);
-
-				// validate 10 again for the next round. But this guy will not have the votes that
-				// it should have had from 1 and 2.
-				assert_ok!(Staking::validate(
-					Origin::signed(10),
-					Default::default()
-				));
-
-				// open the election window and create snapshots.
-				run_to_block(32);
-
-				// a solution that has been prepared after the slash.
-				let (compact, winners, score) = prepare_submission_with(false, 0, |a| {
-					// no one is allowed to vote for 10, except for itself.
-					a.into_iter()
-						.filter(|s| s.who != 11)
-						.for_each(|s|
-							assert!(s.distribution.iter().find(|(t, _)| *t == 11).is_none())
-						);
-				});
-
-				// can be submitted.
-				assert_ok!(submit_solution(
-					Origin::signed(10),
-					winners,
-					compact,
-					score,
-				));
-
-				// a wrong solution.
-				let (compact, winners, score) = prepare_submission_with(false, 0, |a| {
-					// add back the vote that has been filtered out.
-					a.push(StakedAssignment {
-						who: 1,
-						distribution: vec![(11, 100)]
-					});
-				});
-
-				// is rejected.
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenSlashedNomination,
-				);
-			})
-	}
-
-	#[test]
-	fn invalid_phragmen_result_wrong_score() {
-		// A valid voter who's total distributed stake is more than what they bond
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				build_offchain_phragmen_test_ext();
-				run_to_block(12);
-
-				let (compact, winners, mut score) = prepare_submission_with(true, 2, |_| {});
-				score[0] += 1;
-
-				assert_noop!(
-					submit_solution(
-						Origin::signed(10),
-						winners,
-						compact,
-						score,
-					),
-					Error::<Test>::PhragmenBogusScore,
-				);
-			})
-	}
-
-	#[test]
-	fn offchain_storage_is_set() {
-		let mut ext = ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.build();
-		let state = offchainify(&mut ext, 0);
-
-		ext.execute_with(|| {
-			use offchain_election::OFFCHAIN_HEAD_DB;
-			use sp_runtime::offchain::storage::StorageValueRef;
-
-			run_to_block(12);
-
-			Staking::offchain_worker(12);
-			// it works
-			assert_eq!(state.read().transactions.len(), 1);
-
-			// and it is set
-			let storage = StorageValueRef::persistent(&OFFCHAIN_HEAD_DB);
-			assert_eq!(storage.get::<BlockNumber>().unwrap().unwrap(), 12);
+	fn targets_and_desired_targets_work() {
+		ExtBuilder::default().validator_pool(true).validator_count(3).build().execute_with(|| {
+			assert_eq_uvec!(Staking::targets(), vec![11, 21, 31, 41]);
+			assert_eq!(Staking::desired_targets(), 3);
 		})
 	}
 
 	#[test]
-	fn offchain_storage_prevents_duplicate() {
-		let mut ext = ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.build();
-		let _ = offchainify(&mut ext, 0);
+	fn next_election_prediction_works() {
+		ExtBuilder::default().session_per_era(3).session_length(5).build().execute_with(|| {
+			// the first era is elected at genesis; session 0 ends at block 5.
+			assert_eq!(Staking::current_planned_session(), 1);
+			for b in 1..5 {
+				assert_eq!(Staking::next_election_prediction(b), 10);
+			}
 
-		ext.execute_with(|| {
-			use offchain_election::OFFCHAIN_HEAD_DB;
-			use sp_runtime::offchain::storage::StorageValueRef;
-			let storage = StorageValueRef::persistent(&OFFCHAIN_HEAD_DB);
+			run_to_block(5);
+			assert_eq!(Staking::current_planned_session(), 2);
+			for b in 5..10 {
+				assert_eq!(Staking::next_election_prediction(b), 10);
+			}
 
-			run_to_block(12);
+			// the election happens, and the next one is a full era ahead.
+			run_to_block(10);
+			assert_eq!(Staking::current_planned_session(), 3);
+			assert_eq!(Staking::current_era(), Some(1));
+			assert_eq!(Staking::next_election_prediction(10), 25);
+			assert_eq!(Staking::next_election_prediction(12), 25);
 
-			// first run -- ok
-			assert_eq!(
-				offchain_election::set_check_offchain_execution_status::<Test>(12),
-				Ok(()),
-			);
-			assert_eq!(storage.get::<BlockNumber>().unwrap().unwrap(), 12);
+			run_to_block(20);
+			assert_eq!(Staking::next_election_prediction(20), 25);
 
-			// re-execute after the next. not allowed.
-			assert_eq!(
-				offchain_election::set_check_offchain_execution_status::<Test>(13),
-				Err("recently executed."),
-			);
-
-			// a fork like situation -- re-execute 10, 11, 12. But it won't go through.
-			assert_eq!(
-				offchain_election::set_check_offchain_execution_status::<Test>(10),
-				Err("fork."),
-			);
-			assert_eq!(
-				offchain_election::set_check_offchain_execution_status::<Test>(11),
-				Err("fork."),
-			);
-			assert_eq!(
-				offchain_election::set_check_offchain_execution_status::<Test>(12),
-				Err("recently executed."),
-			);
+			run_to_block(25);
+			assert_eq!(Staking::current_era(), Some(2));
+			assert_eq!(Staking::next_election_prediction(25), 40);
 		})
 	}
-
-	#[test]
-	#[should_panic]
-	fn offence_is_blocked_when_window_open() {
-		ExtBuilder::default()
-			.offchain_phragmen_ext()
-			.validator_count(4)
-			.has_stakers(false)
-			.build()
-			.execute_with(|| {
-				run_to_block(12);
-				assert_eq!(Staking::era_election_status(), ElectionStatus::Open(12));
-
-				let offender_expo = Staking::eras_stakers(Staking::active_era().unwrap().index, 10);
-
-				// panic from the impl in mock
-				on_offence_now(
-					&[OffenceDetails {
-						offender: (10, offender_expo.clone()),
-						reporters: vec![],
-					}],
-					&[Perbill::from_percent(10)],
-				);
-			})
-	}
 }
 
 #[test]
@@ -4569,35 +3402,12 @@ fn on_initialize_weight_is_correct() {
 	ExtBuilder::default().has_stakers(false).build_and_execute(|| {
 		assert_eq!(Validators::<Test>::iter().count(), 0);
 		assert_eq!(Nominators::<Test>::iter().count(), 0);
-		// When this pallet has nothing, we do 4 reads each block
-		let base_weight = <Test as frame_system::Trait>::DbWeight::get().reads(4);
+		// When this pallet has nothing, we only do the one read of `on_finalize`.
+		let base_weight = <Test as frame_system::Trait>::DbWeight::get().reads(1);
 		assert_eq!(base_weight, Staking::on_initialize(0));
 	});
-
-	ExtBuilder::default()
-	.offchain_phragmen_ext()
-	.validator_count(4)
-	.has_stakers(false)
-	.build()
-	.execute_with(|| {
-		crate::tests::offchain_phragmen::build_offchain_phragmen_test_ext();
-		run_to_block(11);
-		Staking::on_finalize(System::block_number());
-		System::set_block_number((System::block_number() + 1).into());
-		Timestamp::set_timestamp(System::block_number() * 1000 + INIT_TIMESTAMP);
-		Session::on_initialize(System::block_number());
-
-		assert_eq!(Validators::<Test>::iter().count(), 4);
-		assert_eq!(Nominators::<Test>::iter().count(), 5);
-		// With 4 validators and 5 nominator, we should increase weight by:
-		// - (4 + 5) reads
-		// - 3 Writes
-		let final_weight = <Test as frame_system::Trait>::DbWeight::get().reads_writes(4 + 9, 3);
-		assert_eq!(final_weight, Staking::on_initialize(System::block_number()));
-	});
 }
 
-
 #[test]
 fn payout_creates_controller() {
 	// Here we will test validator can set `max_nominators_payout` and it works.
@@ -4626,3 +3436,54 @@ fn payout_creates_controller() {
 		assert!(Balances::free_balance(1337) > 0);
 	})
 }
+
+#[test]
+fn ledger_slash_reports_slashed_unlocking_chunks() {
+	let mut ledger = StakingLedger::<AccountId, Balance> {
+		stash: 11,
+		total: 1000,
+		active: 500,
+		unlocking: vec![
+			UnlockChunk { value: 100, era: 3 },
+			UnlockChunk { value: 150, era: 3 },
+			UnlockChunk { value: 250, era: 5 },
+		],
+		claimed_rewards: vec![],
+	};
+	let mut slashed_unlocking = BTreeMap::new();
+
+	// the whole active bond, the chunks of era 3 and part of the chunk of era 5 are slashed.
+	assert_eq!(ledger.slash(850, 1, &mut slashed_unlocking), 850);
+	assert_eq!(ledger.active, 0);
+	assert_eq!(ledger.total, 150);
+	assert_eq!(ledger.unlocking, vec![UnlockChunk { value: 150, era: 5 }]);
+	assert_eq!(slashed_unlocking, vec![(3, 250), (5, 100)].into_iter().collect());
+}
+
+#[test]
+fn staking_interface_works() {
+	ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+		assert_eq!(<Staking as StakingInterface>::bonding_duration(), 3);
+		assert_eq!(<Staking as StakingInterface>::current_era(), 0);
+
+		assert_ok!(<Staking as StakingInterface>::bond(11, 10, 1000));
+		assert_eq!(Staking::payee(&11), RewardDestination::Controller);
+		assert_eq!(<Staking as StakingInterface>::active_stake(&10), Some(1000));
+
+		assert_ok!(<Staking as StakingInterface>::nominate(10, vec![21, 31]));
+		assert_eq!(Staking::nominators(&11).unwrap().targets, vec![21, 31]);
+
+		assert_ok!(<Staking as StakingInterface>::unbond(10, 400));
+		assert_eq!(<Staking as StakingInterface>::active_stake(&10), Some(600));
+		assert_eq!(<Staking as StakingInterface>::total_stake(&10), Some(1000));
+
+		CurrentEra::put(3);
+		assert_eq!(<Staking as StakingInterface>::withdraw_unbonded(10, 0), Ok(false));
+		assert_eq!(<Staking as StakingInterface>::total_stake(&10), Some(600));
+
+		assert_ok!(<Staking as StakingInterface>::unbond(10, 600));
+		CurrentEra::put(6);
+		assert_eq!(<Staking as StakingInterface>::withdraw_unbonded(10, 0), Ok(true));
+		assert_eq!(<Staking as StakingInterface>::active_stake(&10), None);
+	});
+}
@@ -204,8 +204,8 @@ fn decl_outer_config<'a>(
 			)
 		});
 	quote!(
-		#scrate::sp_runtime::impl_outer_config! {
-			pub struct GenesisConfig for #runtime {
+		#scrate::impl_outer_config! {
+			pub struct GenesisConfig for #runtime where AllModulesWithSystem = AllModulesWithSystem {
 				#(#modules_tokens)*
 			}
 		}
@@ -369,10 +369,15 @@ fn decl_all_modules<'a>(
 	let all_modules = names.iter()
 		.filter(|n| **n != SYSTEM_MODULE_NAME)
 		.fold(TokenStream2::default(), |combined, name| quote!((#name, #combined)));
+	let all_modules_with_system = names.iter()
+		.fold(TokenStream2::default(), |combined, name| quote!((#name, #combined)));
 
 	quote!(
 		#types
 		type AllModules = ( #all_modules );
+		/// All modules included in the runtime, including the system module.
+		#[allow(dead_code)]
+		type AllModulesWithSystem = ( #all_modules_with_system );
 	)
 }
 
@@ -381,12 +386,13 @@ fn decl_module_to_index<'a>(
 	num_modules: usize,
 	scrate: &TokenStream2,
 ) -> TokenStream2 {
-	let names = module_declarations.map(|d| &d.name);
+	let names = module_declarations.map(|d| &d.name).collect::<Vec<_>>();
+	let name_strings = names.iter().map(|n| n.to_string());
 	let indices = 0..num_modules;
 
 	quote!(
 		/// Provides an implementation of `ModuleToIndex` to map a module
-		/// to its index in the runtime.
+		/// to its index and name in the runtime.
 		pub struct ModuleToIndex;
 
 		impl #scrate::traits::ModuleToIndex for ModuleToIndex {
@@ -400,6 +406,17 @@ fn decl_module_to_index<'a>(
 
 				None
 			}
+
+			fn module_name<M: 'static>() -> Option<&'static str> {
+				let type_id = #scrate::sp_std::any::TypeId::of::<M>();
+				#(
+					if type_id == #scrate::sp_std::any::TypeId::of::<#names>() {
+						return Some(#name_strings)
+					}
+				)*
+
+				None
+			}
 		}
 	)
 }
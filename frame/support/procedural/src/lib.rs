@@ -23,6 +23,8 @@
 
 mod storage;
 mod construct_runtime;
+mod transactional;
+mod pallet_version;
 
 use proc_macro::TokenStream;
 
@@ -289,3 +291,45 @@ pub fn decl_storage(input: TokenStream) -> TokenStream {
 pub fn construct_runtime(input: TokenStream) -> TokenStream {
 	construct_runtime::construct_runtime(input)
 }
+
+/// Execute the annotated function in a new storage transaction.
+///
+/// The return type of the annotated function must be `Result`. All changes to storage performed
+/// by the annotated function are discarded if it returns `Err`, or committed if `Ok`.
+///
+/// ## Example
+///
+/// ```nocompile
+/// #[transactional]
+/// fn value_commits(v: u32) -> result::Result<u32, &'static str> {
+/// 	Value::set(v);
+/// 	Ok(v)
+/// }
+///
+/// #[transactional]
+/// fn value_rollbacks(v: u32) -> result::Result<u32, &'static str> {
+/// 	Value::set(v);
+/// 	Err("nah")
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn transactional(attr: TokenStream, input: TokenStream) -> TokenStream {
+	transactional::transactional(attr, input).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
+/// Convert the current crate version into a [`PalletVersion`](../frame_support/traits/struct.PalletVersion.html).
+///
+/// It uses the `CARGO_PKG_VERSION_MAJOR`, `CARGO_PKG_VERSION_MINOR` and
+/// `CARGO_PKG_VERSION_PATCH` environment variables to fetch the crate version.
+/// This means that the [`PalletVersion`](../frame_support/traits/struct.PalletVersion.html)
+/// object will correspond to the version of the crate the macro is called in!
+///
+/// # Example
+///
+/// ```nocompile
+/// const Version: PalletVersion = crate_to_pallet_version!();
+/// ```
+#[proc_macro]
+pub fn crate_to_pallet_version(input: TokenStream) -> TokenStream {
+	pallet_version::crate_to_pallet_version(input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
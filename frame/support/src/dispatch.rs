@@ -189,6 +189,28 @@ impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + fmt::Debug {}
 /// # fn main() {}
 /// ```
 ///
+/// ### Transactional Function Example
+///
+/// Transactional function discards all changes to storage if it returns `Err`, or commits if
+/// `Ok`, via the #\[transactional\] attribute. Note the attribute must be after #\[weight\].
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate frame_support;
+/// # use frame_support::transactional;
+/// # use frame_system::Trait;
+/// decl_module! {
+/// 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+/// 		#[weight = 0]
+/// 		#[transactional]
+/// 		fn my_short_function(origin) {
+/// 			// Your implementation
+/// 		}
+/// 	}
+/// }
+/// # fn main() {}
+/// ```
+///
 /// ## Multiple Module Instances Example
 ///
 /// A Substrate module can be built such that multiple instances of the same module can be used within a single
@@ -255,6 +277,8 @@ impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + fmt::Debug {}
 /// As such, **calling other modules must be avoided**!! Using this function will implement the
 /// [`OnRuntimeUpgrade`](../sp_runtime/traits/trait.OnRuntimeUpgrade.html) trait.
 /// Function signature must be `fn on_runtime_upgrade() -> frame_support::weights::Weight`.
+/// After it ran, the pallet version declared in the pallet's `Cargo.toml` is written to storage,
+/// see [`PalletVersion`](./traits/struct.PalletVersion.html).
 ///
 /// * `on_initialize`: Executes at the beginning of a block. Using this function will
 /// implement the [`OnInitialize`](./trait.OnInitialize.html) trait.
@@ -1015,6 +1039,7 @@ macro_rules! decl_module {
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
+		$(#[$fn_attr:meta])*
 		$fn_vis:vis fn $fn_name:ident(
 			$origin:ident $( , $(#[$codec_attr:ident])* $param_name:ident : $param:ty )* $(,)?
 		) $( -> $result:ty )* { $( $impl:tt )* }
@@ -1039,6 +1064,7 @@ macro_rules! decl_module {
 				$( $dispatchables )*
 				$(#[doc = $doc_attr])*
 				#[weight = $weight]
+				$(#[$fn_attr])*
 				$fn_vis fn $fn_name(
 					$origin $( , $(#[$codec_attr])* $param_name : $param )*
 				) $( -> $result )* { $( $impl )* }
@@ -1272,6 +1298,7 @@ macro_rules! decl_module {
 
 	(@impl_on_runtime_upgrade
 		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
+		$system:ident;
 		{ $( $other_where_bounds:tt )* }
 		fn on_runtime_upgrade() -> $return:ty { $( $impl:tt )* }
 	) => {
@@ -1281,19 +1308,102 @@ macro_rules! decl_module {
 		{
 			fn on_runtime_upgrade() -> $return {
 				$crate::sp_tracing::enter_span!("on_runtime_upgrade");
-				{ $( $impl )* }
+				let result: $return = (|| { $( $impl )* })();
+
+				$crate::crate_to_pallet_version!()
+					.put_into_storage::<<$trait_instance as $system::Trait>::ModuleToIndex, Self>();
+
+				let additional_write = <
+					<$trait_instance as $system::Trait>::DbWeight
+						as $crate::traits::Get<$crate::weights::RuntimeDbWeight>
+				>::get().writes(1);
+
+				result.saturating_add(additional_write)
+			}
+
+			#[cfg(feature = "try-runtime")]
+			fn post_upgrade() -> Result<(), &'static str> {
+				$crate::decl_module!(@impl_post_upgrade_version_check)
 			}
 		}
 	};
 
 	(@impl_on_runtime_upgrade
 		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
+		$system:ident;
 		{ $( $other_where_bounds:tt )* }
 	) => {
 		impl<$trait_instance: $trait_name$(<I>, $instance: $instantiable)?>
 			$crate::traits::OnRuntimeUpgrade
 			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
-		{}
+		{
+			fn on_runtime_upgrade() -> $crate::dispatch::Weight {
+				$crate::sp_tracing::enter_span!("on_runtime_upgrade");
+
+				$crate::crate_to_pallet_version!()
+					.put_into_storage::<<$trait_instance as $system::Trait>::ModuleToIndex, Self>();
+
+				<
+					<$trait_instance as $system::Trait>::DbWeight
+						as $crate::traits::Get<$crate::weights::RuntimeDbWeight>
+				>::get().writes(1)
+			}
+
+			#[cfg(feature = "try-runtime")]
+			fn post_upgrade() -> Result<(), &'static str> {
+				$crate::decl_module!(@impl_post_upgrade_version_check)
+			}
+		}
+	};
+
+	// Checks that the pallet version was written to storage by the upgrade.
+	(@impl_post_upgrade_version_check) => {
+		{
+			let stored = <
+				Self as $crate::traits::GetPalletVersion
+			>::storage_version();
+
+			if stored == Some($crate::crate_to_pallet_version!()) {
+				Ok(())
+			} else {
+				Err("Pallet version was not updated to the crate version by the runtime upgrade")
+			}
+		}
+	};
+
+	(@impl_pallet_version
+		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
+		$system:ident;
+		{ $( $other_where_bounds:tt )* }
+	) => {
+		impl<$trait_instance: $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::GetPalletVersion
+			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{
+			fn current_version() -> $crate::traits::PalletVersion {
+				$crate::crate_to_pallet_version!()
+			}
+
+			fn storage_version() -> Option<$crate::traits::PalletVersion> {
+				let key = $crate::traits::PalletVersion::storage_key::<
+						<$trait_instance as $system::Trait>::ModuleToIndex, Self
+					>().expect("Every active pallet has a name in the runtime; qed");
+
+				$crate::storage::unhashed::get(&key)
+			}
+		}
+
+		// All pallets write their current version to storage at genesis, so that a chain
+		// started from scratch knows which storage layout its pallets are on.
+		impl<$trait_instance: $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::OnGenesis
+			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{
+			fn on_genesis() {
+				$crate::crate_to_pallet_version!()
+					.put_into_storage::<<$trait_instance as $system::Trait>::ModuleToIndex, Self>();
+			}
+		}
 	};
 
 	(@impl_integrity_test
@@ -1410,12 +1520,12 @@ macro_rules! decl_module {
 		$origin_ty:ty;
 		$error_type:ty;
 		$ignore:ident;
-		$(#[doc = $doc_attr:tt])*
+		$(#[$fn_attr:meta])*
 		$vis:vis fn $name:ident (
 			$origin:ident $(, $param:ident : $param_ty:ty )*
 		) { $( $impl:tt )* }
 	) => {
-		$(#[doc = $doc_attr])*
+		$(#[$fn_attr])*
 		#[allow(unreachable_code)]
 		$vis fn $name(
 			$origin: $origin_ty $(, $param: $param_ty )*
@@ -1432,12 +1542,12 @@ macro_rules! decl_module {
 		$origin_ty:ty;
 		$error_type:ty;
 		$ignore:ident;
-		$(#[doc = $doc_attr:tt])*
+		$(#[$fn_attr:meta])*
 		$vis:vis fn $name:ident (
 			$origin:ident $(, $param:ident : $param_ty:ty )*
 		) -> $result:ty { $( $impl:tt )* }
 	) => {
-		$(#[doc = $doc_attr])*
+		$(#[$fn_attr])*
 		$vis fn $name($origin: $origin_ty $(, $param: $param_ty )* ) -> $result {
 			$crate::sp_tracing::enter_span!(stringify!($name));
 			$( $impl )*
@@ -1569,6 +1679,7 @@ macro_rules! decl_module {
 			$(
 				$(#[doc = $doc_attr:tt])*
 				#[weight = $weight:expr]
+				$(#[$fn_attr:meta])*
 				$fn_vis:vis fn $fn_name:ident(
 					$from:ident $( , $(#[$codec_attr:ident])* $param_name:ident : $param:ty)*
 				) $( -> $result:ty )* { $( $impl:tt )* }
@@ -1606,10 +1717,18 @@ macro_rules! decl_module {
 		$crate::decl_module! {
 			@impl_on_runtime_upgrade
 			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
+			$system;
 			{ $( $other_where_bounds )* }
 			$( $on_runtime_upgrade )*
 		}
 
+		$crate::decl_module! {
+			@impl_pallet_version
+			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
+			$system;
+			{ $( $other_where_bounds )* }
+		}
+
 		$crate::decl_module! {
 			@impl_on_finalize
 			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
@@ -1654,6 +1773,7 @@ macro_rules! decl_module {
 					$(#[doc = $doc_attr])*
 					///
 					/// NOTE: Calling this function will bypass origin filters.
+					$(#[$fn_attr])*
 					$fn_vis fn $fn_name (
 						$from $(, $param_name : $param )*
 					) $( -> $result )* { $( $impl )* }
@@ -2319,6 +2439,8 @@ mod tests {
 			type Call;
 			type BaseCallFilter;
 			type Origin: crate::traits::OriginTrait<Call = Self::Call>;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
 		}
 
 		#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode)]
@@ -2464,6 +2586,8 @@ mod tests {
 		type AccountId = u32;
 		type Call = OuterCall;
 		type BaseCallFilter = ();
+		type DbWeight = ();
+		type ModuleToIndex = ();
 	}
 
 	#[test]
@@ -2519,7 +2643,9 @@ mod tests {
 
 	#[test]
 	fn on_runtime_upgrade_should_work() {
-		assert_eq!(<Module<TraitImpl> as OnRuntimeUpgrade>::on_runtime_upgrade(), 10);
+		sp_io::TestExternalities::default().execute_with(||
+			assert_eq!(<Module<TraitImpl> as OnRuntimeUpgrade>::on_runtime_upgrade(), 10)
+		);
 	}
 
 	#[test]
@@ -546,10 +546,12 @@ mod tests {
 		pub trait Trait {
 			type Origin;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -563,10 +565,12 @@ mod tests {
 		pub trait Trait {
 			type Origin;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -581,10 +585,12 @@ mod tests {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -604,10 +610,12 @@ mod tests {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -635,10 +643,12 @@ mod tests {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -657,10 +667,12 @@ mod tests {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		decl_event!(
@@ -706,34 +718,46 @@ mod tests {
 		type Origin = u32;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl event_module2::Trait for TestRuntime {
 		type Origin = u32;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl system::Trait for TestRuntime {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl event_module::Trait for TestRuntime2 {
 		type Origin = u32;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl event_module2::Trait for TestRuntime2 {
 		type Origin = u32;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	impl system_renamed::Trait for TestRuntime2 {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	const EXPECTED_METADATA: OuterEventMetadata = OuterEventMetadata {
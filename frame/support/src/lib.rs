@@ -66,6 +66,8 @@ pub mod inherent;
 pub mod unsigned;
 #[macro_use]
 pub mod error;
+#[macro_use]
+pub mod genesis_config;
 pub mod traits;
 pub mod weights;
 
@@ -267,7 +269,9 @@ macro_rules! ord_parameter_types {
 }
 
 #[doc(inline)]
-pub use frame_support_procedural::{decl_storage, construct_runtime};
+pub use frame_support_procedural::{
+	decl_storage, construct_runtime, transactional, crate_to_pallet_version,
+};
 
 /// Return Err of the expression: `return Err($expression);`.
 ///
@@ -382,6 +386,8 @@ mod tests {
 	pub trait Trait {
 		type BlockNumber: Codec + EncodeLike + Default;
 		type Origin;
+		type ModuleToIndex: crate::traits::ModuleToIndex;
+		type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 	}
 
 	mod module {
@@ -390,7 +396,7 @@ mod tests {
 		use super::Trait;
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 	}
 	use self::module::Module;
@@ -421,6 +427,8 @@ mod tests {
 	impl Trait for Test {
 		type BlockNumber = u32;
 		type Origin = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	fn new_test_ext() -> TestExternalities {
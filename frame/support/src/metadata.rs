@@ -27,12 +27,17 @@ pub use frame_metadata::{
 /// Example:
 /// ```
 ///# mod module0 {
+///#    use frame_support::traits::Get;
+///#    use frame_support::weights::RuntimeDbWeight;
+///#
 ///#    pub trait Trait {
 ///#        type Origin;
 ///#        type BlockNumber;
+///#        type ModuleToIndex: frame_support::traits::ModuleToIndex;
+///#        type DbWeight: Get<RuntimeDbWeight>;
 ///#    }
 ///#    frame_support::decl_module! {
-///#        pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+///#        pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 ///#    }
 ///#
 ///#    frame_support::decl_storage! {
@@ -44,6 +49,8 @@ pub use frame_metadata::{
 ///# impl module0::Trait for Runtime {
 ///#     type Origin = u32;
 ///#     type BlockNumber = u32;
+///#     type ModuleToIndex = ();
+///#     type DbWeight = ();
 ///# }
 ///#
 ///# type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<(), (), (), ()>;
@@ -298,11 +305,12 @@ mod tests {
 			type BlockNumber: From<u32> + Encode;
 			type SomeValue: Get<u32>;
 			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: Get<crate::weights::RuntimeDbWeight>;
 			type Call;
 		}
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {
 				/// Hi, I am a comment.
 				const BlockNumber: T::BlockNumber = 100.into();
 				const GetType: T::AccountId = T::SomeValue::get().into();
@@ -351,7 +359,7 @@ mod tests {
 		);
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {
 				type Error = Error<T>;
 
 				#[weight = 0]
@@ -371,10 +379,14 @@ mod tests {
 	}
 
 	mod event_module2 {
+		use crate::traits::Get;
+
 		pub trait Trait {
 			type Origin;
 			type Balance;
 			type BlockNumber;
+			type ModuleToIndex: crate::traits::ModuleToIndex;
+			type DbWeight: Get<crate::weights::RuntimeDbWeight>;
 		}
 
 		decl_event!(
@@ -385,7 +397,7 @@ mod tests {
 		);
 
 		decl_module! {
-			pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+			pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 		}
 
 		crate::decl_storage! {
@@ -431,6 +443,8 @@ mod tests {
 		type Origin = Origin;
 		type Balance = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	crate::parameter_types! {
@@ -444,6 +458,7 @@ mod tests {
 		type BlockNumber = u32;
 		type SomeValue = SystemValue;
 		type ModuleToIndex = ();
+		type DbWeight = ();
 		type Call = Call;
 	}
 
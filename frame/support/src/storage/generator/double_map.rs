@@ -455,10 +455,12 @@ mod test_iterators {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber;
+		type ModuleToIndex: crate::traits::ModuleToIndex;
+		type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 	}
 
 	crate::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	#[derive(PartialEq, Eq, Clone, Encode, Decode)]
@@ -44,15 +44,19 @@ mod tests {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber;
+		type ModuleToIndex: crate::traits::ModuleToIndex;
+		type DbWeight: crate::traits::Get<crate::weights::RuntimeDbWeight>;
 	}
 
 	impl Trait for Runtime {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	crate::decl_storage! {
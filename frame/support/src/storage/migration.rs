@@ -17,10 +17,12 @@
 
 //! Some utilities for helping access storage with arbitrary key types.
 
-use sp_std::prelude::*;
+use sp_std::{prelude::*, marker::PhantomData};
 use codec::{Encode, Decode};
 use crate::{StorageHasher, Twox128};
 use crate::hash::ReversibleStorageHasher;
+use crate::traits::{Get, GetPalletVersion, OnRuntimeUpgrade, PalletVersion};
+use crate::weights::Weight;
 
 /// Utility to iterate through raw items in storage.
 pub struct StorageIterator<T> {
@@ -195,3 +197,83 @@ pub fn take_storage_item<K: Encode + Sized, T: Decode + Sized, H: StorageHasher>
 ) -> Option<T> {
 	take_storage_value(module, item, key.using_encoded(H::hash).as_ref())
 }
+
+/// A runtime upgrade that only runs `Inner` if the storage version of `Pallet` is `From`.
+///
+/// The storage version of a pallet is only bumped to the crate version after all `OnRuntimeUpgrade`
+/// hooks of the runtime upgrade ran (see [`GetPalletVersion`]). Thus, `Pallet` still reports the
+/// version it had before the upgrade while this migration runs, and the migration will not run
+/// again in any later upgrade.
+///
+/// A pallet that has no version in storage is treated as being on `PalletVersion::default()`, which
+/// is the case for any chain that ran the pallet before pallet versions were introduced.
+///
+/// # Example
+///
+/// ```nocompile
+/// parameter_types! {
+/// 	pub const StakingV1: PalletVersion = PalletVersion { major: 1, minor: 0, patch: 0 };
+/// }
+///
+/// pub type Migrations = (
+/// 	VersionedMigration<Staking, StakingV1, pallet_staking::migrations::V1ToV2<Runtime>>,
+/// );
+/// ```
+pub struct VersionedMigration<Pallet, From, Inner>(PhantomData<(Pallet, From, Inner)>);
+
+impl<Pallet, From, Inner> VersionedMigration<Pallet, From, Inner> where
+	Pallet: GetPalletVersion,
+	From: Get<PalletVersion>,
+{
+	/// Returns `true` if the storage version of `Pallet` matches `From`.
+	pub fn should_run() -> bool {
+		Pallet::storage_version().unwrap_or_default() == From::get()
+	}
+
+	/// The storage key that is used to hand over between `pre_upgrade` and `post_upgrade` whether
+	/// `Inner` is going to run.
+	#[cfg(feature = "try-runtime")]
+	fn will_run_key() -> [u8; 32] {
+		let mut key = [0u8; 32];
+		key[0..16].copy_from_slice(&Twox128::hash(b":__VERSIONED_MIGRATION__:"));
+		key[16..32].copy_from_slice(&Twox128::hash(sp_std::any::type_name::<Inner>().as_bytes()));
+		key
+	}
+}
+
+impl<Pallet, From, Inner> OnRuntimeUpgrade for VersionedMigration<Pallet, From, Inner> where
+	Pallet: GetPalletVersion,
+	From: Get<PalletVersion>,
+	Inner: OnRuntimeUpgrade,
+{
+	fn on_runtime_upgrade() -> Weight {
+		if Self::should_run() {
+			Inner::on_runtime_upgrade()
+		} else {
+			crate::debug::native::info!(
+				"Skipping migration `{}`, pallet is not on the expected storage version.",
+				sp_std::any::type_name::<Inner>(),
+			);
+			0
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		if Self::should_run() {
+			frame_support::storage::unhashed::put(&Self::will_run_key(), &true);
+			Inner::pre_upgrade()
+		} else {
+			Ok(())
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		if frame_support::storage::unhashed::take::<bool>(&Self::will_run_key()).unwrap_or(false) {
+			Inner::post_upgrade()
+		} else {
+			Ok(())
+		}
+	}
+}
@@ -20,12 +20,12 @@
 //! NOTE: If you're looking for `parameter_types`, it has moved in to the top-level module.
 
 use sp_std::{prelude::*, result, marker::PhantomData, ops::Div, fmt::Debug};
-use codec::{FullCodec, Codec, Encode, Decode, EncodeLike};
+use codec::{FullCodec, Codec, Encode, Decode, EncodeLike, HasCompact};
 use sp_core::u32_trait::Value as U32;
 use sp_runtime::{
-	RuntimeDebug, ConsensusEngineId, DispatchResult, DispatchError, traits::{
+	RuntimeDebug, ConsensusEngineId, DispatchResult, DispatchError, Perbill, traits::{
 		MaybeSerializeDeserialize, AtLeast32Bit, Saturating, TrailingZeroInput, Bounded, Zero,
-		BadOrigin, AtLeast32BitUnsigned
+		BadOrigin, AtLeast32BitUnsigned, Member,
 	},
 };
 use crate::dispatch::Parameter;
@@ -414,6 +414,11 @@ impl<
 /// be the same logical unit that dictates `ShouldEndSession` to the session module. No Assumptions
 /// are made about the scheduling of the sessions.
 pub trait EstimateNextSessionRotation<BlockNumber> {
+	/// Return the average length of a session.
+	///
+	/// This may or may not be accurate.
+	fn average_session_length() -> BlockNumber;
+
 	/// Return the block number at which the next session rotation is estimated to happen.
 	///
 	/// None should be returned if the estimation fails to come to an answer
@@ -423,7 +428,11 @@ pub trait EstimateNextSessionRotation<BlockNumber> {
 	fn weight(now: BlockNumber) -> Weight;
 }
 
-impl<BlockNumber: Bounded> EstimateNextSessionRotation<BlockNumber> for () {
+impl<BlockNumber: Bounded + Default> EstimateNextSessionRotation<BlockNumber> for () {
+	fn average_session_length() -> BlockNumber {
+		Default::default()
+	}
+
 	fn estimate_next_session_rotation(_: BlockNumber) -> Option<BlockNumber> {
 		Default::default()
 	}
@@ -436,6 +445,11 @@ impl<BlockNumber: Bounded> EstimateNextSessionRotation<BlockNumber> for () {
 /// Something that can estimate at which block the next `new_session` will be triggered. This must
 /// always be implemented by the session module.
 pub trait EstimateNextNewSession<BlockNumber> {
+	/// Return the average length of a session.
+	///
+	/// This may or may not be accurate.
+	fn average_session_length() -> BlockNumber;
+
 	/// Return the block number at which the next new session is estimated to happen.
 	fn estimate_next_new_session(now: BlockNumber) -> Option<BlockNumber>;
 
@@ -443,7 +457,11 @@ pub trait EstimateNextNewSession<BlockNumber> {
 	fn weight(now: BlockNumber) -> Weight;
 }
 
-impl<BlockNumber: Bounded> EstimateNextNewSession<BlockNumber> for () {
+impl<BlockNumber: Bounded + Default> EstimateNextNewSession<BlockNumber> for () {
+	fn average_session_length() -> BlockNumber {
+		Default::default()
+	}
+
 	fn estimate_next_new_session(_: BlockNumber) -> Option<BlockNumber> {
 		Default::default()
 	}
@@ -1158,10 +1176,10 @@ pub trait VestingSchedule<AccountId> {
 	/// Returns `None` if the account has no vesting schedule.
 	fn vesting_balance(who: &AccountId) -> Option<<Self::Currency as Currency<AccountId>>::Balance>;
 
-	/// Adds a vesting schedule to a given account.
+	/// Adds a vesting schedule to a given account, alongside any it already has.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
-	/// and nothing is updated.
+	/// If the account cannot take another vesting schedule, an `Err` is returned and nothing is
+	/// updated.
 	///
 	/// Is a no-op if the amount to be vested is zero.
 	///
@@ -1173,7 +1191,7 @@ pub trait VestingSchedule<AccountId> {
 		starting_block: Self::Moment,
 	) -> DispatchResult;
 
-	/// Remove a vesting schedule for a given account.
+	/// Remove all vesting schedules for a given account.
 	///
 	/// NOTE: This doesn't alter the free balance of the account.
 	fn remove_vesting_schedule(who: &AccountId);
@@ -1301,6 +1319,10 @@ pub trait ChangeMembers<AccountId: Clone + Ord> {
 
 	/// Set the prime member.
 	fn set_prime(_prime: Option<AccountId>) {}
+
+	/// Set the vote weights of some members, replacing any previously set weights. Members which
+	/// are not given a weight have a weight of one.
+	fn set_vote_weights(_weights: &[(AccountId, u32)]) {}
 }
 
 impl<T: Clone + Ord> ChangeMembers<T> for () {
@@ -1308,6 +1330,7 @@ impl<T: Clone + Ord> ChangeMembers<T> for () {
 	fn change_members_sorted(_: &[T], _: &[T], _: &[T]) {}
 	fn set_members_sorted(_: &[T], _: &[T]) {}
 	fn set_prime(_: Option<T>) {}
+	fn set_vote_weights(_: &[(T, u32)]) {}
 }
 
 
@@ -1380,10 +1403,13 @@ pub trait ValidatorRegistration<ValidatorId> {
 pub trait ModuleToIndex {
 	/// Convert the given module `M` into an index.
 	fn module_to_index<M: 'static>() -> Option<usize>;
+	/// Convert the given module `M` into the name it was given in `construct_runtime!`.
+	fn module_name<M: 'static>() -> Option<&'static str>;
 }
 
 impl ModuleToIndex for () {
 	fn module_to_index<M: 'static>() -> Option<usize> { Some(0) }
+	fn module_name<M: 'static>() -> Option<&'static str> { Some("test") }
 }
 
 /// The function and pallet name of the Call.
@@ -1454,6 +1480,20 @@ pub trait OnRuntimeUpgrade {
 	///
 	/// Return the non-negotiable weight consumed for runtime upgrade.
 	fn on_runtime_upgrade() -> crate::weights::Weight { 0 }
+
+	/// Execute some pre-checks prior to a runtime upgrade.
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+	/// Any state it needs to hand over to `post_upgrade` should be put into temporary storage
+	/// and removed again in `post_upgrade`.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> { Ok(()) }
+
+	/// Execute some post-checks after a runtime upgrade.
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> { Ok(()) }
 }
 
 #[impl_for_tuples(30)]
@@ -1463,6 +1503,125 @@ impl OnRuntimeUpgrade for Tuple {
 		for_tuples!( #( weight = weight.saturating_add(Tuple::on_runtime_upgrade()); )* );
 		weight
 	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		let mut result = Ok(());
+		for_tuples!( #( result = result.and(Tuple::pre_upgrade()); )* );
+		result
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		let mut result = Ok(());
+		for_tuples!( #( result = result.and(Tuple::post_upgrade()); )* );
+		result
+	}
+}
+
+/// A trait that will be called at genesis.
+///
+/// Implementing this trait for a pallet lets you express operations that should
+/// happen at genesis. It will be called in an externalities provided environment and
+/// will see the genesis state after all pallets have written their genesis state.
+#[impl_for_tuples(30)]
+pub trait OnGenesis {
+	/// Something that should happen at genesis.
+	fn on_genesis() {}
+}
+
+/// The storage key postfix that is used to store the [`PalletVersion`] per pallet.
+///
+/// The full storage key is built by using:
+/// Twox128([`ModuleToIndex::module_name`]) ++ Twox128([`PALLET_VERSION_STORAGE_KEY_POSTFIX`])
+pub const PALLET_VERSION_STORAGE_KEY_POSTFIX: &[u8] = b":__PALLET_VERSION__:";
+
+/// The version of a pallet.
+///
+/// Versions are ordered by `major`, then `minor`, then `patch`.
+///
+/// Each pallet version is stored in the state under a fixed key. See
+/// [`PALLET_VERSION_STORAGE_KEY_POSTFIX`] for how this key is built.
+#[derive(RuntimeDebug, Eq, PartialEq, Encode, Decode, PartialOrd, Ord, Clone, Copy, Default)]
+pub struct PalletVersion {
+	/// The major version of the pallet.
+	pub major: u16,
+	/// The minor version of the pallet.
+	pub minor: u8,
+	/// The patch version of the pallet.
+	pub patch: u8,
+}
+
+impl PalletVersion {
+	/// Creates a new instance of `Self`.
+	pub fn new(major: u16, minor: u8, patch: u8) -> Self {
+		Self { major, minor, patch }
+	}
+
+	/// Returns the storage key for a pallet version.
+	///
+	/// See [`PALLET_VERSION_STORAGE_KEY_POSTFIX`] on how this key is built.
+	///
+	/// Returns `None` if the given `MT` does not know the pallet `P`.
+	pub fn storage_key<MT: ModuleToIndex, P: 'static>() -> Option<[u8; 32]> {
+		let pallet_name = MT::module_name::<P>()?;
+
+		let pallet_name = sp_io::hashing::twox_128(pallet_name.as_bytes());
+		let postfix = sp_io::hashing::twox_128(PALLET_VERSION_STORAGE_KEY_POSTFIX);
+
+		let mut final_key = [0u8; 32];
+		final_key[..16].copy_from_slice(&pallet_name);
+		final_key[16..].copy_from_slice(&postfix);
+
+		Some(final_key)
+	}
+
+	/// Put this pallet version into the storage.
+	///
+	/// It will use the storage key that is associated with the given `Pallet`.
+	///
+	/// # Panics
+	///
+	/// This function will panic iff `Pallet` can not be found by `ModuleToIndex`.
+	/// In a runtime that is put together using
+	/// [`construct_runtime!`](crate::construct_runtime) this should never happen.
+	///
+	/// It will also panic if this function isn't executed in an externalities
+	/// provided environment.
+	pub fn put_into_storage<MT: ModuleToIndex, Pallet: 'static>(&self) {
+		let key = Self::storage_key::<MT, Pallet>()
+			.expect("Every active pallet has a name in the runtime; qed");
+
+		crate::storage::unhashed::put(&key, self);
+	}
+}
+
+/// Provides version information about a pallet.
+///
+/// This trait provides two functions for returning the version of a
+/// pallet. There is a state where both functions can return distinct versions.
+/// See [`GetPalletVersion::storage_version`] for more information about this.
+pub trait GetPalletVersion {
+	/// Returns the current version of the pallet.
+	///
+	/// This is the version declared in the `Cargo.toml` of the pallet crate.
+	fn current_version() -> PalletVersion;
+
+	/// Returns the version of the pallet that is stored in storage.
+	///
+	/// Most of the time this will return the exact same version as
+	/// [`GetPalletVersion::current_version`]. Only when being in
+	/// a state after a runtime upgrade happened and the pallet did
+	/// not yet update its version in storage, this will return a
+	/// different (the previous, seen from the time of calling) version.
+	///
+	/// See [`PalletVersion`] for more information.
+	///
+	/// # Note
+	///
+	/// If there was no previous version of the pallet stored in the state,
+	/// this function returns `None`.
+	fn storage_version() -> Option<PalletVersion>;
 }
 
 /// Off-chain computation trait.
@@ -1486,6 +1645,9 @@ pub trait OffchainWorker<BlockNumber> {
 	fn offchain_worker(_n: BlockNumber) {}
 }
 
+pub mod fungibles;
+pub mod nonfungibles;
+
 pub mod schedule {
 	use super::*;
 
@@ -1516,6 +1678,73 @@ pub mod schedule {
 	/// The lowest priority. Most stuff should be around here.
 	pub const LOWEST_PRIORITY: Priority = 255;
 
+	/// Either the value itself or the hash of its encoding, to be looked up as a preimage.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub enum MaybeHashed<T, Hash> {
+		/// The value itself.
+		Value(T),
+		/// The hash of the encoded value which this value represents.
+		Hash(Hash),
+	}
+
+	impl<T, H> From<T> for MaybeHashed<T, H> {
+		fn from(t: T) -> Self {
+			MaybeHashed::Value(t)
+		}
+	}
+
+	impl<T: Decode, H> MaybeHashed<T, H> {
+		/// Return the value, if we have it.
+		pub fn as_value(&self) -> Option<&T> {
+			match self {
+				Self::Value(c) => Some(c),
+				Self::Hash(_) => None,
+			}
+		}
+
+		/// Return the hash, if this is only a hash.
+		pub fn as_hash(&self) -> Option<&H> {
+			match self {
+				Self::Value(_) => None,
+				Self::Hash(h) => Some(h),
+			}
+		}
+
+		/// Ensure the preimage of the hash, if any, is requested from `P`.
+		pub fn ensure_requested<P: PreimageProvider<H>>(&self) {
+			if let Self::Hash(hash) = self {
+				P::request_preimage(hash)
+			}
+		}
+
+		/// Ensure the preimage of the hash, if any, is no longer requested from `P`.
+		pub fn ensure_unrequested<P: PreimageProvider<H>>(&self) {
+			if let Self::Hash(hash) = self {
+				P::unrequest_preimage(hash)
+			}
+		}
+
+		/// Attempt to resolve a hash into its value using the preimages of `P`.
+		///
+		/// Returns the (possibly) resolved value along with the hash if it was just resolved and
+		/// so its preimage may be unrequested.
+		pub fn resolved<P: PreimageProvider<H>>(self) -> (Self, Option<H>) {
+			match self {
+				Self::Value(c) => (Self::Value(c), None),
+				Self::Hash(h) => {
+					let data = match P::get_preimage(&h) {
+						Some(p) => p,
+						None => return (Self::Hash(h), None),
+					};
+					match T::decode(&mut &data[..]) {
+						Ok(c) => (Self::Value(c), Some(h)),
+						Err(_) => (Self::Hash(h), None),
+					}
+				}
+			}
+		}
+	}
+
 	/// A type that can be used as a scheduler.
 	pub trait Anon<BlockNumber, Call, Origin> {
 		/// An address which can be used for removing a scheduled task.
@@ -1575,6 +1804,129 @@ pub mod schedule {
 	}
 }
 
+/// An interface for looking up preimages from their hash on chain.
+pub trait PreimageProvider<Hash> {
+	/// Returns whether a preimage exists for a given hash.
+	///
+	/// A value of `true` implies that `get_preimage` is `Some`.
+	fn have_preimage(hash: &Hash) -> bool;
+
+	/// Returns the preimage for a given hash.
+	fn get_preimage(hash: &Hash) -> Option<Vec<u8>>;
+
+	/// Returns whether a preimage request exists for a given hash.
+	fn preimage_requested(hash: &Hash) -> bool;
+
+	/// Request that someone report a preimage. Providers use this to optimise the economics for
+	/// preimage reporting.
+	fn request_preimage(hash: &Hash);
+
+	/// Cancel a previous preimage request.
+	fn unrequest_preimage(hash: &Hash);
+}
+
+impl<Hash> PreimageProvider<Hash> for () {
+	fn have_preimage(_: &Hash) -> bool { false }
+	fn get_preimage(_: &Hash) -> Option<Vec<u8>> { None }
+	fn preimage_requested(_: &Hash) -> bool { false }
+	fn request_preimage(_: &Hash) {}
+	fn unrequest_preimage(_: &Hash) {}
+}
+
+/// An interface for managing preimages to hashes on chain.
+///
+/// Note that this API does not assume any underlying user is calling, and thus
+/// does not handle any preimage ownership or fees. Other system level logic that
+/// uses this API should implement that on their own side.
+pub trait PreimageRecipient<Hash>: PreimageProvider<Hash> {
+	/// Maximum size of a preimage.
+	type MaxSize: Get<u32>;
+
+	/// Store the bytes of a preimage on chain.
+	///
+	/// Fails if the preimage is larger than `MaxSize` or has already been noted.
+	fn note_preimage(bytes: Vec<u8>) -> DispatchResult;
+
+	/// Clear a previously noted preimage. This is infallible and should be treated more like a
+	/// hint - if it was not previously noted or if it is now requested, then this will not do
+	/// anything.
+	fn unnote_preimage(hash: &Hash);
+}
+
+impl<Hash> PreimageRecipient<Hash> for () {
+	type MaxSize = ();
+	fn note_preimage(_: Vec<u8>) -> DispatchResult { Ok(()) }
+	fn unnote_preimage(_: &Hash) {}
+}
+
+/// A tally of the votes cast on a poll.
+pub trait VoteTally<Votes> {
+	/// The amount of votes in favour, used to prioritise polls waiting for a decision.
+	fn ayes(&self) -> Votes;
+
+	/// The proportion of the total possible turnout which supports the poll.
+	fn support(&self) -> Perbill;
+
+	/// The proportion of the votes cast which are in favour of the poll.
+	fn approval(&self) -> Perbill;
+}
+
+/// The status of a poll, as seen by a voting pallet.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum PollStatus<Tally, Moment, Class> {
+	/// There is no such poll.
+	None,
+	/// The poll is ongoing with the given tally, in the given class.
+	Ongoing(Tally, Class),
+	/// The poll ended at the given moment, either approved (`true`) or not.
+	Completed(Moment, bool),
+}
+
+impl<Tally, Moment, Class> PollStatus<Tally, Moment, Class> {
+	/// The tally and class of the poll if it is ongoing.
+	pub fn ensure_ongoing(self) -> Option<(Tally, Class)> {
+		match self {
+			PollStatus::Ongoing(tally, class) => Some((tally, class)),
+			_ => None,
+		}
+	}
+}
+
+/// Something hosting polls which are voted on by changing their tally.
+///
+/// Each poll belongs to a class. Voting pallets may treat the classes independently, e.g. to
+/// allow delegating the votes of one class but not the others.
+pub trait Polling<Tally> {
+	/// The index of a poll.
+	type Index: Parameter + Member + Ord + PartialOrd + Copy + HasCompact;
+	/// The type of the votes counted by the tally.
+	type Votes: Parameter + Member + Ord + PartialOrd + Copy + HasCompact;
+	/// The class of a poll.
+	type Class: Parameter + Member + Ord + PartialOrd;
+	/// The moment at which a poll ended.
+	type Moment;
+
+	/// All the classes of polls.
+	fn classes() -> Vec<Self::Class>;
+
+	/// The tally and class of the poll `index` if it is ongoing.
+	fn as_ongoing(index: Self::Index) -> Option<(Tally, Self::Class)>;
+
+	/// Call `f` with the status of the poll `index`. Changes `f` makes to the tally of an
+	/// ongoing poll are kept.
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut Tally, Self::Moment, Self::Class>) -> R,
+	) -> R;
+
+	/// Call `f` with the status of the poll `index`. Changes `f` makes to the tally of an
+	/// ongoing poll are kept only if it returns `Ok`.
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut Tally, Self::Moment, Self::Class>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError>;
+}
+
 /// Some sort of check on the origin is performed by this object.
 pub trait EnsureOrigin<OuterOrigin> {
 	/// A return type.
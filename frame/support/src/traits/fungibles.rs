@@ -0,0 +1,634 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits for dealing with a set of fungible assets, each identified by an asset id.
+//!
+//! Unlike `Currency`, which is tied to a single token, these traits allow a pallet to be
+//! pointed at any one of many assets held in a multi-asset pallet. `ItemOf` adapts a single
+//! asset of such a set back into a `Currency` (and `ReservableCurrency`), so that existing
+//! pallets can be used with it unchanged.
+
+use super::*;
+
+/// The type of an asset identifier in a set of fungible assets.
+pub trait AssetId: FullCodec + Copy + Eq + PartialEq + Debug {}
+impl<T: FullCodec + Copy + Eq + PartialEq + Debug> AssetId for T {}
+
+/// The type of a balance of a fungible asset.
+pub trait Balance: AtLeast32BitUnsigned + FullCodec + Copy + MaybeSerializeDeserialize + Debug + Default {}
+impl<
+	T: AtLeast32BitUnsigned + FullCodec + Copy + MaybeSerializeDeserialize + Debug + Default
+> Balance for T {}
+
+/// One of a number of consequences of depositing a fungible asset into an account.
+#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum DepositConsequence {
+	/// Deposit couldn't happen due to the amount being too low. This is usually because the
+	/// account doesn't yet exist and the deposit wouldn't bring it to at least the minimum needed
+	/// for existence.
+	BelowMinimum,
+	/// Deposit cannot happen since the account cannot be created (usually because it's a consumer
+	/// and there exists no provider reference).
+	CannotCreate,
+	/// The asset is unknown. Usually because an `AssetId` has been presented which doesn't exist
+	/// on the system.
+	UnknownAsset,
+	/// An overflow would occur. This is practically unexpected, but could happen in test systems
+	/// with extremely small balance types or balances that approach the max value of the balance
+	/// type.
+	Overflow,
+	/// Account continued in existence.
+	Success,
+}
+
+impl DepositConsequence {
+	/// Convert the type into a `Result` with `DispatchError` as the error.
+	pub fn into_result(self) -> DispatchResult {
+		use DepositConsequence::*;
+		Err(match self {
+			BelowMinimum => "BelowMinimum",
+			CannotCreate => "CannotCreate",
+			UnknownAsset => "UnknownAsset",
+			Overflow => "Overflow",
+			Success => return Ok(()),
+		}.into())
+	}
+}
+
+/// One of a number of consequences of withdrawing a fungible asset from an account.
+#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum WithdrawConsequence<Balance> {
+	/// Withdraw could not happen since the amount to be withdrawn is less than the total funds in
+	/// the account.
+	NoFunds,
+	/// The withdraw would mean the account dying when it needs to exist (usually because it is a
+	/// provider and there are consumer references on it).
+	WouldDie,
+	/// The asset is unknown. Usually because an `AssetId` has been presented which doesn't exist
+	/// on the system.
+	UnknownAsset,
+	/// There has been an underflow in the system. This is indicative of a corrupt state and
+	/// likely unrecoverable.
+	Underflow,
+	/// Not enough of the funds in the account are available for withdrawal.
+	Frozen,
+	/// Account balance would reduce to zero, potentially destroying it. The parameter is the
+	/// amount of balance which is destroyed.
+	ReducedToZero(Balance),
+	/// Account continued in existence.
+	Success,
+}
+
+impl<Balance: Zero> WithdrawConsequence<Balance> {
+	/// Convert the type into a `Result` with `DispatchError` as the error or the additional
+	/// `Balance` by which the account will be reduced.
+	pub fn into_result(self) -> result::Result<Balance, DispatchError> {
+		use WithdrawConsequence::*;
+		Err(match self {
+			NoFunds => "NoFunds",
+			WouldDie => "WouldDie",
+			UnknownAsset => "UnknownAsset",
+			Underflow => "Underflow",
+			Frozen => "Frozen",
+			ReducedToZero(result) => return Ok(result),
+			Success => return Ok(Zero::zero()),
+		}.into())
+	}
+}
+
+/// Trait for providing balance-inspection access to a set of named fungible assets.
+pub trait Inspect<AccountId> {
+	/// Means of identifying one asset class from another.
+	type AssetId: AssetId;
+
+	/// Scalar type for representing balance of an account.
+	type Balance: Balance;
+
+	/// The total amount of issuance in the system.
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance;
+
+	/// The minimum balance any single account may have.
+	fn minimum_balance(asset: Self::AssetId) -> Self::Balance;
+
+	/// Get the `asset` balance of `who`, including any part of it that is on hold.
+	fn balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+	/// Get the maximum amount of `asset` that `who` can withdraw/transfer successfully.
+	///
+	/// If `keep_alive` is `true`, then the account must be left with at least the minimum
+	/// balance of the asset.
+	fn reducible_balance(asset: Self::AssetId, who: &AccountId, keep_alive: bool) -> Self::Balance;
+
+	/// Returns `true` if the `asset` balance of `who` may be increased by `amount`.
+	fn can_deposit(asset: Self::AssetId, who: &AccountId, amount: Self::Balance)
+		-> DepositConsequence;
+
+	/// Returns `Failed` if the `asset` balance of `who` may not be decreased by `amount`, otherwise
+	/// the consequence.
+	fn can_withdraw(asset: Self::AssetId, who: &AccountId, amount: Self::Balance)
+		-> WithdrawConsequence<Self::Balance>;
+}
+
+/// Trait for providing a set of named fungible assets which can be created and destroyed.
+pub trait Mutate<AccountId>: Inspect<AccountId> {
+	/// Attempt to increase the `asset` balance of `who` by `amount`.
+	///
+	/// If not possible then don't do anything. Possible reasons for failure include:
+	/// - Minimum balance not met.
+	/// - Account cannot be created (e.g. because there is no provider reference and/or the asset
+	///   isn't considered worth anything).
+	///
+	/// Since this is an operation which should be possible to take alone, if successful it will
+	/// increase the overall supply of the underlying token.
+	fn mint_into(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+	/// Attempt to reduce the `asset` balance of `who` by `amount`.
+	///
+	/// If not possible then don't do anything. Possible reasons for failure include:
+	/// - Less funds in the account than `amount`
+	/// - Liquidity requirements (locks, reservations) prevent the funds from being removed
+	/// - Operation would require destroying the account and it is required to stay alive (e.g.
+	///   because it's providing a needed provider reference).
+	///
+	/// Since this is an operation which should be possible to take alone, if successful it will
+	/// reduce the overall supply of the underlying token.
+	///
+	/// Due to minimum balance requirements, it's possible that the amount withdrawn could be up to
+	/// `amount + minimum_balance() - 1`. It's also possible that the amount withdrawn could be
+	/// less than `amount` if the account holds less than that. The actual amount withdrawn is
+	/// returned.
+	fn burn_from(asset: Self::AssetId, who: &AccountId, amount: Self::Balance)
+		-> result::Result<Self::Balance, DispatchError>;
+}
+
+/// Trait for providing a set of named fungible assets which can only be transferred.
+pub trait Transfer<AccountId>: Inspect<AccountId> {
+	/// Transfer funds from one account into another.
+	///
+	/// If `keep_alive` is `true`, then `source` must be left with at least the minimum balance of
+	/// the asset. The amount actually transferred is returned.
+	fn transfer(
+		asset: Self::AssetId,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		keep_alive: bool,
+	) -> result::Result<Self::Balance, DispatchError>;
+}
+
+/// A fungible, multi-asset token class where balances and total issuance may be set directly,
+/// without any further accounting.
+///
+/// This is a low-level interface; it is mostly useful for building higher-level abstractions
+/// such as `ItemOf`, which keep the total issuance in line with account balances themselves.
+pub trait Unbalanced<AccountId>: Inspect<AccountId> {
+	/// Set the `asset` balance of `who` to `amount`. If this cannot be done for some reason (e.g.
+	/// because the account cannot be created or an overflow) then an `Err` is returned.
+	///
+	/// The total issuance of the asset is not changed.
+	fn set_balance(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+	/// Set the total issuance of `asset` to `amount`.
+	fn set_total_issuance(asset: Self::AssetId, amount: Self::Balance);
+}
+
+/// Trait for inspecting a set of named fungible assets which can be placed on hold.
+pub trait InspectHold<AccountId>: Inspect<AccountId> {
+	/// Amount of funds held in hold.
+	fn balance_on_hold(asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+	/// Check to see if some `amount` of `asset` may be held on the account of `who`.
+	fn can_hold(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> bool;
+}
+
+/// Trait for mutating a set of named fungible assets which can be placed on hold.
+pub trait MutateHold<AccountId>: InspectHold<AccountId> + Transfer<AccountId> {
+	/// Hold some funds in an account.
+	fn hold(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+	/// Release some funds in an account from being on hold.
+	///
+	/// If `best_effort` is `true`, then the amount actually released and returned as the inner
+	/// value of `Ok` may be smaller than the `amount` passed.
+	fn release(asset: Self::AssetId, who: &AccountId, amount: Self::Balance, best_effort: bool)
+		-> result::Result<Self::Balance, DispatchError>;
+
+	/// Transfer held funds into a destination account.
+	///
+	/// If `on_hold` is `true`, then the destination account must already exist and the assets
+	/// transferred will still be on hold in the destination account. If not, then the destination
+	/// account need not already exist, but must be creatable.
+	///
+	/// If `best_effort` is `true`, then an amount less than `amount` may be transferred without
+	/// error.
+	///
+	/// The actual amount transferred is returned, or `Err` in the case of error and nothing is
+	/// changed.
+	fn transfer_held(
+		asset: Self::AssetId,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+		on_hold: bool,
+	) -> result::Result<Self::Balance, DispatchError>;
+
+	/// Reduce the held balance of `who` by up to `amount`, destroying the funds. The total
+	/// issuance of the asset is reduced accordingly. Returns the amount actually destroyed.
+	fn slash_held(asset: Self::AssetId, who: &AccountId, amount: Self::Balance)
+		-> result::Result<Self::Balance, DispatchError>;
+}
+
+/// Convert a set of fungible assets into a `Currency` of one of its members, the asset id of
+/// which is given by `A`.
+///
+/// `Currency::free_balance` is the reducible balance of the asset, while
+/// `Currency::total_balance` also includes anything on hold. Imbalances created through this
+/// adapter adjust the total issuance of the asset when dropped, just like those of the balances
+/// pallet.
+pub struct ItemOf<F, A, AccountId>(PhantomData<(F, A, AccountId)>);
+
+impl<
+	F: Mutate<AccountId> + Transfer<AccountId> + Unbalanced<AccountId>,
+	A: Get<<F as Inspect<AccountId>>::AssetId>,
+	AccountId,
+> Currency<AccountId> for ItemOf<F, A, AccountId> {
+	type Balance = <F as Inspect<AccountId>>::Balance;
+	type PositiveImbalance = imbalances::PositiveImbalance<F, A, AccountId>;
+	type NegativeImbalance = imbalances::NegativeImbalance<F, A, AccountId>;
+
+	fn total_balance(who: &AccountId) -> Self::Balance {
+		F::balance(A::get(), who)
+	}
+
+	fn can_slash(who: &AccountId, value: Self::Balance) -> bool {
+		F::reducible_balance(A::get(), who, false) >= value
+	}
+
+	fn total_issuance() -> Self::Balance {
+		F::total_issuance(A::get())
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		F::minimum_balance(A::get())
+	}
+
+	fn burn(amount: Self::Balance) -> Self::PositiveImbalance {
+		let asset = A::get();
+		let issuance = F::total_issuance(asset);
+		let amount = amount.min(issuance);
+		F::set_total_issuance(asset, issuance - amount);
+		imbalances::PositiveImbalance::new(amount)
+	}
+
+	fn issue(amount: Self::Balance) -> Self::NegativeImbalance {
+		let asset = A::get();
+		let issuance = F::total_issuance(asset);
+		let amount = amount.min(Self::Balance::max_value() - issuance);
+		F::set_total_issuance(asset, issuance + amount);
+		imbalances::NegativeImbalance::new(amount)
+	}
+
+	fn free_balance(who: &AccountId) -> Self::Balance {
+		F::reducible_balance(A::get(), who, false)
+	}
+
+	fn ensure_can_withdraw(
+		who: &AccountId,
+		amount: Self::Balance,
+		_reasons: WithdrawReasons,
+		_new_balance: Self::Balance,
+	) -> DispatchResult {
+		F::can_withdraw(A::get(), who, amount).into_result().map(|_| ())
+	}
+
+	fn transfer(
+		source: &AccountId,
+		dest: &AccountId,
+		value: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		let keep_alive = existence_requirement == ExistenceRequirement::KeepAlive;
+		F::transfer(A::get(), source, dest, value, keep_alive).map(|_| ())
+	}
+
+	fn slash(who: &AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		let asset = A::get();
+		let slashed = value.min(F::reducible_balance(asset, who, false));
+		let balance = F::balance(asset, who);
+		match F::set_balance(asset, who, balance - slashed) {
+			Ok(()) => (imbalances::NegativeImbalance::new(slashed), value - slashed),
+			Err(_) => (imbalances::NegativeImbalance::zero(), value),
+		}
+	}
+
+	fn deposit_into_existing(
+		who: &AccountId,
+		value: Self::Balance,
+	) -> result::Result<Self::PositiveImbalance, DispatchError> {
+		let asset = A::get();
+		let balance = F::balance(asset, who);
+		if balance.is_zero() {
+			return Err("DeadAccount".into())
+		}
+		F::can_deposit(asset, who, value).into_result()?;
+		F::set_balance(asset, who, balance + value)?;
+		Ok(imbalances::PositiveImbalance::new(value))
+	}
+
+	fn deposit_creating(who: &AccountId, value: Self::Balance) -> Self::PositiveImbalance {
+		let asset = A::get();
+		if F::can_deposit(asset, who, value).into_result().is_err() {
+			return imbalances::PositiveImbalance::zero()
+		}
+		match F::set_balance(asset, who, F::balance(asset, who) + value) {
+			Ok(()) => imbalances::PositiveImbalance::new(value),
+			Err(_) => imbalances::PositiveImbalance::zero(),
+		}
+	}
+
+	fn withdraw(
+		who: &AccountId,
+		value: Self::Balance,
+		_reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> result::Result<Self::NegativeImbalance, DispatchError> {
+		let asset = A::get();
+		let keep_alive = liveness == ExistenceRequirement::KeepAlive;
+		if F::reducible_balance(asset, who, keep_alive) < value {
+			return Err("InsufficientBalance".into())
+		}
+		// A remainder below the minimum balance is burned along with `value`, reaping the
+		// account.
+		let dust = F::can_withdraw(asset, who, value).into_result()?;
+		F::set_balance(asset, who, F::balance(asset, who) - value - dust)?;
+		F::set_total_issuance(asset, F::total_issuance(asset).saturating_sub(dust));
+		Ok(imbalances::NegativeImbalance::new(value))
+	}
+
+	fn make_free_balance_be(
+		who: &AccountId,
+		balance: Self::Balance,
+	) -> SignedImbalance<Self::Balance, Self::PositiveImbalance> {
+		let asset = A::get();
+		let free = Self::free_balance(who);
+		let total = F::balance(asset, who);
+		let new_total = total - free + balance;
+		if F::set_balance(asset, who, new_total).is_err() {
+			return SignedImbalance::Positive(imbalances::PositiveImbalance::zero())
+		}
+		if balance >= free {
+			SignedImbalance::Positive(imbalances::PositiveImbalance::new(balance - free))
+		} else {
+			SignedImbalance::Negative(imbalances::NegativeImbalance::new(free - balance))
+		}
+	}
+}
+
+impl<
+	F: Mutate<AccountId> + MutateHold<AccountId> + Unbalanced<AccountId>,
+	A: Get<<F as Inspect<AccountId>>::AssetId>,
+	AccountId,
+> ReservableCurrency<AccountId> for ItemOf<F, A, AccountId> {
+	fn can_reserve(who: &AccountId, value: Self::Balance) -> bool {
+		F::can_hold(A::get(), who, value)
+	}
+
+	fn slash_reserved(who: &AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		let asset = A::get();
+		// `slash_held` reduces the total issuance itself, so the imbalance handed back must be
+		// compensated for in advance.
+		match F::slash_held(asset, who, value) {
+			Ok(slashed) => {
+				F::set_total_issuance(asset, F::total_issuance(asset).saturating_add(slashed));
+				(imbalances::NegativeImbalance::new(slashed), value - slashed)
+			}
+			Err(_) => (imbalances::NegativeImbalance::zero(), value),
+		}
+	}
+
+	fn reserved_balance(who: &AccountId) -> Self::Balance {
+		F::balance_on_hold(A::get(), who)
+	}
+
+	fn reserve(who: &AccountId, value: Self::Balance) -> DispatchResult {
+		F::hold(A::get(), who, value)
+	}
+
+	fn unreserve(who: &AccountId, value: Self::Balance) -> Self::Balance {
+		let released = F::release(A::get(), who, value, true).unwrap_or_else(|_| Zero::zero());
+		value - released
+	}
+
+	fn repatriate_reserved(
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError> {
+		let on_hold = status == BalanceStatus::Reserved;
+		let moved = F::transfer_held(A::get(), slashed, beneficiary, value, true, on_hold)?;
+		Ok(value - moved)
+	}
+}
+
+/// Imbalances created by `ItemOf`.
+pub mod imbalances {
+	use super::*;
+
+	/// Opaque, move-only struct with private fields that serves as a token denoting that
+	/// funds have been created without any equal and opposite accounting.
+	#[must_use]
+	pub struct PositiveImbalance<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	>(<F as Inspect<AccountId>>::Balance, PhantomData<(A, AccountId)>);
+
+	impl<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> PositiveImbalance<F, A, AccountId> {
+		/// Create a new positive imbalance from a balance.
+		pub fn new(amount: <F as Inspect<AccountId>>::Balance) -> Self {
+			PositiveImbalance(amount, PhantomData)
+		}
+	}
+
+	/// Opaque, move-only struct with private fields that serves as a token denoting that
+	/// funds have been destroyed without any equal and opposite accounting.
+	#[must_use]
+	pub struct NegativeImbalance<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	>(<F as Inspect<AccountId>>::Balance, PhantomData<(A, AccountId)>);
+
+	impl<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> NegativeImbalance<F, A, AccountId> {
+		/// Create a new negative imbalance from a balance.
+		pub fn new(amount: <F as Inspect<AccountId>>::Balance) -> Self {
+			NegativeImbalance(amount, PhantomData)
+		}
+	}
+
+	impl<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> TryDrop for PositiveImbalance<F, A, AccountId> {
+		fn try_drop(self) -> result::Result<(), Self> {
+			self.drop_zero()
+		}
+	}
+
+	impl<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> Imbalance<<F as Inspect<AccountId>>::Balance> for PositiveImbalance<F, A, AccountId> {
+		type Opposite = NegativeImbalance<F, A, AccountId>;
+
+		fn zero() -> Self {
+			Self::new(Zero::zero())
+		}
+		fn drop_zero(self) -> result::Result<(), Self> {
+			if self.0.is_zero() {
+				Ok(())
+			} else {
+				Err(self)
+			}
+		}
+		fn split(self, amount: <F as Inspect<AccountId>>::Balance) -> (Self, Self) {
+			let first = self.0.min(amount);
+			let second = self.0 - first;
+
+			sp_std::mem::forget(self);
+			(Self::new(first), Self::new(second))
+		}
+		fn merge(mut self, other: Self) -> Self {
+			self.0 = self.0.saturating_add(other.0);
+			sp_std::mem::forget(other);
+
+			self
+		}
+		fn subsume(&mut self, other: Self) {
+			self.0 = self.0.saturating_add(other.0);
+			sp_std::mem::forget(other);
+		}
+		fn offset(self, other: Self::Opposite) -> result::Result<Self, Self::Opposite> {
+			let (a, b) = (self.0, other.0);
+			sp_std::mem::forget((self, other));
+
+			if a >= b {
+				Ok(Self::new(a - b))
+			} else {
+				Err(NegativeImbalance::new(b - a))
+			}
+		}
+		fn peek(&self) -> <F as Inspect<AccountId>>::Balance {
+			self.0
+		}
+	}
+
+	impl<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> TryDrop for NegativeImbalance<F, A, AccountId> {
+		fn try_drop(self) -> result::Result<(), Self> {
+			self.drop_zero()
+		}
+	}
+
+	impl<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> Imbalance<<F as Inspect<AccountId>>::Balance> for NegativeImbalance<F, A, AccountId> {
+		type Opposite = PositiveImbalance<F, A, AccountId>;
+
+		fn zero() -> Self {
+			Self::new(Zero::zero())
+		}
+		fn drop_zero(self) -> result::Result<(), Self> {
+			if self.0.is_zero() {
+				Ok(())
+			} else {
+				Err(self)
+			}
+		}
+		fn split(self, amount: <F as Inspect<AccountId>>::Balance) -> (Self, Self) {
+			let first = self.0.min(amount);
+			let second = self.0 - first;
+
+			sp_std::mem::forget(self);
+			(Self::new(first), Self::new(second))
+		}
+		fn merge(mut self, other: Self) -> Self {
+			self.0 = self.0.saturating_add(other.0);
+			sp_std::mem::forget(other);
+
+			self
+		}
+		fn subsume(&mut self, other: Self) {
+			self.0 = self.0.saturating_add(other.0);
+			sp_std::mem::forget(other);
+		}
+		fn offset(self, other: Self::Opposite) -> result::Result<Self, Self::Opposite> {
+			let (a, b) = (self.0, other.0);
+			sp_std::mem::forget((self, other));
+
+			if a >= b {
+				Ok(Self::new(a - b))
+			} else {
+				Err(PositiveImbalance::new(b - a))
+			}
+		}
+		fn peek(&self) -> <F as Inspect<AccountId>>::Balance {
+			self.0
+		}
+	}
+
+	impl<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> Drop for PositiveImbalance<F, A, AccountId> {
+		/// Basic drop handler will just square up the total issuance.
+		fn drop(&mut self) {
+			let asset = A::get();
+			F::set_total_issuance(asset, F::total_issuance(asset).saturating_add(self.0));
+		}
+	}
+
+	impl<
+		F: Unbalanced<AccountId>,
+		A: Get<<F as Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> Drop for NegativeImbalance<F, A, AccountId> {
+		/// Basic drop handler will just square up the total issuance.
+		fn drop(&mut self) {
+			let asset = A::get();
+			F::set_total_issuance(asset, F::total_issuance(asset).saturating_sub(self.0));
+		}
+	}
+}
@@ -28,9 +28,13 @@ pub trait Trait {
 	type Origin;
 	/// The block number type.
 	type BlockNumber;
+	/// The information about the pallet setup in the runtime.
+	type ModuleToIndex: frame_support::traits::ModuleToIndex;
+	/// The db weights.
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 }
 
 frame_support::decl_module! {
 	/// Some test module
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 }
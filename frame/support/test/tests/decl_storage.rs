@@ -25,12 +25,14 @@ mod tests {
 	use codec::{Encode, Decode, EncodeLike};
 
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	pub trait Trait {
 		type Origin: Encode + Decode + EncodeLike + std::default::Default;
 		type BlockNumber;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_storage! {
@@ -88,6 +90,8 @@ mod tests {
 	impl Trait for TraitImpl {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	const EXPECTED_METADATA: StorageMetadata = StorageMetadata {
@@ -417,10 +421,12 @@ mod test2 {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	type PairOf<T> = (T, T);
@@ -444,6 +450,8 @@ mod test2 {
 	impl Trait for TraitImpl {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 }
 
@@ -453,9 +461,11 @@ mod test3 {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 	frame_support::decl_storage! {
 		trait Store for Module<T: Trait> as Test {
@@ -470,6 +480,8 @@ mod test3 {
 	impl Trait for TraitImpl {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 }
 
@@ -482,10 +494,12 @@ mod test_append_and_len {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	#[derive(PartialEq, Eq, Clone, Encode, Decode)]
@@ -514,6 +528,8 @@ mod test_append_and_len {
 	impl Trait for Test {
 		type Origin = u32;
 		type BlockNumber = u32;
+		type ModuleToIndex = ();
+		type DbWeight = ();
 	}
 
 	#[test]
@@ -26,10 +26,12 @@ mod no_instance {
 	pub trait Trait {
 		type Origin;
 		type BlockNumber: Encode + Decode + EncodeLike + Default + Clone;
+		type ModuleToIndex: frame_support::traits::ModuleToIndex;
+		type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 	}
 
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 	}
 
 	frame_support::decl_storage!{
@@ -50,11 +52,13 @@ mod no_instance {
 }
 
 mod instance {
+	use super::no_instance;
+
 	pub trait Trait<I = DefaultInstance>: super::no_instance::Trait {}
 
 	frame_support::decl_module! {
 		pub struct Module<T: Trait<I>, I: Instance = DefaultInstance>
-			for enum Call where origin: T::Origin {}
+			for enum Call where origin: T::Origin, system=no_instance {}
 	}
 
 	frame_support::decl_storage!{
@@ -18,10 +18,12 @@
 pub trait Trait {
 	type BlockNumber: codec::Codec + codec::EncodeLike + Default;
 	type Origin;
+	type ModuleToIndex: frame_support::traits::ModuleToIndex;
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 }
 
 frame_support::decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {}
 }
 
 frame_support::decl_storage! {
@@ -35,6 +37,8 @@ struct Test;
 impl Trait for Test {
 	type BlockNumber = u32;
 	type Origin = ();
+	type ModuleToIndex = ();
+	type DbWeight = ();
 }
 
 #[test]
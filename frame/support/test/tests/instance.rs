@@ -184,7 +184,8 @@ mod module3 {
 	}
 
 	frame_support::decl_module! {
-		pub struct Module<T: Trait> for enum Call where origin: <T as system::Trait>::Origin {}
+		pub struct Module<T: Trait> for enum Call
+			where origin: <T as system::Trait>::Origin, system=system {}
 	}
 }
 
@@ -17,17 +17,34 @@
 
 use codec::{Encode, Decode, EncodeLike};
 use frame_support::{
-	StorageMap, StorageValue, storage::{with_transaction, TransactionOutcome::*},
+	assert_ok, assert_noop, transactional, StorageMap, StorageValue,
+	dispatch::{DispatchError, DispatchResult}, storage::{with_transaction, TransactionOutcome::*},
 };
 use sp_io::TestExternalities;
+use sp_std::result;
 
 pub trait Trait {
 	type Origin;
 	type BlockNumber: Encode + Decode + EncodeLike + Default + Clone;
+	type ModuleToIndex: frame_support::traits::ModuleToIndex;
+	type DbWeight: frame_support::traits::Get<frame_support::weights::RuntimeDbWeight>;
 }
 
 frame_support::decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin, system=self {
+		#[weight = 0]
+		#[transactional]
+		fn value_commits(_origin, v: u32) {
+			Value::set(v);
+		}
+
+		#[weight = 0]
+		#[transactional]
+		fn value_rollbacks(_origin, v: u32) -> DispatchResult {
+			Value::set(v);
+			Err(DispatchError::Other("nah"))
+		}
+	}
 }
 
 frame_support::decl_storage!{
@@ -37,6 +54,14 @@ frame_support::decl_storage!{
 	}
 }
 
+struct Runtime;
+
+impl Trait for Runtime {
+	type Origin = u32;
+	type BlockNumber = u32;
+	type ModuleToIndex = ();
+	type DbWeight = ();
+}
 
 #[test]
 fn storage_transaction_basic_commit() {
@@ -157,3 +182,36 @@ fn storage_transaction_commit_then_rollback() {
 		assert_eq!(Map::get("val3"), 0);
 	});
 }
+
+#[test]
+fn transactional_annotation() {
+	#[transactional]
+	fn value_commits(v: u32) -> result::Result<u32, &'static str> {
+		Value::set(v);
+		Ok(v)
+	}
+
+	#[transactional]
+	fn value_rollbacks(v: u32) -> result::Result<u32, &'static str> {
+		Value::set(v);
+		Err("nah")
+	}
+
+	TestExternalities::default().execute_with(|| {
+		assert_ok!(value_commits(2), 2);
+		assert_eq!(Value::get(), 2);
+
+		assert_noop!(value_rollbacks(3), "nah");
+	});
+}
+
+#[test]
+fn transactional_annotation_in_decl_module() {
+	TestExternalities::default().execute_with(|| {
+		let origin = 0;
+		assert_ok!(<Module<Runtime>>::value_commits(origin, 2));
+		assert_eq!(Value::get(), 2);
+
+		assert_noop!(<Module<Runtime>>::value_rollbacks(origin, 3), "nah");
+	});
+}
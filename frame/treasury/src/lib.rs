@@ -32,18 +32,6 @@
 //! By way of example, the Council could vote to fund the Treasury with a portion of the block
 //! reward and use the funds to pay developers.
 //!
-//! ### Tipping
-//!
-//! A separate subsystem exists to allow for an agile "tipping" process, whereby a reward may be
-//! given without first having a pre-determined stakeholder group come to consensus on how much
-//! should be paid.
-//!
-//! A group of `Tippers` is determined through the config `Trait`. After half of these have declared
-//! some amount that they believe a particular reported reason deserves, then a countdown period is
-//! entered where any remaining members can declare their tip amounts also. After the close of the
-//! countdown period, the median of all declared tips is paid to the reported beneficiary, along
-//! with any finders fee, in case of a public (and bonded) original report.
-//!
 //! ### Terminology
 //!
 //! - **Proposal:** A suggestion to allocate funds from the pot to a beneficiary.
@@ -54,16 +42,6 @@
 //! respectively.
 //! - **Pot:** Unspent funds accumulated by the treasury module.
 //!
-//! Tipping protocol:
-//! - **Tipping:** The process of gathering declarations of amounts to tip and taking the median
-//!   amount to be transferred from the treasury to a beneficiary account.
-//! - **Tip Reason:** The reason for a tip; generally a URL which embodies or explains why a
-//!   particular individual (identified by an account ID) is worthy of a recognition by the
-//!   treasury.
-//! - **Finder:** The original public reporter of some reason for tipping.
-//! - **Finders Fee:** Some proportion of the tip amount that is paid to the reporter of the tip,
-//!   rather than the main beneficiary.
-//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -75,12 +53,10 @@
 //! - `reject_proposal` - Reject a proposal, slashing the deposit.
 //! - `approve_proposal` - Accept the proposal, returning the deposit.
 //!
-//! Tipping protocol:
-//! - `report_awesome` - Report something worthy of a tip and register for a finders fee.
-//! - `retract_tip` - Retract a previous (finders fee registered) report.
-//! - `tip_new` - Report an item worthy of a tip and declare a specific amount to tip.
-//! - `tip` - Declare or redeclare an amount to tip for a particular reason.
-//! - `close_tip` - Close and pay out a tip.
+//! ## Spending funds elsewhere
+//!
+//! Other pallets, such as `pallet-bounties`, may draw on the pot at each spend period by being
+//! configured as the `SpendFunds` hook. Tips are provided by `pallet-tips`.
 //!
 //! ## GenesisConfig
 //!
@@ -91,35 +67,30 @@
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 use sp_std::prelude::*;
-use frame_support::{decl_module, decl_storage, decl_event, ensure, print, decl_error, Parameter};
+use frame_support::{decl_module, decl_storage, decl_event, ensure, print, decl_error};
 use frame_support::traits::{
 	Currency, Get, Imbalance, OnUnbalanced, ExistenceRequirement::KeepAlive,
 	ReservableCurrency, WithdrawReason
 };
-use sp_runtime::{Permill, ModuleId, Percent, RuntimeDebug, traits::{
-	Zero, StaticLookup, AccountIdConversion, Saturating, Hash, BadOrigin
+use sp_runtime::{Permill, ModuleId, RuntimeDebug, traits::{
+	Zero, StaticLookup, AccountIdConversion, Saturating
 }};
 use frame_support::weights::{Weight, DispatchClass};
-use frame_support::traits::{Contains, ContainsLengthBound, EnsureOrigin};
+use frame_support::traits::EnsureOrigin;
 use codec::{Encode, Decode};
-use frame_system::{self as system, ensure_signed};
+use frame_system::ensure_signed;
 
 mod tests;
 mod benchmarking;
 
-type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
-type PositiveImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::PositiveImbalance;
-type NegativeImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+pub type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+pub type PositiveImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::PositiveImbalance;
+pub type NegativeImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
 
 pub trait WeightInfo {
 	fn propose_spend(u: u32, ) -> Weight;
 	fn reject_proposal(u: u32, ) -> Weight;
 	fn approve_proposal(u: u32, ) -> Weight;
-	fn report_awesome(r: u32, ) -> Weight;
-	fn retract_tip(r: u32, ) -> Weight;
-	fn tip_new(r: u32, t: u32, ) -> Weight;
-	fn tip(t: u32, ) -> Weight;
-	fn close_tip(t: u32, ) -> Weight;
 	fn on_initialize(p: u32, ) -> Weight;
 }
 
@@ -127,11 +98,6 @@ impl WeightInfo for () {
 	fn propose_spend(_u: u32, ) -> Weight { 1_000_000_000 }
 	fn reject_proposal(_u: u32, ) -> Weight { 1_000_000_000 }
 	fn approve_proposal(_u: u32, ) -> Weight { 1_000_000_000 }
-	fn report_awesome(_r: u32, ) -> Weight { 1_000_000_000 }
-	fn retract_tip(_r: u32, ) -> Weight { 1_000_000_000 }
-	fn tip_new(_r: u32, _t: u32, ) -> Weight { 1_000_000_000 }
-	fn tip(_t: u32, ) -> Weight { 1_000_000_000 }
-	fn close_tip(_t: u32, ) -> Weight { 1_000_000_000 }
 	fn on_initialize(_p: u32, ) -> Weight { 1_000_000_000 }
 }
 
@@ -148,23 +114,6 @@ pub trait Trait: frame_system::Trait {
 	/// Origin from which rejections must come.
 	type RejectOrigin: EnsureOrigin<Self::Origin>;
 
-	/// Origin from which tippers must come.
-	///
-	/// `ContainsLengthBound::max_len` must be cost free (i.e. no storage read or heavy operation).
-	type Tippers: Contains<Self::AccountId> + ContainsLengthBound;
-
-	/// The period for which a tip remains open after is has achieved threshold tippers.
-	type TipCountdown: Get<Self::BlockNumber>;
-
-	/// The percent of the final tip which goes to the original reporter of the tip.
-	type TipFindersFee: Get<Percent>;
-
-	/// The amount held on deposit for placing a tip report.
-	type TipReportDepositBase: Get<BalanceOf<Self>>;
-
-	/// The amount held on deposit per byte within the tip report reason.
-	type TipReportDepositPerByte: Get<BalanceOf<Self>>;
-
 	/// The overarching event type.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
@@ -187,10 +136,35 @@ pub trait Trait: frame_system::Trait {
 	/// Handler for the unbalanced decrease when treasury funds are burned.
 	type BurnDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+	/// Runtime hooks to external pallet using treasury to compute spend funds.
+	type SpendFunds: SpendFunds<Self>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
 
+/// A trait to allow the Treasury Pallet to spend its funds for other purposes.
+///
+/// There is an expectation that the implementer of this trait will correctly manage the mutable
+/// variables passed to it:
+/// * `budget_remaining`: How much available funds that can be spent by the treasury. As funds are
+///   spent, you must correctly deduct from this value.
+/// * `imbalance`: Any imbalances that you create should be subsumed in here to maximize efficiency
+///   of updating the total issuance (i.e. `deposit_creating`).
+/// * `total_weight`: Track any weight that your `spend_funds` implementation uses by updating this
+///   value.
+/// * `missed_any`: If there were items that you want to spend on, but there were not enough funds,
+///   mark this value as `true`. This will prevent the treasury from burning the excess funds.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait SpendFunds<T: Trait> {
+	fn spend_funds(
+		budget_remaining: &mut BalanceOf<T>,
+		imbalance: &mut PositiveImbalanceOf<T>,
+		total_weight: &mut Weight,
+		missed_any: &mut bool,
+	);
+}
+
 /// An index of a proposal. Just a `u32`.
 pub type ProposalIndex = u32;
 
@@ -208,33 +182,6 @@ pub struct Proposal<AccountId, Balance> {
 	bond: Balance,
 }
 
-/// An open tipping "motion". Retains all details of a tip including information on the finder
-/// and the members who have voted.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
-pub struct OpenTip<
-	AccountId: Parameter,
-	Balance: Parameter,
-	BlockNumber: Parameter,
-	Hash: Parameter,
-> {
-	/// The hash of the reason for the tip. The reason should be a human-readable UTF-8 encoded string. A URL would be
-	/// sensible.
-	reason: Hash,
-	/// The account to be tipped.
-	who: AccountId,
-	/// The account who began this tip.
-	finder: AccountId,
-	/// The amount held on deposit for this tip.
-	deposit: Balance,
-	/// The block number at which this tip will close if `Some`. If `None`, then no closing is
-	/// scheduled.
-	closes: Option<BlockNumber>,
-	/// The members who have voted for this tip. Sorted by AccountId.
-	tips: Vec<(AccountId, Balance)>,
-	/// Whether this tip should result in the finder taking a fee.
-	finders_fee: bool,
-}
-
 decl_storage! {
 	trait Store for Module<T: Trait> as Treasury {
 		/// Number of proposals that have been made.
@@ -247,17 +194,6 @@ decl_storage! {
 
 		/// Proposal indices that have been approved but not yet awarded.
 		Approvals get(fn approvals): Vec<ProposalIndex>;
-
-		/// Tips that are not yet completed. Keyed by the hash of `(reason, who)` from the value.
-		/// This has the insecure enumerable hash function since the key itself is already
-		/// guaranteed to be a secure hash.
-		pub Tips get(fn tips):
-			map hasher(twox_64_concat) T::Hash
-			=> Option<OpenTip<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>>;
-
-		/// Simple preimage lookup from the reason's hash to the original data. Again, has an
-		/// insecure enumerable hash since the key is guaranteed to be the result of a secure hash.
-		pub Reasons get(fn reasons): map hasher(identity) T::Hash => Option<Vec<u8>>;
 	}
 	add_extra_genesis {
 		build(|_config| {
@@ -275,7 +211,6 @@ decl_event!(
 	where
 		Balance = BalanceOf<T>,
 		<T as frame_system::Trait>::AccountId,
-		<T as frame_system::Trait>::Hash,
 	{
 		/// New proposal. [proposal_index]
 		Proposed(ProposalIndex),
@@ -291,14 +226,6 @@ decl_event!(
 		Rollover(Balance),
 		/// Some funds have been deposited. [deposit]
 		Deposit(Balance),
-		/// A new tip suggestion has been opened. [tip_hash]
-		NewTip(Hash),
-		/// A tip suggestion has reached threshold and is closing. [tip_hash]
-		TipClosing(Hash),
-		/// A tip suggestion has been closed. [tip_hash, who, payout]
-		TipClosed(Hash, AccountId, Balance),
-		/// A tip suggestion has been retracted. [tip_hash]
-		TipRetracted(Hash),
 	}
 );
 
@@ -309,18 +236,6 @@ decl_error! {
 		InsufficientProposersBalance,
 		/// No proposal at that index.
 		InvalidProposalIndex,
-		/// The reason given is just too big.
-		ReasonTooBig,
-		/// The tip was already found/started.
-		AlreadyKnown,
-		/// The tip hash is unknown.
-		UnknownTip,
-		/// The account attempting to retract the tip is not the finder of the tip.
-		NotFinder,
-		/// The tip cannot be claimed/closed because there are not enough tippers yet.
-		StillOpen,
-		/// The tip cannot be claimed/closed because it's still in the countdown period.
-		Premature,
 	}
 }
 
@@ -339,18 +254,6 @@ decl_module! {
 		/// Percentage of spare funds (if any) that are burnt per spend period.
 		const Burn: Permill = T::Burn::get();
 
-		/// The period for which a tip remains open after is has achieved threshold tippers.
-		const TipCountdown: T::BlockNumber = T::TipCountdown::get();
-
-		/// The amount of the final tip which goes to the original reporter of the tip.
-		const TipFindersFee: Percent = T::TipFindersFee::get();
-
-		/// The amount held on deposit for placing a tip report.
-		const TipReportDepositBase: BalanceOf<T> = T::TipReportDepositBase::get();
-
-		/// The amount held on deposit per byte within the tip report reason.
-		const TipReportDepositPerByte: BalanceOf<T> = T::TipReportDepositPerByte::get();
-
 		/// The treasury's module id, used for deriving its sovereign account ID.
 		const ModuleId: ModuleId = T::ModuleId::get();
 
@@ -426,219 +329,18 @@ decl_module! {
 			Approvals::mutate(|v| v.push(proposal_id));
 		}
 
-		/// Report something `reason` that deserves a tip and claim any eventual the finder's fee.
-		///
-		/// The dispatch origin for this call must be _Signed_.
-		///
-		/// Payment: `TipReportDepositBase` will be reserved from the origin account, as well as
-		/// `TipReportDepositPerByte` for each byte in `reason`.
-		///
-		/// - `reason`: The reason for, or the thing that deserves, the tip; generally this will be
-		///   a UTF-8-encoded URL.
-		/// - `who`: The account which should be credited for the tip.
-		///
-		/// Emits `NewTip` if successful.
-		///
-		/// # <weight>
-		/// - Complexity: `O(R)` where `R` length of `reason`.
-		///   - encoding and hashing of 'reason'
-		/// - DbReads: `Reasons`, `Tips`, `who account data`
-		/// - DbWrites: `Tips`, `who account data`
-		/// # </weight>
-		#[weight = 140_000_000 + 4_000 * reason.len() as Weight + T::DbWeight::get().reads_writes(3, 2)]
-		fn report_awesome(origin, reason: Vec<u8>, who: T::AccountId) {
-			let finder = ensure_signed(origin)?;
-
-			const MAX_SENSIBLE_REASON_LENGTH: usize = 16384;
-			ensure!(reason.len() <= MAX_SENSIBLE_REASON_LENGTH, Error::<T>::ReasonTooBig);
-
-			let reason_hash = T::Hashing::hash(&reason[..]);
-			ensure!(!Reasons::<T>::contains_key(&reason_hash), Error::<T>::AlreadyKnown);
-			let hash = T::Hashing::hash_of(&(&reason_hash, &who));
-			ensure!(!Tips::<T>::contains_key(&hash), Error::<T>::AlreadyKnown);
-
-			let deposit = T::TipReportDepositBase::get()
-				+ T::TipReportDepositPerByte::get() * (reason.len() as u32).into();
-			T::Currency::reserve(&finder, deposit)?;
-
-			Reasons::<T>::insert(&reason_hash, &reason);
-			let tip = OpenTip {
-				reason: reason_hash,
-				who,
-				finder,
-				deposit,
-				closes: None,
-				tips: vec![],
-				finders_fee: true
-			};
-			Tips::<T>::insert(&hash, tip);
-			Self::deposit_event(RawEvent::NewTip(hash));
-		}
-
-		/// Retract a prior tip-report from `report_awesome`, and cancel the process of tipping.
-		///
-		/// If successful, the original deposit will be unreserved.
-		///
-		/// The dispatch origin for this call must be _Signed_ and the tip identified by `hash`
-		/// must have been reported by the signing account through `report_awesome` (and not
-		/// through `tip_new`).
-		///
-		/// - `hash`: The identity of the open tip for which a tip value is declared. This is formed
-		///   as the hash of the tuple of the original tip `reason` and the beneficiary account ID.
-		///
-		/// Emits `TipRetracted` if successful.
-		///
-		/// # <weight>
-		/// - Complexity: `O(1)`
-		///   - Depends on the length of `T::Hash` which is fixed.
-		/// - DbReads: `Tips`, `origin account`
-		/// - DbWrites: `Reasons`, `Tips`, `origin account`
-		/// # </weight>
-		#[weight = 120_000_000 + T::DbWeight::get().reads_writes(1, 2)]
-		fn retract_tip(origin, hash: T::Hash) {
-			let who = ensure_signed(origin)?;
-			let tip = Tips::<T>::get(&hash).ok_or(Error::<T>::UnknownTip)?;
-			ensure!(tip.finder == who, Error::<T>::NotFinder);
-
-			Reasons::<T>::remove(&tip.reason);
-			Tips::<T>::remove(&hash);
-			if !tip.deposit.is_zero() {
-				let _ = T::Currency::unreserve(&who, tip.deposit);
-			}
-			Self::deposit_event(RawEvent::TipRetracted(hash));
-		}
-
-		/// Give a tip for something new; no finder's fee will be taken.
-		///
-		/// The dispatch origin for this call must be _Signed_ and the signing account must be a
-		/// member of the `Tippers` set.
-		///
-		/// - `reason`: The reason for, or the thing that deserves, the tip; generally this will be
-		///   a UTF-8-encoded URL.
-		/// - `who`: The account which should be credited for the tip.
-		/// - `tip_value`: The amount of tip that the sender would like to give. The median tip
-		///   value of active tippers will be given to the `who`.
-		///
-		/// Emits `NewTip` if successful.
-		///
-		/// # <weight>
-		/// - Complexity: `O(R + T)` where `R` length of `reason`, `T` is the number of tippers.
-		///   - `O(T)`: decoding `Tipper` vec of length `T`
-		///     `T` is charged as upper bound given by `ContainsLengthBound`.
-		///     The actual cost depends on the implementation of `T::Tippers`.
-		///   - `O(R)`: hashing and encoding of reason of length `R`
-		/// - DbReads: `Tippers`, `Reasons`
-		/// - DbWrites: `Reasons`, `Tips`
-		/// # </weight>
-		#[weight = 110_000_000
-			+ 4_000 * reason.len() as Weight
-			+ 480_000 * T::Tippers::max_len() as Weight
-			+ T::DbWeight::get().reads_writes(2, 2)]
-		fn tip_new(origin, reason: Vec<u8>, who: T::AccountId, tip_value: BalanceOf<T>) {
-			let tipper = ensure_signed(origin)?;
-			ensure!(T::Tippers::contains(&tipper), BadOrigin);
-			let reason_hash = T::Hashing::hash(&reason[..]);
-			ensure!(!Reasons::<T>::contains_key(&reason_hash), Error::<T>::AlreadyKnown);
-			let hash = T::Hashing::hash_of(&(&reason_hash, &who));
-
-			Reasons::<T>::insert(&reason_hash, &reason);
-			Self::deposit_event(RawEvent::NewTip(hash.clone()));
-			let tips = vec![(tipper.clone(), tip_value)];
-			let tip = OpenTip {
-				reason: reason_hash,
-				who,
-				finder: tipper,
-				deposit: Zero::zero(),
-				closes: None,
-				tips,
-				finders_fee: false,
-			};
-			Tips::<T>::insert(&hash, tip);
-		}
-
-		/// Declare a tip value for an already-open tip.
-		///
-		/// The dispatch origin for this call must be _Signed_ and the signing account must be a
-		/// member of the `Tippers` set.
-		///
-		/// - `hash`: The identity of the open tip for which a tip value is declared. This is formed
-		///   as the hash of the tuple of the hash of the original tip `reason` and the beneficiary
-		///   account ID.
-		/// - `tip_value`: The amount of tip that the sender would like to give. The median tip
-		///   value of active tippers will be given to the `who`.
-		///
-		/// Emits `TipClosing` if the threshold of tippers has been reached and the countdown period
-		/// has started.
-		///
-		/// # <weight>
-		/// - Complexity: `O(T)` where `T` is the number of tippers.
-		///   decoding `Tipper` vec of length `T`, insert tip and check closing,
-		///   `T` is charged as upper bound given by `ContainsLengthBound`.
-		///   The actual cost depends on the implementation of `T::Tippers`.
-		///
-		///   Actually weight could be lower as it depends on how many tips are in `OpenTip` but it
-		///   is weighted as if almost full i.e of length `T-1`.
-		/// - DbReads: `Tippers`, `Tips`
-		/// - DbWrites: `Tips`
-		/// # </weight>
-		#[weight = 68_000_000 + 2_000_000 * T::Tippers::max_len() as Weight
-			+ T::DbWeight::get().reads_writes(2, 1)]
-		fn tip(origin, hash: T::Hash, tip_value: BalanceOf<T>) {
-			let tipper = ensure_signed(origin)?;
-			ensure!(T::Tippers::contains(&tipper), BadOrigin);
-
-			let mut tip = Tips::<T>::get(hash).ok_or(Error::<T>::UnknownTip)?;
-			if Self::insert_tip_and_check_closing(&mut tip, tipper, tip_value) {
-				Self::deposit_event(RawEvent::TipClosing(hash.clone()));
-			}
-			Tips::<T>::insert(&hash, tip);
-		}
-
-		/// Close and payout a tip.
-		///
-		/// The dispatch origin for this call must be _Signed_.
-		///
-		/// The tip identified by `hash` must have finished its countdown period.
-		///
-		/// - `hash`: The identity of the open tip for which a tip value is declared. This is formed
-		///   as the hash of the tuple of the original tip `reason` and the beneficiary account ID.
-		///
-		/// # <weight>
-		/// - Complexity: `O(T)` where `T` is the number of tippers.
-		///   decoding `Tipper` vec of length `T`.
-		///   `T` is charged as upper bound given by `ContainsLengthBound`.
-		///   The actual cost depends on the implementation of `T::Tippers`.
-		/// - DbReads: `Tips`, `Tippers`, `tip finder`
-		/// - DbWrites: `Reasons`, `Tips`, `Tippers`, `tip finder`
-		/// # </weight>
-		#[weight = 220_000_000 + 1_100_000 * T::Tippers::max_len() as Weight
-			+ T::DbWeight::get().reads_writes(3, 3)]
-		fn close_tip(origin, hash: T::Hash) {
-			ensure_signed(origin)?;
-
-			let tip = Tips::<T>::get(hash).ok_or(Error::<T>::UnknownTip)?;
-			let n = tip.closes.as_ref().ok_or(Error::<T>::StillOpen)?;
-			ensure!(system::Module::<T>::block_number() >= *n, Error::<T>::Premature);
-			// closed.
-			Reasons::<T>::remove(&tip.reason);
-			Tips::<T>::remove(hash);
-			Self::payout_tip(hash, tip);
-		}
-
 		/// # <weight>
 		/// - Complexity: `O(A)` where `A` is the number of approvals
 		/// - Db reads and writes: `Approvals`, `pot account data`
 		/// - Db reads and writes per approval:
 		///   `Proposals`, `proposer account data`, `beneficiary account data`
 		/// - The weight is overestimated if some approvals got missed.
+		/// - Plus the weight reported by the `SpendFunds` hook.
 		/// # </weight>
 		fn on_initialize(n: T::BlockNumber) -> Weight {
 			// Check to see if we should spend some funds!
 			if (n % T::SpendPeriod::get()).is_zero() {
-				let approvals_len = Self::spend_funds();
-
-				270_000_000 * approvals_len
-					+ T::DbWeight::get().reads_writes(2 + approvals_len * 3, 2 + approvals_len * 3)
+				Self::spend_funds()
 			} else {
 				0
 			}
@@ -662,81 +364,10 @@ impl<T: Trait> Module<T> {
 		T::ProposalBondMinimum::get().max(T::ProposalBond::get() * value)
 	}
 
-	/// Given a mutable reference to an `OpenTip`, insert the tip into it and check whether it
-	/// closes, if so, then deposit the relevant event and set closing accordingly.
-	///
-	/// `O(T)` and one storage access.
-	fn insert_tip_and_check_closing(
-		tip: &mut OpenTip<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>,
-		tipper: T::AccountId,
-		tip_value: BalanceOf<T>,
-	) -> bool {
-		match tip.tips.binary_search_by_key(&&tipper, |x| &x.0) {
-			Ok(pos) => tip.tips[pos] = (tipper, tip_value),
-			Err(pos) => tip.tips.insert(pos, (tipper, tip_value)),
-		}
-		Self::retain_active_tips(&mut tip.tips);
-		let threshold = (T::Tippers::count() + 1) / 2;
-		if tip.tips.len() >= threshold && tip.closes.is_none() {
-			tip.closes = Some(system::Module::<T>::block_number() + T::TipCountdown::get());
-			true
-		} else {
-			false
-		}
-	}
-
-	/// Remove any non-members of `Tippers` from a `tips` vector. `O(T)`.
-	fn retain_active_tips(tips: &mut Vec<(T::AccountId, BalanceOf<T>)>) {
-		let members = T::Tippers::sorted_members();
-		let mut members_iter = members.iter();
-		let mut member = members_iter.next();
-		tips.retain(|(ref a, _)| loop {
-			match member {
-				None => break false,
-				Some(m) if m > a => break false,
-				Some(m) => {
-					member = members_iter.next();
-					if m < a {
-						continue
-					} else {
-						break true;
-					}
-				}
-			}
-		});
-	}
+	/// Spend some money! returns the weight consumed by the spend.
+	pub fn spend_funds() -> Weight {
+		let mut total_weight: Weight = Zero::zero();
 
-	/// Execute the payout of a tip.
-	///
-	/// Up to three balance operations.
-	/// Plus `O(T)` (`T` is Tippers length).
-	fn payout_tip(hash: T::Hash, tip: OpenTip<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>) {
-		let mut tips = tip.tips;
-		Self::retain_active_tips(&mut tips);
-		tips.sort_by_key(|i| i.1);
-		let treasury = Self::account_id();
-		let max_payout = Self::pot();
-		let mut payout = tips[tips.len() / 2].1.min(max_payout);
-		if !tip.deposit.is_zero() {
-			let _ = T::Currency::unreserve(&tip.finder, tip.deposit);
-		}
-		if tip.finders_fee {
-			if tip.finder != tip.who {
-				// pay out the finder's fee.
-				let finders_fee = T::TipFindersFee::get() * payout;
-				payout -= finders_fee;
-				// this should go through given we checked it's at most the free balance, but still
-				// we only make a best-effort.
-				let _ = T::Currency::transfer(&treasury, &tip.finder, finders_fee, KeepAlive);
-			}
-		}
-		// same as above: best-effort only.
-		let _ = T::Currency::transfer(&treasury, &tip.who, payout, KeepAlive);
-		Self::deposit_event(RawEvent::TipClosed(hash, tip.who, payout));
-	}
-
-	/// Spend some money! returns number of approvals before spend.
-	fn spend_funds() -> u64 {
 		let mut budget_remaining = Self::pot();
 		Self::deposit_event(RawEvent::Spending(budget_remaining));
 
@@ -770,6 +401,17 @@ impl<T: Trait> Module<T> {
 			prior_approvals_len
 		});
 
+		total_weight += 270_000_000 * prior_approvals_len
+			+ T::DbWeight::get().reads_writes(2 + prior_approvals_len * 3, 2 + prior_approvals_len * 3);
+
+		// Call Runtime hooks to external pallet using treasury to compute spend funds.
+		T::SpendFunds::spend_funds(
+			&mut budget_remaining,
+			&mut imbalance,
+			&mut total_weight,
+			&mut missed_any,
+		);
+
 		if !missed_any {
 			// burn some proportion of the remaining budget if we run a surplus.
 			let burn = (T::Burn::get() * budget_remaining).min(budget_remaining);
@@ -798,65 +440,16 @@ impl<T: Trait> Module<T> {
 
 		Self::deposit_event(RawEvent::Rollover(budget_remaining));
 
-		prior_approvals_len
+		total_weight
 	}
 
 	/// Return the amount of money in the pot.
 	// The existential deposit is not part of the pot so treasury account never gets deleted.
-	fn pot() -> BalanceOf<T> {
+	pub fn pot() -> BalanceOf<T> {
 		T::Currency::free_balance(&Self::account_id())
 			// Must never be less than 0 but better be safe.
 			.saturating_sub(T::Currency::minimum_balance())
 	}
-
-	pub fn migrate_retract_tip_for_tip_new() {
-		/// An open tipping "motion". Retains all details of a tip including information on the finder
-		/// and the members who have voted.
-		#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
-		pub struct OldOpenTip<
-			AccountId: Parameter,
-			Balance: Parameter,
-			BlockNumber: Parameter,
-			Hash: Parameter,
-		> {
-			/// The hash of the reason for the tip. The reason should be a human-readable UTF-8 encoded string. A URL would be
-			/// sensible.
-			reason: Hash,
-			/// The account to be tipped.
-			who: AccountId,
-			/// The account who began this tip and the amount held on deposit.
-			finder: Option<(AccountId, Balance)>,
-			/// The block number at which this tip will close if `Some`. If `None`, then no closing is
-			/// scheduled.
-			closes: Option<BlockNumber>,
-			/// The members who have voted for this tip. Sorted by AccountId.
-			tips: Vec<(AccountId, Balance)>,
-		}
-
-		use frame_support::{Twox64Concat, migration::StorageKeyIterator};
-
-		for (hash, old_tip) in StorageKeyIterator::<
-			T::Hash,
-			OldOpenTip<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>,
-			Twox64Concat,
-		>::new(b"Treasury", b"Tips").drain()
-		{
-			let (finder, deposit, finders_fee) = match old_tip.finder {
-				Some((finder, deposit)) => (finder, deposit, true),
-				None => (T::AccountId::default(), Zero::zero(), false),
-			};
-			let new_tip = OpenTip {
-				reason: old_tip.reason,
-				who: old_tip.who,
-				finder,
-				deposit,
-				closes: old_tip.closes,
-				tips: old_tip.tips,
-				finders_fee
-			};
-			Tips::<T>::insert(hash, new_tip)
-		}
-	}
 }
 
 impl<T: Trait> OnUnbalanced<NegativeImbalanceOf<T>> for Module<T> {
@@ -20,16 +20,15 @@
 #![cfg(test)]
 
 use super::*;
-use std::cell::RefCell;
 use frame_support::{
 	assert_noop, assert_ok, impl_outer_origin, impl_outer_event, parameter_types, weights::Weight,
-	traits::{Contains, OnInitialize}
+	traits::OnInitialize,
 };
 use sp_core::H256;
 use sp_runtime::{
 	Perbill, ModuleId,
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup, BadOrigin},
+	traits::{BlakeTwo256, IdentityLookup},
 };
 
 impl_outer_origin! {
@@ -97,40 +96,11 @@ impl pallet_balances::Trait for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 }
-thread_local! {
-	static TEN_TO_FOURTEEN: RefCell<Vec<u64>> = RefCell::new(vec![10,11,12,13,14]);
-}
-pub struct TenToFourteen;
-impl Contains<u64> for TenToFourteen {
-	fn sorted_members() -> Vec<u64> {
-		TEN_TO_FOURTEEN.with(|v| {
-			v.borrow().clone()
-		})
-	}
-	#[cfg(feature = "runtime-benchmarks")]
-	fn add(new: &u64) {
-		TEN_TO_FOURTEEN.with(|v| {
-			let mut members = v.borrow_mut();
-			members.push(*new);
-			members.sort();
-		})
-	}
-}
-impl ContainsLengthBound for TenToFourteen {
-	fn max_len() -> usize {
-		TEN_TO_FOURTEEN.with(|v| v.borrow().len())
-	}
-	fn min_len() -> usize { 0 }
-}
 parameter_types! {
 	pub const ProposalBond: Permill = Permill::from_percent(5);
 	pub const ProposalBondMinimum: u64 = 1;
 	pub const SpendPeriod: u64 = 2;
 	pub const Burn: Permill = Permill::from_percent(50);
-	pub const TipCountdown: u64 = 1;
-	pub const TipFindersFee: Percent = Percent::from_percent(20);
-	pub const TipReportDepositBase: u64 = 1;
-	pub const TipReportDepositPerByte: u64 = 1;
 	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
 }
 impl Trait for Test {
@@ -138,11 +108,6 @@ impl Trait for Test {
 	type Currency = pallet_balances::Module<Test>;
 	type ApproveOrigin = frame_system::EnsureRoot<u64>;
 	type RejectOrigin = frame_system::EnsureRoot<u64>;
-	type Tippers = TenToFourteen;
-	type TipCountdown = TipCountdown;
-	type TipFindersFee = TipFindersFee;
-	type TipReportDepositBase = TipReportDepositBase;
-	type TipReportDepositPerByte = TipReportDepositPerByte;
 	type Event = Event;
 	type ProposalRejection = ();
 	type ProposalBond = ProposalBond;
@@ -150,6 +115,7 @@ impl Trait for Test {
 	type SpendPeriod = SpendPeriod;
 	type Burn = Burn;
 	type BurnDestination = ();  // Just gets burned.
+	type SpendFunds = ();
 	type WeightInfo = ();
 }
 type System = frame_system::Module<Test>;
@@ -174,187 +140,6 @@ fn genesis_config_works() {
 	});
 }
 
-fn tip_hash() -> H256 {
-	BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 3u64))
-}
-
-#[test]
-fn tip_new_cannot_be_used_twice() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_ok!(Treasury::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
-		assert_noop!(
-			Treasury::tip_new(Origin::signed(11), b"awesome.dot".to_vec(), 3, 10),
-			Error::<Test>::AlreadyKnown
-		);
-	});
-}
-
-#[test]
-fn report_awesome_and_tip_works() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_ok!(Treasury::report_awesome(Origin::signed(0), b"awesome.dot".to_vec(), 3));
-		assert_eq!(Balances::reserved_balance(0), 12);
-		assert_eq!(Balances::free_balance(0), 88);
-
-		// other reports don't count.
-		assert_noop!(
-			Treasury::report_awesome(Origin::signed(1), b"awesome.dot".to_vec(), 3),
-			Error::<Test>::AlreadyKnown
-		);
-
-		let h = tip_hash();
-		assert_ok!(Treasury::tip(Origin::signed(10), h.clone(), 10));
-		assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 10));
-		assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 10));
-		assert_noop!(Treasury::tip(Origin::signed(9), h.clone(), 10), BadOrigin);
-		System::set_block_number(2);
-		assert_ok!(Treasury::close_tip(Origin::signed(100), h.into()));
-		assert_eq!(Balances::reserved_balance(0), 0);
-		assert_eq!(Balances::free_balance(0), 102);
-		assert_eq!(Balances::free_balance(3), 8);
-	});
-}
-
-#[test]
-fn report_awesome_from_beneficiary_and_tip_works() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_ok!(Treasury::report_awesome(Origin::signed(0), b"awesome.dot".to_vec(), 0));
-		assert_eq!(Balances::reserved_balance(0), 12);
-		assert_eq!(Balances::free_balance(0), 88);
-		let h = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u64));
-		assert_ok!(Treasury::tip(Origin::signed(10), h.clone(), 10));
-		assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 10));
-		assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 10));
-		System::set_block_number(2);
-		assert_ok!(Treasury::close_tip(Origin::signed(100), h.into()));
-		assert_eq!(Balances::reserved_balance(0), 0);
-		assert_eq!(Balances::free_balance(0), 110);
-	});
-}
-
-#[test]
-fn close_tip_works() {
-	new_test_ext().execute_with(|| {
-		System::set_block_number(1);
-
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_eq!(Treasury::pot(), 100);
-
-		assert_ok!(Treasury::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
-
-		let h = tip_hash();
-
-		assert_eq!(
-			System::events().into_iter().map(|r| r.event)
-				.filter_map(|e| {
-					if let Event::treasury(inner) = e { Some(inner) } else { None }
-				})
-				.last()
-				.unwrap(),
-			RawEvent::NewTip(h),
-		);
-
-		assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 10));
-
-		assert_noop!(Treasury::close_tip(Origin::signed(0), h.into()), Error::<Test>::StillOpen);
-
-		assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 10));
-
-		assert_eq!(
-			System::events().into_iter().map(|r| r.event)
-				.filter_map(|e| {
-					if let Event::treasury(inner) = e { Some(inner) } else { None }
-				})
-				.last()
-				.unwrap(),
-			RawEvent::TipClosing(h),
-		);
-
-		assert_noop!(Treasury::close_tip(Origin::signed(0), h.into()), Error::<Test>::Premature);
-
-		System::set_block_number(2);
-		assert_noop!(Treasury::close_tip(Origin::none(), h.into()), BadOrigin);
-		assert_ok!(Treasury::close_tip(Origin::signed(0), h.into()));
-		assert_eq!(Balances::free_balance(3), 10);
-
-		assert_eq!(
-			System::events().into_iter().map(|r| r.event)
-				.filter_map(|e| {
-					if let Event::treasury(inner) = e { Some(inner) } else { None }
-				})
-				.last()
-				.unwrap(),
-			RawEvent::TipClosed(h, 3, 10),
-		);
-
-		assert_noop!(Treasury::close_tip(Origin::signed(100), h.into()), Error::<Test>::UnknownTip);
-	});
-}
-
-#[test]
-fn retract_tip_works() {
-	new_test_ext().execute_with(|| {
-		// with report awesome
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_ok!(Treasury::report_awesome(Origin::signed(0), b"awesome.dot".to_vec(), 3));
-		let h = tip_hash();
-		assert_ok!(Treasury::tip(Origin::signed(10), h.clone(), 10));
-		assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 10));
-		assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 10));
-		assert_noop!(Treasury::retract_tip(Origin::signed(10), h.clone()), Error::<Test>::NotFinder);
-		assert_ok!(Treasury::retract_tip(Origin::signed(0), h.clone()));
-		System::set_block_number(2);
-		assert_noop!(Treasury::close_tip(Origin::signed(0), h.into()), Error::<Test>::UnknownTip);
-
-		// with tip new
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_ok!(Treasury::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
-		let h = tip_hash();
-		assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 10));
-		assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 10));
-		assert_noop!(Treasury::retract_tip(Origin::signed(0), h.clone()), Error::<Test>::NotFinder);
-		assert_ok!(Treasury::retract_tip(Origin::signed(10), h.clone()));
-		System::set_block_number(2);
-		assert_noop!(Treasury::close_tip(Origin::signed(10), h.into()), Error::<Test>::UnknownTip);
-	});
-}
-
-#[test]
-fn tip_median_calculation_works() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_ok!(Treasury::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 0));
-		let h = tip_hash();
-		assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 10));
-		assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 1000000));
-		System::set_block_number(2);
-		assert_ok!(Treasury::close_tip(Origin::signed(0), h.into()));
-		assert_eq!(Balances::free_balance(3), 10);
-	});
-}
-
-#[test]
-fn tip_changing_works() {
-	new_test_ext().execute_with(|| {
-		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_ok!(Treasury::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10000));
-		let h = tip_hash();
-		assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 10000));
-		assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 10000));
-		assert_ok!(Treasury::tip(Origin::signed(13), h.clone(), 0));
-		assert_ok!(Treasury::tip(Origin::signed(14), h.clone(), 0));
-		assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 1000));
-		assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 100));
-		assert_ok!(Treasury::tip(Origin::signed(10), h.clone(), 10));
-		System::set_block_number(2);
-		assert_ok!(Treasury::close_tip(Origin::signed(0), h.into()));
-		assert_eq!(Balances::free_balance(3), 10);
-	});
-}
-
 #[test]
 fn minting_works() {
 	new_test_ext().execute_with(|| {
@@ -560,97 +345,3 @@ fn inexistent_account_works() {
 		assert_eq!(Balances::free_balance(3), 99); // Balance of `3` has changed
 	});
 }
-
-#[test]
-fn test_last_reward_migration() {
-	use sp_storage::Storage;
-
-	let mut s = Storage::default();
-
-	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
-	pub struct OldOpenTip<
-		AccountId: Parameter,
-		Balance: Parameter,
-		BlockNumber: Parameter,
-		Hash: Parameter,
-	> {
-		/// The hash of the reason for the tip. The reason should be a human-readable UTF-8 encoded string. A URL would be
-		/// sensible.
-		reason: Hash,
-		/// The account to be tipped.
-		who: AccountId,
-		/// The account who began this tip and the amount held on deposit.
-		finder: Option<(AccountId, Balance)>,
-		/// The block number at which this tip will close if `Some`. If `None`, then no closing is
-		/// scheduled.
-		closes: Option<BlockNumber>,
-		/// The members who have voted for this tip. Sorted by AccountId.
-		tips: Vec<(AccountId, Balance)>,
-	}
-
-	let reason1 = BlakeTwo256::hash(b"reason1");
-	let hash1 = BlakeTwo256::hash_of(&(reason1, 10u64));
-
-	let old_tip_finder = OldOpenTip::<u64, u64, u64, H256> {
-		reason: reason1,
-		who: 10,
-		finder: Some((20, 30)),
-		closes: Some(13),
-		tips: vec![(40, 50), (60, 70)]
-	};
-
-	let reason2 = BlakeTwo256::hash(b"reason2");
-	let hash2 = BlakeTwo256::hash_of(&(reason2, 20u64));
-
-	let old_tip_no_finder = OldOpenTip::<u64, u64, u64, H256> {
-		reason: reason2,
-		who: 20,
-		finder: None,
-		closes: Some(13),
-		tips: vec![(40, 50), (60, 70)]
-	};
-
-	let data = vec![
-		(
-			Tips::<Test>::hashed_key_for(hash1),
-			old_tip_finder.encode().to_vec()
-		),
-		(
-			Tips::<Test>::hashed_key_for(hash2),
-			old_tip_no_finder.encode().to_vec()
-		),
-	];
-
-	s.top = data.into_iter().collect();
-	sp_io::TestExternalities::new(s).execute_with(|| {
-		Treasury::migrate_retract_tip_for_tip_new();
-
-		// Test w/ finder
-		assert_eq!(
-			Tips::<Test>::get(hash1),
-			Some(OpenTip {
-				reason: reason1,
-				who: 10,
-				finder: 20,
-				deposit: 30,
-				closes: Some(13),
-				tips: vec![(40, 50), (60, 70)],
-				finders_fee: true,
-			})
-		);
-
-		// Test w/o finder
-		assert_eq!(
-			Tips::<Test>::get(hash2),
-			Some(OpenTip {
-				reason: reason2,
-				who: 20,
-				finder: Default::default(),
-				deposit: 0,
-				closes: Some(13),
-				tips: vec![(40, 50), (60, 70)],
-				finders_fee: false,
-			})
-		);
-	});
-}
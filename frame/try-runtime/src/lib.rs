@@ -0,0 +1,38 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for checking storage migrations.
+//!
+//! This API should be implemented by a runtime built with the `try-runtime` feature. An off-chain
+//! tool can then call it on top of a snapshot of real chain state, to check that the runtime
+//! upgrade and all its `pre_upgrade` and `post_upgrade` hooks succeed before the runtime is
+//! deployed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::weights::Weight;
+
+sp_api::decl_runtime_apis! {
+	/// Runtime api for testing the execution of a runtime upgrade.
+	pub trait TryRuntime {
+		/// Dry run the runtime upgrade, including its pre- and post-upgrade checks.
+		///
+		/// Returns the consumed weight of the upgrade and the maximum weight of a block, or the
+		/// error of the first check that failed.
+		fn on_runtime_upgrade() -> Result<(Weight, Weight), sp_runtime::RuntimeString>;
+	}
+}
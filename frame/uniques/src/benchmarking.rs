@@ -0,0 +1,284 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Uniques pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use sp_std::prelude::*;
+use sp_runtime::traits::Bounded;
+use frame_system::RawOrigin as SystemOrigin;
+use frame_benchmarking::{benchmarks, account};
+use frame_support::traits::UnfilteredDispatchable;
+
+use crate::Module as Uniques;
+
+const SEED: u32 = 0;
+
+fn create_class<T: Trait>() -> (T::ClassId, T::AccountId, <T::Lookup as StaticLookup>::Source) {
+	let caller: T::AccountId = account("caller", 0, SEED);
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	let class = Default::default();
+	T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T>::max_value());
+	assert!(Uniques::<T>::create(
+		SystemOrigin::Signed(caller.clone()).into(),
+		class,
+		caller_lookup.clone(),
+	).is_ok());
+	(class, caller, caller_lookup)
+}
+
+fn mint_instance<T: Trait>(index: u32)
+	-> (T::InstanceId, T::AccountId, <T::Lookup as StaticLookup>::Source)
+{
+	let caller = Class::<T>::get(T::ClassId::default()).unwrap().admin;
+	let caller_lookup = T::Lookup::unlookup(caller.clone());
+	let instance = index.into();
+	assert!(Uniques::<T>::mint(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Default::default(),
+		instance,
+		caller_lookup.clone(),
+	).is_ok());
+	(instance, caller, caller_lookup)
+}
+
+fn add_instance_attribute<T: Trait>(instance: T::InstanceId) -> Vec<u8> {
+	let caller = Class::<T>::get(T::ClassId::default()).unwrap().owner;
+	let key = vec![0u8; T::KeyLimit::get() as usize];
+	assert!(Uniques::<T>::set_attribute(
+		SystemOrigin::Signed(caller).into(),
+		Default::default(),
+		Some(instance),
+		key.clone(),
+		vec![0; T::ValueLimit::get() as usize],
+	).is_ok());
+	key
+}
+
+fn add_class_attribute<T: Trait>(index: u32) {
+	let caller = Class::<T>::get(T::ClassId::default()).unwrap().owner;
+	assert!(Uniques::<T>::set_attribute(
+		SystemOrigin::Signed(caller).into(),
+		Default::default(),
+		None,
+		index.encode(),
+		vec![0; T::ValueLimit::get() as usize],
+	).is_ok());
+}
+
+benchmarks! {
+	_ { }
+
+	create {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T>::max_value());
+	}: _(SystemOrigin::Signed(caller.clone()), Default::default(), caller_lookup)
+	verify {
+		assert!(Class::<T>::contains_key(T::ClassId::default()));
+	}
+
+	force_create {
+		let origin = T::ForceOrigin::successful_origin();
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let call = Call::<T>::force_create(Default::default(), caller_lookup, true);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Class::<T>::contains_key(T::ClassId::default()));
+	}
+
+	destroy {
+		let n in 0 .. 1_000;
+		let a in 0 .. 1_000;
+
+		let (class, caller, _) = create_class::<T>();
+		for i in 0..n {
+			mint_instance::<T>(i);
+		}
+		for i in 0..a {
+			add_class_attribute::<T>(i);
+		}
+		let witness = DestroyWitness { instances: n, attributes: a };
+	}: _(SystemOrigin::Signed(caller), class, witness)
+	verify {
+		assert!(!Class::<T>::contains_key(class));
+	}
+
+	mint {
+		let (class, caller, caller_lookup) = create_class::<T>();
+		let instance = Default::default();
+	}: _(SystemOrigin::Signed(caller.clone()), class, instance, caller_lookup)
+	verify {
+		assert_eq!(Uniques::<T>::owner(class, instance), Some(caller));
+	}
+
+	burn {
+		let (class, caller, caller_lookup) = create_class::<T>();
+		let (instance, ..) = mint_instance::<T>(0);
+	}: _(SystemOrigin::Signed(caller.clone()), class, instance, Some(caller_lookup))
+	verify {
+		assert!(!Asset::<T>::contains_key(class, instance));
+	}
+
+	transfer {
+		let (class, caller, _) = create_class::<T>();
+		let (instance, ..) = mint_instance::<T>(0);
+
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), class, instance, target_lookup)
+	verify {
+		assert_eq!(Uniques::<T>::owner(class, instance), Some(target));
+	}
+
+	freeze {
+		let (class, caller, _) = create_class::<T>();
+		let (instance, ..) = mint_instance::<T>(0);
+	}: _(SystemOrigin::Signed(caller.clone()), class, instance)
+	verify {
+		assert!(Asset::<T>::get(class, instance).unwrap().is_frozen);
+	}
+
+	thaw {
+		let (class, caller, _) = create_class::<T>();
+		let (instance, ..) = mint_instance::<T>(0);
+		assert!(Uniques::<T>::freeze(
+			SystemOrigin::Signed(caller.clone()).into(),
+			class,
+			instance,
+		).is_ok());
+	}: _(SystemOrigin::Signed(caller.clone()), class, instance)
+	verify {
+		assert!(!Asset::<T>::get(class, instance).unwrap().is_frozen);
+	}
+
+	freeze_class {
+		let (class, caller, _) = create_class::<T>();
+	}: _(SystemOrigin::Signed(caller.clone()), class)
+	verify {
+		assert!(Class::<T>::get(class).unwrap().is_frozen);
+	}
+
+	thaw_class {
+		let (class, caller, _) = create_class::<T>();
+		assert!(Uniques::<T>::freeze_class(SystemOrigin::Signed(caller.clone()).into(), class).is_ok());
+	}: _(SystemOrigin::Signed(caller.clone()), class)
+	verify {
+		assert!(!Class::<T>::get(class).unwrap().is_frozen);
+	}
+
+	transfer_ownership {
+		let (class, caller, _) = create_class::<T>();
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		T::Currency::make_free_balance_be(&target, DepositBalanceOf::<T>::max_value());
+	}: _(SystemOrigin::Signed(caller), class, target_lookup)
+	verify {
+		assert_eq!(Uniques::<T>::class_owner(class), Some(target));
+	}
+
+	set_team {
+		let (class, caller, _) = create_class::<T>();
+		let target0 = T::Lookup::unlookup(account("target", 0, SEED));
+		let target1 = T::Lookup::unlookup(account("target", 1, SEED));
+		let target2 = T::Lookup::unlookup(account("target", 2, SEED));
+	}: _(SystemOrigin::Signed(caller), class, target0.clone(), target1.clone(), target2.clone())
+	verify {
+		let details = Class::<T>::get(class).unwrap();
+		assert_eq!(details.issuer, account("target", 0, SEED));
+		assert_eq!(details.admin, account("target", 1, SEED));
+		assert_eq!(details.freezer, account("target", 2, SEED));
+	}
+
+	approve_transfer {
+		let (class, caller, _) = create_class::<T>();
+		let (instance, ..) = mint_instance::<T>(0);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), class, instance, delegate_lookup)
+	verify {
+		assert_eq!(Asset::<T>::get(class, instance).unwrap().approved, Some(delegate));
+	}
+
+	cancel_approval {
+		let (class, caller, _) = create_class::<T>();
+		let (instance, ..) = mint_instance::<T>(0);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+		assert!(Uniques::<T>::approve_transfer(
+			SystemOrigin::Signed(caller.clone()).into(),
+			class,
+			instance,
+			delegate_lookup.clone(),
+		).is_ok());
+	}: _(SystemOrigin::Signed(caller.clone()), class, instance, Some(delegate_lookup))
+	verify {
+		assert!(Asset::<T>::get(class, instance).unwrap().approved.is_none());
+	}
+
+	set_attribute {
+		let key = vec![0u8; T::KeyLimit::get() as usize];
+		let value = vec![0u8; T::ValueLimit::get() as usize];
+
+		let (class, caller, _) = create_class::<T>();
+		let (instance, ..) = mint_instance::<T>(0);
+	}: _(SystemOrigin::Signed(caller), class, Some(instance), key.clone(), value.clone())
+	verify {
+		assert_eq!(Attribute::<T>::get((class, Some(instance)), &key).unwrap().0, value);
+	}
+
+	clear_attribute {
+		let (class, caller, _) = create_class::<T>();
+		let (instance, ..) = mint_instance::<T>(0);
+		let key = add_instance_attribute::<T>(instance);
+	}: _(SystemOrigin::Signed(caller), class, Some(instance), key.clone())
+	verify {
+		assert!(!Attribute::<T>::contains_key((class, Some(instance)), &key));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create::<Test>());
+			assert_ok!(test_benchmark_force_create::<Test>());
+			assert_ok!(test_benchmark_destroy::<Test>());
+			assert_ok!(test_benchmark_mint::<Test>());
+			assert_ok!(test_benchmark_burn::<Test>());
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_freeze::<Test>());
+			assert_ok!(test_benchmark_thaw::<Test>());
+			assert_ok!(test_benchmark_freeze_class::<Test>());
+			assert_ok!(test_benchmark_thaw_class::<Test>());
+			assert_ok!(test_benchmark_transfer_ownership::<Test>());
+			assert_ok!(test_benchmark_set_team::<Test>());
+			assert_ok!(test_benchmark_approve_transfer::<Test>());
+			assert_ok!(test_benchmark_cancel_approval::<Test>());
+			assert_ok!(test_benchmark_set_attribute::<Test>());
+			assert_ok!(test_benchmark_clear_attribute::<Test>());
+		});
+	}
+}
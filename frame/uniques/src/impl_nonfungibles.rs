@@ -0,0 +1,124 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations for the `nonfungibles` traits of `frame_support`.
+
+use super::*;
+use frame_support::{
+	IterableStorageMap,
+	traits::nonfungibles::{Inspect, InspectEnumerable, Create, Mutate, Transfer},
+};
+
+impl<T: Trait> Inspect<<T as frame_system::Trait>::AccountId> for Module<T> {
+	type InstanceId = T::InstanceId;
+	type ClassId = T::ClassId;
+
+	fn owner(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<T::AccountId> {
+		Asset::<T>::get(class, instance).map(|a| a.owner)
+	}
+
+	fn class_owner(class: &Self::ClassId) -> Option<T::AccountId> {
+		Class::<T>::get(class).map(|a| a.owner)
+	}
+
+	fn attribute(class: &Self::ClassId, instance: &Self::InstanceId, key: &[u8]) -> Option<Vec<u8>> {
+		Attribute::<T>::get((*class, Some(*instance)), key.to_vec()).map(|a| a.0)
+	}
+
+	fn class_attribute(class: &Self::ClassId, key: &[u8]) -> Option<Vec<u8>> {
+		Attribute::<T>::get((*class, None::<T::InstanceId>), key.to_vec()).map(|a| a.0)
+	}
+
+	/// Returns `true` if the asset `instance` of `class` may be transferred.
+	///
+	/// Frozen instances, and instances of frozen classes, may not be transferred.
+	fn can_transfer(class: &Self::ClassId, instance: &Self::InstanceId) -> bool {
+		match (Class::<T>::get(class), Asset::<T>::get(class, instance)) {
+			(Some(cd), Some(id)) if !cd.is_frozen && !id.is_frozen => true,
+			_ => false,
+		}
+	}
+}
+
+impl<T: Trait> InspectEnumerable<<T as frame_system::Trait>::AccountId> for Module<T> {
+	fn classes() -> Box<dyn Iterator<Item = Self::ClassId>> {
+		Box::new(Class::<T>::iter().map(|(class, _)| class))
+	}
+
+	fn instances(class: &Self::ClassId) -> Box<dyn Iterator<Item = Self::InstanceId>> {
+		Box::new(Asset::<T>::iter_prefix(class).map(|(instance, _)| instance))
+	}
+
+	fn owned(who: &T::AccountId) -> Box<dyn Iterator<Item = (Self::ClassId, Self::InstanceId)>> {
+		Box::new(Account::<T>::iter_prefix(who).map(|(key, _)| key))
+	}
+
+	fn owned_in_class(class: &Self::ClassId, who: &T::AccountId)
+		-> Box<dyn Iterator<Item = Self::InstanceId>>
+	{
+		let class = *class;
+		Box::new(Account::<T>::iter_prefix(who)
+			.filter(move |((c, _), _)| c == &class)
+			.map(|((_, instance), _)| instance))
+	}
+}
+
+impl<T: Trait> Create<<T as frame_system::Trait>::AccountId> for Module<T> {
+	/// Create a `class` of nonfungible assets to be owned by `who` and managed by `admin`.
+	///
+	/// The `ClassDeposit` is reserved from `who`.
+	fn create_class(class: &Self::ClassId, who: &T::AccountId, admin: &T::AccountId) -> DispatchResult {
+		ensure!(!Class::<T>::contains_key(class), Error::<T>::InUse);
+
+		let deposit = T::ClassDeposit::get();
+		T::Currency::reserve(who, deposit)?;
+
+		Class::<T>::insert(class, ClassDetails {
+			owner: who.clone(),
+			issuer: admin.clone(),
+			admin: admin.clone(),
+			freezer: admin.clone(),
+			total_deposit: deposit,
+			free_holding: false,
+			instances: 0,
+			attributes: 0,
+			is_frozen: false,
+		});
+		Self::deposit_event(RawEvent::Created(*class, who.clone(), admin.clone()));
+		Ok(())
+	}
+}
+
+impl<T: Trait> Mutate<<T as frame_system::Trait>::AccountId> for Module<T> {
+	fn mint_into(class: &Self::ClassId, instance: &Self::InstanceId, who: &T::AccountId) -> DispatchResult {
+		Self::do_mint(*class, *instance, who.clone(), |_| Ok(()))
+	}
+
+	fn burn_from(class: &Self::ClassId, instance: &Self::InstanceId) -> DispatchResult {
+		Self::do_burn(*class, *instance, |_, _| Ok(()))
+	}
+}
+
+impl<T: Trait> Transfer<<T as frame_system::Trait>::AccountId> for Module<T> {
+	fn transfer(
+		class: &Self::ClassId,
+		instance: &Self::InstanceId,
+		destination: &T::AccountId,
+	) -> DispatchResult {
+		Self::do_transfer(*class, *instance, destination.clone(), |_, _| Ok(()))
+	}
+}
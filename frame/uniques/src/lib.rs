@@ -0,0 +1,1138 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Uniques Module
+//!
+//! A simple, secure module for dealing with non-fungible assets.
+//!
+//! ## Overview
+//!
+//! The Uniques module provides functionality for the management of non-fungible asset classes,
+//! including:
+//!
+//! * Asset Class Creation
+//! * Asset Instance Issuance (Minting) and Burning
+//! * Asset Instance Transfer
+//! * Asset Instance Transfer Approvals
+//! * Instance and Class Freezing
+//! * Instance and Class Attributes
+//! * Asset Class Destruction
+//!
+//! To use it in your runtime, you need to implement the uniques [`Trait`](./trait.Trait.html).
+//!
+//! The supported dispatchable functions are documented in the [`Call`](./enum.Call.html) enum.
+//!
+//! ### Terminology
+//!
+//! * **Asset class**: A collection of asset instances, identified by a class id and managed by
+//!   a team of accounts.
+//! * **Asset instance**: A single, unique asset within a class, identified by an instance id and
+//!   owned by exactly one account.
+//! * **Owner**: An account ID uniquely privileged to be able to destroy an asset class, to set
+//!   its attributes, or to set the Issuer, Freezer or Admin of that asset class. The owner holds
+//!   all deposits taken for the class, its instances and its attributes.
+//! * **Issuer**: An account ID uniquely privileged to be able to mint instances of a class.
+//! * **Admin**: An account ID uniquely privileged to be able to thaw instances and classes, as
+//!   well as to transfer and burn any instance of a class.
+//! * **Freezer**: An account ID uniquely privileged to be able to freeze instances and classes.
+//! * **Freezing**: Removing the possibility of an unpermissioned transfer of an instance or of
+//!   all instances of a class.
+//! * **Approval**: A permission granted by an instance owner to a delegate, allowing the
+//!   delegate to transfer that one instance.
+//! * **Attribute**: An arbitrary key-value pair attached to a class or to one of its instances,
+//!   held under a deposit.
+//!
+//! ### Goals
+//!
+//! The uniques system in Substrate is designed to make the following possible:
+//!
+//! * Create a new asset class, whose management is delegated to a team of accounts.
+//! * Issue unique asset instances and move them between accounts.
+//! * Let owners approve a delegate to transfer a single instance on their behalf.
+//! * Freeze and thaw individual instances or entire asset classes.
+//! * Attach arbitrary attributes to classes and instances, paid for by a deposit.
+//! * Destroy an entire asset class and return all of its deposits.
+//!
+//! ## Interface
+//!
+//! ### Permissionless Functions
+//!
+//! * `create`: Creates a new asset class, taking the required deposit.
+//! * `transfer`: Transfer an instance owned by the sender, or approved to it, to another account.
+//! * `approve_transfer`: Allow a delegate to transfer an instance owned by the sender.
+//! * `cancel_approval`: Revoke the approval of an instance owned by the sender.
+//! * `burn`: Destroy an instance owned by the sender.
+//!
+//! ### Permissioned Functions
+//!
+//! * `force_create`: Creates a new asset class without taking any deposit.
+//! * `destroy`: Destroys an entire asset class; called by the asset class's Owner or the
+//!   `ForceOrigin`.
+//!
+//! ### Privileged Functions
+//!
+//! * `mint`: Mints a new instance of an asset class; called by the asset class's Issuer.
+//! * `freeze`: Disallows further `transfer`s of an instance; called by the asset class's Freezer.
+//! * `thaw`: Allows further `transfer`s of an instance; called by the asset class's Admin.
+//! * `freeze_class`: Disallows further `transfer`s of all instances of a class; called by the
+//!   asset class's Freezer.
+//! * `thaw_class`: Allows further `transfer`s of all instances of a class; called by the asset
+//!   class's Admin.
+//! * `transfer_ownership`: Changes an asset class's Owner; called by the asset class's Owner.
+//! * `set_team`: Changes an asset class's Admin, Freezer and Issuer; called by the asset class's
+//!   Owner.
+//! * `set_attribute`: Sets an attribute of a class or an instance; called by the asset class's
+//!   Owner.
+//! * `clear_attribute`: Removes an attribute of a class or an instance; called by the asset
+//!   class's Owner.
+//!
+//! Please refer to the [`Call`](./enum.Call.html) enum and its associated variants for documentation on each function.
+//!
+//! ### Public Functions
+//!
+//! * `owner` - Get the owner of an asset instance, if it exists.
+//! * `class_owner` - Get the owner of an asset class, if it exists.
+//!
+//! The module also implements the `nonfungibles` traits of `frame_support`.
+//!
+//! Please refer to the [`Module`](./struct.Module.html) struct for details on publicly available functions.
+//!
+//! ## Related Modules
+//!
+//! * [`System`](../frame_system/index.html)
+//! * [`Support`](../frame_support/index.html)
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+mod default_weight;
+mod impl_nonfungibles;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode, HasCompact};
+use sp_runtime::{
+	RuntimeDebug, DispatchResult, DispatchError,
+	traits::{Zero, StaticLookup, Saturating, AtLeast32BitUnsigned},
+};
+use frame_support::{
+	Parameter, decl_module, decl_event, decl_storage, decl_error, ensure, IterableStorageDoubleMap,
+	traits::{Currency, ReservableCurrency, EnsureOrigin, Get, BalanceStatus::Reserved},
+	weights::Weight,
+};
+use frame_system::ensure_signed;
+
+type DepositBalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn force_create() -> Weight;
+	fn destroy(n: u32, a: u32, ) -> Weight;
+	fn mint() -> Weight;
+	fn burn() -> Weight;
+	fn transfer() -> Weight;
+	fn freeze() -> Weight;
+	fn thaw() -> Weight;
+	fn freeze_class() -> Weight;
+	fn thaw_class() -> Weight;
+	fn transfer_ownership() -> Weight;
+	fn set_team() -> Weight;
+	fn approve_transfer() -> Weight;
+	fn cancel_approval() -> Weight;
+	fn set_attribute() -> Weight;
+	fn clear_attribute() -> Weight;
+}
+
+/// The module configuration trait.
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Identifier for the class of asset.
+	type ClassId: Parameter + HasCompact + Default + Copy;
+
+	/// The type used to identify a unique asset within an asset class.
+	type InstanceId: Parameter + AtLeast32BitUnsigned + HasCompact + Default + Copy;
+
+	/// The currency mechanism, used for paying for reserves.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The origin which may forcibly create or destroy an asset class.
+	type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The basic amount of funds that must be reserved for an asset class.
+	type ClassDeposit: Get<DepositBalanceOf<Self>>;
+
+	/// The basic amount of funds that must be reserved for an asset instance.
+	type InstanceDeposit: Get<DepositBalanceOf<Self>>;
+
+	/// The basic amount of funds that must be reserved when adding an attribute to an asset.
+	type AttributeDepositBase: Get<DepositBalanceOf<Self>>;
+
+	/// The additional funds that must be reserved for the number of bytes stored in an
+	/// attribute key and value.
+	type DepositPerByte: Get<DepositBalanceOf<Self>>;
+
+	/// The maximum length of an attribute key.
+	type KeyLimit: Get<u32>;
+
+	/// The maximum length of an attribute value.
+	type ValueLimit: Get<u32>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct ClassDetails<AccountId, DepositBalance> {
+	/// Can change `owner`, `issuer`, `freezer` and `admin` accounts.
+	owner: AccountId,
+	/// Can mint tokens.
+	issuer: AccountId,
+	/// Can thaw tokens, force transfers and burn tokens from any account.
+	admin: AccountId,
+	/// Can freeze tokens.
+	freezer: AccountId,
+	/// The total balance deposited for all the storage associated with this asset class. Used by
+	/// `destroy`.
+	total_deposit: DepositBalance,
+	/// If `true`, then no deposit is needed to hold instances of this class.
+	free_holding: bool,
+	/// The total number of outstanding instances of this asset class.
+	instances: u32,
+	/// The total number of attributes for this asset class.
+	attributes: u32,
+	/// Whether the asset is frozen for non-admin transfers.
+	is_frozen: bool,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct InstanceDetails<AccountId, DepositBalance> {
+	/// The owner of this asset.
+	owner: AccountId,
+	/// The approved transferrer of this asset, if one is set.
+	approved: Option<AccountId>,
+	/// Whether the asset can be transferred or not.
+	is_frozen: bool,
+	/// The amount held in the pallet's default account for this asset. Free-hold assets will have
+	/// this as zero.
+	deposit: DepositBalance,
+}
+
+/// Witness data for the `destroy` call, bounding the work it has to do.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct DestroyWitness {
+	/// The total number of outstanding instances of this asset class.
+	#[codec(compact)]
+	pub instances: u32,
+	/// The total number of attributes for this asset class.
+	#[codec(compact)]
+	pub attributes: u32,
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Uniques {
+		/// Details of an asset class.
+		Class: map hasher(blake2_128_concat) T::ClassId
+			=> Option<ClassDetails<T::AccountId, DepositBalanceOf<T>>>;
+
+		/// The assets held by any given account; set out this way so that assets owned by a
+		/// single account can be enumerated.
+		Account: double_map
+			hasher(blake2_128_concat) T::AccountId,
+			hasher(blake2_128_concat) (T::ClassId, T::InstanceId)
+			=> Option<()>;
+
+		/// The assets in existence and their ownership details.
+		Asset: double_map
+			hasher(blake2_128_concat) T::ClassId,
+			hasher(blake2_128_concat) T::InstanceId
+			=> Option<InstanceDetails<T::AccountId, DepositBalanceOf<T>>>;
+
+		/// Attributes of an asset class, or of one of its instances when the second item of the
+		/// first key is `Some`. Values are stored together with the deposit taken for them.
+		Attribute: double_map
+			hasher(blake2_128_concat) (T::ClassId, Option<T::InstanceId>),
+			hasher(blake2_128_concat) Vec<u8>
+			=> Option<(Vec<u8>, DepositBalanceOf<T>)>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		<T as frame_system::Trait>::AccountId,
+		<T as Trait>::ClassId,
+		<T as Trait>::InstanceId,
+	{
+		/// An asset class was created. [class, creator, owner]
+		Created(ClassId, AccountId, AccountId),
+		/// An asset class was force-created. [class, owner]
+		ForceCreated(ClassId, AccountId),
+		/// An asset class was destroyed. [class]
+		Destroyed(ClassId),
+		/// An asset instance was issued. [class, instance, owner]
+		Issued(ClassId, InstanceId, AccountId),
+		/// An asset instance was transferred. [class, instance, from, to]
+		Transferred(ClassId, InstanceId, AccountId, AccountId),
+		/// An asset instance was destroyed. [class, instance, owner]
+		Burned(ClassId, InstanceId, AccountId),
+		/// Some asset instance was frozen. [class, instance]
+		Frozen(ClassId, InstanceId),
+		/// Some asset instance was thawed. [class, instance]
+		Thawed(ClassId, InstanceId),
+		/// Some asset class was frozen. [class]
+		ClassFrozen(ClassId),
+		/// Some asset class was thawed. [class]
+		ClassThawed(ClassId),
+		/// The owner changed [class, new_owner]
+		OwnerChanged(ClassId, AccountId),
+		/// The management team changed [class, issuer, admin, freezer]
+		TeamChanged(ClassId, AccountId, AccountId, AccountId),
+		/// An instance of an asset class has been approved by the owner for transfer by a
+		/// delegate. [class, instance, owner, delegate]
+		ApprovedTransfer(ClassId, InstanceId, AccountId, AccountId),
+		/// An approval for a delegate account to transfer the instance of an asset class was
+		/// cancelled by its owner. [class, instance, owner, delegate]
+		ApprovalCancelled(ClassId, InstanceId, AccountId, AccountId),
+		/// New attribute metadata has been set for an asset class or instance.
+		/// [class, maybe_instance, key, value]
+		AttributeSet(ClassId, Option<InstanceId>, Vec<u8>, Vec<u8>),
+		/// Attribute metadata has been cleared for an asset class or instance.
+		/// [class, maybe_instance, key]
+		AttributeCleared(ClassId, Option<InstanceId>, Vec<u8>),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The signing account has no permission to do the operation.
+		NoPermission,
+		/// The given asset ID is unknown.
+		Unknown,
+		/// The asset instance ID has already been used for an asset.
+		AlreadyExists,
+		/// The owner turned out to be different to what was expected.
+		WrongOwner,
+		/// Invalid witness data given.
+		BadWitness,
+		/// The asset ID is already taken.
+		InUse,
+		/// The asset instance or class is frozen.
+		Frozen,
+		/// The delegate turned out to be different to what was expected.
+		WrongDelegate,
+		/// There is no delegate approved.
+		NoDelegate,
+		/// No approval exists that would allow the transfer.
+		Unapproved,
+		/// An attribute key or value is longer than allowed.
+		BadAttribute,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// The basic amount of funds that must be reserved for an asset class.
+		const ClassDeposit: DepositBalanceOf<T> = T::ClassDeposit::get();
+
+		/// The basic amount of funds that must be reserved for an asset instance.
+		const InstanceDeposit: DepositBalanceOf<T> = T::InstanceDeposit::get();
+
+		/// The basic amount of funds that must be reserved when adding an attribute to an asset.
+		const AttributeDepositBase: DepositBalanceOf<T> = T::AttributeDepositBase::get();
+
+		/// The additional funds that must be reserved for each byte of an attribute.
+		const DepositPerByte: DepositBalanceOf<T> = T::DepositPerByte::get();
+
+		/// The maximum length of an attribute key.
+		const KeyLimit: u32 = T::KeyLimit::get();
+
+		/// The maximum length of an attribute value.
+		const ValueLimit: u32 = T::ValueLimit::get();
+
+		/// Issue a new class of non-fungible assets from a public origin.
+		///
+		/// This new asset class has no assets initially and its owner is the origin.
+		///
+		/// The origin must be Signed and the sender must have sufficient funds free.
+		///
+		/// `ClassDeposit` funds of sender are reserved.
+		///
+		/// Parameters:
+		/// - `class`: The identifier of the new asset class. This must not be currently in use.
+		/// - `admin`: The admin of this class of assets. The admin is the initial address of each
+		/// member of the asset class's admin team.
+		///
+		/// Emits `Created` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 reserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::create()]
+		fn create(origin,
+			#[compact] class: T::ClassId,
+			admin: <T::Lookup as StaticLookup>::Source,
+		) {
+			let owner = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+
+			ensure!(!Class::<T>::contains_key(class), Error::<T>::InUse);
+
+			let deposit = T::ClassDeposit::get();
+			T::Currency::reserve(&owner, deposit)?;
+
+			Class::<T>::insert(class, ClassDetails {
+				owner: owner.clone(),
+				issuer: admin.clone(),
+				admin: admin.clone(),
+				freezer: admin.clone(),
+				total_deposit: deposit,
+				free_holding: false,
+				instances: 0,
+				attributes: 0,
+				is_frozen: false,
+			});
+			Self::deposit_event(RawEvent::Created(class, owner, admin));
+		}
+
+		/// Issue a new class of non-fungible assets from a privileged origin.
+		///
+		/// This new asset class has no assets initially.
+		///
+		/// The origin must conform to `ForceOrigin`.
+		///
+		/// Unlike `create`, no funds are reserved.
+		///
+		/// - `class`: The identifier of the new asset. This must not be currently in use.
+		/// - `owner`: The owner of this class of assets. The owner has full superuser permissions
+		/// over this asset, but may later change and configure the permissions using
+		/// `transfer_ownership` and `set_team`.
+		/// - `free_holding`: Whether instances of the class may be minted and hold attributes
+		/// without taking any deposit.
+		///
+		/// Emits `ForceCreated` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read and write.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::force_create()]
+		fn force_create(origin,
+			#[compact] class: T::ClassId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			free_holding: bool,
+		) {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			ensure!(!Class::<T>::contains_key(class), Error::<T>::InUse);
+
+			Class::<T>::insert(class, ClassDetails {
+				owner: owner.clone(),
+				issuer: owner.clone(),
+				admin: owner.clone(),
+				freezer: owner.clone(),
+				total_deposit: Zero::zero(),
+				free_holding,
+				instances: 0,
+				attributes: 0,
+				is_frozen: false,
+			});
+			Self::deposit_event(RawEvent::ForceCreated(class, owner));
+		}
+
+		/// Destroy a class of non-fungible assets.
+		///
+		/// The origin must conform to `ForceOrigin` or must be Signed and the sender must be the
+		/// owner of the asset `class`.
+		///
+		/// - `class`: The identifier of the asset class to be destroyed.
+		/// - `witness`: The number of instances and attributes of the class, which must match the
+		/// values currently held in storage.
+		///
+		/// All instances and attributes of the class are removed, and all deposits taken for them
+		/// are returned to the owner of the class.
+		///
+		/// Emits `Destroyed` event when successful.
+		///
+		/// # <weight>
+		/// - `O(N + A)` where `N` is the number of instances and `A` the number of attributes
+		///   given in the witness.
+		/// - 1 storage removal of the class, up to `2 * N + A` storage deletions.
+		/// - 1 unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::destroy(witness.instances, witness.attributes)]
+		fn destroy(origin, #[compact] class: T::ClassId, witness: DestroyWitness) {
+			let maybe_check_owner = match T::ForceOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+			Class::<T>::try_mutate_exists(class, |maybe_details| -> DispatchResult {
+				let details = maybe_details.take().ok_or(Error::<T>::Unknown)?;
+				if let Some(check_owner) = maybe_check_owner {
+					ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+				}
+				ensure!(details.instances == witness.instances, Error::<T>::BadWitness);
+				ensure!(details.attributes == witness.attributes, Error::<T>::BadWitness);
+
+				for (instance, instance_details) in Asset::<T>::drain_prefix(&class) {
+					Account::<T>::remove(&instance_details.owner, &(class, instance));
+					Attribute::<T>::remove_prefix(&(class, Some(instance)));
+				}
+				Attribute::<T>::remove_prefix(&(class, None::<T::InstanceId>));
+				T::Currency::unreserve(&details.owner, details.total_deposit);
+
+				Self::deposit_event(RawEvent::Destroyed(class));
+				Ok(())
+			})?;
+		}
+
+		/// Mint an asset instance of a particular class.
+		///
+		/// The origin must be Signed and the sender must be the Issuer of the asset `class`.
+		///
+		/// - `class`: The class of the asset to be minted.
+		/// - `instance`: The instance value of the asset to be minted.
+		/// - `owner`: The initial owner of the minted asset.
+		///
+		/// Unless the class is free-holding, `InstanceDeposit` is reserved from the owner of the
+		/// class.
+		///
+		/// Emits `Issued` event when successful.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the class, 2 storage writes.
+		/// - 1 reserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::mint()]
+		fn mint(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			Self::do_mint(class, instance, owner, |class_details| {
+				ensure!(class_details.issuer == origin, Error::<T>::NoPermission);
+				Ok(())
+			})?;
+		}
+
+		/// Destroy a single asset instance.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `class` or the
+		/// owner of the instance.
+		///
+		/// - `class`: The class of the asset to be burned.
+		/// - `instance`: The instance of the asset to be burned.
+		/// - `check_owner`: If `Some` then the operation will fail with `WrongOwner` unless the
+		///   asset is owned by this value.
+		///
+		/// The attributes of the instance are cleared, and the deposits taken for the instance and
+		/// its attributes are returned to the owner of the class.
+		///
+		/// Emits `Burned` with the actual amount burned.
+		///
+		/// # <weight>
+		/// - `O(a)` where `a` is the number of attributes of the instance.
+		/// - 1 storage mutation of the class, `2 + a` storage deletions.
+		/// - `1 + a` unreserves.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::burn()]
+		fn burn(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			check_owner: Option<<T::Lookup as StaticLookup>::Source>,
+		) {
+			let origin = ensure_signed(origin)?;
+			let check_owner = check_owner.map(T::Lookup::lookup).transpose()?;
+
+			Self::do_burn(class, instance, |class_details, details| {
+				let is_permitted = class_details.admin == origin || details.owner == origin;
+				ensure!(is_permitted, Error::<T>::NoPermission);
+				ensure!(check_owner.map_or(true, |o| o == details.owner), Error::<T>::WrongOwner);
+				Ok(())
+			})?;
+		}
+
+		/// Move an asset from the sender account to another.
+		///
+		/// Origin must be Signed and the signing account must be either:
+		/// - the Admin of the asset `class`;
+		/// - the Owner of the asset `instance`;
+		/// - the approved delegate for the asset `instance` (in this case, the approval is reset).
+		///
+		/// Arguments:
+		/// - `class`: The class of the asset to be transferred.
+		/// - `instance`: The instance of the asset to be transferred.
+		/// - `dest`: The account to receive ownership of the asset.
+		///
+		/// Emits `Transferred`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read of the class, 1 storage mutation of the instance.
+		/// - 2 storage mutations of the ownership index.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::transfer()]
+		fn transfer(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			Self::do_transfer(class, instance, dest, |class_details, details| {
+				if details.owner != origin && class_details.admin != origin {
+					let approved = details.approved.as_ref().map_or(false, |a| a == &origin);
+					ensure!(approved, Error::<T>::NoPermission);
+				}
+				Ok(())
+			})?;
+		}
+
+		/// Disallow further unprivileged transfer of an asset instance.
+		///
+		/// Origin must be Signed and the sender should be the Freezer of the asset `class`.
+		///
+		/// - `class`: The class of the asset to be frozen.
+		/// - `instance`: The instance of the asset to be frozen.
+		///
+		/// Emits `Frozen`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read of the class, 1 storage mutation of the instance.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::freeze()]
+		fn freeze(origin, #[compact] class: T::ClassId, #[compact] instance: T::InstanceId) {
+			let origin = ensure_signed(origin)?;
+
+			let mut details = Asset::<T>::get(&class, &instance).ok_or(Error::<T>::Unknown)?;
+			let class_details = Class::<T>::get(&class).ok_or(Error::<T>::Unknown)?;
+			ensure!(class_details.freezer == origin, Error::<T>::NoPermission);
+
+			details.is_frozen = true;
+			Asset::<T>::insert(&class, &instance, &details);
+
+			Self::deposit_event(RawEvent::Frozen(class, instance));
+		}
+
+		/// Re-allow unprivileged transfer of an asset instance.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `class`.
+		///
+		/// - `class`: The class of the asset to be thawed.
+		/// - `instance`: The instance of the asset to be thawed.
+		///
+		/// Emits `Thawed`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read of the class, 1 storage mutation of the instance.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::thaw()]
+		fn thaw(origin, #[compact] class: T::ClassId, #[compact] instance: T::InstanceId) {
+			let origin = ensure_signed(origin)?;
+
+			let mut details = Asset::<T>::get(&class, &instance).ok_or(Error::<T>::Unknown)?;
+			let class_details = Class::<T>::get(&class).ok_or(Error::<T>::Unknown)?;
+			ensure!(class_details.admin == origin, Error::<T>::NoPermission);
+
+			details.is_frozen = false;
+			Asset::<T>::insert(&class, &instance, &details);
+
+			Self::deposit_event(RawEvent::Thawed(class, instance));
+		}
+
+		/// Disallow further unprivileged transfers for a whole asset class.
+		///
+		/// Origin must be Signed and the sender should be the Freezer of the asset `class`.
+		///
+		/// - `class`: The asset class to be frozen.
+		///
+		/// Emits `ClassFrozen`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the class.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::freeze_class()]
+		fn freeze_class(origin, #[compact] class: T::ClassId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			Class::<T>::try_mutate(class, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(&origin == &details.freezer, Error::<T>::NoPermission);
+
+				details.is_frozen = true;
+
+				Self::deposit_event(RawEvent::ClassFrozen(class));
+				Ok(())
+			})
+		}
+
+		/// Re-allow unprivileged transfers for a whole asset class.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `class`.
+		///
+		/// - `class`: The class to be thawed.
+		///
+		/// Emits `ClassThawed`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the class.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::thaw_class()]
+		fn thaw_class(origin, #[compact] class: T::ClassId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+
+			Class::<T>::try_mutate(class, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(&origin == &details.admin, Error::<T>::NoPermission);
+
+				details.is_frozen = false;
+
+				Self::deposit_event(RawEvent::ClassThawed(class));
+				Ok(())
+			})
+		}
+
+		/// Change the Owner of an asset class.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the asset `class`.
+		///
+		/// - `class`: The asset class whose owner should be changed.
+		/// - `owner`: The new Owner of this asset class.
+		///
+		/// All deposits held for the class move to the new owner.
+		///
+		/// Emits `OwnerChanged`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the class.
+		/// - 1 repatriation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::transfer_ownership()]
+		fn transfer_ownership(origin,
+			#[compact] class: T::ClassId,
+			owner: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			Class::<T>::try_mutate(class, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(&origin == &details.owner, Error::<T>::NoPermission);
+				if details.owner == owner {
+					return Ok(())
+				}
+
+				// Move the deposit to the new owner.
+				T::Currency::repatriate_reserved(&details.owner, &owner, details.total_deposit, Reserved)?;
+				details.owner = owner.clone();
+
+				Self::deposit_event(RawEvent::OwnerChanged(class, owner));
+				Ok(())
+			})
+		}
+
+		/// Change the Issuer, Admin and Freezer of an asset class.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the asset `class`.
+		///
+		/// - `class`: The asset class whose team should be changed.
+		/// - `issuer`: The new Issuer of this asset class.
+		/// - `admin`: The new Admin of this asset class.
+		/// - `freezer`: The new Freezer of this asset class.
+		///
+		/// Emits `TeamChanged`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation of the class.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::set_team()]
+		fn set_team(origin,
+			#[compact] class: T::ClassId,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			admin: <T::Lookup as StaticLookup>::Source,
+			freezer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let freezer = T::Lookup::lookup(freezer)?;
+
+			Class::<T>::try_mutate(class, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(&origin == &details.owner, Error::<T>::NoPermission);
+
+				details.issuer = issuer.clone();
+				details.admin = admin.clone();
+				details.freezer = freezer.clone();
+
+				Self::deposit_event(RawEvent::TeamChanged(class, issuer, admin, freezer));
+				Ok(())
+			})
+		}
+
+		/// Approve an instance to be transferred by a delegated third-party account.
+		///
+		/// Origin must be Signed and must be the owner of the asset `instance` or the Admin of
+		/// the asset `class`.
+		///
+		/// - `class`: The class of the asset to be approved for delegated transfer.
+		/// - `instance`: The instance of the asset to be approved for delegated transfer.
+		/// - `delegate`: The account to delegate permission to transfer the asset.
+		///
+		/// Any previous approval of the instance is replaced.
+		///
+		/// Emits `ApprovedTransfer` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read of the class, 1 storage mutation of the instance.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::approve_transfer()]
+		fn approve_transfer(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) {
+			let origin = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+
+			let class_details = Class::<T>::get(&class).ok_or(Error::<T>::Unknown)?;
+			let mut details = Asset::<T>::get(&class, &instance).ok_or(Error::<T>::Unknown)?;
+			let permitted = origin == class_details.admin || origin == details.owner;
+			ensure!(permitted, Error::<T>::NoPermission);
+
+			details.approved = Some(delegate.clone());
+			Asset::<T>::insert(&class, &instance, &details);
+
+			Self::deposit_event(RawEvent::ApprovedTransfer(class, instance, details.owner, delegate));
+		}
+
+		/// Cancel the prior approval for the transfer of an asset by a delegate.
+		///
+		/// Origin must be Signed and must be the owner of the asset `instance` or the Admin of
+		/// the asset `class`.
+		///
+		/// - `class`: The class of the asset of whose approval will be cancelled.
+		/// - `instance`: The instance of the asset of whose approval will be cancelled.
+		/// - `maybe_check_delegate`: If `Some` will ensure that the given account is the one to
+		///   which permission of transfer is delegated.
+		///
+		/// Emits `ApprovalCancelled` on success.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read of the class, 1 storage mutation of the instance.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::cancel_approval()]
+		fn cancel_approval(origin,
+			#[compact] class: T::ClassId,
+			#[compact] instance: T::InstanceId,
+			maybe_check_delegate: Option<<T::Lookup as StaticLookup>::Source>,
+		) {
+			let origin = ensure_signed(origin)?;
+			let maybe_check_delegate = maybe_check_delegate.map(T::Lookup::lookup).transpose()?;
+
+			let class_details = Class::<T>::get(&class).ok_or(Error::<T>::Unknown)?;
+			let mut details = Asset::<T>::get(&class, &instance).ok_or(Error::<T>::Unknown)?;
+			let permitted = origin == class_details.admin || origin == details.owner;
+			ensure!(permitted, Error::<T>::NoPermission);
+
+			let old = details.approved.take().ok_or(Error::<T>::NoDelegate)?;
+			if let Some(check_delegate) = maybe_check_delegate {
+				ensure!(check_delegate == old, Error::<T>::WrongDelegate);
+			}
+			Asset::<T>::insert(&class, &instance, &details);
+
+			Self::deposit_event(RawEvent::ApprovalCancelled(class, instance, details.owner, old));
+		}
+
+		/// Set an attribute for an asset class or instance.
+		///
+		/// Origin must be either `ForceOrigin` or Signed and the sender should be the Owner of
+		/// the asset `class`.
+		///
+		/// If the origin is Signed, then funds of the owner of the class are reserved according
+		/// to the formula: `AttributeDepositBase + DepositPerByte * (key.len + value.len)`,
+		/// taking into account any already reserved funds. Free-holding classes take no deposit.
+		///
+		/// - `class`: The identifier of the asset class whose instance's metadata to set.
+		/// - `maybe_instance`: The identifier of the asset instance whose metadata to set, or
+		///   `None` to set an attribute of the class itself.
+		/// - `key`: The key of the attribute. Limited in length by `KeyLimit`.
+		/// - `value`: The value to which to set the attribute. Limited in length by `ValueLimit`.
+		///
+		/// Emits `AttributeSet`.
+		///
+		/// # <weight>
+		/// - `O(K + V)` where `K` and `V` are the lengths of `key` and `value` (bounded).
+		/// - 1 storage mutation of the class, 1 storage mutation of the attribute.
+		/// - 1 reserve or unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::set_attribute()]
+		fn set_attribute(origin,
+			#[compact] class: T::ClassId,
+			maybe_instance: Option<T::InstanceId>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			ensure!(key.len() <= T::KeyLimit::get() as usize, Error::<T>::BadAttribute);
+			ensure!(value.len() <= T::ValueLimit::get() as usize, Error::<T>::BadAttribute);
+
+			let mut class_details = Class::<T>::get(&class).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &class_details.owner, Error::<T>::NoPermission);
+			}
+			if let Some(ref instance) = maybe_instance {
+				ensure!(Asset::<T>::contains_key(&class, instance), Error::<T>::Unknown);
+			}
+
+			let attribute = Attribute::<T>::get(&(class, maybe_instance), &key);
+			if attribute.is_none() {
+				class_details.attributes = class_details.attributes.saturating_add(1);
+			}
+			let old_deposit = attribute.map_or(Zero::zero(), |m| m.1);
+			class_details.total_deposit = class_details.total_deposit.saturating_sub(old_deposit);
+			let mut deposit = Zero::zero();
+			if !class_details.free_holding && maybe_check_owner.is_some() {
+				deposit = T::DepositPerByte::get()
+					.saturating_mul(((key.len() + value.len()) as u32).into())
+					.saturating_add(T::AttributeDepositBase::get());
+			}
+			class_details.total_deposit = class_details.total_deposit.saturating_add(deposit);
+			if deposit > old_deposit {
+				T::Currency::reserve(&class_details.owner, deposit - old_deposit)?;
+			} else if deposit < old_deposit {
+				T::Currency::unreserve(&class_details.owner, old_deposit - deposit);
+			}
+
+			Attribute::<T>::insert(&(class, maybe_instance), &key, (&value, deposit));
+			Class::<T>::insert(class, &class_details);
+			Self::deposit_event(RawEvent::AttributeSet(class, maybe_instance, key, value));
+		}
+
+		/// Clear an attribute for an asset class or instance.
+		///
+		/// Origin must be either `ForceOrigin` or Signed and the sender should be the Owner of
+		/// the asset `class`.
+		///
+		/// Any deposit is freed for the asset class owner.
+		///
+		/// - `class`: The identifier of the asset class whose instance's metadata to clear.
+		/// - `maybe_instance`: The identifier of the asset instance whose metadata to clear, or
+		///   `None` to clear an attribute of the class itself.
+		/// - `key`: The key of the attribute.
+		///
+		/// Emits `AttributeCleared`.
+		///
+		/// # <weight>
+		/// - `O(K)` where `K` is the length of `key` (bounded).
+		/// - 1 storage mutation of the class, 1 storage deletion of the attribute.
+		/// - 1 unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::WeightInfo::clear_attribute()]
+		fn clear_attribute(origin,
+			#[compact] class: T::ClassId,
+			maybe_instance: Option<T::InstanceId>,
+			key: Vec<u8>,
+		) {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			let mut class_details = Class::<T>::get(&class).ok_or(Error::<T>::Unknown)?;
+			if let Some(check_owner) = &maybe_check_owner {
+				ensure!(check_owner == &class_details.owner, Error::<T>::NoPermission);
+			}
+
+			let (_, deposit) = Attribute::<T>::take(&(class, maybe_instance), &key)
+				.ok_or(Error::<T>::Unknown)?;
+			class_details.attributes = class_details.attributes.saturating_sub(1);
+			class_details.total_deposit = class_details.total_deposit.saturating_sub(deposit);
+			T::Currency::unreserve(&class_details.owner, deposit);
+
+			Class::<T>::insert(class, &class_details);
+			Self::deposit_event(RawEvent::AttributeCleared(class, maybe_instance, key));
+		}
+	}
+}
+
+// The main implementation block for the module.
+impl<T: Trait> Module<T> {
+	// Public immutables
+
+	/// Get the owner of the asset instance, if the asset exists.
+	pub fn owner(class: T::ClassId, instance: T::InstanceId) -> Option<T::AccountId> {
+		Asset::<T>::get(class, instance).map(|i| i.owner)
+	}
+
+	/// Get the owner of the asset class, if the asset class exists.
+	pub fn class_owner(class: T::ClassId) -> Option<T::AccountId> {
+		Class::<T>::get(class).map(|i| i.owner)
+	}
+
+	/// Create a new asset `instance` of `class` owned by `owner`, reserving the instance deposit
+	/// from the owner of the class unless it is free-holding.
+	///
+	/// `with_details` is called with the details of the class before anything is changed, so
+	/// that the caller may check permissions.
+	fn do_mint(
+		class: T::ClassId,
+		instance: T::InstanceId,
+		owner: T::AccountId,
+		with_details: impl FnOnce(&ClassDetails<T::AccountId, DepositBalanceOf<T>>) -> DispatchResult,
+	) -> DispatchResult {
+		ensure!(!Asset::<T>::contains_key(class, instance), Error::<T>::AlreadyExists);
+
+		Class::<T>::try_mutate(&class, |maybe_class_details| -> DispatchResult {
+			let class_details = maybe_class_details.as_mut().ok_or(Error::<T>::Unknown)?;
+
+			with_details(class_details)?;
+
+			let instances = class_details.instances.checked_add(1)
+				.ok_or(DispatchError::Other("Overflow"))?;
+			class_details.instances = instances;
+
+			let deposit = match class_details.free_holding {
+				true => Zero::zero(),
+				false => T::InstanceDeposit::get(),
+			};
+			T::Currency::reserve(&class_details.owner, deposit)?;
+			class_details.total_deposit = class_details.total_deposit.saturating_add(deposit);
+
+			Account::<T>::insert(&owner, (class, instance), ());
+			let details = InstanceDetails { owner: owner.clone(), approved: None, is_frozen: false, deposit };
+			Asset::<T>::insert(&class, &instance, details);
+			Ok(())
+		})?;
+
+		Self::deposit_event(RawEvent::Issued(class, instance, owner));
+		Ok(())
+	}
+
+	/// Destroy the asset `instance` of `class` along with its attributes, returning their
+	/// deposits to the owner of the class.
+	///
+	/// `with_details` is called with the details of the class and of the instance before
+	/// anything is changed, so that the caller may check permissions.
+	fn do_burn(
+		class: T::ClassId,
+		instance: T::InstanceId,
+		with_details: impl FnOnce(
+			&ClassDetails<T::AccountId, DepositBalanceOf<T>>,
+			&InstanceDetails<T::AccountId, DepositBalanceOf<T>>,
+		) -> DispatchResult,
+	) -> DispatchResult {
+		let owner = Class::<T>::try_mutate(&class, |maybe_class_details| -> Result<T::AccountId, DispatchError> {
+			let class_details = maybe_class_details.as_mut().ok_or(Error::<T>::Unknown)?;
+			let details = Asset::<T>::get(&class, &instance).ok_or(Error::<T>::Unknown)?;
+			with_details(class_details, &details)?;
+
+			// Return the deposit.
+			T::Currency::unreserve(&class_details.owner, details.deposit);
+			class_details.total_deposit = class_details.total_deposit.saturating_sub(details.deposit);
+			class_details.instances = class_details.instances.saturating_sub(1);
+
+			// Clear the attributes of the instance, returning their deposits as well.
+			for (_, (_, deposit)) in Attribute::<T>::drain_prefix(&(class, Some(instance))) {
+				T::Currency::unreserve(&class_details.owner, deposit);
+				class_details.total_deposit = class_details.total_deposit.saturating_sub(deposit);
+				class_details.attributes = class_details.attributes.saturating_sub(1);
+			}
+			Ok(details.owner)
+		})?;
+
+		Asset::<T>::remove(&class, &instance);
+		Account::<T>::remove(&owner, &(class, instance));
+
+		Self::deposit_event(RawEvent::Burned(class, instance, owner));
+		Ok(())
+	}
+
+	/// Move the asset `instance` of `class` to `dest`, clearing any approval.
+	///
+	/// Frozen instances and classes cannot be transferred. `with_details` is called with the
+	/// details of the class and of the instance before anything is changed, so that the caller
+	/// may check permissions.
+	fn do_transfer(
+		class: T::ClassId,
+		instance: T::InstanceId,
+		dest: T::AccountId,
+		with_details: impl FnOnce(
+			&ClassDetails<T::AccountId, DepositBalanceOf<T>>,
+			&mut InstanceDetails<T::AccountId, DepositBalanceOf<T>>,
+		) -> DispatchResult,
+	) -> DispatchResult {
+		let class_details = Class::<T>::get(&class).ok_or(Error::<T>::Unknown)?;
+		ensure!(!class_details.is_frozen, Error::<T>::Frozen);
+
+		let mut details = Asset::<T>::get(&class, &instance).ok_or(Error::<T>::Unknown)?;
+		ensure!(!details.is_frozen, Error::<T>::Frozen);
+		with_details(&class_details, &mut details)?;
+
+		Account::<T>::remove(&details.owner, &(class, instance));
+		Account::<T>::insert(&dest, (class, instance), ());
+		let origin = details.owner;
+		details.owner = dest;
+		details.approved = None;
+		Asset::<T>::insert(&class, &instance, &details);
+
+		Self::deposit_event(RawEvent::Transferred(class, instance, origin, details.owner));
+		Ok(())
+	}
+}
@@ -0,0 +1,361 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Uniques pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_ok, assert_noop, traits::{Currency, nonfungibles}};
+use pallet_balances::Error as BalancesError;
+
+fn witness(instances: u32, attributes: u32) -> DestroyWitness {
+	DestroyWitness { instances, attributes }
+}
+
+fn assets() -> Vec<(u64, u32, u32)> {
+	let mut r: Vec<_> = Account::<Test>::iter().map(|(owner, (c, i), _)| (owner, c, i)).collect();
+	r.sort();
+	let mut s: Vec<_> = Asset::<Test>::iter().map(|(c, i, a)| (a.owner, c, i)).collect();
+	s.sort();
+	assert_eq!(r, s);
+	r
+}
+
+fn attributes(class: u32) -> Vec<(Option<u32>, Vec<u8>, Vec<u8>)> {
+	let mut s: Vec<_> = Attribute::<Test>::iter()
+		.filter(|((c, _), _, _)| *c == class)
+		.map(|((_, instance), key, (value, _))| (instance, key, value))
+		.collect();
+	s.sort();
+	s
+}
+
+#[test]
+fn basic_setup_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(assets(), vec![]);
+	});
+}
+
+#[test]
+fn basic_minting_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+		assert_eq!(assets(), vec![(1, 0, 42)]);
+
+		assert_ok!(Uniques::force_create(Origin::root(), 1, 2, true));
+		assert_ok!(Uniques::mint(Origin::signed(2), 1, 69, 1));
+		assert_eq!(assets(), vec![(1, 0, 42), (1, 1, 69)]);
+	});
+}
+
+#[test]
+fn lifecycle_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Uniques::create(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::reserved_balance(&1), 2);
+
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 10));
+		assert_eq!(Balances::reserved_balance(&1), 3);
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 69, 20));
+		assert_eq!(Balances::reserved_balance(&1), 4);
+		assert_eq!(assets(), vec![(10, 0, 42), (20, 0, 69)]);
+		assert_eq!(Class::<Test>::get(0).unwrap().instances, 2);
+
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(42), vec![0], vec![0; 3]));
+		assert_eq!(Balances::reserved_balance(&1), 9);
+		assert_eq!(Class::<Test>::get(0).unwrap().attributes, 1);
+
+		assert_noop!(Uniques::destroy(Origin::signed(1), 0, witness(2, 0)), Error::<Test>::BadWitness);
+		assert_ok!(Uniques::destroy(Origin::signed(1), 0, witness(2, 1)));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+
+		assert!(!Class::<Test>::contains_key(0));
+		assert_eq!(assets(), vec![]);
+		assert_eq!(attributes(0), vec![]);
+	});
+}
+
+#[test]
+fn mint_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+		assert_eq!(Uniques::owner(0, 42).unwrap(), 1);
+		assert_noop!(Uniques::mint(Origin::signed(1), 0, 42, 2), Error::<Test>::AlreadyExists);
+		assert_noop!(Uniques::mint(Origin::signed(2), 0, 43, 2), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::mint(Origin::signed(1), 1, 43, 2), Error::<Test>::Unknown);
+	});
+}
+
+#[test]
+fn mint_requires_deposit_from_class_owner() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 3);
+		assert_ok!(Uniques::create(Origin::signed(1), 0, 1));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 2));
+		assert_noop!(
+			Uniques::mint(Origin::signed(1), 0, 69, 2),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn transfer_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 2));
+
+		assert_ok!(Uniques::transfer(Origin::signed(2), 0, 42, 3));
+		assert_eq!(assets(), vec![(3, 0, 42)]);
+		assert_noop!(Uniques::transfer(Origin::signed(2), 0, 42, 4), Error::<Test>::NoPermission);
+
+		// The admin may transfer any instance.
+		assert_ok!(Uniques::transfer(Origin::signed(1), 0, 42, 4));
+		assert_eq!(assets(), vec![(4, 0, 42)]);
+	});
+}
+
+#[test]
+fn freezing_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+		assert_ok!(Uniques::freeze(Origin::signed(1), 0, 42));
+		assert_noop!(Uniques::transfer(Origin::signed(1), 0, 42, 2), Error::<Test>::Frozen);
+
+		assert_ok!(Uniques::thaw(Origin::signed(1), 0, 42));
+		assert_ok!(Uniques::freeze_class(Origin::signed(1), 0));
+		assert_noop!(Uniques::transfer(Origin::signed(1), 0, 42, 2), Error::<Test>::Frozen);
+
+		assert_ok!(Uniques::thaw_class(Origin::signed(1), 0));
+		assert_ok!(Uniques::transfer(Origin::signed(1), 0, 42, 2));
+	});
+}
+
+#[test]
+fn origin_guards_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+		assert_noop!(Uniques::transfer_ownership(Origin::signed(2), 0, 2), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::set_team(Origin::signed(2), 0, 2, 2, 2), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::freeze(Origin::signed(2), 0, 42), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::thaw(Origin::signed(2), 0, 42), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::freeze_class(Origin::signed(2), 0), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::thaw_class(Origin::signed(2), 0), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::burn(Origin::signed(2), 0, 42, None), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::destroy(Origin::signed(2), 0, witness(1, 0)), Error::<Test>::NoPermission);
+	});
+}
+
+#[test]
+fn transfer_owner_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(Uniques::create(Origin::signed(1), 0, 1));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 1));
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, None, vec![0], vec![0]));
+		assert_eq!(Balances::reserved_balance(&1), 6);
+
+		assert_ok!(Uniques::transfer_ownership(Origin::signed(1), 0, 2));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::reserved_balance(&2), 6);
+		assert_eq!(Uniques::class_owner(0), Some(2));
+
+		assert_noop!(Uniques::transfer_ownership(Origin::signed(1), 0, 1), Error::<Test>::NoPermission);
+
+		// Deposits of new instances are now taken from the new owner.
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 69, 1));
+		assert_eq!(Balances::reserved_balance(&2), 7);
+	});
+}
+
+#[test]
+fn set_team_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_ok!(Uniques::set_team(Origin::signed(1), 0, 2, 3, 4));
+
+		assert_ok!(Uniques::mint(Origin::signed(2), 0, 42, 2));
+		assert_ok!(Uniques::freeze(Origin::signed(4), 0, 42));
+		assert_ok!(Uniques::thaw(Origin::signed(3), 0, 42));
+		assert_ok!(Uniques::transfer(Origin::signed(3), 0, 42, 3));
+		assert_ok!(Uniques::burn(Origin::signed(3), 0, 42, None));
+	});
+}
+
+#[test]
+fn set_attribute_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, false));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 0, 1));
+
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, None, vec![0], vec![0]));
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(0), vec![0], vec![0]));
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(0), vec![1], vec![0]));
+		assert_eq!(attributes(0), vec![
+			(None, vec![0], vec![0]),
+			(Some(0), vec![0], vec![0]),
+			(Some(0), vec![1], vec![0]),
+		]);
+		assert_eq!(Balances::reserved_balance(&1), 10);
+
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, None, vec![0], vec![0; 10]));
+		assert_eq!(Balances::reserved_balance(&1), 19);
+
+		assert_ok!(Uniques::clear_attribute(Origin::signed(1), 0, Some(0), vec![1]));
+		assert_eq!(attributes(0), vec![
+			(None, vec![0], vec![0; 10]),
+			(Some(0), vec![0], vec![0]),
+		]);
+		assert_eq!(Balances::reserved_balance(&1), 16);
+		assert_eq!(Class::<Test>::get(0).unwrap().attributes, 2);
+
+		assert_noop!(
+			Uniques::set_attribute(Origin::signed(1), 0, Some(1), vec![0], vec![0]),
+			Error::<Test>::Unknown,
+		);
+		assert_noop!(
+			Uniques::set_attribute(Origin::signed(1), 0, None, vec![0; 51], vec![0]),
+			Error::<Test>::BadAttribute,
+		);
+		assert_noop!(
+			Uniques::set_attribute(Origin::signed(2), 0, None, vec![0], vec![0]),
+			Error::<Test>::NoPermission,
+		);
+	});
+}
+
+#[test]
+fn burn_works() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, false));
+		assert_ok!(Uniques::set_team(Origin::signed(1), 0, 2, 3, 4));
+
+		assert_noop!(Uniques::burn(Origin::signed(5), 0, 42, Some(5)), Error::<Test>::Unknown);
+
+		assert_ok!(Uniques::mint(Origin::signed(2), 0, 42, 5));
+		assert_ok!(Uniques::mint(Origin::signed(2), 0, 69, 5));
+		assert_eq!(Balances::reserved_balance(&1), 2);
+
+		assert_noop!(Uniques::burn(Origin::signed(0), 0, 42, None), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::burn(Origin::signed(5), 0, 42, Some(6)), Error::<Test>::WrongOwner);
+
+		assert_ok!(Uniques::burn(Origin::signed(5), 0, 42, Some(5)));
+		assert_ok!(Uniques::burn(Origin::signed(3), 0, 69, Some(5)));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(assets(), vec![]);
+	});
+}
+
+#[test]
+fn burn_clears_attributes() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, false));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 0, 1));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 1, 1));
+
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, None, vec![0], vec![0]));
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(0), vec![0], vec![0]));
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(0), vec![1], vec![0]));
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(1), vec![0], vec![0]));
+		assert_eq!(Balances::reserved_balance(&1), 14);
+
+		assert_ok!(Uniques::burn(Origin::signed(1), 0, 0, None));
+		assert_eq!(attributes(0), vec![
+			(None, vec![0], vec![0]),
+			(Some(1), vec![0], vec![0]),
+		]);
+		assert_eq!(Balances::reserved_balance(&1), 7);
+		let class_details = Class::<Test>::get(0).unwrap();
+		assert_eq!(class_details.attributes, 2);
+		assert_eq!(class_details.total_deposit, 7);
+
+		assert_ok!(Uniques::destroy(Origin::signed(1), 0, witness(1, 2)));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(attributes(0), vec![]);
+	});
+}
+
+#[test]
+fn approval_lifecycle_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 2));
+		assert_ok!(Uniques::approve_transfer(Origin::signed(2), 0, 42, 3));
+		assert_ok!(Uniques::transfer(Origin::signed(3), 0, 42, 4));
+		assert_noop!(Uniques::transfer(Origin::signed(3), 0, 42, 3), Error::<Test>::NoPermission);
+		assert!(Asset::<Test>::get(0, 42).unwrap().approved.is_none());
+
+		assert_ok!(Uniques::approve_transfer(Origin::signed(4), 0, 42, 2));
+		assert_ok!(Uniques::transfer(Origin::signed(2), 0, 42, 2));
+	});
+}
+
+#[test]
+fn cancel_approval_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_ok!(Uniques::mint(Origin::signed(1), 0, 42, 2));
+
+		assert_ok!(Uniques::approve_transfer(Origin::signed(2), 0, 42, 3));
+		assert_noop!(Uniques::cancel_approval(Origin::signed(2), 1, 42, None), Error::<Test>::Unknown);
+		assert_noop!(Uniques::cancel_approval(Origin::signed(2), 0, 43, None), Error::<Test>::Unknown);
+		assert_noop!(Uniques::cancel_approval(Origin::signed(3), 0, 42, None), Error::<Test>::NoPermission);
+		assert_noop!(Uniques::cancel_approval(Origin::signed(2), 0, 42, Some(4)), Error::<Test>::WrongDelegate);
+
+		assert_ok!(Uniques::cancel_approval(Origin::signed(2), 0, 42, Some(3)));
+		assert_noop!(Uniques::cancel_approval(Origin::signed(2), 0, 42, None), Error::<Test>::NoDelegate);
+	});
+}
+
+#[test]
+fn nonfungibles_traits_should_work() {
+	new_test_ext().execute_with(|| {
+		use nonfungibles::{Inspect, InspectEnumerable, Mutate, Transfer};
+
+		assert_ok!(Uniques::force_create(Origin::root(), 0, 1, true));
+		assert_ok!(<Uniques as Mutate<u64>>::mint_into(&0, &42, &2));
+		assert_ok!(<Uniques as Mutate<u64>>::mint_into(&0, &69, &2));
+		assert_eq!(<Uniques as Inspect<u64>>::owner(&0, &42), Some(2));
+		assert_eq!(<Uniques as Inspect<u64>>::class_owner(&0), Some(1));
+
+		assert_ok!(Uniques::set_attribute(Origin::signed(1), 0, Some(42), vec![1], vec![2]));
+		assert_eq!(<Uniques as Inspect<u64>>::attribute(&0, &42, &[1]), Some(vec![2]));
+		assert_eq!(<Uniques as Inspect<u64>>::class_attribute(&0, &[1]), None);
+
+		assert_ok!(<Uniques as Transfer<u64>>::transfer(&0, &42, &3));
+		let mut owned: Vec<_> = <Uniques as InspectEnumerable<u64>>::owned_in_class(&0, &2).collect();
+		owned.sort();
+		assert_eq!(owned, vec![69]);
+		assert_eq!(<Uniques as InspectEnumerable<u64>>::classes().collect::<Vec<_>>(), vec![0]);
+
+		assert_ok!(Uniques::freeze(Origin::signed(1), 0, 42));
+		assert!(!<Uniques as Inspect<u64>>::can_transfer(&0, &42));
+		assert_ok!(<Uniques as Mutate<u64>>::burn_from(&0, &42));
+		assert_eq!(assets(), vec![(2, 0, 69)]);
+	});
+}
@@ -54,6 +54,26 @@ benchmarks! {
 		let caller = account("caller", u, SEED);
 		let call = Box::new(frame_system::Call::remark(vec![]).into());
 	}: _(RawOrigin::Signed(caller), u as u16, call)
+
+	batch_all {
+		let c in 0 .. 1000;
+		let mut calls: Vec<<T as Trait>::Call> = Vec::new();
+		for i in 0 .. c {
+			let call = frame_system::Call::remark(vec![]).into();
+			calls.push(call);
+		}
+		let caller = account("caller", 0, SEED);
+	}: _(RawOrigin::Signed(caller), calls)
+	verify {
+		assert_last_event::<T>(Event::BatchCompleted.into())
+	}
+
+	dispatch_as {
+		let caller = account("caller", SEED, SEED);
+		let call = Box::new(frame_system::Call::remark(vec![]).into());
+		let origin: <T as frame_system::Trait>::Origin = RawOrigin::Signed(caller).into();
+		let pallets_origin = T::PalletsOrigin::from_ref(origin.caller()).clone();
+	}: _(RawOrigin::Root, Box::new(pallets_origin), call)
 }
 
 #[cfg(test)]
@@ -67,6 +87,8 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			assert_ok!(test_benchmark_batch::<Test>());
 			assert_ok!(test_benchmark_as_derivative::<Test>());
+			assert_ok!(test_benchmark_batch_all::<Test>());
+			assert_ok!(test_benchmark_dispatch_as::<Test>());
 		});
 	}
 }
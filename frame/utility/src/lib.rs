@@ -23,11 +23,13 @@
 //!
 //! ## Overview
 //!
-//! This module contains two basic pieces of functionality:
+//! This module contains three basic pieces of functionality:
 //! - Batch dispatch: A stateless operation, allowing any origin to execute multiple calls in a
 //!   single dispatch. This can be useful to amalgamate proposals, combining `set_code` with
 //!   corresponding `set_storage`s, for efficient multiple payouts with just a single signature
-//!   verify, or in combination with one of the other two dispatch functionality.
+//!   verify, or in combination with one of the other two dispatch functionality. With
+//!   `batch_all`, the calls are executed atomically: if any of them fails, the storage changes of
+//!   all of them are reverted.
 //! - Pseudonymal dispatch: A stateless operation, allowing a signed origin to execute a call from
 //!   an alternative signed origin. Each account has 2 * 2**16 possible "pseudonyms" (alternative
 //!   account IDs) and these can be stacked. This can be useful as a key management tool, where you
@@ -35,6 +37,8 @@
 //!   it's perfectly fine to have each of them controlled by the same underlying keypair.
 //!   Derivative accounts are, for the purposes of proxy filtering considered exactly the same as
 //!   the oigin and are thus hampered with the origin's filters.
+//! - Origin dispatch: A Root-only operation, allowing a call to be executed from any origin
+//!   known to the runtime, such as a signed account or a collective.
 //!
 //! Since proxy filters are respected in all dispatches of this module, it should never need to be
 //! filtered by any proxy.
@@ -45,10 +49,15 @@
 //!
 //! #### For batch dispatch
 //! * `batch` - Dispatch multiple calls from the sender's origin.
+//! * `batch_all` - Dispatch multiple calls from the sender's origin, reverting all of them if any
+//!   fails.
 //!
 //! #### For pseudonymal dispatch
 //! * `as_derivative` - Dispatch a call from a derivative signed origin.
 //!
+//! #### For origin dispatch
+//! * `dispatch_as` - Dispatch a call from a given origin.
+//!
 //! [`Call`]: ./enum.Call.html
 //! [`Trait`]: ./trait.Trait.html
 
@@ -61,8 +70,9 @@ use sp_core::TypeId;
 use sp_io::hashing::blake2_256;
 use frame_support::{decl_module, decl_event, decl_storage, Parameter};
 use frame_support::{
-	traits::{OriginTrait, UnfilteredDispatchable},
+	traits::{OriginTrait, UnfilteredDispatchable, IsType},
 	weights::{Weight, GetDispatchInfo, DispatchClass}, dispatch::PostDispatchInfo,
+	storage::{with_transaction, TransactionOutcome},
 };
 use frame_system::{ensure_signed, ensure_root};
 use sp_runtime::{DispatchError, DispatchResult, traits::Dispatchable};
@@ -73,11 +83,15 @@ mod benchmarking;
 pub trait WeightInfo {
 	fn batch(c: u32, ) -> Weight;
 	fn as_derivative(u: u32, ) -> Weight;
+	fn batch_all(c: u32, ) -> Weight;
+	fn dispatch_as() -> Weight;
 }
 
 impl WeightInfo for () {
 	fn batch(_c: u32, ) -> Weight { 1_000_000_000 }
 	fn as_derivative(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn batch_all(_c: u32, ) -> Weight { 1_000_000_000 }
+	fn dispatch_as() -> Weight { 1_000_000_000 }
 }
 
 /// Configuration trait.
@@ -90,6 +104,10 @@ pub trait Trait: frame_system::Trait {
 		+ GetDispatchInfo + From<frame_system::Call<Self>>
 		+ UnfilteredDispatchable<Origin=Self::Origin>;
 
+	/// The caller origin, overarching type of all pallets origins.
+	type PalletsOrigin: Parameter + Into<<Self as frame_system::Trait>::Origin>
+		+ IsType<<<Self as frame_system::Trait>::Origin as OriginTrait>::PalletsOrigin>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -106,6 +124,8 @@ decl_event! {
 		BatchInterrupted(u32, DispatchError),
 		/// Batch of dispatches completed fully with no error.
 		BatchCompleted,
+		/// A call was dispatched through `dispatch_as`, with the given [result].
+		DispatchedAs(DispatchResult),
 	}
 }
 
@@ -202,6 +222,81 @@ decl_module! {
 			origin.set_caller_from(frame_system::RawOrigin::Signed(pseudonym));
 			call.dispatch(origin).map(|_| ()).map_err(|e| e.error)
 		}
+
+		/// Send a batch of dispatch calls and atomically execute them.
+		/// The whole transaction will rollback and fail if any of the calls failed.
+		///
+		/// May be called from any origin.
+		///
+		/// - `calls`: The calls to be dispatched from the same origin.
+		///
+		/// If origin is root then call are dispatch without checking origin filter. (This includes
+		/// bypassing `frame_system::Trait::BaseCallFilter`).
+		///
+		/// # <weight>
+		/// - Base weight: 14.39 + .987 * c µs
+		/// - Plus the sum of the weights of the `calls`.
+		/// - Plus one additional event. (repeat read/write)
+		/// # </weight>
+		#[weight = (
+			calls.iter()
+				.map(|call| call.get_dispatch_info().weight)
+				.fold(15_000_000, |a: Weight, n| a.saturating_add(n).saturating_add(1_000_000)),
+			{
+				let all_operational = calls.iter()
+					.map(|call| call.get_dispatch_info().class)
+					.all(|class| class == DispatchClass::Operational);
+				if all_operational {
+					DispatchClass::Operational
+				} else {
+					DispatchClass::Normal
+				}
+			},
+		)]
+		fn batch_all(origin, calls: Vec<<T as Trait>::Call>) -> DispatchResult {
+			let is_root = ensure_root(origin.clone()).is_ok();
+			with_transaction(|| {
+				for call in calls.into_iter() {
+					let result = if is_root {
+						call.dispatch_bypass_filter(origin.clone())
+					} else {
+						call.dispatch(origin.clone())
+					};
+					if let Err(e) = result {
+						return TransactionOutcome::Rollback(Err(e.error));
+					}
+				}
+				Self::deposit_event(Event::BatchCompleted);
+				TransactionOutcome::Commit(Ok(()))
+			})
+		}
+
+		/// Dispatches a function call with a provided origin.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `as_origin`: The origin to dispatch the call from.
+		/// - `call`: The call to be dispatched. Origin filters are not applied to it.
+		///
+		/// Emits `DispatchedAs` with the result of the call.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB write (event).
+		/// - Weight of derivative `call` execution + 10,000.
+		/// # </weight>
+		#[weight = (
+			call.get_dispatch_info().weight.saturating_add(10_000),
+			call.get_dispatch_info().class,
+		)]
+		fn dispatch_as(origin, as_origin: Box<T::PalletsOrigin>, call: Box<<T as Trait>::Call>) {
+			ensure_root(origin)?;
+
+			let res = call.dispatch_bypass_filter((*as_origin).into());
+
+			Self::deposit_event(Event::DispatchedAs(res.map(|_| ()).map_err(|e| e.error)));
+		}
 	}
 }
 
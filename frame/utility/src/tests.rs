@@ -115,6 +115,7 @@ impl Filter<Call> for TestBaseCallFilter {
 impl Trait for Test {
 	type Event = TestEvent;
 	type Call = Call;
+	type PalletsOrigin = OriginCaller;
 	type WeightInfo = ();
 }
 type System = frame_system::Module<Test>;
@@ -236,3 +237,79 @@ fn batch_early_exit_works() {
 		assert_eq!(Balances::free_balance(2), 15);
 	});
 }
+
+#[test]
+fn batch_all_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+		assert_ok!(
+			Utility::batch_all(Origin::signed(1), vec![
+				Call::Balances(BalancesCall::transfer(2, 5)),
+				Call::Balances(BalancesCall::transfer(2, 5))
+			]),
+		);
+		expect_event(Event::BatchCompleted);
+		assert_eq!(Balances::free_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 20);
+	});
+}
+
+#[test]
+fn batch_all_revert() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+		assert_noop!(
+			Utility::batch_all(Origin::signed(1), vec![
+				Call::Balances(BalancesCall::transfer(2, 5)),
+				Call::Balances(BalancesCall::transfer(2, 10)),
+				Call::Balances(BalancesCall::transfer(2, 5)),
+			]),
+			BalancesError::<Test, _>::InsufficientBalance
+		);
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+	});
+}
+
+#[test]
+fn batch_all_with_root_bypasses_filter() {
+	new_test_ext().execute_with(|| {
+		let k = b"a".to_vec();
+		let call = Call::System(frame_system::Call::set_storage(vec![(k.clone(), k.clone())]));
+		assert!(!TestBaseCallFilter::filter(&call));
+		assert_noop!(
+			Utility::batch_all(Origin::signed(1), vec![call.clone()]),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(Utility::batch_all(Origin::root(), vec![
+			Call::Balances(BalancesCall::force_transfer(1, 2, 5)),
+			call,
+		]));
+		assert_eq!(Balances::free_balance(2), 15);
+		assert_eq!(storage::unhashed::get_raw(&k), Some(k));
+	});
+}
+
+#[test]
+fn dispatch_as_works() {
+	new_test_ext().execute_with(|| {
+		let call = Box::new(Call::Balances(BalancesCall::transfer(2, 5)));
+		let as_origin = Box::new(OriginCaller::system(frame_system::RawOrigin::Signed(1)));
+		assert_noop!(
+			Utility::dispatch_as(Origin::signed(1), as_origin.clone(), call.clone()),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(Utility::dispatch_as(Origin::root(), as_origin, call));
+		expect_event(Event::DispatchedAs(Ok(())));
+		assert_eq!(Balances::free_balance(1), 5);
+		assert_eq!(Balances::free_balance(2), 15);
+
+		let call = Box::new(Call::Balances(BalancesCall::transfer(2, 10)));
+		let as_origin = Box::new(OriginCaller::system(frame_system::RawOrigin::Signed(1)));
+		assert_ok!(Utility::dispatch_as(Origin::root(), as_origin, call));
+		let de = DispatchError::from(BalancesError::<Test, _>::InsufficientBalance).stripped();
+		expect_event(Event::DispatchedAs(Err(de)));
+	});
+}
@@ -28,7 +28,6 @@ use sp_runtime::traits::Bounded;
 use crate::Module as Vesting;
 
 const SEED: u32 = 0;
-const MAX_LOCKS: u32 = 20;
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
@@ -41,20 +40,22 @@ fn add_locks<T: Trait>(who: &T::AccountId, n: u8) {
 	}
 }
 
-fn add_vesting_schedule<T: Trait>(who: &T::AccountId) -> Result<(), &'static str> {
+fn add_vesting_schedules<T: Trait>(who: &T::AccountId, n: u32) -> Result<(), &'static str> {
 	let locked = 100;
 	let per_block = 10;
 	let starting_block = 1;
 
 	System::<T>::set_block_number(0.into());
 
-	// Add schedule to avoid `NotVesting` error.
-	Vesting::<T>::add_vesting_schedule(
-		&who,
-		locked.into(),
-		per_block.into(),
-		starting_block.into(),
-	)?;
+	// Add schedules to avoid `NotVesting` error.
+	for _ in 0 .. n {
+		Vesting::<T>::add_vesting_schedule(
+			&who,
+			locked.into(),
+			per_block.into(),
+			starting_block.into(),
+		)?;
+	}
 	Ok(())
 }
 
@@ -63,16 +64,17 @@ benchmarks! {
 
 	vest_locked {
 		let l in 0 .. MAX_LOCKS;
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let caller = account("caller", 0, SEED);
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
 		add_locks::<T>(&caller, l as u8);
-		add_vesting_schedule::<T>(&caller)?;
+		add_vesting_schedules::<T>(&caller, s)?;
 		// At block zero, everything is vested.
 		System::<T>::set_block_number(T::BlockNumber::zero());
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&caller),
-			Some(100.into()),
+			Some((100 * s).into()),
 			"Vesting schedule not added",
 		);
 	}: vest(RawOrigin::Signed(caller.clone()))
@@ -80,18 +82,19 @@ benchmarks! {
 		// Nothing happened since everything is still vested.
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&caller),
-			Some(100.into()),
+			Some((100 * s).into()),
 			"Vesting schedule was removed",
 		);
 	}
 
 	vest_unlocked {
 		let l in 0 .. MAX_LOCKS;
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let caller = account("caller", 0, SEED);
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
 		add_locks::<T>(&caller, l as u8);
-		add_vesting_schedule::<T>(&caller)?;
+		add_vesting_schedules::<T>(&caller, s)?;
 		// At block 20, everything is unvested.
 		System::<T>::set_block_number(20.into());
 		assert_eq!(
@@ -111,17 +114,18 @@ benchmarks! {
 
 	vest_other_locked {
 		let l in 0 .. MAX_LOCKS;
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let other: T::AccountId = account("other", 0, SEED);
 		let other_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(other.clone());
 		T::Currency::make_free_balance_be(&other, BalanceOf::<T>::max_value());
 		add_locks::<T>(&other, l as u8);
-		add_vesting_schedule::<T>(&other)?;
+		add_vesting_schedules::<T>(&other, s)?;
 		// At block zero, everything is vested.
 		System::<T>::set_block_number(T::BlockNumber::zero());
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&other),
-			Some(100.into()),
+			Some((100 * s).into()),
 			"Vesting schedule not added",
 		);
 
@@ -131,19 +135,20 @@ benchmarks! {
 		// Nothing happened since everything is still vested.
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&other),
-			Some(100.into()),
+			Some((100 * s).into()),
 			"Vesting schedule was removed",
 		);
 	}
 
 	vest_other_unlocked {
 		let l in 0 .. MAX_LOCKS;
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let other: T::AccountId = account("other", 0, SEED);
 		let other_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(other.clone());
 		T::Currency::make_free_balance_be(&other, BalanceOf::<T>::max_value());
 		add_locks::<T>(&other, l as u8);
-		add_vesting_schedule::<T>(&other)?;
+		add_vesting_schedules::<T>(&other, s)?;
 		// At block 20, everything is unvested.
 		System::<T>::set_block_number(20.into());
 		assert_eq!(
@@ -165,13 +170,15 @@ benchmarks! {
 
 	vested_transfer {
 		let l in 0 .. MAX_LOCKS;
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
 
 		let caller: T::AccountId = account("caller", 0, SEED);
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
-		// Give target existing locks
+		// Give target existing locks and vesting schedules
 		add_locks::<T>(&target, l as u8);
+		add_vesting_schedules::<T>(&target, s)?;
 
 		let transfer_amount = T::MinVestedTransfer::get();
 
@@ -192,6 +199,74 @@ benchmarks! {
 			Some(T::MinVestedTransfer::get()),
 			"Lock not created",
 		);
+		assert_eq!(
+			Vesting::<T>::vesting(&target).map(|schedules| schedules.len()),
+			Some(s as usize + 1),
+			"Schedule not added",
+		);
+	}
+
+	force_vested_transfer {
+		let l in 0 .. MAX_LOCKS;
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
+
+		let source: T::AccountId = account("source", 0, SEED);
+		let source_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(source.clone());
+		T::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
+		// Give target existing locks and vesting schedules
+		add_locks::<T>(&target, l as u8);
+		add_vesting_schedules::<T>(&target, s)?;
+
+		let transfer_amount = T::MinVestedTransfer::get();
+
+		let vesting_schedule = VestingInfo {
+			locked: transfer_amount,
+			per_block: 10.into(),
+			starting_block: 1.into(),
+		};
+	}: _(RawOrigin::Root, source_lookup, target_lookup, vesting_schedule)
+	verify {
+		assert_eq!(
+			T::MinVestedTransfer::get(),
+			T::Currency::free_balance(&target),
+			"Transfer didn't happen",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&target),
+			Some(T::MinVestedTransfer::get()),
+			"Lock not created",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting(&target).map(|schedules| schedules.len()),
+			Some(s as usize + 1),
+			"Schedule not added",
+		);
+	}
+
+	merge_schedules {
+		let l in 0 .. MAX_LOCKS;
+		let s in 2 .. T::MaxVestingSchedules::get();
+
+		let caller = account("caller", 0, SEED);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		add_locks::<T>(&caller, l as u8);
+		add_vesting_schedules::<T>(&caller, s)?;
+		// At block zero, everything is vested.
+		System::<T>::set_block_number(T::BlockNumber::zero());
+	}: _(RawOrigin::Signed(caller.clone()), 0, s - 1)
+	verify {
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).map(|schedules| schedules.len()),
+			Some(s as usize - 1),
+			"Schedules not merged",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some((100 * s).into()),
+			"Merged schedule does not lock the same amount",
+		);
 	}
 }
 
@@ -209,6 +284,8 @@ mod tests {
 			assert_ok!(test_benchmark_vest_other_locked::<Test>());
 			assert_ok!(test_benchmark_vest_other_unlocked::<Test>());
 			assert_ok!(test_benchmark_vested_transfer::<Test>());
+			assert_ok!(test_benchmark_force_vested_transfer::<Test>());
+			assert_ok!(test_benchmark_merge_schedules::<Test>());
 		});
 	}
 }
@@ -26,6 +26,10 @@
 //! module ensures that there is a lock in place preventing the balance to drop below the *unvested*
 //! amount for any reason other than transaction fee payment.
 //!
+//! An account may have several vesting schedules running at once, up to `MaxVestingSchedules`.
+//! The amount locked on the account is the sum of what remains unvested under each of them, and
+//! two schedules can be merged into one with `merge_schedules` to make room for new ones.
+//!
 //! As the amount vested increases over time, the amount unvested reduces. However, locks remain in
 //! place and explicit action is needed on behalf of the user to ensure that the amount locked is
 //! equivalent to the amount remaining to be vested. This is done through a dispatchable function,
@@ -41,6 +45,8 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `vested_transfer` - Transfer funds to an account, adding a new vesting schedule over them.
+//! - `merge_schedules` - Merge two of the sender's vesting schedules into a single schedule.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Trait`]: ./trait.Trait.html
@@ -51,7 +57,7 @@ use sp_std::prelude::*;
 use sp_std::fmt::Debug;
 use codec::{Encode, Decode};
 use sp_runtime::{DispatchResult, RuntimeDebug, traits::{
-	StaticLookup, Zero, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Convert
+	StaticLookup, Zero, One, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Convert, Saturating,
 }};
 use frame_support::{decl_module, decl_event, decl_storage, decl_error, ensure, weights::Weight};
 use frame_support::traits::{
@@ -64,20 +70,27 @@ mod benchmarking;
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
+/// The number of locks on an account assumed by the weights, as benchmarked.
+const MAX_LOCKS: u32 = 20;
+
 pub trait WeightInfo {
-	fn vest_locked(l: u32, ) -> Weight;
-	fn vest_unlocked(l: u32, ) -> Weight;
-	fn vest_other_locked(l: u32, ) -> Weight;
-	fn vest_other_unlocked(l: u32, ) -> Weight;
-	fn vested_transfer(l: u32, ) -> Weight;
+	fn vest_locked(l: u32, s: u32, ) -> Weight;
+	fn vest_unlocked(l: u32, s: u32, ) -> Weight;
+	fn vest_other_locked(l: u32, s: u32, ) -> Weight;
+	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight;
+	fn vested_transfer(l: u32, s: u32, ) -> Weight;
+	fn force_vested_transfer(l: u32, s: u32, ) -> Weight;
+	fn merge_schedules(l: u32, s: u32, ) -> Weight;
 }
 
 impl WeightInfo for () {
-	fn vest_locked(_l: u32, ) -> Weight { 1_000_000_000 }
-	fn vest_unlocked(_l: u32, ) -> Weight { 1_000_000_000 }
-	fn vest_other_locked(_l: u32, ) -> Weight { 1_000_000_000 }
-	fn vest_other_unlocked(_l: u32, ) -> Weight { 1_000_000_000 }
-	fn vested_transfer(_l: u32, ) -> Weight { 1_000_000_000 }
+	fn vest_locked(_l: u32, _s: u32, ) -> Weight { 1_000_000_000 }
+	fn vest_unlocked(_l: u32, _s: u32, ) -> Weight { 1_000_000_000 }
+	fn vest_other_locked(_l: u32, _s: u32, ) -> Weight { 1_000_000_000 }
+	fn vest_other_unlocked(_l: u32, _s: u32, ) -> Weight { 1_000_000_000 }
+	fn vested_transfer(_l: u32, _s: u32, ) -> Weight { 1_000_000_000 }
+	fn force_vested_transfer(_l: u32, _s: u32, ) -> Weight { 1_000_000_000 }
+	fn merge_schedules(_l: u32, _s: u32, ) -> Weight { 1_000_000_000 }
 }
 
 pub trait Trait: frame_system::Trait {
@@ -93,6 +106,9 @@ pub trait Trait: frame_system::Trait {
 	/// The minimum amount transferred to call `vested_transfer`.
 	type MinVestedTransfer: Get<BalanceOf<Self>>;
 
+	/// The maximum number of vesting schedules an account may have at once.
+	type MaxVestingSchedules: Get<u32>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -130,19 +146,61 @@ impl<
 			Zero::zero()
 		}
 	}
+
+	/// Block number at which the schedule has fully vested, expressed as a balance.
+	///
+	/// A `per_block` of zero is treated as one, so that every schedule has an end.
+	pub fn ending_block_as_balance<
+		BlockNumberToBalance: Convert<BlockNumber, Balance>
+	>(&self) -> Balance {
+		let starting_block = BlockNumberToBalance::convert(self.starting_block);
+		let per_block = self.per_block.max(One::one());
+		let duration = if per_block >= self.locked {
+			One::one()
+		} else {
+			let remainder: Balance = if (self.locked % per_block).is_zero() {
+				Zero::zero()
+			} else {
+				One::one()
+			};
+			self.locked / per_block + remainder
+		};
+		starting_block.saturating_add(duration)
+	}
+}
+
+// A value placed in storage that represents the current version of the Vesting storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	V1,
+	V2,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Vesting {
 		/// Information regarding the vesting of a given account.
+		///
+		/// Holds at most `MaxVestingSchedules` schedules per account.
 		pub Vesting get(fn vesting):
 			map hasher(blake2_128_concat) T::AccountId
-			=> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>>;
+			=> Option<Vec<VestingInfo<BalanceOf<T>, T::BlockNumber>>>;
+
+		/// Storage version of the pallet.
+		///
+		/// New networks start with last version.
+		StorageVersion build(|_| Releases::V2): Releases;
 	}
 	add_extra_genesis {
 		config(vesting): Vec<(T::AccountId, T::BlockNumber, T::BlockNumber, BalanceOf<T>)>;
 		build(|config: &GenesisConfig<T>| {
-			use sp_runtime::traits::Saturating;
 			// Generate initial vesting configuration
 			// * who - Account which we are generating vesting configuration for
 			// * begin - Block when the account will start to vest
@@ -156,11 +214,11 @@ decl_storage! {
 				let length_as_balance = T::BlockNumberToBalance::convert(length);
 				let per_block = locked / length_as_balance.max(sp_runtime::traits::One::one());
 
-				Vesting::<T>::insert(who, VestingInfo {
+				Vesting::<T>::insert(who, vec![VestingInfo {
 					locked: locked,
 					per_block: per_block,
 					starting_block: begin
-				});
+				}]);
 				let reasons = WithdrawReason::Transfer | WithdrawReason::Reserve;
 				T::Currency::set_lock(VESTING_ID, who, locked, reasons);
 			}
@@ -184,10 +242,12 @@ decl_error! {
 	pub enum Error for Module<T: Trait> {
 		/// The account given is not vesting.
 		NotVesting,
-		/// An existing vesting schedule already exists for this account that cannot be clobbered.
-		ExistingVestingSchedule,
+		/// The account already has `MaxVestingSchedules` vesting schedules and cannot take another.
+		AtMaxVestingSchedules,
 		/// Amount being transferred is too low to create a vesting schedule.
 		AmountLow,
+		/// An index was given that does not point to one of the account's vesting schedules.
+		ScheduleIndexOutOfBounds,
 	}
 }
 
@@ -199,8 +259,15 @@ decl_module! {
 		/// The minimum amount to be transferred to create a new vesting schedule.
 		const MinVestedTransfer: BalanceOf<T> = T::MinVestedTransfer::get();
 
+		/// The maximum number of vesting schedules an account may have at once.
+		const MaxVestingSchedules: u32 = T::MaxVestingSchedules::get();
+
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			Self::migrate_to_multiple_schedules()
+		}
+
 		/// Unlock any vested funds of the sender account.
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
@@ -209,16 +276,14 @@ decl_module! {
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules, bounded by `MaxVestingSchedules`.
 		/// - DbWeight: 2 Reads, 2 Writes
 		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
-		/// - Benchmark:
-		///     - Unlocked: 48.76 + .048 * l µs (min square analysis)
-		///     - Locked: 44.43 + .284 * l µs (min square analysis)
-		/// - Using 50 µs fixed. Assuming less than 50 locks on any user, else we may want factor in number of locks.
 		/// # </weight>
-		#[weight = 50_000_000 + T::DbWeight::get().reads_writes(2, 2)]
+		#[weight = T::WeightInfo::vest_locked(MAX_LOCKS, T::MaxVestingSchedules::get())
+			.max(T::WeightInfo::vest_unlocked(MAX_LOCKS, T::MaxVestingSchedules::get()))
+		]
 		fn vest(origin) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Self::update_lock(who)
@@ -234,16 +299,14 @@ decl_module! {
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules, bounded by `MaxVestingSchedules`.
 		/// - DbWeight: 3 Reads, 3 Writes
 		///     - Reads: Vesting Storage, Balances Locks, Target Account
 		///     - Writes: Vesting Storage, Balances Locks, Target Account
-		/// - Benchmark:
-		///     - Unlocked: 44.3 + .294 * l µs (min square analysis)
-		///     - Locked: 48.16 + .103 * l µs (min square analysis)
-		/// - Using 50 µs fixed. Assuming less than 50 locks on any user, else we may want factor in number of locks.
 		/// # </weight>
-		#[weight = 50_000_000 + T::DbWeight::get().reads_writes(3, 3)]
+		#[weight = T::WeightInfo::vest_other_locked(MAX_LOCKS, T::MaxVestingSchedules::get())
+			.max(T::WeightInfo::vest_other_unlocked(MAX_LOCKS, T::MaxVestingSchedules::get()))
+		]
 		fn vest_other(origin, target: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
 			ensure_signed(origin)?;
 			Self::update_lock(T::Lookup::lookup(target)?)
@@ -257,17 +320,18 @@ decl_module! {
 		/// - `amount`: The amount of funds to transfer and will be vested.
 		/// - `schedule`: The vesting schedule attached to the transfer.
 		///
-		/// Emits `VestingCreated`.
+		/// Fails with `AtMaxVestingSchedules` if `target` already has `MaxVestingSchedules`
+		/// schedules.
+		///
+		/// Emits `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules, bounded by `MaxVestingSchedules`.
 		/// - DbWeight: 3 Reads, 3 Writes
 		///     - Reads: Vesting Storage, Balances Locks, Target Account, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, Target Account, [Sender Account]
-		/// - Benchmark: 100.3 + .365 * l µs (min square analysis)
-		/// - Using 100 µs fixed. Assuming less than 50 locks on any user, else we may want factor in number of locks.
 		/// # </weight>
-		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(3, 3)]
+		#[weight = T::WeightInfo::vested_transfer(MAX_LOCKS, T::MaxVestingSchedules::get().saturating_sub(1))]
 		pub fn vested_transfer(
 			origin,
 			target: <T::Lookup as StaticLookup>::Source,
@@ -277,12 +341,12 @@ decl_module! {
 			ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
 
 			let who = T::Lookup::lookup(target)?;
-			ensure!(!Vesting::<T>::contains_key(&who), Error::<T>::ExistingVestingSchedule);
+			ensure!(Self::can_add_vesting_schedule(&who), Error::<T>::AtMaxVestingSchedules);
 
 			T::Currency::transfer(&transactor, &who, schedule.locked, ExistenceRequirement::AllowDeath)?;
 
 			Self::add_vesting_schedule(&who, schedule.locked, schedule.per_block, schedule.starting_block)
-				.expect("user does not have an existing vesting schedule; q.e.d.");
+				.expect("user has room for another vesting schedule; q.e.d.");
 
 			Ok(())
 		}
@@ -296,17 +360,18 @@ decl_module! {
 		/// - `amount`: The amount of funds to transfer and will be vested.
 		/// - `schedule`: The vesting schedule attached to the transfer.
 		///
-		/// Emits `VestingCreated`.
+		/// Fails with `AtMaxVestingSchedules` if `target` already has `MaxVestingSchedules`
+		/// schedules.
+		///
+		/// Emits `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules, bounded by `MaxVestingSchedules`.
 		/// - DbWeight: 4 Reads, 4 Writes
 		///     - Reads: Vesting Storage, Balances Locks, Target Account, Source Account
 		///     - Writes: Vesting Storage, Balances Locks, Target Account, Source Account
-		/// - Benchmark: 100.3 + .365 * l µs (min square analysis)
-		/// - Using 100 µs fixed. Assuming less than 50 locks on any user, else we may want factor in number of locks.
 		/// # </weight>
-		#[weight = 100_000_000 + T::DbWeight::get().reads_writes(4, 4)]
+		#[weight = T::WeightInfo::force_vested_transfer(MAX_LOCKS, T::MaxVestingSchedules::get().saturating_sub(1))]
 		pub fn force_vested_transfer(
 			origin,
 			source: <T::Lookup as StaticLookup>::Source,
@@ -318,37 +383,151 @@ decl_module! {
 
 			let target = T::Lookup::lookup(target)?;
 			let source = T::Lookup::lookup(source)?;
-			ensure!(!Vesting::<T>::contains_key(&target), Error::<T>::ExistingVestingSchedule);
+			ensure!(Self::can_add_vesting_schedule(&target), Error::<T>::AtMaxVestingSchedules);
 
 			T::Currency::transfer(&source, &target, schedule.locked, ExistenceRequirement::AllowDeath)?;
 
 			Self::add_vesting_schedule(&target, schedule.locked, schedule.per_block, schedule.starting_block)
-				.expect("user does not have an existing vesting schedule; q.e.d.");
+				.expect("user has room for another vesting schedule; q.e.d.");
 
 			Ok(())
 		}
+
+		/// Merge two vesting schedules of the sender into a single schedule.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
+		/// locked under this module.
+		///
+		/// - `schedule1_index`: Index of the first schedule to merge.
+		/// - `schedule2_index`: Index of the second schedule to merge.
+		///
+		/// Any funds already vested under either schedule are unlocked first. The merged schedule
+		/// locks what remains of both, starts at the later of the current block and the two
+		/// starting blocks, and ends when the later of the two schedules would have ended. If one
+		/// of the schedules has already ended, the other is kept unchanged. Merging a schedule
+		/// with itself is a no-op.
+		///
+		/// The merged schedule is placed at the end of the account's schedules, so the indices of
+		/// other schedules may change.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of vesting schedules, bounded by `MaxVestingSchedules`.
+		/// - DbWeight: 2 Reads, 2 Writes
+		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
+		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
+		/// # </weight>
+		#[weight = T::WeightInfo::merge_schedules(MAX_LOCKS, T::MaxVestingSchedules::get())]
+		pub fn merge_schedules(origin, schedule1_index: u32, schedule2_index: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if schedule1_index == schedule2_index { return Ok(()) }
+
+			let mut schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+			let (first, second) = (
+				schedule1_index.min(schedule2_index) as usize,
+				schedule1_index.max(schedule2_index) as usize,
+			);
+			ensure!(second < schedules.len(), Error::<T>::ScheduleIndexOutOfBounds);
+
+			let now = <frame_system::Module<T>>::block_number();
+			// Remove the later index first so that the earlier one stays valid.
+			let schedule2 = schedules.remove(second);
+			let schedule1 = schedules.remove(first);
+			if let Some(merged) = Self::merge_vesting_info(now, schedule1, schedule2) {
+				schedules.push(merged);
+			}
+			Vesting::<T>::insert(&who, schedules);
+
+			Self::update_lock(who)
+		}
 	}
 }
 
 impl<T: Trait> Module<T> {
 	/// (Re)set or remove the module's currency lock on `who`'s account in accordance with their
 	/// current unvested amount.
+	///
+	/// Schedules that have fully vested are removed.
 	fn update_lock(who: T::AccountId) -> DispatchResult {
-		let vesting = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+		let schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
 		let now = <frame_system::Module<T>>::block_number();
-		let locked_now = vesting.locked_at::<T::BlockNumberToBalance>(now);
-
-		if locked_now.is_zero() {
+		let mut locked_now = BalanceOf::<T>::zero();
+		let schedules = schedules.into_iter()
+			.filter(|schedule| {
+				let locked = schedule.locked_at::<T::BlockNumberToBalance>(now);
+				locked_now = locked_now.saturating_add(locked);
+				!locked.is_zero()
+			})
+			.collect::<Vec<_>>();
+
+		if schedules.is_empty() {
 			T::Currency::remove_lock(VESTING_ID, &who);
 			Vesting::<T>::remove(&who);
 			Self::deposit_event(RawEvent::VestingCompleted(who));
 		} else {
 			let reasons = WithdrawReason::Transfer | WithdrawReason::Reserve;
 			T::Currency::set_lock(VESTING_ID, &who, locked_now, reasons);
+			Vesting::<T>::insert(&who, schedules);
 			Self::deposit_event(RawEvent::VestingUpdated(who, locked_now));
 		}
 		Ok(())
 	}
+
+	/// Whether `who` has room for another vesting schedule.
+	pub fn can_add_vesting_schedule(who: &T::AccountId) -> bool {
+		Self::vesting(who).map_or(0, |schedules| schedules.len()) < T::MaxVestingSchedules::get() as usize
+	}
+
+	/// Merge two vesting schedules into one as of block `now`.
+	///
+	/// Returns `None` if both schedules have already ended.
+	fn merge_vesting_info(
+		now: T::BlockNumber,
+		schedule1: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		schedule2: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>> {
+		let schedule1_ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>();
+		let schedule2_ending_block = schedule2.ending_block_as_balance::<T::BlockNumberToBalance>();
+		let now_as_balance = T::BlockNumberToBalance::convert(now);
+
+		match (schedule1_ending_block <= now_as_balance, schedule2_ending_block <= now_as_balance) {
+			(true, true) => return None,
+			(true, false) => return Some(schedule2),
+			(false, true) => return Some(schedule1),
+			_ => {},
+		}
+
+		let locked = schedule1.locked_at::<T::BlockNumberToBalance>(now)
+			.saturating_add(schedule2.locked_at::<T::BlockNumberToBalance>(now));
+		let ending_block = schedule1_ending_block.max(schedule2_ending_block);
+		let starting_block = now.max(schedule1.starting_block).max(schedule2.starting_block);
+		let duration = ending_block
+			.saturating_sub(T::BlockNumberToBalance::convert(starting_block))
+			.max(One::one());
+		let per_block = (locked / duration).max(One::one());
+
+		Some(VestingInfo { locked, per_block, starting_block })
+	}
+
+	/// Move every account from a single vesting schedule to a list of schedules.
+	///
+	/// Does nothing if the storage has already been migrated.
+	pub fn migrate_to_multiple_schedules() -> Weight {
+		if StorageVersion::get() != Releases::V1 {
+			return 0
+		}
+
+		let migrated = sp_std::cell::Cell::new(0 as Weight);
+		Vesting::<T>::translate::<VestingInfo<BalanceOf<T>, T::BlockNumber>, _>(|_, schedule| {
+			migrated.set(migrated.get() + 1);
+			Some(vec![schedule])
+		});
+		StorageVersion::put(Releases::V2);
+
+		let migrated = migrated.get();
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
 }
 
 impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
@@ -359,18 +538,20 @@ impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
 
 	/// Get the amount that is currently being vested and cannot be transferred out of this account.
 	fn vesting_balance(who: &T::AccountId) -> Option<BalanceOf<T>> {
-		if let Some(v) = Self::vesting(who) {
+		if let Some(schedules) = Self::vesting(who) {
 			let now = <frame_system::Module<T>>::block_number();
-			let locked_now = v.locked_at::<T::BlockNumberToBalance>(now);
+			let locked_now = schedules.iter().fold(Zero::zero(), |total: BalanceOf<T>, schedule| {
+				total.saturating_add(schedule.locked_at::<T::BlockNumberToBalance>(now))
+			});
 			Some(T::Currency::free_balance(who).min(locked_now))
 		} else {
 			None
 		}
 	}
 
-	/// Adds a vesting schedule to a given account.
+	/// Adds a vesting schedule to a given account, alongside any it already has.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
+	/// If the account already has `MaxVestingSchedules` vesting schedules, an `Err` is returned
 	/// and nothing is updated.
 	///
 	/// On success, a linearly reducing amount of funds will be locked. In order to realise any
@@ -385,21 +566,19 @@ impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
 		starting_block: T::BlockNumber
 	) -> DispatchResult {
 		if locked.is_zero() { return Ok(()) }
-		if Vesting::<T>::contains_key(who) {
-			Err(Error::<T>::ExistingVestingSchedule)?
-		}
+		ensure!(Self::can_add_vesting_schedule(who), Error::<T>::AtMaxVestingSchedules);
 		let vesting_schedule = VestingInfo {
 			locked,
 			per_block,
 			starting_block
 		};
-		Vesting::<T>::insert(who, vesting_schedule);
+		Vesting::<T>::append(who, vesting_schedule);
 		// it can't fail, but even if somehow it did, we don't really care.
 		let _ = Self::update_lock(who.clone());
 		Ok(())
 	}
 
-	/// Remove a vesting schedule for a given account.
+	/// Remove all vesting schedules for a given account.
 	fn remove_vesting_schedule(who: &T::AccountId) {
 		Vesting::<T>::remove(who);
 		// it can't fail, but even if somehow it did, we don't really care.
@@ -473,12 +652,14 @@ mod tests {
 	}
 	parameter_types! {
 		pub const MinVestedTransfer: u64 = 256 * 2;
+		pub const MaxVestingSchedules: u32 = 3;
 	}
 	impl Trait for Test {
 		type Event = ();
 		type Currency = Balances;
 		type BlockNumberToBalance = Identity;
 		type MinVestedTransfer = MinVestedTransfer;
+		type MaxVestingSchedules = MaxVestingSchedules;
 		type WeightInfo = ();
 	}
 	type System = frame_system::Module<Test>;
@@ -560,9 +741,9 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&1), Some(user1_vesting_schedule)); // Account 1 has a vesting schedule
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule)); // Account 2 has a vesting schedule
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule)); // Account 12 has a vesting schedule
+				assert_eq!(Vesting::vesting(&1), Some(vec![user1_vesting_schedule])); // Account 1 has a vesting schedule
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule])); // Account 2 has a vesting schedule
+				assert_eq!(Vesting::vesting(&12), Some(vec![user12_vesting_schedule])); // Account 12 has a vesting schedule
 
 				// Account 1 has only 128 units vested from their illiquid 256 * 5 units at block 1
 				assert_eq!(Vesting::vesting_balance(&1), Some(128 * 9));
@@ -683,7 +864,7 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule));
+				assert_eq!(Vesting::vesting(&12), Some(vec![user12_vesting_schedule]));
 
 				// Account 12 can still send liquid funds
 				assert_ok!(Balances::transfer(Some(12).into(), 3, 256 * 5));
@@ -710,7 +891,7 @@ mod tests {
 				};
 				assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, new_vesting_schedule));
 				// Now account 4 should have vesting.
-				assert_eq!(Vesting::vesting(&4), Some(new_vesting_schedule));
+				assert_eq!(Vesting::vesting(&4), Some(vec![new_vesting_schedule]));
 				// Ensure the transfer happened correctly.
 				let user3_free_balance_updated = Balances::free_balance(&3);
 				assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -749,17 +930,23 @@ mod tests {
 					per_block: 256, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule));
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule]));
 
-				// The vesting schedule we will try to create, fails due to pre-existence of schedule.
+				// Fill up account 2 with the maximum number of schedules.
 				let new_vesting_schedule = VestingInfo {
 					locked: 256 * 5,
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
+				assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule));
+				assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule));
+				let user2_free_balance = Balances::free_balance(&2);
+				let user4_free_balance = Balances::free_balance(&4);
+
+				// The vesting schedule we will try to create, fails due to too many schedules.
 				assert_noop!(
 					Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule),
-					Error::<Test>::ExistingVestingSchedule,
+					Error::<Test>::AtMaxVestingSchedules,
 				);
 
 				// Fails due to too low transfer amount.
@@ -774,8 +961,8 @@ mod tests {
 				);
 
 				// Verify no currency transfer happened.
-				assert_eq!(user2_free_balance, 256 * 20);
-				assert_eq!(user4_free_balance, 256 * 40);
+				assert_eq!(Balances::free_balance(&2), user2_free_balance);
+				assert_eq!(Balances::free_balance(&4), user4_free_balance);
 			});
 	}
 
@@ -800,7 +987,7 @@ mod tests {
 				assert_noop!(Vesting::force_vested_transfer(Some(4).into(), 3, 4, new_vesting_schedule), BadOrigin);
 				assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 4, new_vesting_schedule));
 				// Now account 4 should have vesting.
-				assert_eq!(Vesting::vesting(&4), Some(new_vesting_schedule));
+				assert_eq!(Vesting::vesting(&4), Some(vec![new_vesting_schedule]));
 				// Ensure the transfer happened correctly.
 				let user3_free_balance_updated = Balances::free_balance(&3);
 				assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -839,17 +1026,23 @@ mod tests {
 					per_block: 256, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule));
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule]));
 
-				// The vesting schedule we will try to create, fails due to pre-existence of schedule.
+				// Fill up account 2 with the maximum number of schedules.
 				let new_vesting_schedule = VestingInfo {
 					locked: 256 * 5,
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
+				assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 2, new_vesting_schedule));
+				assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 2, new_vesting_schedule));
+				let user2_free_balance = Balances::free_balance(&2);
+				let user4_free_balance = Balances::free_balance(&4);
+
+				// The vesting schedule we will try to create, fails due to too many schedules.
 				assert_noop!(
 					Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 2, new_vesting_schedule),
-					Error::<Test>::ExistingVestingSchedule,
+					Error::<Test>::AtMaxVestingSchedules,
 				);
 
 				// Fails due to too low transfer amount.
@@ -864,8 +1057,203 @@ mod tests {
 				);
 
 				// Verify no currency transfer happened.
-				assert_eq!(user2_free_balance, 256 * 20);
-				assert_eq!(user4_free_balance, 256 * 40);
+				assert_eq!(Balances::free_balance(&2), user2_free_balance);
+				assert_eq!(Balances::free_balance(&4), user4_free_balance);
+			});
+	}
+
+	#[test]
+	fn multiple_schedules_lock_their_sum() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let schedule1 = VestingInfo {
+					locked: 256 * 5,
+					per_block: 64, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				let schedule2 = VestingInfo {
+					locked: 256 * 10,
+					per_block: 256, // Vesting over 10 blocks
+					starting_block: 5,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, schedule1));
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, schedule2));
+				assert_eq!(Vesting::vesting(&4), Some(vec![schedule1, schedule2]));
+				// Account 4 has both transfers locked.
+				assert_eq!(Vesting::vesting_balance(&4), Some(256 * 15));
+
+				System::set_block_number(10);
+				// Only the second schedule has started vesting.
+				assert_eq!(Vesting::vesting_balance(&4), Some(256 * 5 + 256 * 5));
+
+				System::set_block_number(15);
+				// The second schedule has fully vested, the first has vested 5 * 64 units.
+				assert_eq!(Vesting::vesting_balance(&4), Some(256 * 5 - 5 * 64));
+				assert_ok!(Vesting::vest(Some(4).into()));
+				// The completed schedule is removed.
+				assert_eq!(Vesting::vesting(&4), Some(vec![schedule1]));
+
+				System::set_block_number(30);
+				assert_ok!(Vesting::vest(Some(4).into()));
+				assert_eq!(Vesting::vesting(&4), None);
+				assert_ok!(Balances::transfer(Some(4).into(), 3, 256 * 55));
+			});
+	}
+
+	#[test]
+	fn add_vesting_schedule_appends() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let user2_vesting_schedule = VestingInfo {
+					locked: 256 * 20,
+					per_block: 256, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 64, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				assert_ok!(Vesting::add_vesting_schedule(&2, 256 * 5, 64, 10));
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule, new_vesting_schedule]));
+
+				assert_ok!(Vesting::add_vesting_schedule(&2, 256 * 5, 64, 10));
+				assert_noop!(
+					Vesting::add_vesting_schedule(&2, 256 * 5, 64, 10),
+					Error::<Test>::AtMaxVestingSchedules,
+				);
+			});
+	}
+
+	#[test]
+	fn merge_ongoing_schedules() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				// Account 2 vests from block 10 to 30, this one vests from block 5 to 45.
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 10,
+					per_block: 64,
+					starting_block: 5,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+
+				System::set_block_number(20);
+				// 256 * 10 units left of the first schedule and 256 * 10 - 15 * 64 of the second.
+				let locked = 256 * 10 + 256 * 10 - 15 * 64;
+				assert_eq!(Vesting::vesting_balance(&2), Some(locked));
+
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+				// The merged schedule starts now and ends at block 45.
+				let merged_schedule = VestingInfo {
+					locked,
+					per_block: locked / 25,
+					starting_block: 20,
+				};
+				assert_eq!(Vesting::vesting(&2), Some(vec![merged_schedule]));
+				assert_eq!(Vesting::vesting_balance(&2), Some(locked));
+			});
+	}
+
+	#[test]
+	fn merge_schedules_that_have_not_started() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				// Account 2 vests from block 10 to 30, this one vests from block 10 to 50.
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 10,
+					per_block: 64,
+					starting_block: 10,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 1, 0));
+				let merged_schedule = VestingInfo {
+					locked: 256 * 30,
+					per_block: 256 * 30 / 40,
+					starting_block: 10,
+				};
+				assert_eq!(Vesting::vesting(&2), Some(vec![merged_schedule]));
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 30));
+			});
+	}
+
+	#[test]
+	fn merge_with_ended_schedule_keeps_the_other() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 10,
+					per_block: 64,
+					starting_block: 5,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+
+				// Account 2's original schedule has ended by block 35.
+				System::set_block_number(35);
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+				assert_eq!(Vesting::vesting(&2), Some(vec![new_vesting_schedule]));
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 10 - 30 * 64));
+			});
+	}
+
+	#[test]
+	fn merge_schedules_correctly_fails() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				// Account 4 is not vesting.
+				assert_noop!(Vesting::merge_schedules(Some(4).into(), 0, 1), Error::<Test>::NotVesting);
+				// Account 2 has only one schedule.
+				assert_noop!(
+					Vesting::merge_schedules(Some(2).into(), 0, 1),
+					Error::<Test>::ScheduleIndexOutOfBounds,
+				);
+				// Merging a schedule with itself does nothing.
+				let user2_vesting_schedule = VestingInfo {
+					locked: 256 * 20,
+					per_block: 256,
+					starting_block: 10,
+				};
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 0));
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule]));
+			});
+	}
+
+	#[test]
+	fn migration_to_multiple_schedules_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				use frame_support::storage::{IterableStorageMap, unhashed};
+
+				// Put the genesis schedules back in the old, single schedule format.
+				let schedules = <super::Vesting<Test>>::iter()
+					.map(|(who, schedules)| (who, schedules[0]))
+					.collect::<Vec<_>>();
+				assert_eq!(schedules.len(), 3);
+				for (who, schedule) in &schedules {
+					unhashed::put(&<super::Vesting<Test>>::hashed_key_for(who), schedule);
+				}
+				StorageVersion::put(Releases::V1);
+
+				Vesting::migrate_to_multiple_schedules();
+
+				for (who, schedule) in schedules {
+					assert_eq!(Vesting::vesting(&who), Some(vec![schedule]));
+				}
+				assert_eq!(StorageVersion::get(), Releases::V2);
 			});
 	}
 }
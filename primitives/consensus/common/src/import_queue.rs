@@ -29,6 +29,7 @@
 use std::collections::HashMap;
 
 use sp_runtime::{Justification, traits::{Block as BlockT, Header as _, NumberFor}};
+use sp_core::storage::Storage;
 
 use crate::{
 	error::Error as ConsensusError,
@@ -81,6 +82,8 @@ pub struct IncomingBlock<B: BlockT> {
 	pub allow_missing_state: bool,
 	/// Re-validate existing block.
 	pub import_existing: bool,
+	/// Full state of the block, imported instead of executing the block on its parent's state.
+	pub import_state: Option<Storage>,
 }
 
 /// Type of keys in the blockchain cache that consensus module could use for its needs.
@@ -287,6 +290,7 @@ pub(crate) fn import_single_block_metered<B: BlockT, V: Verifier<B>, Transaction
 		cache.extend(keys.into_iter());
 	}
 	import_block.allow_missing_state = block.allow_missing_state;
+	import_block.import_state = block.import_state;
 
 	import_handler(import_handle.import_block(import_block.convert_transaction(), cache))
 }
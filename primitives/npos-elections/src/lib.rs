@@ -41,7 +41,6 @@ mod mock;
 mod tests;
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
-#[cfg(feature = "std")]
 use codec::{Encode, Decode};
 
 mod node;
@@ -86,6 +85,8 @@ pub enum Error {
 	CompactInvalidIndex,
 	/// An error occurred in some arithmetic operation.
 	ArithmeticError(&'static str),
+	/// An assignment has an edge to a target that is not among the winners.
+	InvalidSupportEdge,
 }
 
 /// A type which is used in the API of this crate as a numeric weight of a vote, most often the
@@ -305,8 +306,8 @@ impl<AccountId> StakedAssignment<AccountId> {
 ///
 /// This, at the current version, resembles the `Exposure` defined in the Staking pallet, yet
 /// they do not necessarily have to be the same.
-#[derive(Default, Debug)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Eq, PartialEq))]
+#[derive(Default, Debug, Clone, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Support<AccountId> {
 	/// Total support.
 	pub total: ExtendedBalance,
@@ -317,6 +318,43 @@ pub struct Support<AccountId> {
 /// A linkage from a candidate and its [`Support`].
 pub type SupportMap<A> = BTreeMap<A, Support<A>>;
 
+/// A flat variant of [`SupportMap`].
+///
+/// The main advantage of this is that it is encodable, and can be passed between modules.
+pub type Supports<A> = Vec<(A, Support<A>)>;
+
+/// Helper trait to convert from a support map to a flat support vector.
+pub trait FlattenSupportMap<A> {
+	/// Flatten the support.
+	fn flatten(self) -> Supports<A>;
+}
+
+impl<A> FlattenSupportMap<A> for SupportMap<A> {
+	fn flatten(self) -> Supports<A> {
+		self.into_iter().collect::<Vec<_>>()
+	}
+}
+
+/// Build the flat [`Supports`] of the given `winners` from `assignments`.
+///
+/// Returns an error if any of the edges in `assignments` points to a target that is not a winner.
+pub fn to_supports<A: IdentifierT>(
+	winners: &[A],
+	assignments: &[StakedAssignment<A>],
+) -> Result<Supports<A>, Error> {
+	let (support_map, errors) = build_support_map(winners, assignments);
+	if errors > 0 {
+		Err(Error::InvalidSupportEdge)
+	} else {
+		Ok(support_map.flatten())
+	}
+}
+
+/// Evaluate a flat support vector. This is the same as [`evaluate_support`], for [`Supports`].
+pub fn evaluate_supports<A>(supports: &Supports<A>) -> ElectionScore {
+	evaluate_support_iter(supports.iter().map(|(_, s)| s))
+}
+
 /// Perform election based on Phragmén algorithm.
 ///
 /// Returns an `Option` the set of winners and their detailed support ratio from each voter if
@@ -600,13 +638,20 @@ pub fn build_support_map<AccountId>(
 /// `O(E)` where `E` is the total number of edges.
 pub fn evaluate_support<AccountId>(
 	support: &SupportMap<AccountId>,
+) -> ElectionScore {
+	evaluate_support_iter(support.values())
+}
+
+/// Evaluate an iterator of supports. See [`evaluate_support`].
+fn evaluate_support_iter<'a, AccountId: 'a>(
+	supports: impl Iterator<Item = &'a Support<AccountId>>,
 ) -> ElectionScore {
 	let mut min_support = ExtendedBalance::max_value();
 	let mut sum: ExtendedBalance = Zero::zero();
 	// NOTE: The third element might saturate but fine for now since this will run on-chain and need
 	// to be fast.
 	let mut sum_squared: ExtendedBalance = Zero::zero();
-	for (_, support) in support.iter() {
+	for support in supports {
 		sum = sum.saturating_add(support.total);
 		let squared = support.total.saturating_mul(support.total);
 		sum_squared = sum_squared.saturating_add(squared);
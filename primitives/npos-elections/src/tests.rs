@@ -20,7 +20,8 @@
 use crate::mock::*;
 use crate::{
 	seq_phragmen, balance_solution, build_support_map, is_score_better, helpers::*,
-	Support, StakedAssignment, Assignment, ElectionResult, ExtendedBalance,
+	Support, StakedAssignment, Assignment, ElectionResult, ExtendedBalance, to_supports,
+	evaluate_support, evaluate_supports, Error,
 };
 use substrate_test_utils::assert_eq_uvec;
 use sp_arithmetic::{Perbill, Permill, Percent, PerU16};
@@ -648,6 +649,31 @@ fn duplicate_target_is_ignored_when_winner() {
 	);
 }
 
+#[test]
+fn to_supports_works_and_matches_support_map() {
+	let winners = vec![1, 2];
+	let assignments = vec![
+		StakedAssignment { who: 10, distribution: vec![(1, 50), (2, 50)] },
+		StakedAssignment { who: 20, distribution: vec![(2, 20)] },
+	];
+
+	let supports = to_supports(&winners, &assignments).unwrap();
+	assert_eq!(
+		supports,
+		vec![
+			(1, Support { total: 50, voters: vec![(10, 50)] }),
+			(2, Support { total: 70, voters: vec![(10, 50), (20, 20)] }),
+		],
+	);
+
+	let (support_map, _) = build_support_map(&winners, &assignments);
+	assert_eq!(evaluate_supports(&supports), evaluate_support(&support_map));
+
+	// an edge to a non-winner is an error.
+	let assignments = vec![StakedAssignment { who: 10, distribution: vec![(3, 10)] }];
+	assert_eq!(to_supports(&winners, &assignments), Err(Error::InvalidSupportEdge));
+}
+
 mod assignment_convert_normalize {
 	use super::*;
 	#[test]
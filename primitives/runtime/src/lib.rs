@@ -32,9 +32,6 @@ pub use serde;
 #[doc(hidden)]
 pub use sp_std;
 
-#[doc(hidden)]
-pub use paste;
-
 #[doc(hidden)]
 pub use sp_application_crypto as app_crypto;
 
@@ -570,116 +567,6 @@ pub fn verify_encoded_lazy<V: Verify, T: codec::Encode>(
 	)
 }
 
-/// Helper macro for `impl_outer_config`
-#[macro_export]
-macro_rules! __impl_outer_config_types {
-	// Generic + Instance
-	(
-		$concrete:ident $config:ident $snake:ident { $instance:ident } < $ignore:ident >;
-		$( $rest:tt )*
-	) => {
-		#[cfg(any(feature = "std", test))]
-		pub type $config = $snake::GenesisConfig<$concrete, $snake::$instance>;
-		$crate::__impl_outer_config_types! { $concrete $( $rest )* }
-	};
-	// Generic
-	(
-		$concrete:ident $config:ident $snake:ident < $ignore:ident >;
-		$( $rest:tt )*
-	) => {
-		#[cfg(any(feature = "std", test))]
-		pub type $config = $snake::GenesisConfig<$concrete>;
-		$crate::__impl_outer_config_types! { $concrete $( $rest )* }
-	};
-	// No Generic and maybe Instance
-	(
-		$concrete:ident $config:ident $snake:ident $( { $instance:ident } )?;
-		$( $rest:tt )*
-	) => {
-		#[cfg(any(feature = "std", test))]
-		pub type $config = $snake::GenesisConfig;
-		$crate::__impl_outer_config_types! { $concrete $( $rest )* }
-	};
-	($concrete:ident) => ()
-}
-
-/// Implement the output "meta" module configuration struct,
-/// which is basically:
-/// pub struct GenesisConfig {
-/// 	rust_module_one: Option<ModuleOneConfig>,
-/// 	...
-/// }
-#[macro_export]
-macro_rules! impl_outer_config {
-	(
-		pub struct $main:ident for $concrete:ident {
-			$( $config:ident =>
-				$snake:ident $( $instance:ident )? $( <$generic:ident> )*, )*
-		}
-	) => {
-		$crate::__impl_outer_config_types! {
-			$concrete $( $config $snake $( { $instance } )? $( <$generic> )*; )*
-		}
-
-		$crate::paste::item! {
-			#[cfg(any(feature = "std", test))]
-			#[derive($crate::serde::Serialize, $crate::serde::Deserialize)]
-			#[serde(rename_all = "camelCase")]
-			#[serde(deny_unknown_fields)]
-			pub struct $main {
-				$(
-					pub [< $snake $(_ $instance )? >]: Option<$config>,
-				)*
-			}
-			#[cfg(any(feature = "std", test))]
-			impl $crate::BuildStorage for $main {
-				fn assimilate_storage(
-					&self,
-					storage: &mut $crate::Storage,
-				) -> std::result::Result<(), String> {
-					$(
-						if let Some(ref extra) = self.[< $snake $(_ $instance )? >] {
-							$crate::impl_outer_config! {
-								@CALL_FN
-								$concrete;
-								$snake;
-								$( $instance )?;
-								extra;
-								storage;
-							}
-						}
-					)*
-					Ok(())
-				}
-			}
-		}
-	};
-	(@CALL_FN
-		$runtime:ident;
-		$module:ident;
-		$instance:ident;
-		$extra:ident;
-		$storage:ident;
-	) => {
-		$crate::BuildModuleGenesisStorage::<$runtime, $module::$instance>::build_module_genesis_storage(
-			$extra,
-			$storage,
-		)?;
-	};
-	(@CALL_FN
-		$runtime:ident;
-		$module:ident;
-		;
-		$extra:ident;
-		$storage:ident;
-	) => {
-		$crate::BuildModuleGenesisStorage::<$runtime, $module::__InherentHiddenInstance>::build_module_genesis_storage(
-			$extra,
-			$storage,
-		)?;
-	}
-}
-
 /// Checks that `$x` is equal to `$y` with an error rate of `$error`.
 ///
 /// # Example
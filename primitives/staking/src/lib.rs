@@ -17,7 +17,82 @@
 //! A crate which contains primitives that are useful for implementation that uses staking
 //! approaches in general. Definitions related to sessions, slashing, etc go here.
 
+use sp_std::{prelude::*, collections::btree_map::BTreeMap};
+use sp_runtime::{DispatchResult, DispatchError};
+
 pub mod offence;
 
 /// Simple index type with which we can count sessions.
 pub type SessionIndex = u32;
+
+/// Counter for the number of eras that have passed.
+pub type EraIndex = u32;
+
+/// Something that is notified when a staker is slashed.
+pub trait OnStakerSlash<AccountId, Balance> {
+	/// A slash was applied to the bonded funds of `stash`.
+	///
+	/// `slashed_active` is the amount slashed from its active bond and `slashed_unlocking` the
+	/// amount slashed from its unlocking funds, keyed by the era at which they unlock.
+	fn on_slash(
+		stash: &AccountId,
+		slashed_active: Balance,
+		slashed_unlocking: &BTreeMap<EraIndex, Balance>,
+	);
+}
+
+impl<AccountId, Balance> OnStakerSlash<AccountId, Balance> for () {
+	fn on_slash(_: &AccountId, _: Balance, _: &BTreeMap<EraIndex, Balance>) {}
+}
+
+/// A generic interface to a staking system, for use by other pallets which stake on behalf of
+/// accounts.
+pub trait StakingInterface {
+	/// Balance type used by the staking system.
+	type Balance;
+
+	/// Account identifier used by the staking system.
+	type AccountId;
+
+	/// The minimum amount which can be bonded.
+	fn minimum_bond() -> Self::Balance;
+
+	/// Number of eras that unbonded funds must remain locked for.
+	fn bonding_duration() -> EraIndex;
+
+	/// The current era index, zero if there is none yet.
+	fn current_era() -> EraIndex;
+
+	/// The amount actively bonded through `controller`, if it controls a stash.
+	fn active_stake(controller: &Self::AccountId) -> Option<Self::Balance>;
+
+	/// The amount bonded through `controller`, active or unlocking, if it controls a stash.
+	fn total_stake(controller: &Self::AccountId) -> Option<Self::Balance>;
+
+	/// Bond `value` of the free balance of `stash`, managed by `controller`.
+	///
+	/// Rewards of the stash are paid into `controller`.
+	fn bond(
+		stash: Self::AccountId,
+		controller: Self::AccountId,
+		value: Self::Balance,
+	) -> DispatchResult;
+
+	/// Bond up to `extra` more of the free balance of `stash`.
+	fn bond_extra(stash: Self::AccountId, extra: Self::Balance) -> DispatchResult;
+
+	/// Schedule `value` of the active bond of the stash of `controller` to be unlocked after the
+	/// bonding duration.
+	fn unbond(controller: Self::AccountId, value: Self::Balance) -> DispatchResult;
+
+	/// Unlock the unbonded funds of the stash of `controller` whose bonding duration has passed.
+	///
+	/// Returns whether the stash was removed from the staking system.
+	fn withdraw_unbonded(
+		controller: Self::AccountId,
+		num_slashing_spans: u32,
+	) -> Result<bool, DispatchError>;
+
+	/// Nominate `targets` with the stash of `controller`.
+	fn nominate(controller: Self::AccountId, targets: Vec<Self::AccountId>) -> DispatchResult;
+}
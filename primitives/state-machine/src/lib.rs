@@ -688,6 +688,142 @@ where
 	Ok(proving_backend.extract_proof())
 }
 
+/// Generate a range storage read proof.
+///
+/// The proof covers the key-values of the top trie, or of the child trie `child_info`, in
+/// lexicographic order of keys, starting after `start_at` (or at the first key if `None`). Reading
+/// stops after the key-value which brings the total size of read keys and values to `size_limit`
+/// bytes or more. Returns the proof and the number of key-values it covers.
+pub fn prove_range_read_with_size<B, H>(
+	mut backend: B,
+	child_info: Option<&ChildInfo>,
+	start_at: Option<&[u8]>,
+	size_limit: usize,
+) -> Result<(StorageProof, u32), Box<dyn Error>>
+where
+	B: Backend<H>,
+	H: Hasher,
+	H::Out: Ord + Codec,
+{
+	let trie_backend = backend.as_trie_backend()
+		.ok_or_else(|| Box::new(ExecutionError::UnableToGenerateProof) as Box<dyn Error>)?;
+	prove_range_read_with_size_on_trie_backend(trie_backend, child_info, start_at, size_limit)
+}
+
+/// Generate a range storage read proof on pre-created trie backend.
+pub fn prove_range_read_with_size_on_trie_backend<S, H>(
+	trie_backend: &TrieBackend<S, H>,
+	child_info: Option<&ChildInfo>,
+	start_at: Option<&[u8]>,
+	size_limit: usize,
+) -> Result<(StorageProof, u32), Box<dyn Error>>
+where
+	S: trie_backend_essence::TrieBackendStorage<H>,
+	H: Hasher,
+	H::Out: Ord + Codec,
+{
+	let proving_backend = proving_backend::ProvingBackend::<S, H>::new(trie_backend);
+	let mut count = 0;
+	let mut size = 0;
+	for_key_values_in_range(&proving_backend, child_info, start_at, |key, value| {
+		count += 1;
+		size += key.len() + value.len();
+		size < size_limit
+	}).map_err(|e| Box::new(e) as Box<dyn Error>)?;
+	Ok((proving_backend.extract_proof(), count))
+}
+
+/// Check a range storage read proof, generated by `prove_range_read_with_size` call.
+///
+/// Returns the key-values the proof covers, and whether they reach the end of the trie.
+pub fn read_range_proof_check<H>(
+	root: H::Out,
+	proof: StorageProof,
+	child_info: Option<&ChildInfo>,
+	start_at: Option<&[u8]>,
+) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, bool), Box<dyn Error>>
+where
+	H: Hasher,
+	H::Out: Ord + Codec,
+{
+	let proving_backend = create_proof_check_backend::<H>(root, proof)?;
+	read_range_proof_check_on_proving_backend(&proving_backend, child_info, start_at)
+}
+
+/// Check a range storage read proof on pre-created proving backend.
+///
+/// Key-values are read until the proof misses the trie nodes to read the next one, so a proof
+/// covering more key-values than requested is accepted. A proof covering no key-value at all and
+/// not proving the end of the trie is invalid.
+pub fn read_range_proof_check_on_proving_backend<H>(
+	proving_backend: &TrieBackend<MemoryDB<H>, H>,
+	child_info: Option<&ChildInfo>,
+	start_at: Option<&[u8]>,
+) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, bool), Box<dyn Error>>
+where
+	H: Hasher,
+	H::Out: Ord + Codec,
+{
+	let mut entries = Vec::new();
+	let result = for_key_values_in_range(proving_backend, child_info, start_at, |key, value| {
+		entries.push((key.to_vec(), value.to_vec()));
+		true
+	});
+	match result {
+		Ok(complete) => Ok((entries, complete)),
+		Err(_) if !entries.is_empty() => Ok((entries, false)),
+		Err(e) => Err(Box::new(e) as Box<dyn Error>),
+	}
+}
+
+/// Call `f` on the key-values of the top trie, or of the child trie `child_info`, in
+/// lexicographic order of keys, starting after `start_at` (or at the first key if `None`), until
+/// it returns `false`.
+///
+/// Returns whether the end of the trie was reached.
+fn for_key_values_in_range<B, H>(
+	backend: &B,
+	child_info: Option<&ChildInfo>,
+	start_at: Option<&[u8]>,
+	mut f: impl FnMut(&[u8], &[u8]) -> bool,
+) -> Result<bool, String>
+where
+	B: Backend<H, Error = String>,
+	H: Hasher,
+{
+	let storage = |key: &[u8]| match child_info {
+		Some(child_info) => backend.child_storage(child_info, key),
+		None => backend.storage(key),
+	};
+	let next_key = |key: &[u8]| match child_info {
+		Some(child_info) => backend.next_child_storage_key(child_info, key),
+		None => backend.next_storage_key(key),
+	};
+
+	// The empty key is the only one which does not follow any other key.
+	if start_at.is_none() {
+		if let Some(value) = storage(&[])? {
+			if !f(&[], &value) {
+				return Ok(false)
+			}
+		}
+	}
+
+	let mut key = start_at.map(|k| k.to_vec()).unwrap_or_default();
+	loop {
+		key = match next_key(&key)? {
+			Some(next) => next,
+			None => return Ok(true),
+		};
+		let value = storage(&key)?.ok_or_else(|| {
+			format!("Missing value of iterated key {}", HexDisplay::from(&key))
+		})?;
+		if !f(&key, &value) {
+			return Ok(false)
+		}
+	}
+}
+
 /// Check storage read proof, generated by `prove_read` call.
 pub fn read_proof_check<H, I>(
 	root: H::Out,
@@ -1286,6 +1422,60 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn prove_range_read_and_proof_check_works() {
+		let remote_backend = trie_backend::tests::test_trie();
+		let remote_root = remote_backend.storage_root(::std::iter::empty()).0;
+		let mut expected = Vec::new();
+		remote_backend.for_key_values_with_prefix(&[], |k, v| expected.push((k.to_vec(), v.to_vec())));
+
+		// read the top trie in chunks of at least 40 bytes
+		let mut entries = Vec::new();
+		let mut complete = false;
+		while !complete {
+			let start_at = entries.last().map(|(k, _): &(Vec<u8>, Vec<u8>)| k.clone());
+			let (proof, count) = prove_range_read_with_size(
+				trie_backend::tests::test_trie(),
+				None,
+				start_at.as_ref().map(|k| k.as_slice()),
+				40,
+			).unwrap();
+			let (chunk, chunk_complete) = read_range_proof_check::<BlakeTwo256>(
+				remote_root,
+				proof,
+				None,
+				start_at.as_ref().map(|k| k.as_slice()),
+			).unwrap();
+			assert!(chunk.len() >= count as usize);
+			entries.extend(chunk);
+			complete = chunk_complete;
+		}
+		assert_eq!(entries, expected);
+
+		// a proof covering nothing is invalid
+		let (proof, _) = prove_range_read_with_size(
+			trie_backend::tests::test_trie(),
+			None,
+			Some(b"key"),
+			1,
+		).unwrap();
+		assert!(read_range_proof_check::<BlakeTwo256>(remote_root, proof, None, Some(&[0xfe])).is_err());
+
+		// on child trie
+		let child_info = ChildInfo::new_default(b"sub1");
+		let (proof, count) = prove_range_read_with_size(
+			trie_backend::tests::test_trie(),
+			Some(&child_info),
+			None,
+			usize::max_value(),
+		).unwrap();
+		assert_eq!(count, 2);
+		assert_eq!(
+			read_range_proof_check::<BlakeTwo256>(remote_root, proof, Some(&child_info), None).unwrap(),
+			(vec![(b"value3".to_vec(), vec![142]), (b"value4".to_vec(), vec![124])], true),
+		);
+	}
+
 	#[test]
 	fn child_storage_uuid() {
 